@@ -17,6 +17,7 @@ use quote::{ToTokens, TokenStreamExt};
 use syn::spanned::Spanned;
 
 use crate::error::{Ctx, DeriveResult};
+use crate::recursion::Shape;
 use crate::use_tracking::UseTracker;
 use crate::util::self_ty;
 
@@ -47,6 +48,20 @@ const TOP_PARAM_NAME: &str = "_top";
 /// Changing the value of this constant constitutes a breaking change!
 const API_PARAM_NAME: &str = "params";
 
+/// The name of the variable holding the strategy for `Self` that is given
+/// to the closure of `.prop_recursive(..)` for a recursive enum.
+const RECURSIVE_INNER_NAME: &str = "_inner";
+
+/// The default depth of recursion for a recursive enum.
+const DEFAULT_RECURSION_DEPTH: u32 = 4;
+
+/// The default desired size of the values generated for a recursive enum.
+const DEFAULT_RECURSION_SIZE: u32 = 64;
+
+/// The default expected branch size for a recursive enum. This is also the
+/// largest number of elements in a `Vec` of `Self` that is generated.
+const DEFAULT_RECURSION_BRANCH: u32 = 8;
+
 //==============================================================================
 // AST Root
 //==============================================================================
@@ -204,6 +219,71 @@ pub fn pair_regex(ty: syn::Type, regex: syn::Expr) -> StratPair {
     (Strategy::Regex(ty.clone()), Ctor::Regex(ty, regex))
 }
 
+/// The type and constructor for `#[proptest(range = "..")]`.
+/// The elements of the range are constrained to `ty`, so that the literals
+/// in it take on the type of the field rather than defaulting to `i32`.
+pub fn pair_range(ty: syn::Type, range: syn::Expr) -> StratPair {
+    pair_existential(
+        ty.clone(),
+        parse_quote!({
+            fn range<T, R: ::std::ops::RangeBounds<T>>(range: R) -> R {
+                range
+            }
+            range::<#ty, _>(#range)
+        }),
+    )
+}
+
+/// The type and constructor for a field of a type which refers to `Self`,
+/// generated from the strategy for `Self` given to `.prop_recursive(..)`.
+/// The `shape` of the type determines how to get there from that strategy.
+pub fn pair_recursive_field(
+    ty: syn::Type,
+    shape: &Shape,
+    recursion: &Recursion,
+) -> StratPair {
+    fn strategy(shape: &Shape, branch: u32) -> syn::Expr {
+        match shape {
+            Shape::Itself => {
+                let inner = call_site_ident(RECURSIVE_INNER_NAME);
+                parse_quote!(::std::clone::Clone::clone(&#inner))
+            }
+            Shape::Pointer(new, elem) => {
+                let elem = strategy(elem, branch);
+                parse_quote!(_proptest::strategy::Strategy::prop_map(
+                    #elem, #new
+                ))
+            }
+            Shape::Vec(elem) => {
+                let elem = strategy(elem, branch);
+                parse_quote!(_proptest::collection::vec(
+                    #elem, 0..=(#branch as usize)
+                ))
+            }
+            Shape::Option(elem) => {
+                let elem = strategy(elem, branch);
+                parse_quote!(_proptest::option::of(#elem))
+            }
+        }
+    }
+
+    pair_existential(ty, strategy(shape, recursion.expected_branch_size))
+}
+
+/// The type and constructor for a recursive strategy for `Self`, where the
+/// `leaf` generates values without recursion, and where the `branch` is
+/// generated from the strategy for `Self` one level of recursion down.
+pub fn pair_recursive(
+    leaf: StratPair,
+    branch: StratPair,
+    recursion: Recursion,
+) -> StratPair {
+    (
+        Strategy::Existential(self_ty()),
+        Ctor::Recursive(Box::new(leaf.1), Box::new(branch.1), recursion),
+    )
+}
+
 /// Same as `pair_regex` for the `Self` type.
 pub fn pair_regex_self(regex: syn::Expr) -> StratPair {
     pair_regex(self_ty(), regex)
@@ -212,14 +292,20 @@ pub fn pair_regex_self(regex: syn::Expr) -> StratPair {
 /// The type and constructor for .prop_map:ing a set of strategies
 /// into the type we are implementing for. The closure for the
 /// `.prop_map(<closure>)` must also be given.
+///
+/// If the closure calls a constructor, which may reject the values, the
+/// mapping is a `.prop_filter_map(..)` instead, and the strategy is erased
+/// since the type of the closure can't be named.
 pub fn pair_map(
     (strats, ctors): (Vec<Strategy>, Vec<Ctor>),
     closure: MapClosure,
 ) -> StratPair {
-    (
-        Strategy::Map(strats.into()),
-        Ctor::Map(ctors.into(), closure),
-    )
+    let strat = if closure.is_fallible() {
+        Strategy::Existential(self_ty())
+    } else {
+        Strategy::Map(strats.into())
+    };
+    (strat, Ctor::Map(ctors.into(), closure))
 }
 
 /// The type and constructor for a union of strategies which produces a new
@@ -245,6 +331,36 @@ pub fn pair_filter(
     })
 }
 
+//==============================================================================
+// Recursion
+//==============================================================================
+
+/// The limits given to `.prop_recursive(..)` for a recursive enum.
+pub struct Recursion {
+    /// The maximum depth of recursion.
+    depth: u32,
+    /// The desired number of total elements in a generated value.
+    desired_size: u32,
+    /// The expected number of items referring to `Self` in a variant.
+    expected_branch_size: u32,
+}
+
+impl Recursion {
+    /// Construct the limits from those given, using defaults for the rest.
+    pub fn new(
+        depth: Option<u32>,
+        desired_size: Option<u32>,
+        expected_branch_size: Option<u32>,
+    ) -> Self {
+        Self {
+            depth: depth.unwrap_or(DEFAULT_RECURSION_DEPTH),
+            desired_size: desired_size.unwrap_or(DEFAULT_RECURSION_SIZE),
+            expected_branch_size: expected_branch_size
+                .unwrap_or(DEFAULT_RECURSION_BRANCH),
+        }
+    }
+}
+
 //==============================================================================
 // Parameters
 //==============================================================================
@@ -442,6 +558,10 @@ pub enum Ctor {
     Extract(Box<Ctor>, ToReg, FromReg),
     /// A filtered strategy with `.prop_filter`.
     Filter(Box<Ctor>, syn::Expr),
+    /// A recursive strategy with `.prop_recursive` from the strategy for the
+    /// leaves and the strategy for the branches, which may use the strategy
+    /// for `Self` one level of recursion down.
+    Recursive(Box<Ctor>, Box<Ctor>, Recursion),
 }
 
 /// Wraps the given strategy producing expression with a move into
@@ -517,6 +637,9 @@ impl ToTokens for Ctor {
                 )
             ),
             Map(ctors, closure) => map_ctor_to_tokens(tokens, &ctors, closure),
+            Recursive(leaf, branch, recursion) => {
+                recursive_ctor_to_tokens(tokens, leaf, branch, recursion)
+            }
             #[cfg(not(feature = "boxed_union"))]
             Union(ctors) => union_ctor_to_tokens(tokens, ctors),
             #[cfg(feature = "boxed_union")]
@@ -565,10 +688,53 @@ fn map_ctor_to_tokens(
 ) {
     let ctors = NestedTuple(ctors);
 
+    if let MapBody::Constructor(constructor) = &closure.0 {
+        quote_append!(tokens,
+            _proptest::strategy::Strategy::boxed(
+                _proptest::strategy::Strategy::prop_filter_map(
+                    #ctors,
+                    stringify!(#constructor),
+                    #closure
+                )
+            )
+        );
+    } else {
+        quote_append!(tokens,
+            _proptest::strategy::Strategy::prop_map(
+                #ctors,
+                #closure
+            )
+        );
+    }
+}
+
+/// Tokenizes a recursive strategy. The strategy is erased since the type of
+/// the closure given to `.prop_recursive(..)` can't be named.
+///
+/// Note that the closure moves the parameters of the enum, if any, into it
+/// so that the strategies for the branches may use them by reference.
+fn recursive_ctor_to_tokens(
+    tokens: &mut TokenStream,
+    leaf: &Ctor,
+    branch: &Ctor,
+    recursion: &Recursion,
+) {
+    let Recursion {
+        depth,
+        desired_size,
+        expected_branch_size,
+    } = recursion;
+    let inner = call_site_ident(RECURSIVE_INNER_NAME);
+
     quote_append!(tokens,
-        _proptest::strategy::Strategy::prop_map(
-            #ctors,
-            #closure
+        _proptest::strategy::Strategy::boxed(
+            _proptest::strategy::Strategy::prop_recursive(
+                #leaf,
+                #depth,
+                #desired_size,
+                #expected_branch_size,
+                move |#inner: _proptest::strategy::BoxedStrategy<Self>| #branch
+            )
         )
     );
 }
@@ -789,12 +955,39 @@ fn param<'a>(fv: usize) -> FreshVar<'a> {
 
 /// Constructs a `MapClosure` for the given `path` and a list of fields.
 pub fn map_closure(path: syn::Path, fs: &[syn::Field]) -> MapClosure {
-    MapClosure(path, fs.to_owned())
+    MapClosure(MapBody::Literal(path), fs.to_owned())
+}
+
+/// Constructs a `MapClosure` which passes a list of fields, in order,
+/// to the given `constructor`.
+pub fn construct_closure(
+    constructor: syn::Expr,
+    fs: &[syn::Field],
+) -> MapClosure {
+    MapClosure(MapBody::Constructor(constructor), fs.to_owned())
 }
 
 /// A `MapClosure` models the closure part inside a `.prop_map(..)` call.
 #[derive(Debug)]
-pub struct MapClosure(syn::Path, Vec<syn::Field>);
+pub struct MapClosure(MapBody, Vec<syn::Field>);
+
+/// Models how the fields are turned into `Self` in a `MapClosure`.
+#[derive(Debug)]
+enum MapBody {
+    /// A struct literal with the given path, as in `Foo { bar: tmp_0 }`.
+    Literal(syn::Path),
+    /// A call to a smart constructor, as in `Foo::new(tmp_0)`, which may
+    /// reject the fields by returning `None` or `Err(..)`.
+    Constructor(syn::Expr),
+}
+
+impl MapClosure {
+    /// Returns true iff the closure may reject the fields, in which case it
+    /// produces an `Option<Self>` for `.prop_filter_map(..)`.
+    fn is_fallible(&self) -> bool {
+        matches!(self.0, MapBody::Constructor(_))
+    }
+}
 
 impl ToTokens for MapClosure {
     fn to_tokens(&self, tokens: &mut TokenStream) {
@@ -802,9 +995,40 @@ impl ToTokens for MapClosure {
             fresh_var("tmp", idx)
         }
 
-        let MapClosure(path, fields) = self;
+        let MapClosure(body, fields) = self;
         let count = fields.len();
         let tmps: Vec<_> = (0..count).map(tmp_var).collect();
+        let path = match body {
+            MapBody::Literal(path) => path,
+            MapBody::Constructor(constructor) => {
+                let pat = NestedTuple(&tmps);
+                quote_append!(tokens, | #pat | {
+                    // Accepts constructors returning `Self`, `Option<Self>`,
+                    // and `Result<Self, E>`. These impls don't overlap since
+                    // e.g. `T = Option<T>` has no solution.
+                    trait Construct<T> {
+                        fn construct(self) -> ::std::option::Option<T>;
+                    }
+                    impl<T> Construct<T> for T {
+                        fn construct(self) -> ::std::option::Option<T> {
+                            ::std::option::Option::Some(self)
+                        }
+                    }
+                    impl<T> Construct<T> for ::std::option::Option<T> {
+                        fn construct(self) -> ::std::option::Option<T> {
+                            self
+                        }
+                    }
+                    impl<T, E> Construct<T> for ::std::result::Result<T, E> {
+                        fn construct(self) -> ::std::option::Option<T> {
+                            ::std::result::Result::ok(self)
+                        }
+                    }
+                    Construct::<Self>::construct((#constructor)(#(#tmps),*))
+                });
+                return;
+            }
+        };
         let inits = fields.iter().enumerate().map(|(idx, field)| {
             let tv = tmp_var(idx);
             if let Some(name) = &field.ident {
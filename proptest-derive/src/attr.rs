@@ -30,12 +30,26 @@ pub struct ParsedAttributes {
     /// The potential weight assigned to an enum variant.
     /// This must be `None` for things that are not enum variants.
     pub weight: Option<u32>,
+    /// The potential depth of recursion for a recursive enum.
+    /// This and the other recursion limits must be `None` for things
+    /// that are not enums.
+    pub recursion_depth: Option<u32>,
+    /// The potential desired size of values for a recursive enum.
+    pub recursion_size: Option<u32>,
+    /// The potential expected branch size for a recursive enum.
+    pub recursion_branch: Option<u32>,
     /// The mode for `Parameters` to use. See that type for more.
     pub params: ParamsMode,
     /// The mode for `Strategy` to use. See that type for more.
     pub strategy: StratMode,
     /// Filter expressions if any.
     pub filter: Vec<syn::Expr>,
+    /// The potential smart constructor that the fields of a struct are
+    /// passed to. This must be `None` for things that are not structs.
+    pub constructor: Option<syn::Expr>,
+    /// The potential predicate checking the invariants of the type.
+    /// This must be `None` for things that are not structs or enums.
+    pub validate: Option<syn::Expr>,
     /// True if no_bound was specified.
     pub no_bound: bool,
 }
@@ -59,6 +73,10 @@ pub enum StratMode {
     /// We don't reuse `Strategy(..)` so that we can produce better and
     /// more tailored error messages.
     Regex(Expr),
+    /// This means that an explicit *range* strategy has been provided.
+    /// This is distinct from `Strategy(..)` since the type of the range
+    /// must be tied to the type of the field it is set on.
+    Range(Expr),
 }
 
 /// The mode for the associated item `Parameters` to use.
@@ -159,11 +177,22 @@ fn parse_attributes_base(
     Ok(ParsedAttributes {
         skip: acc.skip.is_some(),
         weight: acc.weight,
+        recursion_depth: acc.recursion_depth,
+        recursion_size: acc.recursion_size,
+        recursion_branch: acc.recursion_branch,
         filter: acc.filter,
+        constructor: acc.constructor,
+        validate: acc.validate,
         // Process params and no_params together to see which one to use.
         params: parse_params_mode(ctx, acc.no_params, acc.params)?,
         // Process strategy and value together to see which one to use.
-        strategy: parse_strat_mode(ctx, acc.strategy, acc.value, acc.regex)?,
+        strategy: parse_strat_mode(
+            ctx,
+            acc.strategy,
+            acc.value,
+            acc.regex,
+            acc.range,
+        )?,
         no_bound: acc.no_bound.is_some(),
     })
 }
@@ -177,12 +206,18 @@ fn parse_attributes_base(
 struct ParseAcc {
     skip: Option<()>,
     weight: Option<u32>,
+    recursion_depth: Option<u32>,
+    recursion_size: Option<u32>,
+    recursion_branch: Option<u32>,
     no_params: Option<()>,
     params: Option<Type>,
     strategy: Option<Expr>,
     value: Option<Expr>,
     regex: Option<Expr>,
+    range: Option<Expr>,
     filter: Vec<Expr>,
+    constructor: Option<Expr>,
+    validate: Option<Expr>,
     no_bound: Option<()>,
 }
 
@@ -262,12 +297,24 @@ fn dispatch_attribute(ctx: Ctx, mut acc: ParseAcc, meta: Meta) -> ParseAcc {
             // Valid modifiers:
             "skip" => parse_skip(ctx, &mut acc, meta),
             "w" | "weight" => parse_weight(ctx, &mut acc, &meta),
+            "recursion_depth" => {
+                parse_recursion(ctx, &mut acc.recursion_depth, &meta)
+            }
+            "recursion_size" => {
+                parse_recursion(ctx, &mut acc.recursion_size, &meta)
+            }
+            "recursion_branch" => {
+                parse_recursion(ctx, &mut acc.recursion_branch, &meta)
+            }
             "no_params" => parse_no_params(ctx, &mut acc, meta),
             "params" => parse_params(ctx, &mut acc, meta),
             "strategy" => parse_strategy(ctx, &mut acc, &meta),
             "value" => parse_value(ctx, &mut acc, &meta),
             "regex" => parse_regex(ctx, &mut acc, &meta),
+            "range" => parse_range(ctx, &mut acc, &meta),
             "filter" => parse_filter(ctx, &mut acc, &meta),
+            "constructor" => parse_constructor(ctx, &mut acc, &meta),
+            "validate" => parse_validate(ctx, &mut acc, &meta),
             "no_bound" => parse_no_bound(ctx, &mut acc, meta),
             // Invalid modifiers:
             name => dispatch_unknown_mod(ctx, name),
@@ -288,6 +335,18 @@ fn dispatch_unknown_mod(ctx: Ctx, name: &str) {
             error::did_you_mean(ctx, name, "value")
         }
         "regexes" | "regexp" | "re" => error::did_you_mean(ctx, name, "regex"),
+        "depth" | "max_depth" | "recursion" => {
+            error::did_you_mean(ctx, name, "recursion_depth")
+        }
+        "ranges" | "bounds" | "between" => {
+            error::did_you_mean(ctx, name, "range")
+        }
+        "new" | "ctor" | "construct" | "constructed" => {
+            error::did_you_mean(ctx, name, "constructor")
+        }
+        "valid" | "validator" | "validated" | "invariant" => {
+            error::did_you_mean(ctx, name, "validate")
+        }
         "param" | "parameters" => error::did_you_mean(ctx, name, "params"),
         "no_param" | "no_parameters" => {
             error::did_you_mean(ctx, name, "no_params")
@@ -332,24 +391,46 @@ fn parse_skip(ctx: Ctx, acc: &mut ParseAcc, meta: Meta) {
 ///
 /// The `<integer>` must also fit within an `u32` and be unsigned.
 fn parse_weight(ctx: Ctx, acc: &mut ParseAcc, meta: &Meta) {
-    use std::u32;
     error_if_set(ctx, &acc.weight, &meta);
 
+    if let v @ Some(_) = parse_u32(meta) {
+        acc.weight = v;
+    } else {
+        error::weight_malformed(ctx, meta)
+    }
+}
+
+/// Parses an integer or an expression evaluating to one which fits within
+/// an `u32` as proptest requires that for weights and recursion limits.
+fn parse_u32(meta: &Meta) -> Option<u32> {
+    use std::u32;
+
     // Convert to value if possible:
-    let value = normalize_meta(meta.clone())
+    normalize_meta(meta.clone())
         .and_then(extract_lit)
         .and_then(extract_expr)
         // Evaluate the expression into a value:
         .as_ref()
         .and_then(interp::eval_expr)
-        // Ensure that `val` fits within an `u32` as proptest requires that:
+        // Ensure that `val` fits within an `u32`:
         .filter(|&value| value <= u128::from(u32::MAX))
-        .map(|value| value as u32);
+        .map(|value| value as u32)
+}
 
-    if let v @ Some(_) = value {
-        acc.weight = v;
+//==============================================================================
+// Internals: Recursion
+//==============================================================================
+
+/// Parses a limit on the recursion of a recursive enum.
+/// Valid forms are the same as for `weight`, with any of the names
+/// `recursion_depth`, `recursion_size`, or `recursion_branch`.
+fn parse_recursion(ctx: Ctx, loc: &mut Option<u32>, meta: &Meta) {
+    error_if_set(ctx, loc, meta);
+
+    if let v @ Some(_) = parse_u32(meta) {
+        *loc = v;
     } else {
-        error::weight_malformed(ctx, meta)
+        error::recursion_malformed(ctx, meta)
     }
 }
 
@@ -363,14 +444,57 @@ fn parse_weight(ctx: Ctx, acc: &mut ParseAcc, meta: &Meta) {
 /// + `#[proptest(filter = "<expr>")]`
 /// + `#[proptest(filter("<expr>")]`
 fn parse_filter(ctx: Ctx, acc: &mut ParseAcc, meta: &Meta) {
-    if let Some(filter) = match normalize_meta(meta.clone()) {
+    if let Some(filter) = parse_function(meta) {
+        acc.filter.push(filter);
+    } else {
+        error::filter_malformed(ctx, meta)
+    }
+}
+
+/// Parses a function given by name or as an expression. This is a helper.
+/// Valid forms are:
+/// + `#[proptest(<meta.name()>(<ident>))]`
+/// + `#[proptest(<meta.name()> = "<expr>")]`
+/// + `#[proptest(<meta.name()>("<expr>")]`
+fn parse_function(meta: &Meta) -> Option<Expr> {
+    match normalize_meta(meta.clone()) {
         Some(NormMeta::Lit(Lit::Str(lit))) => lit.parse().ok(),
         Some(NormMeta::Word(ident)) => Some(parse_quote!( #ident )),
         _ => None,
-    } {
-        acc.filter.push(filter);
+    }
+}
+
+//==============================================================================
+// Internals: Construction
+//==============================================================================
+
+/// Parses a smart constructor that the fields are passed to.
+/// Valid forms are:
+/// + `#[proptest(constructor(<ident>))]`
+/// + `#[proptest(constructor = "<expr>")]`
+/// + `#[proptest(constructor("<expr>")]`
+fn parse_constructor(ctx: Ctx, acc: &mut ParseAcc, meta: &Meta) {
+    error_if_set(ctx, &acc.constructor, meta);
+
+    if let ctor @ Some(_) = parse_function(meta) {
+        acc.constructor = ctor;
     } else {
-        error::filter_malformed(ctx, meta)
+        error::constructor_malformed(ctx)
+    }
+}
+
+/// Parses a predicate checking the invariants of the type.
+/// Valid forms are:
+/// + `#[proptest(validate(<ident>))]`
+/// + `#[proptest(validate = "<expr>")]`
+/// + `#[proptest(validate("<expr>")]`
+fn parse_validate(ctx: Ctx, acc: &mut ParseAcc, meta: &Meta) {
+    error_if_set(ctx, &acc.validate, meta);
+
+    if let pred @ Some(_) = parse_function(meta) {
+        acc.validate = pred;
+    } else {
+        error::validate_malformed(ctx)
     }
 }
 
@@ -397,6 +521,23 @@ fn parse_regex(ctx: Ctx, acc: &mut ParseAcc, meta: &Meta) {
     }
 }
 
+/// Parses an explicit range as a strategy.
+/// Valid forms are:
+/// + `#[proptest(range = "<expr>")]`
+/// + `#[proptest(range("<expr>"))]`
+fn parse_range(ctx: Ctx, acc: &mut ParseAcc, meta: &Meta) {
+    error_if_set(ctx, &acc.range, meta);
+
+    if let expr @ Some(_) = match normalize_meta(meta.clone()) {
+        Some(NormMeta::Lit(lit @ Lit::Str(_))) => extract_expr(lit),
+        _ => None,
+    } {
+        acc.range = expr;
+    } else {
+        error::range_malformed(ctx)
+    }
+}
+
 /// Parses an explicit value as a strategy.
 /// Valid forms are:
 /// + `#[proptest(value = <literal>)]`
@@ -440,20 +581,22 @@ fn parse_strategy_base(ctx: Ctx, loc: &mut Option<Expr>, meta: &Meta) {
     }
 }
 
-/// Combines any parsed explicit strategy, value, and regex into a single
-/// value and fails if both an explicit strategy / value / regex was set.
-/// Only one of them can be set, or none.
+/// Combines any parsed explicit strategy, value, regex, and range into a
+/// single value and fails if more than one of an explicit strategy / value /
+/// regex / range was set. Only one of them can be set, or none.
 fn parse_strat_mode(
     ctx: Ctx,
     strat: Option<Expr>,
     value: Option<Expr>,
     regex: Option<Expr>,
+    range: Option<Expr>,
 ) -> DeriveResult<StratMode> {
-    Ok(match (strat, value, regex) {
-        (None, None, None) => StratMode::Arbitrary,
-        (None, None, Some(re)) => StratMode::Regex(re),
-        (None, Some(vl), None) => StratMode::Value(vl),
-        (Some(st), None, None) => StratMode::Strategy(st),
+    Ok(match (strat, value, regex, range) {
+        (None, None, None, None) => StratMode::Arbitrary,
+        (None, None, None, Some(rg)) => StratMode::Range(rg),
+        (None, None, Some(re), None) => StratMode::Regex(re),
+        (None, Some(vl), None, None) => StratMode::Value(vl),
+        (Some(st), None, None, None) => StratMode::Strategy(st),
         _ => error::overspecified_strat(ctx)?,
     })
 }
@@ -15,6 +15,7 @@ use syn::{DeriveInput, Expr, Field, Ident, Path, Type, Variant};
 use crate::ast::*;
 use crate::attr::{self, ParamsMode, ParsedAttributes, StratMode};
 use crate::error::{self, Context, Ctx, DeriveResult};
+use crate::recursion::{is_recursive, shape_of};
 use crate::use_tracking::{UseMarkable, UseTracker};
 use crate::util::{fields_to_vec, is_unit_type, self_ty};
 use crate::void::IsUninhabited;
@@ -131,8 +132,15 @@ fn derive_struct(
             error::uninhabited_struct(ctx);
         }
 
-        // Construct the closure for `.prop_map`:
-        let closure = map_closure(v_path, &ast.body);
+        // Construct the closure for `.prop_map`, which may pass the fields
+        // to a constructor. The strategy is then erased, which requires
+        // `Self: 'static`.
+        let closure = if let Some(ctor) = ast.attrs.constructor {
+            ast.tracker.bound_static();
+            construct_closure(ctor, &ast.body)
+        } else {
+            map_closure(v_path, &ast.body)
+        };
 
         // The complexity of the logic depends mostly now on whether
         // parameters were set directly on the type or not.
@@ -159,14 +167,24 @@ fn derive_struct(
             .finish(closure)
         };
 
-        // Possibly apply filter:
-        add_top_filter(ast.attrs.filter, parts)
+        // Possibly apply validator and filter:
+        let filter = validate_and_filter(ast.attrs.validate, ast.attrs.filter);
+        add_top_filter(filter, parts)
     };
 
     // We're done!
     Ok(Impl::new(ast.ident, ast.tracker, parts))
 }
 
+/// Prepends the validator, if any, to the filters of a type, so that the
+/// invariants of the type are checked before anything else.
+fn validate_and_filter(
+    validate: Option<Expr>,
+    filter: Vec<Expr>,
+) -> Vec<Expr> {
+    validate.into_iter().chain(filter).collect()
+}
+
 /// Apply the filter at the top level if provided.
 fn add_top_filter(filter: Vec<Expr>, parts: ImplParts) -> ImplParts {
     let (params, strat, ctor) = parts;
@@ -213,8 +231,9 @@ fn derive_product_has_params(
         .try_fold(StratAcc::new(len), |acc, field| {
             let attrs = attr::parse_attributes(ctx, &field.attrs)?;
 
-            // Deny attributes that are only for enum variants:
+            // Deny attributes that are only for enum variants or the type:
             error::if_enum_attrs_present(ctx, &attrs, item);
+            error::if_type_attrs_present(ctx, &attrs, item);
 
             // Deny setting parameters on the field since it has been set on parent:
             error::if_specified_params(ctx, &attrs, item);
@@ -245,6 +264,8 @@ fn product_handle_default_params(
         StratMode::Value(value) => pair_value(ty, value),
         // Specific regex - dispatch to `_regex` function based on `ty`:
         StratMode::Regex(regex) => pair_regex(ty, regex),
+        // Specific range - use the range with its elements of type `ty`:
+        StratMode::Range(range) => pair_range(ty, range),
         // Use Arbitrary for the given type and mark the type as used:
         StratMode::Arbitrary => {
             ty.mark_uses(ut);
@@ -268,8 +289,9 @@ fn derive_product_no_params(
     fields.into_iter().try_fold(acc, |mut acc, field| {
         let attrs = attr::parse_attributes(ctx, &field.attrs)?;
 
-        // Deny attributes that are only for enum variants:
+        // Deny attributes that are only for enum variants or the type:
         error::if_enum_attrs_present(ctx, &attrs, item);
+        error::if_type_attrs_present(ctx, &attrs, item);
 
         let span = field.span();
         let ty = field.ty;
@@ -286,6 +308,8 @@ fn derive_product_no_params(
                     StratMode::Value(value) => pair_value(ty, value),
                     // Specific regex - dispatch to `_regex` function:
                     StratMode::Regex(regex) => pair_regex(ty, regex),
+                    // Specific range - use the range with elements of `ty`:
+                    StratMode::Range(range) => pair_range(ty, range),
                     // Use Arbitrary for the given type and mark the type as used:
                     StratMode::Arbitrary => {
                         ty.mark_uses(ut);
@@ -321,6 +345,11 @@ fn derive_product_no_params(
                                 error::cant_set_param_and_regex(ctx, item);
                                 pair_regex(ty, regex)
                             }
+                            // Likewise for params and a range.
+                            StratMode::Range(range) => {
+                                error::cant_set_param_and_range(ctx, item);
+                                pair_range(ty, range)
+                            }
                             // Logic error by user.
                             // Pointless to specify params and not the strategy. Bail!
                             StratMode::Arbitrary => {
@@ -368,6 +397,9 @@ fn derive_enum(
     // We don't allow weight on enums directly:
     error::if_weight_present(ctx, &ast.attrs, error::ENUM);
 
+    // Variants can't be passed to a single constructor:
+    error::if_constructor_present(ctx, &ast.attrs, error::ENUM);
+
     // Bail if there are no variants:
     if ast.body.is_empty() {
         error::uninhabited_enum_with_no_variants(ctx)?;
@@ -378,9 +410,32 @@ fn derive_enum(
         error::uninhabited_enum_variants_uninhabited(ctx)?;
     }
 
+    // An enum referring to itself must be generated recursively:
+    let recursive = is_recursive_enum(&ast.ident, &ast.body);
+    if !recursive && error::has_recursion(&ast.attrs) {
+        error::recursion_on_non_recursive_enum(ctx);
+    }
+
     // The complexity of the logic depends mostly now on whether
-    // parameters were set directly on the type or not.
-    let parts = if let Some(sty) = ast.attrs.params.into_option() {
+    // the enum is recursive and whether parameters were set directly
+    // on the type or not.
+    let parts = if recursive {
+        // A recursive enum can only have parameters set on the type itself.
+        let sty = ast.attrs.params.into_option().unwrap_or(None);
+        let recursion = Recursion::new(
+            ast.attrs.recursion_depth,
+            ast.attrs.recursion_size,
+            ast.attrs.recursion_branch,
+        );
+        derive_enum_recursive(
+            ctx,
+            &mut ast.tracker,
+            &ast.ident,
+            ast.body,
+            sty,
+            recursion,
+        )
+    } else if let Some(sty) = ast.attrs.params.into_option() {
         // The logic is much simpler in this branch.
         derive_enum_has_params(ctx, &mut ast.tracker, &ast.ident, ast.body, sty)
     } else {
@@ -388,7 +443,8 @@ fn derive_enum(
         derive_enum_no_params(ctx, &mut ast.tracker, &ast.ident, ast.body)
     }?;
 
-    let parts = add_top_filter(ast.attrs.filter, parts);
+    let filter = validate_and_filter(ast.attrs.validate, ast.attrs.filter);
+    let parts = add_top_filter(filter, parts);
 
     // We're done!
     Ok(Impl::new(ast.ident, ast.tracker, parts))
@@ -467,8 +523,9 @@ fn derive_variant_with_fields<C>(
                 deny_all_attrs_on_fields(ctx, fields)?;
                 pair_regex_self(regex)
             }
-            // No explicit strategy, use strategies for variant fields instead:
-            StratMode::Arbitrary => {
+            // No explicit strategy, use strategies for variant fields instead.
+            // A range has already been reported by `keep_inhabited_variant`.
+            StratMode::Arbitrary | StratMode::Range(_) => {
                 variant_no_explicit_strategy(ctx, ut, v_path, fields, acc)?
             }
         },
@@ -500,7 +557,7 @@ fn derive_variant_with_fields<C>(
                 }
                 // Logic error by user. Pointless to specify params and not
                 // the strategy. Bail!
-                StratMode::Arbitrary => {
+                StratMode::Arbitrary | StratMode::Range(_) => {
                     let ty = self_ty();
                     error::cant_set_param_but_not_strat(
                         ctx,
@@ -569,7 +626,7 @@ fn variant_handle_default_params(
             pair_regex_self(regex)
         }
         // Use Arbitrary for the factors (fields) of variant:
-        StratMode::Arbitrary =>
+        StratMode::Arbitrary | StratMode::Range(_) =>
         // Fields are not allowed to specify params.
         {
             derive_product_has_params(
@@ -633,6 +690,167 @@ fn derive_enum_has_params(
     Ok(add_top_params(sty, acc.finish(ctx)))
 }
 
+/// Deriving for an enum which refers to itself in the fields of some of its
+/// variants. Those variants are the branches of a strategy made with
+/// `.prop_recursive(..)`, where the fields referring to the enum are generated
+/// from the strategy for the enum one level of recursion down. The other
+/// variants are the leaves of that strategy.
+///
+/// The strategy for the branches is constructed once per level of recursion,
+/// so parameters can't be moved into the strategies for the fields. Therefore
+/// parameters may only be set on the enum itself, in which case they can be
+/// used by reference in the strategies of the variants and their fields.
+fn derive_enum_recursive(
+    ctx: Ctx,
+    ut: &mut UseTracker,
+    _self: &Ident,
+    variants: Vec<Variant>,
+    sty: Option<Type>,
+    recursion: Recursion,
+) -> DeriveResult<ImplParts> {
+    // Initialize the accumulators:
+    let mut leaves = StratAcc::new(variants.len());
+    let mut branches = StratAcc::new(variants.len());
+
+    // Fold into the accumulators the strategies for each variant:
+    for variant in variants {
+        let parts = keep_inhabited_variant(ctx, _self, variant)?;
+        if let Some((weight, ident, fields, attrs)) = parts {
+            // Deny setting parameters on the variant, see above:
+            error::if_specified_params(ctx, &attrs, error::ENUM_VARIANT);
+
+            let path = parse_quote!( #_self::#ident );
+            if is_recursive_variant(_self, &attrs, &fields) {
+                // A branch:
+                let closure = map_closure(path, &fields);
+                let (strat, ctor) = add_filter_self(
+                    attrs.filter,
+                    derive_product_recursive(
+                        ctx, ut, _self, &recursion, closure, fields,
+                    )?,
+                );
+                branches = branches.add((strat, (weight, ctor)));
+            } else {
+                // A leaf:
+                let (strat, ctor) = if fields.is_empty() {
+                    // Unit variant:
+                    pair_unit_variant(ctx, &attrs, path)
+                } else {
+                    // Not a unit variant:
+                    let filter = attrs.filter.clone();
+                    add_filter_self(
+                        filter,
+                        variant_handle_default_params(
+                            ctx, ut, path, attrs, fields,
+                        )?,
+                    )
+                };
+                leaves = leaves.add((strat, (weight, ctor)));
+            }
+        }
+    }
+
+    // If all branches were uninhabited there is nothing to recurse with.
+    if branches.is_empty() {
+        ensure_union_has_strategies(ctx, &leaves);
+        return Ok(add_top_params(sty, leaves.finish(ctx)));
+    }
+
+    if leaves.is_empty() {
+        error::recursive_enum_without_leaves(ctx)?;
+    }
+
+    // The strategy for `Self` is erased, which requires `Self: 'static`.
+    ut.bound_static();
+
+    let pair =
+        pair_recursive(leaves.finish(ctx), branches.finish(ctx), recursion);
+    Ok(add_top_params(sty, pair))
+}
+
+/// Deriving for the fields of a branch of a recursive enum. This is the same
+/// as `derive_product_has_params` except that the fields referring to the
+/// enum are generated from the strategy for the enum given to the closure
+/// of `.prop_recursive(..)`.
+fn derive_product_recursive(
+    ctx: Ctx,
+    ut: &mut UseTracker,
+    _self: &Ident,
+    recursion: &Recursion,
+    closure: MapClosure,
+    fields: Vec<Field>,
+) -> DeriveResult<StratPair> {
+    let item = error::ENUM_VARIANT_FIELD;
+    let len = fields.len();
+    fields
+        .into_iter()
+        .try_fold(StratAcc::new(len), |acc, field| {
+            let attrs = attr::parse_attributes(ctx, &field.attrs)?;
+
+            // Deny attributes that are only for enum variants or the type:
+            error::if_enum_attrs_present(ctx, &attrs, item);
+            error::if_type_attrs_present(ctx, &attrs, item);
+
+            // Deny setting parameters on the field, see `derive_enum_recursive`:
+            error::if_specified_params(ctx, &attrs, item);
+
+            // Determine the strategy for this field and add it to acc.
+            let span = field.span();
+            let ty = field.ty.clone();
+            let pair = match attrs.strategy {
+                // Use the strategy for the enum, according to the shape of ty:
+                StratMode::Arbitrary if is_recursive(&ty, _self) => {
+                    match shape_of(&ty, _self) {
+                        Some(shape) => {
+                            pair_recursive_field(ty, &shape, recursion)
+                        }
+                        None => error::unsupported_recursive_field(ctx, &ty)?,
+                    }
+                }
+                strategy => {
+                    product_handle_default_params(ut, ty, span, strategy)
+                }
+            };
+            let pair = pair_filter(attrs.filter, field.ty, pair);
+            Ok(acc.add(pair))
+        })
+        .map(|acc| acc.finish(closure))
+}
+
+/// Returns true iff the enum has a variant, which we have not been ordered to
+/// skip, that refers to the enum as determined by `is_recursive_variant`.
+fn is_recursive_enum(_self: &Ident, variants: &[Variant]) -> bool {
+    // Any errors in the attributes are reported once they are parsed
+    // for the actual deriving, so we discard them here.
+    let ctx = &mut Context::default();
+    variants.iter().any(|variant| {
+        attr::parse_attributes(ctx, &variant.attrs).is_ok_and(|attrs| {
+            let fields = fields_to_vec(variant.fields.clone());
+            !attrs.skip && is_recursive_variant(_self, &attrs, &fields)
+        })
+    })
+}
+
+/// Returns true iff the variant has no explicit strategy and a field that
+/// refers to the enum which also has no explicit strategy. Such a field
+/// would otherwise be generated via `Arbitrary` for the enum, recursing
+/// without bound.
+fn is_recursive_variant(
+    _self: &Ident,
+    attrs: &ParsedAttributes,
+    fields: &[Field],
+) -> bool {
+    // Any errors in the attributes are reported once they are parsed
+    // for the actual deriving, so we discard them here.
+    let ctx = &mut Context::default();
+    matches!(attrs.strategy, StratMode::Arbitrary | StratMode::Range(_))
+        && fields.iter().any(|field| {
+            is_recursive(&field.ty, _self)
+                && attr::parse_attributes(ctx, &field.attrs)
+                    .is_ok_and(|attrs| !attrs.strategy.is_set())
+        })
+}
+
 /// Filters out uninhabited and variants that we've been ordered to skip.
 fn keep_inhabited_variant(
     ctx: Ctx,
@@ -642,6 +860,12 @@ fn keep_inhabited_variant(
     let attrs = attr::parse_attributes(ctx, &variant.attrs)?;
     let fields = fields_to_vec(variant.fields);
 
+    // Deny limits on recursion, which are only for the enum itself:
+    error::if_recursion_present(ctx, &attrs, error::ENUM_VARIANT);
+
+    // Deny a constructor or validator, which are only for the type itself:
+    error::if_type_attrs_present(ctx, &attrs, error::ENUM_VARIANT);
+
     if attrs.skip {
         // We've been ordered to skip this variant!
         // Check that all other attributes are not set.
@@ -649,6 +873,11 @@ fn keep_inhabited_variant(
         fields.into_iter().try_for_each(|field| {
             let f_attrs = attr::parse_attributes(ctx, &field.attrs)?;
             error::if_skip_present(ctx, &f_attrs, error::ENUM_VARIANT_FIELD);
+            error::if_type_attrs_present(
+                ctx,
+                &f_attrs,
+                error::ENUM_VARIANT_FIELD,
+            );
             ensure_has_only_skip_attr(ctx, &f_attrs, error::ENUM_VARIANT_FIELD);
             Ok(())
         })?;
@@ -661,6 +890,12 @@ fn keep_inhabited_variant(
         return Ok(None);
     }
 
+    // A range only makes sense on the fields of a variant; unit variants
+    // report it along with their other attributes.
+    if let (StratMode::Range(_), false) = (&attrs.strategy, fields.is_empty()) {
+        error::illegal_range(ctx, error::ENUM_VARIANT);
+    }
+
     // Compute the weight:
     let weight = attrs.weight.unwrap_or(1);
 
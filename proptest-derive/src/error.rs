@@ -55,13 +55,37 @@ pub fn if_anything_specified(ctx: Ctx, attrs: &ParsedAttributes, item: &str) {
     if_strategy_present(ctx, attrs, item);
     if_specified_params(ctx, attrs, item);
     if_specified_filter(ctx, attrs, item);
+    if_type_attrs_present(ctx, attrs, item);
 }
 
 /// Ensures that things only allowed on an enum variant is not present on
-/// `item` which is not an enum variant.
+/// `item` which is not an enum variant. Since things only allowed on an enum
+/// are not allowed on an enum variant either, they are denied as well.
 pub fn if_enum_attrs_present(ctx: Ctx, attrs: &ParsedAttributes, item: &str) {
     if_skip_present(ctx, attrs, item);
     if_weight_present(ctx, attrs, item);
+    if_recursion_present(ctx, attrs, item);
+}
+
+/// Ensures that things only allowed on the type itself, i.e. a constructor
+/// and a validator, are not present on `item`.
+pub fn if_type_attrs_present(ctx: Ctx, attrs: &ParsedAttributes, item: &str) {
+    if_constructor_present(ctx, attrs, item);
+    if_validate_present(ctx, attrs, item);
+}
+
+/// Ensures that a constructor is not present on `item`.
+pub fn if_constructor_present(ctx: Ctx, attrs: &ParsedAttributes, item: &str) {
+    if attrs.constructor.is_some() {
+        illegal_constructor(ctx, item)
+    }
+}
+
+/// Ensures that a validator is not present on `item`.
+pub fn if_validate_present(ctx: Ctx, attrs: &ParsedAttributes, item: &str) {
+    if attrs.validate.is_some() {
+        illegal_validate(ctx, item)
+    }
 }
 
 /// Ensures that parameters is not present on `item`.
@@ -86,6 +110,7 @@ pub fn if_strategy_present(ctx: Ctx, attrs: &ParsedAttributes, item: &str) {
         Strategy(_) => illegal_strategy(ctx, "strategy", item),
         Value(_) => illegal_strategy(ctx, "value", item),
         Regex(_) => illegal_regex(ctx, item),
+        Range(_) => illegal_range(ctx, item),
     }
 }
 
@@ -98,6 +123,7 @@ pub fn if_present_on_unit_variant(ctx: Ctx, attrs: &ParsedAttributes) {
         Strategy(_) => strategy_on_unit_variant(ctx, "strategy"),
         Value(_) => strategy_on_unit_variant(ctx, "value"),
         Regex(_) => regex_on_unit_variant(ctx),
+        Range(_) => strategy_on_unit_variant(ctx, "range"),
     }
 
     if attrs.params.is_set() {
@@ -109,7 +135,8 @@ pub fn if_present_on_unit_variant(ctx: Ctx, attrs: &ParsedAttributes) {
     }
 }
 
-/// Ensures that parameters or filter is not present on a unit struct.
+/// Ensures that parameters, filter, constructor, or validator is not present
+/// on a unit struct.
 pub fn if_present_on_unit_struct(ctx: Ctx, attrs: &ParsedAttributes) {
     if attrs.params.is_set() {
         params_on_unit_struct(ctx)
@@ -118,6 +145,14 @@ pub fn if_present_on_unit_struct(ctx: Ctx, attrs: &ParsedAttributes) {
     if !attrs.filter.is_empty() {
         filter_on_unit_struct(ctx)
     }
+
+    if attrs.constructor.is_some() {
+        constructor_on_unit_struct(ctx)
+    }
+
+    if attrs.validate.is_some() {
+        validate_on_unit_struct(ctx)
+    }
 }
 
 /// Ensures that skip is not present on `item`.
@@ -134,6 +169,20 @@ pub fn if_weight_present(ctx: Ctx, attrs: &ParsedAttributes, item: &str) {
     }
 }
 
+/// Ensures that no limits on recursion are present on `item`.
+pub fn if_recursion_present(ctx: Ctx, attrs: &ParsedAttributes, item: &str) {
+    if has_recursion(attrs) {
+        illegal_recursion(ctx, item)
+    }
+}
+
+/// Returns true iff any limits on recursion are present.
+pub fn has_recursion(attrs: &ParsedAttributes) -> bool {
+    attrs.recursion_depth.is_some()
+        || attrs.recursion_size.is_some()
+        || attrs.recursion_branch.is_some()
+}
+
 //==============================================================================
 // Messages
 //==============================================================================
@@ -330,6 +379,17 @@ error!(
     item
 );
 
+// Happens when `#[proptest(range = "<range>")]` is specified on an `item`
+// that does not support setting an explicit range.
+// See `illegal_strategy` for more.
+error!(
+    illegal_range(item: &str),
+    E0007,
+    "`#[proptest(range = \"<range>\")]` is not allowed on {0}. Only struct \
+     fields and enum variant fields can use an explicit range.",
+    item
+);
+
 // Happens when `#[proptest(skip)]` is specified on an `item` that does
 // not support skipping. Only enum variants support skipping.
 error!(
@@ -358,7 +418,7 @@ error!(
     E0010,
     "Cannot set the associated type `Parameters` of `Arbitrary` with either \
      `#[proptest(no_params)]` or `#[proptest(params(<type>)]` on {} since it \
-     was set on the parent.",
+     was set on the parent, or since it is part of a recursive enum.",
     item
 );
 
@@ -509,8 +569,9 @@ fatal!(
     overspecified_strat,
     E0025,
     "Cannot set more than one of `#[proptest(value = \"<expr>\")]`,
-    `#[proptest(strategy = \"<expr>\")]`, `#[proptest(regex = \"<string>\")]` \
-    simultaneously. Please pick one of these attributes."
+    `#[proptest(strategy = \"<expr>\")]`, `#[proptest(regex = \"<string>\")]`, \
+    `#[proptest(range = \"<range>\")]` simultaneously. Please pick one of \
+    these attributes."
 );
 
 // Happens when `#[proptest(strategy..)]` or `#[proptest(value..)]` is
@@ -625,6 +686,20 @@ error!(filter_on_unit_struct, E0030,
     "Setting `#[proptest(filter = \"<expr>\")]` on a unit struct has no effect \
     and is redundant because there is nothing to filter.");
 
+// Occurs when `#[proptest(constructor = "<expr>")]` is specified on a unit
+// struct. There's only one way to produce a unit struct, so routing it
+// through a constructor would be pointless.
+error!(constructor_on_unit_struct, E0030,
+    "Setting `#[proptest(constructor = \"<expr>\")]` on a unit struct has no \
+    effect and is redundant because there are no fields to construct it from.");
+
+// Occurs when `#[proptest(validate = "<expr>")]` is specified on a unit
+// struct. There's only one way to produce a unit struct, so validating
+// it would be pointless.
+error!(validate_on_unit_struct, E0030,
+    "Setting `#[proptest(validate = \"<expr>\")]` on a unit struct has no \
+    effect and is redundant because there is nothing to validate.");
+
 // Occurs when `#[proptest(no_bound)]` is specified
 // on something that is not a type variable.
 error!(
@@ -674,6 +749,132 @@ error!(
     item
 );
 
+// Happens when `#[proptest(range..)]` is malformed.
+// For example, `#[proptest(range = 1)]` is not a valid form.
+error!(
+    range_malformed,
+    E0036,
+    "The attribute modifier `range` inside `#[proptest(..)]` must have the \
+    format `#[proptest(range = \"<range>\")]` where `<range>` is a range \
+    expression such as `1..100` or `0.0..=1.0` embedded in a Rust string slice."
+);
+
+// Happens when `#[proptest(params = <type>)]` is set on `item` and then
+// `#[proptest(range = "<range>")]` is also set. We reject this because
+// the params can't be used.
+error!(
+    cant_set_param_and_range(item: &str),
+    E0037,
+    "Cannot set #[proptest(range = \"<range>\")] and \
+     `#[proptest(params = <type>)]` on {0} because the latter is a logic bug \
+     since `params` cannot be used in `<range>`.",
+    item
+);
+
+// Happens when `#[proptest(recursion_depth = <integer>)]` or one of the other
+// limits on recursion is specified on an `item` that is not an enum.
+error!(
+    illegal_recursion(item: &str),
+    E0038,
+    "The limits on recursion `#[proptest(recursion_depth = <integer>)]`, \
+     `#[proptest(recursion_size = <integer>)]`, and \
+     `#[proptest(recursion_branch = <integer>)]` are not allowed on {}. \
+     Only recursive enums can set them.",
+    item
+);
+
+// Happens when `#[proptest(recursion_depth..)]` or one of the other limits on
+// recursion is malformed. For example, `#[proptest(recursion_depth = -1)]`.
+error!(
+    recursion_malformed(meta: &syn::Meta),
+    E0039,
+    "The attribute modifier `{0}` inside `#[proptest(..)]` must have the \
+    format `#[proptest({0} = <integer>)]` where `<integer>` is an integer that \
+    fits within a `u32`. An example: `#[proptest({0} = 4)]`.",
+    meta.path().into_token_stream()
+);
+
+// Happens when the limits on recursion are specified on an enum which is not
+// recursive, i.e. which has no field referring to the enum that is generated
+// via `Arbitrary`. The limits would be meaningless so we reject them.
+error!(
+    recursion_on_non_recursive_enum,
+    E0040,
+    "Cannot set the limits on recursion with \
+     `#[proptest(recursion_depth = <integer>)]` or similar on an enum which \
+     is not recursive. An enum is recursive if the fields of its variants \
+     refer to the enum itself, as in `enum List { Nil, Cons(u8, Box<List>) }`."
+);
+
+// Happens when all variants of a recursive enum that are not skipped refer to
+// the enum. Every value of the enum would then be infinitely large.
+fatal!(
+    recursive_enum_without_leaves,
+    E0041,
+    "The recursive enum you are deriving `Arbitrary` for has no variant that \
+     does not refer to the enum itself, so any value of it would be infinitely \
+     large. Add a variant such as `Nil` in \
+     `enum List { Nil, Cons(u8, Box<List>) }`, or provide a strategy for the \
+     fields referring to the enum with `#[proptest(strategy = \"<expr>\")]`."
+);
+
+// Happens when a field of a recursive enum refers to the enum in a way that
+// we don't know how to generate from a strategy for the enum.
+fatal!(
+    unsupported_recursive_field(ty: &syn::Type),
+    E0042,
+    "The field of type `{}` refers to the recursive enum you are deriving \
+     `Arbitrary` for in a way that is not supported. Only the enum itself, \
+     and `Box`, `Rc`, `Arc`, `Vec` or `Option` of such types are supported. \
+     Provide a strategy for the field with \
+     `#[proptest(strategy = \"<expr>\")]` instead.",
+    quote! { #ty }
+);
+
+// Happens when `#[proptest(constructor = "<expr>")]` is specified on an
+// `item` that is not a struct. The fields of enum variants can't be passed
+// to a single constructor, and fields have no fields of their own.
+error!(
+    illegal_constructor(item: &str),
+    E0043,
+    "`#[proptest(constructor = \"<expr>\")]` is not allowed on {}. Only \
+     structs can be generated through a constructor.",
+    item
+);
+
+// Happens when `#[proptest(constructor..)]` is malformed.
+// For example, `#[proptest(constructor = 1)]` is not a valid form.
+error!(
+    constructor_malformed,
+    E0044,
+    "The attribute modifier `constructor` inside `#[proptest(..)]` must have \
+    the format `#[proptest(constructor = \"<expr>\")]` where `<expr>` is a \
+    function such as `Self::new` which is passed the fields of the struct in \
+    order and which returns `Self`, `Option<Self>`, or `Result<Self, E>`."
+);
+
+// Happens when `#[proptest(validate = "<expr>")]` is specified on an `item`
+// that is neither a struct nor an enum. A validator checks the invariants of
+// the type as a whole; use `#[proptest(filter = "<expr>")]` for parts of it.
+error!(
+    illegal_validate(item: &str),
+    E0045,
+    "`#[proptest(validate = \"<expr>\")]` is not allowed on {}. Only structs \
+     and enums can be validated. To reject some values of {0}, use \
+     `#[proptest(filter = \"<expr>\")]` instead.",
+    item
+);
+
+// Happens when `#[proptest(validate..)]` is malformed.
+// For example, `#[proptest(validate = 1)]` is not a valid form.
+error!(
+    validate_malformed,
+    E0046,
+    "The attribute modifier `validate` inside `#[proptest(..)]` must have the \
+    format `#[proptest(validate = \"<expr>\")]` where `<expr>` is a function \
+    such as `Self::is_valid` which takes `&Self` and returns `bool`."
+);
+
 #[cfg(test)]
 mod tests {
     #[test]
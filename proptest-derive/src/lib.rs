@@ -29,7 +29,10 @@
 //
 // # Recursive types
 //
-// We can't handle self-recursive or mutually recursive types at all right now.
+// We can only handle self-recursive enums, where the fields referring to the
+// enum are the enum itself or `Box`, `Rc`, `Arc`, `Vec` or `Option` of such
+// types. Those enums are generated with `.prop_recursive(..)`. We can't handle
+// self-recursive structs or mutually recursive types at all right now.
 
 extern crate proc_macro as pm;
 extern crate proc_macro2;
@@ -44,6 +47,7 @@ mod attr;
 mod derive;
 mod error;
 mod interp;
+mod recursion;
 mod use_tracking;
 mod util;
 mod void;
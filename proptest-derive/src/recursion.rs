@@ -0,0 +1,123 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Provides detection of types that refer to the type we are deriving for,
+//! as well as the `Shape` of such types that we know how to generate.
+//!
+//! Like in `void`, we can't inspect type definitions, so we only detect
+//! direct references to the type by its name or as `Self`. A field of a
+//! type alias for the type, or a type wrapping it, is not seen as recursive.
+
+use syn::{self, visit};
+
+use crate::util;
+
+//==============================================================================
+// Detection
+//==============================================================================
+
+/// Returns true iff the given type refers to the type named `_self` anywhere.
+pub fn is_recursive(ty: &syn::Type, _self: &syn::Ident) -> bool {
+    let mut recursive = Recursive { _self, found: false };
+    visit::visit_type(&mut recursive, ty);
+    recursive.found
+}
+
+/// Tracks whether a reference to `_self` has been found.
+struct Recursive<'a> {
+    _self: &'a syn::Ident,
+    found: bool,
+}
+
+impl<'a, 'ast> visit::Visit<'ast> for Recursive<'a> {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if refers_to(path, self._self) {
+            self.found = true;
+        }
+        visit::visit_path(self, path)
+    }
+}
+
+/// Returns true iff the path is `Self` or `_self`,
+/// possibly applied to some generic arguments.
+fn refers_to(path: &syn::Path, _self: &syn::Ident) -> bool {
+    !util::path_is_global(path)
+        && util::match_singleton(&path.segments)
+            .is_some_and(|seg| &seg.ident == "Self" || &seg.ident == _self)
+}
+
+//==============================================================================
+// Shapes
+//==============================================================================
+
+/// The shape of a type that refers to the type we are deriving for,
+/// which determines how to generate it from a strategy for that type.
+pub enum Shape {
+    /// The type itself, i.e: `Self` or the name of the type.
+    Itself,
+    /// A pointer such as `Box<T>` to a type of the given shape, along with
+    /// the path to the function that constructs the pointer.
+    Pointer(syn::Path, Box<Shape>),
+    /// A `Vec<T>` of a type of the given shape.
+    Vec(Box<Shape>),
+    /// An `Option<T>` of a type of the given shape.
+    Option(Box<Shape>),
+}
+
+/// Returns the shape of the given type that refers to the type `_self`,
+/// or `None` if we don't know how to generate it.
+pub fn shape_of(ty: &syn::Type, _self: &syn::Ident) -> Option<Shape> {
+    let path = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        syn::Type::Group(group) => return shape_of(&group.elem, _self),
+        syn::Type::Paren(paren) => return shape_of(&paren.elem, _self),
+        _ => return None,
+    };
+
+    if refers_to(path, _self) {
+        return Some(Shape::Itself);
+    }
+
+    let mut prefix = path.clone();
+    let lseg = prefix.segments.pop()?.into_value();
+    let elem = Box::new(shape_of(util::pseg_single_tyvar(&lseg)?, _self)?);
+
+    // We hedge a bet that the user will not declare their own types with
+    // these names. This may give errors, but is worth it usability-wise.
+    let in_mod = |module| {
+        util::match_pathsegs(
+            &prefix,
+            &[
+                "",
+                module,
+                &format!("std::{}", module),
+                &format!("core::{}", module),
+                &format!("alloc::{}", module),
+                &format!("::std::{}", module),
+                &format!("::core::{}", module),
+                &format!("::alloc::{}", module),
+            ],
+        )
+    };
+
+    match lseg.ident.to_string().as_ref() {
+        "Box" if in_mod("boxed") => Some(Shape::Pointer(
+            parse_quote!(::std::boxed::Box::new),
+            elem,
+        )),
+        "Rc" if in_mod("rc") => {
+            Some(Shape::Pointer(parse_quote!(::std::rc::Rc::new), elem))
+        }
+        "Arc" if in_mod("sync") => {
+            Some(Shape::Pointer(parse_quote!(::std::sync::Arc::new), elem))
+        }
+        "Vec" if in_mod("vec") => Some(Shape::Vec(elem)),
+        "Option" if in_mod("option") => Some(Shape::Option(elem)),
+        _ => None,
+    }
+}
@@ -41,6 +41,8 @@ pub struct UseTracker {
     generics: syn::Generics,
     /// If set to `true`, then `mark_used` has no effect.
     track: bool,
+    /// If set to `true`, then all type variables are bounded by `'static`.
+    bound_static: bool,
 }
 
 /// Models a thing that may have type variables in it that
@@ -63,6 +65,7 @@ impl UseTracker {
             used_map,
             where_types: HashSet::default(),
             track: true,
+            bound_static: false,
         }
     }
 
@@ -71,6 +74,12 @@ impl UseTracker {
         self.track = false;
     }
 
+    /// Bound all type variables by `'static`, whether used or not.
+    /// This is needed when the strategy for `Self` is erased.
+    pub fn bound_static(&mut self) {
+        self.bound_static = true;
+    }
+
     /// Mark the _potential_ type variable `tyvar` as used.
     /// If the tracker does not know about the name, it is not
     /// a type variable and this call has no effect.
@@ -131,6 +140,12 @@ impl UseTracker {
             }
         }
 
+        if self.bound_static {
+            for tv in self.generics.type_params_mut() {
+                tv.bounds.push(parse_quote!('static));
+            }
+        }
+
         self.generics.make_where_clause().predicates.extend(
             self.where_types.iter().cloned().map(|ty| {
                 syn::WherePredicate::Type(syn::PredicateType {
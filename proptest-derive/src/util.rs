@@ -83,14 +83,20 @@ pub fn match_pathsegs(path: &syn::Path, against: &[&str]) -> bool {
 /// Returns true iff the given `PathArguments` is one that has one type
 /// applied to it.
 fn pseg_has_single_tyvar(pp: &syn::PathSegment) -> bool {
+    pseg_single_tyvar(pp).is_some()
+}
+
+/// Returns `Some(ty)` iff the given `PathArguments` is one that has the
+/// single type `ty` applied to it.
+pub fn pseg_single_tyvar(pp: &syn::PathSegment) -> Option<&syn::Type> {
     use syn::GenericArgument::Type;
     use syn::PathArguments::AngleBracketed;
     if let AngleBracketed(ab) = &pp.arguments {
-        if let Some(Type(_)) = match_singleton(ab.args.iter()) {
-            return true;
+        if let Some(Type(ty)) = match_singleton(ab.args.iter()) {
+            return Some(ty);
         }
     }
-    false
+    None
 }
 
 /// Returns true iff the given type is of the form `PhantomData<TY>` where
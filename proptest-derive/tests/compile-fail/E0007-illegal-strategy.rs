@@ -83,3 +83,20 @@ struct O(Vec<u8>);
 #[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0007]
 #[proptest(regex = "b*")]
 enum P { V1, V2, }
+
+// range:
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0007]
+#[proptest(range = "1..2")]
+struct Q { field: u8 }
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0007]
+#[proptest(range = "1..2")]
+enum R { V1, V2, }
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0007]
+enum S {
+    #[proptest(range = "1..2")]
+    V1(u8),
+    V2,
+}
@@ -53,3 +53,11 @@ struct T7();
 #[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0030]
 #[proptest(filter(foo))]
 struct T8 {}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0030]
+#[proptest(constructor = "Self::new")]
+struct T9;
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0030]
+#[proptest(validate = "Self::is_valid")]
+struct T10 {}
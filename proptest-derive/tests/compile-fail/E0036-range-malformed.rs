@@ -0,0 +1,47 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use]
+extern crate proptest_derive;
+use proptest_derive::Arbitrary;
+
+fn main() {}
+
+// Show non-fatal:
+#[derive(Debug, Arbitrary)] //~ ERROR: 2 errors:
+                            //~| [proptest_derive, E0036]
+                            //~| [proptest_derive, E0008]
+#[proptest(range)]
+#[proptest(skip)]
+struct NonFatal;
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0036]
+enum T0 {
+    V1 {
+        #[proptest(range)]
+        batman: u8
+    }
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0036]
+struct T1 {
+    #[proptest(range)]
+    field: usize,
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0036]
+struct T2(
+    #[proptest(range = 1)]
+    usize,
+);
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0036]
+struct T3(
+    #[proptest(range(bounds))]
+    usize,
+);
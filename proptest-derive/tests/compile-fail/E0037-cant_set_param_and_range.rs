@@ -0,0 +1,51 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use]
+extern crate proptest_derive;
+use proptest_derive::Arbitrary;
+
+fn main() {}
+
+// Show non-fatal:
+#[derive(Debug, Arbitrary)] //~ ERROR: 2 errors:
+                            //~| [proptest_derive, E0037]
+                            //~| [proptest_derive, E0008]
+#[proptest(skip)]
+struct NonFatal {
+    #[proptest(params(u8), range = "1..10")]
+    field: u8
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0037]
+struct T0 {
+    #[proptest(params(u8), range = "1..10")]
+    field: u8
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0037]
+struct T1(
+    #[proptest(params = "u8", range("0..=5"))]
+    u16
+);
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0037]
+enum T2 {
+    V0 {
+        #[proptest(params(u8), range = "1..10")]
+        field: u8
+    }
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0037]
+enum T3 {
+    V0(
+        #[proptest(params("u8"), range = "1..10")]
+        u32
+    )
+}
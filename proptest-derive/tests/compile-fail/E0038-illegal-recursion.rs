@@ -0,0 +1,36 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use]
+extern crate proptest_derive;
+use proptest_derive::Arbitrary;
+
+fn main() {}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0038]
+#[proptest(recursion_depth = 4)]
+struct T0;
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0038]
+struct T1 {
+    #[proptest(recursion_size = 4)]
+    field: u8,
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0038]
+enum T2 {
+    Leaf,
+    #[proptest(recursion_branch = 4)]
+    Node(Box<T2>),
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0038]
+enum T3 {
+    Leaf,
+    Node(#[proptest(recursion_depth = 4)] Box<T3>),
+}
@@ -0,0 +1,27 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use]
+extern crate proptest_derive;
+use proptest_derive::Arbitrary;
+
+fn main() {}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0040]
+#[proptest(recursion_depth = 4)]
+enum T0 {
+    Leaf,
+    Node(u8),
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0040]
+#[proptest(recursion_size = 16)]
+enum T1 {
+    Leaf,
+    Node(#[proptest(value = "Box::new(T1::Leaf)")] Box<T1>),
+}
@@ -0,0 +1,33 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use]
+extern crate proptest_derive;
+use proptest_derive::Arbitrary;
+
+fn main() {}
+
+use std::collections::HashMap;
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0042]
+enum T0 {
+    Leaf,
+    Node(HashMap<u8, T0>),
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0042]
+enum T1 {
+    Leaf,
+    Node((Box<T1>, Box<T1>)),
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0042]
+enum T2 {
+    Leaf,
+    Node([Box<Self>; 2]),
+}
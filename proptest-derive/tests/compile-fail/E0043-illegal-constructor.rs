@@ -0,0 +1,36 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use]
+extern crate proptest_derive;
+use proptest_derive::Arbitrary;
+
+fn main() {}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0043]
+#[proptest(constructor = "Self::new")]
+enum T0 {
+    V0(u8),
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0043]
+enum T1 {
+    #[proptest(constructor = "Self::new")]
+    V0(u8),
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0043]
+struct T2 {
+    #[proptest(constructor = "u8::from")]
+    field: u8,
+}
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0043]
+enum T3 {
+    V0(#[proptest(constructor = "u8::from")] u8),
+}
@@ -0,0 +1,33 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use]
+extern crate proptest_derive;
+use proptest_derive::Arbitrary;
+
+fn main() {}
+
+// Show non-fatal:
+#[derive(Debug, Arbitrary)] //~ ERROR: 2 errors:
+                            //~| [proptest_derive, E0044]
+                            //~| [proptest_derive, E0008]
+#[proptest(constructor)]
+#[proptest(skip)]
+struct NonFatal(u8);
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0044]
+#[proptest(constructor)]
+struct T0(u8);
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0044]
+#[proptest(constructor = 1)]
+struct T1(u8);
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0044]
+#[proptest(constructor = "Self::")]
+struct T2(u8);
@@ -0,0 +1,31 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use]
+extern crate proptest_derive;
+use proptest_derive::Arbitrary;
+
+fn main() {}
+
+// Show non-fatal:
+#[derive(Debug, Arbitrary)] //~ ERROR: 2 errors:
+                            //~| [proptest_derive, E0046]
+                            //~| [proptest_derive, E0008]
+#[proptest(validate)]
+#[proptest(skip)]
+struct NonFatal(u8);
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0046]
+#[proptest(validate)]
+struct T0(u8);
+
+#[derive(Debug, Arbitrary)] //~ ERROR: [proptest_derive, E0046]
+#[proptest(validate = 1)]
+enum T1 {
+    V0(u8),
+}
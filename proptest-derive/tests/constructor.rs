@@ -0,0 +1,201 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use proptest::prelude::{any_with, proptest, Arbitrary, Just};
+use proptest_derive::Arbitrary;
+
+// constructor returning Self:
+
+#[derive(Debug, Arbitrary)]
+#[proptest(constructor = "Self::new")]
+struct Lower {
+    #[proptest(regex = "[a-zA-Z]{0,8}")]
+    name: String,
+}
+
+impl Lower {
+    fn new(name: String) -> Self {
+        Self {
+            name: name.to_lowercase(),
+        }
+    }
+}
+
+// constructor returning Option<Self>:
+
+#[derive(Debug, Arbitrary)]
+#[proptest(constructor = "Self::new")]
+struct Even(u32);
+
+impl Even {
+    fn new(value: u32) -> Option<Self> {
+        if value % 2 == 0 {
+            Some(Even(value))
+        } else {
+            None
+        }
+    }
+}
+
+// constructor returning Result<Self, E>:
+
+#[derive(Debug, Arbitrary)]
+#[proptest(constructor(make_interval))]
+struct Interval {
+    #[proptest(range = "-10..10")]
+    lo: i32,
+    #[proptest(range = "-10..10")]
+    hi: i32,
+}
+
+fn make_interval(lo: i32, hi: i32) -> Result<Interval, String> {
+    if lo <= hi {
+        Ok(Interval { lo, hi })
+    } else {
+        Err(format!("{} > {}", lo, hi))
+    }
+}
+
+// constructor with parameters:
+
+#[derive(Debug, Arbitrary)]
+#[proptest(params = "u8", constructor = "Self::new")]
+struct Bounded {
+    #[proptest(strategy = "0..=params")]
+    value: u8,
+    #[proptest(strategy = "Just(params)")]
+    bound: u8,
+}
+
+impl Bounded {
+    fn new(value: u8, bound: u8) -> Self {
+        Bounded { value, bound }
+    }
+}
+
+// constructor on a generic struct:
+
+#[derive(Debug, Arbitrary)]
+#[proptest(constructor = "Self::new")]
+struct Sorted<T: Ord> {
+    items: Vec<T>,
+}
+
+impl<T: Ord> Sorted<T> {
+    fn new(mut items: Vec<T>) -> Self {
+        items.sort();
+        Sorted { items }
+    }
+}
+
+// validate:
+
+#[derive(Debug, Arbitrary)]
+#[proptest(validate = "Self::is_valid")]
+struct Ratio {
+    num: u8,
+    den: u8,
+}
+
+impl Ratio {
+    fn is_valid(&self) -> bool {
+        self.den != 0
+    }
+}
+
+#[derive(Debug, Arbitrary)]
+#[proptest(validate(is_small), filter("|s| !matches!(s, Shape::Dot)"))]
+enum Shape {
+    Dot,
+    Square(u8),
+    Rect(u8, u8),
+}
+
+fn is_small(shape: &Shape) -> bool {
+    match shape {
+        Shape::Dot => true,
+        Shape::Square(side) => *side < 100,
+        Shape::Rect(w, h) => *w < 100 && *h < 100,
+    }
+}
+
+// constructor and validate:
+
+#[derive(Debug, Arbitrary)]
+#[proptest(constructor = "Self::new", validate = "Self::is_valid")]
+struct Percent(u8);
+
+impl Percent {
+    fn new(value: u8) -> Self {
+        Percent(value.min(100))
+    }
+
+    fn is_valid(&self) -> bool {
+        self.0 != 50
+    }
+}
+
+proptest! {
+    #[test]
+    fn lower_is_constructed(v: Lower) {
+        assert_eq!(v.name, v.name.to_lowercase());
+    }
+
+    #[test]
+    fn even_rejects_none(v: Even) {
+        assert_eq!(v.0 % 2, 0);
+    }
+
+    #[test]
+    fn interval_rejects_err(v: Interval) {
+        assert!((-10..10).contains(&v.lo));
+        assert!(v.lo <= v.hi && v.hi < 10);
+    }
+
+    #[test]
+    fn bounded_uses_params(v in any_with::<Bounded>(7)) {
+        assert_eq!(v.bound, 7);
+        assert!(v.value <= v.bound);
+    }
+
+    #[test]
+    fn sorted_is_constructed(v: Sorted<u8>) {
+        assert!(v.items.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn ratio_is_validated(v: Ratio) {
+        assert_ne!(v.den, 0);
+        assert!(v.num / v.den <= v.num);
+    }
+
+    #[test]
+    fn shape_is_validated_and_filtered(v: Shape) {
+        assert!(is_small(&v));
+        assert!(!matches!(v, Shape::Dot));
+    }
+
+    #[test]
+    fn percent_is_constructed_then_validated(v: Percent) {
+        assert!(v.0 <= 100 && v.0 != 50);
+    }
+}
+
+#[test]
+fn asserting_arbitrary() {
+    fn assert_arbitrary<T: Arbitrary>() {}
+
+    assert_arbitrary::<Lower>();
+    assert_arbitrary::<Even>();
+    assert_arbitrary::<Interval>();
+    assert_arbitrary::<Bounded>();
+    assert_arbitrary::<Sorted<u8>>();
+    assert_arbitrary::<Ratio>();
+    assert_arbitrary::<Shape>();
+    assert_arbitrary::<Percent>();
+}
@@ -0,0 +1,101 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use proptest::prelude::{proptest, Arbitrary};
+use proptest_derive::Arbitrary;
+
+// struct:
+
+#[derive(Debug, Arbitrary)]
+struct T0 {
+    #[proptest(range = "1..100")]
+    foo: u8,
+    #[proptest(range("-5..=5"))]
+    bar: i64,
+    #[proptest(range = "0.0..1.0")]
+    baz: f32,
+    #[proptest(range = "0..=9", filter("|c| *c != 5"))]
+    quux: u16,
+}
+
+#[derive(Debug, Arbitrary)]
+struct T1(
+    #[proptest(range = "1..100")] u8,
+    #[proptest(range("-5..=5"))] i64,
+    #[proptest(range = "0.0..1.0")] f32,
+    #[proptest(range = "0..=9", filter("|c| *c != 5"))] u16,
+);
+
+// enum:
+
+#[derive(Debug, Arbitrary)]
+enum T2 {
+    V0 {
+        #[proptest(range = "1..100")]
+        foo: u8,
+        #[proptest(range("-5..=5"))]
+        bar: i64,
+        #[proptest(range = "0.0..1.0")]
+        baz: f32,
+        #[proptest(range = "0..=9", filter("|c| *c != 5"))]
+        quux: u16,
+    },
+}
+
+#[derive(Debug, Arbitrary)]
+enum T3 {
+    V0(
+        #[proptest(range = "1..100")] u8,
+        #[proptest(range("-5..=5"))] i64,
+        #[proptest(range = "0.0..1.0")] f32,
+        #[proptest(range = "0..=9", filter("|c| *c != 5"))] u16,
+    ),
+}
+
+fn assert_adherence(x0: u8, x1: i64, x2: f32, x3: u16) {
+    assert!((1..100).contains(&x0));
+    assert!((-5..=5).contains(&x1));
+    assert!((0.0..1.0).contains(&x2));
+    assert!(x3 <= 9 && x3 != 5);
+}
+
+proptest! {
+    #[test]
+    fn t0_adhering_to_range(v: T0) {
+        let T0 { foo: x0, bar: x1, baz: x2, quux: x3 } = v;
+        assert_adherence(x0, x1, x2, x3);
+    }
+
+    #[test]
+    fn t1_adhering_to_range(v: T1) {
+        let T1(x0, x1, x2, x3) = v;
+        assert_adherence(x0, x1, x2, x3);
+    }
+
+    #[test]
+    fn t2_adhering_to_range(v: T2) {
+        let T2::V0 { foo: x0, bar: x1, baz: x2, quux: x3 } = v;
+        assert_adherence(x0, x1, x2, x3);
+    }
+
+    #[test]
+    fn t3_adhering_to_range(v: T3) {
+        let T3::V0(x0, x1, x2, x3) = v;
+        assert_adherence(x0, x1, x2, x3);
+    }
+}
+
+#[test]
+fn asserting_arbitrary() {
+    fn assert_arbitrary<T: Arbitrary>() {}
+
+    assert_arbitrary::<T0>();
+    assert_arbitrary::<T1>();
+    assert_arbitrary::<T2>();
+    assert_arbitrary::<T3>();
+}
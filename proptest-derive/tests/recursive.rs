@@ -0,0 +1,197 @@
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use proptest::prelude::{any, any_with, proptest, Arbitrary, Just, Strategy};
+use proptest_derive::Arbitrary;
+
+#[derive(Debug, Arbitrary)]
+enum Expr {
+    Lit(#[proptest(range = "0..10")] u8),
+    Var(#[proptest(regex = "[a-z]")] String),
+    Neg(Box<Expr>),
+    Add(Box<Self>, Box<Self>),
+    Call(#[proptest(range = "0..4")] u8, Vec<Expr>),
+}
+
+impl Expr {
+    fn depth(&self) -> u32 {
+        match self {
+            Expr::Lit(_) | Expr::Var(_) => 0,
+            Expr::Neg(e) => 1 + e.depth(),
+            Expr::Add(l, r) => 1 + l.depth().max(r.depth()),
+            Expr::Call(_, args) => {
+                1 + args.iter().map(Expr::depth).max().unwrap_or(0)
+            }
+        }
+    }
+
+    fn assert_adherence(&self) {
+        match self {
+            Expr::Lit(x) => assert!(*x < 10),
+            Expr::Var(name) => {
+                assert!(name.len() == 1);
+                assert!(name.chars().all(|c| c.is_ascii_lowercase()));
+            }
+            Expr::Neg(e) => e.assert_adherence(),
+            Expr::Add(l, r) => {
+                l.assert_adherence();
+                r.assert_adherence();
+            }
+            Expr::Call(function, args) => {
+                assert!(*function < 4);
+                args.iter().for_each(Expr::assert_adherence);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Arbitrary)]
+#[proptest(recursion_depth = 2, recursion_size = 8, recursion_branch = 2)]
+enum Shallow {
+    Leaf,
+    #[proptest(weight = 3)]
+    Node(Vec<Shallow>),
+}
+
+impl Shallow {
+    fn depth(&self) -> u32 {
+        match self {
+            Shallow::Leaf => 0,
+            Shallow::Node(children) => {
+                1 + children.iter().map(Shallow::depth).max().unwrap_or(0)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Arbitrary)]
+enum Pointers {
+    End,
+    Rc(Rc<Pointers>),
+    Arc(Arc<Pointers>),
+    Maybe(Option<Box<Pointers>>),
+}
+
+impl Pointers {
+    fn depth(&self) -> u32 {
+        match self {
+            Pointers::End | Pointers::Maybe(None) => 0,
+            Pointers::Rc(p) => 1 + p.depth(),
+            Pointers::Arc(p) => 1 + p.depth(),
+            Pointers::Maybe(Some(p)) => 1 + p.depth(),
+        }
+    }
+}
+
+#[derive(Debug, Arbitrary)]
+enum Tree<T> {
+    Leaf(T),
+    Node(Box<Tree<T>>, Box<Tree<T>>),
+}
+
+impl<T> Tree<T> {
+    fn leaves(&self) -> Vec<&T> {
+        match self {
+            Tree::Leaf(value) => vec![value],
+            Tree::Node(l, r) => {
+                let mut leaves = l.leaves();
+                leaves.extend(r.leaves());
+                leaves
+            }
+        }
+    }
+}
+
+#[derive(Debug, Arbitrary)]
+#[proptest(params = "u8")]
+enum Bounded {
+    #[proptest(strategy = "(0..=params).prop_map(Bounded::Leaf)")]
+    Leaf(u8),
+    Node(Box<Bounded>),
+}
+
+impl Bounded {
+    fn leaf(&self) -> u8 {
+        match self {
+            Bounded::Leaf(value) => *value,
+            Bounded::Node(inner) => inner.leaf(),
+        }
+    }
+}
+
+// Fields referring to the enum with an explicit strategy do not make it
+// recursive, so parameters may be passed through as usual:
+#[derive(Clone, Debug, Arbitrary)]
+enum Explicit {
+    Leaf(u8),
+    Node(#[proptest(strategy = "Just(Box::new(Explicit::Leaf(0)))")] Box<Self>),
+}
+
+proptest! {
+    #[test]
+    fn expr_depth_is_bounded(e: Expr) {
+        assert!(e.depth() <= 4);
+        e.assert_adherence();
+    }
+
+    #[test]
+    fn shallow_depth_is_bounded(s: Shallow) {
+        assert!(s.depth() <= 2);
+    }
+
+    #[test]
+    fn pointers_depth_is_bounded(p: Pointers) {
+        assert!(p.depth() <= 4);
+    }
+
+    #[test]
+    fn tree_has_leaves(t: Tree<u8>) {
+        assert!(!t.leaves().is_empty());
+    }
+
+    #[test]
+    fn params_are_used_by_reference(b in any_with::<Bounded>(3)) {
+        assert!(b.leaf() <= 3);
+    }
+
+    #[test]
+    fn explicit_is_not_recursive(e: Explicit) {
+        if let Explicit::Node(inner) = e {
+            assert!(matches!(*inner, Explicit::Leaf(0)));
+        }
+    }
+}
+
+#[test]
+fn generates_branches() {
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    let mut runner = TestRunner::deterministic();
+    let strategy = any::<Expr>();
+    let deep = (0..256)
+        .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+        .filter(|e| e.depth() > 0)
+        .count();
+    assert!(deep > 0);
+}
+
+#[test]
+fn asserting_arbitrary() {
+    fn assert_arbitrary<T: Arbitrary>() {}
+
+    assert_arbitrary::<Expr>();
+    assert_arbitrary::<Shallow>();
+    assert_arbitrary::<Pointers>();
+    assert_arbitrary::<Tree<u8>>();
+    assert_arbitrary::<Bounded>();
+    assert_arbitrary::<Explicit>();
+}
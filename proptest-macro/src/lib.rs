@@ -65,6 +65,53 @@ mod property_test;
 /// }
 /// ```
 /// Multiple `#[strategy = <expr>]` attributes on an argument are not allowed.
+///
+/// ## Explicit examples
+///
+/// Known edge cases and past regressions can be kept next to the property with the `examples`
+/// attribute. Each example is run, in order, before any generated inputs, and the test panics on
+/// the first which fails. With a single parameter, each example is the value of that parameter;
+/// otherwise, it is a tuple of the values of all parameters, in order. Any expression which can be
+/// iterated over is accepted.
+///
+/// ```
+/// # use proptest_macro::property_test;
+/// #[property_test(examples = [(0, 1), (i32::MAX, i32::MIN)])]
+/// fn add_commutes(x: i32, y: i32) {
+///     assert_eq!(x.wrapping_add(y), y.wrapping_add(x));
+/// }
+/// ```
+///
+/// Examples are not shrunk, and failing examples are not persisted.
+///
+/// ## Fixtures
+///
+/// Parameters marked `#[fixture]` are not generated, but set up once before the first case and
+/// shared by all cases, e.g. for temporary directories, database handles or async runtimes. Their
+/// type must be a shared reference to a type implementing `proptest::test_runner::Fixture`, whose
+/// `teardown` runs after the last case, whether the test passes or fails. With
+/// `#[fixture = <expr>]`, the fixture is instead the value of `expr`, which is simply dropped after
+/// the last case.
+///
+/// ```rust,ignore
+/// impl Fixture for Database {
+///     fn setup() -> Self {
+///         Database::connect("postgres://localhost/test")
+///     }
+///
+///     fn teardown(self) {
+///         self.drop_all_tables();
+///     }
+/// }
+///
+/// #[property_test]
+/// fn insert_then_get(#[fixture] db: &Database, #[fixture = tempdir()] dir: &TempDir, x: u32) {
+///     db.insert(x);
+///     assert!(db.contains(x));
+/// }
+/// ```
+///
+/// Fixtures are not part of the `examples` given in the attribute.
 /// 
 /// ## Semver guarantees of generated code
 ///
@@ -17,13 +17,15 @@ mod test_body;
 /// The rough process is:
 ///  - strip out the function args from the provided function
 ///  - turn them into a struct
-///  - implement `Arbitrary` for that struct (simple field-wise impl)
+///  - implement `Arbitrary` for that struct (simple field-wise impl), leaving out fixtures
 ///  - create a runner, do the rest
 ///
 ///  Currently, any attributes on parameters are ignored - in the future, we probably want to read
 ///  these for things like customizing strategies
 pub(super) fn generate(item_fn: ItemFn, options: Options) -> TokenStream {
     let (mut argless_fn, args) = strip_args(item_fn);
+    let (fixtures, args): (Vec<_>, Vec<_>) =
+        args.into_iter().partition(|arg| arg.fixture.is_some());
 
     let struct_tokens = generate_struct(&argless_fn.sig.ident, &args);
     let arb_tokens =
@@ -37,6 +39,7 @@ pub(super) fn generate(item_fn: ItemFn, options: Options) -> TokenStream {
     let new_body = test_body::body(
         *argless_fn.block,
         &args,
+        &fixtures,
         struct_and_arb,
         &argless_fn.sig.ident,
         &argless_fn.sig.output,
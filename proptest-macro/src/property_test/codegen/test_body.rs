@@ -1,8 +1,11 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{parse2, Block, Expr, Ident, Pat, ReturnType, Type, TypeTuple};
 
-use crate::property_test::{options::Options, utils::Argument};
+use crate::property_test::{
+    options::Options,
+    utils::{Argument, Fixture},
+};
 
 use super::{nth_field_name, struct_name};
 
@@ -11,6 +14,7 @@ use super::{nth_field_name, struct_name};
 pub(super) fn body(
     block: Block,
     args: &[Argument],
+    fixtures: &[Argument],
     struct_and_impl: TokenStream,
     fn_name: &Ident,
     ret_ty: &ReturnType,
@@ -51,6 +55,19 @@ pub(super) fn body(
 
     let config = make_config(options.config.as_ref(), fn_name);
 
+    let fixture_setup = fixtures.iter().enumerate().map(set_up_fixture);
+    let fixture_bindings = fixtures.iter().enumerate().map(|(index, arg)| {
+        let pat = &arg.pat_ty.pat;
+        let ty = &arg.pat_ty.ty;
+        let holder = fixture_holder(index);
+        quote! { let #pat: #ty = &#holder; }
+    });
+
+    let examples = options
+        .examples
+        .as_ref()
+        .map(|examples| run_examples(examples, args, &struct_name));
+
     let tokens = quote! ( {
 
         #(#errors)*
@@ -59,16 +76,25 @@ pub(super) fn body(
 
         #config
 
+        #(#fixture_setup)*
+
         let mut runner = ::proptest::test_runner::TestRunner::new(config);
-        
+
+        let test = |::proptest::sugar::NamedArguments(_, #struct_pattern): ::proptest::sugar::NamedArguments<&'static str, #struct_name>|
+            -> ::proptest::test_runner::TestCaseResult
+        {
+            #(#fixture_bindings)*
+            let result = #block;
+            #handle_result
+        };
+
+        #examples
+
         let result = runner.run(
             &::proptest::strategy::Strategy::prop_map(::proptest::prelude::any::<#struct_name>(), |values| {
                 ::proptest::sugar::NamedArguments(stringify!(#struct_name), values)
             }),
-            |::proptest::sugar::NamedArguments(_, #struct_pattern)| {
-                let result = #block;
-                #handle_result
-            },
+            &test,
         );
 
         match result {
@@ -82,6 +108,61 @@ pub(super) fn body(
     parse2(tokens).unwrap()
 }
 
+/// Set up the fixture for the `index`th fixture argument, which lives until the end of the test
+///
+/// Fixtures are torn down in the reverse order of their arguments, after the last case has run
+fn set_up_fixture((index, arg): (usize, &Argument)) -> TokenStream {
+    let holder = fixture_holder(index);
+
+    match (&arg.fixture, arg.pat_ty.ty.as_ref()) {
+        (Some(Fixture::Expr(expr)), _) => quote! { let #holder = #expr; },
+        (Some(Fixture::Trait), Type::Reference(reference)) => {
+            let ty = &reference.elem;
+            quote! {
+                let #holder = ::proptest::test_runner::FixtureGuard::<#ty>::setup();
+            }
+        }
+        _ => panic!("invalid fixtures should be filtered by validate"),
+    }
+}
+
+/// The name of the local holding the `index`th fixture, which is not visible to the test body
+fn fixture_holder(index: usize) -> Ident {
+    Ident::new(&format!("fixture{index}"), Span::mixed_site())
+}
+
+/// Run each of the `examples` given in the attribute through `test` before any generated inputs,
+/// panicking on the first which fails
+///
+/// With a single argument, each example is the value of that argument, otherwise it is a tuple of
+/// the values of all arguments, in order
+fn run_examples(
+    examples: &Expr,
+    args: &[Argument],
+    struct_name: &Ident,
+) -> TokenStream {
+    let field_names: Vec<_> =
+        (0..args.len()).map(|index| nth_field_name(args, index)).collect();
+
+    let to_struct = match field_names.as_slice() {
+        [field_name] => quote! { #struct_name { #field_name: example } },
+        _ => quote! {{
+            let (#(#field_names,)*) = example;
+            #struct_name { #(#field_names,)* }
+        }},
+    };
+
+    quote! {
+        for (index, example) in ::core::iter::IntoIterator::into_iter(#examples).enumerate() {
+            let example = #to_struct;
+            let input = format!("{:?}", example);
+            if let Err(e) = test(::proptest::sugar::NamedArguments(stringify!(#struct_name), example)) {
+                panic!("Example {} failed: {}\ninput: {}", index, e, input);
+            }
+        }
+    }
+}
+
 /// rough heuristic for whether we should use result-style syntax - if the function returns either
 /// nothing (i.e. `()`) or an empty tuple, it will be non-result handling, otherwise it uses
 /// result-style handling
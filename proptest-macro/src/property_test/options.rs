@@ -11,6 +11,8 @@ pub(super) struct Options {
     /// Collect compiler errors and emit them later, since errors here are largely recoverable
     pub errors: Vec<TokenStream>,
     pub config: Option<Expr>,
+    /// Inputs to run before any generated ones, e.g. `examples = [(0, 1)]`
+    pub examples: Option<Expr>,
 }
 
 impl Parse for Options {
@@ -23,6 +25,7 @@ impl Parse for Options {
         let mut errors = Vec::new();
 
         let mut config = None;
+        let mut examples = None;
 
         for MetaNameValue { path, value, .. } in pairs {
             let path_string = path.get_ident().map(Ident::to_string);
@@ -30,6 +33,7 @@ impl Parse for Options {
             match path_string.as_deref() {
                 None => errors.push(quote_spanned!(path.span() => compile_error!("unknown argument"))),
                 Some("config") => config = Some(value),
+                Some("examples") => examples = Some(value),
                 Some(other) => {
                     let error_message = format!("unknown argument: {other}");
                     let error_message = LitStr::new(&error_message, other.span());
@@ -39,7 +43,11 @@ impl Parse for Options {
             }
         }
 
-        Ok(Self { errors, config })
+        Ok(Self {
+            errors,
+            config,
+            examples,
+        })
     }
 }
 
@@ -51,10 +59,24 @@ mod tests {
 
     #[test]
     fn simple_parse_example() {
-        let Options { errors, config } =
-            parse_str("config = (), random = 123").unwrap();
+        let Options {
+            errors,
+            config,
+            examples,
+        } = parse_str("config = (), random = 123").unwrap();
 
         assert!(config.is_some());
+        assert!(examples.is_none());
         assert_eq!(errors.len(), 1);
     }
+
+    #[test]
+    fn parses_examples() {
+        let Options {
+            errors, examples, ..
+        } = parse_str("examples = [(0, 1), (2, 3)]").unwrap();
+
+        assert!(examples.is_some());
+        assert!(errors.is_empty());
+    }
 }
@@ -8,12 +8,18 @@ use crate::property_test::{codegen, options::Options};
 /// to it. The generated code is then formatted, and passed to the snapshot testing library
 ///
 /// If `fails` is supplied, then the output will be
+///
+/// Options given in parentheses after the name are parsed as the contents of the attribute.
 macro_rules! snapshot_test {
     ($name:ident {$($t:tt)*}) => {
+        snapshot_test!($name () {$($t)*});
+    };
+    ($name:ident ($($options:tt)*) {$($t:tt)*}) => {
         #[test]
         fn $name() {
             let input = parse_quote! { $($t)* };
-            let tokens = codegen::generate(input, Options::default());
+            let options: Options = parse_quote! { $($options)* };
+            let tokens = codegen::generate(input, options);
             let file = syn::parse_file(&tokens.to_string()).unwrap();
             let formatted = prettyplease::unparse(&file);
 
@@ -45,3 +51,21 @@ snapshot_test!(mix_custom_and_default_strategies {
         let x = 1;
     }
 });
+
+snapshot_test!(examples (examples = [(0, "zero".into()), (1, "one".into())]) {
+    fn foo(x: i32, y: String) {
+        let x = 1;
+    }
+});
+
+snapshot_test!(single_arg_examples (examples = EDGE_CASES) {
+    fn foo(x: i32) {
+        let x = 1;
+    }
+});
+
+snapshot_test!(fixtures {
+    fn foo(#[fixture] db: &Database, x: i32, #[fixture = tempdir()] dir: &TempDir) {
+        let x = 1;
+    }
+});
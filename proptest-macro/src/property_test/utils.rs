@@ -1,9 +1,18 @@
 use syn::{AttrStyle, Attribute, Expr, FnArg, ItemFn, Meta, PatType};
 
-/// A parsed argument, with an optional custom strategy
+/// A parsed argument, with an optional custom strategy, or marked as a fixture
 pub struct Argument {
     pub pat_ty: PatType,
     pub strategy: Option<Expr>,
+    pub fixture: Option<Fixture>,
+}
+
+/// Where the value of a fixture argument comes from
+pub enum Fixture {
+    /// `#[fixture]`, set up and torn down through the `Fixture` trait
+    Trait,
+    /// `#[fixture = <expr>]`, evaluated once and dropped after the last case
+    Expr(Expr),
 }
 
 /// Convert a function to a zero-arg function, and return the args
@@ -25,7 +34,9 @@ pub fn strip_args(mut f: ItemFn) -> (ItemFn, Vec<Argument>) {
 }
 
 fn strip_strategy(mut pat_ty: PatType) -> Argument {
-    let (strategies, others) = pat_ty.attrs.into_iter().partition(is_strategy);
+    let (strategies, others): (Vec<_>, _) =
+        pat_ty.attrs.into_iter().partition(is_strategy);
+    let (fixtures, others) = others.into_iter().partition(is_fixture);
 
     pat_ty.attrs = others;
 
@@ -38,7 +49,23 @@ fn strip_strategy(mut pat_ty: PatType) -> Argument {
         _ => panic!("multiple strategies should be filtered by validate"),
     };
 
-    Argument { pat_ty, strategy }
+    let fixture = match &fixtures[..] {
+        [] => None,
+        [f] => match &f.meta {
+            Meta::Path(_) => Some(Fixture::Trait),
+            Meta::NameValue(name_value) => {
+                Some(Fixture::Expr(name_value.value.clone()))
+            }
+            _ => panic!("invalid fixtures should be filtered by validate"),
+        },
+        _ => panic!("multiple fixtures should be filtered by validate"),
+    };
+
+    Argument {
+        pat_ty,
+        strategy,
+        fixture,
+    }
 }
 
 /// Checks if an attribute counts as a "strategy" attribute
@@ -60,6 +87,23 @@ pub fn is_strategy(attr: &Attribute) -> bool {
     path_correct && has_equals && is_outer
 }
 
+/// Checks if an attribute counts as a "fixture" attribute
+///
+/// This means:
+///  - it is an outer attribute (i.e. `#[...]` not `#![...]`)
+///  - it is either `fixture` or `fixture = <expr>`
+pub fn is_fixture(attr: &Attribute) -> bool {
+    let path_correct = attr
+        .path()
+        .get_ident()
+        .map(|ident| ident == "fixture")
+        .unwrap_or(false);
+
+    let is_outer = matches!(attr.style, AttrStyle::Outer);
+
+    path_correct && is_outer
+}
+
 #[cfg(test)]
 mod tests {
     use quote::ToTokens;
@@ -78,6 +122,7 @@ mod tests {
         let arg = args.pop().unwrap();
         assert_eq!(arg.pat_ty.to_token_stream().to_string(), "i : i32");
         assert!(arg.strategy.is_none());
+        assert!(arg.fixture.is_none());
     }
 
     #[test]
@@ -108,11 +153,27 @@ mod tests {
     #[test]
     fn strip_strategy_works() {
         let f = parse_quote! {fn foo(#[strategy = 123] x: i32) {} };
-        let Argument { pat_ty, strategy } = strip_args(f).1.pop().unwrap();
+        let Argument {
+            pat_ty, strategy, ..
+        } = strip_args(f).1.pop().unwrap();
         // let Argument { pat_ty, strategy } = strip_strategy(parse_quote! {
         //     #[strategy] x: i32
         // });
         assert_eq!(pat_ty.to_token_stream().to_string(), "x : i32");
         assert_eq!(strategy.to_token_stream().to_string(), "123");
     }
+
+    #[test]
+    fn strip_fixture_works() {
+        let f = parse_quote! {
+            fn foo(#[fixture] a: &Db, #[fixture = make_dir()] b: &Dir) {}
+        };
+        let (_, args) = strip_args(f);
+
+        assert_eq!(args[0].pat_ty.to_token_stream().to_string(), "a : & Db");
+        assert!(matches!(args[0].fixture, Some(Fixture::Trait)));
+        assert!(
+            matches!(&args[1].fixture, Some(Fixture::Expr(e)) if e.to_token_stream().to_string() == "make_dir ()")
+        );
+    }
 }
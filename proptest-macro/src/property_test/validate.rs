@@ -1,8 +1,8 @@
 use proc_macro2::TokenStream;
 use quote::{quote_spanned, ToTokens};
-use syn::{spanned::Spanned, FnArg, ItemFn, Meta};
+use syn::{spanned::Spanned, FnArg, ItemFn, Meta, Type};
 
-use super::utils::is_strategy;
+use super::utils::{is_fixture, is_strategy};
 
 /// Validate an `ItemFn` for some basic sanity checks
 ///
@@ -29,7 +29,8 @@ fn all_args_non_self(f: &mut ItemFn) -> Result<(), TokenStream> {
     }
 }
 
-/// Make sure we only have `#[strategy = <expr>]` attributes on function parameters
+/// Make sure we only have `#[strategy = <expr>]` and `#[fixture]` attributes on function
+/// parameters
 fn validate_parameter_attrs(f: &mut ItemFn) -> Result<(), TokenStream> {
     let mut error = quote::quote! {};
 
@@ -38,19 +39,61 @@ fn validate_parameter_attrs(f: &mut ItemFn) -> Result<(), TokenStream> {
             unreachable!("should be impossible due to `all_args_non_self`");
         };
 
-        // add error for any non-`strategy` error or inner attributes (i.e. `#![...]` )
-        for attr in pat_ty.attrs.iter().filter(|a| !is_strategy(a)) {
+        // add error for any other attributes or inner attributes (i.e. `#![...]` )
+        for attr in pat_ty
+            .attrs
+            .iter()
+            .filter(|a| !is_strategy(a) && !is_fixture(a))
+        {
             error.extend(quote_spanned! {
-                attr.span() => compile_error!("only `#[strategy = <expr>]` attributes are allowed here");
+                attr.span() => compile_error!("only `#[strategy = <expr>]` and `#[fixture]` attributes are allowed here");
             });
         }
 
         let mut first_strategy_seen = false;
+        let mut first_fixture_seen = false;
         let mut final_attrs = Vec::with_capacity(pat_ty.attrs.len());
         let old_attrs = std::mem::take(&mut pat_ty.attrs);
+        let (fixture_attrs, old_attrs): (Vec<_>, Vec<_>) =
+            old_attrs.into_iter().partition(is_fixture);
+
+        // every fixture attr should have the form `#[fixture]` or `#[fixture = <expr>]`, on a
+        // parameter taking a shared reference, which is passed to every case
+        for attr in fixture_attrs {
+            if first_fixture_seen {
+                let pat = pat_ty.pat.clone().into_token_stream().to_string();
+                let message =
+                    format!("{pat} has duplicate `#[fixture]` attribute");
+                error.extend(quote_spanned! {
+                    attr.span() => compile_error!(#message);
+                });
+                continue;
+            }
+            first_fixture_seen = true;
+
+            if matches!(attr.meta, Meta::List(_)) {
+                error.extend(quote_spanned! {
+                    attr.meta.span() => compile_error!("`fixture` attributes must have the form `#[fixture]` or `#[fixture = <expr>]`");
+                });
+            }
+            if !matches!(pat_ty.ty.as_ref(), Type::Reference(r) if r.mutability.is_none())
+            {
+                error.extend(quote_spanned! {
+                    pat_ty.ty.span() => compile_error!("fixture parameters must be shared references, e.g. `db: &Database`");
+                });
+            }
+            final_attrs.push(attr);
+        }
 
         // every strategy attr should have the form `#[strategy = <expr>]`
         for attr in old_attrs.into_iter().filter(is_strategy) {
+            if first_fixture_seen {
+                error.extend(quote_spanned! {
+                    attr.span() => compile_error!("fixture parameters cannot have a `#[strategy = <expr>]` attribute");
+                });
+                continue;
+            }
+
             match attr.meta {
                 // a "good" strategy - if we see more than one, emit an error
                 Meta::NameValue(_) => {
@@ -126,4 +169,28 @@ mod tests {
         let error = validate(&mut function).unwrap_err();
         assert!(error.to_string().contains("compile_error"));
     }
+
+    #[test]
+    fn validate_accepts_fixtures() {
+        let mut function = parse_quote! {
+            fn foo(#[fixture] a: &Db, #[fixture = make_dir()] b: &Dir, x: i32) {}
+        };
+
+        assert!(validate(&mut function).is_ok());
+    }
+
+    #[test]
+    fn validate_fails_with_invalid_fixture() {
+        let invalids = [
+            parse_quote! {fn foo(#[fixture] a: Db) {}},
+            parse_quote! {fn foo(#[fixture] a: &mut Db) {}},
+            parse_quote! {fn foo(#[fixture(x)] a: &Db) {}},
+            parse_quote! {fn foo(#[fixture] #[fixture] a: &Db) {}},
+            parse_quote! {fn foo(#[fixture] #[strategy = 1] a: &Db) {}},
+        ];
+
+        for mut invalid in invalids {
+            assert!(validate(&mut invalid).is_err());
+        }
+    }
 }
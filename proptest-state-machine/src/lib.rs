@@ -13,7 +13,9 @@
 //! when and how to use this and how it's made.
 
 pub mod strategy;
+pub mod symbolic;
 pub mod test_runner;
 
 pub use strategy::*;
+pub use symbolic::*;
 pub use test_runner::*;
@@ -17,7 +17,7 @@ use proptest::collection::SizeRange;
 use proptest::num::sample_uniform_incl;
 use proptest::std_facade::fmt::{Debug, Formatter, Result};
 use proptest::std_facade::Vec;
-use proptest::strategy::BoxedStrategy;
+use proptest::strategy::{BoxedStrategy, Just};
 use proptest::strategy::{NewTree, Strategy, ValueTree};
 use proptest::test_runner::TestRunner;
 
@@ -110,6 +110,38 @@ pub trait ReferenceStateMachine: 'static {
             Self::apply,
         )
     }
+
+    /// A concurrent strategy generates a sequential prefix of transitions,
+    /// followed by a suffix of transitions for each of the given number of
+    /// `threads`. The prefix length is taken from `prefix` and the length of
+    /// every suffix from `suffix`. The test, implemented with the help of
+    /// [`crate::ConcurrentStateMachineTest`] trait, applies the prefix and
+    /// then the suffixes concurrently, one thread each.
+    ///
+    /// The number of interleavings to check grows very quickly with the
+    /// length of the suffixes, so these are best kept short.
+    ///
+    /// You typically never need to override this method.
+    fn concurrent_strategy(
+        prefix: impl Into<SizeRange>,
+        suffix: impl Into<SizeRange>,
+        threads: usize,
+    ) -> Concurrent<
+        Self::State,
+        Self::Transition,
+        BoxedStrategy<Self::State>,
+        BoxedStrategy<Self::Transition>,
+    > {
+        Concurrent::new(
+            prefix.into(),
+            suffix.into(),
+            threads,
+            Self::init_state,
+            Self::preconditions,
+            Self::transitions,
+            Self::apply,
+        )
+    }
 }
 
 /// In a sequential state machine strategy, we first generate an acceptable
@@ -668,6 +700,176 @@ impl<
     }
 }
 
+/// The transitions of a concurrent state machine strategy are generated as a
+/// single sequence that satisfies the pre-conditions, exactly like in the
+/// [`Sequential`] strategy, where the first transitions form the prefix and
+/// the rest are dealt to the threads in turns. This sequence is one of the
+/// interleavings of the concurrent suffixes, so there is always at least one
+/// in which all the pre-conditions hold.
+///
+/// The shrinking is that of the [`Sequential`] strategy, whose deletions and
+/// simplifications apply to the prefix and all the suffixes alike. Every
+/// transition keeps the thread it was dealt to, so deleting transitions from
+/// the suffixes also reduces the number of their possible interleavings.
+pub struct Concurrent<State, Transition, StateStrategy, TransitionStrategy> {
+    prefix: SizeRange,
+    suffix: SizeRange,
+    threads: usize,
+    init_state: Arc<dyn Fn() -> StateStrategy + Send + Sync>,
+    preconditions: Arc<dyn Fn(&State, &Transition) -> bool + Send + Sync>,
+    transitions: Arc<dyn Fn(&State) -> TransitionStrategy + Send + Sync>,
+    next: Arc<dyn Fn(State, &Transition) -> State + Send + Sync>,
+}
+
+impl<State, Transition, StateStrategy, TransitionStrategy>
+    Concurrent<State, Transition, StateStrategy, TransitionStrategy>
+where
+    State: 'static,
+    Transition: 'static,
+    StateStrategy: 'static,
+    TransitionStrategy: 'static,
+{
+    pub fn new(
+        prefix: SizeRange,
+        suffix: SizeRange,
+        threads: usize,
+        init_state: impl Fn() -> StateStrategy + 'static + Send + Sync,
+        preconditions: impl Fn(&State, &Transition) -> bool + 'static + Send + Sync,
+        transitions: impl Fn(&State) -> TransitionStrategy + 'static + Send + Sync,
+        next: impl Fn(State, &Transition) -> State + 'static + Send + Sync,
+    ) -> Self {
+        assert!(
+            threads > 0,
+            "A concurrent strategy needs at least one thread"
+        );
+        Self {
+            prefix,
+            suffix,
+            threads,
+            init_state: Arc::new(init_state),
+            preconditions: Arc::new(preconditions),
+            transitions: Arc::new(transitions),
+            next: Arc::new(next),
+        }
+    }
+}
+
+impl<State, Transition, StateStrategy, TransitionStrategy> Debug
+    for Concurrent<State, Transition, StateStrategy, TransitionStrategy>
+{
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.debug_struct("Concurrent")
+            .field("prefix", &self.prefix)
+            .field("suffix", &self.suffix)
+            .field("threads", &self.threads)
+            .finish()
+    }
+}
+
+/// The state of the sequence generated for a concurrent strategy, paired with
+/// the number of transitions generated so far.
+type Counted<State> = (State, usize);
+/// A transition of the sequence generated for a concurrent strategy, paired
+/// with the thread it's dealt to, where `0` is the prefix and `n` is the
+/// suffix of the `n`th thread.
+type Dealt<Transition> = (usize, Transition);
+
+impl<
+        State: Clone + Debug + 'static,
+        Transition: Clone + Debug + 'static,
+        StateStrategy: Strategy<Value = State> + 'static,
+        TransitionStrategy: Strategy<Value = Transition> + 'static,
+    > Strategy
+    for Concurrent<State, Transition, StateStrategy, TransitionStrategy>
+{
+    type Tree = ConcurrentValueTree<State, Transition>;
+    type Value = (State, Vec<Transition>, Vec<Vec<Transition>>);
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let (min_prefix, max_prefix) = self.prefix.start_end_incl();
+        let prefix_len = sample_uniform_incl(runner, min_prefix, max_prefix);
+        let (min_suffix, max_suffix) = self.suffix.start_end_incl();
+        let suffix_len = sample_uniform_incl(runner, min_suffix, max_suffix);
+        let len = prefix_len + self.threads * suffix_len;
+        let threads = self.threads;
+
+        let init_state = self.init_state.clone();
+        let preconditions = self.preconditions.clone();
+        let transitions = self.transitions.clone();
+        let next = self.next.clone();
+        let sequential = Sequential::new(
+            (len..=len).into(),
+            move || (init_state)().prop_map(|state| (state, 0)).boxed(),
+            move |(state, _): &Counted<State>,
+                  (_, transition): &Dealt<Transition>| {
+                (preconditions)(state, transition)
+            },
+            move |(state, generated): &Counted<State>| {
+                let thread = if *generated < prefix_len {
+                    0
+                } else {
+                    (generated - prefix_len) % threads + 1
+                };
+                (Just(thread), (transitions)(state)).boxed()
+            },
+            move |(state, generated): Counted<State>,
+                  (_, transition): &Dealt<Transition>| {
+                ((next)(state, transition), generated + 1)
+            },
+        );
+
+        let mut sequential = sequential.new_tree(runner)?;
+        // All the transitions are always applied, so none can be deleted for
+        // being unseen
+        sequential.seen_transitions_counter = None;
+
+        Ok(ConcurrentValueTree {
+            sequential,
+            threads,
+        })
+    }
+}
+
+/// The generated value tree for a concurrent state machine.
+pub struct ConcurrentValueTree<State, Transition> {
+    /// The value tree of all the transitions in the order they were generated
+    sequential: SequentialValueTree<
+        Counted<State>,
+        Dealt<Transition>,
+        Box<dyn ValueTree<Value = Counted<State>>>,
+        Box<dyn ValueTree<Value = Dealt<Transition>>>,
+    >,
+    /// The number of concurrent suffixes
+    threads: usize,
+}
+
+impl<State: Clone + Debug, Transition: Clone + Debug> ValueTree
+    for ConcurrentValueTree<State, Transition>
+{
+    type Value = (State, Vec<Transition>, Vec<Vec<Transition>>);
+
+    fn current(&self) -> Self::Value {
+        let ((initial_state, _), transitions, _) = self.sequential.current();
+        let mut prefix = Vec::new();
+        let mut suffixes = vec![Vec::new(); self.threads];
+        for (thread, transition) in transitions {
+            match thread {
+                0 => prefix.push(transition),
+                n => suffixes[n - 1].push(transition),
+            }
+        }
+        (initial_state, prefix, suffixes)
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.sequential.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.sequential.complicate()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,441 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Symbolic variables, which let transitions refer to the results of earlier
+//! transitions.
+//!
+//! Many systems hand out identifiers or handles (file descriptors, database
+//! keys, connection objects, ...) which later operations take as arguments.
+//! The reference state machine cannot know the concrete values, since they
+//! only exist once the transitions are applied to the SUT. Instead, a
+//! transition which produces such a value is given a fresh [`Var`] from the
+//! reference state's [`Vars`], and later transitions refer to the value
+//! through that var. The SUT binds each var to the concrete value in an
+//! [`Env`] as it applies the transitions, and looks it up from there.
+//!
+//! Each var is identified by the transition which defines it rather than by
+//! its position, so deleting transitions while shrinking never makes a var
+//! refer to a different value. A reference to a var whose defining transition
+//! has been deleted is caught by checking [`Vars::is_defined`] in
+//! [`crate::ReferenceStateMachine::preconditions`], which makes shrinking
+//! keep the defining transition.
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use proptest_state_machine::{Env, ReferenceStateMachine, Var, Vars};
+//!
+//! struct Handle;
+//!
+//! #[derive(Clone, Debug, Default)]
+//! struct Model {
+//!     vars: Vars,
+//!     open: Vec<Var<Handle>>,
+//! }
+//!
+//! #[derive(Clone, Debug)]
+//! enum Transition {
+//!     Open(Var<Handle>),
+//!     Close(Var<Handle>),
+//! }
+//!
+//! struct FileSystem;
+//!
+//! impl ReferenceStateMachine for FileSystem {
+//!     type State = Model;
+//!     type Transition = Transition;
+//!
+//!     fn init_state() -> BoxedStrategy<Model> {
+//!         Just(Model::default()).boxed()
+//!     }
+//!
+//!     fn transitions(state: &Model) -> BoxedStrategy<Transition> {
+//!         let open = Just(Transition::Open(state.vars.fresh()));
+//!         if state.open.is_empty() {
+//!             open.boxed()
+//!         } else {
+//!             prop_oneof![
+//!                 open,
+//!                 proptest::sample::select(state.open.clone())
+//!                     .prop_map(Transition::Close),
+//!             ]
+//!             .boxed()
+//!         }
+//!     }
+//!
+//!     fn apply(mut state: Model, transition: &Transition) -> Model {
+//!         match *transition {
+//!             Transition::Open(handle) => {
+//!                 state.vars.define(handle);
+//!                 state.open.push(handle);
+//!             }
+//!             Transition::Close(handle) => {
+//!                 state.open.retain(|&h| h != handle);
+//!             }
+//!         }
+//!         state
+//!     }
+//!
+//!     fn preconditions(state: &Model, transition: &Transition) -> bool {
+//!         match *transition {
+//!             Transition::Open(handle) => !state.vars.is_defined(&handle),
+//!             Transition::Close(handle) => state.open.contains(&handle),
+//!         }
+//!     }
+//! }
+//!
+//! // Meanwhile, `StateMachineTest::apply` keeps an `Env` in the SUT state:
+//! let mut env = Env::new();
+//! let handle = Model::default().vars.fresh::<u32>();
+//! env.bind(handle, 42);
+//! assert_eq!(42, env[&handle]);
+//! ```
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::Index;
+
+/// A symbolic reference to a value of type `T` produced by the transition
+/// which defined it.
+///
+/// Vars are cheap to copy and compare, and are shown as `Var(n)` in the
+/// transitions of a failing test.
+pub struct Var<T> {
+    id: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Var<T> {
+    /// The number identifying this var, unique within a test case.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+}
+
+impl<T> Clone for Var<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Var<T> {}
+
+impl<T> fmt::Debug for Var<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Var({})", self.id)
+    }
+}
+
+impl<T> PartialEq for Var<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for Var<T> {}
+
+impl<T> PartialOrd for Var<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Var<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<T> Hash for Var<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+/// Allocates and tracks the vars of a reference state.
+///
+/// Keep one of these in the reference state. Use `fresh` in
+/// [`crate::ReferenceStateMachine::transitions`] to give a transition the var
+/// its result will be bound to, and `define` in
+/// [`crate::ReferenceStateMachine::apply`] once that transition is applied.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vars {
+    next: usize,
+    defined: BTreeSet<usize>,
+}
+
+impl Vars {
+    /// Create an empty set of vars.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return a var which has not been defined yet.
+    ///
+    /// This does not change the state, so calling it again returns the same
+    /// var until it is passed to `define`.
+    pub fn fresh<T>(&self) -> Var<T> {
+        Var {
+            id: self.next,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Record that the transition defining `var` has been applied.
+    pub fn define<T>(&mut self, var: Var<T>) {
+        self.defined.insert(var.id);
+        self.next = self.next.max(var.id + 1);
+    }
+
+    /// Record that `var` may no longer be referred to, for example because
+    /// the value it refers to has been destroyed.
+    pub fn undefine<T>(&mut self, var: &Var<T>) {
+        self.defined.remove(&var.id);
+    }
+
+    /// Return whether `var` has been defined (and not undefined since).
+    pub fn is_defined<T>(&self, var: &Var<T>) -> bool {
+        self.defined.contains(&var.id)
+    }
+}
+
+/// The concrete values of vars, kept alongside the SUT.
+///
+/// Values of any type may be bound, and are looked up by the type of the var.
+#[derive(Default)]
+pub struct Env {
+    values: BTreeMap<usize, Box<dyn Any>>,
+}
+
+impl Env {
+    /// Create an empty environment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind `var` to `value`, replacing any previous value.
+    pub fn bind<T: 'static>(&mut self, var: Var<T>, value: T) {
+        self.values.insert(var.id, Box::new(value));
+    }
+
+    /// Return the value bound to `var`, if any.
+    pub fn get<T: 'static>(&self, var: &Var<T>) -> Option<&T> {
+        self.values.get(&var.id).and_then(|value| value.downcast_ref())
+    }
+
+    /// Return a mutable reference to the value bound to `var`, if any.
+    pub fn get_mut<T: 'static>(&mut self, var: &Var<T>) -> Option<&mut T> {
+        self.values
+            .get_mut(&var.id)
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// Remove the binding of `var`, returning its value, if any.
+    pub fn remove<T: 'static>(&mut self, var: &Var<T>) -> Option<T> {
+        let value = self.values.remove(&var.id)?;
+        value.downcast().ok().map(|value| *value)
+    }
+}
+
+impl fmt::Debug for Env {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.values.keys()).finish()
+    }
+}
+
+/// Look up the value bound to a var.
+///
+/// # Panics
+///
+/// Panics if `var` is not bound, which indicates that the preconditions of
+/// the reference state machine let through a transition referring to a var
+/// which has not been defined.
+impl<T: 'static> Index<&Var<T>> for Env {
+    type Output = T;
+
+    fn index(&self, var: &Var<T>) -> &T {
+        self.get(var)
+            .unwrap_or_else(|| panic!("{:?} is not bound in the environment", var))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use proptest::prelude::*;
+    use proptest::test_runner::{
+        Config, RngAlgorithm, TestError, TestRng, TestRunner,
+    };
+
+    use crate::{ReferenceStateMachine, StateMachineTest};
+
+    #[test]
+    fn vars_are_allocated_and_tracked() {
+        let mut vars = Vars::new();
+        let a = vars.fresh::<u32>();
+        assert_eq!(a, vars.fresh::<u32>());
+        assert!(!vars.is_defined(&a));
+
+        vars.define(a);
+        let b = vars.fresh::<u32>();
+        assert_ne!(a, b);
+        assert!(vars.is_defined(&a));
+
+        vars.undefine(&a);
+        assert!(!vars.is_defined(&a));
+        assert_ne!(a, vars.fresh::<u32>());
+    }
+
+    #[test]
+    fn env_binds_values_of_any_type() {
+        let mut vars = Vars::new();
+        let number = vars.fresh::<u32>();
+        vars.define(number);
+        let name = vars.fresh::<String>();
+
+        let mut env = Env::new();
+        env.bind(number, 7);
+        env.bind(name, "seven".to_owned());
+        *env.get_mut(&number).unwrap() += 1;
+
+        assert_eq!(8, env[&number]);
+        assert_eq!("seven", env[&name]);
+        assert_eq!(Some("seven".to_owned()), env.remove(&name));
+        assert_eq!(None, env.get(&name));
+    }
+
+    /// A key-value store handing out keys for the values inserted, which
+    /// loses values once more than two are stored.
+    struct Store;
+
+    #[derive(Clone, Debug, Default)]
+    struct StoreModel {
+        vars: Vars,
+        keys: Vec<Var<usize>>,
+    }
+
+    #[derive(Clone, Debug)]
+    enum StoreTransition {
+        Insert(Var<usize>, u8),
+        Get(Var<usize>),
+    }
+
+    impl ReferenceStateMachine for Store {
+        type State = StoreModel;
+        type Transition = StoreTransition;
+
+        fn init_state() -> BoxedStrategy<StoreModel> {
+            Just(StoreModel::default()).boxed()
+        }
+
+        fn transitions(state: &StoreModel) -> BoxedStrategy<StoreTransition> {
+            let insert = any::<u8>()
+                .prop_map({
+                    let key = state.vars.fresh();
+                    move |value| StoreTransition::Insert(key, value)
+                })
+                .boxed();
+            if state.keys.is_empty() {
+                insert
+            } else {
+                prop_oneof![
+                    insert,
+                    proptest::sample::select(state.keys.clone())
+                        .prop_map(StoreTransition::Get),
+                ]
+                .boxed()
+            }
+        }
+
+        fn apply(
+            mut state: StoreModel,
+            transition: &StoreTransition,
+        ) -> StoreModel {
+            if let StoreTransition::Insert(key, _) = *transition {
+                state.vars.define(key);
+                state.keys.push(key);
+            }
+            state
+        }
+
+        fn preconditions(
+            state: &StoreModel,
+            transition: &StoreTransition,
+        ) -> bool {
+            match *transition {
+                StoreTransition::Insert(key, _) => !state.vars.is_defined(&key),
+                StoreTransition::Get(key) => state.vars.is_defined(&key),
+            }
+        }
+    }
+
+    impl StateMachineTest for Store {
+        type SystemUnderTest = (Vec<u8>, Env, Vec<(Var<usize>, u8)>);
+        type Reference = Self;
+
+        fn init_test(_: &StoreModel) -> Self::SystemUnderTest {
+            (Vec::new(), Env::new(), Vec::new())
+        }
+
+        fn apply(
+            (mut store, mut env, mut expected): Self::SystemUnderTest,
+            _: &StoreModel,
+            transition: StoreTransition,
+        ) -> Self::SystemUnderTest {
+            match transition {
+                StoreTransition::Insert(var, value) => {
+                    if store.len() == 2 {
+                        store.clear();
+                    }
+                    store.push(value);
+                    env.bind(var, store.len() - 1);
+                    expected.push((var, value));
+                }
+                StoreTransition::Get(var) => {
+                    let value = expected.iter().find(|(v, _)| *v == var);
+                    assert_eq!(store.get(env[&var]), value.map(|(_, x)| x));
+                }
+            }
+            (store, env, expected)
+        }
+    }
+
+    #[test]
+    fn shrinking_keeps_vars_defined() {
+        let mut runner = TestRunner::new_with_rng(
+            Config {
+                failure_persistence: None,
+                ..Config::default()
+            },
+            TestRng::deterministic_rng(RngAlgorithm::ChaCha),
+        );
+        let result = runner.run(&Store::sequential_strategy(1..30), |case| {
+            let (state, transitions, seen) = case;
+            Store::test_sequential(Config::default(), state, transitions, seen);
+            Ok(())
+        });
+
+        let transitions = match result {
+            Err(TestError::Fail(_, (_, transitions, _))) => transitions,
+            e => panic!("Unexpected result: {:?}", e),
+        };
+        // Three inserts are needed to lose a value, and a get to notice.
+        assert!(transitions.len() >= 4, "{:?}", transitions);
+        assert!(matches!(transitions.last(), Some(StoreTransition::Get(_))));
+        let mut model = StoreModel::default();
+        for transition in &transitions {
+            assert!(Store::preconditions(&model, transition));
+            model = <Store as ReferenceStateMachine>::apply(model, transition);
+        }
+    }
+}
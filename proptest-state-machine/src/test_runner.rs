@@ -9,8 +9,11 @@
 
 //! Test declaration helpers and runners for abstract state machine testing.
 
+use std::fmt::Debug;
+use std::panic;
 use std::sync::atomic::{self, AtomicUsize};
 use std::sync::Arc;
+use std::thread;
 
 use crate::strategy::ReferenceStateMachine;
 use proptest::test_runner::Config;
@@ -136,13 +139,231 @@ pub trait StateMachineTest {
     }
 }
 
+/// State machine test that applies transitions to a SUT shared by several
+/// threads and checks that their results are linearizable, i.e. that they
+/// can be explained by applying the same transitions one at a time to the
+/// reference state machine, in some interleaving that keeps the order of
+/// every thread's transitions.
+///
+/// Whether a data race shows up depends on how the threads happen to be
+/// scheduled, so a failure might not be found on every run of a test case,
+/// nor on every run while shrinking it.
+pub trait ConcurrentStateMachineTest {
+    /// The concrete state, that is the system under test (SUT). It is
+    /// shared by reference between the threads.
+    type SystemUnderTest: Sync;
+
+    /// The abstract state machine that implements [`ReferenceStateMachine`]
+    /// drives the generation of the state machine's transitions.
+    type Reference: ReferenceStateMachine;
+
+    /// The observable result of applying a transition to the SUT.
+    type Output: Debug + Send;
+
+    /// Initialize the state of SUT.
+    ///
+    /// If the reference state machine is generated from a non-constant
+    /// strategy, ensure to use it to initialize the SUT to a corresponding
+    /// state.
+    fn init_test(
+        ref_state: &<Self::Reference as ReferenceStateMachine>::State,
+    ) -> Self::SystemUnderTest;
+
+    /// Apply a transition in the SUT state, returning its result.
+    ///
+    /// This may be called from several threads at once.
+    fn apply(
+        state: &Self::SystemUnderTest,
+        transition: <Self::Reference as ReferenceStateMachine>::Transition,
+    ) -> Self::Output;
+
+    /// Check that the `output` of a transition is the one expected by the
+    /// reference state machine.
+    ///
+    /// Note that unlike in [`StateMachineTest::apply`], the `ref_state` is
+    /// the state *before* this `transition` is applied. This is called while
+    /// searching for a valid interleaving, so it must not panic when the
+    /// check fails.
+    fn postconditions(
+        ref_state: &<Self::Reference as ReferenceStateMachine>::State,
+        transition: &<Self::Reference as ReferenceStateMachine>::Transition,
+        output: &Self::Output,
+    ) -> bool;
+
+    /// Override this function to add some teardown logic on the SUT state
+    /// at the end of each test case. The default implementation simply drops
+    /// the state. The `ref_state` is the one reached by the interleaving that
+    /// was found to explain the results.
+    fn teardown(
+        state: Self::SystemUnderTest,
+        ref_state: <Self::Reference as ReferenceStateMachine>::State,
+    ) {
+        // This is to avoid `unused_variables` warning
+        let _ = state;
+        let _ = ref_state;
+    }
+
+    /// Run the test by applying the `prefix` sequentially and then each of the
+    /// `suffixes` on its own thread. You typically don't need to override
+    /// this method.
+    fn test_concurrent(
+        config: Config,
+        mut ref_state: <Self::Reference as ReferenceStateMachine>::State,
+        prefix: Vec<<Self::Reference as ReferenceStateMachine>::Transition>,
+        suffixes: Vec<
+            Vec<<Self::Reference as ReferenceStateMachine>::Transition>,
+        >,
+    ) where
+        <Self::Reference as ReferenceStateMachine>::Transition: Send,
+    {
+        #[cfg(feature = "std")]
+        use proptest::test_runner::INFO_LOG;
+
+        #[cfg(feature = "std")]
+        if config.verbose >= INFO_LOG {
+            eprintln!();
+            eprintln!(
+                "Running a test case with {} transitions in the prefix and \
+                 {:?} in the concurrent suffixes.",
+                prefix.len(),
+                suffixes.iter().map(Vec::len).collect::<Vec<_>>()
+            );
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = config;
+
+        let concrete_state = Self::init_test(&ref_state);
+
+        for transition in prefix {
+            let output = Self::apply(&concrete_state, transition.clone());
+            assert!(
+                Self::postconditions(&ref_state, &transition, &output),
+                "Post-conditions failed for {:?} in the sequential prefix, \
+                 with output {:?}",
+                transition,
+                output
+            );
+            ref_state = <Self::Reference as ReferenceStateMachine>::apply(
+                ref_state,
+                &transition,
+            );
+        }
+
+        let histories: Vec<Vec<_>> = thread::scope(|scope| {
+            let concrete_state = &concrete_state;
+            let handles: Vec<_> = suffixes
+                .into_iter()
+                .map(|suffix| {
+                    scope.spawn(move || {
+                        suffix
+                            .into_iter()
+                            .map(|transition| {
+                                let output = Self::apply(
+                                    concrete_state,
+                                    transition.clone(),
+                                );
+                                (transition, output)
+                            })
+                            .collect()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| match handle.join() {
+                    Ok(history) => history,
+                    Err(panic) => panic::resume_unwind(panic),
+                })
+                .collect()
+        });
+
+        match linearize::<Self>(ref_state, &histories) {
+            Some(ref_state) => Self::teardown(concrete_state, ref_state),
+            None => {
+                let mut msg = String::from(
+                    "The results of the concurrent suffixes are not \
+                     linearizable:",
+                );
+                for (ix, history) in histories.iter().enumerate() {
+                    msg.push_str(&format!("\nthread {}:", ix));
+                    for (transition, output) in history {
+                        msg.push_str(&format!(
+                            "\n    {:?} => {:?}",
+                            transition, output
+                        ));
+                    }
+                }
+                panic!("{}", msg);
+            }
+        }
+    }
+}
+
+/// The transitions applied by a thread, in order, with their outputs.
+type History<T> = Vec<(
+    <<T as ConcurrentStateMachineTest>::Reference as ReferenceStateMachine>::Transition,
+    <T as ConcurrentStateMachineTest>::Output,
+)>;
+
+/// Search for an interleaving of the threads' `histories` of transitions and
+/// their outputs in which all the pre-conditions and post-conditions hold,
+/// starting from `ref_state`. Returns the state reached by the first such
+/// interleaving, if any.
+fn linearize<T: ConcurrentStateMachineTest + ?Sized>(
+    ref_state: <T::Reference as ReferenceStateMachine>::State,
+    histories: &[History<T>],
+) -> Option<<T::Reference as ReferenceStateMachine>::State> {
+    fn search<T: ConcurrentStateMachineTest + ?Sized>(
+        ref_state: &<T::Reference as ReferenceStateMachine>::State,
+        histories: &[History<T>],
+        applied: &mut [usize],
+    ) -> Option<<T::Reference as ReferenceStateMachine>::State> {
+        if applied
+            .iter()
+            .zip(histories)
+            .all(|(applied, history)| *applied == history.len())
+        {
+            return Some(ref_state.clone());
+        }
+        for thread in 0..histories.len() {
+            let Some((transition, output)) =
+                histories[thread].get(applied[thread])
+            else {
+                continue;
+            };
+            if <T::Reference as ReferenceStateMachine>::preconditions(
+                ref_state, transition,
+            ) && T::postconditions(ref_state, transition, output)
+            {
+                let next = <T::Reference as ReferenceStateMachine>::apply(
+                    ref_state.clone(),
+                    transition,
+                );
+                applied[thread] += 1;
+                let found = search::<T>(&next, histories, applied);
+                applied[thread] -= 1;
+                if found.is_some() {
+                    return found;
+                }
+            }
+        }
+        None
+    }
+
+    search::<T>(&ref_state, histories, &mut vec![0; histories.len()])
+}
+
 /// This macro helps to turn a state machine test implementation into a runnable
 /// test. The macro expects a function header whose arguments follow a special
 /// syntax rules: First, we declare if we want to apply the state machine
-/// transitions sequentially or concurrently (currently, only the `sequential`
-/// is supported). Next, we give a range of how many transitions to generate,
-/// followed by `=>` and finally, an identifier that must implement
-/// `StateMachineTest`.
+/// transitions `sequential`ly or `concurrent`ly. For `sequential`, we next give
+/// a range of how many transitions to generate, followed by `=>` and finally,
+/// an identifier that must implement `StateMachineTest`. For `concurrent`, we
+/// instead give the ranges of the lengths of the sequential prefix and of
+/// each concurrent suffix and the number of threads, separated by commas,
+/// followed by `=>` and an identifier that must implement
+/// `ConcurrentStateMachineTest`. A single invocation declares tests of only
+/// one of the two kinds.
 ///
 /// ## Example
 ///
@@ -173,6 +394,20 @@ pub trait StateMachineTest {
 ///     }
 /// }
 /// ```
+///
+/// A concurrent test with a prefix of up to 4 transitions, followed by 2
+/// threads with 1 to 3 transitions each, is declared as:
+///
+/// ```rust,ignore
+/// struct MyConcurrentTest;
+///
+/// impl ConcurrentStateMachineTest for MyConcurrentTest {}
+///
+/// prop_state_machine! {
+///     #[test]
+///     fn run_concurrently(concurrent 0..5, 1..4, 2 => MyConcurrentTest);
+/// }
+/// ```
 #[macro_export]
 macro_rules! prop_state_machine {
     // With proptest config annotation
@@ -213,6 +448,45 @@ macro_rules! prop_state_machine {
             }
         )*
     };
+
+    // Concurrent, with proptest config annotation
+    (#![proptest_config($config:expr)]
+    $(
+        $(#[$meta:meta])*
+        fn $test_name:ident(concurrent $prefix:expr, $suffix:expr, $threads:expr => $test:ident $(< $( $ty_param:tt ),+ >)?);
+    )*) => {
+        $(
+            ::proptest::proptest! {
+                #![proptest_config($config)]
+                $(#[$meta])*
+                fn $test_name(
+                    (initial_state, prefix, suffixes) in <<$test $(< $( $ty_param ),+ >)? as $crate::ConcurrentStateMachineTest>::Reference as $crate::ReferenceStateMachine>::concurrent_strategy($prefix, $suffix, $threads)
+                ) {
+
+                    let config = $config.__sugar_to_owned();
+                    <$test $(::< $( $ty_param ),+ >)? as $crate::ConcurrentStateMachineTest>::test_concurrent(config, initial_state, prefix, suffixes)
+                }
+            }
+        )*
+    };
+
+    // Concurrent, without proptest config annotation
+    ($(
+        $(#[$meta:meta])*
+        fn $test_name:ident(concurrent $prefix:expr, $suffix:expr, $threads:expr => $test:ident $(< $( $ty_param:tt ),+ >)?);
+    )*) => {
+        $(
+            ::proptest::proptest! {
+                $(#[$meta])*
+                fn $test_name(
+                    (initial_state, prefix, suffixes) in <<$test $(< $( $ty_param ),+ >)? as $crate::ConcurrentStateMachineTest>::Reference as $crate::ReferenceStateMachine>::concurrent_strategy($prefix, $suffix, $threads)
+                ) {
+                    <$test $(::< $( $ty_param ),+ >)? as $crate::ConcurrentStateMachineTest>::test_concurrent(
+                        ::proptest::test_runner::Config::default(), initial_state, prefix, suffixes)
+                }
+            }
+        )*
+    };
 }
 
 #[cfg(test)]
@@ -284,4 +558,173 @@ mod tests {
             fn with_config_annotation(sequential 1..2 => Test);
         }
     }
+
+    mod concurrent {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::thread;
+        use std::time::Duration;
+
+        use proptest::prelude::*;
+        use proptest::test_runner::{Config, TestError, TestRunner};
+
+        use crate::test_runner::linearize;
+        use crate::{ConcurrentStateMachineTest, ReferenceStateMachine};
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Transition {
+            Increment,
+            Get,
+        }
+
+        /// A counter, whose `Increment` returns the incremented value.
+        struct Counter;
+
+        impl ReferenceStateMachine for Counter {
+            type State = u64;
+            type Transition = Transition;
+
+            fn init_state() -> BoxedStrategy<Self::State> {
+                Just(0).boxed()
+            }
+
+            fn transitions(_: &Self::State) -> BoxedStrategy<Self::Transition> {
+                prop_oneof![Just(Transition::Increment), Just(Transition::Get)]
+                    .boxed()
+            }
+
+            fn apply(state: Self::State, transition: &Transition) -> u64 {
+                match transition {
+                    Transition::Increment => state + 1,
+                    Transition::Get => state,
+                }
+            }
+        }
+
+        fn postconditions(
+            state: &u64,
+            transition: &Transition,
+            output: &u64,
+        ) -> bool {
+            *output == Counter::apply(*state, transition)
+        }
+
+        struct AtomicCounter;
+
+        impl ConcurrentStateMachineTest for AtomicCounter {
+            type SystemUnderTest = AtomicU64;
+            type Reference = Counter;
+            type Output = u64;
+
+            fn init_test(ref_state: &u64) -> AtomicU64 {
+                AtomicU64::new(*ref_state)
+            }
+
+            fn apply(state: &AtomicU64, transition: Transition) -> u64 {
+                match transition {
+                    Transition::Increment => {
+                        state.fetch_add(1, Ordering::SeqCst) + 1
+                    }
+                    Transition::Get => state.load(Ordering::SeqCst),
+                }
+            }
+
+            fn postconditions(
+                state: &u64,
+                transition: &Transition,
+                output: &u64,
+            ) -> bool {
+                postconditions(state, transition, output)
+            }
+        }
+
+        /// Loses updates when incremented from several threads at once.
+        struct RacyCounter;
+
+        impl ConcurrentStateMachineTest for RacyCounter {
+            type SystemUnderTest = AtomicU64;
+            type Reference = Counter;
+            type Output = u64;
+
+            fn init_test(ref_state: &u64) -> AtomicU64 {
+                AtomicU64::new(*ref_state)
+            }
+
+            fn apply(state: &AtomicU64, transition: Transition) -> u64 {
+                match transition {
+                    Transition::Increment => {
+                        let value = state.load(Ordering::SeqCst) + 1;
+                        thread::sleep(Duration::from_millis(10));
+                        state.store(value, Ordering::SeqCst);
+                        value
+                    }
+                    Transition::Get => state.load(Ordering::SeqCst),
+                }
+            }
+
+            fn postconditions(
+                state: &u64,
+                transition: &Transition,
+                output: &u64,
+            ) -> bool {
+                postconditions(state, transition, output)
+            }
+        }
+
+        #[test]
+        fn linearize_finds_an_interleaving() {
+            use Transition::*;
+
+            let histories =
+                vec![vec![(Increment, 1)], vec![(Get, 1), (Increment, 2)]];
+            assert_eq!(linearize::<AtomicCounter>(0, &histories), Some(2));
+
+            let histories =
+                vec![vec![(Get, 0), (Increment, 2)], vec![(Increment, 1)]];
+            assert_eq!(linearize::<AtomicCounter>(0, &histories), Some(2));
+
+            let histories = vec![vec![(Increment, 1)], vec![(Increment, 1)]];
+            assert_eq!(linearize::<AtomicCounter>(0, &histories), None);
+        }
+
+        #[test]
+        fn racy_counter_shrinks_to_two_concurrent_increments() {
+            let mut runner = TestRunner::new(Config {
+                failure_persistence: None,
+                ..Config::default()
+            });
+            let result = runner.run(
+                &Counter::concurrent_strategy(0..3, 1..4, 2),
+                |(initial_state, prefix, suffixes)| {
+                    RacyCounter::test_concurrent(
+                        Config::default(),
+                        initial_state,
+                        prefix,
+                        suffixes,
+                    );
+                    Ok(())
+                },
+            );
+
+            match result {
+                Err(TestError::Fail(_, (_, prefix, suffixes))) => {
+                    assert_eq!(prefix, vec![]);
+                    assert_eq!(suffixes, vec![vec![Transition::Increment]; 2]);
+                }
+                result => panic!("Unexpected result: {:?}", result),
+            }
+        }
+
+        prop_state_machine! {
+            #![proptest_config(Config {
+                cases: 32,
+                failure_persistence: None,
+                ..Config::default()
+            })]
+
+            #[test]
+            fn atomic_counter_is_linearizable(
+                concurrent 0..3, 1..4, 3 => AtomicCounter
+            );
+        }
+    }
 }
@@ -7,7 +7,6 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use core::convert::TryFrom;
 #[cfg(not(target_arch = "wasm32"))]
 use core::num::{NonZeroI128, NonZeroU128};
 use core::num::{
@@ -15,20 +14,17 @@ use core::num::{
     NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
 };
 
-use crate::arbitrary::{any, Arbitrary, StrategyFor};
-use crate::strategy::{FilterMap, Strategy};
+use crate::arbitrary::Arbitrary;
+use crate::num;
 
 macro_rules! non_zero_impl {
-    ($nz:ty, $prim:ty) => {
+    ($nz:ty, $prim:ident) => {
         impl Arbitrary for $nz {
             type Parameters = ();
-            type Strategy =
-                FilterMap<StrategyFor<$prim>, fn($prim) -> Option<Self>>;
+            type Strategy = num::$prim::NonZeroAny;
 
             fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
-                any::<$prim>().prop_filter_map("must be non zero", |i| {
-                    Self::try_from(i).ok()
-                })
+                num::$prim::NON_ZERO
             }
         }
     };
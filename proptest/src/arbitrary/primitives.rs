@@ -26,6 +26,18 @@ arbitrary!(f64, f64::Any; {
     f64::POSITIVE | f64::NEGATIVE | f64::ZERO | f64::SUBNORMAL | f64::NORMAL
 });
 
+#[cfg(feature = "half")]
+arbitrary!(::half::f16, crate::num::half::f16::Any; {
+    use crate::num::half::f16;
+    f16::POSITIVE | f16::NEGATIVE | f16::ZERO | f16::SUBNORMAL | f16::NORMAL
+});
+#[cfg(feature = "half")]
+arbitrary!(::half::bf16, crate::num::half::bf16::Any; {
+    use crate::num::half::bf16;
+    bf16::POSITIVE | bf16::NEGATIVE | bf16::ZERO | bf16::SUBNORMAL |
+        bf16::NORMAL
+});
+
 arbitrary!(char, char::CharStrategy<'static>; char::any());
 
 #[cfg(test)]
@@ -38,4 +50,7 @@ mod test {
         i8 => i8, i16 => i16, i32 => i32, i64 => i64, i128 => i128,
         u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128
     );
+
+    #[cfg(feature = "half")]
+    no_panic_test!(half_f16 => ::half::f16, half_bf16 => ::half::bf16);
 }
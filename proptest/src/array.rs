@@ -128,6 +128,13 @@ impl<S: Strategy, const N: usize> Strategy for [S; N] {
             last_shrinker: None,
         })
     }
+
+    fn describe(&self) -> Description {
+        self.iter()
+            .fold(Description::new("array"), |description, s| {
+                description.child(s.describe())
+            })
+    }
 }
 impl<S: Strategy, const N: usize> Strategy
     for UniformArrayStrategy<S, [S::Value; N]>
@@ -144,6 +151,12 @@ impl<S: Strategy, const N: usize> Strategy
             last_shrinker: None,
         })
     }
+
+    fn describe(&self) -> Description {
+        Description::new("uniform")
+            .param("size", N)
+            .child(self.strategy.describe())
+    }
 }
 impl<T: ValueTree, const N: usize> ValueTree for ArrayValueTree<[T; N]> {
     type Value = [T::Value; N];
@@ -20,6 +20,10 @@ use crate::std_facade::{fmt, Vec};
 use core::marker::PhantomData;
 use core::mem;
 
+#[cfg(feature = "fixedbitset")]
+use ::fixedbitset::FixedBitSet;
+#[cfg(feature = "roaring")]
+use ::roaring::RoaringBitmap;
 #[cfg(feature = "bit-set")]
 use bit_set::BitSet;
 #[cfg(feature = "bit-set")]
@@ -28,6 +32,7 @@ use rand::{self, seq::IteratorRandom, Rng};
 
 use crate::collection::SizeRange;
 use crate::num::sample_uniform_incl;
+use crate::option::Probability;
 use crate::strategy::*;
 use crate::test_runner::*;
 
@@ -98,6 +103,43 @@ int_bitset!(i64);
 int_bitset!(i128);
 int_bitset!(isize);
 
+macro_rules! array_bitset {
+    ($typ:ty) => {
+        /// Bit `ix` is stored in element `ix / BITS`, counting from the
+        /// least significant bit of each element.
+        impl<const N: usize> BitSetLike for [$typ; N] {
+            fn new_bitset(_: usize) -> Self {
+                [0; N]
+            }
+            fn len(&self) -> usize {
+                N * <$typ>::BITS as usize
+            }
+            fn test(&self, ix: usize) -> bool {
+                let bits = <$typ>::BITS as usize;
+                self.get(ix / bits).is_some_and(|word| word.test(ix % bits))
+            }
+            fn set(&mut self, ix: usize) {
+                let bits = <$typ>::BITS as usize;
+                self[ix / bits].set(ix % bits);
+            }
+            fn clear(&mut self, ix: usize) {
+                let bits = <$typ>::BITS as usize;
+                if let Some(word) = self.get_mut(ix / bits) {
+                    word.clear(ix % bits);
+                }
+            }
+            fn count(&self) -> usize {
+                self.iter().map(|word| word.count_ones() as usize).sum()
+            }
+        }
+    };
+}
+array_bitset!(u8);
+array_bitset!(u16);
+array_bitset!(u32);
+array_bitset!(u64);
+array_bitset!(u128);
+
 #[cfg(feature = "bit-set")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bit-set")))]
 impl BitSetLike for BitSet {
@@ -126,6 +168,70 @@ impl BitSetLike for BitSet {
     }
 }
 
+#[cfg(feature = "fixedbitset")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fixedbitset")))]
+impl BitSetLike for FixedBitSet {
+    fn new_bitset(max: usize) -> Self {
+        FixedBitSet::with_capacity(max)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn test(&self, bit: usize) -> bool {
+        self.contains(bit)
+    }
+
+    fn set(&mut self, bit: usize) {
+        self.grow(bit + 1);
+        self.insert(bit);
+    }
+
+    fn clear(&mut self, bit: usize) {
+        if bit < self.len() {
+            self.set(bit, false);
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.count_ones(..)
+    }
+}
+
+#[cfg(feature = "roaring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "roaring")))]
+impl BitSetLike for RoaringBitmap {
+    /// Roaring bitmaps are sparse, so no space is reserved up front.
+    fn new_bitset(_: usize) -> Self {
+        RoaringBitmap::new()
+    }
+
+    fn len(&self) -> usize {
+        self.max().map_or(0, |max| max as usize + 1)
+    }
+
+    fn test(&self, bit: usize) -> bool {
+        u32::try_from(bit).is_ok_and(|bit| self.contains(bit))
+    }
+
+    fn set(&mut self, bit: usize) {
+        let bit = u32::try_from(bit)
+            .expect("RoaringBitmap can only hold bits below 2^32");
+        self.insert(bit);
+    }
+
+    fn clear(&mut self, bit: usize) {
+        if let Ok(bit) = u32::try_from(bit) {
+            self.remove(bit);
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.len() as usize
+    }
+}
+
 impl BitSetLike for Vec<bool> {
     fn new_bitset(max: usize) -> Self {
         vec![false; max]
@@ -165,13 +271,18 @@ impl BitSetLike for Vec<bool> {
 /// Generates values as a set of bits between the two bounds.
 ///
 /// Values are generated by uniformly setting individual bits to 0
-/// or 1 between the bounds. Shrinking iteratively clears bits.
+/// or 1 between the bounds, unless a different density has been requested
+/// with [`with_density`](Self::with_density). Shrinking iteratively clears
+/// bits, or flips them toward a target given with
+/// [`shrink_toward`](Self::shrink_toward).
 #[must_use = "strategies do nothing unless used"]
 #[derive(Clone, Copy, Debug)]
 pub struct BitSetStrategy<T: BitSetLike> {
     min: usize,
     max: usize,
     mask: Option<T>,
+    density: Option<Probability>,
+    target: Option<T>,
 }
 
 impl<T: BitSetLike> BitSetStrategy<T> {
@@ -185,6 +296,8 @@ impl<T: BitSetLike> BitSetStrategy<T> {
             min,
             max,
             mask: None,
+            density: None,
+            target: None,
         }
     }
 
@@ -195,8 +308,40 @@ impl<T: BitSetLike> BitSetStrategy<T> {
             min: 0,
             max: mask.len(),
             mask: Some(mask),
+            density: None,
+            target: None,
         }
     }
+
+    /// Set the probability with which each eligible bit is set when
+    /// generating a value.
+    ///
+    /// A density near `0.0` produces sparse bit sets while a density near
+    /// `1.0` produces dense ones. The default is `0.5`. Shrinking is
+    /// unaffected and still clears one bit at a time.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `density` is not in the interval `[0.0, 1.0]`.
+    pub fn with_density(mut self, density: impl Into<Probability>) -> Self {
+        self.density = Some(density.into());
+        self
+    }
+
+    /// Shrink generated values toward `target` instead of toward zero.
+    ///
+    /// Simplification flips one bit at a time to match its value in
+    /// `target`, so the simplest value is `target` itself. Only the bits of
+    /// `target` which this strategy could generate are considered; any others
+    /// are treated as clear.
+    pub fn shrink_toward(mut self, target: T) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    fn is_eligible(&self, bit: usize) -> bool {
+        self.mask.as_ref().is_none_or(|mask| mask.test(bit))
+    }
 }
 
 impl<T: BitSetLike> Strategy for BitSetStrategy<T> {
@@ -206,19 +351,27 @@ impl<T: BitSetLike> Strategy for BitSetStrategy<T> {
     fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
         let mut inner = T::new_bitset(self.max);
         for bit in self.min..self.max {
-            if self.mask.as_ref().map_or(true, |mask| mask.test(bit))
-                && runner.rng().random()
+            if self.is_eligible(bit)
+                && match self.density {
+                    Some(density) => runner.rng().random_bool(density.into()),
+                    None => runner.rng().random(),
+                }
             {
                 inner.set(bit);
             }
         }
 
-        Ok(BitSetValueTree {
-            inner,
-            shrink: self.min,
-            prev_shrink: None,
-            min_count: 0,
-        })
+        let target = self.target.as_ref().map(|target| {
+            let mut eligible_target = T::new_bitset(self.max);
+            for bit in self.min..self.max {
+                if self.is_eligible(bit) && target.test(bit) {
+                    eligible_target.set(bit);
+                }
+            }
+            eligible_target
+        });
+
+        Ok(BitSetValueTree::new(inner, target, self.min, 0))
     }
 }
 
@@ -234,6 +387,7 @@ impl<T: BitSetLike> Strategy for BitSetStrategy<T> {
 pub struct SampledBitSetStrategy<T: BitSetLike> {
     size: SizeRange,
     bits: SizeRange,
+    mask: Option<T>,
     _marker: PhantomData<T>,
 }
 
@@ -266,6 +420,39 @@ impl<T: BitSetLike> SampledBitSetStrategy<T> {
         SampledBitSetStrategy {
             size,
             bits,
+            mask: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a strategy which generates values where only bits set in `mask`
+    /// may be set. The number of bits that are set is chosen to be in the
+    /// range given by `size`.
+    ///
+    /// Due to the generics, the functions in the typed submodules are usually
+    /// preferable to calling this directly.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` includes a value that is greater than the number of
+    /// bits set in `mask`.
+    pub fn masked(size: impl Into<SizeRange>, mask: T) -> Self {
+        let size = size.into();
+        size.assert_nonempty();
+
+        let available_bits = mask.count();
+        assert!(
+            size.end_excl() <= available_bits + 1,
+            "Illegal SampledBitSetStrategy: have {} bits available in mask, \
+             but requested size is {}..{}",
+            available_bits,
+            size.start(),
+            size.end_excl()
+        );
+        SampledBitSetStrategy {
+            size,
+            bits: (0..mask.len()).into(),
+            mask: Some(mask),
             _marker: PhantomData,
         }
     }
@@ -286,26 +473,334 @@ impl<T: BitSetLike> Strategy for SampledBitSetStrategy<T> {
             panic!("not enough bits to sample");
         }
 
-        for bit in self.bits.iter().choose_multiple(runner.rng(), count) {
+        let chosen = match self.mask {
+            Some(ref mask) => self
+                .bits
+                .iter()
+                .filter(|&bit| mask.test(bit))
+                .choose_multiple(runner.rng(), count),
+            None => self.bits.iter().choose_multiple(runner.rng(), count),
+        };
+        for bit in chosen {
             bits.set(bit);
         }
 
-        Ok(BitSetValueTree {
-            inner: bits,
-            shrink: self.bits.start(),
-            prev_shrink: None,
-            min_count: self.size.start(),
+        Ok(BitSetValueTree::new(
+            bits,
+            None,
+            self.bits.start(),
+            self.size.start(),
+        ))
+    }
+}
+
+/// A set operation used to combine two bit sets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetOp {
+    /// Bits set in either operand.
+    Union,
+    /// Bits set in both operands.
+    Intersection,
+    /// Bits set in the left operand but not the right.
+    Difference,
+    /// Bits set in exactly one of the operands.
+    SymmetricDifference,
+}
+
+impl SetOp {
+    fn apply<T: BitSetLike>(self, left: &T, right: &T) -> T {
+        let len = left.len().max(right.len());
+        let mut result = T::new_bitset(len);
+        for bit in 0..len {
+            let (l, r) = (left.test(bit), right.test(bit));
+            let set = match self {
+                SetOp::Union => l || r,
+                SetOp::Intersection => l && r,
+                SetOp::Difference => l && !r,
+                SetOp::SymmetricDifference => l != r,
+            };
+            if set {
+                result.set(bit);
+            }
+        }
+        result
+    }
+}
+
+/// Generates bit sets by combining the values of two other bit set strategies
+/// with a [`SetOp`].
+///
+/// Created by `union_with`, `intersect_with`, `difference_with` and
+/// `symmetric_difference_with` on the bit set strategies in this module.
+///
+/// Shrinking simplifies the left operand and then the right one, like a
+/// tuple. Simplifications of an operand which do not change the combined
+/// value (e.g. clearing a bit of the left operand of an intersection which
+/// is not set in the right operand) are skipped over, so every step taken
+/// by the value tree changes the generated value.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct BitSetCombine<A, B> {
+    left: A,
+    right: B,
+    op: SetOp,
+}
+
+impl<A, B> BitSetCombine<A, B> {
+    /// Create a strategy which combines the values of `left` and `right`
+    /// with `op`.
+    pub fn new(left: A, right: B, op: SetOp) -> Self {
+        BitSetCombine { left, right, op }
+    }
+}
+
+impl<T, A, B> Strategy for BitSetCombine<A, B>
+where
+    T: BitSetLike,
+    A: Strategy<Value = T>,
+    B: Strategy<Value = T>,
+{
+    type Tree = BitSetCombineValueTree<A::Tree, B::Tree>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(BitSetCombineValueTree {
+            left: self.left.new_tree(runner)?,
+            right: self.right.new_tree(runner)?,
+            op: self.op,
+            shrink_right: false,
+            prev_shrink_right: None,
         })
     }
 }
 
+/// Value tree produced by `BitSetCombine`.
+#[derive(Clone, Copy, Debug)]
+pub struct BitSetCombineValueTree<A, B> {
+    left: A,
+    right: B,
+    op: SetOp,
+    shrink_right: bool,
+    prev_shrink_right: Option<bool>,
+}
+
+impl<T, A, B> ValueTree for BitSetCombineValueTree<A, B>
+where
+    T: BitSetLike,
+    A: ValueTree<Value = T>,
+    B: ValueTree<Value = T>,
+{
+    type Value = T;
+
+    fn current(&self) -> T {
+        self.op.apply(&self.left.current(), &self.right.current())
+    }
+
+    fn simplify(&mut self) -> bool {
+        let before = self.current();
+        let differs = |after: &T| {
+            let len = before.len().max(after.len());
+            (0..len).any(|bit| before.test(bit) != after.test(bit))
+        };
+
+        if !self.shrink_right {
+            while self.left.simplify() {
+                if differs(&self.current()) {
+                    self.prev_shrink_right = Some(false);
+                    return true;
+                }
+            }
+            self.shrink_right = true;
+        }
+
+        while self.right.simplify() {
+            if differs(&self.current()) {
+                self.prev_shrink_right = Some(true);
+                return true;
+            }
+        }
+
+        self.prev_shrink_right = None;
+        false
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.prev_shrink_right {
+            Some(false) => {
+                if self.left.complicate() {
+                    self.shrink_right = false;
+                    true
+                } else {
+                    self.prev_shrink_right = None;
+                    false
+                }
+            }
+            Some(true) => {
+                if self.right.complicate() {
+                    true
+                } else {
+                    self.prev_shrink_right = None;
+                    false
+                }
+            }
+            None => false,
+        }
+    }
+}
+
+macro_rules! set_algebra_methods {
+    ($([$($gen:tt)*] $strategy:ty;)*) => {$(
+        impl<$($gen)*> $strategy {
+            /// Combine the values of this strategy and `other` by taking
+            /// their union.
+            pub fn union_with<S>(self, other: S) -> BitSetCombine<Self, S> {
+                BitSetCombine::new(self, other, SetOp::Union)
+            }
+
+            /// Combine the values of this strategy and `other` by taking
+            /// their intersection.
+            pub fn intersect_with<S>(self, other: S) -> BitSetCombine<Self, S> {
+                BitSetCombine::new(self, other, SetOp::Intersection)
+            }
+
+            /// Combine the values of this strategy and `other` by removing
+            /// the bits set in values of `other`.
+            pub fn difference_with<S>(self, other: S) -> BitSetCombine<Self, S> {
+                BitSetCombine::new(self, other, SetOp::Difference)
+            }
+
+            /// Combine the values of this strategy and `other` by taking
+            /// the bits set in exactly one of them.
+            pub fn symmetric_difference_with<S>(
+                self,
+                other: S,
+            ) -> BitSetCombine<Self, S> {
+                BitSetCombine::new(self, other, SetOp::SymmetricDifference)
+            }
+        }
+    )*};
+}
+
+set_algebra_methods! {
+    [T: BitSetLike] BitSetStrategy<T>;
+    [T: BitSetLike] SampledBitSetStrategy<T>;
+    [A, B] BitSetCombine<A, B>;
+}
+
+/// The number of removable bits above which `BitSetValueTree` starts by
+/// clearing runs of bits rather than single bits.
+const CHUNKED_SHRINK_THRESHOLD: usize = 64;
+
 /// Value tree produced by `BitSetStrategy` and `SampledBitSetStrategy`.
+///
+/// Shrinking normally clears one bit at a time. When a value has more than
+/// 64 bits which could be cleared, it instead first tries to clear runs of
+/// set bits, starting with half of them and halving the run length each time
+/// a full pass over the value has been made, before falling back to clearing
+/// single bits.
+///
+/// If the strategy was given a target with
+/// [`BitSetStrategy::shrink_toward`], bits are flipped toward their value in
+/// the target rather than cleared.
 #[derive(Clone, Copy, Debug)]
 pub struct BitSetValueTree<T: BitSetLike> {
     inner: T,
+    target: Option<T>,
+    start: usize,
     shrink: usize,
     prev_shrink: Option<usize>,
     min_count: usize,
+    chunk: usize,
+    prev_chunk: Option<T>,
+}
+
+impl<T: BitSetLike> BitSetValueTree<T> {
+    fn new(
+        inner: T,
+        target: Option<T>,
+        start: usize,
+        min_count: usize,
+    ) -> Self {
+        let mut tree = BitSetValueTree {
+            inner,
+            target,
+            start,
+            shrink: start,
+            prev_shrink: None,
+            min_count,
+            chunk: 1,
+            prev_chunk: None,
+        };
+
+        let excess = tree.removable();
+        if excess > CHUNKED_SHRINK_THRESHOLD {
+            tree.chunk = excess / 2;
+        }
+        tree
+    }
+
+    /// Return the value `bit` has once fully shrunk.
+    fn goal(&self, bit: usize) -> bool {
+        self.target.as_ref().is_some_and(|target| target.test(bit))
+    }
+
+    /// Return one past the last bit which may need to be flipped.
+    fn limit(&self) -> usize {
+        self.target
+            .as_ref()
+            .map_or(0, BitSetLike::len)
+            .max(self.inner.len())
+    }
+
+    /// Return the number of bits which may still be flipped.
+    fn removable(&self) -> usize {
+        match self.target {
+            Some(ref target) => (0..self.limit())
+                .filter(|&bit| self.inner.test(bit) != target.test(bit))
+                .count(),
+            None => self.inner.count().saturating_sub(self.min_count),
+        }
+    }
+
+    /// Flip `bit` to its value in the target.
+    fn flip_toward_goal(&mut self, bit: usize) {
+        if self.goal(bit) {
+            self.inner.set(bit);
+        } else {
+            self.inner.clear(bit);
+        }
+    }
+
+    /// Try to flip the next run of `self.chunk` bits toward the target,
+    /// halving the run length whenever the end of the value is reached.
+    /// Returns `false` once the run length has dropped to a single bit.
+    fn simplify_chunk(&mut self) -> bool {
+        while self.chunk > 1 {
+            let chunk = self.chunk.min(self.removable());
+            let limit = self.limit();
+            let mut flipped = 0;
+            let mut backup = None;
+
+            while self.shrink < limit && flipped < chunk {
+                if self.inner.test(self.shrink) != self.goal(self.shrink) {
+                    backup.get_or_insert_with(|| self.inner.clone());
+                    self.flip_toward_goal(self.shrink);
+                    flipped += 1;
+                }
+                self.shrink += 1;
+            }
+
+            if backup.is_some() {
+                self.prev_chunk = backup;
+                return true;
+            }
+
+            self.chunk /= 2;
+            self.shrink = self.start;
+        }
+
+        false
+    }
 }
 
 impl<T: BitSetLike> ValueTree for BitSetValueTree<T> {
@@ -316,28 +811,44 @@ impl<T: BitSetLike> ValueTree for BitSetValueTree<T> {
     }
 
     fn simplify(&mut self) -> bool {
-        if self.inner.count() <= self.min_count {
+        self.prev_chunk = None;
+        if 0 == self.removable() {
             return false;
         }
 
-        while self.shrink < self.inner.len() && !self.inner.test(self.shrink) {
+        if self.simplify_chunk() {
+            self.prev_shrink = None;
+            return true;
+        }
+
+        let limit = self.limit();
+        while self.shrink < limit
+            && self.inner.test(self.shrink) == self.goal(self.shrink)
+        {
             self.shrink += 1;
         }
 
-        if self.shrink >= self.inner.len() {
+        if self.shrink >= limit {
             self.prev_shrink = None;
             false
         } else {
             self.prev_shrink = Some(self.shrink);
-            self.inner.clear(self.shrink);
+            self.flip_toward_goal(self.shrink);
             self.shrink += 1;
             true
         }
     }
 
     fn complicate(&mut self) -> bool {
-        if let Some(bit) = self.prev_shrink.take() {
-            self.inner.set(bit);
+        if let Some(prev) = self.prev_chunk.take() {
+            self.inner = prev;
+            true
+        } else if let Some(bit) = self.prev_shrink.take() {
+            if self.goal(bit) {
+                self.inner.clear(bit);
+            } else {
+                self.inner.set(bit);
+            }
             true
         } else {
             false
@@ -356,6 +867,8 @@ macro_rules! int_api {
                 min: 0,
                 max: $max,
                 mask: None,
+                density: None,
+                target: None,
             };
 
             /// Generates values where bits between the given bounds may be
@@ -384,6 +897,21 @@ macro_rules! int_api {
             ) -> SampledBitSetStrategy<$typ> {
                 SampledBitSetStrategy::new(size, bits)
             }
+
+            /// Create a strategy which generates values where only bits set
+            /// in `mask` may be set. The number of bits that are set is
+            /// chosen to be in the range given by `size`.
+            ///
+            /// ## Panics
+            ///
+            /// Panics if `size` includes a value that is greater than the
+            /// number of bits set in `mask`.
+            pub fn sampled_masked(
+                size: impl Into<SizeRange>,
+                mask: $typ,
+            ) -> SampledBitSetStrategy<$typ> {
+                SampledBitSetStrategy::masked(size, mask)
+            }
         }
     };
 }
@@ -431,6 +959,21 @@ macro_rules! minimal_api {
             ) -> SampledBitSetStrategy<$typ> {
                 SampledBitSetStrategy::new(size, bits)
             }
+
+            /// Create a strategy which generates values where only bits set
+            /// in `mask` may be set. The number of bits that are set is
+            /// chosen to be in the range given by `size`.
+            ///
+            /// ## Panics
+            ///
+            /// Panics if `size` includes a value that is greater than the
+            /// number of bits set in `mask`.
+            pub fn sampled_masked(
+                size: impl Into<SizeRange>,
+                mask: $typ,
+            ) -> SampledBitSetStrategy<$typ> {
+                SampledBitSetStrategy::masked(size, mask)
+            }
         }
     };
 }
@@ -439,9 +982,20 @@ minimal_api!(isize, isize);
 #[cfg(feature = "bit-set")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bit-set")))]
 minimal_api!(bitset, BitSet);
+#[cfg(feature = "fixedbitset")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fixedbitset")))]
+minimal_api!(fixedbitset, FixedBitSet);
+#[cfg(feature = "roaring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "roaring")))]
+minimal_api!(roaring, RoaringBitmap);
 minimal_api!(bool_vec, Vec<bool>);
 
-pub(crate) mod varsize {
+/// Strategies and a bit set type for variable-size bit sets.
+///
+/// `VarBitSet` is backed by `BitSet` when the `bit-set` feature is enabled and
+/// by `Vec<bool>` otherwise, so the strategies here are always available
+/// regardless of which features are enabled.
+pub mod varsize {
     use super::*;
     use core::iter::FromIterator;
 
@@ -455,6 +1009,11 @@ pub(crate) mod varsize {
     pub struct VarBitSet(Inner);
 
     impl VarBitSet {
+        /// Create an empty bit set with space for up to `len` bits.
+        pub fn new(len: usize) -> Self {
+            VarBitSet::new_bitset(len)
+        }
+
         /// Create a bit set of `len` set values.
         #[cfg(not(feature = "bit-set"))]
         pub fn saturated(len: usize) -> Self {
@@ -467,13 +1026,17 @@ pub(crate) mod varsize {
             Self(BitSet::from_bit_vec(BitVec::from_elem(len, true)))
         }
 
+        /// Iterate over the indices of the bits which are set, in ascending
+        /// order.
         #[cfg(not(feature = "bit-set"))]
-        pub(crate) fn iter<'a>(&'a self) -> impl Iterator<Item = usize> + 'a {
+        pub fn iter<'a>(&'a self) -> impl Iterator<Item = usize> + 'a {
             (0..self.len()).into_iter().filter(move |&ix| self.test(ix))
         }
 
+        /// Iterate over the indices of the bits which are set, in ascending
+        /// order.
         #[cfg(feature = "bit-set")]
-        pub(crate) fn iter<'a>(&'a self) -> impl Iterator<Item = usize> + 'a {
+        pub fn iter<'a>(&'a self) -> impl Iterator<Item = usize> + 'a {
             self.0.iter()
         }
     }
@@ -516,33 +1079,199 @@ pub(crate) mod varsize {
         }
     }
 
-    /*
-    pub(crate) fn between(min: usize, max: usize) -> BitSetStrategy<VarBitSet> {
+    impl Extend<usize> for VarBitSet {
+        fn extend<T: IntoIterator<Item = usize>>(&mut self, iter: T) {
+            for bit in iter {
+                self.set(bit);
+            }
+        }
+    }
+
+    /// Generates values where bits between the given bounds may be set.
+    pub fn between(min: usize, max: usize) -> BitSetStrategy<VarBitSet> {
         BitSetStrategy::new(min, max)
     }
 
-    pub(crate) fn masked(mask: VarBitSet) -> BitSetStrategy<VarBitSet> {
+    /// Generates values where any bits set in `mask` (and no others) may be
+    /// set.
+    pub fn masked(mask: VarBitSet) -> BitSetStrategy<VarBitSet> {
         BitSetStrategy::masked(mask)
     }
-    */
 
-    pub(crate) fn sampled(
+    /// Create a strategy which generates values where bits within the bounds
+    /// given by `bits` may be set. The number of bits that are set is chosen
+    /// to be in the range given by `size`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` includes a value that is greater than the number of
+    /// bits in `bits`.
+    pub fn sampled(
         size: impl Into<SizeRange>,
         bits: impl Into<SizeRange>,
     ) -> SampledBitSetStrategy<VarBitSet> {
         SampledBitSetStrategy::new(size, bits)
     }
+
+    /// Create a strategy which generates values where only bits set in `mask`
+    /// may be set. The number of bits that are set is chosen to be in the
+    /// range given by `size`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` includes a value that is greater than the number of
+    /// bits set in `mask`.
+    pub fn sampled_masked(
+        size: impl Into<SizeRange>,
+        mask: VarBitSet,
+    ) -> SampledBitSetStrategy<VarBitSet> {
+        SampledBitSetStrategy::masked(size, mask)
+    }
 }
 
 pub use self::varsize::VarBitSet;
 
-#[cfg(test)]
-mod test {
+/// Strategies for types generated by the `bitflags!` macro.
+///
+/// Unlike the integer strategies in this module, values are built only from
+/// the flags declared on the type, so no unknown bits are ever produced.
+/// Shrinking removes one declared flag at a time.
+#[cfg(feature = "bitflags")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitflags")))]
+pub mod flags {
     use super::*;
+    use crate::std_facade::Arc;
+    use bitflags::{Bits, Flags};
 
-    #[test]
-    fn generates_values_in_range() {
-        let input = u32::between(4, 8);
+    /// Generates unions of the declared flags of `F`.
+    ///
+    /// Created by the functions in this module.
+    #[must_use = "strategies do nothing unless used"]
+    #[derive(Debug)]
+    pub struct FlagsStrategy<F> {
+        flags: Arc<[F]>,
+        bits: FlagsBits,
+    }
+
+    #[derive(Clone, Debug)]
+    enum FlagsBits {
+        Any(BitSetStrategy<VarBitSet>),
+        Sampled(SampledBitSetStrategy<VarBitSet>),
+    }
+
+    impl<F> Clone for FlagsStrategy<F> {
+        fn clone(&self) -> Self {
+            FlagsStrategy {
+                flags: Arc::clone(&self.flags),
+                bits: self.bits.clone(),
+            }
+        }
+    }
+
+    /// Return the distinct, non-empty declared flags of `F` which are
+    /// contained in `mask`.
+    fn declared_flags<F: Flags>(mask: &F) -> Arc<[F]> {
+        let mut flags: Vec<F> = Vec::new();
+        for flag in F::FLAGS {
+            let bits = flag.value().bits();
+            if bits != F::Bits::EMPTY
+                && mask.contains(F::from_bits_retain(bits))
+                && !flags.iter().any(|f| f.bits() == bits)
+            {
+                flags.push(F::from_bits_retain(bits));
+            }
+        }
+        flags.into()
+    }
+
+    /// Generates arbitrary unions of the flags declared on `F`.
+    pub fn any<F: Flags + Clone + fmt::Debug>() -> FlagsStrategy<F> {
+        masked(F::all())
+    }
+
+    /// Generates unions of those declared flags of `F` which are contained in
+    /// `mask`.
+    pub fn masked<F: Flags + Clone + fmt::Debug>(mask: F) -> FlagsStrategy<F> {
+        let flags = declared_flags(&mask);
+        let bits = FlagsBits::Any(varsize::between(0, flags.len()));
+        FlagsStrategy { flags, bits }
+    }
+
+    /// Generates unions of the flags declared on `F`, where the number of
+    /// flags in each union is chosen to be in the range given by `size`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` includes a value that is greater than the number of
+    /// distinct flags declared on `F`.
+    pub fn sampled<F: Flags + Clone + fmt::Debug>(
+        size: impl Into<SizeRange>,
+    ) -> FlagsStrategy<F> {
+        let flags = declared_flags(&F::all());
+        let bits = FlagsBits::Sampled(varsize::sampled(size, 0..flags.len()));
+        FlagsStrategy { flags, bits }
+    }
+
+    impl<F: Flags + Clone + fmt::Debug> Strategy for FlagsStrategy<F> {
+        type Tree = FlagsValueTree<F>;
+        type Value = F;
+
+        fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+            let inner = match self.bits {
+                FlagsBits::Any(ref s) => s.new_tree(runner)?,
+                FlagsBits::Sampled(ref s) => s.new_tree(runner)?,
+            };
+            Ok(FlagsValueTree {
+                flags: Arc::clone(&self.flags),
+                inner,
+            })
+        }
+    }
+
+    /// Value tree produced by `FlagsStrategy`.
+    #[derive(Debug)]
+    pub struct FlagsValueTree<F> {
+        flags: Arc<[F]>,
+        inner: BitSetValueTree<VarBitSet>,
+    }
+
+    impl<F> Clone for FlagsValueTree<F> {
+        fn clone(&self) -> Self {
+            FlagsValueTree {
+                flags: Arc::clone(&self.flags),
+                inner: self.inner.clone(),
+            }
+        }
+    }
+
+    impl<F: Flags + Clone + fmt::Debug> ValueTree for FlagsValueTree<F> {
+        type Value = F;
+
+        fn current(&self) -> F {
+            let mut value = F::empty();
+            for ix in self.inner.current().iter() {
+                value.insert(self.flags[ix].clone());
+            }
+            value
+        }
+
+        fn simplify(&mut self) -> bool {
+            self.inner.simplify()
+        }
+
+        fn complicate(&mut self) -> bool {
+            self.inner.complicate()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generates_values_in_range() {
+        let input = u32::between(4, 8);
 
         let mut runner = TestRunner::default();
         for _ in 0..256 {
@@ -586,6 +1315,84 @@ mod test {
         assert!(seen_2);
     }
 
+    #[cfg(feature = "fixedbitset")]
+    #[test]
+    fn mask_bounds_for_fixedbitset_correct() {
+        let mut seen_0 = false;
+        let mut seen_2 = false;
+
+        let mut mask = FixedBitSet::with_capacity(4);
+        mask.insert(0);
+        mask.insert(2);
+
+        let mut runner = TestRunner::deterministic();
+        let input = fixedbitset::masked(mask);
+        for _ in 0..32 {
+            let v = input.new_tree(&mut runner).unwrap().current();
+            assert!(v.ones().all(|bit| bit == 0 || bit == 2));
+            seen_0 |= v.contains(0);
+            seen_2 |= v.contains(2);
+        }
+
+        assert!(seen_0);
+        assert!(seen_2);
+    }
+
+    #[cfg(feature = "fixedbitset")]
+    #[test]
+    fn fixedbitset_sampled_shrinks_to_min_size() {
+        let input = fixedbitset::sampled(2..6, 100..200);
+
+        let mut runner = TestRunner::default();
+        for _ in 0..64 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            assert!(value
+                .current()
+                .ones()
+                .all(|bit| (100..200).contains(&bit)));
+            while value.simplify() {}
+            assert_eq!(2, value.current().count_ones(..));
+        }
+    }
+
+    #[cfg(feature = "roaring")]
+    #[test]
+    fn mask_bounds_for_roaring_correct() {
+        let mut seen_0 = false;
+        let mut seen_1000 = false;
+
+        let mask: RoaringBitmap = [0, 1000].iter().cloned().collect();
+
+        let mut runner = TestRunner::deterministic();
+        let input = roaring::masked(mask);
+        for _ in 0..32 {
+            let v = input.new_tree(&mut runner).unwrap().current();
+            assert!(v.iter().all(|bit| bit == 0 || bit == 1000));
+            seen_0 |= v.contains(0);
+            seen_1000 |= v.contains(1000);
+        }
+
+        assert!(seen_0);
+        assert!(seen_1000);
+    }
+
+    #[cfg(feature = "roaring")]
+    #[test]
+    fn roaring_between_shrinks_to_empty() {
+        let input = roaring::between(10, 5000);
+
+        let mut runner = TestRunner::default();
+        for _ in 0..16 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            assert!(value
+                .current()
+                .iter()
+                .all(|bit| (10..5000).contains(&bit)));
+            while value.simplify() {}
+            assert!(value.current().is_empty());
+        }
+    }
+
     #[test]
     fn mask_bounds_for_vecbool_correct() {
         let mut seen_0 = false;
@@ -689,11 +1496,467 @@ mod test {
         }
     }
 
+    #[test]
+    fn sampled_masked_selects_exact_count_from_mask() {
+        let input = u32::sampled_masked(3, 0xdeadbeef);
+        let mut accum = 0;
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..1024 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            assert_eq!(3, value.count_ones());
+            assert_eq!(0, value & !0xdeadbeef);
+            accum |= value;
+        }
+
+        assert_eq!(0xdeadbeef, accum);
+    }
+
+    #[test]
+    fn sampled_masked_doesnt_shrink_below_min_size() {
+        let input = u64::sampled_masked(2..5, 0xf0f0_0000_0000_f0f0);
+
+        let mut runner = TestRunner::default();
+        for _ in 0..256 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            while value.simplify() {}
+
+            assert_eq!(2, value.current().count_ones());
+            assert_eq!(0, value.current() & !0xf0f0_0000_0000_f0f0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn sampled_masked_rejects_oversized_request() {
+        let _ = u8::sampled_masked(4, 0b0101);
+    }
+
+    #[test]
+    fn density_controls_proportion_of_set_bits() {
+        let sparse = u64::ANY.with_density(0.1);
+        let dense = u64::ANY.with_density(0.9);
+
+        let mut runner = TestRunner::deterministic();
+        let mut sparse_count = 0;
+        let mut dense_count = 0;
+        for _ in 0..256 {
+            sparse_count +=
+                sparse.new_tree(&mut runner).unwrap().current().count_ones();
+            dense_count +=
+                dense.new_tree(&mut runner).unwrap().current().count_ones();
+        }
+
+        // 256 * 64 = 16384 bits sampled for each strategy.
+        assert!(
+            sparse_count > 1000 && sparse_count < 2300,
+            "{}",
+            sparse_count
+        );
+        assert!(
+            dense_count > 14000 && dense_count < 15400,
+            "{}",
+            dense_count
+        );
+    }
+
+    #[test]
+    fn density_extremes_are_exact() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..32 {
+            let none = u32::between(4, 8).with_density(0.0);
+            assert_eq!(0, none.new_tree(&mut runner).unwrap().current());
+            let all = u32::masked(0xdeadbeef).with_density(1.0);
+            assert_eq!(
+                0xdeadbeef,
+                all.new_tree(&mut runner).unwrap().current()
+            );
+        }
+    }
+
+    #[test]
+    fn dense_values_shrink_one_bit_at_a_time() {
+        let input = u32::between(4, 24).with_density(0.9);
+
+        let mut runner = TestRunner::default();
+        for _ in 0..64 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            let mut prev = value.current();
+            while value.simplify() {
+                let v = value.current();
+                assert_eq!(1, (prev & !v).count_ones());
+                prev = v;
+            }
+
+            assert_eq!(0, value.current());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn density_out_of_range_panics() {
+        let _ = u8::ANY.with_density(1.5);
+    }
+
+    #[cfg(feature = "bitflags")]
+    bitflags! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        struct TestFlags: u16 {
+            const A = 0b0001;
+            const B = 0b0010;
+            const C = 0b1000;
+            const AB = Self::A.bits() | Self::B.bits();
+            const ALIAS = 0b0010;
+        }
+    }
+
+    #[cfg(feature = "bitflags")]
+    #[test]
+    fn flags_only_generates_declared_flags() {
+        let input = flags::any::<TestFlags>();
+        let mut seen = [false; 16];
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            assert!(TestFlags::all().contains(value));
+            seen[value.bits() as usize] = true;
+        }
+
+        for (bits, &seen) in seen.iter().enumerate() {
+            assert_eq!(0 == bits & 0b0100, seen, "bits {:04b}", bits);
+        }
+    }
+
+    #[cfg(feature = "bitflags")]
+    #[test]
+    fn flags_masked_respects_mask() {
+        let input = flags::masked(TestFlags::A | TestFlags::C);
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            assert!(!value.contains(TestFlags::B));
+        }
+    }
+
+    #[cfg(feature = "bitflags")]
+    #[test]
+    fn flags_shrink_one_flag_at_a_time() {
+        let input = flags::sampled::<TestFlags>(1..5);
+
+        let mut runner = TestRunner::default();
+        for _ in 0..64 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            let mut prev = value.current();
+            while value.simplify() {
+                let v = value.current();
+                assert!(prev.contains(v));
+                prev = v;
+            }
+            let minimal = value.current();
+            assert!(
+                minimal == TestFlags::A
+                    || minimal == TestFlags::B
+                    || minimal == TestFlags::C
+                    || minimal == TestFlags::AB,
+                "Shrank to {:?}",
+                minimal
+            );
+        }
+    }
+
+    #[test]
+    fn large_sets_shrink_in_chunks() {
+        let input = varsize::between(0, 2000);
+
+        let mut runner = TestRunner::deterministic();
+        let mut value = input.new_tree(&mut runner).unwrap();
+        let mut steps = 0;
+        while value.simplify() {
+            steps += 1;
+        }
+
+        assert_eq!(0, value.current().count());
+        assert!(steps < 20, "Took {} steps", steps);
+    }
+
+    #[test]
+    fn chunked_shrinking_finds_minimal_subset() {
+        // Simulate a test which fails whenever bits 100 and 1234 are both set.
+        let input = varsize::between(0, 2000);
+        let fails = |v: &VarBitSet| v.test(100) && v.test(1234);
+
+        let mut runner = TestRunner::deterministic();
+        let mut checked = 0;
+        for _ in 0..8 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            if !fails(&value.current()) {
+                continue;
+            }
+            checked += 1;
+
+            let mut steps = 0;
+            while value.simplify() {
+                steps += 1;
+                if !fails(&value.current()) {
+                    assert!(value.complicate());
+                    assert!(fails(&value.current()));
+                }
+            }
+
+            assert_eq!(
+                vec![100, 1234],
+                value.current().iter().collect::<Vec<_>>()
+            );
+            assert!(steps < 500, "Took {} steps", steps);
+        }
+
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn chunked_shrinking_respects_min_count() {
+        let input = varsize::sampled(100..200, 0..500);
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..16 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            while value.simplify() {
+                assert!(value.current().count() >= 100);
+            }
+            assert_eq!(100, value.current().count());
+        }
+    }
+
+    #[test]
+    fn shrinks_toward_target() {
+        let input = u32::between(4, 24).shrink_toward(0x00f0_0ff0);
+
+        let mut runner = TestRunner::default();
+        for _ in 0..256 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            let mut prev = value.current();
+            while value.simplify() {
+                let v = value.current();
+                assert_eq!(1, (prev ^ v).count_ones());
+                assert_eq!(0, (v ^ prev) & (v ^ 0x00f0_0ff0));
+                prev = v;
+            }
+
+            // Bits outside 4..24 are not eligible, so bits 24..28 of the
+            // target are never set.
+            assert_eq!(0x0000_0ff0 | 0x00f0_0000, value.current());
+        }
+    }
+
+    #[test]
+    fn complicates_away_from_target() {
+        let input = u32::masked(0xdeadbeef).shrink_toward(0xffff_0000);
+
+        let mut runner = TestRunner::default();
+        for _ in 0..256 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            let orig = value.current();
+            if value.simplify() {
+                assert!(value.complicate());
+                assert_eq!(orig, value.current());
+            }
+        }
+    }
+
+    #[test]
+    fn masked_shrinks_to_eligible_part_of_target() {
+        let input = u32::masked(0xdeadbeef).shrink_toward(0xffff_0000);
+
+        let mut runner = TestRunner::default();
+        for _ in 0..256 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            while value.simplify() {}
+            assert_eq!(0xdead_0000, value.current());
+        }
+    }
+
+    #[test]
+    fn large_sets_shrink_toward_target_in_chunks() {
+        let target: VarBitSet = (0..2000).filter(|bit| bit % 3 == 0).collect();
+        let input = varsize::between(0, 2000).shrink_toward(target.clone());
+
+        let mut runner = TestRunner::deterministic();
+        let mut value = input.new_tree(&mut runner).unwrap();
+        let mut steps = 0;
+        while value.simplify() {
+            steps += 1;
+        }
+
+        assert!(target.iter().eq(value.current().iter()));
+        assert!(steps < 20, "Took {} steps", steps);
+    }
+
+    #[test]
+    fn array_generates_values_in_range() {
+        let input = BitSetStrategy::<[u64; 4]>::new(60, 200);
+
+        let mut runner = TestRunner::deterministic();
+        let mut accum = [0u64; 4];
+        for _ in 0..256 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            for (acc, word) in accum.iter_mut().zip(value.iter()) {
+                *acc |= word;
+            }
+        }
+
+        assert_eq!([0xf000_0000_0000_0000, !0, !0, 0xff], accum);
+    }
+
+    #[test]
+    fn array_masked_and_shrinks_to_zero() {
+        let mask = [0xde, 0xad, 0xbe, 0xef, 0, 0x01];
+        let input = BitSetStrategy::<[u8; 6]>::masked(mask);
+
+        let mut runner = TestRunner::default();
+        for _ in 0..256 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            let current = value.current();
+            for (word, mask) in current.iter().zip(mask.iter()) {
+                assert_eq!(0, word & !mask);
+            }
+            while value.simplify() {}
+            assert_eq!([0; 6], value.current());
+        }
+    }
+
+    #[test]
+    fn array_sampled_selects_correct_count() {
+        let input = SampledBitSetStrategy::<[u8; 32]>::new(10..20, 0..256);
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            assert!((10..20).contains(&value.count()));
+        }
+    }
+
+    #[test]
+    fn set_algebra_combines_values() {
+        let mut runner = TestRunner::deterministic();
+        let union = u32::between(0, 8).union_with(u32::between(24, 32));
+        let inter = u32::between(0, 16).intersect_with(u32::between(8, 24));
+        let diff = u32::ANY.difference_with(u32::masked(0xffff_0000));
+        let sym = u32::masked(0xff).symmetric_difference_with(Just(0x0f));
+
+        let mut seen = [0u32; 4];
+        for _ in 0..256 {
+            let v = union.new_tree(&mut runner).unwrap().current();
+            assert_eq!(0, v & 0x00ff_ff00);
+            seen[0] |= v;
+            let v = inter.new_tree(&mut runner).unwrap().current();
+            assert_eq!(0, v & !0xff00);
+            seen[1] |= v;
+            let v = diff.new_tree(&mut runner).unwrap().current();
+            seen[2] |= v;
+            let v = sym.new_tree(&mut runner).unwrap().current();
+            assert_eq!(0, v & !0xff);
+            seen[3] |= v;
+        }
+
+        assert_eq!([0xff00_00ff, 0xff00, 0xffff_ffff, 0xff], seen);
+    }
+
+    #[test]
+    fn set_algebra_shrinking_always_changes_value() {
+        let input = u64::ANY.intersect_with(u64::masked(0x0f0f_0f0f_0f0f_0f0f));
+
+        let mut runner = TestRunner::default();
+        for _ in 0..256 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            let mut prev = value.current();
+            while value.simplify() {
+                let v = value.current();
+                assert_ne!(prev, v);
+                prev = v;
+            }
+            assert_eq!(0, value.current());
+        }
+    }
+
+    #[test]
+    fn set_algebra_complicates_to_previous() {
+        let input = u32::between(4, 24)
+            .union_with(u32::between(0, 8))
+            .difference_with(u32::masked(0x0f0f_0f0f));
+
+        let mut runner = TestRunner::default();
+        for _ in 0..256 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            let orig = value.current();
+            if value.simplify() {
+                assert!(value.complicate());
+                assert_eq!(orig, value.current());
+            }
+        }
+    }
+
+    #[test]
+    fn set_algebra_test_sanity() {
+        check_strategy_sanity(
+            u32::masked(0xdeadbeef).union_with(u32::between(8, 16)),
+            None,
+        );
+    }
+
     #[test]
     fn test_sanity() {
         check_strategy_sanity(u32::masked(0xdeadbeef), None);
     }
 
+    #[test]
+    fn varsize_generates_values_in_range() {
+        let input = varsize::between(4, 100);
+
+        let mut runner = TestRunner::deterministic();
+        let mut seen = VarBitSet::new(100);
+        for _ in 0..256 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            for bit in value.iter() {
+                assert!((4..100).contains(&bit), "Generated bit {}", bit);
+            }
+            seen.extend(value.iter());
+        }
+
+        assert_eq!(96, seen.count());
+    }
+
+    #[test]
+    fn varsize_generates_values_in_mask() {
+        let mask: VarBitSet = [0, 2, 70].iter().cloned().collect();
+
+        let mut runner = TestRunner::deterministic();
+        let input = varsize::masked(mask);
+        let mut seen = VarBitSet::new(71);
+        for _ in 0..64 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            for bit in value.iter() {
+                assert!(bit == 0 || bit == 2 || bit == 70);
+            }
+            seen.extend(value.iter());
+        }
+
+        assert_eq!(vec![0, 2, 70], seen.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn varsize_sampled_shrinks_to_min_size() {
+        let input = varsize::sampled(3..10, 0..200);
+
+        let mut runner = TestRunner::default();
+        for _ in 0..64 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            while value.simplify() {}
+            assert_eq!(3, value.current().count());
+        }
+    }
+
     #[test]
     fn u128_generates_values_in_range() {
         let input = u128::between(64, 128);
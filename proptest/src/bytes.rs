@@ -0,0 +1,290 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating `bytes::Bytes` and `bytes::BytesMut` buffers.
+//!
+//! `bytes()` and `bytes_mut()` generate buffers of arbitrary bytes with a
+//! length in a given range, while `bytes_of()` and `bytes_mut_of()` take
+//! their contents from any strategy for `Vec<u8>`, such as a `vec()` of a
+//! restricted range of bytes.
+//!
+//! Independent random bytes rarely exercise the paths that parsers and
+//! protocol implementations get wrong, so `interesting()` generates contents
+//! made of runs of one byte value, repeats of earlier content and the byte
+//! values at the edges of the signed and unsigned ranges, with lengths biased
+//! toward the sizes just around powers of two.
+//!
+//! `Bytes` and `BytesMut` also implement `Arbitrary` with a `SizeRange` as
+//! parameters.
+
+use ::bytes::{Bytes, BytesMut};
+use rand::Rng;
+
+use crate::arbitrary::Arbitrary;
+use crate::collection::{vec, SizeRange, VecStrategy, VecValueTree};
+use crate::num::{self, sample_uniform_incl};
+use crate::std_facade::Vec;
+use crate::strategy::*;
+use crate::test_runner::*;
+
+mapfn! {
+    [] fn VecToBytes[](vec: Vec<u8>) -> Bytes {
+        Bytes::from(vec)
+    }
+}
+
+mapfn! {
+    [] fn VecToBytesMut[](vec: Vec<u8>) -> BytesMut {
+        BytesMut::from(&vec[..])
+    }
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy to create `Bytes` from the contents generated by another
+    /// strategy.
+    ///
+    /// Created by the `bytes()` and `bytes_of()` functions in the same
+    /// module.
+    #[derive(Clone, Debug)]
+    pub struct BytesStrategy[<S>][where S : Strategy<Value = Vec<u8>>](
+        statics::Map<S, VecToBytes>)
+        -> BytesValueTree<S::Tree>;
+    /// `ValueTree` corresponding to `BytesStrategy`.
+    #[derive(Clone, Debug)]
+    pub struct BytesValueTree[<T>][where T : ValueTree<Value = Vec<u8>>](
+        statics::Map<T, VecToBytes>)
+        -> Bytes;
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy to create `BytesMut` from the contents generated by another
+    /// strategy.
+    ///
+    /// Created by the `bytes_mut()` and `bytes_mut_of()` functions in the
+    /// same module.
+    #[derive(Clone, Debug)]
+    pub struct BytesMutStrategy[<S>][where S : Strategy<Value = Vec<u8>>](
+        statics::Map<S, VecToBytesMut>)
+        -> BytesMutValueTree<S::Tree>;
+    /// `ValueTree` corresponding to `BytesMutStrategy`.
+    #[derive(Clone, Debug)]
+    pub struct BytesMutValueTree[<T>][where T : ValueTree<Value = Vec<u8>>](
+        statics::Map<T, VecToBytesMut>)
+        -> BytesMut;
+}
+
+/// Create a strategy to generate `Bytes` of arbitrary bytes with a length in
+/// the range given by `size`.
+pub fn bytes(
+    size: impl Into<SizeRange>,
+) -> BytesStrategy<VecStrategy<num::u8::Any>> {
+    bytes_of(vec(num::u8::ANY, size))
+}
+
+/// Create a strategy to generate `Bytes` whose contents are drawn from
+/// `content`.
+pub fn bytes_of<S: Strategy<Value = Vec<u8>>>(content: S) -> BytesStrategy<S> {
+    BytesStrategy(statics::Map::new(content, VecToBytes))
+}
+
+/// Create a strategy to generate `BytesMut` of arbitrary bytes with a length
+/// in the range given by `size`.
+pub fn bytes_mut(
+    size: impl Into<SizeRange>,
+) -> BytesMutStrategy<VecStrategy<num::u8::Any>> {
+    bytes_mut_of(vec(num::u8::ANY, size))
+}
+
+/// Create a strategy to generate `BytesMut` whose contents are drawn from
+/// `content`.
+pub fn bytes_mut_of<S: Strategy<Value = Vec<u8>>>(
+    content: S,
+) -> BytesMutStrategy<S> {
+    BytesMutStrategy(statics::Map::new(content, VecToBytesMut))
+}
+
+/// Byte values at the edges of the signed and unsigned ranges.
+const BOUNDARY_BYTES: [u8; 4] = [0x00, 0x7F, 0x80, 0xFF];
+
+/// The longest run or repeat `interesting()` generates at once.
+const MAX_SEGMENT: usize = 256;
+
+/// Strategy to create structurally interesting byte strings.
+///
+/// Created by the `interesting()` function in the same module.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct InterestingBytes {
+    size: SizeRange,
+}
+
+/// Create a strategy to generate `Vec<u8>`s with a length in the range given
+/// by `size`, built from runs of a single byte value, repeats of earlier
+/// content, boundary byte values and stretches of random bytes.
+///
+/// Half of the time the length is one of the sizes just around a power of
+/// two, such as 255, 256 or 257, which lie within `size`.
+///
+/// The values shrink like those of `vec()`, by deleting bytes and then
+/// shrinking the remaining bytes toward 0. Use `bytes_of()` or
+/// `bytes_mut_of()` to generate buffers from them.
+pub fn interesting(size: impl Into<SizeRange>) -> InterestingBytes {
+    let size = size.into();
+    size.assert_nonempty();
+    InterestingBytes { size }
+}
+
+impl Strategy for InterestingBytes {
+    type Tree = VecValueTree<num::u8::BinarySearch>;
+    type Value = Vec<u8>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let (start, end) = self.size.start_end_incl();
+        let end = runner.sized(start, end);
+
+        let boundaries = (0..usize::BITS)
+            .flat_map(|k| {
+                let pow = 1usize << k;
+                [pow - 1, pow, pow.saturating_add(1)]
+            })
+            .filter(|len| (start..=end).contains(len))
+            .collect::<Vec<_>>();
+        let len = if !boundaries.is_empty() && runner.rng().random() {
+            boundaries[runner.rng().random_range(0..boundaries.len())]
+        } else {
+            sample_uniform_incl(runner, start, end)
+        };
+
+        let rng = runner.rng();
+        let mut data = Vec::with_capacity(len);
+        while data.len() < len {
+            let remaining = len - data.len();
+            match rng.random_range(0..4) {
+                0 => {
+                    // A run of a single byte value.
+                    let byte = if rng.random() {
+                        BOUNDARY_BYTES[rng.random_range(0..4)]
+                    } else {
+                        rng.random()
+                    };
+                    let n = rng.random_range(1..=remaining.min(MAX_SEGMENT));
+                    data.resize(data.len() + n, byte);
+                }
+                1 if !data.is_empty() => {
+                    // A repeat of some earlier content.
+                    let from = rng.random_range(0..data.len());
+                    let n = rng.random_range(
+                        1..=remaining.min(data.len() - from).min(MAX_SEGMENT),
+                    );
+                    data.extend_from_within(from..from + n);
+                }
+                2 => {
+                    data.push(BOUNDARY_BYTES[rng.random_range(0..4)]);
+                }
+                _ => {
+                    let n = rng.random_range(1..=remaining.min(16));
+                    data.extend((0..n).map(|_| rng.random::<u8>()));
+                }
+            }
+        }
+
+        Ok(VecValueTree::new(
+            data.into_iter().map(num::u8::BinarySearch::new).collect(),
+            start,
+        ))
+    }
+}
+
+impl Arbitrary for Bytes {
+    type Parameters = SizeRange;
+    type Strategy = BytesStrategy<VecStrategy<num::u8::Any>>;
+
+    fn arbitrary_with(size: SizeRange) -> Self::Strategy {
+        bytes(size)
+    }
+}
+
+impl Arbitrary for BytesMut {
+    type Parameters = SizeRange;
+    type Strategy = BytesMutStrategy<VecStrategy<num::u8::Any>>;
+
+    fn arbitrary_with(size: SizeRange) -> Self::Strategy {
+        bytes_mut(size)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arbitrary::any;
+
+    #[test]
+    fn bytes_respect_size() {
+        let mut runner = TestRunner::deterministic();
+        let input = bytes(3..10);
+        for _ in 0..64 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            assert!((3..10).contains(&value.len()));
+        }
+
+        let input = bytes_mut_of(vec(0u8..4, 5));
+        for _ in 0..64 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            assert_eq!(5, value.len());
+            assert!(value.iter().all(|&b| b < 4));
+        }
+    }
+
+    #[test]
+    fn interesting_hits_boundary_sizes() {
+        let mut runner = TestRunner::deterministic();
+        let input = interesting(0..1000);
+        let mut boundary = 0;
+        for _ in 0..256 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            assert!(value.len() < 1000);
+            let len = value.len();
+            if [len, len + 1, len.saturating_sub(1)]
+                .iter()
+                .any(|n| n.is_power_of_two())
+            {
+                boundary += 1;
+            }
+        }
+        assert!(boundary > 64, "Only {} boundary sizes", boundary);
+    }
+
+    #[test]
+    fn interesting_has_structure() {
+        let mut runner = TestRunner::deterministic();
+        let input = interesting(64..65);
+        let mut with_runs = 0;
+        for _ in 0..64 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            assert_eq!(64, value.len());
+            if value.windows(4).any(|w| w.iter().all(|&b| b == w[0])) {
+                with_runs += 1;
+            }
+        }
+        // Four equal bytes in a row are unlikely among 64 random bytes.
+        assert!(with_runs > 16, "Only {} values with runs", with_runs);
+    }
+
+    #[test]
+    fn arbitrary_bytes() {
+        let mut runner = TestRunner::deterministic();
+        let value = any::<Bytes>().new_tree(&mut runner).unwrap().current();
+        assert!(value.len() < 100);
+    }
+
+    #[test]
+    fn interesting_sanity() {
+        check_strategy_sanity(interesting(1..300), None);
+    }
+}
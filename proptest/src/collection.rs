@@ -11,11 +11,14 @@
 
 use core::cmp::Ord;
 use core::hash::Hash;
+use core::marker::PhantomData;
 use core::ops::{Add, Range, RangeInclusive, RangeTo, RangeToInclusive};
 use core::usize;
 
+use num_traits::CheckedSub;
+
 use crate::std_facade::{
-    fmt, BTreeMap, BTreeSet, BinaryHeap, LinkedList, Vec, VecDeque,
+    fmt, Arc, BTreeMap, BTreeSet, BinaryHeap, LinkedList, Vec, VecDeque,
 };
 
 #[cfg(feature = "std")]
@@ -529,190 +532,1286 @@ where
     ))
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Shrink {
-    DeleteElement(usize),
-    ShrinkElement(usize),
+/// Maps a generated key to the strategy for its whole entry: the key itself
+/// alongside the value strategy derived from it.
+struct KeyToEntry<V, F>(Arc<F>, PhantomData<fn() -> V>);
+
+impl<V, F> Clone for KeyToEntry<V, F> {
+    fn clone(&self) -> Self {
+        KeyToEntry(Arc::clone(&self.0), PhantomData)
+    }
 }
 
-/// `ValueTree` corresponding to `VecStrategy`.
-#[derive(Clone, Debug)]
-pub struct VecValueTree<T: ValueTree> {
-    elements: Vec<T>,
-    included_elements: VarBitSet,
-    min_size: usize,
-    shrink: Shrink,
-    prev_shrink: Option<Shrink>,
+impl<K: Clone + fmt::Debug, V: Strategy, F: Fn(&K) -> V> statics::MapFn<K>
+    for KeyToEntry<V, F>
+{
+    type Output = (Just<K>, V);
+
+    fn apply(&self, key: K) -> (Just<K>, V) {
+        let value = (self.0)(&key);
+        (Just(key), value)
+    }
 }
 
-impl<T: Strategy> Strategy for VecStrategy<T> {
-    type Tree = VecValueTree<T::Tree>;
-    type Value = Vec<T::Value>;
+type KeyedEntries<K, V, F> =
+    VecStrategy<Flatten<statics::Map<K, KeyToEntry<V, F>>>>;
+type KeyedEntriesValueTree<K, V, F> =
+    VecValueTree<FlattenValueTree<statics::Map<K, KeyToEntry<V, F>>>>;
 
-    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
-        let (start, end) = self.size.start_end_incl();
-        let max_size = sample_uniform_incl(runner, start, end);
-        let mut elements = Vec::with_capacity(max_size);
-        while elements.len() < max_size {
-            elements.push(self.element.new_tree(runner)?);
-        }
+fn keyed_entries<K: Strategy, V: Strategy, F: Fn(&K::Value) -> V>(
+    key: K,
+    value: F,
+    size: SizeRange,
+) -> KeyedEntries<K, V, F>
+where
+    K::Value: Clone,
+{
+    vec(
+        Flatten::new(statics::Map::new(
+            key,
+            KeyToEntry(Arc::new(value), PhantomData),
+        )),
+        size,
+    )
+}
 
-        Ok(VecValueTree {
-            elements,
-            included_elements: VarBitSet::saturated(max_size),
-            min_size: start,
-            shrink: Shrink::DeleteElement(0),
-            prev_shrink: None,
-        })
+/// Strategy to create `HashMap`s whose values are generated from their keys,
+/// with a length in a certain range.
+///
+/// Created by the `hash_map_with()` function in the same module.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[must_use = "strategies do nothing unless used"]
+pub struct HashMapWithStrategy<K, V, F>(
+    statics::Filter<statics::Map<KeyedEntries<K, V, F>, VecToHashMap>, MinSize>,
+)
+where
+    K: Strategy,
+    K::Value: Clone,
+    V: Strategy,
+    F: Fn(&K::Value) -> V;
+
+/// `ValueTree` corresponding to `HashMapWithStrategy`.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct HashMapWithValueTree<K, V, F>(
+    statics::Filter<
+        statics::Map<KeyedEntriesValueTree<K, V, F>, VecToHashMap>,
+        MinSize,
+    >,
+)
+where
+    K: ValueTree,
+    K::Value: Clone,
+    V: Strategy,
+    F: Fn(&K::Value) -> V;
+
+/// Create a strategy to generate `HashMap`s containing keys drawn from `key`
+/// and values drawn from the strategy `value` returns for each key, and with
+/// a size within the given range.
+///
+/// This is useful when a value must agree with its key, such as a record
+/// which embeds its own identifier. Each entry shrinks its key first,
+/// regenerating the value from the simplified key, and then shrinks the value
+/// itself, in the same way as `Strategy::prop_flat_map`.
+///
+/// This strategy will implicitly do local rejects to ensure that the `HashMap`
+/// has at least the minimum number of elements, in case `key` should produce
+/// duplicate values.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn hash_map_with<K: Strategy, V: Strategy, F: Fn(&K::Value) -> V>(
+    key: K,
+    value: F,
+    size: impl Into<SizeRange>,
+) -> HashMapWithStrategy<K, V, F>
+where
+    K::Value: Hash + Eq + Clone,
+{
+    let size = size.into();
+    HashMapWithStrategy(statics::Filter::new(
+        statics::Map::new(
+            keyed_entries(key, value, size.clone()),
+            VecToHashMap,
+        ),
+        "HashMap minimum size".into(),
+        MinSize(size.start()),
+    ))
+}
+
+#[cfg(feature = "std")]
+impl<K: Strategy, V: Strategy, F: Fn(&K::Value) -> V> fmt::Debug
+    for HashMapWithStrategy<K, V, F>
+where
+    K::Value: Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("HashMapWithStrategy").field(&self.0).finish()
     }
 }
 
-impl<T: Strategy> Strategy for Vec<T> {
-    type Tree = VecValueTree<T::Tree>;
-    type Value = Vec<T::Value>;
+#[cfg(feature = "std")]
+impl<K: Strategy + Clone, V: Strategy, F: Fn(&K::Value) -> V> Clone
+    for HashMapWithStrategy<K, V, F>
+where
+    K::Value: Clone,
+{
+    fn clone(&self) -> Self {
+        HashMapWithStrategy(self.0.clone())
+    }
+}
 
-    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
-        let len = self.len();
-        let elements = self
-            .iter()
-            .map(|t| t.new_tree(runner))
-            .collect::<Result<Vec<_>, Reason>>()?;
+#[cfg(feature = "std")]
+impl<K: Strategy, V: Strategy, F: Fn(&K::Value) -> V> Strategy
+    for HashMapWithStrategy<K, V, F>
+where
+    K::Value: Hash + Eq + Clone,
+{
+    type Tree = HashMapWithValueTree<K::Tree, V, F>;
+    type Value = HashMap<K::Value, V::Value>;
 
-        Ok(VecValueTree {
-            elements,
-            included_elements: VarBitSet::saturated(len),
-            min_size: len,
-            shrink: Shrink::ShrinkElement(0),
-            prev_shrink: None,
-        })
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.0.new_tree(runner).map(HashMapWithValueTree)
     }
 }
 
-impl<T: ValueTree> ValueTree for VecValueTree<T> {
-    type Value = Vec<T::Value>;
+#[cfg(feature = "std")]
+impl<K: ValueTree, V: Strategy, F: Fn(&K::Value) -> V> fmt::Debug
+    for HashMapWithValueTree<K, V, F>
+where
+    K: fmt::Debug,
+    K::Value: Clone,
+    V::Tree: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("HashMapWithValueTree")
+            .field(&self.0)
+            .finish()
+    }
+}
 
-    fn current(&self) -> Vec<T::Value> {
-        self.elements
-            .iter()
-            .enumerate()
-            .filter(|&(ix, _)| self.included_elements.test(ix))
-            .map(|(_, element)| element.current())
-            .collect()
+#[cfg(feature = "std")]
+impl<K: ValueTree, V: Strategy, F: Fn(&K::Value) -> V> Clone
+    for HashMapWithValueTree<K, V, F>
+where
+    K: Clone,
+    K::Value: Clone,
+    V: Clone,
+    V::Tree: Clone,
+{
+    fn clone(&self) -> Self {
+        HashMapWithValueTree(self.0.clone())
     }
+}
 
-    fn simplify(&mut self) -> bool {
-        // The overall strategy here is to iteratively delete elements from the
-        // list until we can do so no further, then to shrink each remaining
-        // element in sequence.
-        //
-        // For `complicate()`, we simply undo the last shrink operation, if
-        // there was any.
-        if let Shrink::DeleteElement(ix) = self.shrink {
-            // Can't delete an element if beyond the end of the vec or if it
-            // would put us under the minimum length.
-            if ix >= self.elements.len()
-                || self.included_elements.count() == self.min_size
-            {
-                self.shrink = Shrink::ShrinkElement(0);
-            } else {
-                self.included_elements.clear(ix);
-                self.prev_shrink = Some(self.shrink);
-                self.shrink = Shrink::DeleteElement(ix + 1);
-                return true;
-            }
-        }
+#[cfg(feature = "std")]
+impl<K: ValueTree, V: Strategy, F: Fn(&K::Value) -> V> ValueTree
+    for HashMapWithValueTree<K, V, F>
+where
+    K::Value: Hash + Eq + Clone,
+{
+    type Value = HashMap<K::Value, V::Value>;
 
-        while let Shrink::ShrinkElement(ix) = self.shrink {
-            if ix >= self.elements.len() {
-                // Nothing more we can do
-                return false;
-            }
+    delegate_vt_0!();
+}
 
-            if !self.included_elements.test(ix) {
-                // No use shrinking something we're not including.
-                self.shrink = Shrink::ShrinkElement(ix + 1);
-                continue;
-            }
+/// Strategy to create `BTreeMap`s whose values are generated from their keys,
+/// with a length in a certain range.
+///
+/// Created by the `btree_map_with()` function in the same module.
+#[must_use = "strategies do nothing unless used"]
+pub struct BTreeMapWithStrategy<K, V, F>(
+    statics::Filter<
+        statics::Map<KeyedEntries<K, V, F>, VecToBTreeMap>,
+        MinSize,
+    >,
+)
+where
+    K: Strategy,
+    K::Value: Clone,
+    V: Strategy,
+    F: Fn(&K::Value) -> V;
+
+/// `ValueTree` corresponding to `BTreeMapWithStrategy`.
+pub struct BTreeMapWithValueTree<K, V, F>(
+    statics::Filter<
+        statics::Map<KeyedEntriesValueTree<K, V, F>, VecToBTreeMap>,
+        MinSize,
+    >,
+)
+where
+    K: ValueTree,
+    K::Value: Clone,
+    V: Strategy,
+    F: Fn(&K::Value) -> V;
+
+/// Create a strategy to generate `BTreeMap`s containing keys drawn from `key`
+/// and values drawn from the strategy `value` returns for each key, and with
+/// a size within the given range.
+///
+/// See `hash_map_with()` for how the entries are shrunk.
+///
+/// This strategy will implicitly do local rejects to ensure that the
+/// `BTreeMap` has at least the minimum number of elements, in case `key`
+/// should produce duplicate values.
+pub fn btree_map_with<K: Strategy, V: Strategy, F: Fn(&K::Value) -> V>(
+    key: K,
+    value: F,
+    size: impl Into<SizeRange>,
+) -> BTreeMapWithStrategy<K, V, F>
+where
+    K::Value: Ord + Clone,
+{
+    let size = size.into();
+    BTreeMapWithStrategy(statics::Filter::new(
+        statics::Map::new(
+            keyed_entries(key, value, size.clone()),
+            VecToBTreeMap,
+        ),
+        "BTreeMap minimum size".into(),
+        MinSize(size.start()),
+    ))
+}
 
-            if !self.elements[ix].simplify() {
-                // Move on to the next element
-                self.shrink = Shrink::ShrinkElement(ix + 1);
-            } else {
-                self.prev_shrink = Some(self.shrink);
-                return true;
-            }
-        }
+impl<K: Strategy, V: Strategy, F: Fn(&K::Value) -> V> fmt::Debug
+    for BTreeMapWithStrategy<K, V, F>
+where
+    K::Value: Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("BTreeMapWithStrategy")
+            .field(&self.0)
+            .finish()
+    }
+}
 
-        panic!("Unexpected shrink state");
+impl<K: Strategy + Clone, V: Strategy, F: Fn(&K::Value) -> V> Clone
+    for BTreeMapWithStrategy<K, V, F>
+where
+    K::Value: Clone,
+{
+    fn clone(&self) -> Self {
+        BTreeMapWithStrategy(self.0.clone())
     }
+}
 
-    fn complicate(&mut self) -> bool {
-        match self.prev_shrink {
-            None => false,
-            Some(Shrink::DeleteElement(ix)) => {
-                // Undo the last item we deleted. Can't complicate any further,
-                // so unset prev_shrink.
-                self.included_elements.set(ix);
-                self.prev_shrink = None;
-                true
-            }
-            Some(Shrink::ShrinkElement(ix)) => {
-                if self.elements[ix].complicate() {
-                    // Don't unset prev_shrink; we may be able to complicate
-                    // again.
-                    true
-                } else {
-                    // Can't complicate the last element any further.
-                    self.prev_shrink = None;
-                    false
-                }
-            }
-        }
+impl<K: Strategy, V: Strategy, F: Fn(&K::Value) -> V> Strategy
+    for BTreeMapWithStrategy<K, V, F>
+where
+    K::Value: Ord + Clone,
+{
+    type Tree = BTreeMapWithValueTree<K::Tree, V, F>;
+    type Value = BTreeMap<K::Value, V::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.0.new_tree(runner).map(BTreeMapWithValueTree)
     }
 }
 
-//==============================================================================
-// Tests
-//==============================================================================
+impl<K: ValueTree, V: Strategy, F: Fn(&K::Value) -> V> fmt::Debug
+    for BTreeMapWithValueTree<K, V, F>
+where
+    K: fmt::Debug,
+    K::Value: Clone,
+    V::Tree: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("BTreeMapWithValueTree")
+            .field(&self.0)
+            .finish()
+    }
+}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+impl<K: ValueTree, V: Strategy, F: Fn(&K::Value) -> V> Clone
+    for BTreeMapWithValueTree<K, V, F>
+where
+    K: Clone,
+    K::Value: Clone,
+    V: Clone,
+    V::Tree: Clone,
+{
+    fn clone(&self) -> Self {
+        BTreeMapWithValueTree(self.0.clone())
+    }
+}
 
-    use crate::bits;
+impl<K: ValueTree, V: Strategy, F: Fn(&K::Value) -> V> ValueTree
+    for BTreeMapWithValueTree<K, V, F>
+where
+    K::Value: Ord + Clone,
+{
+    type Value = BTreeMap<K::Value, V::Value>;
 
-    #[test]
-    fn test_vec() {
-        let input = vec(1usize..20usize, 5..20);
-        let mut num_successes = 0;
+    delegate_vt_0!();
+}
 
-        let mut runner = TestRunner::deterministic();
-        for _ in 0..256 {
-            let case = input.new_tree(&mut runner).unwrap();
-            let start = case.current();
-            // Has correct length
-            assert!(start.len() >= 5 && start.len() < 20);
-            // Has at least 2 distinct values
-            assert!(start.iter().map(|&v| v).collect::<VarBitSet>().len() >= 2);
+mapfn! {
+    {#[cfg(feature = "std")]}
+    [] fn VecDedup[<T : fmt::Debug + Hash + Eq>](vec: Vec<T>) -> Vec<T> {
+        let keep = {
+            let mut seen = HashSet::new();
+            vec.iter().map(|e| seen.insert(e)).collect::<Vec<_>>()
+        };
+        vec.into_iter()
+            .zip(keep)
+            .filter_map(|(e, keep)| if keep { Some(e) } else { None })
+            .collect()
+    }
+}
 
-            let result = runner.run_one(case, |v| {
-                prop_assert!(
-                    v.iter().map(|&v| v).sum::<usize>() < 9,
-                    "greater than 8"
-                );
-                Ok(())
-            });
+#[cfg(feature = "std")]
+struct VecDedupByKey<T, K>(fn(&T) -> K);
 
-            match result {
-                Ok(true) => num_successes += 1,
-                Err(TestError::Fail(_, value)) => {
-                    // The minimal case always has between 5 (due to min
-                    // length) and 9 (min element value = 1) elements, and
-                    // always sums to exactly 9.
-                    assert!(
-                        value.len() >= 5
-                            && value.len() <= 9
-                            && value.iter().map(|&v| v).sum::<usize>() == 9,
+#[cfg(feature = "std")]
+impl<T, K> Clone for VecDedupByKey<T, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, K> Copy for VecDedupByKey<T, K> {}
+
+#[cfg(feature = "std")]
+impl<T, K> fmt::Debug for VecDedupByKey<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("VecDedupByKey").field(&"<function>").finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug, K: Hash + Eq> statics::MapFn<Vec<T>>
+    for VecDedupByKey<T, K>
+{
+    type Output = Vec<T>;
+
+    fn apply(&self, mut vec: Vec<T>) -> Vec<T> {
+        let mut seen = HashSet::new();
+        vec.retain(|e| seen.insert((self.0)(e)));
+        vec
+    }
+}
+
+impl<T> statics::FilterFn<Vec<T>> for MinSize {
+    fn apply(&self, vec: &Vec<T>) -> bool {
+        vec.len() >= self.0
+    }
+}
+
+opaque_strategy_wrapper! {
+    {#[cfg(feature = "std")]}
+    {#[cfg_attr(docsrs, doc(cfg(feature = "std")))]}
+    /// Strategy to create `Vec`s of pairwise distinct elements with a length
+    /// in a certain range.
+    ///
+    /// Created by the `vec_distinct()` function in the same module.
+    #[derive(Clone, Debug)]
+    pub struct VecDistinctStrategy[<T>][where T : Strategy, T::Value : Hash + Eq](
+        statics::Filter<statics::Map<VecStrategy<T>, VecDedup>, MinSize>)
+        -> VecDistinctValueTree<T::Tree>;
+    /// `ValueTree` corresponding to `VecDistinctStrategy`.
+    #[derive(Clone, Debug)]
+    pub struct VecDistinctValueTree[<T>][where T : ValueTree, T::Value : Hash + Eq](
+        statics::Filter<statics::Map<VecValueTree<T>, VecDedup>, MinSize>)
+        -> Vec<T::Value>;
+}
+
+/// Create a strategy to generate `Vec`s of pairwise distinct elements drawn
+/// from `element` and with a size range given by `size`.
+///
+/// Unlike collecting a `hash_set()` into a `Vec`, the elements keep the order
+/// in which they were generated; when `element` produces a value equal to an
+/// earlier one, the later occurrence is dropped. This also applies while
+/// shrinking, so an element which shrinks into a duplicate is removed.
+///
+/// This strategy will implicitly do local rejects to ensure that the `Vec`
+/// has at least the minimum number of elements, in case `element` should
+/// produce duplicate values.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn vec_distinct<T: Strategy>(
+    element: T,
+    size: impl Into<SizeRange>,
+) -> VecDistinctStrategy<T>
+where
+    T::Value: Hash + Eq,
+{
+    let size = size.into();
+    VecDistinctStrategy(statics::Filter::new(
+        statics::Map::new(vec(element, size.clone()), VecDedup),
+        "Vec minimum distinct size".into(),
+        MinSize(size.start()),
+    ))
+}
+
+opaque_strategy_wrapper! {
+    {#[cfg(feature = "std")]}
+    {#[cfg_attr(docsrs, doc(cfg(feature = "std")))]}
+    /// Strategy to create `Vec`s of elements with pairwise distinct keys and
+    /// with a length in a certain range.
+    ///
+    /// Created by the `vec_distinct_by_key()` function in the same module.
+    #[derive(Clone, Debug)]
+    pub struct VecDistinctByKeyStrategy[<T, K>]
+        [where T : Strategy, K : fmt::Debug + Hash + Eq](
+            statics::Filter<statics::Map<VecStrategy<T>,
+            VecDedupByKey<T::Value, K>>, MinSize>)
+        -> VecDistinctByKeyValueTree<T::Tree, K>;
+    /// `ValueTree` corresponding to `VecDistinctByKeyStrategy`.
+    #[derive(Clone, Debug)]
+    pub struct VecDistinctByKeyValueTree[<T, K>]
+        [where T : ValueTree, K : fmt::Debug + Hash + Eq](
+            statics::Filter<statics::Map<VecValueTree<T>,
+            VecDedupByKey<T::Value, K>>, MinSize>)
+        -> Vec<T::Value>;
+}
+
+/// Like `vec_distinct()`, but elements are considered duplicates when `key`
+/// maps them to equal values rather than when they are themselves equal.
+///
+/// The first element generated for each key is kept, and the `Vec` preserves
+/// generation order.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn vec_distinct_by_key<T: Strategy, K: fmt::Debug + Hash + Eq>(
+    element: T,
+    size: impl Into<SizeRange>,
+    key: fn(&T::Value) -> K,
+) -> VecDistinctByKeyStrategy<T, K> {
+    let size = size.into();
+    VecDistinctByKeyStrategy(statics::Filter::new(
+        statics::Map::new(vec(element, size.clone()), VecDedupByKey(key)),
+        "Vec minimum distinct size".into(),
+        MinSize(size.start()),
+    ))
+}
+
+/// Strategy to create `Vec`s sorted in ascending order with a length in a
+/// certain range.
+///
+/// Created by the `sorted_vec()` function in the same module.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct SortedVecStrategy<T: Strategy>(VecStrategy<T>);
+
+/// `ValueTree` corresponding to `SortedVecStrategy`.
+#[derive(Clone, Debug)]
+pub struct SortedVecValueTree<T: ValueTree>(VecValueTree<T>);
+
+/// Create a strategy to generate `Vec`s sorted in ascending order, containing
+/// elements drawn from `element` and with a size range given by `size`.
+///
+/// Sorting the output of `vec()` with `prop_map` also produces sorted values,
+/// but shrinking an element then moves it to a different position in the
+/// result. Here the elements are sorted once when the value is generated and
+/// each element only shrinks as far as it can without falling below its
+/// predecessor, so every shrink candidate is sorted in its own right.
+pub fn sorted_vec<T: Strategy>(
+    element: T,
+    size: impl Into<SizeRange>,
+) -> SortedVecStrategy<T>
+where
+    T::Value: Ord,
+{
+    SortedVecStrategy(vec(element, size))
+}
+
+impl<T: Strategy> Strategy for SortedVecStrategy<T>
+where
+    T::Value: Ord,
+{
+    type Tree = SortedVecValueTree<T::Tree>;
+    type Value = Vec<T::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let mut inner = self.0.new_tree(runner)?;
+        inner.elements.sort_by_cached_key(|e| e.current());
+        Ok(SortedVecValueTree(inner))
+    }
+}
+
+impl<T: ValueTree> ValueTree for SortedVecValueTree<T>
+where
+    T::Value: Ord,
+{
+    type Value = Vec<T::Value>;
+
+    fn current(&self) -> Vec<T::Value> {
+        self.0.current()
+    }
+
+    fn simplify(&mut self) -> bool {
+        if self.0.simplify() {
+            self.0.ensure_ordered(|a, b| a <= b);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn complicate(&mut self) -> bool {
+        if self.0.complicate() {
+            self.0.ensure_ordered(|a, b| a <= b);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The ordering invariant maintained by `MonotonicVecStrategy`.
+#[derive(Clone, Debug)]
+struct Monotonic<V> {
+    decreasing: bool,
+    min_gap: Option<V>,
+}
+
+impl<V: Ord + CheckedSub> Monotonic<V> {
+    fn ordered(&self, prev: &V, next: &V) -> bool {
+        let (lo, hi) = if self.decreasing {
+            (next, prev)
+        } else {
+            (prev, next)
+        };
+        // A difference too large to represent certainly exceeds the gap.
+        lo < hi
+            && self.min_gap.as_ref().is_none_or(|gap| {
+                hi.checked_sub(lo).is_none_or(|diff| diff >= *gap)
+            })
+    }
+}
+
+/// Strategy to create strictly monotonic `Vec`s with a length in a certain
+/// range.
+///
+/// Created by the `monotonic_vec()` function in the same module.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct MonotonicVecStrategy<T: Strategy>
+where
+    T::Value: Clone,
+{
+    vec: VecStrategy<T>,
+    order: Monotonic<T::Value>,
+}
+
+/// `ValueTree` corresponding to `MonotonicVecStrategy`.
+#[derive(Clone, Debug)]
+pub struct MonotonicVecValueTree<T: ValueTree>
+where
+    T::Value: Clone,
+{
+    inner: VecValueTree<T>,
+    order: Monotonic<T::Value>,
+}
+
+/// Create a strategy to generate strictly increasing `Vec`s containing
+/// elements drawn from `element` and with a size range given by `size`.
+///
+/// Use `MonotonicVecStrategy::decreasing` to generate strictly decreasing
+/// `Vec`s instead, and `MonotonicVecStrategy::min_gap` to require adjacent
+/// elements to differ by at least a given amount.
+///
+/// Like `sorted_vec()`, elements are ordered when the value is generated and
+/// only shrink as far as the ordering invariant allows. Generated elements
+/// which cannot be placed in the sequence, such as duplicates, are dropped;
+/// this strategy will implicitly do local rejects to ensure that the `Vec`
+/// has at least the minimum number of elements.
+pub fn monotonic_vec<T: Strategy>(
+    element: T,
+    size: impl Into<SizeRange>,
+) -> MonotonicVecStrategy<T>
+where
+    T::Value: Ord + CheckedSub + Clone,
+{
+    MonotonicVecStrategy {
+        vec: vec(element, size),
+        order: Monotonic {
+            decreasing: false,
+            min_gap: None,
+        },
+    }
+}
+
+impl<T: Strategy> MonotonicVecStrategy<T>
+where
+    T::Value: Ord + CheckedSub + Clone,
+{
+    /// Generate strictly decreasing rather than strictly increasing `Vec`s.
+    pub fn decreasing(mut self) -> Self {
+        self.order.decreasing = true;
+        self
+    }
+
+    /// Require adjacent elements to differ by at least `gap`.
+    pub fn min_gap(mut self, gap: T::Value) -> Self {
+        self.order.min_gap = Some(gap);
+        self
+    }
+}
+
+impl<T: Strategy> Strategy for MonotonicVecStrategy<T>
+where
+    T::Value: Ord + CheckedSub + Clone,
+{
+    type Tree = MonotonicVecValueTree<T::Tree>;
+    type Value = Vec<T::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        loop {
+            let mut inner = self.vec.new_tree(runner)?;
+            inner.elements.sort_by_cached_key(|e| e.current());
+            if self.order.decreasing {
+                inner.elements.reverse();
+            }
+
+            // Greedily keep each element which can follow the last one kept,
+            // which yields the longest sequence satisfying the invariant.
+            let mut last = None;
+            inner.elements.retain(|e| {
+                let value = e.current();
+                let keep = last
+                    .as_ref()
+                    .is_none_or(|last| self.order.ordered(last, &value));
+                if keep {
+                    last = Some(value);
+                }
+                keep
+            });
+
+            if inner.elements.len() >= inner.min_size {
+                inner.included_elements =
+                    VarBitSet::saturated(inner.elements.len());
+                return Ok(MonotonicVecValueTree {
+                    inner,
+                    order: self.order.clone(),
+                });
+            }
+
+            runner.reject_local("monotonic Vec minimum size")?;
+        }
+    }
+}
+
+impl<T: ValueTree> ValueTree for MonotonicVecValueTree<T>
+where
+    T::Value: Ord + CheckedSub + Clone,
+{
+    type Value = Vec<T::Value>;
+
+    fn current(&self) -> Vec<T::Value> {
+        self.inner.current()
+    }
+
+    fn simplify(&mut self) -> bool {
+        if self.inner.simplify() {
+            let order = &self.order;
+            self.inner.ensure_ordered(|a, b| order.ordered(a, b));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn complicate(&mut self) -> bool {
+        if self.inner.complicate() {
+            let order = &self.order;
+            self.inner.ensure_ordered(|a, b| order.ordered(a, b));
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// A rectangular N-dimensional array of values, stored in row-major order.
+///
+/// Generated by the `nd_vec()` function in the same module. The element at
+/// index `[i0, i1, ..., in]` is found at offset
+/// `((i0 * shape[1] + i1) * shape[2] + ...) + in` of `data`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NdVec<T> {
+    /// The length of each dimension.
+    pub shape: Vec<usize>,
+    /// The elements, with the last dimension varying fastest.
+    pub data: Vec<T>,
+}
+
+/// Strategy to create rectangular N-dimensional arrays with the length of
+/// each dimension in a certain range.
+///
+/// Created by the `nd_vec()` function in the same module.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct NdVecStrategy<T: Strategy> {
+    element: T,
+    shape: Vec<SizeRange>,
+}
+
+/// Create a strategy to generate rectangular N-dimensional arrays containing
+/// elements drawn from `element`, where the length of each dimension is drawn
+/// from the corresponding size range in `shape`.
+///
+/// Unlike nesting `vec()`s, every slice along a dimension has the same
+/// length. Shrinking first deletes whole slices, such as rows or columns,
+/// from each dimension down to its minimum length, and then shrinks the
+/// remaining elements.
+pub fn nd_vec<T: Strategy, S: Into<SizeRange>>(
+    element: T,
+    shape: impl IntoIterator<Item = S>,
+) -> NdVecStrategy<T> {
+    let shape = shape.into_iter().map(Into::into).collect::<Vec<_>>();
+    for size in &shape {
+        size.assert_nonempty();
+    }
+    NdVecStrategy { element, shape }
+}
+
+impl<T: Strategy> Strategy for NdVecStrategy<T> {
+    type Tree = NdVecValueTree<T::Tree>;
+    type Value = NdVec<T::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let mut shape = Vec::with_capacity(self.shape.len());
+        let mut min_shape = Vec::with_capacity(self.shape.len());
+        for size in &self.shape {
+            let (start, end) = size.start_end_incl();
+            let end = runner.sized(start, end);
+            shape.push(sample_uniform_incl(runner, start, end));
+            min_shape.push(start);
+        }
+
+        let len = shape.iter().product();
+        let mut elements = Vec::with_capacity(len);
+        while elements.len() < len {
+            elements.push(self.element.new_tree(runner)?);
+        }
+
+        Ok(NdVecValueTree {
+            elements,
+            included: shape.iter().map(|&n| VarBitSet::saturated(n)).collect(),
+            shape,
+            min_shape,
+            shrink: NdShrink::DeleteSlice(0, 0),
+            prev_shrink: None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NdShrink {
+    DeleteSlice(usize, usize),
+    ShrinkElement(usize),
+}
+
+/// `ValueTree` corresponding to `NdVecStrategy`.
+#[derive(Clone, Debug)]
+pub struct NdVecValueTree<T: ValueTree> {
+    elements: Vec<T>,
+    shape: Vec<usize>,
+    // For each dimension, which of its original indices are still present.
+    included: Vec<VarBitSet>,
+    min_shape: Vec<usize>,
+    shrink: NdShrink,
+    prev_shrink: Option<NdShrink>,
+}
+
+impl<T: ValueTree> NdVecValueTree<T> {
+    /// Whether the element at offset `ix` of `elements` lies in a slice which
+    /// has not been deleted.
+    fn is_included(&self, mut ix: usize) -> bool {
+        for (&len, included) in self.shape.iter().zip(&self.included).rev() {
+            if !included.test(ix % len) {
+                return false;
+            }
+            ix /= len;
+        }
+        true
+    }
+}
+
+impl<T: ValueTree> ValueTree for NdVecValueTree<T> {
+    type Value = NdVec<T::Value>;
+
+    fn current(&self) -> NdVec<T::Value> {
+        NdVec {
+            shape: self.included.iter().map(BitSetLike::count).collect(),
+            data: self
+                .elements
+                .iter()
+                .enumerate()
+                .filter(|&(ix, _)| self.is_included(ix))
+                .map(|(_, element)| element.current())
+                .collect(),
+        }
+    }
+
+    fn simplify(&mut self) -> bool {
+        // As with `VecValueTree`, delete as much as we can before shrinking
+        // the remaining elements, one dimension at a time.
+        while let NdShrink::DeleteSlice(dim, ix) = self.shrink {
+            if dim >= self.shape.len() {
+                self.shrink = NdShrink::ShrinkElement(0);
+            } else if ix >= self.shape[dim]
+                || self.included[dim].count() == self.min_shape[dim]
+            {
+                self.shrink = NdShrink::DeleteSlice(dim + 1, 0);
+            } else if !self.included[dim].test(ix) {
+                self.shrink = NdShrink::DeleteSlice(dim, ix + 1);
+            } else {
+                self.included[dim].clear(ix);
+                self.prev_shrink = Some(self.shrink);
+                self.shrink = NdShrink::DeleteSlice(dim, ix + 1);
+                return true;
+            }
+        }
+
+        while let NdShrink::ShrinkElement(ix) = self.shrink {
+            if ix >= self.elements.len() {
+                // Nothing more we can do
+                return false;
+            }
+
+            if !self.is_included(ix) {
+                // No use shrinking something we're not including.
+                self.shrink = NdShrink::ShrinkElement(ix + 1);
+                continue;
+            }
+
+            if !self.elements[ix].simplify() {
+                // Move on to the next element
+                self.shrink = NdShrink::ShrinkElement(ix + 1);
+            } else {
+                self.prev_shrink = Some(self.shrink);
+                return true;
+            }
+        }
+
+        panic!("Unexpected shrink state");
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.prev_shrink {
+            None => false,
+            Some(NdShrink::DeleteSlice(dim, ix)) => {
+                // Undo the last slice we deleted. Can't complicate any
+                // further, so unset prev_shrink.
+                self.included[dim].set(ix);
+                self.prev_shrink = None;
+                true
+            }
+            Some(NdShrink::ShrinkElement(ix)) => {
+                if self.elements[ix].complicate() {
+                    // Don't unset prev_shrink; we may be able to complicate
+                    // again.
+                    true
+                } else {
+                    // Can't complicate the last element any further.
+                    self.prev_shrink = None;
+                    false
+                }
+            }
+        }
+    }
+}
+
+mapfn! {
+    [] fn NdVecToMatrix[<T : fmt::Debug>](nd: NdVec<T>) -> Vec<Vec<T>> {
+        let (rows, cols) = (nd.shape[0], nd.shape[1]);
+        let mut data = nd.data.into_iter();
+        (0..rows).map(|_| data.by_ref().take(cols).collect()).collect()
+    }
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy to create matrices with row and column counts in certain
+    /// ranges.
+    ///
+    /// Created by the `matrix()` function in the same module.
+    #[derive(Clone, Debug)]
+    pub struct MatrixStrategy[<T>][where T : Strategy](
+        statics::Map<NdVecStrategy<T>, NdVecToMatrix>)
+        -> MatrixValueTree<T::Tree>;
+    /// `ValueTree` corresponding to `MatrixStrategy`.
+    #[derive(Clone, Debug)]
+    pub struct MatrixValueTree[<T>][where T : ValueTree](
+        statics::Map<NdVecValueTree<T>, NdVecToMatrix>)
+        -> Vec<Vec<T::Value>>;
+}
+
+/// Create a strategy to generate matrices, as a `Vec` of rows, containing
+/// elements drawn from `element` with a number of rows in the range `rows`
+/// and a number of columns in the range `cols`.
+///
+/// Every row has the same length. Shrinking deletes whole rows and columns
+/// before shrinking individual elements; see `nd_vec()` for arrays of other
+/// dimensions.
+pub fn matrix<T: Strategy>(
+    element: T,
+    rows: impl Into<SizeRange>,
+    cols: impl Into<SizeRange>,
+) -> MatrixStrategy<T> {
+    MatrixStrategy(statics::Map::new(
+        nd_vec(element, [rows.into(), cols.into()]),
+        NdVecToMatrix,
+    ))
+}
+
+mapfn! {
+    [] fn DeltaSum[<T : fmt::Debug + Clone + Add<D, Output = T>,
+                    D : fmt::Debug>]
+        (input: (T, Vec<D>)) -> Vec<T>
+    {
+        let (mut prev, deltas) = input;
+        deltas
+            .into_iter()
+            .map(|delta| {
+                prev = prev.clone() + delta;
+                prev.clone()
+            })
+            .collect()
+    }
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy to create `Vec`s in which each element differs from the
+    /// previous one by a bounded delta.
+    ///
+    /// Created by the `delta_vec()` function in the same module.
+    #[derive(Clone, Debug)]
+    pub struct DeltaVecStrategy[<T, D>]
+        [where T : Strategy, D : Strategy,
+         T::Value : Clone + Add<D::Value, Output = T::Value>](
+            statics::Map<(T, VecStrategy<D>), DeltaSum>)
+        -> DeltaVecValueTree<T::Tree, D::Tree>;
+    /// `ValueTree` corresponding to `DeltaVecStrategy`.
+    #[derive(Clone, Debug)]
+    pub struct DeltaVecValueTree[<T, D>]
+        [where T : ValueTree, D : ValueTree,
+         T::Value : Clone + Add<D::Value, Output = T::Value>](
+            statics::Map<TupleValueTree<(T, VecValueTree<D>)>, DeltaSum>)
+        -> Vec<T::Value>;
+}
+
+/// Create a strategy to generate `Vec`s with a length in the given range,
+/// such as sensor readings, prices or timestamps, in which each element is
+/// the previous element plus a delta drawn from `delta`. The first element
+/// is the value drawn from `start` plus a delta.
+///
+/// Any types which can be added together work, so `delta` can be a numeric
+/// range like `-5i32..=5`, or a range of `Duration`s to advance an
+/// `Instant` or `SystemTime`. The caller is responsible for choosing `start`
+/// and `delta` such that the sums do not overflow.
+///
+/// Shrinking removes elements from the sequence and shrinks the deltas as
+/// well as the start value, so sequences shrink toward the constant sequence
+/// when `delta` shrinks toward zero.
+///
+/// ```
+/// use proptest::collection::delta_vec;
+/// use proptest::prelude::*;
+///
+/// proptest! {
+///     fn readings_change_slowly(
+///         readings in delta_vec(-100i32..100, -3i32..=3, 1..50)
+///     ) {
+///         for pair in readings.windows(2) {
+///             prop_assert!((pair[1] - pair[0]).abs() <= 3);
+///         }
+///     }
+/// }
+/// # fn main() { readings_change_slowly(); }
+/// ```
+pub fn delta_vec<T: Strategy, D: Strategy>(
+    start: T,
+    delta: D,
+    size: impl Into<SizeRange>,
+) -> DeltaVecStrategy<T, D>
+where
+    T::Value: Clone + Add<D::Value, Output = T::Value>,
+{
+    DeltaVecStrategy(statics::Map::new((start, vec(delta, size)), DeltaSum))
+}
+
+#[derive(Clone, Copy, Debug)]
+#[allow(clippy::enum_variant_names)]
+enum Shrink {
+    // Delete the next run of the given number of included elements, starting
+    // at the given index.
+    DeleteChunk(usize, usize),
+    DeleteElement(usize),
+    ShrinkElement(usize),
+}
+
+/// `ValueTree` corresponding to `VecStrategy`.
+#[derive(Clone, Debug)]
+pub struct VecValueTree<T: ValueTree> {
+    elements: Vec<T>,
+    included_elements: VarBitSet,
+    min_size: usize,
+    shrink: Shrink,
+    prev_shrink: Option<Shrink>,
+    // The elements removed by the last `DeleteChunk`, to restore them on
+    // `complicate()`.
+    deleted_chunk: Vec<usize>,
+}
+
+impl<T: Strategy> Strategy for VecStrategy<T> {
+    type Tree = VecValueTree<T::Tree>;
+    type Value = Vec<T::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let (start, end) = self.size.start_end_incl();
+        let end = runner.sized(start, end);
+        let max_size = sample_uniform_incl(runner, start, end);
+        let mut elements = Vec::with_capacity(max_size);
+        while elements.len() < max_size {
+            elements.push(self.element.new_tree(runner)?);
+        }
+
+        Ok(VecValueTree::new(elements, start))
+    }
+
+    fn describe(&self) -> Description {
+        let (start, end) = self.size.start_end_incl();
+        Description::new("vec")
+            .param("size", start..=end)
+            .child(self.element.describe())
+    }
+}
+
+impl<T: Strategy> Strategy for Vec<T> {
+    type Tree = VecValueTree<T::Tree>;
+    type Value = Vec<T::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let len = self.len();
+        let elements = self
+            .iter()
+            .map(|t| t.new_tree(runner))
+            .collect::<Result<Vec<_>, Reason>>()?;
+
+        Ok(VecValueTree {
+            elements,
+            included_elements: VarBitSet::saturated(len),
+            min_size: len,
+            shrink: Shrink::ShrinkElement(0),
+            prev_shrink: None,
+            deleted_chunk: Vec::new(),
+        })
+    }
+
+    fn describe(&self) -> Description {
+        self.iter().fold(Description::new("vec"), |description, s| {
+            description.child(s.describe())
+        })
+    }
+}
+
+impl<T: ValueTree> VecValueTree<T> {
+    /// Create a tree over `elements` which shrinks to no fewer than
+    /// `min_size` of them.
+    pub(crate) fn new(elements: Vec<T>, min_size: usize) -> Self {
+        let len = elements.len();
+        VecValueTree {
+            elements,
+            included_elements: VarBitSet::saturated(len),
+            min_size,
+            shrink: Shrink::DeleteChunk(len / 2, 0),
+            prev_shrink: None,
+            deleted_chunk: Vec::new(),
+        }
+    }
+
+    /// Complicate until every pair of adjacent included elements satisfies
+    /// `ordered`. This always succeeds if the value this tree started from
+    /// did.
+    fn ensure_ordered(
+        &mut self,
+        ordered: impl Fn(&T::Value, &T::Value) -> bool,
+    ) {
+        while !self.current().windows(2).all(|w| ordered(&w[0], &w[1])) {
+            if !self.complicate() {
+                panic!(
+                    "Unable to complicate ordered Vec back into \
+                     acceptable value"
+                );
+            }
+        }
+    }
+}
+
+impl<T: ValueTree> ValueTree for VecValueTree<T> {
+    type Value = Vec<T::Value>;
+
+    fn current(&self) -> Vec<T::Value> {
+        self.elements
+            .iter()
+            .enumerate()
+            .filter(|&(ix, _)| self.included_elements.test(ix))
+            .map(|(_, element)| element.current())
+            .collect()
+    }
+
+    fn simplify(&mut self) -> bool {
+        // The overall strategy here is to iteratively delete elements from the
+        // list until we can do so no further, then to shrink each remaining
+        // element in sequence.
+        //
+        // Deletion starts with chunks of half the list, then halves the chunk
+        // size after each pass over the list, in the style of delta
+        // debugging, so that large lists lose most of their irrelevant
+        // elements without one attempt per element. Single elements are then
+        // deleted as before.
+        //
+        // For `complicate()`, we simply undo the last shrink operation, if
+        // there was any.
+        while let Shrink::DeleteChunk(size, ix) = self.shrink {
+            if size <= 1 {
+                self.shrink = Shrink::DeleteElement(0);
+                break;
+            }
+
+            let chunk = (ix..self.elements.len())
+                .filter(|&ix| self.included_elements.test(ix))
+                .take(size)
+                .collect::<Vec<_>>();
+            if chunk.is_empty()
+                || self.included_elements.count() - chunk.len() < self.min_size
+            {
+                // End of this pass, or deleting the chunk would put us under
+                // the minimum length; try again with smaller chunks.
+                self.shrink = Shrink::DeleteChunk(size / 2, 0);
+                continue;
+            }
+
+            for &ix in &chunk {
+                self.included_elements.clear(ix);
+            }
+            self.prev_shrink = Some(self.shrink);
+            self.shrink = Shrink::DeleteChunk(size, chunk[chunk.len() - 1] + 1);
+            self.deleted_chunk = chunk;
+            return true;
+        }
+
+        while let Shrink::DeleteElement(ix) = self.shrink {
+            // Can't delete an element if beyond the end of the vec or if it
+            // would put us under the minimum length.
+            if ix >= self.elements.len()
+                || self.included_elements.count() == self.min_size
+            {
+                self.shrink = Shrink::ShrinkElement(0);
+            } else if !self.included_elements.test(ix) {
+                // Already deleted along with a chunk.
+                self.shrink = Shrink::DeleteElement(ix + 1);
+            } else {
+                self.included_elements.clear(ix);
+                self.prev_shrink = Some(self.shrink);
+                self.shrink = Shrink::DeleteElement(ix + 1);
+                return true;
+            }
+        }
+
+        while let Shrink::ShrinkElement(ix) = self.shrink {
+            if ix >= self.elements.len() {
+                // Nothing more we can do
+                return false;
+            }
+
+            if !self.included_elements.test(ix) {
+                // No use shrinking something we're not including.
+                self.shrink = Shrink::ShrinkElement(ix + 1);
+                continue;
+            }
+
+            if !self.elements[ix].simplify() {
+                // Move on to the next element
+                self.shrink = Shrink::ShrinkElement(ix + 1);
+            } else {
+                self.prev_shrink = Some(self.shrink);
+                return true;
+            }
+        }
+
+        panic!("Unexpected shrink state");
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.prev_shrink {
+            None => false,
+            Some(Shrink::DeleteChunk(..)) => {
+                // Undo the last chunk we deleted. Can't complicate any
+                // further, so unset prev_shrink.
+                for &ix in &self.deleted_chunk {
+                    self.included_elements.set(ix);
+                }
+                self.prev_shrink = None;
+                true
+            }
+            Some(Shrink::DeleteElement(ix)) => {
+                // Undo the last item we deleted. Can't complicate any further,
+                // so unset prev_shrink.
+                self.included_elements.set(ix);
+                self.prev_shrink = None;
+                true
+            }
+            Some(Shrink::ShrinkElement(ix)) => {
+                if self.elements[ix].complicate() {
+                    // Don't unset prev_shrink; we may be able to complicate
+                    // again.
+                    true
+                } else {
+                    // Can't complicate the last element any further.
+                    self.prev_shrink = None;
+                    false
+                }
+            }
+        }
+    }
+}
+
+//==============================================================================
+// Tests
+//==============================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::bits;
+
+    #[test]
+    fn test_vec() {
+        let input = vec(1usize..20usize, 5..20);
+        let mut num_successes = 0;
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let case = input.new_tree(&mut runner).unwrap();
+            let start = case.current();
+            // Has correct length
+            assert!(start.len() >= 5 && start.len() < 20);
+            // Has at least 2 distinct values
+            assert!(start.iter().map(|&v| v).collect::<VarBitSet>().len() >= 2);
+
+            let result = runner.run_one(case, |v| {
+                prop_assert!(
+                    v.iter().map(|&v| v).sum::<usize>() < 9,
+                    "greater than 8"
+                );
+                Ok(())
+            });
+
+            match result {
+                Ok(true) => num_successes += 1,
+                Err(TestError::Fail(_, value)) => {
+                    // The minimal case always has between 5 (due to min
+                    // length) and 9 (min element value = 1) elements, and
+                    // always sums to exactly 9.
+                    assert!(
+                        value.len() >= 5
+                            && value.len() <= 9
+                            && value.iter().map(|&v| v).sum::<usize>() == 9,
                         "Unexpected minimal value: {:?}",
                         value
                     );
@@ -724,6 +1823,30 @@ mod test {
         assert!(num_successes < 256);
     }
 
+    #[test]
+    fn test_vec_deletes_chunks() {
+        let mut runner = TestRunner::deterministic();
+        let mut case = vec(0u32..10, 10_000).new_tree(&mut runner).unwrap();
+        case.min_size = 0;
+        assert!(case.current().contains(&7));
+
+        // Deleting one element at a time would take at least 10,000 steps.
+        let mut steps = 0;
+        loop {
+            steps += 1;
+            let more = if case.current().contains(&7) {
+                case.simplify()
+            } else {
+                case.complicate()
+            };
+            if !more {
+                break;
+            }
+        }
+        assert_eq!(vec![7], case.current());
+        assert!(steps < 1000, "Took {} steps to shrink", steps);
+    }
+
     #[test]
     fn test_vec_sanity() {
         check_strategy_sanity(vec(0i32..1000, 5..10), None);
@@ -776,4 +1899,377 @@ mod test {
             assert_eq!(2, v.len());
         }
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vec_distinct() {
+        // Only 8 possible values
+        let input = vec_distinct("[ab]{3}", 2..5);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..256 {
+            let v = input.new_tree(&mut runner).unwrap().current();
+            assert!((2..5).contains(&v.len()));
+            assert_eq!(v.len(), v.iter().collect::<HashSet<_>>().len());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vec_distinct_preserves_order() {
+        let input = vec_distinct(0u8..4, 4);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            loop {
+                let mut v = case.current();
+                assert_eq!(4, v.len());
+                v.sort();
+                assert_eq!(vec![0, 1, 2, 3], v);
+                if !case.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vec_distinct_shrinks_to_distinct() {
+        let input = vec_distinct(0u32..1000, 3..10);
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&input, |v| {
+            prop_assert!(v.iter().sum::<u32>() < 100);
+            Ok(())
+        });
+
+        match result {
+            Err(TestError::Fail(_, value)) => {
+                assert_eq!(
+                    value.len(),
+                    value.iter().collect::<HashSet<_>>().len()
+                );
+                assert!(value.len() >= 3);
+            }
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vec_distinct_by_key() {
+        let input =
+            vec_distinct_by_key((0u32..100, 0u32..100), 1..8, |&(k, _)| k % 8);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..256 {
+            let v = input.new_tree(&mut runner).unwrap().current();
+            assert!((1..8).contains(&v.len()));
+            let keys = v.iter().map(|&(k, _)| k % 8).collect::<HashSet<_>>();
+            assert_eq!(v.len(), keys.len());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vec_distinct_sanity() {
+        check_strategy_sanity(
+            vec_distinct(0i32..1000, 5..10),
+            Some(CheckStrategySanityOptions {
+                // Shrinking an element into a duplicate drops it, which can
+                // fall below the minimum size and be rejected internally.
+                strict_complicate_after_simplify: false,
+                ..CheckStrategySanityOptions::default()
+            }),
+        );
+    }
+
+    #[test]
+    fn test_sorted_vec_shrinks_sorted() {
+        let input = sorted_vec(0u32..1000, 2..10);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            loop {
+                let v = case.current();
+                assert!((2..10).contains(&v.len()));
+                assert!(v.windows(2).all(|w| w[0] <= w[1]));
+                if !case.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sorted_vec_minimal() {
+        let input = sorted_vec(0u32..1000, 1..10);
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&input, |v| {
+            prop_assert!(v[v.len() - 1] < 500);
+            Ok(())
+        });
+
+        match result {
+            Err(TestError::Fail(_, value)) => assert_eq!(vec![500], value),
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_monotonic_vec_increasing() {
+        let input = monotonic_vec(0u8..50, 3..10).min_gap(2);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            loop {
+                let v = case.current();
+                assert!((3..10).contains(&v.len()));
+                assert!(v.windows(2).all(|w| w[0] + 2 <= w[1]));
+                if !case.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_monotonic_vec_decreasing() {
+        let input = monotonic_vec(-5i32..5, 4).decreasing();
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            loop {
+                let v = case.current();
+                assert_eq!(4, v.len());
+                assert!(v.windows(2).all(|w| w[0] > w[1]));
+                if !case.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_monotonic_vec_gap_overflow() {
+        let input = monotonic_vec(crate::num::i8::ANY, 2..4).min_gap(100);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let v = input.new_tree(&mut runner).unwrap().current();
+            assert!(v
+                .windows(2)
+                .all(|w| i16::from(w[1]) - i16::from(w[0]) >= 100));
+        }
+    }
+
+    #[test]
+    fn test_sorted_vec_sanity() {
+        check_strategy_sanity(
+            sorted_vec(0i32..1000, 5..10),
+            Some(CheckStrategySanityOptions {
+                // Elements which shrink out of order are complicated back,
+                // so `simplify()` can converge on what `complicate()` does.
+                strict_complicate_after_simplify: false,
+                ..CheckStrategySanityOptions::default()
+            }),
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash_map_with() {
+        let input = hash_map_with(0u32..100, |&k| (Just(k), 0u32..=k), 1..10);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            loop {
+                let map = case.current();
+                assert!((1..10).contains(&map.len()));
+                for (k, &(embedded, v)) in &map {
+                    assert_eq!(*k, embedded);
+                    assert!(v <= *k);
+                }
+                if !case.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_btree_map_with_minimal() {
+        let input = btree_map_with(0u32..1000, |&k| k..k + 10, 1..5);
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&input, |map| {
+            prop_assert!(map.keys().all(|&k| k < 500));
+            Ok(())
+        });
+
+        match result {
+            Err(TestError::Fail(_, value)) => {
+                // Shrinking the key regenerates its value, so the key need
+                // not reach 500 exactly, but the value follows it down.
+                assert_eq!(1, value.len());
+                let (&k, &v) = value.iter().next().unwrap();
+                assert!((500..510).contains(&k));
+                assert_eq!(k, v);
+            }
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_btree_map_with_sanity() {
+        check_strategy_sanity(
+            btree_map_with(0i32..1000, |&k| (k - 10)..=k, 1..4),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_matrix_is_rectangular() {
+        let input = matrix(0u32..100, 1..5, 0..4);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            loop {
+                let m = case.current();
+                assert!((1..5).contains(&m.len()));
+                assert!((0..4).contains(&m[0].len()));
+                assert!(m.iter().all(|row| row.len() == m[0].len()));
+                if !case.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matrix_shrinks_rows_and_columns() {
+        let input = matrix(0u32..100, 1..10, 1..10);
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&input, |m| {
+            prop_assert!(m.iter().flatten().all(|&v| v < 50));
+            Ok(())
+        });
+
+        match result {
+            Err(TestError::Fail(_, value)) => {
+                assert_eq!(vec![vec![50]], value)
+            }
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_nd_vec() {
+        let input = nd_vec(0u8..10, [1..3, 2..4, 0..3]);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            loop {
+                let nd = case.current();
+                assert_eq!(3, nd.shape.len());
+                assert!((1..3).contains(&nd.shape[0]));
+                assert!((2..4).contains(&nd.shape[1]));
+                assert!((0..3).contains(&nd.shape[2]));
+                assert_eq!(nd.shape.iter().product::<usize>(), nd.data.len());
+                if !case.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nd_vec_keeps_layout() {
+        // Deleting the first slice of the outermost dimension drops the
+        // first 8 elements and leaves the rest in row-major order.
+        let mut runner = TestRunner::deterministic();
+        let input = nd_vec(0u32..1000, [1..4, 4..5, 2..3]);
+        for _ in 0..16 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            let original = case.current();
+            if original.shape[0] < 2 {
+                continue;
+            }
+            assert!(case.simplify());
+            let nd = case.current();
+            assert_eq!(vec![original.shape[0] - 1, 4, 2], nd.shape);
+            assert_eq!(&original.data[8..], &nd.data[..]);
+        }
+    }
+
+    #[test]
+    fn test_nd_vec_sanity() {
+        check_strategy_sanity(nd_vec(0i32..1000, [1..3, 1..4]), None);
+    }
+
+    #[test]
+    fn test_delta_vec_bounded() {
+        let input = delta_vec(-100i32..100, -3i32..=3, 1..50);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            loop {
+                let v = case.current();
+                assert!((1..50).contains(&v.len()));
+                assert!(v.windows(2).all(|w| (w[1] - w[0]).abs() <= 3));
+                if !case.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_delta_vec_shrinks_to_constant() {
+        let input = delta_vec(0i32..100, -10i32..=10, 1..20);
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&input, |v| {
+            prop_assert!(v.len() < 5);
+            Ok(())
+        });
+
+        match result {
+            Err(TestError::Fail(_, value)) => assert_eq!(vec![0; 5], value),
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_delta_vec_durations() {
+        use std::time::{Duration, Instant};
+
+        let now = Instant::now();
+        let input = delta_vec(
+            Just(now),
+            (0u64..1000).prop_map(Duration::from_millis),
+            0..20,
+        );
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let v = input.new_tree(&mut runner).unwrap().current();
+            assert!(v.first().is_none_or(|&t| t >= now));
+            assert!(v
+                .windows(2)
+                .all(|w| w[0] <= w[1] && w[1] - w[0] < Duration::from_secs(1)));
+        }
+    }
+
+    #[test]
+    fn test_delta_vec_sanity() {
+        check_strategy_sanity(delta_vec(0i64..1000, -5i64..=5, 0..10), None);
+    }
 }
@@ -0,0 +1,182 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Adapters between proptest and the `Arbitrary` trait of the `arbitrary`
+//! crate.
+//!
+//! [`from_arbitrary`] turns any type implementing `arbitrary::Arbitrary`,
+//! such as one which derives it for fuzzing, into a proptest strategy. The
+//! strategy generates a buffer of bytes and builds the value from it with
+//! `arbitrary::Unstructured`; shrinking the buffer, by removing bytes and
+//! moving them toward zero, shrinks the value built from it.
+//!
+//! ```rust
+//! use proptest::compat::arbitrary::from_arbitrary;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn sort_is_idempotent(mut v in from_arbitrary::<Vec<u16>>()) {
+//!         v.sort();
+//!         let sorted = v.clone();
+//!         v.sort();
+//!         prop_assert_eq!(sorted, v);
+//!     }
+//! }
+//! # fn main() { sort_is_idempotent(); }
+//! ```
+//!
+//! Going the other way, [`generate`] draws a value of a proptest strategy
+//! from an `Unstructured`, for use in a hand-written `Arbitrary` impl or with
+//! `#[arbitrary(with = ...)]` on a derived one.
+
+use ::arbitrary::{Arbitrary, Unstructured};
+
+use crate::collection::{self, SizeRange, VecStrategy};
+use crate::fuzz::decode_measured;
+use crate::num;
+use crate::std_facade::Vec;
+use crate::strategy::*;
+
+/// The default range of the number of bytes a value is built from.
+const DEFAULT_LEN: core::ops::Range<usize> = 0..1024;
+
+/// Strategy returned by [`from_arbitrary`] and [`from_arbitrary_with`].
+pub type FromArbitrary<T> =
+    FilterMap<VecStrategy<num::u8::Any>, fn(Vec<u8>) -> Option<T>>;
+
+fn build<T: for<'a> Arbitrary<'a>>(bytes: Vec<u8>) -> Option<T> {
+    T::arbitrary(&mut Unstructured::new(&bytes)).ok()
+}
+
+/// Generates values of `T` through its implementation of
+/// `arbitrary::Arbitrary`, each built from up to 1024 bytes.
+///
+/// Values which `T::arbitrary` fails to build are rejected, like those of a
+/// filter.
+pub fn from_arbitrary<T>() -> FromArbitrary<T>
+where
+    T: for<'a> Arbitrary<'a> + core::fmt::Debug,
+{
+    from_arbitrary_with(DEFAULT_LEN)
+}
+
+/// Like [`from_arbitrary`], but builds each value from a number of bytes in
+/// `len`, which bounds the size of the values `T::arbitrary` can build.
+pub fn from_arbitrary_with<T>(len: impl Into<SizeRange>) -> FromArbitrary<T>
+where
+    T: for<'a> Arbitrary<'a> + core::fmt::Debug,
+{
+    collection::vec(num::u8::ANY, len).prop_filter_map(
+        "arbitrary::Arbitrary failed to build a value",
+        build::<T> as fn(Vec<u8>) -> Option<T>,
+    )
+}
+
+/// Draws a value of `strategy` from the bytes of `u`, consuming those used.
+///
+/// The bytes take the place of the random number generator, as in
+/// [`fuzz::decode`](crate::fuzz::decode), so the same bytes always produce
+/// the same value. Once the bytes run out, generation continues with a fixed
+/// pseudo-random sequence. Returns `arbitrary::Error::IncorrectFormat` if
+/// `strategy` rejects every value it can generate from the bytes.
+///
+/// This matches the signature expected by `#[arbitrary(with = ...)]`, once
+/// the strategy is fixed:
+///
+/// ```rust
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use proptest::compat::arbitrary::generate;
+///
+/// #[derive(Debug)]
+/// struct Port(u16);
+///
+/// impl<'a> Arbitrary<'a> for Port {
+///     fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+///         generate(&(1024..=u16::MAX), u).map(Port)
+///     }
+/// }
+///
+/// let port = Port::arbitrary(&mut Unstructured::new(&[1, 2, 3, 4])).unwrap();
+/// assert!(port.0 >= 1024);
+/// ```
+pub fn generate<S: Strategy>(
+    strategy: &S,
+    u: &mut Unstructured<'_>,
+) -> ::arbitrary::Result<S::Value> {
+    let data = u.peek_bytes(u.len()).unwrap_or(&[]);
+    let (value, consumed) = decode_measured(strategy, data)
+        .ok_or(::arbitrary::Error::IncorrectFormat)?;
+    u.bytes(consumed.min(data.len()))?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_runner::*;
+
+    #[test]
+    fn builds_values_through_arbitrary() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = from_arbitrary::<(bool, Vec<u32>)>();
+        let mut non_empty = 0;
+        for _ in 0..256 {
+            let (_, v) = strategy.new_tree(&mut runner).unwrap().current();
+            if !v.is_empty() {
+                non_empty += 1;
+            }
+        }
+        assert!(non_empty > 0);
+    }
+
+    #[test]
+    fn shrinks_through_the_bytes() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&from_arbitrary::<u8>(), |x| {
+            prop_assert!(x < 100);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, x)) => assert_eq!(100, x),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&from_arbitrary::<Vec<u32>>(), |v| {
+            prop_assert!(v.iter().all(|&x| x <= 1000));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, v)) => {
+                assert_eq!(1, v.len(), "{:?}", v);
+                assert!(v[0] > 1000, "{:?}", v);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn generates_from_unstructured() {
+        let data = [7u8; 64];
+        let mut u = Unstructured::new(&data);
+        let first = generate(&(0..10u32), &mut u).unwrap();
+        assert!(first < 10);
+        assert!(u.len() < data.len());
+
+        let mut u = Unstructured::new(&data);
+        assert_eq!(first, generate(&(0..10u32), &mut u).unwrap());
+
+        let mut u = Unstructured::new(&[]);
+        assert!(generate(&(0..10u32), &mut u).is_ok());
+        assert_eq!(
+            Err(::arbitrary::Error::IncorrectFormat),
+            generate(&(0..10u32).prop_filter("never", |_| false), &mut u)
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An adapter from the `Arbitrary` trait of the `quickcheck` crate to
+//! proptest strategies, to ease moving a test suite from quickcheck to
+//! proptest.
+//!
+//! [`from_quickcheck`] generates values with the `arbitrary` method of
+//! `quickcheck::Arbitrary` and shrinks them with its `shrink` method. The
+//! `quickcheck::Gen` is seeded from the test runner, so failures reproduce
+//! like those of any other strategy.
+//!
+//! ```rust
+//! use proptest::compat::quickcheck::from_quickcheck;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn reverse_twice(v in from_quickcheck::<Vec<i32>>()) {
+//!         let mut w = v.clone();
+//!         w.reverse();
+//!         w.reverse();
+//!         prop_assert_eq!(v, w);
+//!     }
+//! }
+//! # fn main() { reverse_twice(); }
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::quickcheck::{Arbitrary, Gen};
+use rand::RngCore;
+
+use crate::std_facade::Box;
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// The size of the `Gen` used by [`from_quickcheck`], which is also the
+/// default of quickcheck itself.
+const DEFAULT_SIZE: usize = 100;
+
+/// Generates and shrinks values of `T` through its implementation of
+/// `quickcheck::Arbitrary`, with a `Gen` of size 100.
+pub fn from_quickcheck<T: Arbitrary + fmt::Debug>() -> FromQuickcheck<T> {
+    from_quickcheck_with(DEFAULT_SIZE)
+}
+
+/// Like [`from_quickcheck`], but with a `Gen` of the given size, which
+/// bounds the size of collections and numbers quickcheck generates.
+pub fn from_quickcheck_with<T: Arbitrary + fmt::Debug>(
+    size: usize,
+) -> FromQuickcheck<T> {
+    FromQuickcheck {
+        size,
+        _marker: PhantomData,
+    }
+}
+
+/// Strategy returned by [`from_quickcheck`] and [`from_quickcheck_with`].
+#[must_use = "strategies do nothing unless used"]
+pub struct FromQuickcheck<T> {
+    size: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for FromQuickcheck<T> {
+    fn clone(&self) -> Self {
+        FromQuickcheck {
+            size: self.size,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for FromQuickcheck<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FromQuickcheck")
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<T: Arbitrary + fmt::Debug> Strategy for FromQuickcheck<T> {
+    type Tree = QuickcheckValueTree<T>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let seed = runner.rng().next_u64();
+        let value = T::arbitrary(&mut Gen::from_size_and_seed(self.size, seed));
+        Ok(QuickcheckValueTree {
+            shrinks: value.shrink(),
+            accepted: value,
+            candidate: None,
+        })
+    }
+}
+
+/// `ValueTree` corresponding to `FromQuickcheck`.
+///
+/// Each call to `simplify` tries the next value which `shrink` yields for the
+/// simplest value known to fail, and moves on to the shrinks of the value
+/// tried if it fails as well.
+pub struct QuickcheckValueTree<T> {
+    /// The simplest value known to fail.
+    accepted: T,
+    /// The remaining shrinks of `accepted`.
+    shrinks: Box<dyn Iterator<Item = T>>,
+    /// The value being tried, if it is not `accepted`.
+    candidate: Option<T>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for QuickcheckValueTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("QuickcheckValueTree")
+            .field("accepted", &self.accepted)
+            .field("candidate", &self.candidate)
+            .finish()
+    }
+}
+
+impl<T: Arbitrary + fmt::Debug> ValueTree for QuickcheckValueTree<T> {
+    type Value = T;
+
+    fn current(&self) -> T {
+        self.candidate.as_ref().unwrap_or(&self.accepted).clone()
+    }
+
+    fn simplify(&mut self) -> bool {
+        // Being asked to simplify again without `complicate` in between
+        // means the candidate still fails, so shrink it instead.
+        if let Some(candidate) = self.candidate.take() {
+            self.shrinks = candidate.shrink();
+            self.accepted = candidate;
+        }
+        self.candidate = self.shrinks.next();
+        self.candidate.is_some()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.candidate.take().is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::std_facade::Vec;
+
+    #[test]
+    fn generation_is_reproducible() {
+        let strategy = from_quickcheck::<Vec<u32>>();
+        let generate = || {
+            let mut runner = TestRunner::deterministic();
+            (0..16)
+                .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+                .collect::<Vec<_>>()
+        };
+        let values = generate();
+        assert!(values.iter().any(|v| !v.is_empty()));
+        assert_eq!(values, generate());
+    }
+
+    #[test]
+    fn shrinks_with_quickcheck_shrinkers() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&from_quickcheck::<Vec<u32>>(), |v| {
+            prop_assert!(v.iter().all(|&x| x < 10));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, v)) => assert_eq!(vec![10], v),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn complicate_returns_to_the_failing_value() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = from_quickcheck_with::<i64>(1000)
+            .new_tree(&mut runner)
+            .unwrap();
+        while tree.current() == 0 {
+            tree = from_quickcheck_with::<i64>(1000)
+                .new_tree(&mut runner)
+                .unwrap();
+        }
+        let start = tree.current();
+        assert!(tree.simplify());
+        assert!(tree.complicate());
+        assert_eq!(start, tree.current());
+        assert!(!tree.complicate());
+    }
+}
@@ -0,0 +1,587 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating valid calendar dates and times, without
+//! depending on a date and time crate.
+//!
+//! Dates are in the proleptic Gregorian calendar, so every generated date
+//! exists: months have the right number of days, and February 29th only
+//! occurs in leap years. Times are in UTC and may optionally include leap
+//! seconds, which are only generated as `23:59:60` on the last day of June or
+//! December, where leap seconds are inserted.
+//!
+//! The values shrink toward 1970-01-01T00:00:00, or the nearest bound of the
+//! range of years if it does not include 1970.
+//!
+//! ```rust
+//! use proptest::date_time;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn parses_iso_8601(dt in date_time::date_times(1900..=2100)) {
+//!         let text = dt.to_string();
+//!         // ... parse `text` and compare the result with `dt`
+//!         prop_assert_eq!(text.len(), 19);
+//!     }
+//! }
+//! ```
+
+use core::fmt;
+use core::ops::{
+    Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo,
+    RangeToInclusive,
+};
+
+use crate::num;
+use crate::std_facade::Box;
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// The number of seconds in a day without a leap second.
+const SECONDS_PER_DAY: i64 = 86_400;
+/// The years to pick from when a range of years is unbounded, which are the
+/// years ISO 8601 represents with four digits.
+const DEFAULT_YEARS: RangeInclusive<i32> = 0..=9999;
+/// The year toward which leap seconds shrink.
+const EPOCH_YEAR: i64 = 1970;
+
+/// Returns whether `year` is a leap year in the Gregorian calendar.
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Returns the number of days in `month` (from 1 to 12) of `year`.
+///
+/// ## Panics
+///
+/// Panics if `month` is not between 1 and 12.
+pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => panic!("Invalid month {}", month),
+    }
+}
+
+/// A date in the proleptic Gregorian calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year, where 0 is 1 BC.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1.
+    pub day: u8,
+}
+
+impl Date {
+    /// Returns the given date, or `None` if it does not exist.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        if (1..=12).contains(&month)
+            && 1 <= day
+            && day <= days_in_month(year, month)
+        {
+            Some(Date { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the date `days` days after 1970-01-01.
+    fn from_days(days: i64) -> Self {
+        // See http://howardhinnant.github.io/date_algorithms.html
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460
+            + day_of_era / 36_524
+            - day_of_era / 146_096)
+            / 365;
+        let day_of_year = day_of_era
+            - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        // Months counted from March
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// Returns the number of days from 1970-01-01 to this date.
+    fn days(self) -> i64 {
+        let month = i64::from(self.month);
+        let year = i64::from(self.year) - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let shifted_month = (month + 9) % 12;
+        let day_of_year =
+            (153 * shifted_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4
+            - year_of_era / 100
+            + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+}
+
+impl From<Date> for (i32, u8, u8) {
+    fn from(date: Date) -> Self {
+        (date.year, date.month, date.day)
+    }
+}
+
+fn fmt_year(year: i32, f: &mut fmt::Formatter) -> fmt::Result {
+    if (0..=9999).contains(&year) {
+        write!(f, "{:04}", year)
+    } else {
+        // ISO 8601 expanded representation
+        write!(f, "{:+05}", year)
+    }
+}
+
+/// Formats the date as in ISO 8601, e.g. `2024-02-29`.
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_year(self.year, f)?;
+        write!(f, "-{:02}-{:02}", self.month, self.day)
+    }
+}
+
+/// A date and time of day in UTC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    /// The year, where 0 is 1 BC.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1.
+    pub day: u8,
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+    /// The second, from 0 to 59, or 60 for a leap second.
+    pub second: u8,
+}
+
+impl DateTime {
+    /// Returns the date of this date and time.
+    pub fn date(&self) -> Date {
+        Date {
+            year: self.year,
+            month: self.month,
+            day: self.day,
+        }
+    }
+
+    /// Returns the date and time `seconds` seconds after
+    /// 1970-01-01T00:00:00, not counting leap seconds.
+    fn from_seconds(seconds: i64) -> Self {
+        let date = Date::from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        let second_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+        DateTime {
+            year: date.year,
+            month: date.month,
+            day: date.day,
+            hour: (second_of_day / 3600) as u8,
+            minute: (second_of_day / 60 % 60) as u8,
+            second: (second_of_day % 60) as u8,
+        }
+    }
+
+    /// Returns the leap second at the end of the `half`th half year after
+    /// the first half of 1970.
+    fn leap_second(half: i64) -> Self {
+        let year = (EPOCH_YEAR + half.div_euclid(2)) as i32;
+        let (month, day) = if half.rem_euclid(2) == 0 {
+            (6, 30)
+        } else {
+            (12, 31)
+        };
+        DateTime {
+            year,
+            month,
+            day,
+            hour: 23,
+            minute: 59,
+            second: 60,
+        }
+    }
+}
+
+impl From<DateTime> for (i32, u8, u8, u8, u8, u8) {
+    fn from(dt: DateTime) -> Self {
+        (dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second)
+    }
+}
+
+/// Formats the date and time as in ISO 8601, e.g. `2016-12-31T23:59:60`.
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}T{:02}:{:02}:{:02}",
+            self.date(),
+            self.hour,
+            self.minute,
+            self.second
+        )
+    }
+}
+
+/// Returns the first and last of `years`, or of 0 to 9999 where unbounded.
+fn year_bounds(years: impl RangeBounds<i32>) -> (i32, i32) {
+    let start = match years.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => *DEFAULT_YEARS.start(),
+    };
+    let end = match years.end_bound() {
+        Bound::Included(&end) => end,
+        Bound::Excluded(&end) => end - 1,
+        Bound::Unbounded => *DEFAULT_YEARS.end(),
+    };
+    assert!(start <= end, "Empty range of years {}..={}", start, end);
+    (start, end)
+}
+
+/// Returns the days from 1970-01-01 to the first and last day of `years`.
+fn day_bounds(years: impl RangeBounds<i32>) -> (i64, i64) {
+    let (start, end) = year_bounds(years);
+    (
+        Date::new(start, 1, 1).unwrap().days(),
+        Date::new(end, 12, 31).unwrap().days(),
+    )
+}
+
+/// An inclusive range of years, used as the `Arbitrary` parameters of the
+/// date and time types of other crates.
+///
+/// It can be created from any range of `i32`s, such as `1900..2100`, and
+/// where the range is unbounded it extends to year 0 or 9999. The `Default`
+/// is `0..=9999`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct YearRange {
+    start: i32,
+    end: i32,
+}
+
+impl YearRange {
+    /// Creates a `YearRange` from the given range of years.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `years` is empty.
+    pub fn new(years: impl RangeBounds<i32>) -> Self {
+        let (start, end) = year_bounds(years);
+        YearRange { start, end }
+    }
+
+    /// The first year of the range.
+    pub fn start(&self) -> i32 {
+        self.start
+    }
+
+    /// The last year of the range.
+    pub fn end(&self) -> i32 {
+        self.end
+    }
+}
+
+impl Default for YearRange {
+    fn default() -> Self {
+        YearRange::new(DEFAULT_YEARS)
+    }
+}
+
+impl RangeBounds<i32> for YearRange {
+    fn start_bound(&self) -> Bound<&i32> {
+        Bound::Included(&self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&i32> {
+        Bound::Included(&self.end)
+    }
+}
+
+macro_rules! year_range_from {
+    ($($range:ty),*) => {$(
+        impl From<$range> for YearRange {
+            fn from(years: $range) -> Self {
+                YearRange::new(years)
+            }
+        }
+    )*};
+}
+
+year_range_from!(
+    Range<i32>,
+    RangeInclusive<i32>,
+    RangeFrom<i32>,
+    RangeTo<i32>,
+    RangeToInclusive<i32>,
+    RangeFull
+);
+
+/// Generates dates within the given range of years, such as `1900..2100`.
+/// Where the range is unbounded, it extends to year 0 or 9999.
+///
+/// Shrinks toward 1970-01-01, or the nearest bound of the range.
+///
+/// ## Panics
+///
+/// Panics if `years` is empty.
+pub fn dates(years: impl RangeBounds<i32>) -> DateStrategy {
+    let (start, end) = day_bounds(years);
+    DateStrategy { days: start..=end }
+}
+
+/// Strategy returned by [`dates`].
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct DateStrategy {
+    days: RangeInclusive<i64>,
+}
+
+impl Strategy for DateStrategy {
+    type Tree = DateValueTree;
+    type Value = Date;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.days.clone().new_tree(runner).map(DateValueTree)
+    }
+}
+
+/// `ValueTree` corresponding to `DateStrategy`.
+#[derive(Clone, Copy, Debug)]
+pub struct DateValueTree(num::i64::BinarySearch);
+
+impl ValueTree for DateValueTree {
+    type Value = Date;
+
+    fn current(&self) -> Date {
+        Date::from_days(self.0.current())
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.0.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.0.complicate()
+    }
+}
+
+/// Generates dates and times within the given range of years, such as
+/// `1900..2100`. Where the range is unbounded, it extends to year 0 or 9999.
+///
+/// Leap seconds are not generated unless enabled with
+/// [`DateTimeStrategy::with_leap_seconds`].
+///
+/// Shrinks toward 1970-01-01T00:00:00, or the nearest bound of the range.
+///
+/// ## Panics
+///
+/// Panics if `years` is empty.
+pub fn date_times(years: impl RangeBounds<i32>) -> DateTimeStrategy {
+    let (start, end) = year_bounds(years);
+    DateTimeStrategy {
+        years: start..=end,
+        leap_second_weight: 0,
+    }
+}
+
+/// Strategy returned by [`date_times`].
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct DateTimeStrategy {
+    years: RangeInclusive<i32>,
+    leap_second_weight: u32,
+}
+
+impl DateTimeStrategy {
+    /// Also generate leap seconds, as `23:59:60` on the last day of June or
+    /// December, in 1 of every 16 values.
+    ///
+    /// Leap seconds are generated on any such day, rather than only on those
+    /// where a leap second was actually inserted, and shrink toward the
+    /// values without leap seconds.
+    pub fn with_leap_seconds(self) -> Self {
+        DateTimeStrategy {
+            leap_second_weight: 1,
+            ..self
+        }
+    }
+}
+
+impl Strategy for DateTimeStrategy {
+    type Tree = Box<dyn ValueTree<Value = DateTime>>;
+    type Value = DateTime;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let (start, end) = day_bounds(self.years.clone());
+        let seconds = (start * SECONDS_PER_DAY
+            ..=end * SECONDS_PER_DAY + SECONDS_PER_DAY - 1)
+            .prop_map(DateTime::from_seconds);
+        if self.leap_second_weight == 0 {
+            return seconds.boxed().new_tree(runner);
+        }
+
+        let halves = (i64::from(*self.years.start()) - EPOCH_YEAR) * 2
+            ..=(i64::from(*self.years.end()) - EPOCH_YEAR) * 2 + 1;
+        let leap_seconds = halves.prop_map(DateTime::leap_second);
+        prop_oneof![
+            15 * self.leap_second_weight => seconds,
+            self.leap_second_weight => leap_seconds,
+        ]
+        .boxed()
+        .new_tree(runner)
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+pub mod chrono;
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+pub mod time;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn days_round_trip_through_dates() {
+        let mut expected = Date::new(-401, 1, 1).unwrap();
+        for days in expected.days()..Date::new(2401, 1, 1).unwrap().days() {
+            let date = Date::from_days(days);
+            assert_eq!(expected, date);
+            assert_eq!(days, date.days());
+
+            expected = if date.day < days_in_month(date.year, date.month) {
+                Date::new(date.year, date.month, date.day + 1)
+            } else if date.month < 12 {
+                Date::new(date.year, date.month + 1, 1)
+            } else {
+                Date::new(date.year + 1, 1, 1)
+            }
+            .unwrap();
+        }
+        assert_eq!(0, Date::new(1970, 1, 1).unwrap().days());
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(is_leap_year(2000));
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(-4));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2023));
+        assert_eq!(None, Date::new(2023, 2, 29));
+        assert!(Date::new(2024, 2, 29).is_some());
+        assert_eq!(None, Date::new(2024, 4, 31));
+        assert_eq!(None, Date::new(2024, 13, 1));
+    }
+
+    #[test]
+    fn formats_as_iso_8601() {
+        let dt = DateTime {
+            year: 2016,
+            month: 12,
+            day: 31,
+            hour: 23,
+            minute: 59,
+            second: 60,
+        };
+        assert_eq!("2016-12-31T23:59:60", dt.to_string());
+        assert_eq!("0042-01-02", Date::new(42, 1, 2).unwrap().to_string());
+        assert_eq!("-0044-03-15", Date::new(-44, 3, 15).unwrap().to_string());
+        assert_eq!("+10000-01-01", Date::new(10000, 1, 1).unwrap().to_string());
+    }
+
+    #[test]
+    fn generates_valid_values_in_range() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = date_times(1999..2001).with_leap_seconds();
+        let mut leap_seconds = 0;
+        for _ in 0..1024 {
+            let dt = strategy.new_tree(&mut runner).unwrap().current();
+            assert!((1999..2001).contains(&dt.year));
+            assert!(Date::new(dt.year, dt.month, dt.day).is_some());
+            assert!(dt.hour < 24 && dt.minute < 60);
+            if dt.second == 60 {
+                leap_seconds += 1;
+                assert!(matches!(
+                    (dt.month, dt.day, dt.hour, dt.minute),
+                    (6, 30, 23, 59) | (12, 31, 23, 59)
+                ));
+            } else {
+                assert!(dt.second < 60);
+            }
+        }
+        assert!(leap_seconds > 0);
+
+        for _ in 0..1024 {
+            let date = dates(..2).new_tree(&mut runner).unwrap().current();
+            assert!((0..2).contains(&date.year));
+            assert!(Date::new(date.year, date.month, date.day).is_some());
+        }
+    }
+
+    #[test]
+    fn year_ranges_are_inclusive() {
+        assert_eq!((0, 9999), {
+            let years = YearRange::default();
+            (years.start(), years.end())
+        });
+        assert_eq!(YearRange::new(1900..=2099), (1900..2100).into());
+        assert_eq!(YearRange::new(0..=1999), (..2000).into());
+        assert_eq!(YearRange::new(-44..=9999), (-44..).into());
+        assert_eq!(YearRange::default(), (..).into());
+        assert_eq!((1900, 2099), year_bounds(YearRange::new(1900..2100)));
+    }
+
+    #[test]
+    fn shrinks_toward_epoch() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&date_times(1900..=2100), |dt| {
+            prop_assert!(dt.year < 2000);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, dt)) => {
+                assert_eq!("2000-01-01T00:00:00", dt.to_string())
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&dates(2010..=2020), |date| {
+            prop_assert!(date.year < 2012);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, date)) => {
+                assert_eq!("2012-01-01", date.to_string())
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for the dates and times of the `chrono` crate.
+//!
+//! Dates are generated by the strategies of the parent module, so that only
+//! dates which exist are generated, and shrink toward 1970-01-01. Times of
+//! day shrink toward midnight, and include nanoseconds. Leap seconds are not
+//! generated.
+//!
+//! `NaiveDate`, `NaiveTime`, `NaiveDateTime` and `DateTime<Utc>` also
+//! implement `Arbitrary`. For the types with a date, the parameters are the
+//! [`YearRange`] to generate dates in.
+//!
+//! ```rust
+//! use chrono::Datelike;
+//! use proptest::date_time::chrono::naive_dates;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn ordinal_round_trips(date in naive_dates(1900..2100)) {
+//!         let ordinal = date.ordinal();
+//!         prop_assert_eq!(Some(date), date.with_ordinal(ordinal));
+//!     }
+//! }
+//! # fn main() { ordinal_round_trips(); }
+//! ```
+
+use core::ops::{Range, RangeBounds};
+
+use ::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use super::{dates, Date, DateStrategy, YearRange};
+use crate::arbitrary::Arbitrary;
+use crate::strategy::statics::{static_map, Map};
+
+/// The number of seconds in a day without a leap second.
+const SECONDS_PER_DAY: u32 = 86_400;
+/// The number of nanoseconds in a second.
+const NANOS_PER_SECOND: u32 = 1_000_000_000;
+
+/// Strategy returned by [`naive_dates`].
+pub type NaiveDateStrategy = Map<DateStrategy, fn(Date) -> NaiveDate>;
+
+/// Strategy returned by [`naive_times`].
+pub type NaiveTimeStrategy =
+    Map<(Range<u32>, Range<u32>), fn((u32, u32)) -> NaiveTime>;
+
+/// Strategy returned by [`naive_date_times`].
+pub type NaiveDateTimeStrategy = Map<
+    (NaiveDateStrategy, NaiveTimeStrategy),
+    fn((NaiveDate, NaiveTime)) -> NaiveDateTime,
+>;
+
+/// Strategy returned by [`date_times_utc`].
+pub type DateTimeUtcStrategy =
+    Map<NaiveDateTimeStrategy, fn(NaiveDateTime) -> DateTime<Utc>>;
+
+fn to_naive_date(date: Date) -> NaiveDate {
+    NaiveDate::from_ymd_opt(date.year, date.month.into(), date.day.into())
+        .expect("date out of the range of NaiveDate")
+}
+
+fn to_naive_time((seconds, nanos): (u32, u32)) -> NaiveTime {
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos).unwrap()
+}
+
+fn to_naive_date_time((date, time): (NaiveDate, NaiveTime)) -> NaiveDateTime {
+    NaiveDateTime::new(date, time)
+}
+
+fn to_date_time_utc(dt: NaiveDateTime) -> DateTime<Utc> {
+    dt.and_utc()
+}
+
+/// Generates `NaiveDate`s within the given range of years, such as
+/// `1900..2100`. Where the range is unbounded, it extends to year 0 or 9999.
+///
+/// Shrinks toward 1970-01-01, or the nearest bound of the range.
+///
+/// ## Panics
+///
+/// Panics if `years` is empty, or if it is not within the range of years
+/// `NaiveDate` can represent, when the strategy is used.
+pub fn naive_dates(years: impl RangeBounds<i32>) -> NaiveDateStrategy {
+    static_map(dates(years), to_naive_date as fn(Date) -> NaiveDate)
+}
+
+/// Generates `NaiveTime`s throughout the day, with nanoseconds.
+///
+/// Shrinks toward midnight.
+pub fn naive_times() -> NaiveTimeStrategy {
+    static_map(
+        (0..SECONDS_PER_DAY, 0..NANOS_PER_SECOND),
+        to_naive_time as fn((u32, u32)) -> NaiveTime,
+    )
+}
+
+/// Generates `NaiveDateTime`s within the given range of years, such as
+/// `1900..2100`. Where the range is unbounded, it extends to year 0 or 9999.
+///
+/// Shrinks toward 1970-01-01T00:00:00, or the nearest bound of the range.
+///
+/// ## Panics
+///
+/// Panics if `years` is empty, or if it is not within the range of years
+/// `NaiveDate` can represent, when the strategy is used.
+pub fn naive_date_times(years: impl RangeBounds<i32>) -> NaiveDateTimeStrategy {
+    static_map(
+        (naive_dates(years), naive_times()),
+        to_naive_date_time as fn((NaiveDate, NaiveTime)) -> NaiveDateTime,
+    )
+}
+
+/// Generates `DateTime<Utc>`s within the given range of years, such as
+/// `1900..2100`. Where the range is unbounded, it extends to year 0 or 9999.
+///
+/// Shrinks toward 1970-01-01T00:00:00Z, or the nearest bound of the range.
+///
+/// ## Panics
+///
+/// Panics if `years` is empty, or if it is not within the range of years
+/// `NaiveDate` can represent, when the strategy is used.
+pub fn date_times_utc(years: impl RangeBounds<i32>) -> DateTimeUtcStrategy {
+    static_map(
+        naive_date_times(years),
+        to_date_time_utc as fn(NaiveDateTime) -> DateTime<Utc>,
+    )
+}
+
+impl Arbitrary for NaiveDate {
+    type Parameters = YearRange;
+    type Strategy = NaiveDateStrategy;
+
+    fn arbitrary_with(years: YearRange) -> NaiveDateStrategy {
+        naive_dates(years)
+    }
+}
+
+impl Arbitrary for NaiveTime {
+    type Parameters = ();
+    type Strategy = NaiveTimeStrategy;
+
+    fn arbitrary_with((): ()) -> NaiveTimeStrategy {
+        naive_times()
+    }
+}
+
+impl Arbitrary for NaiveDateTime {
+    type Parameters = YearRange;
+    type Strategy = NaiveDateTimeStrategy;
+
+    fn arbitrary_with(years: YearRange) -> NaiveDateTimeStrategy {
+        naive_date_times(years)
+    }
+}
+
+impl Arbitrary for DateTime<Utc> {
+    type Parameters = YearRange;
+    type Strategy = DateTimeUtcStrategy;
+
+    fn arbitrary_with(years: YearRange) -> DateTimeUtcStrategy {
+        date_times_utc(years)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ::chrono::{Datelike, Timelike};
+    use std::string::ToString;
+
+    use super::*;
+    use crate::arbitrary::{any, any_with};
+    use crate::date_time::days_in_month;
+    use crate::strategy::*;
+    use crate::test_runner::*;
+
+    #[test]
+    fn generates_valid_dates_in_range() {
+        let mut runner = TestRunner::deterministic();
+        let mut leap_days = 0;
+        for _ in 0..4096 {
+            let date = naive_dates(1896..=1904)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!((1896..=1904).contains(&date.year()), "{}", date);
+            assert!(
+                date.day()
+                    <= u32::from(days_in_month(
+                        date.year(),
+                        date.month() as u8
+                    ))
+            );
+            if date.month() == 2 && date.day() == 29 {
+                leap_days += 1;
+                assert!(date.year() == 1896 || date.year() == 1904);
+            }
+        }
+        assert!(leap_days > 0);
+    }
+
+    #[test]
+    fn arbitrary_uses_year_range() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = any_with::<DateTime<Utc>>((-50..50).into());
+        for _ in 0..1024 {
+            let dt = strategy.new_tree(&mut runner).unwrap().current();
+            assert!((-50..50).contains(&dt.year()), "{}", dt);
+        }
+
+        let strategy = any::<NaiveDateTime>();
+        for _ in 0..1024 {
+            let dt = strategy.new_tree(&mut runner).unwrap().current();
+            assert!((0..=9999).contains(&dt.year()), "{}", dt);
+        }
+    }
+
+    #[test]
+    fn shrinks_toward_epoch() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let mut tree =
+                any::<DateTime<Utc>>().new_tree(&mut runner).unwrap();
+            while tree.simplify() {}
+            assert_eq!(DateTime::UNIX_EPOCH, tree.current());
+        }
+
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&naive_date_times(1900..=2100), |dt| {
+            prop_assert!(dt.year() < 2000 || dt.hour() < 12);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, dt)) => {
+                assert_eq!("2000-01-01 12:00:00", dt.to_string())
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(naive_dates(1900..2100), None);
+        check_strategy_sanity(any::<NaiveTime>(), None);
+        check_strategy_sanity(any::<DateTime<Utc>>(), None);
+    }
+}
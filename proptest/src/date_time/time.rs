@@ -0,0 +1,255 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for the dates and times of the `time` crate.
+//!
+//! Dates are generated by the strategies of the parent module, so that only
+//! dates which exist are generated, and shrink toward 1970-01-01. Times of
+//! day shrink toward midnight, and include nanoseconds. `OffsetDateTime`s are
+//! always in UTC.
+//!
+//! `Date`, `Time`, `PrimitiveDateTime` and `OffsetDateTime` also implement
+//! `Arbitrary`. For the types with a date, the parameters are the
+//! [`YearRange`] to generate dates in.
+//!
+//! ```rust
+//! use proptest::date_time::time::dates;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn julian_days_round_trip(date in dates(1900..2100)) {
+//!         let julian_day = date.to_julian_day();
+//!         prop_assert_eq!(Ok(date), time::Date::from_julian_day(julian_day));
+//!     }
+//! }
+//! # fn main() { julian_days_round_trip(); }
+//! ```
+
+use core::ops::{Range, RangeBounds};
+
+use ::time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+use super::YearRange;
+use crate::arbitrary::Arbitrary;
+use crate::strategy::statics::{static_map, Map};
+
+/// The number of seconds in a day.
+const SECONDS_PER_DAY: u32 = 86_400;
+/// The number of nanoseconds in a second.
+const NANOS_PER_SECOND: u32 = 1_000_000_000;
+
+/// Strategy returned by [`dates`].
+pub type DateStrategy = Map<super::DateStrategy, fn(super::Date) -> Date>;
+
+/// Strategy returned by [`times`].
+pub type TimeStrategy = Map<(Range<u32>, Range<u32>), fn((u32, u32)) -> Time>;
+
+/// Strategy returned by [`primitive_date_times`].
+pub type PrimitiveDateTimeStrategy =
+    Map<(DateStrategy, TimeStrategy), fn((Date, Time)) -> PrimitiveDateTime>;
+
+/// Strategy returned by [`offset_date_times`].
+pub type OffsetDateTimeStrategy =
+    Map<PrimitiveDateTimeStrategy, fn(PrimitiveDateTime) -> OffsetDateTime>;
+
+fn to_date(date: super::Date) -> Date {
+    Month::try_from(date.month)
+        .and_then(|month| Date::from_calendar_date(date.year, month, date.day))
+        .expect("date out of the range of time::Date")
+}
+
+fn to_time((seconds, nanos): (u32, u32)) -> Time {
+    Time::from_hms_nano(
+        (seconds / 3600) as u8,
+        (seconds / 60 % 60) as u8,
+        (seconds % 60) as u8,
+        nanos,
+    )
+    .unwrap()
+}
+
+fn to_primitive_date_time((date, time): (Date, Time)) -> PrimitiveDateTime {
+    PrimitiveDateTime::new(date, time)
+}
+
+fn to_offset_date_time(dt: PrimitiveDateTime) -> OffsetDateTime {
+    dt.assume_utc()
+}
+
+/// Generates `Date`s within the given range of years, such as `1900..2100`.
+/// Where the range is unbounded, it extends to year 0 or 9999.
+///
+/// Shrinks toward 1970-01-01, or the nearest bound of the range.
+///
+/// ## Panics
+///
+/// Panics if `years` is empty, or if it is not within the range of years
+/// `Date` can represent, when the strategy is used.
+pub fn dates(years: impl RangeBounds<i32>) -> DateStrategy {
+    static_map(super::dates(years), to_date as fn(super::Date) -> Date)
+}
+
+/// Generates `Time`s throughout the day, with nanoseconds.
+///
+/// Shrinks toward midnight.
+pub fn times() -> TimeStrategy {
+    static_map(
+        (0..SECONDS_PER_DAY, 0..NANOS_PER_SECOND),
+        to_time as fn((u32, u32)) -> Time,
+    )
+}
+
+/// Generates `PrimitiveDateTime`s within the given range of years, such as
+/// `1900..2100`. Where the range is unbounded, it extends to year 0 or 9999.
+///
+/// Shrinks toward 1970-01-01T00:00:00, or the nearest bound of the range.
+///
+/// ## Panics
+///
+/// Panics if `years` is empty, or if it is not within the range of years
+/// `Date` can represent, when the strategy is used.
+pub fn primitive_date_times(
+    years: impl RangeBounds<i32>,
+) -> PrimitiveDateTimeStrategy {
+    static_map(
+        (dates(years), times()),
+        to_primitive_date_time as fn((Date, Time)) -> PrimitiveDateTime,
+    )
+}
+
+/// Generates `OffsetDateTime`s in UTC within the given range of years, such
+/// as `1900..2100`. Where the range is unbounded, it extends to year 0 or
+/// 9999.
+///
+/// Shrinks toward 1970-01-01T00:00:00Z, or the nearest bound of the range.
+///
+/// ## Panics
+///
+/// Panics if `years` is empty, or if it is not within the range of years
+/// `Date` can represent, when the strategy is used.
+pub fn offset_date_times(
+    years: impl RangeBounds<i32>,
+) -> OffsetDateTimeStrategy {
+    static_map(
+        primitive_date_times(years),
+        to_offset_date_time as fn(PrimitiveDateTime) -> OffsetDateTime,
+    )
+}
+
+impl Arbitrary for Date {
+    type Parameters = YearRange;
+    type Strategy = DateStrategy;
+
+    fn arbitrary_with(years: YearRange) -> DateStrategy {
+        dates(years)
+    }
+}
+
+impl Arbitrary for Time {
+    type Parameters = ();
+    type Strategy = TimeStrategy;
+
+    fn arbitrary_with((): ()) -> TimeStrategy {
+        times()
+    }
+}
+
+impl Arbitrary for PrimitiveDateTime {
+    type Parameters = YearRange;
+    type Strategy = PrimitiveDateTimeStrategy;
+
+    fn arbitrary_with(years: YearRange) -> PrimitiveDateTimeStrategy {
+        primitive_date_times(years)
+    }
+}
+
+impl Arbitrary for OffsetDateTime {
+    type Parameters = YearRange;
+    type Strategy = OffsetDateTimeStrategy;
+
+    fn arbitrary_with(years: YearRange) -> OffsetDateTimeStrategy {
+        offset_date_times(years)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arbitrary::{any, any_with};
+    use crate::date_time::is_leap_year;
+    use crate::strategy::*;
+    use crate::test_runner::*;
+
+    #[test]
+    fn generates_valid_dates_in_range() {
+        let mut runner = TestRunner::deterministic();
+        let mut leap_days = 0;
+        for _ in 0..4096 {
+            let date =
+                dates(1896..=1904).new_tree(&mut runner).unwrap().current();
+            assert!((1896..=1904).contains(&date.year()), "{}", date);
+            if date.month() == Month::February && date.day() == 29 {
+                leap_days += 1;
+                assert!(is_leap_year(date.year()), "{}", date);
+            }
+        }
+        assert!(leap_days > 0);
+    }
+
+    #[test]
+    fn arbitrary_uses_year_range() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = any_with::<OffsetDateTime>((-50..50).into());
+        for _ in 0..1024 {
+            let dt = strategy.new_tree(&mut runner).unwrap().current();
+            assert!((-50..50).contains(&dt.year()), "{}", dt);
+            assert!(dt.offset().is_utc());
+        }
+
+        let strategy = any::<PrimitiveDateTime>();
+        for _ in 0..1024 {
+            let dt = strategy.new_tree(&mut runner).unwrap().current();
+            assert!((0..=9999).contains(&dt.year()), "{}", dt);
+        }
+    }
+
+    #[test]
+    fn shrinks_toward_epoch() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let mut tree =
+                any::<OffsetDateTime>().new_tree(&mut runner).unwrap();
+            while tree.simplify() {}
+            assert_eq!(OffsetDateTime::UNIX_EPOCH, tree.current());
+        }
+
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&primitive_date_times(1900..=2100), |dt| {
+            prop_assert!(dt.year() < 2000 || dt.hour() < 12);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, dt)) => assert_eq!(
+                PrimitiveDateTime::new(
+                    Date::from_calendar_date(2000, Month::January, 1).unwrap(),
+                    Time::from_hms(12, 0, 0).unwrap(),
+                ),
+                dt
+            ),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(dates(1900..2100), None);
+        check_strategy_sanity(any::<Time>(), None);
+        check_strategy_sanity(any::<OffsetDateTime>(), None);
+    }
+}
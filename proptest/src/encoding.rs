@@ -0,0 +1,506 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for pairs of bytes and their base64 or hex encoding.
+//!
+//! `base64()` and `hex()` generate `(raw, encoded)` pairs, where `encoded` is
+//! the encoding of `raw`, for testing that codecs round-trip. The encoding is
+//! computed from the bytes, so shrinking the bytes shrinks both halves of the
+//! pair together.
+//!
+//! With `corrupt` set in the parameters, `encoded` is instead made invalid,
+//! by an unexpected character, a truncation, wrong padding or, for base64,
+//! non-zero trailing bits, for testing that decoders reject it. `raw` is
+//! then the bytes which `encoded` was derived from.
+
+use crate::arbitrary::any;
+use crate::collection::{vec, SizeRange};
+use crate::sample::{select, Index};
+use crate::std_facade::{Box, String, Vec};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates bytes paired with their encoding.
+    ///
+    /// Created by the `base64()` and `hex()` functions in the same module.
+    #[derive(Debug)]
+    pub struct EncodingStrategy[][](SBoxedStrategy<(Vec<u8>, String)>)
+        -> EncodingValueTree;
+    /// `ValueTree` corresponding to `EncodingStrategy`.
+    pub struct EncodingValueTree[][](
+        Box<dyn ValueTree<Value = (Vec<u8>, String)>>)
+        -> (Vec<u8>, String);
+}
+
+/// A way of making an encoding invalid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Corruption {
+    /// A character is replaced by one outside the alphabet.
+    InvalidChar,
+    /// The encoding is cut to a length no valid encoding has.
+    Truncated,
+    /// Padding is missing or superfluous.
+    Padding,
+    /// The bits of the last character which do not encode any data are not
+    /// all zero.
+    TrailingBits,
+}
+
+/// Generates corruptions if `corrupt` is true, with indices selecting the
+/// position and the replacement character of `Corruption::InvalidChar`.
+fn corruption(
+    corrupt: bool,
+    corruptions: &'static [Corruption],
+) -> SBoxedStrategy<Option<(Corruption, Index, Index)>> {
+    if corrupt {
+        (select(corruptions), any::<Index>(), any::<Index>())
+            .prop_map(Some)
+            .sboxed()
+    } else {
+        Just(None).sboxed()
+    }
+}
+
+/// Replaces a character of `encoded`, selected by `position`, by one of the
+/// printable ASCII characters for which `valid` is false, selected by
+/// `replacement`.
+fn replace_char(
+    encoded: &mut Vec<u8>,
+    valid: impl Fn(u8) -> bool,
+    position: Index,
+    replacement: Index,
+) {
+    let invalid: Vec<u8> = (b' '..=b'~').filter(|&c| !valid(c)).collect();
+    let c = *replacement.get(&invalid);
+    if encoded.is_empty() {
+        encoded.push(c);
+    } else {
+        let position = position.index(encoded.len());
+        encoded[position] = c;
+    }
+}
+
+/// The alphabets of base64.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Base64Alphabet {
+    /// The standard alphabet of [RFC 4648][rfc4648], ending in `+` and `/`.
+    ///
+    /// [rfc4648]: https://www.rfc-editor.org/rfc/rfc4648#section-4
+    Standard,
+    /// The URL and filename safe alphabet of [RFC 4648][rfc4648], ending in
+    /// `-` and `_`.
+    ///
+    /// [rfc4648]: https://www.rfc-editor.org/rfc/rfc4648#section-5
+    UrlSafe,
+    /// The alphabet of `crypt(3)`, starting with `.` and `/` followed by
+    /// digits.
+    Crypt,
+    /// The alphabet of bcrypt, starting with `.` and `/` followed by
+    /// letters.
+    Bcrypt,
+    /// The alphabet of IMAP's modified UTF-7, ending in `+` and `,`.
+    ImapMutf7,
+    /// The alphabet of BinHex 4.0.
+    BinHex,
+}
+
+impl Base64Alphabet {
+    /// All the alphabets.
+    pub const ALL: &'static [Base64Alphabet] = &[
+        Base64Alphabet::Standard,
+        Base64Alphabet::UrlSafe,
+        Base64Alphabet::Crypt,
+        Base64Alphabet::Bcrypt,
+        Base64Alphabet::ImapMutf7,
+        Base64Alphabet::BinHex,
+    ];
+
+    /// Returns the characters encoding each value from 0 to 63.
+    pub fn chars(self) -> &'static [u8; 64] {
+        match self {
+            Base64Alphabet::Standard => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+            }
+            Base64Alphabet::UrlSafe => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+            }
+            Base64Alphabet::Crypt => {
+                b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz"
+            }
+            Base64Alphabet::Bcrypt => {
+                b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"
+            }
+            Base64Alphabet::ImapMutf7 => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+,"
+            }
+            Base64Alphabet::BinHex => {
+                b"!\"#$%&'()*+,-012345689@ABCDEFGHIJKLMNPQRSTUVXYZ[`abcdefhijklmpqr"
+            }
+        }
+    }
+}
+
+/// Parameters for generating base64 with `base64()`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Base64Params {
+    /// The alphabet to encode with.
+    ///
+    /// The default is `Base64Alphabet::Standard`.
+    pub alphabet: Base64Alphabet,
+    /// Whether encodings end with `=` padding to a multiple of 4
+    /// characters.
+    ///
+    /// The default is `true`.
+    pub padding: bool,
+    /// Whether to make encodings invalid.
+    ///
+    /// The default is `false`.
+    pub corrupt: bool,
+    /// The range of the number of raw bytes.
+    ///
+    /// The default is `0..64`.
+    pub len: SizeRange,
+}
+
+impl Default for Base64Params {
+    fn default() -> Self {
+        Base64Params {
+            alphabet: Base64Alphabet::Standard,
+            padding: true,
+            corrupt: false,
+            len: (0..64).into(),
+        }
+    }
+}
+
+/// Encodes `raw` in base64 with `alphabet`.
+fn encode_base64(alphabet: &[u8; 64], padding: bool, raw: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(raw.len().div_ceil(3) * 4);
+    for group in raw.chunks(3) {
+        let bits = group
+            .iter()
+            .enumerate()
+            .fold(0u32, |bits, (i, &b)| bits | u32::from(b) << (16 - 8 * i));
+        for i in 0..=group.len() {
+            encoded.push(alphabet[(bits >> (18 - 6 * i)) as usize & 0x3f]);
+        }
+        if padding {
+            encoded.resize(encoded.len() + 3 - group.len(), b'=');
+        }
+    }
+    encoded
+}
+
+/// Makes the base64 `encoded` invalid with `corruption`.
+fn corrupt_base64(
+    alphabet: &[u8; 64],
+    padding: bool,
+    encoded: &mut Vec<u8>,
+    (corruption, position, replacement): (Corruption, Index, Index),
+) {
+    let data_len = encoded
+        .iter()
+        .position(|&c| c == b'=')
+        .unwrap_or(encoded.len());
+    match corruption {
+        Corruption::Truncated => {
+            // No valid encoding has a length of 1 modulo 4, padded or not
+            if data_len == 0 {
+                encoded.clear();
+                encoded.push(alphabet[0]);
+            } else {
+                encoded.truncate((data_len - 1) / 4 * 4 + 1);
+            }
+        }
+        Corruption::Padding => {
+            if padding && data_len < encoded.len() {
+                encoded.pop();
+            } else {
+                encoded.push(b'=');
+            }
+        }
+        // Only the last character of a partial group has trailing bits
+        Corruption::TrailingBits if data_len % 4 >= 2 => {
+            let last = &mut encoded[data_len - 1];
+            let value = alphabet.iter().position(|c| c == last).unwrap();
+            *last = alphabet[value ^ 1];
+        }
+        Corruption::InvalidChar | Corruption::TrailingBits => replace_char(
+            encoded,
+            |c| c == b'=' || alphabet.contains(&c),
+            position,
+            replacement,
+        ),
+    }
+}
+
+/// Creates a strategy which generates bytes paired with their base64
+/// encoding, as described by [RFC 4648][rfc4648], with the alphabet and
+/// padding in `params`.
+///
+/// Encodings are canonical: the bits of the last character which do not
+/// encode any data are zero. With `params.corrupt`, encodings are instead
+/// invalid for a strict decoder with the same alphabet and padding.
+///
+/// [rfc4648]: https://www.rfc-editor.org/rfc/rfc4648#section-4
+pub fn base64(params: Base64Params) -> EncodingStrategy {
+    let alphabet = params.alphabet.chars();
+    let padding = params.padding;
+    let corruption = corruption(
+        params.corrupt,
+        &[
+            Corruption::InvalidChar,
+            Corruption::Truncated,
+            Corruption::Padding,
+            Corruption::TrailingBits,
+        ],
+    );
+    EncodingStrategy(
+        (vec(any::<u8>(), params.len), corruption)
+            .prop_map(move |(raw, corruption)| {
+                let mut encoded = encode_base64(alphabet, padding, &raw);
+                if let Some(corruption) = corruption {
+                    corrupt_base64(alphabet, padding, &mut encoded, corruption);
+                }
+                // Every alphabet is ASCII
+                (raw, String::from_utf8(encoded).unwrap())
+            })
+            .sboxed(),
+    )
+}
+
+/// The case of the letters in hex.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HexCase {
+    /// Lower case letters, as in `c0ffee`.
+    Lower,
+    /// Upper case letters, as in `C0FFEE`.
+    Upper,
+    /// Any mix of cases, as in `C0fFeE`.
+    Mixed,
+}
+
+/// Parameters for generating hex with `hex()`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HexParams {
+    /// The case of the letters.
+    ///
+    /// The default is `HexCase::Lower`.
+    pub case: HexCase,
+    /// Whether to make encodings invalid.
+    ///
+    /// The default is `false`.
+    pub corrupt: bool,
+    /// The range of the number of raw bytes.
+    ///
+    /// The default is `0..64`.
+    pub len: SizeRange,
+}
+
+impl Default for HexParams {
+    fn default() -> Self {
+        HexParams {
+            case: HexCase::Lower,
+            corrupt: false,
+            len: (0..64).into(),
+        }
+    }
+}
+
+/// Creates a strategy which generates bytes paired with their hex encoding,
+/// two digits per byte, most significant first, with letters in the case
+/// given by `params`.
+///
+/// With `params.corrupt`, encodings instead contain a character which is
+/// not a hex digit or have an odd length.
+pub fn hex(params: HexParams) -> EncodingStrategy {
+    let case = params.case;
+    let upper = match case {
+        HexCase::Lower => Just(Vec::new()).sboxed(),
+        HexCase::Upper => Just(vec![true]).sboxed(),
+        // Which letters are upper case, cycling over the encoding
+        HexCase::Mixed => vec(any::<bool>(), 1..16).sboxed(),
+    };
+    let corruption = corruption(
+        params.corrupt,
+        &[Corruption::InvalidChar, Corruption::Truncated],
+    );
+    EncodingStrategy(
+        (vec(any::<u8>(), params.len), upper, corruption)
+            .prop_map(|(raw, upper, corruption)| {
+                let mut encoded: Vec<u8> = raw
+                    .iter()
+                    .flat_map(|b| [b >> 4, b & 0xf])
+                    .map(|digit| b"0123456789abcdef"[usize::from(digit)])
+                    .collect();
+                if !upper.is_empty() {
+                    for (c, &upper) in
+                        encoded.iter_mut().zip(upper.iter().cycle())
+                    {
+                        if upper {
+                            c.make_ascii_uppercase();
+                        }
+                    }
+                }
+                match corruption {
+                    None => (),
+                    Some((Corruption::Truncated, _, _))
+                        if encoded.is_empty() =>
+                    {
+                        encoded.push(b'0')
+                    }
+                    Some((Corruption::Truncated, _, _)) => {
+                        encoded.pop();
+                    }
+                    Some((_, position, replacement)) => replace_char(
+                        &mut encoded,
+                        |c| c.is_ascii_hexdigit(),
+                        position,
+                        replacement,
+                    ),
+                }
+                (raw, String::from_utf8(encoded).unwrap())
+            })
+            .sboxed(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use ::base64::alphabet::{self, Alphabet};
+    use ::base64::engine::{
+        DecodePaddingMode, Engine, GeneralPurpose, GeneralPurposeConfig,
+    };
+
+    use super::*;
+
+    fn engine(params: &Base64Params) -> GeneralPurpose {
+        let alphabet: &Alphabet = match params.alphabet {
+            Base64Alphabet::Standard => &alphabet::STANDARD,
+            Base64Alphabet::UrlSafe => &alphabet::URL_SAFE,
+            Base64Alphabet::Crypt => &alphabet::CRYPT,
+            Base64Alphabet::Bcrypt => &alphabet::BCRYPT,
+            Base64Alphabet::ImapMutf7 => &alphabet::IMAP_MUTF7,
+            Base64Alphabet::BinHex => &alphabet::BIN_HEX,
+        };
+        let padding_mode = if params.padding {
+            DecodePaddingMode::RequireCanonical
+        } else {
+            DecodePaddingMode::RequireNone
+        };
+        GeneralPurpose::new(
+            alphabet,
+            GeneralPurposeConfig::new()
+                .with_encode_padding(params.padding)
+                .with_decode_padding_mode(padding_mode),
+        )
+    }
+
+    fn sample(input: EncodingStrategy) -> Vec<(Vec<u8>, String)> {
+        let mut runner = TestRunner::deterministic();
+        (0..256)
+            .map(|_| input.new_tree(&mut runner).unwrap().current())
+            .collect()
+    }
+
+    #[test]
+    fn base64_matches_reference() {
+        for &alphabet in Base64Alphabet::ALL {
+            for padding in [false, true] {
+                let params = Base64Params {
+                    alphabet,
+                    padding,
+                    ..Base64Params::default()
+                };
+                let engine = engine(&params);
+                for (raw, encoded) in sample(base64(params)) {
+                    assert_eq!(engine.encode(&raw), encoded);
+                    assert_eq!(raw, engine.decode(&encoded).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn corrupted_base64_is_rejected() {
+        for &alphabet in Base64Alphabet::ALL {
+            for padding in [false, true] {
+                let params = Base64Params {
+                    alphabet,
+                    padding,
+                    corrupt: true,
+                    ..Base64Params::default()
+                };
+                let engine = engine(&params);
+                for (_, encoded) in sample(base64(params)) {
+                    assert!(engine.decode(&encoded).is_err(), "{:?}", encoded);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hex_matches_reference() {
+        for case in [HexCase::Lower, HexCase::Upper, HexCase::Mixed] {
+            let params = HexParams {
+                case,
+                ..HexParams::default()
+            };
+            let samples = sample(hex(params));
+            for (raw, encoded) in &samples {
+                assert_eq!(raw, &::hex::decode(encoded).unwrap());
+                match case {
+                    HexCase::Lower => assert_eq!(::hex::encode(raw), *encoded),
+                    HexCase::Upper => {
+                        assert_eq!(::hex::encode_upper(raw), *encoded)
+                    }
+                    HexCase::Mixed => (),
+                }
+            }
+            if case == HexCase::Mixed {
+                assert!(samples.iter().any(|(_, encoded)| {
+                    encoded.contains(|c: char| c.is_ascii_uppercase())
+                        && encoded.contains(|c: char| c.is_ascii_lowercase())
+                }));
+            }
+        }
+    }
+
+    #[test]
+    fn corrupted_hex_is_rejected() {
+        let params = HexParams {
+            corrupt: true,
+            ..HexParams::default()
+        };
+        for (_, encoded) in sample(hex(params)) {
+            assert!(::hex::decode(&encoded).is_err(), "{:?}", encoded);
+        }
+    }
+
+    #[test]
+    fn shrinks_pairs_together() {
+        let mut runner = TestRunner::deterministic();
+        let result =
+            runner.run(&base64(Base64Params::default()), |(raw, _)| {
+                if raw.len() < 4 {
+                    Ok(())
+                } else {
+                    Err(TestCaseError::fail("too long"))
+                }
+            });
+        match result {
+            Err(TestError::Fail(_, (raw, encoded))) => {
+                assert_eq!(vec![0; 4], raw);
+                assert_eq!("AAAAAA==", encoded);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
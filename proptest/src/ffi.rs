@@ -0,0 +1,189 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating `std::ffi` types.
+//!
+//! The `Arbitrary` implementation for `OsString` only generates valid
+//! Unicode. `os_string()` also generates the contents which only an
+//! `OsString` can hold: bytes which are not UTF-8 on Unix, and unpaired
+//! surrogates on Windows.
+
+use std::ffi::{CString, OsString};
+
+use crate::arbitrary::any;
+use crate::collection::{vec, SizeRange};
+#[cfg(not(any(unix, windows)))]
+use crate::std_facade::String;
+use crate::std_facade::{Box, Vec};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates `CString`s.
+    ///
+    /// Created by the `c_string()` function in the same module.
+    #[derive(Debug)]
+    pub struct CStringStrategy[][](SBoxedStrategy<CString>)
+        -> CStringValueTree;
+    /// `ValueTree` corresponding to `CStringStrategy`.
+    pub struct CStringValueTree[][](Box<dyn ValueTree<Value = CString>>)
+        -> CString;
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates `OsString`s, including ones which are not
+    /// valid Unicode.
+    ///
+    /// Created by the `os_string()` function in the same module.
+    #[derive(Debug)]
+    pub struct OsStringStrategy[][](SBoxedStrategy<OsString>)
+        -> OsStringValueTree;
+    /// `ValueTree` corresponding to `OsStringStrategy`.
+    pub struct OsStringValueTree[][](Box<dyn ValueTree<Value = OsString>>)
+        -> OsString;
+}
+
+/// Creates a strategy which generates `CString`s whose length in bytes,
+/// excluding the terminating NUL, is within `size`.
+///
+/// The strings contain any bytes other than NUL, so most are not valid
+/// UTF-8. Bytes shrink toward printable ASCII.
+pub fn c_string(size: impl Into<SizeRange>) -> CStringStrategy {
+    let byte = prop_oneof![3 => b' '..=b'~', 1 => 1..=u8::MAX];
+    CStringStrategy(
+        vec(byte, size)
+            .prop_map(|bytes| {
+                CString::new(bytes).expect("generated an interior NUL")
+            })
+            .sboxed(),
+    )
+}
+
+/// A piece of an `OsString` which a `String` cannot represent.
+#[cfg(unix)]
+type Raw = u8;
+#[cfg(windows)]
+type Raw = u16;
+
+/// Creates a strategy which generates `OsString`s made of a number of units
+/// within `size`.
+///
+/// Each unit is usually a `char`, but on Unix may be a byte which does not
+/// form valid UTF-8 with its neighbours, and on Windows may be an unpaired
+/// surrogate. On other platforms, `OsString`s can only hold valid Unicode,
+/// and every unit is a `char`. Units shrink toward ASCII characters.
+pub fn os_string(size: impl Into<SizeRange>) -> OsStringStrategy {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+
+        let unit = prop_oneof![
+            3 => any::<char>().prop_map(Ok),
+            // Continuation and invalid lead bytes
+            1 => (0x80..=Raw::MAX).prop_map(Err),
+        ];
+        OsStringStrategy(
+            vec(unit, size)
+                .prop_map(|units| {
+                    let mut bytes = Vec::new();
+                    for unit in units {
+                        match unit {
+                            Ok(c) => bytes.extend_from_slice(
+                                c.encode_utf8(&mut [0; 4]).as_bytes(),
+                            ),
+                            Err(byte) => bytes.push(byte),
+                        }
+                    }
+                    OsString::from_vec(bytes)
+                })
+                .sboxed(),
+        )
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStringExt;
+
+        let unit = prop_oneof![
+            3 => any::<char>().prop_map(Ok),
+            // Leading and trailing surrogates
+            1 => (0xd800..=(0xdfff as Raw)).prop_map(Err),
+        ];
+        OsStringStrategy(
+            vec(unit, size)
+                .prop_map(|units| {
+                    let mut wide = Vec::new();
+                    for unit in units {
+                        match unit {
+                            Ok(c) => wide
+                                .extend_from_slice(c.encode_utf16(&mut [0; 2])),
+                            Err(surrogate) => wide.push(surrogate),
+                        }
+                    }
+                    OsString::from_wide(&wide)
+                })
+                .sboxed(),
+        )
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        OsStringStrategy(
+            vec(any::<char>(), size)
+                .prop_map(|chars| chars.into_iter().collect::<String>().into())
+                .sboxed(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn c_strings_have_requested_length() {
+        let mut runner = TestRunner::deterministic();
+        let input = c_string(2..5);
+        for _ in 0..256 {
+            let s = input.new_tree(&mut runner).unwrap().current();
+            assert!((2..5).contains(&s.as_bytes().len()), "{:?}", s);
+        }
+    }
+
+    #[cfg(any(unix, windows))]
+    #[test]
+    fn os_strings_are_sometimes_not_unicode() {
+        let mut runner = TestRunner::deterministic();
+        let input = os_string(0..16);
+        let mut unicode = 0;
+        let mut not_unicode = 0;
+        for _ in 0..256 {
+            let s = input.new_tree(&mut runner).unwrap().current();
+            match s.to_str() {
+                Some(_) => unicode += 1,
+                None => not_unicode += 1,
+            }
+        }
+        assert!(unicode > 0 && not_unicode > 0);
+    }
+
+    #[cfg(any(unix, windows))]
+    #[test]
+    fn os_strings_shrink_to_unicode() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&os_string(1..16), |s| {
+            prop_assert!(s.len() < 8);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, s)) => assert!(s.to_str().is_some()),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,457 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for strings in common standardised formats.
+//!
+//! Each strategy follows the grammar of its format, rather than a regular
+//! expression approximating it, and shrinks toward a short valid value, such
+//! as `0.0.0` for `semver_version()`.
+//!
+//! `email()` and `url()` generate email addresses and URLs, with parameters
+//! selecting which parts of their grammars to exercise.
+
+use crate::arbitrary::any;
+use crate::collection::{btree_map, btree_set, vec};
+use crate::option;
+use crate::sample::select;
+use crate::std_facade::{string::ToString, Box, String, ToOwned};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+mod email;
+mod url;
+
+pub use self::email::*;
+pub use self::url::*;
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates strings in a standardised format.
+    ///
+    /// Created by the functions in the same module.
+    #[derive(Debug)]
+    pub struct FormatStrategy[][](SBoxedStrategy<String>)
+        -> FormatValueTree;
+    /// `ValueTree` corresponding to `FormatStrategy`.
+    pub struct FormatValueTree[][](Box<dyn ValueTree<Value = String>>)
+        -> String;
+}
+
+/// Generates a numeric semver component, usually a small one.
+fn semver_number() -> impl Strategy<Value = u64> + Clone {
+    prop_oneof![4 => 0..20u64, 1 => any::<u64>()]
+}
+
+/// Generates the dot-separated identifiers of a pre-release version.
+fn semver_prerelease() -> impl Strategy<Value = String> + Clone {
+    let identifier = prop_oneof![
+        semver_number().prop_map(|n| n.to_string()),
+        // Alphanumeric identifiers contain at least one non-digit, so
+        // leading zeros are allowed
+        "[0-9]{0,2}[A-Za-z-][0-9A-Za-z-]{0,5}",
+    ];
+    vec(identifier, 1..4).prop_map(|identifiers| identifiers.join("."))
+}
+
+/// Creates a strategy which generates [Semantic Versioning 2.0.0][semver]
+/// versions, such as `1.2.3-alpha.1+build.5`.
+///
+/// Versions may have pre-release identifiers and build metadata. They shrink
+/// toward `0.0.0`.
+///
+/// [semver]: https://semver.org/spec/v2.0.0.html
+pub fn semver_version() -> FormatStrategy {
+    let build = "[0-9A-Za-z-]{1,8}(\\.[0-9A-Za-z-]{1,8}){0,2}";
+    FormatStrategy(
+        (
+            semver_number(),
+            semver_number(),
+            semver_number(),
+            option::of(semver_prerelease()),
+            option::of(build),
+        )
+            .prop_map(|(major, minor, patch, prerelease, build)| {
+                let mut version = format!("{}.{}.{}", major, minor, patch);
+                if let Some(prerelease) = prerelease {
+                    version.push('-');
+                    version.push_str(&prerelease);
+                }
+                if let Some(build) = build {
+                    version.push('+');
+                    version.push_str(&build);
+                }
+                version
+            })
+            .sboxed(),
+    )
+}
+
+/// The comparison operators of version requirements. The empty operator is
+/// equivalent to `^`.
+const SEMVER_OPERATORS: &[&str] = &["", "=", ">", ">=", "<", "<=", "~", "^"];
+
+/// Creates a strategy which generates version requirements in the syntax
+/// used by Cargo, such as `>=1.2, <2` or `~0.3.1-beta`.
+///
+/// Requirements are `*` or up to three comma-separated comparators. Each
+/// comparator is an operator and a version whose minor and patch parts may
+/// be omitted, or a wildcard version such as `1.*`. Requirements shrink
+/// toward `0`.
+pub fn semver_requirement() -> FormatStrategy {
+    let partial = (
+        semver_number(),
+        option::of((
+            semver_number(),
+            option::of((semver_number(), option::of(semver_prerelease()))),
+        )),
+    )
+        .prop_map(|(major, rest)| {
+            let mut version = major.to_string();
+            if let Some((minor, rest)) = rest {
+                version.push_str(&format!(".{}", minor));
+                if let Some((patch, prerelease)) = rest {
+                    version.push_str(&format!(".{}", patch));
+                    if let Some(prerelease) = prerelease {
+                        version.push('-');
+                        version.push_str(&prerelease);
+                    }
+                }
+            }
+            version
+        });
+    let comparator = prop_oneof![
+        (select(SEMVER_OPERATORS), partial)
+            .prop_map(|(operator, version)| format!("{}{}", operator, version)),
+        (semver_number(), option::of(semver_number())).prop_map(
+            |(major, minor)| match minor {
+                Some(minor) => format!("{}.{}.*", major, minor),
+                None => format!("{}.*", major),
+            }
+        ),
+    ];
+    FormatStrategy(
+        prop_oneof![
+            vec(comparator, 1..4)
+                .prop_map(|comparators| comparators.join(", ")),
+            Just("*".to_owned()),
+        ]
+        .sboxed(),
+    )
+}
+
+/// Registered top-level media types.
+const MIME_TYPES: &[&str] = &[
+    "text",
+    "application",
+    "image",
+    "audio",
+    "video",
+    "font",
+    "model",
+    "multipart",
+    "message",
+];
+
+/// Common media subtypes.
+const MIME_SUBTYPES: &[&str] = &[
+    "plain",
+    "html",
+    "json",
+    "octet-stream",
+    "xml",
+    "png",
+    "csv",
+    "form-data",
+    "x-www-form-urlencoded",
+    "vnd.api+json",
+];
+
+/// Common media type parameter names.
+const MIME_PARAMETERS: &[&str] = &["charset", "boundary", "version"];
+
+/// Creates a strategy which generates media types, such as
+/// `text/html; charset=utf-8`, following [RFC 6838][names] for the type and
+/// subtype names and [RFC 9110][params] for the parameters.
+///
+/// Types and subtypes are usually registered or common ones. Parameter
+/// values may be tokens or quoted strings. Media types shrink toward
+/// `text/plain`.
+///
+/// [names]: https://www.rfc-editor.org/rfc/rfc6838#section-4.2
+/// [params]: https://www.rfc-editor.org/rfc/rfc9110#section-8.3.1
+pub fn mime_type() -> FormatStrategy {
+    let restricted_name = "[A-Za-z0-9][A-Za-z0-9!#$&^_.+-]{0,15}";
+    let token = "[A-Za-z0-9!#$%&'*+.^_`|~-]{1,12}";
+    let value = prop_oneof![
+        token,
+        // Quoted strings, with quoted pairs
+        "\"([\t !#-\\[\\]-~]|\\\\[\t -~]){0,12}\"",
+    ];
+    let parameter_name = prop_oneof![
+        select(MIME_PARAMETERS).prop_map(str::to_owned),
+        "[a-z0-9][a-z0-9!#$&^_.+-]{0,7}",
+    ];
+    FormatStrategy(
+        (
+            prop_oneof![
+                select(MIME_TYPES).prop_map(str::to_owned),
+                restricted_name
+            ],
+            prop_oneof![
+                select(MIME_SUBTYPES).prop_map(str::to_owned),
+                restricted_name
+            ],
+            btree_map(parameter_name, value, 0..3),
+        )
+            .prop_map(|(mime_type, subtype, parameters)| {
+                let mut mime = format!("{}/{}", mime_type, subtype);
+                for (name, value) in parameters {
+                    mime.push_str(&format!("; {}={}", name, value));
+                }
+                mime
+            })
+            .sboxed(),
+    )
+}
+
+/// Common primary language subtags.
+const LANGUAGES: &[&str] =
+    &["en", "zh", "es", "hi", "ar", "fr", "pt", "ru", "ja", "de"];
+
+/// Creates a strategy which generates well-formed [BCP 47][bcp47] language
+/// tags, such as `en-US` or `zh-Hant-TW`.
+///
+/// Tags may have any of the optional subtags in the grammar: an extended
+/// language, script, region, variants, extensions and private use subtags.
+/// Subtags have their conventional case, and variants and extensions are
+/// never repeated. The subtags usually do not appear in the IANA registry.
+/// Tags shrink toward `en`.
+///
+/// [bcp47]: https://www.rfc-editor.org/rfc/rfc5646#section-2.1
+pub fn language_tag() -> FormatStrategy {
+    let language = prop_oneof![
+        select(LANGUAGES).prop_map(str::to_owned),
+        // With an optional extended language subtag
+        "[a-z]{2,3}(-[a-z]{3})?",
+        "[a-z]{4,8}",
+    ];
+    let script = "[A-Z][a-z]{3}";
+    let region = prop_oneof!["[A-Z]{2}", "[0-9]{3}"];
+    let variant = prop_oneof!["[a-z0-9]{5,8}", "[0-9][a-z0-9]{3}"];
+    let extension = vec("[a-z0-9]{2,8}", 1..3).prop_map(|s| s.join("-"));
+    let private_use = "x(-[a-z0-9]{1,8}){1,3}";
+    let langtag = (
+        language,
+        option::of(script),
+        option::of(region),
+        btree_set(variant, 0..3),
+        // Any singleton except `x`, which starts private use subtags
+        btree_map("[0-9a-wyz]", extension, 0..3),
+        option::of(private_use),
+    )
+        .prop_map(
+            |(language, script, region, variants, extensions, private_use)| {
+                let mut tag = language;
+                for subtag in script.into_iter().chain(region).chain(variants) {
+                    tag.push('-');
+                    tag.push_str(&subtag);
+                }
+                for (singleton, subtags) in extensions {
+                    tag.push_str(&format!("-{}-{}", singleton, subtags));
+                }
+                if let Some(private_use) = private_use {
+                    tag.push('-');
+                    tag.push_str(&private_use);
+                }
+                tag
+            },
+        );
+    FormatStrategy(prop_oneof![4 => langtag, 1 => private_use].sboxed())
+}
+
+/// Common top-level domains.
+const TOP_LEVEL_DOMAINS: &[&str] = &[
+    "com",
+    "org",
+    "net",
+    "io",
+    "dev",
+    "example",
+    "test",
+    "localhost",
+];
+
+/// The maximum length of a hostname.
+const MAX_HOSTNAME_LEN: usize = 253;
+
+/// Creates a strategy which generates hostnames as defined by
+/// [RFC 1123][rfc1123], such as `api.example.com`.
+///
+/// Hostnames are dot-separated labels of 1 to 63 letters, digits and
+/// hyphens, neither starting nor ending with a hyphen, up to 253 characters
+/// in total. The last label always starts with a letter, so hostnames
+/// cannot be mistaken for IPv4 addresses. Hostnames shrink toward `com`.
+///
+/// [rfc1123]: https://www.rfc-editor.org/rfc/rfc1123#section-2.1
+pub fn hostname() -> FormatStrategy {
+    let label = prop_oneof![
+        4 => "[a-z0-9]([a-z0-9-]{0,8}[a-z0-9])?",
+        1 => "[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?",
+    ];
+    let top_level = prop_oneof![
+        select(TOP_LEVEL_DOMAINS).prop_map(str::to_owned),
+        "[a-z]([a-z0-9-]{0,8}[a-z0-9])?",
+    ];
+    FormatStrategy(
+        (vec(label, 0..4), top_level)
+            .prop_map(|(mut labels, top_level)| {
+                labels.push(top_level);
+                let mut hostname = labels.join(".");
+                shorten_hostname(&mut hostname, MAX_HOSTNAME_LEN);
+                hostname
+            })
+            .sboxed(),
+    )
+}
+
+/// Removes labels from the start of `hostname` until it is at most
+/// `max_len` bytes long. The last label is always kept.
+fn shorten_hostname(hostname: &mut String, max_len: usize) {
+    while hostname.len() > max_len {
+        match hostname.find('.') {
+            Some(first) => drop(hostname.drain(..=first)),
+            None => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check_all(
+        input: FormatStrategy,
+        mut check: impl FnMut(&str) -> Result<(), String>,
+    ) {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let s = input.new_tree(&mut runner).unwrap().current();
+            if let Err(message) = check(&s) {
+                panic!("{} in {:?}", message, s);
+            }
+        }
+    }
+
+    fn minimal(input: FormatStrategy) -> String {
+        let mut runner = TestRunner::deterministic();
+        match runner.run(&input, |_| Err(TestCaseError::fail("minimize"))) {
+            Err(TestError::Fail(_, s)) => s,
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    fn is_token(s: &str) -> bool {
+        !s.is_empty()
+            && s.chars().all(|c| {
+                c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+            })
+    }
+
+    #[test]
+    fn semver_versions_parse() {
+        check_all(semver_version(), |s| {
+            let version =
+                semver::Version::parse(s).map_err(|e| e.to_string())?;
+            if version.to_string() != s {
+                return Err(format!("round-tripped to {}", version));
+            }
+            Ok(())
+        });
+        assert_eq!("0.0.0", minimal(semver_version()));
+    }
+
+    #[test]
+    fn semver_requirements_parse() {
+        check_all(semver_requirement(), |s| {
+            semver::VersionReq::parse(s)
+                .map(drop)
+                .map_err(|e| e.to_string())
+        });
+        assert_eq!("0", minimal(semver_requirement()));
+    }
+
+    #[test]
+    fn mime_types_are_well_formed() {
+        check_all(mime_type(), |s| {
+            let mut parts = s.split("; ");
+            let (mime_type, subtype) = parts
+                .next()
+                .and_then(|essence| essence.split_once('/'))
+                .ok_or("no type")?;
+            if !is_token(mime_type) || !is_token(subtype) {
+                return Err("bad type".into());
+            }
+            for parameter in parts {
+                let (name, value) =
+                    parameter.split_once('=').ok_or("no parameter value")?;
+                let quoted = value.len() >= 2
+                    && value.starts_with('"')
+                    && value.ends_with('"');
+                if !is_token(name) || !(is_token(value) || quoted) {
+                    return Err("bad parameter".into());
+                }
+            }
+            Ok(())
+        });
+        assert_eq!("text/plain", minimal(mime_type()));
+    }
+
+    #[test]
+    fn language_tags_parse() {
+        check_all(language_tag(), |s| {
+            let tag = language_tags::LanguageTag::parse(s)
+                .map_err(|e| e.to_string())?;
+            match tag.validate() {
+                Err(language_tags::ValidationError::DuplicateVariant)
+                | Err(language_tags::ValidationError::DuplicateExtension)
+                | Err(
+                    language_tags::ValidationError::MultipleExtendedLanguageSubtags,
+                ) => Err("not valid".into()),
+                _ => Ok(()),
+            }
+        });
+        assert_eq!("en", minimal(language_tag()));
+    }
+
+    #[test]
+    fn hostnames_are_well_formed() {
+        check_all(hostname(), |s| {
+            if s.len() > MAX_HOSTNAME_LEN {
+                return Err("too long".into());
+            }
+            for label in s.split('.') {
+                if label.is_empty()
+                    || label.len() > 63
+                    || label.starts_with('-')
+                    || label.ends_with('-')
+                    || !label
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+                {
+                    return Err(format!("bad label {:?}", label));
+                }
+            }
+            let last = s.rsplit('.').next().unwrap();
+            if !last.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                return Err("numeric top-level label".into());
+            }
+            Ok(())
+        });
+        assert_eq!("com", minimal(hostname()));
+    }
+}
@@ -0,0 +1,194 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for email addresses.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use super::{hostname, shorten_hostname, FormatStrategy};
+use crate::arbitrary::any;
+use crate::strategy::*;
+
+/// The maximum length of a whole address.
+const MAX_ADDRESS_LEN: usize = 254;
+
+/// Parameters for generating email addresses with `email()`.
+///
+/// By default, addresses are ordinary ones such as `jane.doe+tag@example.com`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EmailParams {
+    /// Whether local parts may be quoted strings, such as `"jane doe"`.
+    pub quoted_local_parts: bool,
+    /// Whether domains may be address literals, such as `[192.0.2.1]` or
+    /// `[IPv6:2001:db8::1]`.
+    pub address_literals: bool,
+    /// Whether to generate addresses which are valid but abusive. Local
+    /// parts use every character the grammar allows, including quoted pairs
+    /// in quoted strings, and addresses may reach the length limits. Implies
+    /// `quoted_local_parts` and `address_literals`.
+    pub abusive: bool,
+}
+
+/// Creates a strategy which generates email addresses valid according to
+/// the `Mailbox` grammar of [RFC 5321][rfc5321], within its limits of 64
+/// bytes for the local part and 254 for the whole address.
+///
+/// Domains are hostnames as generated by `hostname()`, or address literals
+/// if enabled by `params`. Addresses shrink toward a single character
+/// before `@com`.
+///
+/// [rfc5321]: https://www.rfc-editor.org/rfc/rfc5321#section-4.1.2
+pub fn email(params: EmailParams) -> FormatStrategy {
+    let (dot_atom, quoted) = if params.abusive {
+        (
+            "[A-Za-z0-9!#$%&'*+/=?^_`{|}~-]{1,15}\
+             (\\.[A-Za-z0-9!#$%&'*+/=?^_`{|}~-]{1,15}){0,3}",
+            // Any printable ASCII, with quoted pairs
+            "\"([ !#-\\[\\]-~]|\\\\[ -~]){0,30}\"",
+        )
+    } else {
+        (
+            "[a-z0-9]{1,10}([._+-][a-z0-9]{1,10}){0,2}",
+            "\"[a-z0-9 .,@]{1,12}\"",
+        )
+    };
+    let local_part = if params.quoted_local_parts || params.abusive {
+        prop_oneof![3 => dot_atom, 1 => quoted].sboxed()
+    } else {
+        dot_atom.sboxed()
+    };
+
+    let domain = if params.address_literals || params.abusive {
+        prop_oneof![
+            3 => hostname(),
+            1 => any::<Ipv4Addr>().prop_map(|ip| format!("[{}]", ip)),
+            1 => any::<Ipv6Addr>().prop_map(|ip| format!("[IPv6:{}]", ip)),
+        ]
+        .sboxed()
+    } else {
+        hostname().sboxed()
+    };
+
+    FormatStrategy(
+        (local_part, domain)
+            .prop_map(|(local_part, mut domain)| {
+                if !domain.starts_with('[') {
+                    shorten_hostname(
+                        &mut domain,
+                        MAX_ADDRESS_LEN - local_part.len() - 1,
+                    );
+                }
+                format!("{}@{}", local_part, domain)
+            })
+            .sboxed(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_runner::*;
+
+    fn is_local_part(s: &str) -> bool {
+        match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(quoted) => {
+                let mut chars = quoted.chars();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            if !matches!(chars.next(), Some(' '..='~')) {
+                                return false;
+                            }
+                        }
+                        ' ' | '!' | '#'..='[' | ']'..='~' => (),
+                        _ => return false,
+                    }
+                }
+                true
+            }
+            None => s.split('.').all(|atom| {
+                !atom.is_empty()
+                    && atom.chars().all(|c| {
+                        c.is_ascii_alphanumeric()
+                            || "!#$%&'*+-/=?^_`{|}~".contains(c)
+                    })
+            }),
+        }
+    }
+
+    fn is_domain(s: &str) -> bool {
+        match s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            Some(literal) => match literal.strip_prefix("IPv6:") {
+                Some(ip) => ip.parse::<Ipv6Addr>().is_ok(),
+                None => literal.parse::<Ipv4Addr>().is_ok(),
+            },
+            None => s.split('.').all(|label| {
+                (1..=63).contains(&label.len())
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+            }),
+        }
+    }
+
+    fn check_emails(params: EmailParams) -> (bool, bool) {
+        let mut runner = TestRunner::deterministic();
+        let input = email(params);
+        let mut quoted = false;
+        let mut literal = false;
+        for _ in 0..256 {
+            let address = input.new_tree(&mut runner).unwrap().current();
+            assert!(address.len() <= MAX_ADDRESS_LEN, "{:?}", address);
+            let (local_part, domain) = address.rsplit_once('@').unwrap();
+            assert!(local_part.len() <= 64, "{:?}", address);
+            assert!(is_local_part(local_part), "{:?}", address);
+            assert!(is_domain(domain), "{:?}", address);
+            quoted |= local_part.starts_with('"');
+            literal |= domain.starts_with('[');
+        }
+        (quoted, literal)
+    }
+
+    #[test]
+    fn generates_valid_addresses() {
+        assert_eq!((false, false), check_emails(EmailParams::default()));
+        let params = EmailParams {
+            quoted_local_parts: true,
+            address_literals: true,
+            ..EmailParams::default()
+        };
+        assert_eq!((true, true), check_emails(params));
+    }
+
+    #[test]
+    fn generates_abusive_addresses() {
+        let params = EmailParams {
+            abusive: true,
+            ..EmailParams::default()
+        };
+        assert_eq!((true, true), check_emails(params));
+    }
+
+    #[test]
+    fn shrinks_to_short_address() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&email(EmailParams::default()), |_| {
+            Err(TestCaseError::fail("minimize"))
+        });
+        match result {
+            Err(TestError::Fail(_, address)) => {
+                assert_eq!(5, address.len(), "{:?}", address);
+                assert!(address.ends_with("@com"), "{:?}", address);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,405 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for URLs.
+
+use core::fmt::Write;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use super::{hostname, FormatStrategy};
+use crate::arbitrary::any;
+use crate::collection::vec;
+use crate::option;
+use crate::sample::select;
+use crate::std_facade::{string::ToString, String, Vec};
+use crate::strategy::*;
+
+/// Parameters for generating URLs with `url()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UrlParams {
+    /// The schemes to choose from. They must be valid schemes for URLs with
+    /// an authority, i.e. followed by `://`.
+    ///
+    /// The default is `http` and `https`.
+    pub schemes: &'static [&'static str],
+    /// Whether URLs may have userinfo, such as `user:password@`.
+    ///
+    /// The default is `false`.
+    pub userinfo: bool,
+    /// Whether hosts may be IPv6 addresses, such as `[2001:db8::1]`. Hosts
+    /// are otherwise hostnames or IPv4 addresses.
+    ///
+    /// The default is `false`.
+    pub ipv6_hosts: bool,
+    /// Whether URLs may have a port.
+    ///
+    /// The default is `true`.
+    pub ports: bool,
+    /// Whether URLs may have a query.
+    ///
+    /// The default is `true`.
+    pub queries: bool,
+    /// Whether URLs may have a fragment.
+    ///
+    /// The default is `true`.
+    pub fragments: bool,
+    /// Whether the text of paths, queries, fragments and userinfo may
+    /// contain any printable characters, percent-encoded where they are not
+    /// allowed literally. Otherwise, the text only uses characters allowed
+    /// literally.
+    ///
+    /// The default is `true`.
+    pub percent_encoding: bool,
+    /// Whether to generate URLs which are valid but abusive. Schemes have
+    /// mixed case, every component uses all the delimiters allowed in it
+    /// literally, paths may have empty, `.` and `..` segments, ports may be
+    /// empty or have leading zeros, and userinfo may be empty.
+    ///
+    /// The default is `false`.
+    pub abusive: bool,
+}
+
+impl Default for UrlParams {
+    fn default() -> Self {
+        UrlParams {
+            schemes: &["http", "https"],
+            userinfo: false,
+            ipv6_hosts: false,
+            ports: true,
+            queries: true,
+            fragments: true,
+            percent_encoding: true,
+            abusive: false,
+        }
+    }
+}
+
+/// Returns whether `c` is an unreserved character.
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._~".contains(c)
+}
+
+/// Returns whether `c` is a character which may appear literally in
+/// userinfo.
+fn is_userinfo(c: char) -> bool {
+    is_unreserved(c) || "!$&'()*+,;=:".contains(c)
+}
+
+/// Returns whether `c` is a character which may appear literally in a path
+/// segment.
+fn is_pchar(c: char) -> bool {
+    is_unreserved(c) || "!$&'()*+,;=:@".contains(c)
+}
+
+/// Returns whether `c` is a character which may appear literally in a query
+/// or fragment.
+fn is_query(c: char) -> bool {
+    is_pchar(c) || "/?".contains(c)
+}
+
+/// Appends `text` to `out`, percent-encoding the characters for which
+/// `allowed` is false.
+fn encode(text: &str, allowed: fn(char) -> bool, out: &mut String) {
+    for c in text.chars() {
+        if allowed(c) {
+            out.push(c);
+        } else {
+            for byte in c.encode_utf8(&mut [0; 4]).bytes() {
+                write!(out, "%{:02X}", byte).unwrap();
+            }
+        }
+    }
+}
+
+/// Generates the raw text of a URL component, before percent-encoding.
+///
+/// `literal` is a regex generating text which needs no encoding.
+fn text(params: &UrlParams, literal: &'static str) -> SBoxedStrategy<String> {
+    if params.percent_encoding {
+        prop_oneof![3 => literal, 1 => "\\PC{1,8}"].sboxed()
+    } else {
+        literal.sboxed()
+    }
+}
+
+/// Creates a strategy which generates URLs valid according to
+/// [RFC 3986][rfc3986], which are also accepted by the [WHATWG URL
+/// Standard][whatwg].
+///
+/// URLs have a scheme, an authority with a host and optional userinfo and
+/// port, a possibly empty absolute path, and optional query and fragment.
+/// `params` selects which of the optional parts may be present and how
+/// unusual they may be. Queries are `key=value` pairs separated by `&`,
+/// except in abusive mode, where they are arbitrary. URLs shrink toward the
+/// first scheme followed by `://com`.
+///
+/// [rfc3986]: https://www.rfc-editor.org/rfc/rfc3986#section-3
+/// [whatwg]: https://url.spec.whatwg.org/
+///
+/// ## Panics
+///
+/// Panics if `params.schemes` is empty.
+pub fn url(params: UrlParams) -> FormatStrategy {
+    let abusive = params.abusive;
+    let scheme = select(params.schemes);
+    let scheme = if abusive {
+        (scheme, vec(any::<bool>(), 0..8))
+            .prop_map(|(scheme, upper)| {
+                let upper = upper.into_iter().chain(core::iter::repeat(false));
+                let mut mixed = String::new();
+                for (c, upper) in scheme.chars().zip(upper) {
+                    mixed.push(if upper { c.to_ascii_uppercase() } else { c });
+                }
+                mixed
+            })
+            .sboxed()
+    } else {
+        scheme.prop_map(String::from).sboxed()
+    };
+
+    let userinfo = if !params.userinfo {
+        Just(None).sboxed()
+    } else if abusive {
+        option::of((
+            text(&params, "[A-Za-z0-9._~!$&'()*+,;=-]{0,8}"),
+            option::of(text(&params, "[A-Za-z0-9._~!$&'()*+,;=:-]{0,8}")),
+        ))
+        .sboxed()
+    } else {
+        option::of((
+            text(&params, "[a-z0-9]{1,8}"),
+            option::of(text(&params, "[A-Za-z0-9]{1,12}")),
+        ))
+        .sboxed()
+    };
+
+    let host = if params.ipv6_hosts {
+        prop_oneof![
+            3 => hostname(),
+            1 => any::<Ipv4Addr>().prop_map(|ip| ip.to_string()),
+            1 => any::<Ipv6Addr>().prop_map(|ip| format!("[{}]", ip)),
+        ]
+        .sboxed()
+    } else {
+        prop_oneof![
+            3 => hostname(),
+            1 => any::<Ipv4Addr>().prop_map(|ip| ip.to_string()),
+        ]
+        .sboxed()
+    };
+
+    let port = if !params.ports {
+        Just(None).sboxed()
+    } else if abusive {
+        option::of(prop_oneof![
+            any::<u16>().prop_map(|port| port.to_string()),
+            Just(String::new()),
+            (0..=65535u16).prop_map(|port| format!("00{}", port)),
+        ])
+        .sboxed()
+    } else {
+        option::of(any::<u16>().prop_map(|port| port.to_string())).sboxed()
+    };
+
+    let path = if abusive {
+        vec(
+            prop_oneof![
+                text(&params, "[A-Za-z0-9._~!$&'()*+,;=:@-]{1,8}"),
+                Just(String::new()),
+                Just(".".into()),
+                Just("..".into()),
+            ],
+            0..6,
+        )
+        .sboxed()
+    } else {
+        vec(text(&params, "[A-Za-z0-9_~-][A-Za-z0-9._~-]{0,9}"), 0..4).sboxed()
+    };
+
+    let query = if !params.queries {
+        Just(None).sboxed()
+    } else if abusive {
+        option::of(
+            text(&params, "[A-Za-z0-9._~!$&'()*+,;=:@/?-]{0,12}").prop_map(
+                |query| {
+                    let mut out = String::new();
+                    encode(&query, is_query, &mut out);
+                    out
+                },
+            ),
+        )
+        .sboxed()
+    } else {
+        option::of(
+            vec(
+                (
+                    text(&params, "[a-z][a-z0-9_]{0,7}"),
+                    text(&params, "[A-Za-z0-9._~-]{0,10}"),
+                ),
+                1..4,
+            )
+            .prop_map(|pairs| {
+                let mut out = String::new();
+                for (i, (key, value)) in pairs.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push('&');
+                    }
+                    encode(&key, is_unreserved, &mut out);
+                    out.push('=');
+                    encode(&value, is_unreserved, &mut out);
+                }
+                out
+            }),
+        )
+        .sboxed()
+    };
+
+    let fragment = if !params.fragments {
+        Just(None).sboxed()
+    } else if abusive {
+        option::of(text(&params, "[A-Za-z0-9._~!$&'()*+,;=:@/?-]{0,12}"))
+            .sboxed()
+    } else {
+        option::of(text(&params, "[A-Za-z0-9_-]{1,10}")).sboxed()
+    };
+
+    FormatStrategy(
+        ((scheme, userinfo, host, port), (path, query, fragment))
+            .prop_map(
+                |((scheme, userinfo, host, port), (path, query, fragment))| {
+                    render(scheme, userinfo, host, port, path, query, fragment)
+                },
+            )
+            .sboxed(),
+    )
+}
+
+/// Assembles a URL from its components. Everything other than the query is
+/// percent-encoded here.
+fn render(
+    scheme: String,
+    userinfo: Option<(String, Option<String>)>,
+    host: String,
+    port: Option<String>,
+    path: Vec<String>,
+    query: Option<String>,
+    fragment: Option<String>,
+) -> String {
+    let mut url = scheme;
+    url.push_str("://");
+    if let Some((user, password)) = userinfo {
+        encode(&user, |c| is_userinfo(c) && c != ':', &mut url);
+        if let Some(password) = password {
+            url.push(':');
+            encode(&password, is_userinfo, &mut url);
+        }
+        url.push('@');
+    }
+    url.push_str(&host);
+    if let Some(port) = port {
+        url.push(':');
+        url.push_str(&port);
+    }
+    for segment in path {
+        url.push('/');
+        encode(&segment, is_pchar, &mut url);
+    }
+    if let Some(query) = query {
+        url.push('?');
+        url.push_str(&query);
+    }
+    if let Some(fragment) = fragment {
+        url.push('#');
+        encode(&fragment, is_query, &mut url);
+    }
+    url
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_runner::*;
+
+    fn check_urls(params: UrlParams) -> Vec<::url::Url> {
+        let mut runner = TestRunner::deterministic();
+        let input = url(params);
+        (0..256)
+            .map(|_| {
+                let s = input.new_tree(&mut runner).unwrap().current();
+                assert!(
+                    s.chars().all(|c| is_query(c) || "%#[]".contains(c)),
+                    "{:?}",
+                    s
+                );
+                ::url::Url::parse(&s)
+                    .unwrap_or_else(|err| panic!("{} in {:?}", err, s))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn generates_valid_urls() {
+        let urls = check_urls(UrlParams::default());
+        assert!(urls.iter().all(|url| url.username().is_empty()));
+        assert!(urls
+            .iter()
+            .all(|url| !matches!(url.host(), Some(::url::Host::Ipv6(_)))));
+        assert!(urls.iter().any(|url| url.port().is_some()));
+        assert!(urls.iter().any(|url| url.query().is_some()));
+        assert!(urls.iter().any(|url| url.fragment().is_some()));
+        assert!(urls.iter().any(|url| url.path().contains('%')));
+    }
+
+    #[test]
+    fn respects_params() {
+        let params = UrlParams {
+            schemes: &["ftp"],
+            userinfo: true,
+            ipv6_hosts: true,
+            ports: false,
+            queries: false,
+            fragments: false,
+            percent_encoding: false,
+            abusive: false,
+        };
+        let urls = check_urls(params);
+        assert!(urls.iter().all(|url| url.scheme() == "ftp"
+            && url.port().is_none()
+            && url.query().is_none()
+            && url.fragment().is_none()
+            && !url.path().contains('%')));
+        assert!(urls.iter().any(|url| !url.username().is_empty()));
+        assert!(urls
+            .iter()
+            .any(|url| matches!(url.host(), Some(::url::Host::Ipv6(_)))));
+    }
+
+    #[test]
+    fn generates_abusive_urls() {
+        let params = UrlParams {
+            userinfo: true,
+            ipv6_hosts: true,
+            abusive: true,
+            ..UrlParams::default()
+        };
+        check_urls(params);
+    }
+
+    #[test]
+    fn shrinks_to_minimal_url() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&url(UrlParams::default()), |_| {
+            Err(TestCaseError::fail("minimize"))
+        });
+        match result {
+            Err(TestError::Fail(_, url)) => assert_eq!("http://com", url),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
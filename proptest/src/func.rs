@@ -0,0 +1,232 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating pure functions, for testing code which takes
+//! functions as input, such as higher-order functions or caches.
+//!
+//! A generated [`Func`] is total and deterministic: it maps the arguments in
+//! a generated table to their results, and every other argument to a
+//! generated default result. Shrinking removes entries from the table, then
+//! simplifies the remaining arguments and results, and finally the default,
+//! so a minimal failing function is usually constant apart from the few
+//! arguments which matter.
+//!
+//! ```rust
+//! use proptest::func::Func;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(
+//!     v in prop::collection::vec(0..10u8, 0..10),
+//!     f in any::<Func<u8, u16>>(),
+//!     g in any::<Func<u16, bool>>(),
+//! )| {
+//!     let composed: Vec<bool> = v.iter().map(|x| g.call(&f.call(x))).collect();
+//!     let chained: Vec<bool> =
+//!         v.iter().map(|x| f.call(x)).map(|y| g.call(&y)).collect();
+//!     prop_assert_eq!(composed, chained);
+//! });
+//! ```
+//!
+//! Arguments drawn from a large domain rarely hit the table by chance, so
+//! for such types it is usually better to use [`of`] with a strategy for
+//! arguments from the same small domain as the inputs of the test.
+
+use core::fmt;
+use core::hash::Hash;
+
+use crate::arbitrary::{any_with, Arbitrary};
+use crate::collection::{
+    hash_map, HashMapStrategy, HashMapValueTree, SizeRange,
+};
+use crate::std_facade::HashMap;
+use crate::strategy::*;
+use crate::test_runner::*;
+use crate::tuple::TupleValueTree;
+
+/// A total, deterministic function from `A` to `B`, represented by a table of
+/// results for some arguments and a default result for all others.
+///
+/// Created by the strategies in this module.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Func<A: Hash + Eq, B> {
+    table: HashMap<A, B>,
+    default: B,
+}
+
+impl<A: Hash + Eq, B: Clone> Func<A, B> {
+    /// Create a function returning the result in `table` for the arguments
+    /// it contains and `default` for any other.
+    pub fn new(table: HashMap<A, B>, default: B) -> Self {
+        Func { table, default }
+    }
+
+    /// Create a function returning `result` for every argument.
+    pub fn constant(result: B) -> Self {
+        Func::new(HashMap::new(), result)
+    }
+
+    /// Apply the function to `arg`.
+    pub fn call(&self, arg: &A) -> B {
+        self.table.get(arg).unwrap_or(&self.default).clone()
+    }
+
+    /// Convert the function into a closure, to pass it where an `Fn` is
+    /// expected.
+    pub fn into_fn(self) -> impl Fn(&A) -> B {
+        move |arg| self.call(arg)
+    }
+
+    /// Return the arguments with a result other than the default one and
+    /// their results.
+    pub fn table(&self) -> &HashMap<A, B> {
+        &self.table
+    }
+
+    /// Return the result for the arguments not in the table.
+    pub fn default_result(&self) -> &B {
+        &self.default
+    }
+}
+
+/// Formats the function as its table followed by the default result, for
+/// example `{1: "a", 5: "b", _: ""}`.
+impl<A: Hash + Eq + fmt::Debug, B: fmt::Debug> fmt::Debug for Func<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(&self.table)
+            .entry(&format_args!("_"), &self.default)
+            .finish()
+    }
+}
+
+mapfn! {
+    [] fn TableToFunc[<A : fmt::Debug + Hash + Eq, B : fmt::Debug>]
+        (parts: (HashMap<A, B>, B)) -> Func<A, B>
+    {
+        let (table, default) = parts;
+        Func { table, default }
+    }
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy to create `Func`s.
+    ///
+    /// Created by the `of()` function in the same module.
+    #[derive(Clone, Debug)]
+    pub struct FuncStrategy[<A, B>]
+        [where A : Strategy, B : Strategy, A::Value : Hash + Eq](
+            statics::Map<(HashMapStrategy<A, B>, B), TableToFunc>)
+        -> FuncValueTree<A::Tree, B::Tree>;
+    /// `ValueTree` corresponding to `FuncStrategy`.
+    #[derive(Clone, Debug)]
+    pub struct FuncValueTree[<A, B>]
+        [where A : ValueTree, B : ValueTree, A::Value : Hash + Eq](
+            statics::Map<TupleValueTree<(HashMapValueTree<A, B>, B)>,
+            TableToFunc>)
+        -> Func<A::Value, B::Value>;
+}
+
+impl<A: Strategy, B: Strategy> FuncStrategy<A, B>
+where
+    A::Value: Hash + Eq,
+{
+    fn new(args: A, results: B, default: B, size: SizeRange) -> Self {
+        FuncStrategy(statics::Map::new(
+            (hash_map(args, results, size), default),
+            TableToFunc,
+        ))
+    }
+}
+
+/// Create a strategy to generate `Func`s whose table maps arguments drawn
+/// from `args` to results drawn from `results`, with a number of entries
+/// within `size`, and whose default result is also drawn from `results`.
+///
+/// Like `hash_map()`, this strategy implicitly does local rejects to ensure
+/// that the table has at least the minimum number of entries, in case `args`
+/// produces duplicate values.
+pub fn of<A: Strategy, B: Strategy + Clone>(
+    args: A,
+    results: B,
+    size: impl Into<SizeRange>,
+) -> FuncStrategy<A, B>
+where
+    A::Value: Hash + Eq,
+{
+    FuncStrategy::new(args, results.clone(), results, size.into())
+}
+
+impl<A, B> Arbitrary for Func<A, B>
+where
+    A: Arbitrary + Hash + Eq,
+    B: Arbitrary + Clone,
+    B::Parameters: Clone,
+{
+    type Parameters = product_type![SizeRange, A::Parameters, B::Parameters];
+    type Strategy = FuncStrategy<A::Strategy, B::Strategy>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        let product_unpack![size, a, b] = args;
+        FuncStrategy::new(
+            any_with::<A>(a),
+            any_with::<B>(b.clone()),
+            any_with::<B>(b),
+            size,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arbitrary::any;
+    use crate::test_runner::{TestError, TestRunner};
+    use std::format;
+
+    #[test]
+    fn functions_are_deterministic() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = of(0..8u8, any::<u32>(), 2..5);
+        for _ in 0..64 {
+            let f = strategy.new_tree(&mut runner).unwrap().current();
+            assert!((2..5).contains(&f.table().len()));
+            for arg in 0..16 {
+                assert_eq!(f.call(&arg), f.call(&arg));
+                if arg >= 8 {
+                    assert_eq!(f.default_result(), &f.call(&arg));
+                }
+            }
+            let g = f.clone().into_fn();
+            assert!((0..16).all(|arg| f.call(&arg) == g(&arg)));
+        }
+    }
+
+    #[test]
+    fn shrinks_to_table_of_relevant_arguments() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&of(0..4u8, 0..100u32, 0..8), |f| {
+            prop_assert!(f.call(&2) < 50);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, f)) => {
+                let expected = Func::new([(2, 50)].into_iter().collect(), 0);
+                // The default may also be the failing result
+                assert!(f == expected || f == Func::constant(50), "{:?}", f);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn debug_shows_table_and_default() {
+        let f = Func::new([(1u8, "a")].into_iter().collect(), "");
+        assert_eq!(r#"{1: "a", _: ""}"#, format!("{:?}", f));
+    }
+}
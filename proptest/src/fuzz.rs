@@ -0,0 +1,304 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support for running properties on inputs from a coverage-guided fuzzer,
+//! such as libFuzzer through `cargo fuzz` or AFL++ through `cargo afl`.
+//!
+//! A fuzzer hands its target a buffer of bytes. The functions here decode
+//! such a buffer into a value of any strategy by using it in place of the
+//! random number generator, as the `PassThrough` RNG algorithm does, so the
+//! same bytes always produce the same value and the fuzzer's mutations of the
+//! bytes become changes to the generated value. Once the buffer is exhausted,
+//! it continues with a fixed pseudo-random sequence rather than the 0s of
+//! `PassThrough`, so every buffer, including an empty one, decodes to some
+//! value.
+//!
+//! A property written as a function can then run both as a property test and
+//! as a fuzz target:
+//!
+//! ```rust,no_run
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestCaseResult;
+//!
+//! fn strategy() -> impl Strategy<Value = Vec<u8>> {
+//!     prop::collection::vec(any::<u8>(), 0..100)
+//! }
+//!
+//! fn reverse_twice(v: Vec<u8>) -> TestCaseResult {
+//!     let mut w = v.clone();
+//!     w.reverse();
+//!     w.reverse();
+//!     prop_assert_eq!(v, w);
+//!     Ok(())
+//! }
+//!
+//! proptest! {
+//!     #[test]
+//!     fn test_reverse_twice(v in strategy()) {
+//!         reverse_twice(v)?;
+//!     }
+//! }
+//!
+//! // In `fuzz/fuzz_targets/reverse_twice.rs`:
+//! //
+//! // fuzz_target!(|data: &[u8]| {
+//! //     proptest::fuzz::check(&strategy(), data, reverse_twice);
+//! // });
+//! ```
+//!
+//! With the `afl` feature, `afl_harness` turns the same function into an
+//! AFL++ target for the `afl` crate's persistent mode, which runs it on many
+//! inputs in one process. The target's own crate depends on `afl` and is
+//! built with `cargo afl`:
+//!
+//! ```rust,ignore
+//! // In `src/bin/reverse_twice.rs` of the fuzz crate:
+//! fn main() {
+//!     let harness = proptest::fuzz::afl_harness(strategy(), reverse_twice);
+//!     afl::fuzz!(|data: &[u8]| harness(data));
+//! }
+//! ```
+//!
+//! To let the fuzzer start from inputs proptest would generate, seed its
+//! corpus with [`export_corpus`]. Going the other way, a
+//! [`CorpusFailurePersistence`](crate::test_runner::CorpusFailurePersistence)
+//! pointed at the fuzzer's corpus or artifacts directory replays its inputs
+//! as part of the property test.
+
+use std::fs;
+use std::io;
+#[cfg(feature = "afl")]
+use std::panic::RefUnwindSafe;
+use std::path::Path;
+
+use rand::RngCore;
+
+use crate::num::sample_uniform_incl;
+use crate::strategy::{Strategy, ValueTree};
+use crate::test_runner::{
+    corpus_file, Config, PersistedSeed, Seed, TestCaseError, TestCaseResult,
+    TestRng, TestRunner,
+};
+
+/// The largest input written by [`export_corpus`], which is also the default
+/// maximum input length of libFuzzer.
+const MAX_INPUT_LEN: usize = 4096;
+
+/// Decode `data` into a value of `strategy`.
+///
+/// Returns `None` if `strategy` rejects every value it can generate from
+/// `data`, for example because of a filter.
+pub fn decode<S: Strategy>(strategy: &S, data: &[u8]) -> Option<S::Value> {
+    let (value, _) = decode_measured(strategy, data)?;
+    Some(value)
+}
+
+/// Like `decode`, but also returns the number of bytes consumed from the
+/// front of `data`.
+pub(crate) fn decode_measured<S: Strategy>(
+    strategy: &S,
+    data: &[u8],
+) -> Option<(S::Value, usize)> {
+    let rng = TestRng::from_fuzz_input(data);
+    let mut runner = TestRunner::new_with_rng(Config::default(), rng);
+    let tree = strategy.new_tree(&mut runner).ok()?;
+    let consumed = runner.rng().pass_through_consumed().unwrap_or(data.len());
+    Some((tree.current(), consumed))
+}
+
+/// Decode `data` into a value of `strategy` and run `test` on it.
+///
+/// This is meant to be the whole body of a fuzz target. If `test` fails, this
+/// panics with the reason and the value, which the fuzzer reports as a crash.
+/// Inputs which `strategy` or `test` reject are ignored.
+pub fn check<S: Strategy>(
+    strategy: &S,
+    data: &[u8],
+    test: impl FnOnce(S::Value) -> TestCaseResult,
+) {
+    let rng = TestRng::from_fuzz_input(data);
+    let mut runner = TestRunner::new_with_rng(Config::default(), rng);
+    let tree = match strategy.new_tree(&mut runner) {
+        Ok(tree) => tree,
+        Err(_) => return,
+    };
+
+    match test(tree.current()) {
+        Ok(()) | Err(TestCaseError::Reject(_)) => (),
+        Err(TestCaseError::Fail(why)) => {
+            panic!("Test failed: {}.\nfailing input: {:?}", why, tree.current())
+        }
+    }
+}
+
+/// Return a fuzz target which runs `test` on the values `strategy` decodes
+/// from its input, as [`check`] does, for AFL++'s persistent mode.
+///
+/// The returned function is meant to be called from the closure passed to
+/// `afl::fuzz!`, which runs it on many inputs in the same process. The
+/// strategy is created only once, and the function is `RefUnwindSafe` as
+/// `afl::fuzz!` requires, provided that `strategy` and `test` are.
+#[cfg(feature = "afl")]
+#[cfg_attr(docsrs, doc(cfg(feature = "afl")))]
+pub fn afl_harness<S: Strategy + RefUnwindSafe>(
+    strategy: S,
+    test: impl Fn(S::Value) -> TestCaseResult + RefUnwindSafe,
+) -> impl Fn(&[u8]) + RefUnwindSafe {
+    move |data| check(&strategy, data, &test)
+}
+
+/// Write `cases` inputs which decode to values of `strategy` into
+/// `directory`, to seed the corpus of a fuzzer.
+///
+/// Each input is a buffer of random bytes, of random length up to the 4096
+/// bytes libFuzzer accepts by default, cut down to the bytes actually needed
+/// to decode its value. Inputs which `strategy` rejects are skipped. The
+/// files are named after a hash of their contents, like the files of
+/// `cargo fuzz`, and the directory is created if it does not exist.
+///
+/// The randomness comes from a `TestRunner` created from `Config::default()`,
+/// so a fixed `PROPTEST_RNG_SEED` makes the export reproducible.
+///
+/// Returns the number of files written.
+pub fn export_corpus<S: Strategy>(
+    strategy: &S,
+    directory: impl AsRef<Path>,
+    cases: u32,
+) -> io::Result<u32> {
+    let directory = directory.as_ref();
+    fs::create_dir_all(directory)?;
+
+    let mut runner = TestRunner::new(Config::default());
+    let mut written = 0;
+    for _ in 0..cases {
+        let len = sample_uniform_incl(&mut runner, 0, MAX_INPUT_LEN);
+        let mut data = vec![0; len];
+        runner.rng().fill_bytes(&mut data);
+
+        let (value, consumed) = match decode_measured(strategy, &data) {
+            Some(decoded) => decoded,
+            None => continue,
+        };
+        // Bytes past those consumed may still have been split off to seed
+        // other RNGs, so only cut them if the value stays the same without.
+        let trimmed = &data[..consumed];
+        if decode(strategy, trimmed).is_some_and(|trimmed_value| {
+            format!("{:?}", trimmed_value) == format!("{:?}", value)
+        }) {
+            data.truncate(consumed);
+        }
+
+        let (contents, name) = corpus_file(&PersistedSeed(
+            Seed::PassThrough(None, data.into()),
+            None,
+        ));
+        let path = directory.join(name);
+        if !path.exists() {
+            fs::write(path, contents)?;
+            written += 1;
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::collection::vec;
+    use crate::prelude::*;
+    use crate::test_runner::{CorpusFailurePersistence, FailurePersistence};
+    use std::borrow::ToOwned;
+    use std::boxed::Box;
+    use std::env;
+    use std::vec::Vec;
+
+    fn strategy() -> impl Strategy<Value = Vec<u32>> {
+        vec(0..1000u32, 0..20)
+    }
+
+    #[test]
+    fn decoding_is_deterministic() {
+        let data = [7u8, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        assert_eq!(decode(&strategy(), &data), decode(&strategy(), &data));
+        // Exhausted inputs continue deterministically
+        assert!(decode(&strategy(), &[]).is_some());
+        assert_eq!(decode(&strategy(), &[]), decode(&strategy(), &[]));
+        assert_eq!(
+            decode(&any::<u8>().prop_filter("never", |_| false), &[1]),
+            None
+        );
+    }
+
+    #[test]
+    fn check_passes_and_ignores_rejections() {
+        check(&strategy(), &[3, 9, 9, 9, 9], |v| {
+            prop_assert!(v.len() < 20);
+            Ok(())
+        });
+        check(&strategy(), &[3, 9, 9, 9, 9], |_| {
+            prop_assume!(false);
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "failing input")]
+    fn check_panics_on_failure() {
+        check(&strategy(), &[], |v| {
+            prop_assert!(v.len() > 20);
+            Ok(())
+        });
+    }
+
+    #[cfg(feature = "afl")]
+    #[test]
+    fn afl_harness_checks_every_input() {
+        use std::panic;
+
+        fn assert_ref_unwind_safe(_: &impl RefUnwindSafe) {}
+
+        let passing = afl_harness(strategy(), |v| {
+            prop_assert!(v.len() < 20);
+            Ok(())
+        });
+        assert_ref_unwind_safe(&passing);
+        let failing = afl_harness(strategy(), |v| {
+            prop_assert!(v.len() >= 20);
+            Ok(())
+        });
+
+        for data in [&[][..], &[1, 2, 3], &[0xff; 64]] {
+            passing(data);
+            assert!(panic::catch_unwind(|| failing(data)).is_err());
+        }
+    }
+
+    #[test]
+    fn exported_corpus_replays_as_persisted_failures() {
+        let dir = env::temp_dir()
+            .join(format!("proptest-fuzz-corpus-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let written = export_corpus(&strategy(), &dir, 16).unwrap();
+        assert!(written > 0);
+
+        let persistence = CorpusFailurePersistence::new(Box::leak(
+            dir.to_str().unwrap().to_owned().into_boxed_str(),
+        ));
+        let loaded = persistence.load_persisted_failures2(None);
+        assert_eq!(written as usize, loaded.len());
+        for entry in fs::read_dir(&dir).unwrap() {
+            let data = fs::read(entry.unwrap().path()).unwrap();
+            assert!(data.len() <= MAX_INPUT_LEN);
+            assert!(decode(&strategy(), &data).is_some());
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
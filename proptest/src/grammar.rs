@@ -0,0 +1,650 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies which generate derivations of context-free grammars.
+//!
+//! A `Grammar` maps rule names to expressions, built either with the
+//! functions in this module or by parsing EBNF text with `Grammar::parse()`.
+//! `Grammar::strings()` then creates a strategy generating the text derived
+//! from the start rule, and `Grammar::trees()` one generating the derivations
+//! themselves as `ParseTree`s.
+//!
+//! Every reference to a rule nests the derivation one level deeper. Once the
+//! nesting approaches `Grammar::max_depth()`, only the alternatives which can
+//! still be completed within the remaining depth are chosen, so recursive
+//! grammars always produce finite derivations.
+//!
+//! Derivations shrink by removing repetitions and by replacing the choice
+//! made for an alternation with an earlier alternative, so grammars shrink
+//! best when the simplest alternative of each rule is listed first.
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use proptest::grammar::*;
+//!
+//! proptest! {
+//!     fn parens_are_balanced(s in Grammar::new("expr")
+//!         .rule("expr", choice([
+//!             rule("num"),
+//!             seq([lit("("), rule("expr"), lit("+"), rule("expr"), lit(")")]),
+//!         ]))
+//!         .rule("num", terminal("[0-9]{1,3}"))
+//!         .strings()
+//!         .unwrap()
+//!     ) {
+//!         prop_assert_eq!(s.matches('(').count(), s.matches(')').count());
+//!     }
+//! }
+//! # fn main() { parens_are_balanced(); }
+//! ```
+
+mod ebnf;
+
+use crate::std_facade::{fmt, BTreeMap, Box, String, ToOwned, Vec};
+
+use crate::collection::{vec, SizeRange};
+use crate::strategy::*;
+use crate::string::string_regex;
+use crate::test_runner::*;
+
+/// An expression on the right-hand side of a grammar rule.
+///
+/// Expressions are usually built with the functions in this module rather
+/// than directly.
+#[derive(Clone, Debug)]
+pub enum Expr {
+    /// Exactly the given text.
+    Literal(String),
+    /// Text generated by a strategy.
+    Terminal(SBoxedStrategy<String>),
+    /// A derivation of the named rule.
+    Rule(String),
+    /// Each expression in turn.
+    Seq(Vec<Expr>),
+    /// One of the expressions.
+    Choice(Vec<Expr>),
+    /// The expression repeated between `min` and `max` times, inclusive. A
+    /// `max` of `None` uses the grammar's `max_repeat()`.
+    Repeat {
+        /// The expression repeated.
+        expr: Box<Expr>,
+        /// The least number of repetitions.
+        min: usize,
+        /// The greatest number of repetitions, if not the grammar's default.
+        max: Option<usize>,
+    },
+}
+
+/// An expression generating exactly `text`.
+pub fn lit(text: impl Into<String>) -> Expr {
+    Expr::Literal(text.into())
+}
+
+/// An expression generating the strings matching the regular expression
+/// `regex`, as `string::string_regex()` does.
+///
+/// ## Panics
+///
+/// Panics if `regex` is not valid, or not supported by `string_regex()`.
+pub fn terminal(regex: &str) -> Expr {
+    match string_regex(regex) {
+        Ok(strategy) => Expr::Terminal(strategy.sboxed()),
+        Err(err) => panic!("Invalid terminal {:?}: {}", regex, err),
+    }
+}
+
+/// An expression generating the strings generated by `strategy`.
+pub fn terminal_from<S>(strategy: S) -> Expr
+where
+    S: Strategy<Value = String> + Send + Sync + 'static,
+    S::Tree: Send + Sync + 'static,
+{
+    Expr::Terminal(strategy.sboxed())
+}
+
+/// An expression generating a derivation of the rule called `name`.
+pub fn rule(name: impl Into<String>) -> Expr {
+    Expr::Rule(name.into())
+}
+
+/// An expression generating each of `exprs` in turn.
+pub fn seq(exprs: impl IntoIterator<Item = Expr>) -> Expr {
+    Expr::Seq(exprs.into_iter().collect())
+}
+
+/// An expression generating one of `exprs`.
+///
+/// Derivations shrink toward the first alternative, so it should usually be
+/// the simplest.
+pub fn choice(exprs: impl IntoIterator<Item = Expr>) -> Expr {
+    Expr::Choice(exprs.into_iter().collect())
+}
+
+/// An expression generating `expr` a number of times within `size`.
+pub fn repeat(expr: Expr, size: impl Into<SizeRange>) -> Expr {
+    let (min, max) = size.into().start_end_incl();
+    Expr::Repeat {
+        expr: Box::new(expr),
+        min,
+        max: Some(max),
+    }
+}
+
+/// An expression generating `expr` zero times or once.
+pub fn optional(expr: Expr) -> Expr {
+    repeat(expr, 0..=1)
+}
+
+/// An expression generating `expr` between zero and `max_repeat()` times.
+pub fn zero_or_more(expr: Expr) -> Expr {
+    Expr::Repeat {
+        expr: Box::new(expr),
+        min: 0,
+        max: None,
+    }
+}
+
+/// An expression generating `expr` between one and `max_repeat()` times.
+pub fn one_or_more(expr: Expr) -> Expr {
+    Expr::Repeat {
+        expr: Box::new(expr),
+        min: 1,
+        max: None,
+    }
+}
+
+/// Errors which may occur when parsing or compiling a grammar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The EBNF text was not syntactically valid. Holds the byte offset at
+    /// which the error was found, and a description of it.
+    Syntax(usize, &'static str),
+    /// A rule was referenced, or used as the start rule, but never defined.
+    UndefinedRule(String),
+    /// The start rule has no derivation within the maximum depth.
+    NoDerivation(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax(offset, message) => {
+                write!(f, "{} at byte {}", message, offset)
+            }
+            Error::UndefinedRule(name) => {
+                write!(f, "rule `{}` is not defined", name)
+            }
+            Error::NoDerivation(name) => write!(
+                f,
+                "rule `{}` has no derivation within the maximum depth",
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A derivation of a grammar rule.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParseTree {
+    /// Text generated by a literal or terminal.
+    Terminal(String),
+    /// A derivation of the named rule, made of the derivations of the
+    /// literals, terminals and rules in its expression.
+    Rule(String, Vec<ParseTree>),
+}
+
+impl ParseTree {
+    /// Return the text derived by this tree.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        self.push_text(&mut text);
+        text
+    }
+
+    fn push_text(&self, out: &mut String) {
+        match self {
+            ParseTree::Terminal(text) => out.push_str(text),
+            ParseTree::Rule(_, children) => {
+                for child in children {
+                    child.push_text(out);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParseTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text())
+    }
+}
+
+/// A context-free grammar from which derivations can be generated.
+///
+/// See the module documentation for details.
+#[derive(Clone, Debug)]
+pub struct Grammar {
+    start: String,
+    rules: BTreeMap<String, Expr>,
+    max_depth: usize,
+    max_repeat: usize,
+}
+
+impl Grammar {
+    /// Create a grammar with no rules, whose derivations start from the rule
+    /// called `start`.
+    ///
+    /// The maximum depth defaults to 8 and the maximum number of unbounded
+    /// repetitions to 4.
+    pub fn new(start: impl Into<String>) -> Self {
+        Grammar {
+            start: start.into(),
+            rules: BTreeMap::new(),
+            max_depth: 8,
+            max_repeat: 4,
+        }
+    }
+
+    /// Parse a grammar from EBNF text. The first rule defined is the start
+    /// rule.
+    ///
+    /// Rules have the form `name = expression ;`, where the expression may
+    /// contain:
+    ///
+    /// - strings in double or single quotes, which may use the escapes `\\`,
+    ///   `\"`, `\'`, `\n`, `\r` and `\t`;
+    /// - the names of other rules, which may also be written in angle
+    ///   brackets as in BNF;
+    /// - concatenation with `,` or simply by juxtaposition;
+    /// - alternation with `|`;
+    /// - grouping with `( ... )`, options with `[ ... ]` and repetition with
+    ///   `{ ... }`;
+    /// - the postfix operators `?`, `*` and `+`.
+    ///
+    /// `::=` may be used in place of `=`, and `.` in place of `;`. Comments
+    /// are written `(* ... *)`. A rule defined more than once has each
+    /// definition as an alternative.
+    ///
+    /// ```
+    /// use proptest::grammar::Grammar;
+    ///
+    /// let grammar = Grammar::parse(r#"
+    ///     list = "[", [ item, { ",", item } ], "]" ;
+    ///     item = "0" | "1" | list ;
+    /// "#).unwrap();
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let rules = ebnf::parse(text)?;
+        let mut grammar = Grammar::new(rules[0].0.clone());
+        for (name, expr) in rules {
+            let expr = match grammar.rules.remove(&name) {
+                None => expr,
+                Some(Expr::Choice(mut alternatives)) => {
+                    alternatives.push(expr);
+                    Expr::Choice(alternatives)
+                }
+                Some(previous) => Expr::Choice(vec![previous, expr]),
+            };
+            grammar.rules.insert(name, expr);
+        }
+        Ok(grammar)
+    }
+
+    /// Define the rule called `name` as `expr`, replacing any previous
+    /// definition.
+    pub fn rule(mut self, name: impl Into<String>, expr: Expr) -> Self {
+        self.rules.insert(name.into(), expr);
+        self
+    }
+
+    /// Set the greatest depth to which rules may be nested in a derivation.
+    /// The start rule is at depth 1.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Set the greatest number of repetitions generated by `zero_or_more()`
+    /// and `one_or_more()`, and by repetition in EBNF.
+    pub fn max_repeat(mut self, max: usize) -> Self {
+        self.max_repeat = max;
+        self
+    }
+
+    /// Create a strategy generating derivations of the start rule.
+    ///
+    /// Fails if any rule referenced is not defined, or if the start rule has
+    /// no derivation within the maximum depth.
+    pub fn trees(&self) -> Result<GrammarStrategy<ParseTree>, Error> {
+        let mut compiler = Compiler::new(self)?;
+        Ok(GrammarStrategy(compiler.rule(&self.start, self.max_depth)))
+    }
+
+    /// Create a strategy generating the text of derivations of the start
+    /// rule.
+    ///
+    /// Fails under the same conditions as `trees()`.
+    pub fn strings(&self) -> Result<GrammarStrategy<String>, Error> {
+        let trees = self.trees()?;
+        Ok(GrammarStrategy(
+            trees.0.prop_map(|tree| tree.text()).sboxed(),
+        ))
+    }
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates derivations of a grammar, or their text.
+    ///
+    /// Created by `Grammar::trees()` and `Grammar::strings()`.
+    #[derive(Clone, Debug)]
+    pub struct GrammarStrategy[<T>][where T : fmt::Debug]
+        (SBoxedStrategy<T>) -> GrammarValueTree<T>;
+    /// `ValueTree` corresponding to `GrammarStrategy`.
+    pub struct GrammarValueTree[<T>][where T : fmt::Debug]
+        (Box<dyn ValueTree<Value = T>>) -> T;
+}
+
+/// Builds the strategies for a grammar, sharing the strategy for each rule
+/// at each depth.
+struct Compiler<'a> {
+    grammar: &'a Grammar,
+    /// The least depth needed to derive each rule, or `usize::MAX` if the
+    /// rule has no finite derivation.
+    min_depths: BTreeMap<&'a str, usize>,
+    strategies: BTreeMap<(&'a str, usize), SBoxedStrategy<ParseTree>>,
+}
+
+impl<'a> Compiler<'a> {
+    fn new(grammar: &'a Grammar) -> Result<Self, Error> {
+        check_defined(grammar, &rule(grammar.start.as_str()))?;
+        for expr in grammar.rules.values() {
+            check_defined(grammar, expr)?;
+        }
+
+        let mut compiler = Compiler {
+            grammar,
+            min_depths: grammar
+                .rules
+                .keys()
+                .map(|name| (name.as_str(), usize::MAX))
+                .collect(),
+            strategies: BTreeMap::new(),
+        };
+        // Iterate to a fixed point, since rules may refer to each other.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (name, expr) in &grammar.rules {
+                let depth = compiler.min_depth(expr).saturating_add(1);
+                if depth < compiler.min_depths[name.as_str()] {
+                    compiler.min_depths.insert(name, depth);
+                    changed = true;
+                }
+            }
+        }
+
+        if compiler.min_depths[grammar.start.as_str()] > grammar.max_depth {
+            return Err(Error::NoDerivation(grammar.start.clone()));
+        }
+        Ok(compiler)
+    }
+
+    fn min_depth(&self, expr: &Expr) -> usize {
+        match expr {
+            Expr::Literal(_) | Expr::Terminal(_) => 0,
+            Expr::Rule(name) => self.min_depths[name.as_str()],
+            Expr::Seq(exprs) => {
+                exprs.iter().map(|e| self.min_depth(e)).max().unwrap_or(0)
+            }
+            Expr::Choice(exprs) => exprs
+                .iter()
+                .map(|e| self.min_depth(e))
+                .min()
+                .unwrap_or(usize::MAX),
+            Expr::Repeat { expr, min, .. } => {
+                if *min == 0 {
+                    0
+                } else {
+                    self.min_depth(expr)
+                }
+            }
+        }
+    }
+
+    /// Return the strategy for the rule called `name`, which must have a
+    /// derivation within `depth`.
+    fn rule(
+        &mut self,
+        name: &'a str,
+        depth: usize,
+    ) -> SBoxedStrategy<ParseTree> {
+        if let Some(strategy) = self.strategies.get(&(name, depth)) {
+            return strategy.clone();
+        }
+
+        let expr = &self.grammar.rules[name];
+        let owned = name.to_owned();
+        let strategy = self
+            .expr(expr, depth - 1)
+            .prop_map(move |children| ParseTree::Rule(owned.clone(), children))
+            .sboxed();
+        self.strategies.insert((name, depth), strategy.clone());
+        strategy
+    }
+
+    /// Return the strategy for `expr`, which must have a derivation within
+    /// `depth`.
+    fn expr(
+        &mut self,
+        expr: &'a Expr,
+        depth: usize,
+    ) -> SBoxedStrategy<Vec<ParseTree>> {
+        match expr {
+            Expr::Literal(text) => {
+                Just(vec![ParseTree::Terminal(text.clone())]).sboxed()
+            }
+            Expr::Terminal(strategy) => strategy
+                .clone()
+                .prop_map(|text| vec![ParseTree::Terminal(text)])
+                .sboxed(),
+            Expr::Rule(name) => {
+                self.rule(name, depth).prop_map(|tree| vec![tree]).sboxed()
+            }
+            Expr::Seq(exprs) => {
+                let mut parts = exprs.iter().map(|e| self.expr(e, depth));
+                let first =
+                    parts.next().unwrap_or_else(|| Just(vec![]).sboxed());
+                parts.fold(first, |lhs, rhs| {
+                    (lhs, rhs)
+                        .prop_map(|(mut lhs, rhs)| {
+                            lhs.extend(rhs);
+                            lhs
+                        })
+                        .sboxed()
+                })
+            }
+            Expr::Choice(exprs) => {
+                let alternatives = exprs
+                    .iter()
+                    .filter(|e| self.min_depth(e) <= depth)
+                    .collect::<Vec<_>>();
+                Union::new(
+                    alternatives.into_iter().map(|e| self.expr(e, depth)),
+                )
+                .sboxed()
+            }
+            Expr::Repeat { expr, min, max } => {
+                if self.min_depth(expr) > depth {
+                    // Only reachable if `min` is 0.
+                    return Just(vec![]).sboxed();
+                }
+                let max = max.unwrap_or(self.grammar.max_repeat).max(*min);
+                vec(self.expr(expr, depth), *min..=max)
+                    .prop_map(|parts| parts.concat())
+                    .sboxed()
+            }
+        }
+    }
+}
+
+fn check_defined(grammar: &Grammar, expr: &Expr) -> Result<(), Error> {
+    match expr {
+        Expr::Literal(_) | Expr::Terminal(_) => Ok(()),
+        Expr::Rule(name) => {
+            if grammar.rules.contains_key(name) {
+                Ok(())
+            } else {
+                Err(Error::UndefinedRule(name.clone()))
+            }
+        }
+        Expr::Seq(exprs) | Expr::Choice(exprs) => {
+            exprs.iter().try_for_each(|e| check_defined(grammar, e))
+        }
+        Expr::Repeat { expr, .. } => check_defined(grammar, expr),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn arithmetic() -> Grammar {
+        Grammar::new("expr")
+            .rule(
+                "expr",
+                choice([
+                    rule("num"),
+                    seq([
+                        lit("("),
+                        rule("expr"),
+                        lit("+"),
+                        rule("expr"),
+                        lit(")"),
+                    ]),
+                    seq([lit("-"), rule("expr")]),
+                ]),
+            )
+            .rule("num", terminal("[0-9]{1,3}"))
+    }
+
+    fn depth(tree: &ParseTree) -> usize {
+        match tree {
+            ParseTree::Terminal(_) => 0,
+            ParseTree::Rule(_, children) => {
+                1 + children.iter().map(depth).max().unwrap_or(0)
+            }
+        }
+    }
+
+    /// Evaluate an `arithmetic()` expression, checking its structure.
+    fn eval(s: &str) -> (i64, &str) {
+        if let Some(rest) = s.strip_prefix('(') {
+            let (lhs, rest) = eval(rest);
+            let rest = rest.strip_prefix('+').unwrap();
+            let (rhs, rest) = eval(rest);
+            (lhs + rhs, rest.strip_prefix(')').unwrap())
+        } else if let Some(rest) = s.strip_prefix('-') {
+            let (value, rest) = eval(rest);
+            (-value, rest)
+        } else {
+            let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            assert!((1..=3).contains(&end), "{:?}", s);
+            (s[..end].parse().unwrap(), &s[end..])
+        }
+    }
+
+    #[test]
+    fn strings_are_derivations() {
+        let mut runner = TestRunner::deterministic();
+        let input = arithmetic().strings().unwrap();
+        let mut nested = 0;
+        for _ in 0..256 {
+            let s = input.new_tree(&mut runner).unwrap().current();
+            assert_eq!("", eval(&s).1, "{:?}", s);
+            if s.starts_with('(') {
+                nested += 1;
+            }
+        }
+        assert!(nested > 32, "{}", nested);
+    }
+
+    #[test]
+    fn depth_is_bounded() {
+        let mut runner = TestRunner::deterministic();
+        for max_depth in 2..6 {
+            let input = arithmetic().max_depth(max_depth).trees().unwrap();
+            let mut deepest = 0;
+            for _ in 0..256 {
+                let tree = input.new_tree(&mut runner).unwrap().current();
+                deepest = deepest.max(depth(&tree));
+            }
+            assert_eq!(max_depth, deepest);
+        }
+    }
+
+    #[test]
+    fn repetition_is_bounded() {
+        let mut runner = TestRunner::deterministic();
+        let input = Grammar::new("list")
+            .rule("list", seq([lit("["), zero_or_more(lit("x")), lit("]")]))
+            .max_repeat(3)
+            .strings()
+            .unwrap();
+        let mut seen = [false; 4];
+        for _ in 0..256 {
+            let s = input.new_tree(&mut runner).unwrap().current();
+            seen[s.len() - 2] = true;
+        }
+        assert_eq!([true; 4], seen);
+    }
+
+    #[test]
+    fn shrinks_to_simplest_derivation() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&arithmetic().strings().unwrap(), |s| {
+            prop_assert!(!s.contains('+'));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, s)) => assert_eq!("(0+0)", s),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let grammar = Grammar::new("a").rule("a", seq([lit("x"), rule("b")]));
+        assert_eq!(
+            Error::UndefinedRule("b".to_owned()),
+            grammar.trees().unwrap_err()
+        );
+        assert_eq!(
+            Error::UndefinedRule("b".to_owned()),
+            Grammar::new("b").strings().unwrap_err()
+        );
+
+        let grammar = Grammar::new("a").rule("a", seq([lit("x"), rule("a")]));
+        assert_eq!(
+            Error::NoDerivation("a".to_owned()),
+            grammar.trees().unwrap_err()
+        );
+
+        let grammar = Grammar::new("a")
+            .rule("a", rule("b"))
+            .rule("b", rule("c"))
+            .rule("c", lit("x"));
+        assert!(grammar.clone().max_depth(3).trees().is_ok());
+        assert_eq!(
+            Error::NoDerivation("a".to_owned()),
+            grammar.max_depth(2).trees().unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Parser for the EBNF accepted by `Grammar::parse()`.
+
+use crate::std_facade::{String, Vec};
+
+use super::{one_or_more, optional, zero_or_more, Error, Expr};
+
+/// Parse `text` into its rule definitions, in order. There is at least one.
+pub(super) fn parse(text: &str) -> Result<Vec<(String, Expr)>, Error> {
+    let mut parser = Parser { text, pos: 0 };
+    let mut rules = Vec::new();
+    loop {
+        parser.skip_space()?;
+        if parser.pos == text.len() {
+            break;
+        }
+
+        let name = parser.name()?;
+        parser.skip_space()?;
+        if !parser.eat("::=") && !parser.eat("=") {
+            return parser.error("expected `=`");
+        }
+        let expr = parser.alternatives()?;
+        parser.skip_space()?;
+        if !parser.eat(";") && !parser.eat(".") {
+            return parser.error("expected `;`");
+        }
+        rules.push((name, expr));
+    }
+
+    if rules.is_empty() {
+        return parser.error("grammar defines no rules");
+    }
+    Ok(rules)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: &'static str) -> Result<T, Error> {
+        Err(Error::Syntax(self.pos, message))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Consume `token` if the text continues with it.
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skip whitespace and comments.
+    fn skip_space(&mut self) -> Result<(), Error> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+
+            if !trimmed.starts_with("(*") {
+                return Ok(());
+            }
+            match trimmed[2..].find("*)") {
+                Some(end) => self.pos += end + 4,
+                None => return self.error("unterminated comment"),
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<String, Error> {
+        let bracketed = self.eat("<");
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+        if len == 0 || rest.starts_with(|c: char| c.is_numeric()) {
+            return self.error("expected a rule name");
+        }
+        let name = rest[..len].into();
+        self.pos += len;
+        if bracketed && !self.eat(">") {
+            return self.error("expected `>`");
+        }
+        Ok(name)
+    }
+
+    /// Parse alternatives separated by `|`.
+    fn alternatives(&mut self) -> Result<Expr, Error> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.eat("|") {
+            alternatives.push(self.sequence()?);
+        }
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Expr::Choice(alternatives)
+        })
+    }
+
+    /// Parse a possibly empty sequence of factors, optionally separated by
+    /// commas.
+    fn sequence(&mut self) -> Result<Expr, Error> {
+        let mut factors = Vec::new();
+        loop {
+            self.skip_space()?;
+            match self.peek() {
+                None | Some('|' | ')' | ']' | '}' | ';' | '.') => break,
+                _ => factors.push(self.factor()?),
+            }
+            self.skip_space()?;
+            self.eat(",");
+        }
+        Ok(if factors.len() == 1 {
+            factors.pop().unwrap()
+        } else {
+            Expr::Seq(factors)
+        })
+    }
+
+    /// Parse a term and any postfix operators.
+    fn factor(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.term()?;
+        loop {
+            expr = if self.eat("?") {
+                optional(expr)
+            } else if self.eat("*") {
+                zero_or_more(expr)
+            } else if self.eat("+") {
+                one_or_more(expr)
+            } else {
+                return Ok(expr);
+            };
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, Error> {
+        let (close, wrap): (&str, fn(Expr) -> Expr) = match self.peek() {
+            Some(quote @ ('"' | '\'')) => return self.literal(quote),
+            Some('(') => (")", |e| e),
+            Some('[') => ("]", optional),
+            Some('{') => ("}", zero_or_more),
+            _ => return self.name().map(Expr::Rule),
+        };
+        self.pos += 1;
+        let expr = self.alternatives()?;
+        self.skip_space()?;
+        if !self.eat(close) {
+            return self.error("unclosed group");
+        }
+        Ok(wrap(expr))
+    }
+
+    fn literal(&mut self, quote: char) -> Result<Expr, Error> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut text = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((offset, c)) = chars.next() {
+            if c == quote {
+                self.pos += offset + 1;
+                return Ok(Expr::Literal(text));
+            }
+            if c != '\\' {
+                text.push(c);
+                continue;
+            }
+            text.push(match chars.next() {
+                Some((_, c @ ('\\' | '"' | '\''))) => c,
+                Some((_, 'n')) => '\n',
+                Some((_, 'r')) => '\r',
+                Some((_, 't')) => '\t',
+                _ => {
+                    self.pos += offset;
+                    return self.error("invalid escape");
+                }
+            });
+        }
+        self.pos = start;
+        self.error("unterminated string")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grammar::{Error, Grammar, ParseTree};
+    use crate::strategy::*;
+    use crate::test_runner::*;
+
+    #[test]
+    fn parses_ebnf() {
+        let grammar = Grammar::parse(
+            r#"
+            (* A JSON-like list. *)
+            list = "[", [ item, { ",", item } ], "]" ;
+            item = '0' | "1" | list | "\"\n\"" ;
+            "#,
+        )
+        .unwrap();
+
+        let mut runner = TestRunner::deterministic();
+        let input = grammar.trees().unwrap();
+        for _ in 0..256 {
+            let tree = input.new_tree(&mut runner).unwrap().current();
+            let s = tree.text();
+            assert!(s.starts_with('[') && s.ends_with(']'), "{:?}", s);
+            assert!(!s.contains(",,") && !s.contains("[,"), "{:?}", s);
+            match tree {
+                ParseTree::Rule(name, _) => assert_eq!("list", name),
+                tree => panic!("Unexpected tree: {:?}", tree),
+            }
+        }
+    }
+
+    #[test]
+    fn parses_bnf_and_postfix_operators() {
+        let grammar = Grammar::parse(
+            r#"
+            <number> ::= <digit>+ ("." <digit>+)? <exp>*
+            <digit> ::= "0" | "1"
+            <exp> ::= "e" ;
+            "#,
+        );
+        // Without terminators, `<digit>` is part of the first rule, which
+        // then runs into `::=`.
+        match grammar {
+            Err(Error::Syntax(_, "expected a rule name")) => (),
+            grammar => panic!("Unexpected result: {:?}", grammar),
+        }
+
+        let grammar = Grammar::parse(
+            r#"
+            <number> ::= <digit>+ ("." <digit>+)? <exp>* ;
+            <digit> ::= "0" | "1" ;
+            <digit> ::= "2" ;
+            <exp> ::= "e" ;
+            "#,
+        )
+        .unwrap();
+
+        let mut runner = TestRunner::deterministic();
+        let input = grammar.strings().unwrap();
+        let mut seen_two = false;
+        for _ in 0..256 {
+            let s = input.new_tree(&mut runner).unwrap().current();
+            let number = s.trim_end_matches('e');
+            assert!(number.parse::<f64>().is_ok(), "{:?}", s);
+            seen_two |= s.contains('2');
+        }
+        assert!(seen_two);
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        let error = |text| Grammar::parse(text).unwrap_err();
+        assert_eq!(Error::Syntax(0, "grammar defines no rules"), error(""));
+        assert_eq!(Error::Syntax(2, "expected `=`"), error("a ; b"));
+        assert_eq!(Error::Syntax(4, "expected a rule name"), error("a = 1 ;"));
+        assert_eq!(Error::Syntax(7, "unclosed group"), error("a = (b ;"));
+        assert_eq!(Error::Syntax(4, "unterminated string"), error("a = 'b ;"));
+        assert_eq!(Error::Syntax(5, "invalid escape"), error(r#"a = "\q" ;"#));
+        assert_eq!(Error::Syntax(0, "unterminated comment"), error("(* a"));
+    }
+}
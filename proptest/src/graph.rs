@@ -0,0 +1,609 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating `petgraph` graphs.
+//!
+//! [`directed`] and [`undirected`] generate graphs with a number of nodes in
+//! a given range, whose node and edge weights are generated by strategies of
+//! their own. The number of edges can be bounded with
+//! [`GraphStrategy::with_edges`], and the graphs restricted to connected or
+//! acyclic ones with [`GraphStrategy::connected`] and
+//! [`GraphStrategy::acyclic`]. Graphs never have self-loops or parallel
+//! edges.
+//!
+//! Graphs shrink by first removing edges, then nodes along with their edges,
+//! and finally by shrinking the weights, while staying within the bounds and
+//! keeping connected graphs connected.
+//!
+//! ```rust
+//! use petgraph::algo::toposort;
+//! use proptest::graph;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn dags_sort_topologically(
+//!         g in graph::directed(Just(()), Just(()), 0..20).acyclic()
+//!     ) {
+//!         let order = toposort(&g, None).unwrap();
+//!         prop_assert_eq!(g.node_count(), order.len());
+//!     }
+//! }
+//! # fn main() { dags_sort_topologically(); }
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use std::collections::BTreeSet;
+
+use ::petgraph::graph::{Graph, NodeIndex};
+use ::petgraph::{Directed, EdgeType, Undirected};
+use rand::seq::SliceRandom;
+
+use crate::collection::SizeRange;
+use crate::num::sample_uniform_incl;
+use crate::std_facade::{vec, Vec};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// Strategy to create graphs with a number of nodes in a certain range.
+///
+/// Created by the `directed()` and `undirected()` functions in the same
+/// module.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone)]
+pub struct GraphStrategy<N, E, Ty> {
+    node: N,
+    edge: E,
+    nodes: SizeRange,
+    edges: Option<SizeRange>,
+    connected: bool,
+    acyclic: bool,
+    _marker: PhantomData<fn() -> Ty>,
+}
+
+impl<N: fmt::Debug, E: fmt::Debug, Ty> fmt::Debug for GraphStrategy<N, E, Ty> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GraphStrategy")
+            .field("node", &self.node)
+            .field("edge", &self.edge)
+            .field("nodes", &self.nodes)
+            .field("edges", &self.edges)
+            .field("connected", &self.connected)
+            .field("acyclic", &self.acyclic)
+            .finish()
+    }
+}
+
+/// Create a strategy to generate directed graphs with a number of nodes in
+/// `nodes`, whose node weights are generated by `node` and whose edge
+/// weights are generated by `edge`.
+///
+/// Unless bounded with [`GraphStrategy::with_edges`], the graphs have up to
+/// twice as many edges as nodes.
+pub fn directed<N: Strategy, E: Strategy>(
+    node: N,
+    edge: E,
+    nodes: impl Into<SizeRange>,
+) -> GraphStrategy<N, E, Directed> {
+    GraphStrategy::new(node, edge, nodes.into())
+}
+
+/// Create a strategy to generate undirected graphs with a number of nodes in
+/// `nodes`, whose node weights are generated by `node` and whose edge
+/// weights are generated by `edge`.
+///
+/// Unless bounded with [`GraphStrategy::with_edges`], the graphs have up to
+/// twice as many edges as nodes.
+pub fn undirected<N: Strategy, E: Strategy>(
+    node: N,
+    edge: E,
+    nodes: impl Into<SizeRange>,
+) -> GraphStrategy<N, E, Undirected> {
+    GraphStrategy::new(node, edge, nodes.into())
+}
+
+impl<N, E, Ty> GraphStrategy<N, E, Ty> {
+    fn new(node: N, edge: E, nodes: SizeRange) -> Self {
+        nodes.assert_nonempty();
+        GraphStrategy {
+            node,
+            edge,
+            nodes,
+            edges: None,
+            connected: false,
+            acyclic: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Generate a number of edges in `edges`, as far as the number of nodes
+    /// and the other restrictions allow.
+    pub fn with_edges(self, edges: impl Into<SizeRange>) -> Self {
+        let edges = edges.into();
+        edges.assert_nonempty();
+        GraphStrategy {
+            edges: Some(edges),
+            ..self
+        }
+    }
+
+    /// Only generate connected graphs, or weakly connected ones if they are
+    /// directed.
+    ///
+    /// The graphs are built around a random spanning tree, so they have at
+    /// least one edge fewer than nodes.
+    pub fn connected(self) -> Self {
+        GraphStrategy {
+            connected: true,
+            ..self
+        }
+    }
+
+    /// Only generate acyclic graphs: directed acyclic graphs if they are
+    /// directed, or forests if they are undirected.
+    pub fn acyclic(self) -> Self {
+        GraphStrategy {
+            acyclic: true,
+            ..self
+        }
+    }
+}
+
+/// Pick `count` of `candidates` at random, or all of them if there are not
+/// that many.
+fn pick<T>(
+    runner: &mut TestRunner,
+    mut candidates: Vec<T>,
+    count: usize,
+) -> Vec<T> {
+    candidates.shuffle(runner.rng());
+    candidates.truncate(count);
+    candidates
+}
+
+impl<N: Strategy, E: Strategy, Ty: EdgeType> Strategy
+    for GraphStrategy<N, E, Ty>
+{
+    type Tree = GraphValueTree<N::Tree, E::Tree, Ty>;
+    type Value = Graph<N::Value, E::Value, Ty>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let (start, end) = self.nodes.start_end_incl();
+        let end = runner.sized(start, end);
+        let n = sample_uniform_incl(runner, start, end);
+        let directed = Ty::is_directed();
+
+        let max_edges = match (directed, self.acyclic) {
+            (true, false) => n * n.saturating_sub(1),
+            (false, true) => n.saturating_sub(1),
+            _ => n * n.saturating_sub(1) / 2,
+        };
+        let required = if self.connected {
+            n.saturating_sub(1)
+        } else {
+            0
+        };
+        let (min_edges, max) = match self.edges {
+            Some(ref edges) => edges.start_end_incl(),
+            None => (0, 2 * n),
+        };
+        let low = min_edges.max(required).min(max_edges);
+        let high = max.min(max_edges).max(low);
+        let edge_count = sample_uniform_incl(runner, low, high);
+
+        // Edges are chosen between ranks, each (source, target, required).
+        // In acyclic graphs they go from lower to higher ranks, and in
+        // connected ones each rank but the first has a required edge from a
+        // lower one, forming a spanning tree.
+        let mut edges = Vec::with_capacity(edge_count);
+        if self.connected {
+            for rank in 1..n {
+                let parent = sample_uniform_incl(runner, 0, rank - 1);
+                edges.push((parent, rank, true));
+            }
+        }
+        let extra = edge_count - edges.len();
+        if !directed && self.acyclic {
+            // A forest, where some ranks have an edge from a lower one.
+            let children = pick(runner, (1..n).collect(), extra);
+            for rank in children {
+                let parent = sample_uniform_incl(runner, 0, rank - 1);
+                edges.push((parent, rank, false));
+            }
+        } else {
+            let present = edges
+                .iter()
+                .map(|&(source, target, _)| (source, target))
+                .collect::<BTreeSet<_>>();
+            let mut candidates = Vec::new();
+            for source in 0..n {
+                for target in 0..n {
+                    let forward = source < target;
+                    if source != target
+                        && (forward || (directed && !self.acyclic))
+                        && !present.contains(&(source, target))
+                    {
+                        candidates.push((source, target, false));
+                    }
+                }
+            }
+            edges.extend(pick(runner, candidates, extra));
+        }
+
+        let mut ranked = (0..n).collect::<Vec<_>>();
+        ranked.shuffle(runner.rng());
+
+        let mut nodes = Vec::with_capacity(n);
+        while nodes.len() < n {
+            nodes.push(self.node.new_tree(runner)?);
+        }
+        let mut graph_edges = Vec::with_capacity(edges.len());
+        for (source, target, required) in edges {
+            graph_edges.push(GraphEdge {
+                source: ranked[source],
+                target: ranked[target],
+                weight: self.edge.new_tree(runner)?,
+                required,
+            });
+        }
+
+        Ok(GraphValueTree {
+            included_nodes: vec![true; nodes.len()],
+            included_edges: vec![true; graph_edges.len()],
+            nodes,
+            edges: graph_edges,
+            min_nodes: start,
+            min_edges: min_edges.min(edge_count),
+            shrink: Shrink::RemoveEdge(0),
+            prev_shrink: None,
+            pass_nodes: 0,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Shrink {
+    RemoveEdge(usize),
+    RemoveNode(usize),
+    Node(usize),
+    Edge(usize),
+}
+
+#[derive(Clone, Debug)]
+struct GraphEdge<E> {
+    source: usize,
+    target: usize,
+    weight: E,
+    /// Whether the edge is part of the spanning tree of a connected graph,
+    /// going from a parent to a child.
+    required: bool,
+}
+
+/// `ValueTree` corresponding to `GraphStrategy`.
+#[derive(Clone)]
+pub struct GraphValueTree<N, E, Ty> {
+    nodes: Vec<N>,
+    edges: Vec<GraphEdge<E>>,
+    included_nodes: Vec<bool>,
+    included_edges: Vec<bool>,
+    min_nodes: usize,
+    min_edges: usize,
+    shrink: Shrink,
+    prev_shrink: Option<Shrink>,
+    /// The number of nodes at the start of the current pass over the nodes.
+    /// Removing some may have turned others into leaves of the spanning tree,
+    /// so passes repeat until one removes none.
+    pass_nodes: usize,
+    _marker: PhantomData<fn() -> Ty>,
+}
+
+impl<N: fmt::Debug, E: fmt::Debug, Ty> fmt::Debug for GraphValueTree<N, E, Ty> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GraphValueTree")
+            .field("nodes", &self.nodes)
+            .field("edges", &self.edges)
+            .field("included_nodes", &self.included_nodes)
+            .field("included_edges", &self.included_edges)
+            .field("shrink", &self.shrink)
+            .field("prev_shrink", &self.prev_shrink)
+            .finish()
+    }
+}
+
+impl<N, E, Ty> GraphValueTree<N, E, Ty> {
+    fn is_live(&self, ix: usize) -> bool {
+        let edge = &self.edges[ix];
+        self.included_edges[ix]
+            && self.included_nodes[edge.source]
+            && self.included_nodes[edge.target]
+    }
+
+    fn node_count(&self) -> usize {
+        self.included_nodes.iter().filter(|&&i| i).count()
+    }
+
+    fn live_edges(&self) -> usize {
+        (0..self.edges.len()).filter(|&ix| self.is_live(ix)).count()
+    }
+
+    fn can_remove_edge(&self, ix: usize) -> bool {
+        self.is_live(ix)
+            && !self.edges[ix].required
+            && self.live_edges() > self.min_edges
+    }
+
+    fn can_remove_node(&self, node: usize) -> bool {
+        if !self.included_nodes[node] || self.node_count() <= self.min_nodes {
+            return false;
+        }
+
+        let mut incident = 0;
+        for ix in 0..self.edges.len() {
+            let edge = &self.edges[ix];
+            if !self.is_live(ix) {
+                continue;
+            }
+            // Only leaves of the spanning tree can go without disconnecting
+            // the graph.
+            if edge.required && edge.source == node {
+                return false;
+            }
+            if edge.source == node || edge.target == node {
+                incident += 1;
+            }
+        }
+        self.live_edges() - incident >= self.min_edges
+    }
+}
+
+impl<N: ValueTree, E: ValueTree, Ty: EdgeType> ValueTree
+    for GraphValueTree<N, E, Ty>
+{
+    type Value = Graph<N::Value, E::Value, Ty>;
+
+    fn current(&self) -> Graph<N::Value, E::Value, Ty> {
+        let mut graph = Graph::default();
+        let indices = self
+            .nodes
+            .iter()
+            .zip(&self.included_nodes)
+            .map(|(node, &included)| {
+                if included {
+                    Some(graph.add_node(node.current()))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<Option<NodeIndex>>>();
+        for (ix, edge) in self.edges.iter().enumerate() {
+            if self.is_live(ix) {
+                graph.add_edge(
+                    indices[edge.source].unwrap(),
+                    indices[edge.target].unwrap(),
+                    edge.weight.current(),
+                );
+            }
+        }
+        graph
+    }
+
+    fn simplify(&mut self) -> bool {
+        loop {
+            match self.shrink {
+                Shrink::RemoveEdge(ix) if ix < self.edges.len() => {
+                    if self.can_remove_edge(ix) {
+                        self.included_edges[ix] = false;
+                        self.prev_shrink = Some(self.shrink);
+                        self.shrink = Shrink::RemoveEdge(ix + 1);
+                        return true;
+                    }
+                    self.shrink = Shrink::RemoveEdge(ix + 1);
+                }
+                Shrink::RemoveEdge(_) => {
+                    self.pass_nodes = self.node_count();
+                    self.shrink = Shrink::RemoveNode(0);
+                }
+                Shrink::RemoveNode(node) if node < self.nodes.len() => {
+                    if self.can_remove_node(node) {
+                        self.included_nodes[node] = false;
+                        self.prev_shrink = Some(self.shrink);
+                        self.shrink = Shrink::RemoveNode(node + 1);
+                        return true;
+                    }
+                    self.shrink = Shrink::RemoveNode(node + 1);
+                }
+                Shrink::RemoveNode(_)
+                    if self.node_count() < self.pass_nodes =>
+                {
+                    self.pass_nodes = self.node_count();
+                    self.shrink = Shrink::RemoveNode(0);
+                }
+                Shrink::RemoveNode(_) => self.shrink = Shrink::Node(0),
+                Shrink::Node(node) if node < self.nodes.len() => {
+                    if self.included_nodes[node] && self.nodes[node].simplify()
+                    {
+                        self.prev_shrink = Some(self.shrink);
+                        return true;
+                    }
+                    self.shrink = Shrink::Node(node + 1);
+                }
+                Shrink::Node(_) => self.shrink = Shrink::Edge(0),
+                Shrink::Edge(ix) if ix < self.edges.len() => {
+                    if self.is_live(ix) && self.edges[ix].weight.simplify() {
+                        self.prev_shrink = Some(self.shrink);
+                        return true;
+                    }
+                    self.shrink = Shrink::Edge(ix + 1);
+                }
+                Shrink::Edge(_) => {
+                    self.prev_shrink = None;
+                    return false;
+                }
+            }
+        }
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.prev_shrink {
+            None => false,
+            Some(Shrink::RemoveEdge(ix)) => {
+                self.included_edges[ix] = true;
+                self.prev_shrink = None;
+                true
+            }
+            Some(Shrink::RemoveNode(node)) => {
+                self.included_nodes[node] = true;
+                self.prev_shrink = None;
+                true
+            }
+            Some(Shrink::Node(node)) => {
+                if self.nodes[node].complicate() {
+                    true
+                } else {
+                    self.prev_shrink = None;
+                    false
+                }
+            }
+            Some(Shrink::Edge(ix)) => {
+                if self.edges[ix].weight.complicate() {
+                    true
+                } else {
+                    self.prev_shrink = None;
+                    false
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ::petgraph::algo::{
+        connected_components, is_cyclic_directed, is_cyclic_undirected,
+    };
+
+    use super::*;
+
+    #[test]
+    fn counts_are_in_range() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let g = directed(Just(()), Just(()), 2..10)
+                .with_edges(3..=5)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!((2..10).contains(&g.node_count()));
+            // Two nodes admit only two directed edges.
+            assert!(
+                (3.min(g.node_count() * (g.node_count() - 1))..=5)
+                    .contains(&g.edge_count()),
+                "{:?}",
+                g
+            );
+            for edge in g.raw_edges() {
+                assert_ne!(edge.source(), edge.target());
+            }
+        }
+    }
+
+    #[test]
+    fn restrictions_hold_while_shrinking() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let mut tree = directed(0..10u8, 0..10u8, 0..12)
+                .acyclic()
+                .connected()
+                .new_tree(&mut runner)
+                .unwrap();
+            loop {
+                let g = tree.current();
+                assert!(!is_cyclic_directed(&g), "{:?}", g);
+                assert!(connected_components(&g) <= 1, "{:?}", g);
+                if !tree.simplify() {
+                    break;
+                }
+            }
+
+            let mut tree = undirected(0..10u8, 0..10u8, 0..12)
+                .acyclic()
+                .new_tree(&mut runner)
+                .unwrap();
+            loop {
+                let g = tree.current();
+                assert!(!is_cyclic_undirected(&g), "{:?}", g);
+                if !tree.simplify() {
+                    break;
+                }
+            }
+
+            let mut tree = undirected(Just(()), Just(()), 1..12)
+                .connected()
+                .with_edges(0..30)
+                .new_tree(&mut runner)
+                .unwrap();
+            loop {
+                let g = tree.current();
+                assert_eq!(1, connected_components(&g), "{:?}", g);
+                if !tree.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shrinks_edges_then_nodes() {
+        let mut runner = TestRunner::deterministic();
+        let strategy =
+            undirected(0..100u32, 0..100u32, 0..20).with_edges(0..60);
+        let result = runner.run(&strategy, |g| {
+            prop_assert!(g.node_indices().all(|n| g.neighbors(n).count() < 3));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, g)) => {
+                assert_eq!(4, g.node_count(), "{:?}", g);
+                assert_eq!(3, g.edge_count(), "{:?}", g);
+                assert!(g.node_weights().all(|&w| w == 0), "{:?}", g);
+                assert!(g.edge_weights().all(|&w| w == 0), "{:?}", g);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    /// The weights and endpoints of a graph, since `Graph` itself cannot be
+    /// compared.
+    fn contents<N: Clone, E: Clone, Ty: EdgeType>(
+        g: Graph<N, E, Ty>,
+    ) -> (Vec<N>, Vec<(usize, usize, E)>) {
+        let nodes = g.node_weights().cloned().collect();
+        let edges = g
+            .raw_edges()
+            .iter()
+            .map(|e| (e.source().index(), e.target().index(), e.weight.clone()))
+            .collect();
+        (nodes, edges)
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(
+            directed(0..10u8, 0..10u8, 0..8).prop_map(contents),
+            None,
+        );
+        check_strategy_sanity(
+            undirected(0..10u8, Just(()), 0..8)
+                .connected()
+                .prop_map(contents),
+            None,
+        );
+    }
+}
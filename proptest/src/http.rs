@@ -0,0 +1,640 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating syntactically valid HTTP/1.1 requests and
+//! responses.
+//!
+//! Messages are generated as [`Request`]s and [`Response`]s, which serialize
+//! themselves with `to_bytes()`. Their headers always include the ones that
+//! frame the body, either `Content-Length` or `Transfer-Encoding: chunked`,
+//! consistently with the body, and requests always have a single `Host`
+//! header. [`HttpParams`] controls the remaining headers, which may repeat
+//! names or use unusual casing, and the body.
+
+use crate::arbitrary::any;
+use crate::collection::{vec, SizeRange};
+use crate::formats::hostname;
+use crate::option;
+use crate::sample::{select, Index};
+use crate::std_facade::{string::ToString, Box, String, ToOwned, Vec};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// The methods of requests, from [RFC 9110][rfc9110] and
+/// [RFC 5789][rfc5789].
+///
+/// [rfc9110]: https://www.rfc-editor.org/rfc/rfc9110#section-9
+/// [rfc5789]: https://www.rfc-editor.org/rfc/rfc5789
+const METHODS: &[&str] = &[
+    "GET", "HEAD", "POST", "PUT", "DELETE", "CONNECT", "OPTIONS", "TRACE",
+    "PATCH",
+];
+
+/// Common header names, other than those framing the body.
+const HEADER_NAMES: &[&str] = &[
+    "Accept",
+    "Accept-Encoding",
+    "Accept-Language",
+    "Cache-Control",
+    "Content-Type",
+    "Cookie",
+    "Date",
+    "ETag",
+    "Server",
+    "User-Agent",
+    "Vary",
+];
+
+/// Common status codes and their reason phrases.
+const STATUSES: &[(u16, &str)] = &[
+    (100, "Continue"),
+    (200, "OK"),
+    (201, "Created"),
+    (204, "No Content"),
+    (206, "Partial Content"),
+    (301, "Moved Permanently"),
+    (304, "Not Modified"),
+    (400, "Bad Request"),
+    (404, "Not Found"),
+    (500, "Internal Server Error"),
+    (503, "Service Unavailable"),
+];
+
+/// How the body of a message is delimited.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Framing {
+    /// There is no body, and no header framing it.
+    None,
+    /// The body follows the headers as is, with its length in a
+    /// `Content-Length` header.
+    ContentLength,
+    /// The body is split in chunks of the given lengths, and there is a
+    /// `Transfer-Encoding: chunked` header.
+    Chunked(Vec<usize>),
+}
+
+/// An HTTP/1.1 request.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Request {
+    /// The method, such as `GET`.
+    pub method: String,
+    /// The request target, such as `/index.html`.
+    pub target: String,
+    /// The names and values of the headers, in order, including `Host` and
+    /// the header framing the body.
+    pub headers: Vec<(String, String)>,
+    /// The body, before any framing.
+    pub body: Vec<u8>,
+    /// How the body is delimited.
+    pub framing: Framing,
+}
+
+/// An HTTP/1.1 response.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Response {
+    /// The status code, from 100 to 599.
+    pub status: u16,
+    /// The reason phrase, which may be empty.
+    pub reason: String,
+    /// The names and values of the headers, in order, including the header
+    /// framing the body.
+    pub headers: Vec<(String, String)>,
+    /// The body, before any framing.
+    pub body: Vec<u8>,
+    /// How the body is delimited.
+    pub framing: Framing,
+}
+
+impl Request {
+    /// Returns the request as it would be sent.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = format!("{} {} HTTP/1.1\r\n", self.method, self.target)
+            .into_bytes();
+        write_rest(&mut bytes, &self.headers, &self.body, &self.framing);
+        bytes
+    }
+}
+
+impl Response {
+    /// Returns the response as it would be sent.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = format!("HTTP/1.1 {} {}\r\n", self.status, self.reason)
+            .into_bytes();
+        write_rest(&mut bytes, &self.headers, &self.body, &self.framing);
+        bytes
+    }
+}
+
+/// Write the headers and the framed body of a message.
+fn write_rest(
+    out: &mut Vec<u8>,
+    headers: &[(String, String)],
+    body: &[u8],
+    framing: &Framing,
+) {
+    for (name, value) in headers {
+        out.extend(format!("{}: {}\r\n", name, value).into_bytes());
+    }
+    out.extend(b"\r\n");
+    match *framing {
+        Framing::None => (),
+        Framing::ContentLength => out.extend(body),
+        Framing::Chunked(ref lengths) => {
+            let mut rest = body;
+            for &len in lengths {
+                let (chunk, tail) = rest.split_at(len);
+                out.extend(format!("{:x}\r\n", len).into_bytes());
+                out.extend(chunk);
+                out.extend(b"\r\n");
+                rest = tail;
+            }
+            out.extend(b"0\r\n\r\n");
+        }
+    }
+}
+
+/// Parameters for generating requests and responses.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HttpParams {
+    /// The range of the number of headers, not counting `Host` and the
+    /// header framing the body.
+    ///
+    /// The default is `0..6`.
+    pub headers: SizeRange,
+    /// Whether several headers may have the same name. `Host` and the
+    /// headers framing the body are never repeated.
+    ///
+    /// The default is `false`.
+    pub duplicate_headers: bool,
+    /// Whether header names may use any mix of upper and lower case, rather
+    /// than the usual `Content-Type` style.
+    ///
+    /// The default is `false`.
+    pub odd_casing: bool,
+    /// Whether bodies may use chunked framing.
+    ///
+    /// The default is `true`.
+    pub chunked: bool,
+    /// The range of the length of the body, for messages which can have one.
+    ///
+    /// The default is `0..64`.
+    pub body: SizeRange,
+}
+
+impl Default for HttpParams {
+    fn default() -> Self {
+        HttpParams {
+            headers: (0..6).into(),
+            duplicate_headers: false,
+            odd_casing: false,
+            chunked: true,
+            body: (0..64).into(),
+        }
+    }
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates HTTP/1.1 requests.
+    ///
+    /// Created by the `requests()` function in the same module.
+    #[derive(Clone, Debug)]
+    pub struct RequestStrategy[][](SBoxedStrategy<Request>)
+        -> RequestValueTree;
+    /// `ValueTree` corresponding to `RequestStrategy`.
+    pub struct RequestValueTree[][](Box<dyn ValueTree<Value = Request>>)
+        -> Request;
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates HTTP/1.1 responses.
+    ///
+    /// Created by the `responses()` function in the same module.
+    #[derive(Clone, Debug)]
+    pub struct ResponseStrategy[][](SBoxedStrategy<Response>)
+        -> ResponseValueTree;
+    /// `ValueTree` corresponding to `ResponseStrategy`.
+    pub struct ResponseValueTree[][](Box<dyn ValueTree<Value = Response>>)
+        -> Response;
+}
+
+/// Change the case of the letters of `name` according to the bits of
+/// `case`, upper case for set bits.
+fn recase(name: &str, case: u32) -> String {
+    name.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if case & (1 << (i % 32)) != 0 {
+                c.to_ascii_uppercase()
+            } else {
+                c.to_ascii_lowercase()
+            }
+        })
+        .collect()
+}
+
+/// Generates the masks passed to `recase()`, or `None` to keep names as
+/// they are.
+fn casing(params: &HttpParams) -> SBoxedStrategy<Option<u32>> {
+    if params.odd_casing {
+        option::weighted(0.9, any::<u32>()).sboxed()
+    } else {
+        Just(None).sboxed()
+    }
+}
+
+/// Generates a header value: visible ASCII characters, with spaces and tabs
+/// inside.
+fn header_value() -> &'static str {
+    "[!-~]([ \t!-~]{0,20}[!-~])?"
+}
+
+/// Generates the headers other than `Host` and the header framing the body.
+fn headers(params: &HttpParams) -> SBoxedStrategy<Vec<(String, String)>> {
+    let name = prop_oneof![
+        3 => select(HEADER_NAMES).prop_map(ToOwned::to_owned),
+        1 => "X-[A-Z][a-z0-9]{0,8}(-[A-Z][a-z0-9]{0,8}){0,2}",
+    ];
+    // The index of an earlier header to take the name of
+    let repeat = if params.duplicate_headers {
+        option::weighted(0.3, any::<Index>()).sboxed()
+    } else {
+        Just(None).sboxed()
+    };
+    let duplicate_headers = params.duplicate_headers;
+    vec((name, header_value(), repeat), params.headers.clone())
+        .prop_map(move |generated| {
+            let mut headers: Vec<(String, String)> = Vec::new();
+            for (name, value, repeat) in generated {
+                let name = match repeat {
+                    Some(index) if !headers.is_empty() => {
+                        headers[index.index(headers.len())].0.clone()
+                    }
+                    _ => name,
+                };
+                if duplicate_headers
+                    || !headers
+                        .iter()
+                        .any(|(n, _)| n.eq_ignore_ascii_case(&name))
+                {
+                    headers.push((name, value));
+                }
+            }
+            headers
+        })
+        .sboxed()
+}
+
+/// Generates a body and how to frame it. With `chunked`, the indices give
+/// the boundaries between chunks.
+fn body(
+    params: &HttpParams,
+) -> impl Strategy<Value = (Vec<u8>, Option<Vec<Index>>)> {
+    let chunks = if params.chunked {
+        option::of(vec(any::<Index>(), 0..4)).sboxed()
+    } else {
+        Just(None).sboxed()
+    };
+    (vec(any::<u8>(), params.body.clone()), chunks)
+}
+
+/// Returns the framing of `body`, and the header it needs if any.
+fn framing(
+    body: &[u8],
+    chunks: Option<Vec<Index>>,
+    empty_without_header: bool,
+) -> (Framing, Option<(String, String)>) {
+    match chunks {
+        Some(boundaries) => {
+            let mut boundaries: Vec<usize> = boundaries
+                .iter()
+                .map(|index| index.index(body.len() + 1))
+                .chain([0, body.len()])
+                .collect();
+            boundaries.sort_unstable();
+            boundaries.dedup();
+            let lengths = boundaries.windows(2).map(|w| w[1] - w[0]).collect();
+            (
+                Framing::Chunked(lengths),
+                Some(("Transfer-Encoding".to_owned(), "chunked".to_owned())),
+            )
+        }
+        None if body.is_empty() && empty_without_header => {
+            (Framing::None, None)
+        }
+        None => (
+            Framing::ContentLength,
+            Some(("Content-Length".to_owned(), body.len().to_string())),
+        ),
+    }
+}
+
+/// Applies `case` to the names of `headers`.
+fn recase_all(headers: &mut [(String, String)], case: Option<u32>) {
+    if let Some(case) = case {
+        for (i, (name, _)) in headers.iter_mut().enumerate() {
+            *name = recase(name, case.rotate_left(i as u32));
+        }
+    }
+}
+
+/// Creates a strategy which generates syntactically valid HTTP/1.1
+/// requests, as defined by [RFC 9112][rfc9112].
+///
+/// Methods are the standard ones. Targets are in the form appropriate to the
+/// method: an authority such as `example.com:443` for `CONNECT`, possibly
+/// `*` for `OPTIONS`, and otherwise a path and query such as `/a/b?c=d`, or
+/// an absolute URL. Bodies may be empty, in which case requests often have
+/// no header framing them.
+///
+/// Requests shrink toward `GET / HTTP/1.1` with only a `Host` header.
+///
+/// [rfc9112]: https://www.rfc-editor.org/rfc/rfc9112
+pub fn requests(params: HttpParams) -> RequestStrategy {
+    let target = (
+        0..3u8,
+        "/([A-Za-z0-9._~-]{1,8}(/[A-Za-z0-9._~-]{0,8}){0,3})?\
+         (\\?[A-Za-z0-9=&._~-]{0,16})?",
+        1..=u16::MAX,
+    );
+    RequestStrategy(
+        (
+            select(METHODS),
+            hostname(),
+            target,
+            headers(&params),
+            body(&params),
+            casing(&params),
+        )
+            .prop_map(
+                |(method, host, (form, path, port), headers, body, case)| {
+                    let target = match (method, form) {
+                        ("CONNECT", _) => format!("{}:{}", host, port),
+                        ("OPTIONS", 2) => "*".to_owned(),
+                        (_, 1) => format!("http://{}{}", host, path),
+                        _ => path,
+                    };
+                    let (body, chunks) = body;
+                    let (framing, framing_header) =
+                        framing(&body, chunks, true);
+
+                    let mut all_headers = vec![("Host".to_owned(), host)];
+                    all_headers.extend(headers);
+                    all_headers.extend(framing_header);
+                    recase_all(&mut all_headers, case);
+                    Request {
+                        method: method.to_owned(),
+                        target,
+                        headers: all_headers,
+                        body,
+                        framing,
+                    }
+                },
+            )
+            .sboxed(),
+    )
+}
+
+/// Creates a strategy which generates syntactically valid HTTP/1.1
+/// responses, as defined by [RFC 9112][rfc9112].
+///
+/// Status codes are common ones with their usual reason phrase, or any code
+/// from 100 to 599 with an arbitrary one. Responses with a 1xx, 204 or 304
+/// status have no body and no header framing it; all others have one,
+/// possibly empty.
+///
+/// Responses shrink toward `HTTP/1.1 100 Continue` with no headers.
+///
+/// [rfc9112]: https://www.rfc-editor.org/rfc/rfc9112
+pub fn responses(params: HttpParams) -> ResponseStrategy {
+    let status = prop_oneof![
+        3 => select(STATUSES).prop_map(|(status, reason)| {
+            (status, reason.to_owned())
+        }),
+        1 => (100..600u16, "([A-Za-z][A-Za-z ]{0,20})?"),
+    ];
+    ResponseStrategy(
+        (status, headers(&params), body(&params), casing(&params))
+            .prop_map(|((status, reason), mut headers, body, case)| {
+                let (mut body, chunks) = body;
+                let framing = if (100..200).contains(&status)
+                    || status == 204
+                    || status == 304
+                {
+                    body.clear();
+                    Framing::None
+                } else {
+                    let (framing, framing_header) =
+                        self::framing(&body, chunks, false);
+                    headers.extend(framing_header);
+                    framing
+                };
+                recase_all(&mut headers, case);
+                Response {
+                    status,
+                    reason,
+                    headers,
+                    body,
+                    framing,
+                }
+            })
+            .sboxed(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Check that `rest`, which follows the headers, is the body framed as
+    /// the headers say, and return the body.
+    fn parse_body(headers: &[httparse::Header<'_>], rest: &[u8]) -> Vec<u8> {
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|header| header.name.eq_ignore_ascii_case(name))
+                .map(|header| std::str::from_utf8(header.value).unwrap())
+        };
+        match (header("Content-Length"), header("Transfer-Encoding")) {
+            (None, None) => {
+                assert!(rest.is_empty());
+                Vec::new()
+            }
+            (Some(len), None) => {
+                assert_eq!(len.parse::<usize>().unwrap(), rest.len());
+                rest.to_vec()
+            }
+            (None, Some("chunked")) => {
+                let mut body = Vec::new();
+                let mut rest = rest;
+                loop {
+                    let (start, len) = match httparse::parse_chunk_size(rest) {
+                        Ok(httparse::Status::Complete(chunk)) => chunk,
+                        result => panic!("Bad chunk: {:?}", result),
+                    };
+                    let len = len as usize;
+                    let chunk = &rest[start..];
+                    if len == 0 {
+                        assert_eq!(b"\r\n", chunk);
+                        return body;
+                    }
+                    body.extend(&chunk[..len]);
+                    assert_eq!(b"\r\n", &chunk[len..len + 2]);
+                    rest = &chunk[len + 2..];
+                }
+            }
+            framing => panic!("Unexpected framing: {:?}", framing),
+        }
+    }
+
+    fn check_requests(params: HttpParams) -> Vec<Request> {
+        let mut runner = TestRunner::deterministic();
+        let input = requests(params);
+        (0..256)
+            .map(|_| {
+                let request = input.new_tree(&mut runner).unwrap().current();
+                let bytes = request.to_bytes();
+                let mut headers = [httparse::EMPTY_HEADER; 16];
+                let mut parsed = httparse::Request::new(&mut headers);
+                let len = match parsed.parse(&bytes) {
+                    Ok(httparse::Status::Complete(len)) => len,
+                    result => panic!("{:?}: {:?}", request, result),
+                };
+                assert_eq!(Some(request.method.as_str()), parsed.method);
+                assert_eq!(Some(request.target.as_str()), parsed.path);
+                assert_eq!(Some(1), parsed.version);
+                assert_eq!(request.headers.len(), parsed.headers.len());
+                let hosts = parsed
+                    .headers
+                    .iter()
+                    .filter(|h| h.name.eq_ignore_ascii_case("host"))
+                    .count();
+                assert_eq!(1, hosts);
+                assert_eq!(
+                    request.body,
+                    parse_body(parsed.headers, &bytes[len..])
+                );
+                request
+            })
+            .collect()
+    }
+
+    fn check_responses(params: HttpParams) -> Vec<Response> {
+        let mut runner = TestRunner::deterministic();
+        let input = responses(params);
+        (0..256)
+            .map(|_| {
+                let response = input.new_tree(&mut runner).unwrap().current();
+                let bytes = response.to_bytes();
+                let mut headers = [httparse::EMPTY_HEADER; 16];
+                let mut parsed = httparse::Response::new(&mut headers);
+                let len = match parsed.parse(&bytes) {
+                    Ok(httparse::Status::Complete(len)) => len,
+                    result => panic!("{:?}: {:?}", response, result),
+                };
+                assert_eq!(Some(response.status), parsed.code);
+                assert_eq!(Some(response.reason.as_str()), parsed.reason);
+                assert_eq!(response.headers.len(), parsed.headers.len());
+                assert_eq!(
+                    response.body,
+                    parse_body(parsed.headers, &bytes[len..])
+                );
+                response
+            })
+            .collect()
+    }
+
+    fn has_duplicates(headers: &[(String, String)]) -> bool {
+        headers.iter().enumerate().any(|(i, (name, _))| {
+            headers[..i]
+                .iter()
+                .any(|(n, _)| n.eq_ignore_ascii_case(name))
+        })
+    }
+
+    #[test]
+    fn generates_valid_requests() {
+        let requests = check_requests(HttpParams::default());
+        assert!(requests.iter().all(|r| !has_duplicates(&r.headers)));
+        for method in METHODS {
+            assert!(requests.iter().any(|r| r.method == *method));
+        }
+        assert!(requests.iter().any(|r| r.target == "*"));
+        assert!(requests.iter().any(|r| r.target.starts_with("http://")));
+        assert!(requests.iter().any(
+            |r| matches!(r.framing, Framing::Chunked(ref c) if c.len() > 1)
+        ));
+        assert!(requests.iter().any(|r| r.framing == Framing::ContentLength));
+    }
+
+    #[test]
+    fn generates_valid_responses() {
+        let responses = check_responses(HttpParams::default());
+        assert!(responses.iter().all(|r| !has_duplicates(&r.headers)));
+        assert!(responses.iter().any(|r| r.framing == Framing::None));
+        assert!(responses.iter().any(|r| r.reason.is_empty()));
+        assert!(responses
+            .iter()
+            .any(|r| matches!(r.framing, Framing::Chunked(_))));
+    }
+
+    #[test]
+    fn generates_duplicate_and_oddly_cased_headers() {
+        let params = HttpParams {
+            duplicate_headers: true,
+            odd_casing: true,
+            ..HttpParams::default()
+        };
+        let requests = check_requests(params.clone());
+        assert!(requests.iter().any(|r| has_duplicates(&r.headers)));
+        assert!(requests.iter().any(|r| r
+            .headers
+            .iter()
+            .any(|(name, _)| name.starts_with(|c: char| c.is_lowercase())
+                && name.contains(|c: char| c.is_uppercase()))));
+        let responses = check_responses(params);
+        assert!(responses.iter().any(|r| has_duplicates(&r.headers)));
+    }
+
+    #[test]
+    fn generates_content_length_only_without_chunked() {
+        let params = HttpParams {
+            chunked: false,
+            ..HttpParams::default()
+        };
+        for request in check_requests(params) {
+            assert!(!matches!(request.framing, Framing::Chunked(_)));
+        }
+    }
+
+    #[test]
+    fn shrinks_to_simple_messages() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&requests(HttpParams::default()), |_| {
+            Err(TestCaseError::fail("minimize"))
+        });
+        match result {
+            Err(TestError::Fail(_, request)) => assert_eq!(
+                b"GET / HTTP/1.1\r\nHost: com\r\n\r\n".to_vec(),
+                request.to_bytes()
+            ),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        let result = runner.run(&responses(HttpParams::default()), |_| {
+            Err(TestCaseError::fail("minimize"))
+        });
+        match result {
+            Err(TestError::Fail(_, response)) => assert_eq!(
+                b"HTTP/1.1 100 Continue\r\n\r\n".to_vec(),
+                response.to_bytes()
+            ),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating arbitrary JSON documents as
+//! `serde_json::Value`s.
+//!
+//! Values are built from leaves (`null`, booleans, numbers and strings)
+//! nested in arrays and objects, within the limits of the given
+//! [`ValueParams`]. Shrinking first removes elements from arrays and entries
+//! from objects, then simplifies the remaining ones, and eventually replaces
+//! collections by leaves.
+//!
+//! `Value` also implements `Arbitrary` with `ValueParams` as parameters, so
+//! `any::<Value>()` generates values with the default parameters.
+//!
+//! With the `json-schema` feature, the `schema` module generates values
+//! conforming to a JSON Schema instead.
+
+#[cfg(feature = "json-schema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json-schema")))]
+pub mod schema;
+
+use serde_json::{Number, Value};
+
+use crate::arbitrary::{any, Arbitrary};
+use crate::collection::{btree_map, vec, SizeRange};
+use crate::num;
+use crate::std_facade::{String, Vec};
+use crate::strategy::*;
+
+bitflags! {
+    /// The types of leaf values, which are the values other than arrays and
+    /// objects, to generate.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct Leaves: u8 {
+        /// `null`
+        const NULL = 0b0_0001;
+        /// `true` and `false`
+        const BOOL = 0b0_0010;
+        /// Numbers without a fractional part or exponent
+        const INTEGER = 0b0_0100;
+        /// Finite numbers with a fractional part or exponent
+        const FLOAT = 0b0_1000;
+        /// Strings
+        const STRING = 0b1_0000;
+    }
+}
+
+/// The largest integer such that it and all smaller integers are exactly
+/// representable as an `f64`, which is how many JSON implementations store
+/// numbers.
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+/// Parameters for generating `serde_json::Value`s.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueParams {
+    /// The maximum number of levels of arrays and objects nested in each
+    /// other. With a depth of 0, only leaves are generated.
+    ///
+    /// The default is 4.
+    pub max_depth: u32,
+    /// The range of the number of elements of each array and of the entries
+    /// of each object.
+    ///
+    /// The default is `0..8`.
+    pub size: SizeRange,
+    /// The types of leaves to generate. Must not be empty.
+    ///
+    /// The default is all of them.
+    pub leaves: Leaves,
+    /// Whether to generate integers whose magnitude exceeds
+    /// 2<sup>53</sup> - 1. These are valid JSON, but cannot be represented
+    /// exactly by implementations that store numbers as floating point, such
+    /// as JavaScript.
+    ///
+    /// The default is `true`.
+    pub unsafe_integers: bool,
+}
+
+impl Default for ValueParams {
+    fn default() -> Self {
+        ValueParams {
+            max_depth: 4,
+            size: (0..8).into(),
+            leaves: Leaves::all(),
+            unsafe_integers: true,
+        }
+    }
+}
+
+/// Generates leaf values of the types in `params.leaves`.
+///
+/// ## Panics
+///
+/// Panics if `params.leaves` is empty.
+pub fn leaves(params: &ValueParams) -> BoxedStrategy<Value> {
+    let mut options: Vec<BoxedStrategy<Value>> = Vec::new();
+    if params.leaves.contains(Leaves::NULL) {
+        options.push(Just(Value::Null).boxed());
+    }
+    if params.leaves.contains(Leaves::BOOL) {
+        options.push(any::<bool>().prop_map(Value::Bool).boxed());
+    }
+    if params.leaves.contains(Leaves::INTEGER) {
+        if params.unsafe_integers {
+            options.push(
+                prop_oneof![
+                    any::<i64>().prop_map(Number::from),
+                    any::<u64>().prop_map(Number::from),
+                ]
+                .prop_map(Value::Number)
+                .boxed(),
+            );
+        } else {
+            options.push(
+                (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER)
+                    .prop_map(|n| Value::Number(n.into()))
+                    .boxed(),
+            );
+        }
+    }
+    if params.leaves.contains(Leaves::FLOAT) {
+        options.push(finite_floats());
+    }
+    if params.leaves.contains(Leaves::STRING) {
+        options.push(any::<String>().prop_map(Value::String).boxed());
+    }
+
+    assert!(
+        !options.is_empty(),
+        "No leaves to generate JSON values from"
+    );
+    Union::new(options).boxed()
+}
+
+/// Generates numbers from any finite `f64`.
+fn finite_floats() -> BoxedStrategy<Value> {
+    let finite = num::f64::POSITIVE
+        | num::f64::NEGATIVE
+        | num::f64::NORMAL
+        | num::f64::SUBNORMAL
+        | num::f64::ZERO;
+    finite.prop_map(float_value).boxed()
+}
+
+/// Converts a finite `f64` to a JSON number.
+fn float_value(f: f64) -> Value {
+    Value::Number(Number::from_f64(f).expect("finite f64 is a number"))
+}
+
+/// Generates JSON values within the limits of `params`.
+///
+/// ## Panics
+///
+/// Panics if `params.leaves` is empty.
+pub fn values(params: ValueParams) -> BoxedStrategy<Value> {
+    let size = params.size.clone();
+    let (start, end) = size.start_end_incl();
+    leaves(&params)
+        .prop_recursive(
+            params.max_depth,
+            // Target around 64 values in total
+            64,
+            ((start + end) / 2).max(1) as u32,
+            move |inner| {
+                prop_oneof![
+                    vec(inner.clone(), size.clone()).prop_map(Value::Array),
+                    btree_map(any::<String>(), inner, size.clone()).prop_map(
+                        |entries| Value::Object(entries.into_iter().collect())
+                    ),
+                ]
+            },
+        )
+        .boxed()
+}
+
+impl Arbitrary for Value {
+    type Parameters = ValueParams;
+    type Strategy = BoxedStrategy<Value>;
+
+    fn arbitrary_with(params: ValueParams) -> Self::Strategy {
+        values(params)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_runner::{TestError, TestRunner};
+
+    /// Returns the number of levels of arrays and objects nested in `value`.
+    fn depth(value: &Value) -> u32 {
+        match value {
+            Value::Array(elements) => {
+                1 + elements.iter().map(depth).max().unwrap_or(0)
+            }
+            Value::Object(entries) => {
+                1 + entries.values().map(depth).max().unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+
+    fn check_limits(value: &Value, params: &ValueParams) {
+        let (min_size, max_size) = params.size.start_end_incl();
+        let size_ok = |len| min_size <= len && len <= max_size;
+        match value {
+            Value::Array(elements) => {
+                assert!(size_ok(elements.len()));
+                elements.iter().for_each(|v| check_limits(v, params));
+            }
+            Value::Object(entries) => {
+                assert!(size_ok(entries.len()));
+                entries.values().for_each(|v| check_limits(v, params));
+            }
+            Value::Null => assert!(params.leaves.contains(Leaves::NULL)),
+            Value::Bool(_) => assert!(params.leaves.contains(Leaves::BOOL)),
+            Value::String(_) => {
+                assert!(params.leaves.contains(Leaves::STRING))
+            }
+            Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    assert!(params.leaves.contains(Leaves::INTEGER));
+                    assert!(
+                        params.unsafe_integers || i.abs() <= MAX_SAFE_INTEGER
+                    );
+                } else if n.is_u64() {
+                    assert!(params.leaves.contains(Leaves::INTEGER));
+                    assert!(params.unsafe_integers);
+                } else {
+                    assert!(params.leaves.contains(Leaves::FLOAT));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn values_respect_params() {
+        let params = ValueParams {
+            max_depth: 2,
+            size: (1..4).into(),
+            leaves: Leaves::INTEGER | Leaves::STRING,
+            unsafe_integers: false,
+        };
+        let strategy = values(params.clone());
+        let mut runner = TestRunner::deterministic();
+        let mut max_depth = 0;
+        for _ in 0..256 {
+            let value = strategy.new_tree(&mut runner).unwrap().current();
+            check_limits(&value, &params);
+            max_depth = max_depth.max(depth(&value));
+            // Every generated value is valid JSON
+            let text = serde_json::to_string(&value).unwrap();
+            assert_eq!(value, serde_json::from_str::<Value>(&text).unwrap());
+        }
+        assert_eq!(2, max_depth);
+    }
+
+    #[test]
+    fn depth_zero_generates_only_leaves() {
+        let strategy = values(ValueParams {
+            max_depth: 0,
+            ..ValueParams::default()
+        });
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let value = strategy.new_tree(&mut runner).unwrap().current();
+            assert_eq!(0, depth(&value));
+        }
+    }
+
+    #[test]
+    fn shrinks_elements_before_leaves() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&any::<Value>(), |value| {
+            prop_assert!(!matches!(value, Value::Array(ref a) if a.len() >= 2));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, value)) => {
+                assert_eq!(Value::Array(vec![Value::Null, Value::Null]), value)
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
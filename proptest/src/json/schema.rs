@@ -0,0 +1,1738 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating JSON documents which conform to a
+//! [JSON Schema](https://json-schema.org/).
+//!
+//! [`values`] turns a schema into a strategy generating only documents the
+//! schema accepts, so that code consuming JSON can be tested against exactly
+//! the documents its contract allows:
+//!
+//! ```rust
+//! use proptest::json::{schema, ValueParams};
+//! use proptest::prelude::*;
+//! use serde_json::json;
+//!
+//! fn orders() -> impl Strategy<Value = serde_json::Value> {
+//!     schema::values(
+//!         &json!({
+//!             "type": "object",
+//!             "properties": {
+//!                 "id": { "type": "string", "pattern": "^[A-Z]{3}-[0-9]{4}$" },
+//!                 "quantity": { "type": "integer", "minimum": 1 },
+//!                 "express": { "type": "boolean" }
+//!             },
+//!             "required": ["id", "quantity"],
+//!             "additionalProperties": false
+//!         }),
+//!         ValueParams::default(),
+//!     )
+//!     .unwrap()
+//! }
+//!
+//! proptest!(|(order in orders())| {
+//!     prop_assert!(order["quantity"].as_i64().unwrap() >= 1);
+//! });
+//! ```
+//!
+//! Both draft 7 and draft 2020-12 schemas are supported, with the following
+//! limitations:
+//!
+//! - `$ref` may only point into the same document, as `#` followed by a JSON
+//!   pointer such as `#/$defs/node`. References may be recursive, in which
+//!   case they are expanded up to `ValueParams::max_depth` times.
+//!
+//! - `$dynamicRef`, `$recursiveRef`, `unevaluatedItems` and
+//!   `unevaluatedProperties` are not supported.
+//!
+//! - Patterns are interpreted by the `regex` crate rather than as ECMA 262
+//!   regular expressions, and must otherwise be supported by
+//!   [`string_regex`](crate::string::string_regex) once stripped of a
+//!   leading `^` and a trailing `$`.
+//!
+//! - `format` is honoured for `date`, `date-time`, `time`, `email`,
+//!   `hostname`, `ipv4`, `ipv6`, `uri` and `uuid`, and ignored otherwise.
+//!
+//! Parts of the schema which leave values unconstrained, such as the `true`
+//! schema or arrays without `items`, are generated as by
+//! [`json::values`](super::values) with the given `ValueParams`, whose `size`
+//! also gives the default number of array elements and extra object entries.
+//!
+//! Some keywords, such as `oneOf`, `not` or `uniqueItems`, are only
+//! approximated while generating values, but every generated value is checked
+//! against the whole schema, and those which do not conform are rejected.
+//! Schemas relying heavily on such keywords may therefore cause many
+//! rejections.
+
+use core::fmt;
+use std::string::ToString;
+use std::sync::Mutex;
+
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use super::{finite_floats, float_value, ValueParams, MAX_SAFE_INTEGER};
+use crate::arbitrary::any;
+use crate::collection::{btree_map, vec};
+use crate::date_time;
+use crate::option;
+use crate::sample::{select, Index};
+use crate::std_facade::{Arc, BTreeMap, Box, String, ToOwned, Vec};
+use crate::strategy::*;
+use crate::string::{self, string_regex};
+
+/// Errors which may occur when building a strategy from a JSON Schema.
+#[derive(Debug)]
+pub enum Error {
+    /// The schema is not a valid JSON Schema, for example because a keyword
+    /// has a value of the wrong type.
+    InvalidSchema(String),
+    /// The schema is valid, but uses features not supported by proptest.
+    UnsupportedSchema(String),
+    /// No document conforms to the schema, or none can be generated without
+    /// expanding a recursive `$ref` more than `ValueParams::max_depth` times.
+    Unsatisfiable(String),
+    /// A `pattern` or a key of `patternProperties` cannot be used to generate
+    /// strings.
+    Pattern(Box<string::Error>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidSchema(message) => {
+                write!(f, "Invalid schema: {}", message)
+            }
+            Error::UnsupportedSchema(message) => {
+                write!(f, "Unsupported schema: {}", message)
+            }
+            Error::Unsatisfiable(message) => {
+                write!(f, "Unsatisfiable schema: {}", message)
+            }
+            Error::Pattern(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Pattern(err) => Some(&**err),
+            _ => None,
+        }
+    }
+}
+
+impl From<string::Error> for Error {
+    fn from(err: string::Error) -> Error {
+        Error::Pattern(Box::new(err))
+    }
+}
+
+type SchemaResult<T> = Result<T, Error>;
+
+/// The types of JSON Schema, in the order they are tried when a schema does
+/// not restrict them.
+const TYPES: [&str; 7] = [
+    "null", "boolean", "integer", "number", "string", "array", "object",
+];
+
+/// Keywords which are not supported at all.
+const UNSUPPORTED: [&str; 4] = [
+    "$dynamicRef",
+    "$recursiveRef",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Keywords which only apply to values of one type, used to infer the type of
+/// schemas without `type`.
+const TYPE_KEYWORDS: [(&str, &[&str]); 4] = [
+    (
+        "number",
+        &[
+            "minimum",
+            "maximum",
+            "exclusiveMinimum",
+            "exclusiveMaximum",
+            "multipleOf",
+        ],
+    ),
+    ("string", &["minLength", "maxLength", "pattern", "format"]),
+    (
+        "array",
+        &[
+            "items",
+            "prefixItems",
+            "additionalItems",
+            "minItems",
+            "maxItems",
+            "uniqueItems",
+            "contains",
+        ],
+    ),
+    (
+        "object",
+        &[
+            "properties",
+            "patternProperties",
+            "additionalProperties",
+            "propertyNames",
+            "required",
+            "minProperties",
+            "maxProperties",
+        ],
+    ),
+];
+
+/// The length of the longest string generated for a schema without
+/// `maxLength`, beyond its `minLength`.
+const DEFAULT_MAX_LENGTH: usize = 32;
+
+/// The number of schemas which may be nested while checking a value, to stop
+/// `$ref` cycles which never reach into the value.
+const MAX_CHECK_DEPTH: u32 = 256;
+
+/// Generates JSON documents conforming to `schema`.
+///
+/// See the [module documentation](self) for which schemas are supported and
+/// how `params` is used.
+pub fn values(
+    schema: &Value,
+    params: ValueParams,
+) -> Result<BoxedStrategy<Value>, Error> {
+    let context = Arc::new(Context {
+        root: schema.clone(),
+        params,
+        regexes: Mutex::new(BTreeMap::new()),
+    });
+    let strategy = Generator {
+        context: Arc::clone(&context),
+        refs: Vec::new(),
+    }
+    .compile(schema)?;
+    Ok(strategy
+        .prop_filter("value does not conform to the schema", move |value| {
+            context.conforms(&context.root, value, 0)
+        })
+        .boxed())
+}
+
+/// The schema being generated from, shared by the strategies built from it.
+struct Context {
+    root: Value,
+    params: ValueParams,
+    /// Regexes compiled while checking values, by pattern.
+    regexes: Mutex<BTreeMap<String, Regex>>,
+}
+
+impl Context {
+    /// Returns the schema `reference` points to.
+    fn resolve(&self, reference: &str) -> SchemaResult<&Value> {
+        let pointer = reference.strip_prefix('#').ok_or_else(|| {
+            Error::UnsupportedSchema(format!(
+                "`$ref` to another document: {}",
+                reference
+            ))
+        })?;
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return Err(Error::UnsupportedSchema(format!(
+                "`$ref` to an anchor: {}",
+                reference
+            )));
+        }
+        self.root.pointer(pointer).ok_or_else(|| {
+            Error::InvalidSchema(format!(
+                "`$ref` to a missing schema: {}",
+                reference
+            ))
+        })
+    }
+
+    /// Returns whether `pattern` matches anywhere in `text`. Invalid patterns
+    /// match nothing.
+    fn is_match(&self, pattern: &str, text: &str) -> bool {
+        let mut regexes = self.regexes.lock().unwrap();
+        if !regexes.contains_key(pattern) {
+            match Regex::new(pattern) {
+                Ok(regex) => {
+                    regexes.insert(pattern.to_owned(), regex);
+                }
+                Err(_) => return false,
+            }
+        }
+        regexes[pattern].is_match(text)
+    }
+
+    /// Returns whether `value` conforms to `schema`.
+    fn conforms(&self, schema: &Value, value: &Value, depth: u32) -> bool {
+        if depth > MAX_CHECK_DEPTH {
+            return false;
+        }
+        let schema = match schema {
+            Value::Bool(accept) => return *accept,
+            Value::Object(schema) => schema,
+            _ => return false,
+        };
+        schema.iter().all(|(keyword, arg)| {
+            self.keyword_conforms(schema, keyword, arg, value, depth + 1)
+        })
+    }
+
+    /// Returns whether `value` satisfies `keyword` of `schema`, whose value
+    /// is `arg`.
+    fn keyword_conforms(
+        &self,
+        schema: &Map<String, Value>,
+        keyword: &str,
+        arg: &Value,
+        value: &Value,
+        depth: u32,
+    ) -> bool {
+        let number = value.as_f64().filter(|_| value.is_number());
+        let length = value.as_str().map(|s| s.chars().count());
+        match (keyword, value) {
+            ("$ref", _) => match arg.as_str().map(|r| self.resolve(r)) {
+                Some(Ok(target)) => self.conforms(target, value, depth),
+                _ => false,
+            },
+            ("type", _) => match arg {
+                Value::String(ty) => has_type(value, ty),
+                Value::Array(types) => types.iter().any(|ty| {
+                    ty.as_str().is_some_and(|ty| has_type(value, ty))
+                }),
+                _ => false,
+            },
+            ("const", _) => json_eq(arg, value),
+            ("enum", _) => arg
+                .as_array()
+                .is_some_and(|values| values.iter().any(|v| json_eq(v, value))),
+
+            ("minimum", _) | ("maximum", _) => match (number, arg.as_f64()) {
+                (Some(n), Some(bound)) => {
+                    let (exclusive, ok) = if keyword == "minimum" {
+                        (schema.get("exclusiveMinimum"), n >= bound)
+                    } else {
+                        (schema.get("exclusiveMaximum"), n <= bound)
+                    };
+                    ok && (exclusive != Some(&Value::Bool(true)) || n != bound)
+                }
+                _ => true,
+            },
+            ("exclusiveMinimum", _) => match (number, arg.as_f64()) {
+                (Some(n), Some(bound)) => n > bound,
+                _ => true,
+            },
+            ("exclusiveMaximum", _) => match (number, arg.as_f64()) {
+                (Some(n), Some(bound)) => n < bound,
+                _ => true,
+            },
+            ("multipleOf", _) => match (number, arg.as_f64()) {
+                (Some(n), Some(m)) => is_multiple(n, m),
+                _ => true,
+            },
+
+            ("minLength", _) => match (length, arg.as_u64()) {
+                (Some(len), Some(min)) => len as u64 >= min,
+                _ => true,
+            },
+            ("maxLength", _) => match (length, arg.as_u64()) {
+                (Some(len), Some(max)) => len as u64 <= max,
+                _ => true,
+            },
+            ("pattern", Value::String(s)) => {
+                arg.as_str().is_some_and(|p| self.is_match(p, s))
+            }
+
+            ("prefixItems", Value::Array(items)) => {
+                self.items_conform(arg.as_array(), None, items, depth)
+            }
+            ("items", Value::Array(items)) => match arg {
+                Value::Array(prefix) => {
+                    self.items_conform(Some(prefix), None, items, depth)
+                }
+                _ => {
+                    let skip = schema
+                        .get("prefixItems")
+                        .and_then(Value::as_array)
+                        .map_or(0, Vec::len);
+                    items
+                        .iter()
+                        .skip(skip)
+                        .all(|item| self.conforms(arg, item, depth))
+                }
+            },
+            ("additionalItems", Value::Array(items)) => {
+                match schema.get("items") {
+                    Some(Value::Array(prefix)) => items
+                        .iter()
+                        .skip(prefix.len())
+                        .all(|item| self.conforms(arg, item, depth)),
+                    _ => true,
+                }
+            }
+            ("minItems", Value::Array(items)) => {
+                arg.as_u64().is_some_and(|min| items.len() as u64 >= min)
+            }
+            ("maxItems", Value::Array(items)) => {
+                arg.as_u64().is_some_and(|max| items.len() as u64 <= max)
+            }
+            ("uniqueItems", Value::Array(items)) => {
+                arg != &Value::Bool(true)
+                    || items
+                        .iter()
+                        .enumerate()
+                        .all(|(i, a)| items[..i].iter().all(|b| !json_eq(a, b)))
+            }
+            ("contains", Value::Array(items)) => {
+                let count = items
+                    .iter()
+                    .filter(|item| self.conforms(arg, item, depth))
+                    .count() as u64;
+                let min = schema
+                    .get("minContains")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(1);
+                let max = schema
+                    .get("maxContains")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(u64::MAX);
+                min <= count && count <= max
+            }
+
+            ("properties", Value::Object(entries)) => {
+                arg.as_object().is_some_and(|properties| {
+                    properties.iter().all(|(name, schema)| {
+                        entries
+                            .get(name)
+                            .is_none_or(|v| self.conforms(schema, v, depth))
+                    })
+                })
+            }
+            ("patternProperties", Value::Object(entries)) => {
+                arg.as_object().is_some_and(|patterns| {
+                    patterns.iter().all(|(pattern, schema)| {
+                        entries.iter().all(|(name, v)| {
+                            !self.is_match(pattern, name)
+                                || self.conforms(schema, v, depth)
+                        })
+                    })
+                })
+            }
+            ("additionalProperties", Value::Object(entries)) => {
+                entries.iter().all(|(name, v)| {
+                    !self.is_additional(schema, name)
+                        || self.conforms(arg, v, depth)
+                })
+            }
+            ("propertyNames", Value::Object(entries)) => {
+                entries.keys().all(|name| {
+                    self.conforms(arg, &Value::String(name.clone()), depth)
+                })
+            }
+            ("required", Value::Object(entries)) => {
+                arg.as_array().is_some_and(|names| {
+                    names.iter().all(|name| {
+                        name.as_str().is_some_and(|n| entries.contains_key(n))
+                    })
+                })
+            }
+            ("minProperties", Value::Object(entries)) => {
+                arg.as_u64().is_some_and(|min| entries.len() as u64 >= min)
+            }
+            ("maxProperties", Value::Object(entries)) => {
+                arg.as_u64().is_some_and(|max| entries.len() as u64 <= max)
+            }
+            ("dependentRequired", Value::Object(entries))
+            | ("dependentSchemas", Value::Object(entries))
+            | ("dependencies", Value::Object(entries)) => {
+                arg.as_object().is_some_and(|dependencies| {
+                    dependencies
+                        .iter()
+                        .filter(|(name, _)| entries.contains_key(*name))
+                        .all(|(_, dependency)| match dependency {
+                            Value::Array(names) => names.iter().all(|name| {
+                                name.as_str()
+                                    .is_some_and(|n| entries.contains_key(n))
+                            }),
+                            schema => self.conforms(schema, value, depth),
+                        })
+                })
+            }
+
+            ("allOf", _) => arg.as_array().is_some_and(|schemas| {
+                schemas.iter().all(|s| self.conforms(s, value, depth))
+            }),
+            ("anyOf", _) => arg.as_array().is_some_and(|schemas| {
+                schemas.iter().any(|s| self.conforms(s, value, depth))
+            }),
+            ("oneOf", _) => arg.as_array().is_some_and(|schemas| {
+                schemas
+                    .iter()
+                    .filter(|s| self.conforms(s, value, depth))
+                    .count()
+                    == 1
+            }),
+            ("not", _) => !self.conforms(arg, value, depth),
+            ("if", _) => {
+                let branch = if self.conforms(arg, value, depth) {
+                    schema.get("then")
+                } else {
+                    schema.get("else")
+                };
+                branch.is_none_or(|s| self.conforms(s, value, depth))
+            }
+
+            // Annotations, and keywords which do not apply to this type
+            _ => true,
+        }
+    }
+
+    /// Returns whether the elements of `items` conform to the schemas at the
+    /// same index in `prefix`, and those after to `rest` if given.
+    fn items_conform(
+        &self,
+        prefix: Option<&Vec<Value>>,
+        rest: Option<&Value>,
+        items: &[Value],
+        depth: u32,
+    ) -> bool {
+        let prefix = match prefix {
+            Some(prefix) => prefix,
+            None => return false,
+        };
+        items
+            .iter()
+            .enumerate()
+            .all(|(i, item)| match prefix.get(i).or(rest) {
+                Some(schema) => self.conforms(schema, item, depth),
+                None => true,
+            })
+    }
+
+    /// Returns whether the property `name` is subject to the
+    /// `additionalProperties` of `schema`.
+    fn is_additional(&self, schema: &Map<String, Value>, name: &str) -> bool {
+        let declared = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .is_some_and(|properties| properties.contains_key(name));
+        let matched = schema
+            .get("patternProperties")
+            .and_then(Value::as_object)
+            .is_some_and(|patterns| {
+                patterns.keys().any(|pattern| self.is_match(pattern, name))
+            });
+        !declared && !matched
+    }
+}
+
+/// Builds strategies for the schemas of a `Context`.
+struct Generator {
+    context: Arc<Context>,
+    /// The `$ref`s being expanded.
+    refs: Vec<String>,
+}
+
+impl Generator {
+    fn params(&self) -> &ValueParams {
+        &self.context.params
+    }
+
+    /// Builds a strategy for `schema`, which approximates the values
+    /// conforming to it.
+    fn compile(
+        &mut self,
+        schema: &Value,
+    ) -> SchemaResult<BoxedStrategy<Value>> {
+        let schema = match schema {
+            Value::Bool(true) => {
+                return Ok(super::values(self.params().clone()))
+            }
+            Value::Bool(false) => {
+                return Err(Error::Unsatisfiable(
+                    "the schema is `false`".into(),
+                ))
+            }
+            Value::Object(schema) => schema,
+            _ => return Err(not_a_schema()),
+        };
+        if let Some(keyword) =
+            UNSUPPORTED.iter().find(|k| schema.contains_key(**k))
+        {
+            return Err(Error::UnsupportedSchema(format!(
+                "`{}` is not supported",
+                keyword
+            )));
+        }
+
+        if let Some(reference) = schema.get("$ref") {
+            return self.compile_ref(schema, as_str(reference, "$ref")?);
+        }
+        if let Some(all_of) = schema.get("allOf") {
+            let mut merged = Value::Object(without(schema, &["allOf"]));
+            for sub in as_array(all_of, "allOf")? {
+                merged = merge(&merged, sub)?;
+            }
+            return self.compile(&merged);
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(branches) = schema.get(keyword) {
+                let rest = Value::Object(without(schema, &[keyword]));
+                let branches = as_array(branches, keyword)?
+                    .iter()
+                    .map(|branch| merge(&rest, branch))
+                    .collect::<SchemaResult<Vec<_>>>()?;
+                return self.compile_any(&branches, keyword);
+            }
+        }
+        if let Some(condition) = schema.get("if") {
+            let rest = Value::Object(without(schema, &["if", "then", "else"]));
+            let then = schema.get("then").unwrap_or(&Value::Bool(true));
+            let otherwise = schema.get("else").unwrap_or(&Value::Bool(true));
+            let branches = [
+                merge(&merge(&rest, condition)?, then)?,
+                merge(&rest, otherwise)?,
+            ];
+            return self.compile_any(&branches, "if");
+        }
+
+        let candidates = match (schema.get("const"), schema.get("enum")) {
+            (Some(value), _) => Some(vec![value.clone()]),
+            (None, Some(values)) => Some(as_array(values, "enum")?.clone()),
+            (None, None) => None,
+        };
+        if let Some(candidates) = candidates {
+            // `not` and the keywords of the schema other than `const` and
+            // `enum` only rule out some of the candidates.
+            let whole = Value::Object(schema.clone());
+            let candidates: Vec<Value> = candidates
+                .into_iter()
+                .filter(|v| self.context.conforms(&whole, v, 0))
+                .collect();
+            if candidates.is_empty() {
+                return Err(Error::Unsatisfiable(
+                    "no value of `const` or `enum` conforms to the schema"
+                        .into(),
+                ));
+            }
+            return Ok(select(candidates).boxed());
+        }
+
+        let types = match schema.get("type") {
+            Some(Value::String(ty)) => vec![check_type(ty)?],
+            Some(Value::Array(types)) => types
+                .iter()
+                .map(|ty| check_type(as_str(ty, "type")?))
+                .collect::<SchemaResult<Vec<_>>>()?,
+            Some(_) => {
+                return Err(Error::InvalidSchema(
+                    "`type` must be a string or an array".into(),
+                ))
+            }
+            None => {
+                let inferred: Vec<&str> = TYPE_KEYWORDS
+                    .iter()
+                    .filter(|(_, keywords)| {
+                        keywords.iter().any(|k| schema.contains_key(*k))
+                    })
+                    .map(|(ty, _)| *ty)
+                    .collect();
+                if inferred.is_empty() {
+                    // `not` may only rule out some of the values.
+                    return Ok(super::values(self.params().clone()));
+                }
+                inferred
+            }
+        };
+
+        let mut options = Vec::new();
+        for ty in TYPES.iter().filter(|ty| types.contains(*ty)) {
+            let strategy = match *ty {
+                "null" => Ok(Just(Value::Null).boxed()),
+                "boolean" => Ok(any::<bool>().prop_map(Value::Bool).boxed()),
+                "integer" => self.compile_integer(schema),
+                "number" => self.compile_number(schema),
+                "string" => self.compile_string(schema),
+                "array" => self.compile_array(schema),
+                _ => self.compile_object(schema),
+            };
+            match strategy {
+                Ok(strategy) => options.push(strategy),
+                Err(Error::Unsatisfiable(_)) => (),
+                Err(err) => return Err(err),
+            }
+        }
+        if options.is_empty() {
+            return Err(Error::Unsatisfiable(
+                "no value of the allowed types conforms to the schema".into(),
+            ));
+        }
+        Ok(Union::new(options).boxed())
+    }
+
+    /// Builds a strategy for a `schema` whose `$ref` is `reference`.
+    fn compile_ref(
+        &mut self,
+        schema: &Map<String, Value>,
+        reference: &str,
+    ) -> SchemaResult<BoxedStrategy<Value>> {
+        let expansions = self.refs.iter().filter(|r| *r == reference).count();
+        if expansions as u32 > self.params().max_depth {
+            return Err(Error::Unsatisfiable(format!(
+                "`{}` is expanded more than {} times",
+                reference,
+                self.params().max_depth
+            )));
+        }
+        let target = self.context.resolve(reference)?.clone();
+        let merged =
+            merge(&Value::Object(without(schema, &["$ref"])), &target)?;
+
+        self.refs.push(reference.to_owned());
+        let result = self.compile(&merged);
+        self.refs.pop();
+        result
+    }
+
+    /// Builds a strategy for the values conforming to any of `branches`,
+    /// skipping those which cannot be satisfied.
+    fn compile_any(
+        &mut self,
+        branches: &[Value],
+        keyword: &str,
+    ) -> SchemaResult<BoxedStrategy<Value>> {
+        let mut options = Vec::new();
+        for branch in branches {
+            match self.compile(branch) {
+                Ok(strategy) => options.push(strategy),
+                Err(Error::Unsatisfiable(_)) => (),
+                Err(err) => return Err(err),
+            }
+        }
+        if options.is_empty() {
+            return Err(Error::Unsatisfiable(format!(
+                "no branch of `{}` can be satisfied",
+                keyword
+            )));
+        }
+        Ok(Union::new(options).boxed())
+    }
+
+    fn compile_integer(
+        &mut self,
+        schema: &Map<String, Value>,
+    ) -> SchemaResult<BoxedStrategy<Value>> {
+        let (min, max) = integer_bounds(schema, self.params().unsafe_integers)?;
+        let step = match schema.get("multipleOf") {
+            None => 1,
+            Some(m) => integer_step(as_positive(m, "multipleOf")?).ok_or_else(
+                || {
+                    Error::UnsupportedSchema(format!(
+                        "integers which are a multiple of {}",
+                        m
+                    ))
+                },
+            )?,
+        };
+        let (low, high) = (div_ceil(min, step), max.div_euclid(step));
+        if low > high {
+            return Err(Error::Unsatisfiable(
+                "no integer is within the bounds".into(),
+            ));
+        }
+        Ok((low..=high)
+            .prop_map(move |k| Value::from(k * step))
+            .boxed())
+    }
+
+    fn compile_number(
+        &mut self,
+        schema: &Map<String, Value>,
+    ) -> SchemaResult<BoxedStrategy<Value>> {
+        let (min, max) = float_bounds(schema)?;
+        let floats = if let Some(m) = schema.get("multipleOf") {
+            let m = as_positive(m, "multipleOf")?;
+            let limit = MAX_SAFE_INTEGER as f64;
+            let low =
+                min.map_or(-limit, |(min, _)| (min / m).ceil().max(-limit));
+            let high =
+                max.map_or(limit, |(max, _)| (max / m).floor().min(limit));
+            if low > high {
+                return Err(Error::Unsatisfiable(
+                    "no multiple is within the bounds".into(),
+                ));
+            }
+            (low as i64..=high as i64)
+                .prop_map(move |k| k as f64 * m)
+                .boxed()
+        } else {
+            match (min, max) {
+                (None, None) => {
+                    return Ok(prop_oneof![
+                        self.compile_integer(schema)?,
+                        finite_floats(),
+                    ]
+                    .boxed())
+                }
+                (Some((min, _)), None) => {
+                    (min..=(min + min.abs().max(1e6)).min(f64::MAX)).boxed()
+                }
+                (None, Some((max, _))) => {
+                    ((max - max.abs().max(1e6)).max(f64::MIN)..=max).boxed()
+                }
+                (Some((min, _)), Some((max, _))) => {
+                    if min > max {
+                        return Err(Error::Unsatisfiable(
+                            "no number is within the bounds".into(),
+                        ));
+                    }
+                    (min..=max).boxed()
+                }
+            }
+        };
+        let floats = floats
+            .prop_filter("number is not within the bounds", move |&f| {
+                f.is_finite()
+                    && min.is_none_or(|(min, excl)| !(excl && f == min))
+                    && max.is_none_or(|(max, excl)| !(excl && f == max))
+            })
+            .prop_map(float_value);
+
+        match self.compile_integer(schema) {
+            Ok(integers) => Ok(prop_oneof![integers, floats].boxed()),
+            Err(Error::Unsatisfiable(_)) | Err(Error::UnsupportedSchema(_)) => {
+                Ok(floats.boxed())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn compile_string(
+        &mut self,
+        schema: &Map<String, Value>,
+    ) -> SchemaResult<BoxedStrategy<Value>> {
+        let min = as_count(schema, "minLength")?.unwrap_or(0);
+        let max = as_count(schema, "maxLength")?;
+        if max.is_some_and(|max| max < min) {
+            return Err(Error::Unsatisfiable(
+                "`maxLength` is less than `minLength`".into(),
+            ));
+        }
+
+        let strings = if let Some(pattern) = schema.get("pattern") {
+            pattern_strings(as_str(pattern, "pattern")?)?
+        } else if let Some(strings) = schema
+            .get("format")
+            .and_then(Value::as_str)
+            .and_then(format_strings)
+        {
+            strings
+        } else {
+            let max = max.unwrap_or(min + DEFAULT_MAX_LENGTH);
+            return Ok(vec(any::<char>(), min..=max)
+                .prop_map(|chars| Value::String(chars.into_iter().collect()))
+                .boxed());
+        };
+        Ok(strings
+            .prop_filter("string length is not within the bounds", move |s| {
+                let len = s.chars().count();
+                min <= len && max.is_none_or(|max| len <= max)
+            })
+            .prop_map(Value::String)
+            .boxed())
+    }
+
+    fn compile_array(
+        &mut self,
+        schema: &Map<String, Value>,
+    ) -> SchemaResult<BoxedStrategy<Value>> {
+        let empty = Vec::new();
+        let (prefix, rest) =
+            match (schema.get("prefixItems"), schema.get("items")) {
+                (Some(prefix), rest) => {
+                    (as_array(prefix, "prefixItems")?, rest)
+                }
+                (None, Some(Value::Array(prefix))) => {
+                    (prefix, schema.get("additionalItems"))
+                }
+                (None, rest) => (&empty, rest),
+            };
+        let min = as_count(schema, "minItems")?.unwrap_or(0);
+        let max = as_count(schema, "maxItems")?;
+        let (_, size) = self.params().size.start_end_incl();
+
+        // Elements of the prefix which cannot be satisfied cut the array
+        // short.
+        let mut prefix_items = Vec::new();
+        for item in prefix.iter().take(max.unwrap_or(usize::MAX)) {
+            match self.compile(item) {
+                Ok(strategy) => prefix_items.push(strategy),
+                Err(Error::Unsatisfiable(_)) => break,
+                Err(err) => return Err(err),
+            }
+        }
+        let cut_short = prefix_items.len() < prefix.len();
+        let rest_items = match rest {
+            _ if cut_short => None,
+            None => Some(super::values(self.params().clone())),
+            Some(rest) => match self.compile(rest) {
+                Ok(strategy) => Some(strategy),
+                Err(Error::Unsatisfiable(_)) => None,
+                Err(err) => return Err(err),
+            },
+        };
+
+        let prefix_len = prefix_items.len();
+        let tail_min = min.saturating_sub(prefix_len);
+        let tail_max = match (&rest_items, max) {
+            (None, _) => 0,
+            (Some(_), Some(max)) => max.saturating_sub(prefix_len),
+            (Some(_), None) => tail_min.max(size),
+        };
+        if tail_min > tail_max {
+            return Err(Error::Unsatisfiable(
+                "no array of the allowed length conforms to the schema".into(),
+            ));
+        }
+
+        let min_contains = as_count(schema, "minContains")?;
+        let contains = match schema.get("contains") {
+            Some(contains) if tail_max > 0 && min_contains != Some(0) => {
+                let contains =
+                    merge(contains, rest.unwrap_or(&Value::Bool(true)))?;
+                Some(self.compile(&contains)?)
+            }
+            _ => None,
+        };
+        let (tail_min, tail_max) = if contains.is_some() {
+            (tail_min.saturating_sub(1), tail_max - 1)
+        } else {
+            (tail_min, tail_max)
+        };
+        let contains = match contains {
+            Some(contains) => (contains, any::<Index>()).prop_map(Some).boxed(),
+            None => Just(None).boxed(),
+        };
+        let tail = match rest_items {
+            Some(items) => vec(items, tail_min..=tail_max).boxed(),
+            None => Just(Vec::new()).boxed(),
+        };
+        let unique = schema.get("uniqueItems") == Some(&Value::Bool(true));
+
+        Ok((
+            prefix_items,
+            min.min(prefix_len)..=prefix_len,
+            tail,
+            contains,
+        )
+            .prop_map(move |(mut items, len, mut tail, contains)| {
+                if let Some((value, index)) = contains {
+                    tail.insert(index.index(tail.len() + 1), value);
+                }
+                if tail.is_empty() {
+                    items.truncate(len);
+                }
+                items.extend(tail);
+                if unique {
+                    let mut unique_items: Vec<Value> = Vec::new();
+                    for item in items {
+                        if !unique_items.iter().any(|u| json_eq(u, &item)) {
+                            unique_items.push(item);
+                        }
+                    }
+                    items = unique_items;
+                }
+                Value::Array(items)
+            })
+            .boxed())
+    }
+
+    fn compile_object(
+        &mut self,
+        schema: &Map<String, Value>,
+    ) -> SchemaResult<BoxedStrategy<Value>> {
+        let empty = Map::new();
+        let properties = match schema.get("properties") {
+            Some(properties) => as_object(properties, "properties")?,
+            None => &empty,
+        };
+        let patterns = match schema.get("patternProperties") {
+            Some(patterns) => as_object(patterns, "patternProperties")?,
+            None => &empty,
+        };
+        let required = match schema.get("required") {
+            Some(required) => as_array(required, "required")?
+                .iter()
+                .map(|name| as_str(name, "required"))
+                .collect::<SchemaResult<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        let additional = schema
+            .get("additionalProperties")
+            .unwrap_or(&Value::Bool(true));
+        let min = as_count(schema, "minProperties")?.unwrap_or(0);
+        let max = as_count(schema, "maxProperties")?;
+        let (_, size) = self.params().size.start_end_incl();
+
+        if max.is_some_and(|max| max < required.len()) {
+            return Err(Error::Unsatisfiable(
+                "`maxProperties` is less than the number of required properties"
+                    .into(),
+            ));
+        }
+
+        // The declared and required properties
+        let mut names: Vec<&str> =
+            properties.keys().map(String::as_str).collect();
+        names.extend(
+            required
+                .iter()
+                .filter(|name| !properties.contains_key(**name)),
+        );
+        let mut fields = Vec::new();
+        for name in names {
+            let mut property =
+                properties.get(name).cloned().unwrap_or(Value::Bool(true));
+            let mut matched = properties.contains_key(name);
+            for (pattern, sub) in patterns {
+                if self.context.is_match(pattern, name) {
+                    property = merge(&property, sub)?;
+                    matched = true;
+                }
+            }
+            if !matched {
+                property = additional.clone();
+            }
+
+            let owned_name = name.to_owned();
+            let is_required = required.contains(&name);
+            match self.compile(&property) {
+                Ok(strategy) => {
+                    let field = strategy
+                        .prop_map(move |value| (owned_name.clone(), value));
+                    fields.push(if is_required {
+                        field.prop_map(Some).boxed()
+                    } else {
+                        option::of(field).boxed()
+                    });
+                }
+                Err(Error::Unsatisfiable(_)) if !is_required => (),
+                Err(err) => return Err(err),
+            }
+        }
+
+        // Entries for `patternProperties` and `additionalProperties`
+        let declared: Vec<String> = properties.keys().cloned().collect();
+        let mut extras = Vec::new();
+        for (pattern, sub) in patterns {
+            let values = match self.compile(sub) {
+                Ok(values) => values,
+                Err(Error::Unsatisfiable(_)) => continue,
+                Err(err) => return Err(err),
+            };
+            let declared = declared.clone();
+            let keys = pattern_strings(pattern)?
+                .prop_filter("key is a declared property", move |key| {
+                    !declared.contains(key)
+                });
+            extras.push(btree_map(keys, values, 0..=size).boxed());
+        }
+        let values = match self.compile(additional) {
+            Ok(values) => Some(values),
+            Err(Error::Unsatisfiable(_)) => None,
+            Err(err) => return Err(err),
+        };
+        if let Some(values) = values {
+            let keys = match schema.get("propertyNames") {
+                Some(names) => self
+                    .compile(&merge(
+                        names,
+                        &serde_json::json!({"type": "string"}),
+                    )?)?
+                    .prop_map(|name| match name {
+                        Value::String(name) => name,
+                        _ => unreachable!("property name is not a string"),
+                    })
+                    .boxed(),
+                None => any::<String>().boxed(),
+            };
+            let context = Arc::clone(&self.context);
+            let schema = schema.clone();
+            let keys = keys
+                .prop_filter("key is not an additional property", move |key| {
+                    context.is_additional(&schema, key)
+                });
+            let extra_min = min.saturating_sub(fields.len());
+            let extra_max = match max {
+                Some(max) => max.saturating_sub(required.len()).max(extra_min),
+                None => extra_min.max(size),
+            };
+            extras.push(btree_map(keys, values, extra_min..=extra_max).boxed());
+        }
+
+        Ok((fields, extras)
+            .prop_map(|(fields, extras)| {
+                let mut entries = Map::new();
+                for (key, value) in extras.into_iter().flatten() {
+                    entries.insert(key, value);
+                }
+                for (key, value) in fields.into_iter().flatten() {
+                    entries.insert(key, value);
+                }
+                Value::Object(entries)
+            })
+            .boxed())
+    }
+}
+
+/// Returns a schema which only accepts the values which conform to both `a`
+/// and `b`, or one which accepts more values if that cannot be expressed
+/// simply.
+fn merge(a: &Value, b: &Value) -> SchemaResult<Value> {
+    let (a, b) = match (a, b) {
+        (Value::Bool(false), _) | (_, Value::Bool(false)) => {
+            return Ok(Value::Bool(false))
+        }
+        (Value::Bool(true), b) => return Ok(b.clone()),
+        (a, Value::Bool(true)) => return Ok(a.clone()),
+        (Value::Object(a), Value::Object(b)) => (a, b),
+        _ => return Err(not_a_schema()),
+    };
+
+    let mut merged = a.clone();
+    for (keyword, b_arg) in b {
+        let a_arg = match merged.get(keyword) {
+            None => {
+                merged.insert(keyword.clone(), b_arg.clone());
+                continue;
+            }
+            Some(a_arg) if a_arg == b_arg => continue,
+            Some(a_arg) => a_arg.clone(),
+        };
+        let combined =
+            match (keyword.as_str(), &a_arg, b_arg) {
+                (
+                    "minimum" | "exclusiveMinimum" | "minLength" | "minItems"
+                    | "minProperties" | "minContains",
+                    Value::Number(x),
+                    Value::Number(y),
+                ) => {
+                    if x.as_f64() >= y.as_f64() {
+                        a_arg.clone()
+                    } else {
+                        b_arg.clone()
+                    }
+                }
+                (
+                    "maximum" | "exclusiveMaximum" | "maxLength" | "maxItems"
+                    | "maxProperties" | "maxContains",
+                    Value::Number(x),
+                    Value::Number(y),
+                ) => {
+                    if x.as_f64() <= y.as_f64() {
+                        a_arg.clone()
+                    } else {
+                        b_arg.clone()
+                    }
+                }
+                ("type", _, _) => {
+                    let (x, y) = (type_names(&a_arg)?, type_names(b_arg)?);
+                    let both = |ty: &str, other: &[&str]| {
+                        other.contains(&ty)
+                            || (ty == "integer" && other.contains(&"number"))
+                    };
+                    let mut types: Vec<&str> =
+                        x.iter().copied().filter(|ty| both(ty, &y)).collect();
+                    types.extend(y.iter().filter(|ty| {
+                        **ty == "integer" && x.contains(&"number")
+                    }));
+                    types.dedup();
+                    Value::from(types)
+                }
+                ("required", Value::Array(x), Value::Array(y)) => {
+                    let mut names = x.clone();
+                    names.extend(
+                        y.iter().filter(|name| !x.contains(name)).cloned(),
+                    );
+                    Value::Array(names)
+                }
+                ("enum", Value::Array(x), Value::Array(y)) => Value::Array(
+                    x.iter()
+                        .filter(|v| y.iter().any(|w| json_eq(v, w)))
+                        .cloned()
+                        .collect(),
+                ),
+                ("const", _, _) => {
+                    if json_eq(&a_arg, b_arg) {
+                        a_arg.clone()
+                    } else {
+                        return Ok(Value::Bool(false));
+                    }
+                }
+                (
+                    "properties" | "patternProperties" | "dependentSchemas",
+                    Value::Object(x),
+                    Value::Object(y),
+                ) => {
+                    let mut schemas = x.clone();
+                    for (name, schema) in y {
+                        let schema = match x.get(name) {
+                            Some(other) => merge(other, schema)?,
+                            None => schema.clone(),
+                        };
+                        schemas.insert(name.clone(), schema);
+                    }
+                    Value::Object(schemas)
+                }
+                ("items", Value::Array(_), _)
+                | ("items", _, Value::Array(_)) => a_arg.clone(),
+                (
+                    "items"
+                    | "additionalItems"
+                    | "additionalProperties"
+                    | "propertyNames",
+                    _,
+                    _,
+                ) => merge(&a_arg, b_arg)?,
+                ("allOf", Value::Array(x), Value::Array(y)) => {
+                    Value::Array(x.iter().chain(y).cloned().collect())
+                }
+                ("$ref", _, _) => {
+                    // Expanded later, through `allOf`.
+                    let reference = serde_json::json!({ "$ref": b_arg });
+                    match merged.get_mut("allOf") {
+                        Some(Value::Array(all_of)) => all_of.push(reference),
+                        _ => {
+                            merged.insert(
+                                "allOf".into(),
+                                Value::Array(vec![reference]),
+                            );
+                        }
+                    }
+                    continue;
+                }
+                // The values conforming to `a` but not to `b` are rejected once
+                // generated.
+                _ => a_arg.clone(),
+            };
+        merged.insert(keyword.clone(), combined);
+    }
+    Ok(Value::Object(merged))
+}
+
+/// Generates strings matching `pattern`.
+fn pattern_strings(pattern: &str) -> SchemaResult<BoxedStrategy<String>> {
+    // Patterns match anywhere in a string, so strings matching them as a
+    // whole also conform without their anchors.
+    let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+    let pattern = match pattern.strip_suffix('$') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => pattern,
+    };
+    Ok(string_regex(pattern)?.boxed())
+}
+
+/// Generates strings in `format`, or returns `None` if it is not one of the
+/// supported formats.
+fn format_strings(format: &str) -> Option<BoxedStrategy<String>> {
+    let regex = |pattern: &str| string_regex(pattern).unwrap().boxed();
+    Some(match format {
+        "date" => date_time::dates(1900..2100)
+            .prop_map(|date| date.to_string())
+            .boxed(),
+        "date-time" => date_time::date_times(1900..2100)
+            .prop_map(|date_time| format!("{}Z", date_time))
+            .boxed(),
+        "time" => date_time::date_times(2000..2001)
+            .prop_map(|t| format!("{:02}:{:02}:{:02}Z", t.hour, t.minute, t.second))
+            .boxed(),
+        "email" => regex("[a-z0-9]{1,16}(\\.[a-z0-9]{1,16}){0,2}@[a-z0-9]{1,16}(\\.[a-z]{2,6}){1,2}"),
+        "hostname" => regex("[a-z0-9]{1,16}(\\.[a-z0-9]{1,16}){0,3}"),
+        "ipv4" => any::<[u8; 4]>()
+            .prop_map(|ip| std::net::Ipv4Addr::from(ip).to_string())
+            .boxed(),
+        "ipv6" => any::<[u16; 8]>()
+            .prop_map(|ip| std::net::Ipv6Addr::from(ip).to_string())
+            .boxed(),
+        "uri" => regex("https?://[a-z0-9]{1,16}(\\.[a-z]{2,6}){1,2}(/[a-zA-Z0-9_-]{0,16}){0,4}"),
+        "uuid" => any::<u128>()
+            .prop_map(|uuid| {
+                let hex = format!("{:032x}", uuid);
+                format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..]
+                )
+            })
+            .boxed(),
+        _ => return None,
+    })
+}
+
+/// Returns whether `value` is of the JSON Schema type `ty`.
+fn has_type(value: &Value, ty: &str) -> bool {
+    match (ty, value) {
+        ("null", Value::Null)
+        | ("boolean", Value::Bool(_))
+        | ("number", Value::Number(_))
+        | ("string", Value::String(_))
+        | ("array", Value::Array(_))
+        | ("object", Value::Object(_)) => true,
+        ("integer", Value::Number(n)) => {
+            n.is_i64()
+                || n.is_u64()
+                || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether `a` and `b` are equal in the sense of JSON Schema, where
+/// numbers are equal if they have the same value.
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => {
+            x == y || ((x.is_f64() || y.is_f64()) && x.as_f64() == y.as_f64())
+        }
+        (Value::Array(x), Value::Array(y)) => {
+            x.len() == y.len() && x.iter().zip(y).all(|(v, w)| json_eq(v, w))
+        }
+        (Value::Object(x), Value::Object(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|(key, v)| y.get(key).is_some_and(|w| json_eq(v, w)))
+        }
+        _ => a == b,
+    }
+}
+
+/// Returns whether `n` is a multiple of `m`, allowing for rounding.
+fn is_multiple(n: f64, m: f64) -> bool {
+    let quotient = n / m;
+    (quotient - quotient.round()).abs() <= 1e-9 * quotient.abs().max(1.0)
+}
+
+/// Returns the smallest positive integer which is a multiple of `m`, if not
+/// too large.
+fn integer_step(m: f64) -> Option<i64> {
+    (1..=1024)
+        .map(|i| m * i as f64)
+        .find(|step| step.fract() == 0.0 && is_multiple(*step, m))
+        .filter(|step| *step <= MAX_SAFE_INTEGER as f64)
+        .map(|step| step as i64)
+}
+
+/// Returns the smallest integer at least `n / d`, for positive `d`.
+fn div_ceil(n: i64, d: i64) -> i64 {
+    -((-(n as i128)).div_euclid(d as i128)) as i64
+}
+
+/// Returns the bounds of the numbers of `schema`, with whether each is
+/// exclusive.
+#[allow(clippy::type_complexity)]
+fn float_bounds(
+    schema: &Map<String, Value>,
+) -> SchemaResult<(Option<(f64, bool)>, Option<(f64, bool)>)> {
+    let bound = |inclusive: &str,
+                 exclusive: &str,
+                 tighter: fn(f64, f64) -> bool|
+     -> SchemaResult<Option<(f64, bool)>> {
+        let mut bound = match schema.get(inclusive) {
+            Some(value) => Some((as_f64(value, inclusive)?, false)),
+            None => None,
+        };
+        match schema.get(exclusive) {
+            // Draft 4 style
+            Some(Value::Bool(true)) => bound = bound.map(|(b, _)| (b, true)),
+            Some(Value::Bool(false)) | None => (),
+            Some(value) => {
+                let value = as_f64(value, exclusive)?;
+                if bound.is_none_or(|(b, _)| !tighter(b, value)) {
+                    bound = Some((value, true));
+                }
+            }
+        }
+        Ok(bound)
+    };
+    Ok((
+        bound("minimum", "exclusiveMinimum", |a, b| a > b)?,
+        bound("maximum", "exclusiveMaximum", |a, b| a < b)?,
+    ))
+}
+
+/// Returns the bounds of the integers of `schema`, limited to `i64` or to
+/// the integers exactly representable as `f64` unless `unsafe_integers`.
+fn integer_bounds(
+    schema: &Map<String, Value>,
+    unsafe_integers: bool,
+) -> SchemaResult<(i64, i64)> {
+    let limit = if unsafe_integers {
+        i64::MAX
+    } else {
+        MAX_SAFE_INTEGER
+    };
+    let (min, max) = float_bounds(schema)?;
+    // Casts saturate, which is then clamped to the limits.
+    let min = min.map_or(-limit, |(min, exclusive)| {
+        let min = if exclusive {
+            min.floor() + 1.0
+        } else {
+            min.ceil()
+        };
+        (min as i64).max(-limit)
+    });
+    let max = max.map_or(limit, |(max, exclusive)| {
+        let max = if exclusive {
+            max.ceil() - 1.0
+        } else {
+            max.floor()
+        };
+        (max as i64).min(limit)
+    });
+    Ok((min, max))
+}
+
+fn not_a_schema() -> Error {
+    Error::InvalidSchema("a schema must be an object or a boolean".into())
+}
+
+fn without(
+    schema: &Map<String, Value>,
+    keywords: &[&str],
+) -> Map<String, Value> {
+    let mut schema = schema.clone();
+    for keyword in keywords {
+        schema.remove(*keyword);
+    }
+    schema
+}
+
+fn check_type(ty: &str) -> SchemaResult<&str> {
+    if TYPES.contains(&ty) {
+        Ok(ty)
+    } else {
+        Err(Error::InvalidSchema(format!("unknown type `{}`", ty)))
+    }
+}
+
+fn type_names(arg: &Value) -> SchemaResult<Vec<&str>> {
+    match arg {
+        Value::String(ty) => Ok(vec![check_type(ty)?]),
+        Value::Array(types) => types
+            .iter()
+            .map(|ty| check_type(as_str(ty, "type")?))
+            .collect(),
+        _ => Err(Error::InvalidSchema(
+            "`type` must be a string or an array".into(),
+        )),
+    }
+}
+
+fn as_str<'a>(value: &'a Value, keyword: &str) -> SchemaResult<&'a str> {
+    value.as_str().ok_or_else(|| {
+        Error::InvalidSchema(format!("`{}` must be a string", keyword))
+    })
+}
+
+fn as_array<'a>(
+    value: &'a Value,
+    keyword: &str,
+) -> SchemaResult<&'a Vec<Value>> {
+    value.as_array().ok_or_else(|| {
+        Error::InvalidSchema(format!("`{}` must be an array", keyword))
+    })
+}
+
+fn as_object<'a>(
+    value: &'a Value,
+    keyword: &str,
+) -> SchemaResult<&'a Map<String, Value>> {
+    value.as_object().ok_or_else(|| {
+        Error::InvalidSchema(format!("`{}` must be an object", keyword))
+    })
+}
+
+fn as_f64(value: &Value, keyword: &str) -> SchemaResult<f64> {
+    value.as_f64().ok_or_else(|| {
+        Error::InvalidSchema(format!("`{}` must be a number", keyword))
+    })
+}
+
+fn as_positive(value: &Value, keyword: &str) -> SchemaResult<f64> {
+    Some(as_f64(value, keyword)?)
+        .filter(|f| *f > 0.0)
+        .ok_or_else(|| {
+            Error::InvalidSchema(format!("`{}` must be positive", keyword))
+        })
+}
+
+/// Returns the value of `keyword` of `schema`, which must be a non-negative
+/// integer if present.
+fn as_count(
+    schema: &Map<String, Value>,
+    keyword: &str,
+) -> SchemaResult<Option<usize>> {
+    match schema.get(keyword) {
+        None => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .or_else(|| {
+                value
+                    .as_f64()
+                    .filter(|f| *f >= 0.0 && f.fract() == 0.0)
+                    .map(|f| f as u64)
+            })
+            .map(|count| Some(count.min(usize::MAX as u64) as usize))
+            .ok_or_else(|| {
+                Error::InvalidSchema(format!(
+                    "`{}` must be a non-negative integer",
+                    keyword
+                ))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_runner::TestRunner;
+    use serde_json::json;
+
+    fn generate(
+        schema: &Value,
+        params: ValueParams,
+        count: usize,
+    ) -> Vec<Value> {
+        let strategy = values(schema, params).unwrap();
+        let mut runner = TestRunner::deterministic();
+        (0..count)
+            .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+            .collect()
+    }
+
+    fn conforms(schema: &Value, value: &Value) -> bool {
+        let context = Context {
+            root: schema.clone(),
+            params: ValueParams::default(),
+            regexes: Mutex::new(BTreeMap::new()),
+        };
+        context.conforms(schema, value, 0)
+    }
+
+    #[test]
+    fn checks_values_against_schemas() {
+        assert!(conforms(&json!({"const": 1}), &json!(1.0)));
+        assert!(conforms(&json!({"type": "integer"}), &json!(2.0)));
+        assert!(!conforms(&json!({"type": "integer"}), &json!(2.5)));
+        assert!(conforms(&json!({"pattern": "b+"}), &json!("abbc")));
+        assert!(!conforms(&json!({"pattern": "^b+$"}), &json!("abbc")));
+        assert!(!conforms(
+            &json!({"minimum": 3, "exclusiveMinimum": true}),
+            &json!(3)
+        ));
+        assert!(conforms(&json!({"exclusiveMaximum": 3}), &json!(2.9)));
+        assert!(conforms(&json!({"multipleOf": 0.1}), &json!(0.3)));
+        let one_of = json!({"oneOf": [{"type": "integer"}, {"minimum": 2}]});
+        assert!(conforms(&one_of, &json!(1)));
+        assert!(!conforms(&one_of, &json!(2)));
+        let tuple =
+            json!({"items": [{"type": "null"}], "additionalItems": false});
+        assert!(conforms(&tuple, &json!([null])));
+        assert!(!conforms(&tuple, &json!([null, null])));
+        let objects = json!({
+            "properties": {"a": {"type": "null"}},
+            "patternProperties": {"^x": {"type": "boolean"}},
+            "additionalProperties": false
+        });
+        assert!(conforms(&objects, &json!({"a": null, "xy": true})));
+        assert!(!conforms(&objects, &json!({"xy": 1})));
+        assert!(!conforms(&objects, &json!({"b": null})));
+    }
+
+    #[test]
+    fn generates_conforming_documents() {
+        let schema = json!({
+            "$defs": {
+                "sku": {"type": "string", "pattern": "^[A-Z]{3}-[0-9]{4}$"}
+            },
+            "type": "object",
+            "properties": {
+                "sku": {"$ref": "#/$defs/sku"},
+                "quantity": {"type": "integer", "minimum": 1, "maximum": 99},
+                "price": {"type": "number", "exclusiveMinimum": 0, "multipleOf": 0.01},
+                "status": {"enum": ["open", "paid", "shipped"]},
+                "tags": {
+                    "type": "array",
+                    "items": {"type": "string", "maxLength": 8},
+                    "minItems": 1,
+                    "uniqueItems": true
+                },
+                "contact": {
+                    "oneOf": [
+                        {"type": "string", "format": "email"},
+                        {"type": "null"}
+                    ]
+                },
+                "created": {"type": "string", "format": "date-time"}
+            },
+            "required": ["sku", "quantity", "status", "tags"],
+            "additionalProperties": false
+        });
+        for value in generate(&schema, ValueParams::default(), 256) {
+            assert!(conforms(&schema, &value), "{}", value);
+            let sku = value["sku"].as_str().unwrap();
+            assert_eq!(8, sku.len());
+            assert!((1..=99).contains(&value["quantity"].as_i64().unwrap()));
+            assert!(!value["tags"].as_array().unwrap().is_empty());
+            let keys = [
+                "sku", "quantity", "price", "status", "tags", "contact",
+                "created",
+            ];
+            assert!(value
+                .as_object()
+                .unwrap()
+                .keys()
+                .all(|k| keys.contains(&k.as_str())));
+        }
+    }
+
+    #[test]
+    fn expands_recursive_refs_up_to_max_depth() {
+        let schema = json!({
+            "$ref": "#/definitions/node",
+            "definitions": {
+                "node": {
+                    "type": "object",
+                    "properties": {
+                        "children": {
+                            "type": "array",
+                            "items": {"$ref": "#/definitions/node"}
+                        }
+                    },
+                    "required": ["children"],
+                    "additionalProperties": false
+                }
+            }
+        });
+        fn height(node: &Value) -> u32 {
+            node["children"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|child| 1 + height(child))
+                .max()
+                .unwrap_or(0)
+        }
+
+        let params = ValueParams {
+            max_depth: 2,
+            size: (0..3).into(),
+            ..ValueParams::default()
+        };
+        let mut max_height = 0;
+        for value in generate(&schema, params, 256) {
+            assert!(conforms(&schema, &value), "{}", value);
+            max_height = max_height.max(height(&value));
+        }
+        assert_eq!(2, max_height);
+    }
+
+    #[test]
+    fn generates_formats_and_tuples() {
+        let schema = json!({
+            "type": "array",
+            "prefixItems": [
+                {"type": "string", "format": "ipv4"},
+                {"type": "string", "format": "uuid"},
+                {"type": "string", "format": "date"}
+            ],
+            "items": false,
+            "minItems": 2
+        });
+        for value in generate(&schema, ValueParams::default(), 64) {
+            let items = value.as_array().unwrap();
+            assert!((2..=3).contains(&items.len()));
+            items[0]
+                .as_str()
+                .unwrap()
+                .parse::<std::net::Ipv4Addr>()
+                .unwrap();
+            assert_eq!(36, items[1].as_str().unwrap().len());
+            if let Some(date) = items.get(2) {
+                assert_eq!(10, date.as_str().unwrap().len());
+            }
+        }
+    }
+
+    #[test]
+    fn merges_all_of_and_branches() {
+        let schema = json!({
+            "allOf": [
+                {"type": ["integer", "string"], "minimum": 10},
+                {"type": "number", "maximum": 20, "not": {"const": 15}}
+            ]
+        });
+        for value in generate(&schema, ValueParams::default(), 64) {
+            let n = value.as_i64().unwrap();
+            assert!((10..=20).contains(&n) && n != 15);
+        }
+
+        let schema = json!({
+            "type": "object",
+            "if": {"properties": {"kind": {"const": "a"}}, "required": ["kind"]},
+            "then": {"required": ["a"]},
+            "else": {"required": ["b"]},
+            "properties": {"kind": {"enum": ["a", "b"]}}
+        });
+        for value in generate(&schema, ValueParams::default(), 64) {
+            assert!(conforms(&schema, &value), "{}", value);
+        }
+    }
+
+    #[test]
+    fn reports_unusable_schemas() {
+        let error =
+            |schema: Value| values(&schema, ValueParams::default()).err();
+        assert!(matches!(error(json!(false)), Some(Error::Unsatisfiable(_))));
+        assert!(matches!(
+            error(json!({"type": "integer", "minimum": 5, "maximum": 4})),
+            Some(Error::Unsatisfiable(_))
+        ));
+        assert!(matches!(
+            error(
+                json!({"type": "object", "required": ["a"], "properties": {"a": false}})
+            ),
+            Some(Error::Unsatisfiable(_))
+        ));
+        assert!(matches!(
+            error(json!({"type": 5})),
+            Some(Error::InvalidSchema(_))
+        ));
+        assert!(matches!(
+            error(json!({"$ref": "#/$defs/missing"})),
+            Some(Error::InvalidSchema(_))
+        ));
+        assert!(matches!(
+            error(json!({"$ref": "other.json"})),
+            Some(Error::UnsupportedSchema(_))
+        ));
+        assert!(matches!(
+            error(json!({"unevaluatedProperties": false})),
+            Some(Error::UnsupportedSchema(_))
+        ));
+        assert!(matches!(
+            error(json!({"type": "string", "pattern": "("})),
+            Some(Error::Pattern(_))
+        ));
+    }
+}
@@ -13,6 +13,14 @@
 //!
 //! For documentation on how to get started with proptest and general usage
 //! advice, please refer to the [Proptest Book](https://proptest-rs.github.io/proptest/intro.html).
+//!
+//! Stateful testing, where sequences of commands with preconditions are
+//! checked against a reference model and shrunk by removing and simplifying
+//! commands, is provided by the companion
+//! [`proptest-state-machine`](https://docs.rs/proptest-state-machine) crate,
+//! which builds on the strategies and runner in this one. See the
+//! [State Machine testing](https://proptest-rs.github.io/proptest/proptest/state-machine.html)
+//! chapter of the book.
 
 #![forbid(future_incompatible)]
 #![deny(missing_docs, bare_trait_objects)]
@@ -66,14 +74,61 @@ pub mod arbitrary;
 pub mod array;
 pub mod bits;
 pub mod bool;
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+pub mod bytes;
 pub mod char;
 pub mod collection;
+#[cfg(any(feature = "arbitrary", feature = "quickcheck"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "arbitrary", feature = "quickcheck")))
+)]
+pub mod compat;
+pub mod date_time;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod encoding;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod ffi;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod formats;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod fuzz;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod func;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod grammar;
+#[cfg(feature = "petgraph")]
+#[cfg_attr(docsrs, doc(cfg(feature = "petgraph")))]
+pub mod graph;
+#[cfg(feature = "http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+pub mod http;
+#[cfg(feature = "serde_json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+pub mod json;
+#[cfg(feature = "ndarray")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+pub mod ndarray;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod net;
 pub mod num;
 #[cfg(feature = "std")]
 pub mod range_subset;
 pub mod strategy;
 pub mod test_runner;
+pub mod tree;
 pub mod tuple;
+#[cfg(feature = "uuid")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+pub mod uuid;
 
 pub mod option;
 #[cfg(feature = "std")]
@@ -83,7 +138,13 @@ pub mod result;
 pub mod sample;
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod sql;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub mod string;
+#[cfg(feature = "xml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+pub mod xml;
 
 pub mod prelude;
 
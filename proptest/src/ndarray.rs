@@ -0,0 +1,531 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for arrays of the `ndarray` crate.
+//!
+//! The arrays have a shape within given ranges and their elements are
+//! generated by a strategy of their own. They shrink by first dropping the
+//! last index along each axis, as far as the ranges allow, and then by
+//! shrinking the elements which remain.
+//!
+//! Besides arrays of one, two or any number of dimensions, there are
+//! strategies for square matrices which are symmetric or strictly diagonally
+//! dominant, such as the inputs of many linear algebra routines.
+//!
+//! ```rust
+//! use proptest::ndarray;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn transpose_twice(m in ndarray::array2(-1.0f64..1.0, 0..8, 0..8)) {
+//!         let transposed = m.t();
+//!         prop_assert_eq!(transposed.t(), m.view());
+//!     }
+//! }
+//! # fn main() { transpose_twice(); }
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::ndarray::{Array, Dimension, Ix1, Ix2, IxDyn};
+use num_traits::Signed;
+
+use crate::collection::SizeRange;
+use crate::num::sample_uniform_incl;
+use crate::std_facade::{vec, Vec};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// Strategy to create arrays with a shape in certain ranges.
+///
+/// Created by the functions in the same module.
+#[must_use = "strategies do nothing unless used"]
+pub struct ArrayStrategy<S: Strategy, D> {
+    element: S,
+    /// The range of the number of axes.
+    ndim: SizeRange,
+    /// The range of the length of each axis, where the last one applies to
+    /// any further axes.
+    lens: Vec<SizeRange>,
+    /// Whether all axes have the same length, shrinking together.
+    square: bool,
+    /// Whether the elements mirror those above the diagonal.
+    symmetric: bool,
+    /// Adjusts the elements of a square matrix, given its order.
+    fixup: Option<fn(&mut [S::Value], usize)>,
+    _marker: PhantomData<fn() -> D>,
+}
+
+impl<S: Strategy + Clone, D> Clone for ArrayStrategy<S, D> {
+    fn clone(&self) -> Self {
+        ArrayStrategy {
+            element: self.element.clone(),
+            ndim: self.ndim.clone(),
+            lens: self.lens.clone(),
+            square: self.square,
+            symmetric: self.symmetric,
+            fixup: self.fixup,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: Strategy, D> fmt::Debug for ArrayStrategy<S, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ArrayStrategy")
+            .field("element", &self.element)
+            .field("ndim", &self.ndim)
+            .field("lens", &self.lens)
+            .field("square", &self.square)
+            .field("symmetric", &self.symmetric)
+            .finish()
+    }
+}
+
+impl<S: Strategy, D> ArrayStrategy<S, D> {
+    fn new(element: S, ndim: SizeRange, lens: Vec<SizeRange>) -> Self {
+        ndim.assert_nonempty();
+        for len in &lens {
+            len.assert_nonempty();
+        }
+        ArrayStrategy {
+            element,
+            ndim,
+            lens,
+            square: false,
+            symmetric: false,
+            fixup: None,
+            _marker: PhantomData,
+        }
+    }
+
+    fn len_range(&self, axis: usize) -> &SizeRange {
+        &self.lens[axis.min(self.lens.len() - 1)]
+    }
+}
+
+/// Create a strategy to generate one-dimensional arrays with a length in
+/// `len`, whose elements are generated by `element`.
+pub fn array1<S: Strategy>(
+    element: S,
+    len: impl Into<SizeRange>,
+) -> ArrayStrategy<S, Ix1> {
+    ArrayStrategy::new(element, 1.into(), vec![len.into()])
+}
+
+/// Create a strategy to generate matrices with a number of rows in `rows`
+/// and of columns in `cols`, whose elements are generated by `element`.
+pub fn array2<S: Strategy>(
+    element: S,
+    rows: impl Into<SizeRange>,
+    cols: impl Into<SizeRange>,
+) -> ArrayStrategy<S, Ix2> {
+    ArrayStrategy::new(element, 2.into(), vec![rows.into(), cols.into()])
+}
+
+/// Create a strategy to generate arrays of a dynamic number of dimensions
+/// in `ndim`, where each axis has a length in `len`, and whose elements are
+/// generated by `element`.
+///
+/// The number of elements is the product of the lengths of the axes, so keep
+/// both ranges small. The number of dimensions does not shrink.
+pub fn array_dyn<S: Strategy>(
+    element: S,
+    ndim: impl Into<SizeRange>,
+    len: impl Into<SizeRange>,
+) -> ArrayStrategy<S, IxDyn> {
+    ArrayStrategy::new(element, ndim.into(), vec![len.into()])
+}
+
+/// Create a strategy to generate symmetric square matrices with an order in
+/// `order`, whose elements on and above the diagonal are generated by
+/// `element`.
+pub fn symmetric<S: Strategy>(
+    element: S,
+    order: impl Into<SizeRange>,
+) -> ArrayStrategy<S, Ix2> {
+    ArrayStrategy {
+        square: true,
+        symmetric: true,
+        ..ArrayStrategy::new(element, 2.into(), vec![order.into()])
+    }
+}
+
+/// Create a strategy to generate strictly diagonally dominant square
+/// matrices with an order in `order`, which are invertible.
+///
+/// The elements off the diagonal are generated by `element`. Each element on
+/// the diagonal is the sum of the absolute values of the others in its row,
+/// plus the absolute value of one generated by `element`, plus one. For
+/// integers, keep `element` small enough for these sums not to overflow.
+pub fn diagonally_dominant<S>(
+    element: S,
+    order: impl Into<SizeRange>,
+) -> ArrayStrategy<S, Ix2>
+where
+    S: Strategy,
+    S::Value: Signed,
+{
+    ArrayStrategy {
+        square: true,
+        fixup: Some(make_diagonally_dominant::<S::Value>),
+        ..ArrayStrategy::new(element, 2.into(), vec![order.into()])
+    }
+}
+
+fn make_diagonally_dominant<V: Signed>(values: &mut [V], order: usize) {
+    for i in 0..order {
+        let row = &values[i * order..(i + 1) * order];
+        let off_diagonal = row
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .fold(V::zero(), |sum, (_, value)| sum + value.abs());
+        let diagonal = row[i].abs() + off_diagonal + V::one();
+        values[i * order + i] = diagonal;
+    }
+}
+
+impl<S: Strategy, D: Dimension> Strategy for ArrayStrategy<S, D> {
+    type Tree = ArrayValueTree<S::Tree, D>;
+    type Value = Array<S::Value, D>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let (start, end) = self.ndim.start_end_incl();
+        let ndim = sample_uniform_incl(runner, start, end);
+        let mut shape = Vec::with_capacity(ndim);
+        let mut min_lens = Vec::with_capacity(ndim);
+        for axis in 0..ndim {
+            if self.square && axis > 0 {
+                shape.push(shape[0]);
+                min_lens.push(min_lens[0]);
+                continue;
+            }
+            let (start, end) = self.len_range(axis).start_end_incl();
+            let end = runner.sized(start, end);
+            shape.push(sample_uniform_incl(runner, start, end));
+            min_lens.push(start);
+        }
+
+        let count = shape.iter().product();
+        let mut elements = Vec::with_capacity(count);
+        while elements.len() < count {
+            elements.push(self.element.new_tree(runner)?);
+        }
+
+        Ok(ArrayValueTree {
+            elements,
+            lens: shape.clone(),
+            shape,
+            min_lens,
+            square: self.square,
+            symmetric: self.symmetric,
+            fixup: self.fixup,
+            shrink: Shrink::Axis(0),
+            prev_shrink: None,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Shrink {
+    Axis(usize),
+    Element(usize),
+}
+
+/// `ValueTree` corresponding to `ArrayStrategy`.
+pub struct ArrayValueTree<T: ValueTree, D> {
+    /// The elements of the array as first generated, in row-major order.
+    elements: Vec<T>,
+    /// The shape of the array as first generated.
+    shape: Vec<usize>,
+    /// The current length of each axis, which selects the leading block of
+    /// the array as first generated.
+    lens: Vec<usize>,
+    min_lens: Vec<usize>,
+    square: bool,
+    symmetric: bool,
+    fixup: Option<fn(&mut [T::Value], usize)>,
+    shrink: Shrink,
+    prev_shrink: Option<Shrink>,
+    _marker: PhantomData<fn() -> D>,
+}
+
+impl<T: ValueTree + Clone, D> Clone for ArrayValueTree<T, D> {
+    fn clone(&self) -> Self {
+        ArrayValueTree {
+            elements: self.elements.clone(),
+            shape: self.shape.clone(),
+            lens: self.lens.clone(),
+            min_lens: self.min_lens.clone(),
+            square: self.square,
+            symmetric: self.symmetric,
+            fixup: self.fixup,
+            shrink: self.shrink,
+            prev_shrink: self.prev_shrink,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ValueTree + fmt::Debug, D> fmt::Debug for ArrayValueTree<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ArrayValueTree")
+            .field("elements", &self.elements)
+            .field("shape", &self.shape)
+            .field("lens", &self.lens)
+            .field("shrink", &self.shrink)
+            .field("prev_shrink", &self.prev_shrink)
+            .finish()
+    }
+}
+
+impl<T: ValueTree, D> ArrayValueTree<T, D> {
+    /// Returns the index into `elements` of the element at `index` of the
+    /// array as first generated.
+    fn offset(&self, index: &[usize]) -> usize {
+        index
+            .iter()
+            .zip(&self.shape)
+            .fold(0, |offset, (&i, &len)| offset * len + i)
+    }
+
+    /// Returns the index of the element which `offset` is in the array as
+    /// first generated.
+    fn index(&self, mut offset: usize) -> Vec<usize> {
+        let mut index = vec![0; self.shape.len()];
+        for (i, &len) in index.iter_mut().zip(&self.shape).rev() {
+            *i = offset % len;
+            offset /= len;
+        }
+        index
+    }
+
+    /// Returns the index into `elements` of the element at `index` of the
+    /// current array.
+    fn source(&self, index: &mut [usize]) -> usize {
+        if self.symmetric && index[0] > index[1] {
+            index.swap(0, 1);
+        }
+        self.offset(index)
+    }
+
+    /// Returns whether shrinking the element at `offset` of `elements` can
+    /// change the current array.
+    fn is_used(&self, offset: usize) -> bool {
+        let index = self.index(offset);
+        index.iter().zip(&self.lens).all(|(i, len)| i < len)
+            && !(self.symmetric && index[0] > index[1])
+    }
+
+    fn axes_to_shrink(&self, axis: usize) -> core::ops::Range<usize> {
+        if self.square {
+            0..self.lens.len()
+        } else {
+            axis..axis + 1
+        }
+    }
+}
+
+impl<T: ValueTree, D: Dimension> ValueTree for ArrayValueTree<T, D> {
+    type Value = Array<T::Value, D>;
+
+    fn current(&self) -> Array<T::Value, D> {
+        let count = self.lens.iter().product();
+        let mut values = Vec::with_capacity(count);
+        let mut index = vec![0; self.lens.len()];
+        for _ in 0..count {
+            let mut source = index.clone();
+            values.push(self.elements[self.source(&mut source)].current());
+            // Advance to the next index in row-major order.
+            for (i, &len) in index.iter_mut().zip(&self.lens).rev() {
+                *i += 1;
+                if *i < len {
+                    break;
+                }
+                *i = 0;
+            }
+        }
+        if let Some(fixup) = self.fixup {
+            fixup(&mut values, self.lens[0]);
+        }
+
+        Array::from_shape_vec(IxDyn(&self.lens), values)
+            .and_then(|array| array.into_dimensionality::<D>())
+            .expect("array shape does not match its dimensionality")
+    }
+
+    fn simplify(&mut self) -> bool {
+        // Drop the last index along each axis in turn as far as possible,
+        // then shrink each remaining element in turn.
+        loop {
+            match self.shrink {
+                Shrink::Axis(axis) if axis < self.lens.len() => {
+                    if self.lens[axis] > self.min_lens[axis] {
+                        for axis in self.axes_to_shrink(axis) {
+                            self.lens[axis] -= 1;
+                        }
+                        self.prev_shrink = Some(self.shrink);
+                        return true;
+                    }
+                    self.shrink = if self.square {
+                        Shrink::Element(0)
+                    } else {
+                        Shrink::Axis(axis + 1)
+                    };
+                }
+                Shrink::Axis(_) => self.shrink = Shrink::Element(0),
+                Shrink::Element(ix) if ix < self.elements.len() => {
+                    if self.is_used(ix) && self.elements[ix].simplify() {
+                        self.prev_shrink = Some(self.shrink);
+                        return true;
+                    }
+                    self.shrink = Shrink::Element(ix + 1);
+                }
+                Shrink::Element(_) => {
+                    self.prev_shrink = None;
+                    return false;
+                }
+            }
+        }
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.prev_shrink {
+            None => false,
+            Some(Shrink::Axis(axis)) => {
+                for axis in self.axes_to_shrink(axis) {
+                    self.lens[axis] += 1;
+                }
+                self.shrink = if self.square {
+                    Shrink::Element(0)
+                } else {
+                    Shrink::Axis(axis + 1)
+                };
+                self.prev_shrink = None;
+                true
+            }
+            Some(Shrink::Element(ix)) => {
+                if self.elements[ix].complicate() {
+                    // Don't unset prev_shrink; we may be able to complicate
+                    // again.
+                    true
+                } else {
+                    self.prev_shrink = None;
+                    false
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shapes_are_in_range() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let a = array1(0..10u8, 2..5).new_tree(&mut runner).unwrap();
+            assert!((2..5).contains(&a.current().len()));
+
+            let m = array2(0..10u8, 1..3, 4..=6)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!((1..3).contains(&m.nrows()), "{:?}", m);
+            assert!((4..=6).contains(&m.ncols()), "{:?}", m);
+
+            let d = array_dyn(0..10u8, 1..=3, 1..4)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!((1..=3).contains(&d.ndim()), "{:?}", d);
+            assert!(d.shape().iter().all(|len| (1..4).contains(len)));
+        }
+    }
+
+    #[test]
+    fn symmetric_matrices_are_symmetric() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let mut tree =
+                symmetric(-100i32..100, 0..6).new_tree(&mut runner).unwrap();
+            loop {
+                let m = tree.current();
+                assert_eq!(m.nrows(), m.ncols());
+                assert_eq!(m.t(), m, "{:?}", m);
+                if !tree.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn diagonally_dominant_matrices_are_dominant() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let m = diagonally_dominant(-1.0f64..1.0, 1..6)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert_eq!(m.nrows(), m.ncols());
+            for (i, row) in m.outer_iter().enumerate() {
+                let off_diagonal: f64 = row
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, x)| x.abs())
+                    .sum();
+                assert!(row[i].abs() > off_diagonal, "{:?}", m);
+            }
+        }
+    }
+
+    #[test]
+    fn shrinks_shape_then_elements() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&array2(0..100u32, 1..10, 1..10), |m| {
+            prop_assert!(m.iter().all(|&x| x < 50));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, m)) => {
+                assert_eq!(Array::from_elem((1, 1), 50), m)
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn shrinks_to_minimal_shape() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let mut tree =
+                array2(0..10u8, 2..6, 3..6).new_tree(&mut runner).unwrap();
+            while tree.simplify() {}
+            let m = tree.current();
+            assert_eq!(&[2, 3], m.shape());
+            assert!(m.iter().all(|&x| x == 0));
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(array1(0..10u8, 0..8), None);
+        check_strategy_sanity(array2(0..10u8, 0..4, 0..4), None);
+        check_strategy_sanity(symmetric(0..10u8, 0..4), None);
+        check_strategy_sanity(diagonally_dominant(-10i32..10, 0..4), None);
+        check_strategy_sanity(array_dyn(0..10u8, 0..3, 0..3), None);
+    }
+}
@@ -0,0 +1,367 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for IP addresses in specific networks, ports and socket
+//! addresses.
+//!
+//! The `Arbitrary` implementations for the `std::net` types generate
+//! addresses from the whole address space. The strategies in this module
+//! instead generate addresses within a subnet, such as
+//! `ipv4_in_subnet("10.0.0.0/8")`, or within one of the special-purpose
+//! ranges named by `AddrRange`. Addresses shrink toward the first address of
+//! their network.
+//!
+//! The `packets` module generates IPv4 packets carrying TCP and UDP.
+
+use core::ops::RangeInclusive;
+use core::str::FromStr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use crate::std_facade::Box;
+use crate::strategy::*;
+use crate::test_runner::*;
+
+pub mod packets;
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates IPv4 addresses within some networks.
+    ///
+    /// Created by the `ipv4_in_subnet()` and `ipv4_in()` functions in the
+    /// same module.
+    #[derive(Clone, Debug)]
+    pub struct Ipv4Strategy[][](SBoxedStrategy<Ipv4Addr>)
+        -> Ipv4ValueTree;
+    /// `ValueTree` corresponding to `Ipv4Strategy`.
+    pub struct Ipv4ValueTree[][](Box<dyn ValueTree<Value = Ipv4Addr>>)
+        -> Ipv4Addr;
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates IPv6 addresses within some networks.
+    ///
+    /// Created by the `ipv6_in_prefix()` and `ipv6_in()` functions in the
+    /// same module.
+    #[derive(Clone, Debug)]
+    pub struct Ipv6Strategy[][](SBoxedStrategy<Ipv6Addr>)
+        -> Ipv6ValueTree;
+    /// `ValueTree` corresponding to `Ipv6Strategy`.
+    pub struct Ipv6ValueTree[][](Box<dyn ValueTree<Value = Ipv6Addr>>)
+        -> Ipv6Addr;
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates socket addresses.
+    ///
+    /// Created by the `socket_addr()` function in the same module.
+    #[derive(Clone, Debug)]
+    pub struct SocketAddrStrategy[][](SBoxedStrategy<SocketAddr>)
+        -> SocketAddrValueTree;
+    /// `ValueTree` corresponding to `SocketAddrStrategy`.
+    pub struct SocketAddrValueTree[][](Box<dyn ValueTree<Value = SocketAddr>>)
+        -> SocketAddr;
+}
+
+/// Parse `subnet` in CIDR notation, such as `10.0.0.0/8`, into its address
+/// and prefix length.
+fn parse_subnet<A: FromStr>(subnet: &str, max_prefix: u32) -> (A, u32) {
+    subnet
+        .split_once('/')
+        .and_then(|(addr, prefix)| {
+            Some((addr.parse().ok()?, prefix.parse().ok()?))
+        })
+        .filter(|&(_, prefix)| prefix <= max_prefix)
+        .unwrap_or_else(|| panic!("Invalid subnet {:?}", subnet))
+}
+
+/// Generates the addresses in the IPv4 network with the given address and
+/// prefix length.
+fn ipv4_network(addr: Ipv4Addr, prefix: u32) -> SBoxedStrategy<Ipv4Addr> {
+    let host_mask = u32::MAX.checked_shr(prefix).unwrap_or(0);
+    let network = u32::from(addr) & !host_mask;
+    (0..=host_mask)
+        .prop_map(move |host| Ipv4Addr::from(network | host))
+        .sboxed()
+}
+
+/// Generates the addresses in the IPv6 network with the given address and
+/// prefix length.
+fn ipv6_network(addr: Ipv6Addr, prefix: u32) -> SBoxedStrategy<Ipv6Addr> {
+    let host_mask = u128::MAX.checked_shr(prefix).unwrap_or(0);
+    let network = u128::from(addr) & !host_mask;
+    (0..=host_mask)
+        .prop_map(move |host| Ipv6Addr::from(network | host))
+        .sboxed()
+}
+
+/// Creates a strategy which generates IPv4 addresses within `subnet`, given
+/// in CIDR notation such as `"10.0.0.0/8"`.
+///
+/// Any host bits set in the address of `subnet` are ignored. Addresses
+/// shrink toward the network address.
+///
+/// ## Panics
+///
+/// Panics if `subnet` is not an IPv4 address followed by `/` and a prefix
+/// length of at most 32.
+pub fn ipv4_in_subnet(subnet: &str) -> Ipv4Strategy {
+    let (addr, prefix) = parse_subnet(subnet, 32);
+    Ipv4Strategy(ipv4_network(addr, prefix))
+}
+
+/// Creates a strategy which generates IPv6 addresses within `prefix`, given
+/// in CIDR notation such as `"2001:db8::/32"`.
+///
+/// Any host bits set in the address of `prefix` are ignored. Addresses
+/// shrink toward the first address of the prefix.
+///
+/// ## Panics
+///
+/// Panics if `prefix` is not an IPv6 address followed by `/` and a prefix
+/// length of at most 128.
+pub fn ipv6_in_prefix(prefix: &str) -> Ipv6Strategy {
+    let (addr, prefix) = parse_subnet(prefix, 128);
+    Ipv6Strategy(ipv6_network(addr, prefix))
+}
+
+/// Special-purpose address ranges, for use with `ipv4_in()` and `ipv6_in()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AddrRange {
+    /// Loopback addresses: `127.0.0.0/8` and `::1`.
+    Loopback,
+    /// Private addresses: `10.0.0.0/8`, `172.16.0.0/12` and `192.168.0.0/16`
+    /// ([RFC 1918]), and unique local addresses `fc00::/7` ([RFC 4193]).
+    ///
+    /// [RFC 1918]: https://www.rfc-editor.org/rfc/rfc1918
+    /// [RFC 4193]: https://www.rfc-editor.org/rfc/rfc4193
+    Private,
+    /// Link-local addresses: `169.254.0.0/16` and `fe80::/10`.
+    LinkLocal,
+    /// Multicast addresses: `224.0.0.0/4` and `ff00::/8`.
+    Multicast,
+    /// Addresses reserved for documentation: `192.0.2.0/24`,
+    /// `198.51.100.0/24` and `203.0.113.0/24` ([RFC 5737]), and
+    /// `2001:db8::/32` ([RFC 3849]).
+    ///
+    /// [RFC 5737]: https://www.rfc-editor.org/rfc/rfc5737
+    /// [RFC 3849]: https://www.rfc-editor.org/rfc/rfc3849
+    Documentation,
+}
+
+impl AddrRange {
+    fn ipv4_subnets(self) -> &'static [&'static str] {
+        match self {
+            AddrRange::Loopback => &["127.0.0.0/8"],
+            AddrRange::Private => {
+                &["10.0.0.0/8", "172.16.0.0/12", "192.168.0.0/16"]
+            }
+            AddrRange::LinkLocal => &["169.254.0.0/16"],
+            AddrRange::Multicast => &["224.0.0.0/4"],
+            AddrRange::Documentation => {
+                &["192.0.2.0/24", "198.51.100.0/24", "203.0.113.0/24"]
+            }
+        }
+    }
+
+    fn ipv6_prefixes(self) -> &'static [&'static str] {
+        match self {
+            AddrRange::Loopback => &["::1/128"],
+            AddrRange::Private => &["fc00::/7"],
+            AddrRange::LinkLocal => &["fe80::/10"],
+            AddrRange::Multicast => &["ff00::/8"],
+            AddrRange::Documentation => &["2001:db8::/32"],
+        }
+    }
+}
+
+/// Creates a strategy which generates IPv4 addresses within `range`.
+///
+/// When `range` has several subnets, each is equally likely to be chosen.
+pub fn ipv4_in(range: AddrRange) -> Ipv4Strategy {
+    let subnets = range.ipv4_subnets().iter().map(|subnet| {
+        let (addr, prefix) = parse_subnet(subnet, 32);
+        ipv4_network(addr, prefix)
+    });
+    Ipv4Strategy(Union::new(subnets).sboxed())
+}
+
+/// Creates a strategy which generates IPv6 addresses within `range`.
+pub fn ipv6_in(range: AddrRange) -> Ipv6Strategy {
+    let prefixes = range.ipv6_prefixes().iter().map(|prefix| {
+        let (addr, prefix) = parse_subnet(prefix, 128);
+        ipv6_network(addr, prefix)
+    });
+    Ipv6Strategy(Union::new(prefixes).sboxed())
+}
+
+/// Classes of port numbers, as defined by [RFC 6335].
+///
+/// [RFC 6335]: https://www.rfc-editor.org/rfc/rfc6335#section-6
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PortClass {
+    /// System, or well-known, ports: 0 to 1023.
+    System,
+    /// User, or registered, ports: 1024 to 49151.
+    User,
+    /// Dynamic, or ephemeral, ports: 49152 to 65535.
+    Dynamic,
+}
+
+/// Creates a strategy which generates port numbers in `class`.
+///
+/// Ports shrink toward the lowest port of the class.
+pub fn port(class: PortClass) -> RangeInclusive<u16> {
+    match class {
+        PortClass::System => 0..=1023,
+        PortClass::User => 1024..=49151,
+        PortClass::Dynamic => 49152..=65535,
+    }
+}
+
+/// Creates a strategy which generates socket addresses with an IP address
+/// from `ip` and a port from `port`.
+///
+/// `ip` may generate `Ipv4Addr`, `Ipv6Addr` or `IpAddr`.
+///
+/// ```
+/// use proptest::net::{ipv4_in, port, socket_addr, AddrRange, PortClass};
+/// use proptest::prelude::*;
+///
+/// proptest! {
+///     fn local_addresses_are_private(
+///         addr in socket_addr(
+///             ipv4_in(AddrRange::Private),
+///             port(PortClass::Dynamic),
+///         )
+///     ) {
+///         prop_assert!(addr.port() >= 49152);
+///     }
+/// }
+/// # fn main() { local_addresses_are_private(); }
+/// ```
+pub fn socket_addr<I, P>(ip: I, port: P) -> SocketAddrStrategy
+where
+    I: Strategy + Send + Sync + 'static,
+    I::Value: Into<IpAddr>,
+    P: Strategy<Value = u16> + Send + Sync + 'static,
+{
+    SocketAddrStrategy(
+        (ip, port)
+            .prop_map(|(ip, port)| SocketAddr::new(ip.into(), port))
+            .sboxed(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::std_facade::Vec;
+
+    fn sample<S: Strategy>(input: S) -> Vec<S::Value> {
+        let mut runner = TestRunner::deterministic();
+        (0..256)
+            .map(|_| input.new_tree(&mut runner).unwrap().current())
+            .collect()
+    }
+
+    fn minimal<S: Strategy>(input: S) -> S::Value {
+        let mut runner = TestRunner::deterministic();
+        match runner.run(&input, |_| Err(TestCaseError::fail("minimize"))) {
+            Err(TestError::Fail(_, value)) => value,
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn generates_addresses_in_subnet() {
+        for addr in sample(ipv4_in_subnet("10.1.2.3/20")) {
+            assert_eq!(0x0a01_0000, u32::from(addr) & 0xffff_f000, "{}", addr);
+        }
+        assert_eq!(
+            Ipv4Addr::new(10, 1, 0, 0),
+            minimal(ipv4_in_subnet("10.1.2.3/20"))
+        );
+        assert_eq!(
+            vec![Ipv4Addr::new(1, 2, 3, 4); 256],
+            sample(ipv4_in_subnet("1.2.3.4/32"))
+        );
+        assert!(sample(ipv4_in_subnet("0.0.0.0/0"))
+            .iter()
+            .any(|addr| addr.octets()[0] >= 128));
+    }
+
+    #[test]
+    fn generates_addresses_in_prefix() {
+        for addr in sample(ipv6_in_prefix("2001:db8:1::/48")) {
+            assert_eq!([0x2001, 0xdb8, 1], addr.segments()[..3], "{}", addr);
+        }
+        assert_eq!(
+            "2001:db8:1::".parse::<Ipv6Addr>().unwrap(),
+            minimal(ipv6_in_prefix("2001:db8:1::/48"))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid subnet")]
+    fn rejects_long_prefix() {
+        let _ = ipv4_in_subnet("10.0.0.0/33");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid subnet")]
+    fn rejects_wrong_family() {
+        let _ = ipv6_in_prefix("10.0.0.0/8");
+    }
+
+    #[test]
+    fn generates_addresses_in_ranges() {
+        let ranges = [
+            (AddrRange::Loopback, Ipv4Addr::is_loopback as fn(&_) -> _),
+            (AddrRange::Private, Ipv4Addr::is_private),
+            (AddrRange::LinkLocal, Ipv4Addr::is_link_local),
+            (AddrRange::Multicast, Ipv4Addr::is_multicast),
+            (AddrRange::Documentation, Ipv4Addr::is_documentation),
+        ];
+        for (range, check) in ranges {
+            let addrs = sample(ipv4_in(range));
+            assert!(addrs.iter().all(check), "{:?}", range);
+        }
+
+        let ranges = [
+            (AddrRange::Loopback, Ipv6Addr::is_loopback as fn(&_) -> _),
+            (AddrRange::Private, Ipv6Addr::is_unique_local),
+            (AddrRange::LinkLocal, Ipv6Addr::is_unicast_link_local),
+            (AddrRange::Multicast, Ipv6Addr::is_multicast),
+            (AddrRange::Documentation, |addr: &Ipv6Addr| {
+                addr.segments()[..2] == [0x2001, 0xdb8]
+            }),
+        ];
+        for (range, check) in ranges {
+            let addrs = sample(ipv6_in(range));
+            assert!(addrs.iter().all(check), "{:?}", range);
+        }
+    }
+
+    #[test]
+    fn uses_every_subnet_of_range() {
+        let addrs = sample(ipv4_in(AddrRange::Private));
+        for first in [10, 172, 192] {
+            assert!(addrs.iter().any(|addr| addr.octets()[0] == first));
+        }
+    }
+
+    #[test]
+    fn generates_socket_addresses() {
+        let input =
+            socket_addr(ipv6_in(AddrRange::LinkLocal), port(PortClass::User));
+        for addr in sample(input) {
+            assert!(addr.is_ipv6());
+            assert!((1024..=49151).contains(&addr.port()));
+        }
+    }
+}
@@ -0,0 +1,785 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for structurally valid IPv4 packets carrying TCP, UDP or
+//! other payloads.
+//!
+//! Generated packets are byte vectors wrapped in `Ipv4Packet`, which has
+//! accessors for the header fields and views of the TCP or UDP header. Every
+//! length field matches the data, options are well-formed, and checksums are
+//! correct unless `PacketParams::corrupt_checksums` asks for them not to be.
+//! All other fields take arbitrary values, so packets may have unusual flag
+//! combinations, TTLs, options and ports.
+
+use core::ops::Range;
+use std::net::Ipv4Addr;
+
+use crate::arbitrary::any;
+use crate::collection::{vec, SizeRange};
+use crate::option;
+use crate::std_facade::{Box, Vec};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// The IP protocol number of TCP.
+pub const TCP: u8 = 6;
+/// The IP protocol number of UDP.
+pub const UDP: u8 = 17;
+
+/// The maximum length of the options of IPv4 and TCP headers.
+const MAX_OPTIONS_LEN: usize = 40;
+
+/// An IPv4 packet, including its header.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ipv4Packet {
+    bytes: Vec<u8>,
+}
+
+/// Read the big-endian `u16` at `offset` in `bytes`.
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+/// Read the big-endian `u32` at `offset` in `bytes`.
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+impl Ipv4Packet {
+    /// Returns the bytes of the packet.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the bytes of the packet.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Returns the length of the header in bytes, including options.
+    pub fn header_len(&self) -> usize {
+        usize::from(self.bytes[0] & 0xf) * 4
+    }
+
+    /// Returns the differentiated services code point.
+    pub fn dscp(&self) -> u8 {
+        self.bytes[1] >> 2
+    }
+
+    /// Returns the explicit congestion notification bits.
+    pub fn ecn(&self) -> u8 {
+        self.bytes[1] & 0x3
+    }
+
+    /// Returns the total length of the packet in bytes.
+    pub fn total_len(&self) -> u16 {
+        read_u16(&self.bytes, 2)
+    }
+
+    /// Returns the identification field.
+    pub fn identification(&self) -> u16 {
+        read_u16(&self.bytes, 4)
+    }
+
+    /// Returns whether the don't fragment flag is set.
+    pub fn dont_fragment(&self) -> bool {
+        self.bytes[6] & 0x40 != 0
+    }
+
+    /// Returns whether the more fragments flag is set.
+    pub fn more_fragments(&self) -> bool {
+        self.bytes[6] & 0x20 != 0
+    }
+
+    /// Returns the fragment offset, in units of 8 bytes.
+    pub fn fragment_offset(&self) -> u16 {
+        read_u16(&self.bytes, 6) & 0x1fff
+    }
+
+    /// Returns the time to live.
+    pub fn ttl(&self) -> u8 {
+        self.bytes[8]
+    }
+
+    /// Returns the protocol number of the payload.
+    pub fn protocol(&self) -> u8 {
+        self.bytes[9]
+    }
+
+    /// Returns the header checksum.
+    pub fn checksum(&self) -> u16 {
+        read_u16(&self.bytes, 10)
+    }
+
+    /// Returns whether the header checksum is correct.
+    pub fn checksum_is_valid(&self) -> bool {
+        sum(&self.bytes[..self.header_len()], 0) == 0xffff
+    }
+
+    /// Returns the source address.
+    pub fn source(&self) -> Ipv4Addr {
+        Ipv4Addr::from(read_u32(&self.bytes, 12))
+    }
+
+    /// Returns the destination address.
+    pub fn destination(&self) -> Ipv4Addr {
+        Ipv4Addr::from(read_u32(&self.bytes, 16))
+    }
+
+    /// Returns the options of the header, including any padding.
+    pub fn options(&self) -> &[u8] {
+        &self.bytes[20..self.header_len()]
+    }
+
+    /// Returns the payload following the header.
+    pub fn payload(&self) -> &[u8] {
+        &self.bytes[self.header_len()..]
+    }
+
+    /// Returns the TCP segment in the payload, if the protocol is TCP.
+    pub fn tcp(&self) -> Option<TcpSegment<'_>> {
+        (self.protocol() == TCP).then_some(TcpSegment { packet: self })
+    }
+
+    /// Returns the UDP datagram in the payload, if the protocol is UDP.
+    pub fn udp(&self) -> Option<UdpDatagram<'_>> {
+        (self.protocol() == UDP).then_some(UdpDatagram { packet: self })
+    }
+
+    /// Returns the one's complement sum of the pseudo-header used in TCP and
+    /// UDP checksums.
+    fn pseudo_header_sum(&self) -> u32 {
+        let words = [
+            read_u16(&self.bytes, 12),
+            read_u16(&self.bytes, 14),
+            read_u16(&self.bytes, 16),
+            read_u16(&self.bytes, 18),
+            u16::from(self.protocol()),
+            self.payload().len() as u16,
+        ];
+        words.iter().map(|&word| u32::from(word)).sum()
+    }
+}
+
+/// A view of the TCP segment in an `Ipv4Packet`.
+#[derive(Clone, Copy, Debug)]
+pub struct TcpSegment<'a> {
+    packet: &'a Ipv4Packet,
+}
+
+impl<'a> TcpSegment<'a> {
+    /// Returns the bytes of the segment, including its header.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.packet.payload()
+    }
+
+    /// Returns the source port.
+    pub fn source_port(&self) -> u16 {
+        read_u16(self.as_bytes(), 0)
+    }
+
+    /// Returns the destination port.
+    pub fn destination_port(&self) -> u16 {
+        read_u16(self.as_bytes(), 2)
+    }
+
+    /// Returns the sequence number.
+    pub fn sequence_number(&self) -> u32 {
+        read_u32(self.as_bytes(), 4)
+    }
+
+    /// Returns the acknowledgment number.
+    pub fn acknowledgment_number(&self) -> u32 {
+        read_u32(self.as_bytes(), 8)
+    }
+
+    /// Returns the length of the header in bytes, including options.
+    pub fn header_len(&self) -> usize {
+        usize::from(self.as_bytes()[12] >> 4) * 4
+    }
+
+    /// Returns the flags, from CWR in the most significant bit to FIN in the
+    /// least significant bit.
+    pub fn flags(&self) -> u8 {
+        self.as_bytes()[13]
+    }
+
+    /// Returns the window size.
+    pub fn window(&self) -> u16 {
+        read_u16(self.as_bytes(), 14)
+    }
+
+    /// Returns the checksum.
+    pub fn checksum(&self) -> u16 {
+        read_u16(self.as_bytes(), 16)
+    }
+
+    /// Returns whether the checksum is correct.
+    pub fn checksum_is_valid(&self) -> bool {
+        sum(self.as_bytes(), self.packet.pseudo_header_sum()) == 0xffff
+    }
+
+    /// Returns the urgent pointer.
+    pub fn urgent_pointer(&self) -> u16 {
+        read_u16(self.as_bytes(), 18)
+    }
+
+    /// Returns the options of the header, including any padding.
+    pub fn options(&self) -> &'a [u8] {
+        &self.as_bytes()[20..self.header_len()]
+    }
+
+    /// Returns the payload following the header.
+    pub fn payload(&self) -> &'a [u8] {
+        &self.as_bytes()[self.header_len()..]
+    }
+}
+
+/// A view of the UDP datagram in an `Ipv4Packet`.
+#[derive(Clone, Copy, Debug)]
+pub struct UdpDatagram<'a> {
+    packet: &'a Ipv4Packet,
+}
+
+impl<'a> UdpDatagram<'a> {
+    /// Returns the bytes of the datagram, including its header.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.packet.payload()
+    }
+
+    /// Returns the source port.
+    pub fn source_port(&self) -> u16 {
+        read_u16(self.as_bytes(), 0)
+    }
+
+    /// Returns the destination port.
+    pub fn destination_port(&self) -> u16 {
+        read_u16(self.as_bytes(), 2)
+    }
+
+    /// Returns the length of the datagram in bytes, including its header.
+    pub fn len(&self) -> u16 {
+        read_u16(self.as_bytes(), 4)
+    }
+
+    /// Returns whether the datagram has no payload.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 8
+    }
+
+    /// Returns the checksum. Zero means that the sender did not compute one.
+    pub fn checksum(&self) -> u16 {
+        read_u16(self.as_bytes(), 6)
+    }
+
+    /// Returns whether the checksum is correct or absent.
+    pub fn checksum_is_valid(&self) -> bool {
+        self.checksum() == 0
+            || sum(self.as_bytes(), self.packet.pseudo_header_sum()) == 0xffff
+    }
+
+    /// Returns the payload following the header.
+    pub fn payload(&self) -> &'a [u8] {
+        &self.as_bytes()[8..]
+    }
+}
+
+/// Returns the one's complement sum of the 16-bit words of `data` and
+/// `initial`.
+fn sum(data: &[u8], initial: u32) -> u16 {
+    let mut sum = initial;
+    for word in data.chunks(2) {
+        sum += u32::from(u16::from_be_bytes([
+            word[0],
+            *word.get(1).unwrap_or(&0),
+        ]));
+    }
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    sum as u16
+}
+
+/// Returns a checksum which is wrong wherever `checksum` is right. `offset`
+/// must be in `1..0xffff`.
+fn corrupt(checksum: u16, offset: u16) -> u16 {
+    // 0 and 0xffff are the same number in one's complement, so stay away
+    // from everything equivalent to the correct checksum.
+    let wrong = (u32::from(checksum) % 0xffff + u32::from(offset)) % 0xffff;
+    match wrong {
+        // Zero would mean no checksum in UDP
+        0 => 0xffff,
+        wrong => wrong as u16,
+    }
+}
+
+/// Parameters for generating packets.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PacketParams {
+    /// The range of the length of the innermost payload, i.e. after the TCP
+    /// or UDP header if there is one.
+    ///
+    /// The default is `0..64`.
+    pub payload_len: SizeRange,
+    /// Whether IPv4 and TCP headers may have options.
+    ///
+    /// The default is `true`.
+    pub options: bool,
+    /// Whether every packet should have at least one wrong checksum, in the
+    /// IPv4 header or in the TCP or UDP header.
+    ///
+    /// The default is `false`.
+    pub corrupt_checksums: bool,
+}
+
+impl Default for PacketParams {
+    fn default() -> Self {
+        PacketParams {
+            payload_len: (0..64).into(),
+            options: true,
+            corrupt_checksums: false,
+        }
+    }
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates IPv4 packets.
+    ///
+    /// Created by the `ipv4()`, `tcp()` and `udp()` functions in the same
+    /// module.
+    #[derive(Clone, Debug)]
+    pub struct PacketStrategy[][](SBoxedStrategy<Ipv4Packet>)
+        -> PacketValueTree;
+    /// `ValueTree` corresponding to `PacketStrategy`.
+    pub struct PacketValueTree[][](Box<dyn ValueTree<Value = Ipv4Packet>>)
+        -> Ipv4Packet;
+}
+
+/// Generates the options of an IPv4 or TCP header, padded to a multiple of
+/// 4 bytes with end of options list markers.
+fn options(
+    params: &PacketParams,
+    option: impl Strategy<Value = Vec<u8>> + Send + Sync + 'static,
+) -> SBoxedStrategy<Vec<u8>> {
+    if !params.options {
+        return Just(Vec::new()).sboxed();
+    }
+    vec(option, 0..6)
+        .prop_map(|options| {
+            let mut bytes = Vec::new();
+            for option in options {
+                if bytes.len() + option.len() <= MAX_OPTIONS_LEN {
+                    bytes.extend(option);
+                }
+            }
+            bytes.resize(bytes.len().next_multiple_of(4), 0);
+            bytes
+        })
+        .sboxed()
+}
+
+/// Generates the options of an IPv4 header: no-operations, and options of
+/// any other type with arbitrary data.
+fn ipv4_options(params: &PacketParams) -> SBoxedStrategy<Vec<u8>> {
+    options(
+        params,
+        prop_oneof![
+            Just(vec![1]),
+            (2..=u8::MAX, vec(any::<u8>(), 0..6)).prop_map(|(kind, data)| {
+                let mut option = vec![kind, data.len() as u8 + 2];
+                option.extend(data);
+                option
+            }),
+        ],
+    )
+}
+
+/// Generates the options of a TCP header: no-operations, maximum segment
+/// sizes, window scales, selective acknowledgment permitted and
+/// timestamps.
+fn tcp_options(params: &PacketParams) -> SBoxedStrategy<Vec<u8>> {
+    options(
+        params,
+        prop_oneof![
+            Just(vec![1]),
+            any::<u16>().prop_map(|mss| {
+                let [high, low] = mss.to_be_bytes();
+                vec![2, 4, high, low]
+            }),
+            (0..=14u8).prop_map(|shift| vec![3, 3, shift]),
+            Just(vec![4, 2]),
+            any::<(u32, u32)>().prop_map(|(value, echo)| {
+                let mut option = vec![8, 10];
+                option.extend(value.to_be_bytes());
+                option.extend(echo.to_be_bytes());
+                option
+            }),
+        ],
+    )
+}
+
+/// The IPv4 header fields generated independently of the payload.
+#[derive(Clone, Debug)]
+struct Ipv4Fields {
+    tos: u8,
+    identification: u16,
+    flags_and_offset: u16,
+    ttl: u8,
+    source: Ipv4Addr,
+    destination: Ipv4Addr,
+    options: Vec<u8>,
+}
+
+/// Generates IPv4 header fields. Unless `fragments` is true, packets are not
+/// fragments.
+fn ipv4_fields(
+    params: &PacketParams,
+    fragments: bool,
+) -> impl Strategy<Value = Ipv4Fields> {
+    let flags_and_offset = if fragments {
+        // Any flags other than the reserved one, and any offset
+        (0..0x8000u16).sboxed()
+    } else {
+        prop_oneof![Just(0), Just(0x4000)].sboxed()
+    };
+    (
+        any::<(u8, u16, u8)>(),
+        flags_and_offset,
+        any::<(Ipv4Addr, Ipv4Addr)>(),
+        ipv4_options(params),
+    )
+        .prop_map(
+            |(
+                (tos, identification, ttl),
+                flags_and_offset,
+                (source, destination),
+                options,
+            )| Ipv4Fields {
+                tos,
+                identification,
+                flags_and_offset,
+                ttl,
+                source,
+                destination,
+                options,
+            },
+        )
+}
+
+/// Generates which checksums to corrupt, if any, and by how much. The first
+/// offset applies to the IPv4 header and the second to the TCP or UDP
+/// header; at least one is present if `params` asks for corruption.
+fn corruption(
+    params: &PacketParams,
+) -> SBoxedStrategy<(Option<u16>, Option<u16>)> {
+    if !params.corrupt_checksums {
+        return Just((None, None)).sboxed();
+    }
+    const OFFSETS: Range<u16> = 1..0xffff;
+    prop_oneof![
+        (OFFSETS, option::of(OFFSETS))
+            .prop_map(|(ip, transport)| (Some(ip), transport)),
+        OFFSETS.prop_map(|transport| (None, Some(transport))),
+    ]
+    .sboxed()
+}
+
+/// Builds a packet from `fields` and `payload`, filling in the lengths and
+/// the header checksum.
+fn ipv4_packet(
+    fields: Ipv4Fields,
+    protocol: u8,
+    payload: &[u8],
+    corrupt_checksum: Option<u16>,
+) -> Ipv4Packet {
+    let header_len = 20 + fields.options.len();
+    let total_len = (header_len + payload.len()) as u16;
+    let mut bytes = Vec::with_capacity(usize::from(total_len));
+    bytes.push(0x40 | (header_len / 4) as u8);
+    bytes.push(fields.tos);
+    bytes.extend(total_len.to_be_bytes());
+    bytes.extend(fields.identification.to_be_bytes());
+    bytes.extend(fields.flags_and_offset.to_be_bytes());
+    bytes.push(fields.ttl);
+    bytes.push(protocol);
+    bytes.extend([0, 0]);
+    bytes.extend(fields.source.octets());
+    bytes.extend(fields.destination.octets());
+    bytes.extend(fields.options);
+
+    let mut checksum = !sum(&bytes, 0);
+    if let Some(offset) = corrupt_checksum {
+        checksum = corrupt(checksum, offset);
+    }
+    bytes[10..12].copy_from_slice(&checksum.to_be_bytes());
+
+    bytes.extend(payload);
+    Ipv4Packet { bytes }
+}
+
+/// Fills in the transport checksum at `offset` in the payload of `packet`.
+fn fill_transport_checksum(
+    mut packet: Ipv4Packet,
+    offset: usize,
+    corrupt_checksum: Option<u16>,
+) -> Ipv4Packet {
+    let mut checksum = !sum(packet.payload(), packet.pseudo_header_sum());
+    if checksum == 0 {
+        // Zero would mean no checksum in UDP
+        checksum = 0xffff;
+    }
+    if let Some(corruption) = corrupt_checksum {
+        checksum = corrupt(checksum, corruption);
+    }
+    let start = packet.header_len() + offset;
+    packet.bytes[start..start + 2].copy_from_slice(&checksum.to_be_bytes());
+    packet
+}
+
+/// Creates a strategy which generates IPv4 packets carrying TCP segments.
+///
+/// Segments have arbitrary ports, sequence numbers, flags, window sizes and
+/// urgent pointers, and options made of no-operations, maximum segment
+/// sizes, window scales, selective acknowledgment permitted and timestamps.
+/// Packets are never fragments.
+pub fn tcp(params: PacketParams) -> PacketStrategy {
+    let segment = (
+        any::<(u16, u16, u32, u32)>(),
+        any::<(u8, u16, u16)>(),
+        tcp_options(&params),
+        vec(any::<u8>(), params.payload_len.clone()),
+    );
+    PacketStrategy(
+        (ipv4_fields(&params, false), segment, corruption(&params))
+            .prop_map(
+                |(
+                    fields,
+                    (
+                        (source_port, destination_port, seq, ack),
+                        (flags, window, urgent),
+                        options,
+                        payload,
+                    ),
+                    (corrupt_ip, corrupt_tcp),
+                )| {
+                    let header_len = 20 + options.len();
+                    let mut segment = Vec::new();
+                    segment.extend(source_port.to_be_bytes());
+                    segment.extend(destination_port.to_be_bytes());
+                    segment.extend(seq.to_be_bytes());
+                    segment.extend(ack.to_be_bytes());
+                    segment.push(((header_len / 4) as u8) << 4);
+                    segment.push(flags);
+                    segment.extend(window.to_be_bytes());
+                    segment.extend([0, 0]);
+                    segment.extend(urgent.to_be_bytes());
+                    segment.extend(options);
+                    segment.extend(payload);
+                    let packet = ipv4_packet(fields, TCP, &segment, corrupt_ip);
+                    fill_transport_checksum(packet, 16, corrupt_tcp)
+                },
+            )
+            .sboxed(),
+    )
+}
+
+/// Creates a strategy which generates IPv4 packets carrying UDP datagrams.
+///
+/// Datagrams have arbitrary ports, and occasionally no checksum. Packets
+/// are never fragments.
+pub fn udp(params: PacketParams) -> PacketStrategy {
+    let datagram = (
+        any::<(u16, u16)>(),
+        vec(any::<u8>(), params.payload_len.clone()),
+        // Whether to leave out the checksum
+        prop_oneof![4 => Just(false), 1 => Just(true)],
+    );
+    PacketStrategy(
+        (ipv4_fields(&params, false), datagram, corruption(&params))
+            .prop_map(
+                |(
+                    fields,
+                    ((source_port, destination_port), payload, no_checksum),
+                    (corrupt_ip, corrupt_udp),
+                )| {
+                    let mut datagram = Vec::new();
+                    datagram.extend(source_port.to_be_bytes());
+                    datagram.extend(destination_port.to_be_bytes());
+                    datagram.extend((8 + payload.len() as u16).to_be_bytes());
+                    datagram.extend([0, 0]);
+                    datagram.extend(payload);
+                    let packet =
+                        ipv4_packet(fields, UDP, &datagram, corrupt_ip);
+                    if no_checksum && corrupt_udp.is_none() {
+                        packet
+                    } else {
+                        fill_transport_checksum(packet, 6, corrupt_udp)
+                    }
+                },
+            )
+            .sboxed(),
+    )
+}
+
+/// Creates a strategy which generates IPv4 packets carrying TCP segments,
+/// UDP datagrams, or arbitrary payloads of other protocols.
+///
+/// Packets with other protocols may be fragments, with any offset.
+pub fn ipv4(params: PacketParams) -> PacketStrategy {
+    let other = (
+        ipv4_fields(&params, true),
+        any::<u8>().prop_filter("TCP and UDP have their own strategies", |p| {
+            *p != TCP && *p != UDP
+        }),
+        vec(any::<u8>(), params.payload_len.clone()),
+        corruption(&params),
+    )
+        .prop_map(|(fields, protocol, payload, corruption)| {
+            // There is only the header checksum to corrupt
+            let corrupt_ip = corruption.0.or(corruption.1);
+            ipv4_packet(fields, protocol, &payload, corrupt_ip)
+        });
+    PacketStrategy(
+        prop_oneof![tcp(params.clone()), udp(params), other].sboxed(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample(input: PacketStrategy) -> Vec<Ipv4Packet> {
+        let mut runner = TestRunner::deterministic();
+        (0..256)
+            .map(|_| input.new_tree(&mut runner).unwrap().current())
+            .collect()
+    }
+
+    fn check_structure(packet: &Ipv4Packet) {
+        assert_eq!(4, packet.as_bytes()[0] >> 4);
+        assert!(packet.header_len() >= 20);
+        assert_eq!(usize::from(packet.total_len()), packet.as_bytes().len());
+        if let Some(tcp) = packet.tcp() {
+            assert!(tcp.header_len() >= 20);
+            assert!(tcp.header_len() <= tcp.as_bytes().len());
+        }
+        if let Some(udp) = packet.udp() {
+            assert_eq!(usize::from(udp.len()), udp.as_bytes().len());
+        }
+    }
+
+    fn transport_checksum_is_valid(packet: &Ipv4Packet) -> bool {
+        match (packet.tcp(), packet.udp()) {
+            (Some(tcp), _) => tcp.checksum_is_valid(),
+            (_, Some(udp)) => udp.checksum_is_valid(),
+            _ => true,
+        }
+    }
+
+    #[test]
+    fn validates_known_checksum() {
+        let header = [
+            0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0xb8,
+            0x61, 0xc0, 0xa8, 0x00, 0x01, 0xc0, 0xa8, 0x00, 0xc7,
+        ];
+        let mut bytes = header.to_vec();
+        bytes.resize(0x73, 0);
+        let packet = Ipv4Packet { bytes };
+        assert!(packet.checksum_is_valid());
+        assert_eq!(Ipv4Addr::new(192, 168, 0, 199), packet.destination());
+        assert!(packet.dont_fragment());
+    }
+
+    #[test]
+    fn corrupted_checksums_are_wrong() {
+        for checksum in [0, 1, 0x1234, 0xfffe, 0xffff] {
+            for offset in [1, 2, 0x8000, 0xfffe] {
+                let wrong = corrupt(checksum, offset);
+                assert_ne!(0, wrong);
+                assert_ne!(checksum % 0xffff, wrong % 0xffff);
+            }
+        }
+    }
+
+    #[test]
+    fn generates_valid_packets() {
+        let packets = sample(ipv4(PacketParams::default()));
+        for packet in &packets {
+            check_structure(packet);
+            assert!(packet.checksum_is_valid(), "{:?}", packet);
+            assert!(transport_checksum_is_valid(packet), "{:?}", packet);
+        }
+        assert!(packets.iter().any(|packet| packet.tcp().is_some()));
+        assert!(packets.iter().any(|packet| packet.udp().is_some()));
+        assert!(packets
+            .iter()
+            .any(|packet| packet.tcp().is_none() && packet.udp().is_none()));
+        assert!(packets.iter().any(|packet| !packet.options().is_empty()));
+        assert!(packets.iter().any(|packet| packet
+            .tcp()
+            .is_some_and(|tcp| tcp.header_len() > 20)));
+    }
+
+    #[test]
+    fn generates_requested_payload_lengths() {
+        let params = PacketParams {
+            payload_len: (3..5).into(),
+            options: false,
+            ..PacketParams::default()
+        };
+        for packet in sample(tcp(params.clone())) {
+            let tcp = packet.tcp().unwrap();
+            assert_eq!(20, packet.header_len());
+            assert_eq!(20, tcp.header_len());
+            assert!((3..5).contains(&tcp.payload().len()));
+        }
+        for packet in sample(udp(params)) {
+            let udp = packet.udp().unwrap();
+            assert!((3..5).contains(&udp.payload().len()));
+        }
+    }
+
+    #[test]
+    fn corrupts_checksums_on_demand() {
+        let params = PacketParams {
+            corrupt_checksums: true,
+            ..PacketParams::default()
+        };
+        for packet in sample(ipv4(params)) {
+            check_structure(&packet);
+            assert!(
+                !packet.checksum_is_valid()
+                    || !transport_checksum_is_valid(&packet),
+                "{:?}",
+                packet
+            );
+        }
+    }
+
+    #[test]
+    fn shrinks_to_minimal_packet() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&udp(PacketParams::default()), |_| {
+            Err(TestCaseError::fail("minimize"))
+        });
+        match result {
+            Err(TestError::Fail(_, packet)) => {
+                assert_eq!(28, packet.as_bytes().len());
+                assert!(packet.checksum_is_valid());
+                assert!(packet.udp().unwrap().checksum_is_valid());
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
@@ -10,13 +10,33 @@
 //! Strategies to generate numeric values (as opposed to integers used as bit
 //! fields).
 //!
-//! All strategies in this module shrink by binary searching towards 0.
-
+//! All strategies in this module shrink by binary searching towards 0, except
+//! for `stochastic_vec()`, which shrinks towards the uniform distribution, the
+//! distribution-shaped strategies such as `f64::normal()` and `u64::zipf()`,
+//! which shrink towards the mode of their distribution, and the `NON_ZERO`
+//! strategies, which shrink towards 1 or -1.
+
+#[macro_use]
+mod distribution;
+#[macro_use]
+mod edge;
+#[macro_use]
+mod float_builder;
 mod float_samplers;
+mod step_range;
+mod stochastic;
+#[macro_use]
+mod wrappers;
+
+pub use self::edge::{EdgeBias, EdgeCases};
+pub use self::step_range::*;
+pub use self::stochastic::*;
+pub use self::wrappers::*;
 
 use crate::test_runner::TestRunner;
 use rand::distr::uniform::{SampleUniform, Uniform};
 use rand::distr::{Distribution, StandardUniform};
+use rand::Rng;
 
 /// Generate a random value of `X`, sampled uniformly from the half
 /// open range `[low, high)` (excluding `high`). Panics if `low >= high`.
@@ -72,6 +92,72 @@ sample_uniform!(isize_sample_uniform, isize_sample_uniform_incl, i32, isize);
 #[cfg(target_pointer_width = "16")]
 sample_uniform!(isize_sample_uniform, isize_sample_uniform_incl, i16, isize);
 
+/// Multiply `a` by `b`, returning the high and low halves of the 256-bit
+/// product.
+fn wmul_u128(a: u128, b: u128) -> (u128, u128) {
+    const LOWER: u128 = u64::MAX as u128;
+
+    let (a_lo, a_hi) = (a & LOWER, a >> 64);
+    let (b_lo, b_hi) = (b & LOWER, b >> 64);
+    let low = a_lo * b_lo;
+    let cross_1 = a_lo * b_hi;
+    let cross_2 = a_hi * b_lo;
+    let mid = (low >> 64) + (cross_1 & LOWER) + (cross_2 & LOWER);
+
+    (
+        a_hi * b_hi + (cross_1 >> 64) + (cross_2 >> 64) + (mid >> 64),
+        (low & LOWER) | (mid << 64),
+    )
+}
+
+/// Generate a random `u128` sampled uniformly from `[0, range)`, or from all
+/// `u128` values if `range` is 0.
+///
+/// This uses Lemire's widening multiply method, which only needs to divide
+/// in the rare case that a sample might have to be rejected. 128-bit division
+/// is slow enough that this is considerably faster than `sample_uniform()`
+/// for `u128` and `i128`, which use it internally.
+pub fn sample_u128_below(run: &mut TestRunner, range: u128) -> u128 {
+    let rng = run.rng();
+    if 0 == range {
+        return rng.random();
+    }
+
+    let (mut hi, mut lo) = wmul_u128(rng.random(), range);
+    if lo < range {
+        // Products whose low half is below this would make the values of
+        // `hi` slightly uneven.
+        let threshold = range.wrapping_neg() % range;
+        while lo < threshold {
+            (hi, lo) = wmul_u128(rng.random(), range);
+        }
+    }
+    hi
+}
+
+macro_rules! sample_uniform_128 {
+    ($name: ident, $incl:ident, $typ:ty) => {
+        // `X` matches the signature `numeric_api!` expects.
+        #[allow(clippy::extra_unused_type_parameters)]
+        fn $name<X>(run: &mut TestRunner, start: $typ, end: $typ) -> $typ {
+            assert!(start < end, "not uniform");
+            let range = (end as u128).wrapping_sub(start as u128);
+            start.wrapping_add(sample_u128_below(run, range) as $typ)
+        }
+
+        #[allow(clippy::extra_unused_type_parameters)]
+        fn $incl<X>(run: &mut TestRunner, start: $typ, end: $typ) -> $typ {
+            assert!(start <= end, "not uniform");
+            let range =
+                (end as u128).wrapping_sub(start as u128).wrapping_add(1);
+            start.wrapping_add(sample_u128_below(run, range) as $typ)
+        }
+    };
+}
+
+sample_uniform_128!(u128_sample_uniform, u128_sample_uniform_incl, u128);
+sample_uniform_128!(i128_sample_uniform, i128_sample_uniform_incl, i128);
+
 macro_rules! supported_int_any {
     ($runner:ident, $typ:ty) => {
         $runner.rng().random()
@@ -245,6 +331,8 @@ macro_rules! signed_integer_bin_search {
             use crate::test_runner::TestRunner;
 
             int_any!($typ, $int_any);
+            int_edge_cases!($typ);
+            int_non_zero!($typ, $incl, signed);
 
             /// Shrinks an integer towards 0, using binary search to find
             /// boundary points.
@@ -338,10 +426,10 @@ macro_rules! signed_integer_bin_search {
 }
 
 macro_rules! unsigned_integer_bin_search {
-    ($typ:ident) => {
-        unsigned_integer_bin_search!($typ, supported_int_any, sample_uniform, sample_uniform_incl);
+    ($typ:ident $(, $dist:ident)?) => {
+        unsigned_integer_bin_search!($typ, supported_int_any, sample_uniform, sample_uniform_incl $(, $dist)?);
     };
-    ($typ:ident, $int_any: ident, $uniform: ident, $incl: ident) => {
+    ($typ:ident, $int_any: ident, $uniform: ident, $incl: ident $(, $dist:ident)?) => {
         #[allow(missing_docs)]
         pub mod $typ {
             #[allow(unused_imports)]
@@ -351,6 +439,9 @@ macro_rules! unsigned_integer_bin_search {
             use crate::test_runner::TestRunner;
 
             int_any!($typ, $int_any);
+            int_edge_cases!($typ);
+            int_non_zero!($typ, $incl, unsigned);
+            $($dist!($typ);)?
 
             /// Shrinks an integer towards 0, using binary search to find
             /// boundary points.
@@ -433,14 +524,14 @@ signed_integer_bin_search!(i8);
 signed_integer_bin_search!(i16);
 signed_integer_bin_search!(i32);
 signed_integer_bin_search!(i64);
-signed_integer_bin_search!(i128);
+signed_integer_bin_search!(i128, supported_int_any, i128_sample_uniform, i128_sample_uniform_incl);
 signed_integer_bin_search!(isize, unsupported_int_any, isize_sample_uniform, isize_sample_uniform_incl);
 unsigned_integer_bin_search!(u8);
 unsigned_integer_bin_search!(u16);
-unsigned_integer_bin_search!(u32);
-unsigned_integer_bin_search!(u64);
-unsigned_integer_bin_search!(u128);
-unsigned_integer_bin_search!(usize, unsupported_int_any, usize_sample_uniform, usize_sample_uniform_incl);
+unsigned_integer_bin_search!(u32, zipf_distribution);
+unsigned_integer_bin_search!(u64, zipf_distribution);
+unsigned_integer_bin_search!(u128, supported_int_any, u128_sample_uniform, u128_sample_uniform_incl);
+unsigned_integer_bin_search!(usize, unsupported_int_any, usize_sample_uniform, usize_sample_uniform_incl, zipf_distribution);
 
 bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -775,7 +866,7 @@ macro_rules! float_any {
 }
 
 macro_rules! float_bin_search {
-    ($typ:ident, $sample_typ:ident) => {
+    ($typ:ident, $sample_typ:ident $(, $ext:ident)*) => {
         #[allow(missing_docs)]
         pub mod $typ {
             use super::float_samplers::$sample_typ;
@@ -791,6 +882,8 @@ macro_rules! float_bin_search {
             use crate::test_runner::TestRunner;
 
             float_any!($typ);
+            float_edge_cases!($typ);
+            $($ext!($typ);)*
 
             /// Shrinks a float towards 0, using binary search to find boundary
             /// points.
@@ -815,7 +908,10 @@ macro_rules! float_bin_search {
                     }
                 }
 
-                fn new_with_types(start: $typ, allowed: FloatTypes) -> Self {
+                pub(super) fn new_with_types(
+                    start: $typ,
+                    allowed: FloatTypes,
+                ) -> Self {
                     BinarySearch {
                         lo: 0.0,
                         curr: start,
@@ -827,7 +923,11 @@ macro_rules! float_bin_search {
                 /// Creates a new binary searcher which will not produce values
                 /// on the other side of `lo` or `hi` from `start`. `lo` is
                 /// inclusive, `hi` is exclusive.
-                fn new_clamped(lo: $typ, start: $typ, hi: $typ) -> Self {
+                pub(super) fn new_clamped(
+                    lo: $typ,
+                    start: $typ,
+                    hi: $typ,
+                ) -> Self {
                     BinarySearch {
                         lo: if start.is_sign_negative() {
                             hi.min(0.0)
@@ -962,8 +1062,24 @@ macro_rules! float_bin_search {
 
 #[cfg(feature = "f16")]
 float_bin_search!(f16, F16U);
-float_bin_search!(f32, F32U);
-float_bin_search!(f64, F64U);
+float_bin_search!(f32, F32U, float_distributions, float_builder);
+float_bin_search!(f64, F64U, float_distributions, float_builder);
+
+#[cfg(feature = "num-bigint")]
+#[cfg_attr(docsrs, doc(cfg(feature = "num-bigint")))]
+pub mod bigint;
+#[cfg(feature = "num-complex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "num-complex")))]
+pub mod complex;
+#[cfg(feature = "rust_decimal")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rust_decimal")))]
+pub mod decimal;
+#[cfg(feature = "fixed")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fixed")))]
+pub mod fixed;
+#[cfg(feature = "half")]
+#[cfg_attr(docsrs, doc(cfg(feature = "half")))]
+pub mod half;
 
 #[cfg(test)]
 mod test {
@@ -972,6 +1088,60 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn wmul_u128_matches_known_products() {
+        assert_eq!((0, 6), wmul_u128(2, 3));
+        assert_eq!((1, 0), wmul_u128(1 << 64, 1 << 64));
+        assert_eq!((u128::MAX - 1, 1), wmul_u128(u128::MAX, u128::MAX));
+        let a = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+        assert_eq!((0, a), wmul_u128(a, 1));
+        assert_eq!((a >> 1, a << 127), wmul_u128(a, 1 << 127));
+    }
+
+    #[test]
+    fn sample_u128_below_is_uniform() {
+        let mut runner = TestRunner::deterministic();
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            counts[sample_u128_below(&mut runner, 6) as usize] += 1;
+        }
+        assert!(
+            counts.iter().all(|&c| (850..1150).contains(&c)),
+            "{:?}",
+            counts
+        );
+
+        let range = (1u128 << 127) + 12345;
+        let mut high = 0;
+        for _ in 0..1000 {
+            let x = sample_u128_below(&mut runner, range);
+            assert!(x < range);
+            if x >= 1 << 126 {
+                high += 1;
+            }
+        }
+        assert!((400..600).contains(&high), "{}", high);
+    }
+
+    #[test]
+    fn int128_ranges_respected() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..1000 {
+            let x = (-5i128..5).new_tree(&mut runner).unwrap().current();
+            assert!((-5..5).contains(&x));
+            let x = (i128::MIN..=i128::MIN + 1)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!(x <= i128::MIN + 1);
+            let x = (u128::MAX - 3..).new_tree(&mut runner).unwrap().current();
+            assert!(x >= u128::MAX - 3);
+        }
+        // The whole of the type is a range of 2^128 values.
+        let _ = (i128::MIN..=i128::MAX).new_tree(&mut runner).unwrap();
+        let _ = (0..=u128::MAX).new_tree(&mut runner).unwrap();
+    }
+
     #[test]
     fn u8_inclusive_end_included() {
         let mut runner = TestRunner::deterministic();
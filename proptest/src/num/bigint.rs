@@ -0,0 +1,491 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for the arbitrary-precision `BigInt` and `BigUint` of the
+//! `num-bigint` crate.
+//!
+//! Ranges of either type are strategies, sampling uniformly however wide the
+//! range is. [`bigints`] and [`biguints`] instead generate values of up to a
+//! given number of bits, picking the bit length uniformly first so that
+//! small values are as likely as large ones. Every strategy shrinks toward
+//! zero, or toward the bound of the range closest to it.
+//!
+//! `BigInt` and `BigUint` also implement `Arbitrary`, taking a [`BitLength`]
+//! as parameters.
+//!
+//! ```rust
+//! use num_bigint::BigInt;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn addition_is_commutative(
+//!         a in any::<BigInt>(),
+//!         b in BigInt::from(-1) << 512u32..BigInt::from(1) << 512u32,
+//!     ) {
+//!         prop_assert_eq!(&a + &b, &b + &a);
+//!     }
+//! }
+//! # fn main() { addition_is_commutative(); }
+//! ```
+
+use core::ops::{Range, RangeInclusive};
+
+use ::num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{One, Signed, Zero};
+use rand::{Rng, RngCore};
+
+use crate::arbitrary::Arbitrary;
+use crate::std_facade::Vec;
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// The maximum number of bits in the magnitude of values generated by
+/// `any::<BigInt>()` or `any::<BigUint>()`.
+///
+/// Defaults to 256 bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BitLength(u64);
+
+impl BitLength {
+    /// Allow magnitudes of up to `bits` bits.
+    pub fn new(bits: u64) -> Self {
+        BitLength(bits)
+    }
+
+    /// Return the maximum number of bits.
+    pub fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for BitLength {
+    fn default() -> Self {
+        BitLength(256)
+    }
+}
+
+impl From<u64> for BitLength {
+    fn from(bits: u64) -> Self {
+        BitLength(bits)
+    }
+}
+
+/// Generate a value uniformly from `[0, max]`.
+fn sample_up_to(runner: &mut TestRunner, max: &BigUint) -> BigUint {
+    let bits = max.bits();
+    let digits = bits.div_ceil(32) as usize;
+    // Rejecting values above `max` discards fewer than half of the candidates
+    // on average, since they have no more bits than it.
+    loop {
+        let mut candidate = (0..digits)
+            .map(|_| runner.rng().next_u32())
+            .collect::<Vec<_>>();
+        if let Some(top) = candidate.last_mut() {
+            *top >>= digits as u64 * 32 - bits;
+        }
+        let candidate = BigUint::new(candidate);
+        if candidate <= *max {
+            return candidate;
+        }
+    }
+}
+
+/// Generate a magnitude with a bit length chosen uniformly from `0..=bits`.
+fn sample_bits(runner: &mut TestRunner, bits: u64) -> BigUint {
+    let len = runner.rng().random_range(0..=bits);
+    sample_up_to(runner, &((BigUint::one() << len) - 1u32))
+}
+
+/// Shrinks a `BigInt` towards 0, using binary search to find boundary
+/// points.
+#[derive(Clone, Debug)]
+pub struct BigIntValueTree {
+    lo: BigInt,
+    curr: BigInt,
+    hi: BigInt,
+}
+
+impl BigIntValueTree {
+    /// Creates a new binary searcher starting at the given value.
+    pub fn new(start: BigInt) -> Self {
+        BigIntValueTree {
+            lo: BigInt::zero(),
+            curr: start.clone(),
+            hi: start,
+        }
+    }
+
+    /// Creates a binary searcher starting at a value uniformly sampled from
+    /// `[lo, hi]`, which will not produce values outside of that range.
+    fn sample(runner: &mut TestRunner, lo: &BigInt, hi: &BigInt) -> Self {
+        let span = (hi - lo).into_parts().1;
+        let start = lo + BigInt::from(sample_up_to(runner, &span));
+        let target = if lo.is_positive() {
+            lo.clone()
+        } else if hi.is_negative() {
+            hi.clone()
+        } else {
+            BigInt::zero()
+        };
+        BigIntValueTree {
+            lo: target,
+            curr: start.clone(),
+            hi: start,
+        }
+    }
+
+    fn reposition(&mut self) -> bool {
+        let new_mid = &self.lo + (&self.hi - &self.lo) / 2;
+
+        if new_mid == self.curr {
+            false
+        } else {
+            self.curr = new_mid;
+            true
+        }
+    }
+
+    fn magnitude_greater(lhs: &BigInt, rhs: &BigInt) -> bool {
+        if lhs.is_zero() {
+            false
+        } else if lhs.is_negative() {
+            lhs < rhs
+        } else {
+            lhs > rhs
+        }
+    }
+}
+
+impl ValueTree for BigIntValueTree {
+    type Value = BigInt;
+
+    fn current(&self) -> BigInt {
+        self.curr.clone()
+    }
+
+    fn simplify(&mut self) -> bool {
+        if !Self::magnitude_greater(&self.hi, &self.lo) {
+            return false;
+        }
+
+        self.hi = self.curr.clone();
+        self.reposition()
+    }
+
+    fn complicate(&mut self) -> bool {
+        if !Self::magnitude_greater(&self.hi, &self.lo) {
+            return false;
+        }
+
+        self.lo = if self.hi.is_negative() {
+            &self.curr - 1
+        } else {
+            &self.curr + 1
+        };
+        self.reposition()
+    }
+}
+
+/// Shrinks a `BigUint` towards 0, using binary search to find boundary
+/// points.
+#[derive(Clone, Debug)]
+pub struct BigUintValueTree(BigIntValueTree);
+
+impl BigUintValueTree {
+    /// Creates a new binary searcher starting at the given value.
+    pub fn new(start: BigUint) -> Self {
+        BigUintValueTree(BigIntValueTree::new(start.into()))
+    }
+}
+
+impl ValueTree for BigUintValueTree {
+    type Value = BigUint;
+
+    fn current(&self) -> BigUint {
+        self.0.curr.magnitude().clone()
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.0.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.0.complicate()
+    }
+}
+
+/// Strategy to generate `BigInt`s whose magnitude has up to a certain number
+/// of bits.
+///
+/// Created by the `bigints()` function in the same module.
+#[derive(Clone, Copy, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct BigIntStrategy {
+    bits: u64,
+}
+
+/// Create a strategy to generate `BigInt`s of either sign whose magnitude
+/// has up to `bits` bits.
+///
+/// The bit length is chosen uniformly before the value itself, so values of
+/// every size are generated equally often.
+pub fn bigints(bits: u64) -> BigIntStrategy {
+    BigIntStrategy { bits }
+}
+
+impl Strategy for BigIntStrategy {
+    type Tree = BigIntValueTree;
+    type Value = BigInt;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let sign = if runner.rng().random() {
+            Sign::Minus
+        } else {
+            Sign::Plus
+        };
+        let magnitude = sample_bits(runner, self.bits);
+        Ok(BigIntValueTree::new(BigInt::from_biguint(sign, magnitude)))
+    }
+}
+
+/// Strategy to generate `BigUint`s with up to a certain number of bits.
+///
+/// Created by the `biguints()` function in the same module.
+#[derive(Clone, Copy, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct BigUintStrategy {
+    bits: u64,
+}
+
+/// Create a strategy to generate `BigUint`s with up to `bits` bits.
+///
+/// The bit length is chosen uniformly before the value itself, so values of
+/// every size are generated equally often.
+pub fn biguints(bits: u64) -> BigUintStrategy {
+    BigUintStrategy { bits }
+}
+
+impl Strategy for BigUintStrategy {
+    type Tree = BigUintValueTree;
+    type Value = BigUint;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(BigUintValueTree::new(sample_bits(runner, self.bits)))
+    }
+}
+
+impl Strategy for Range<BigInt> {
+    type Tree = BigIntValueTree;
+    type Value = BigInt;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        if self.is_empty() {
+            panic!("Invalid use of empty range {}..{}.", self.start, self.end);
+        }
+
+        Ok(BigIntValueTree::sample(
+            runner,
+            &self.start,
+            &(&self.end - 1),
+        ))
+    }
+}
+
+impl Strategy for RangeInclusive<BigInt> {
+    type Tree = BigIntValueTree;
+    type Value = BigInt;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        if self.is_empty() {
+            panic!(
+                "Invalid use of empty range {}..={}.",
+                self.start(),
+                self.end()
+            );
+        }
+
+        Ok(BigIntValueTree::sample(runner, self.start(), self.end()))
+    }
+}
+
+impl Strategy for Range<BigUint> {
+    type Tree = BigUintValueTree;
+    type Value = BigUint;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        if self.is_empty() {
+            panic!("Invalid use of empty range {}..{}.", self.start, self.end);
+        }
+
+        Ok(BigUintValueTree(BigIntValueTree::sample(
+            runner,
+            &self.start.clone().into(),
+            &(BigInt::from(self.end.clone()) - 1),
+        )))
+    }
+}
+
+impl Strategy for RangeInclusive<BigUint> {
+    type Tree = BigUintValueTree;
+    type Value = BigUint;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        if self.is_empty() {
+            panic!(
+                "Invalid use of empty range {}..={}.",
+                self.start(),
+                self.end()
+            );
+        }
+
+        Ok(BigUintValueTree(BigIntValueTree::sample(
+            runner,
+            &self.start().clone().into(),
+            &self.end().clone().into(),
+        )))
+    }
+}
+
+impl Arbitrary for BigInt {
+    type Parameters = BitLength;
+    type Strategy = BigIntStrategy;
+
+    fn arbitrary_with(bits: BitLength) -> BigIntStrategy {
+        bigints(bits.get())
+    }
+}
+
+impl Arbitrary for BigUint {
+    type Parameters = BitLength;
+    type Strategy = BigUintStrategy;
+
+    fn arbitrary_with(bits: BitLength) -> BigUintStrategy {
+        biguints(bits.get())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arbitrary::{any, any_with};
+
+    fn minimal<S: Strategy>(
+        strategy: S,
+        fails: impl Fn(&S::Value) -> bool,
+    ) -> S::Value {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&strategy, |v| {
+            prop_assert!(!fails(&v));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, v)) => v,
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn ranges_are_respected() {
+        let mut runner = TestRunner::deterministic();
+        let lo = -(BigInt::one() << 200u32);
+        let hi = BigInt::one() << 300u32;
+        let unsigned = BigUint::one() << 70u32..=BigUint::one() << 80u32;
+        for _ in 0..256 {
+            let v = (lo.clone()..hi.clone())
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!(lo <= v && v < hi, "{}", v);
+
+            let v = (lo.clone()..=lo.clone())
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert_eq!(lo, v);
+
+            let v = unsigned.new_tree(&mut runner).unwrap().current();
+            assert!(unsigned.contains(&v), "{}", v);
+        }
+    }
+
+    #[test]
+    fn wide_ranges_are_sampled_uniformly() {
+        let mut runner = TestRunner::deterministic();
+        let third = BigUint::one() << 100u32;
+        let range = BigUint::zero()..&third * 3u32;
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            let v = range.new_tree(&mut runner).unwrap().current();
+            counts[(v / &third).to_u32_digits().first().copied().unwrap_or(0)
+                as usize] += 1;
+        }
+        for count in counts {
+            assert!((850..1150).contains(&count), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn bit_lengths_are_respected() {
+        let mut runner = TestRunner::deterministic();
+        let mut lengths = [false; 65];
+        for _ in 0..1024 {
+            let v = any_with::<BigInt>(BitLength::new(64))
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            lengths[v.bits() as usize] = true;
+
+            let v = biguints(8).new_tree(&mut runner).unwrap().current();
+            assert!(v.bits() <= 8, "{}", v);
+        }
+        assert!(lengths.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn shrinks_toward_zero() {
+        let threshold = BigInt::from(10).pow(30);
+        assert_eq!(
+            &threshold + 1,
+            minimal(any::<BigInt>(), |v| *v > threshold)
+        );
+        assert_eq!(
+            -&threshold - 1,
+            minimal(any::<BigInt>(), |v| *v < -&threshold)
+        );
+        assert_eq!(
+            BigUint::from(1000u32),
+            minimal(any::<BigUint>(), |v| *v >= BigUint::from(1000u32))
+        );
+    }
+
+    #[test]
+    fn shrinks_toward_bound_nearest_zero() {
+        let hi = -(BigInt::one() << 100u32);
+        let lo = &hi * 2u32;
+        assert_eq!(hi, minimal(lo.clone()..=hi.clone(), |_| true));
+        assert_eq!(-&hi, minimal(-&hi..-&lo, |_| true));
+        let start = BigUint::one() << 90u32;
+        assert_eq!(start, minimal(start.clone()..&start * 2u32, |_| true));
+    }
+
+    #[test]
+    fn sanity() {
+        // Few bits, since each check shrinks a thousand values.
+        check_strategy_sanity(any_with::<BigInt>(BitLength::new(40)), None);
+        check_strategy_sanity(biguints(40), None);
+        check_strategy_sanity(
+            -(BigInt::one() << 40u32)..BigInt::from(-5),
+            None,
+        );
+        check_strategy_sanity(
+            BigUint::from(3u32)..=BigUint::one() << 40u32,
+            None,
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for `Complex` numbers from the `num-complex` crate.
+//!
+//! A complex number can be described either by its real and imaginary parts,
+//! using `rectangular()`, or by its magnitude and phase, using `polar()`.
+//! Either way, each part is generated by its own strategy, usually a float
+//! range, and shrinks as that strategy does, so that values shrink toward
+//! zero as far as the strategies allow.
+//!
+//! `Complex` also implements `Arbitrary`, generating both parts with `any()`.
+
+use ::num_complex::Complex;
+use num_traits::float::Float;
+
+use crate::arbitrary::Arbitrary;
+use crate::strategy::*;
+use crate::test_runner::*;
+use crate::tuple::TupleValueTree;
+
+/// Strategy to generate complex numbers from their real and imaginary parts.
+///
+/// Created by the `rectangular()` function in the same module.
+#[derive(Clone, Copy, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct Rectangular<R, I> {
+    re: R,
+    im: I,
+}
+
+/// Create a strategy to generate complex numbers whose real part is
+/// generated by `re` and whose imaginary part is generated by `im`.
+///
+/// ```
+/// use proptest::prelude::*;
+/// use proptest::num::complex;
+///
+/// proptest! {
+///     fn conjugate_negates_imaginary_part(
+///         z in complex::rectangular(-10.0f64..10.0, -10.0f64..10.0)
+///     ) {
+///         prop_assert_eq!(-z.im, z.conj().im);
+///     }
+/// }
+/// # fn main() { conjugate_negates_imaginary_part(); }
+/// ```
+pub fn rectangular<R, I>(re: R, im: I) -> Rectangular<R, I>
+where
+    R: Strategy,
+    I: Strategy<Value = R::Value>,
+{
+    Rectangular { re, im }
+}
+
+/// `ValueTree` corresponding to `Rectangular`.
+#[derive(Clone, Copy, Debug)]
+pub struct RectangularValueTree<R, I> {
+    parts: TupleValueTree<(R, I)>,
+}
+
+impl<R, I> Strategy for Rectangular<R, I>
+where
+    R: Strategy,
+    I: Strategy<Value = R::Value>,
+{
+    type Tree = RectangularValueTree<R::Tree, I::Tree>;
+    type Value = Complex<R::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(RectangularValueTree {
+            parts: (&self.re, &self.im).new_tree(runner)?,
+        })
+    }
+}
+
+impl<R, I> ValueTree for RectangularValueTree<R, I>
+where
+    R: ValueTree,
+    I: ValueTree<Value = R::Value>,
+{
+    type Value = Complex<R::Value>;
+
+    fn current(&self) -> Complex<R::Value> {
+        let (re, im) = self.parts.current();
+        Complex::new(re, im)
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.parts.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.parts.complicate()
+    }
+}
+
+/// Strategy to generate complex numbers from their magnitude and phase.
+///
+/// Created by the `polar()` function in the same module.
+#[derive(Clone, Copy, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct Polar<M, P> {
+    magnitude: M,
+    phase: P,
+}
+
+/// Create a strategy to generate complex numbers whose magnitude is
+/// generated by `magnitude` and whose phase, in radians, is generated by
+/// `phase`.
+///
+/// `magnitude` should only generate non-negative values. The magnitude of
+/// the complex numbers generated is only approximately the one generated,
+/// since converting from polar form rounds.
+///
+/// Note that a magnitude sampled uniformly from a range does not make the
+/// values uniformly distributed over the corresponding disc or annulus, but
+/// concentrates them toward its centre.
+///
+/// ```
+/// use std::f64::consts::TAU;
+///
+/// use proptest::prelude::*;
+/// use proptest::num::complex;
+///
+/// proptest! {
+///     fn unit_disc_is_closed_under_multiplication(
+///         a in complex::polar(0.0f64..=1.0, 0.0..TAU),
+///         b in complex::polar(0.0f64..=1.0, 0.0..TAU),
+///     ) {
+///         prop_assert!((a * b).norm_sqr() <= 1.0 + 1e-9);
+///     }
+/// }
+/// # fn main() { unit_disc_is_closed_under_multiplication(); }
+/// ```
+pub fn polar<M, P>(magnitude: M, phase: P) -> Polar<M, P>
+where
+    M: Strategy,
+    M::Value: Float,
+    P: Strategy<Value = M::Value>,
+{
+    Polar { magnitude, phase }
+}
+
+/// `ValueTree` corresponding to `Polar`.
+#[derive(Clone, Copy, Debug)]
+pub struct PolarValueTree<M, P> {
+    parts: TupleValueTree<(M, P)>,
+}
+
+impl<M, P> Strategy for Polar<M, P>
+where
+    M: Strategy,
+    M::Value: Float,
+    P: Strategy<Value = M::Value>,
+{
+    type Tree = PolarValueTree<M::Tree, P::Tree>;
+    type Value = Complex<M::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(PolarValueTree {
+            parts: (&self.magnitude, &self.phase).new_tree(runner)?,
+        })
+    }
+}
+
+impl<M, P> ValueTree for PolarValueTree<M, P>
+where
+    M: ValueTree,
+    M::Value: Float,
+    P: ValueTree<Value = M::Value>,
+{
+    type Value = Complex<M::Value>;
+
+    fn current(&self) -> Complex<M::Value> {
+        let (magnitude, phase) = self.parts.current();
+        Complex::new(magnitude * phase.cos(), magnitude * phase.sin())
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.parts.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.parts.complicate()
+    }
+}
+
+impl<A: Arbitrary> Arbitrary for Complex<A> {
+    type Parameters = (A::Parameters, A::Parameters);
+    type Strategy = Rectangular<A::Strategy, A::Strategy>;
+
+    fn arbitrary_with((re, im): Self::Parameters) -> Self::Strategy {
+        rectangular(A::arbitrary_with(re), A::arbitrary_with(im))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::f64::consts::{PI, TAU};
+
+    use super::*;
+
+    #[test]
+    fn rectangular_respects_ranges() {
+        let mut runner = TestRunner::deterministic();
+        let input = rectangular(-1.0f32..1.0, 10.0f32..=20.0);
+        for _ in 0..1024 {
+            let z = input.new_tree(&mut runner).unwrap().current();
+            assert!((-1.0..1.0).contains(&z.re), "{}", z);
+            assert!((10.0..=20.0).contains(&z.im), "{}", z);
+        }
+    }
+
+    #[test]
+    fn polar_respects_magnitude() {
+        let mut runner = TestRunner::deterministic();
+        let input = polar(2.0f64..=3.0, -PI..PI);
+        for _ in 0..1024 {
+            let z = input.new_tree(&mut runner).unwrap().current();
+            let norm = z.re.hypot(z.im);
+            assert!((2.0 - 1e-12..=3.0 + 1e-12).contains(&norm), "{}", z);
+        }
+    }
+
+    #[test]
+    fn shrinks_toward_zero() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let mut tree = rectangular(-5.0f64..5.0, -5.0f64..5.0)
+                .new_tree(&mut runner)
+                .unwrap();
+            while tree.simplify() {}
+            assert_eq!(Complex::new(0.0, 0.0), tree.current());
+
+            let mut tree =
+                polar(1.0f64..2.0, 0.0..TAU).new_tree(&mut runner).unwrap();
+            while tree.simplify() {}
+            assert_eq!(Complex::new(1.0, 0.0), tree.current());
+        }
+    }
+
+    #[test]
+    fn shrinks_to_minimal_magnitude() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&polar(0.0f64..100.0, 0.0..TAU), |z| {
+            prop_assert!(z.re.hypot(z.im) < 50.0);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, z)) => {
+                assert!(z.re.hypot(z.im) >= 50.0 && z.im == 0.0, "{}", z)
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(rectangular(-1.0f64..1.0, 0.0f64..1.0), None);
+        check_strategy_sanity(polar(0.0f32..=1.0, 0.0f32..6.0), None);
+        check_strategy_sanity(crate::arbitrary::any::<Complex<i32>>(), None);
+    }
+}
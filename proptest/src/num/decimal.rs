@@ -0,0 +1,322 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for the `Decimal` type of the `rust_decimal` crate.
+//!
+//! A `Decimal` is a mantissa along with a scale, the number of its digits
+//! after the decimal point. [`decimals`] generates values with up to a given
+//! number of significant digits and a scale in a given range, like an SQL
+//! `NUMERIC(precision, scale)` column, while ranges of `Decimal`s generate
+//! values with the scale of their more precise bound. Either way the
+//! mantissa shrinks toward zero, or toward the bound of the range closest to
+//! it, while the scale stays the same, so `12.50` shrinks to `0.00` rather
+//! than to `0`.
+//!
+//! `Decimal` also implements `Arbitrary`, taking [`DecimalParams`] as
+//! parameters.
+//!
+//! ```rust
+//! use proptest::num::decimal;
+//! use proptest::prelude::*;
+//! use rust_decimal::Decimal;
+//!
+//! proptest! {
+//!     fn prices_round_trip(price in decimal::decimals(10, 2)) {
+//!         prop_assert_eq!(2, price.scale());
+//!         prop_assert_eq!(Ok(price), price.to_string().parse::<Decimal>());
+//!     }
+//! }
+//! # fn main() { prices_round_trip(); }
+//! ```
+
+use core::ops::{Range, RangeInclusive};
+
+use ::rust_decimal::Decimal;
+
+use crate::arbitrary::Arbitrary;
+use crate::collection::SizeRange;
+use crate::num::{self, sample_uniform_incl};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// The greatest mantissa of a `Decimal`, `2^96 - 1`.
+const MAX_MANTISSA: i128 = (1 << 96) - 1;
+
+/// The precision and scale of the `Decimal`s generated by
+/// `any::<Decimal>()`.
+///
+/// Defaults to 28 digits with a scale of 0 to 28.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecimalParams {
+    precision: u32,
+    scale: SizeRange,
+}
+
+impl DecimalParams {
+    /// Allow up to `precision` significant digits, `scale` of which follow
+    /// the decimal point.
+    ///
+    /// Panics if `precision` or the scale exceeds 28.
+    pub fn new(precision: u32, scale: impl Into<SizeRange>) -> Self {
+        let scale = scale.into();
+        scale.assert_nonempty();
+        assert!(
+            precision <= Decimal::MAX_SCALE,
+            "Precision {} exceeds the {} digits of a Decimal",
+            precision,
+            Decimal::MAX_SCALE
+        );
+        assert!(
+            scale.end_incl() <= Decimal::MAX_SCALE as usize,
+            "Scale {} exceeds the {} digits of a Decimal",
+            scale.end_incl(),
+            Decimal::MAX_SCALE
+        );
+        DecimalParams { precision, scale }
+    }
+
+    /// Return the maximum number of significant digits.
+    pub fn precision(&self) -> u32 {
+        self.precision
+    }
+
+    /// Return the range of the number of digits after the decimal point.
+    pub fn scale(&self) -> &SizeRange {
+        &self.scale
+    }
+}
+
+impl Default for DecimalParams {
+    fn default() -> Self {
+        DecimalParams::new(Decimal::MAX_SCALE, 0..=Decimal::MAX_SCALE as usize)
+    }
+}
+
+/// Strategy to generate `Decimal`s whose mantissa and scale lie in certain
+/// ranges.
+///
+/// Created by the `decimals()` function in the same module.
+#[derive(Clone, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct DecimalStrategy {
+    mantissa: RangeInclusive<i128>,
+    scale: SizeRange,
+}
+
+/// Create a strategy to generate `Decimal`s with up to `precision`
+/// significant digits and a number of digits after the decimal point in
+/// `scale`.
+///
+/// Each value keeps its scale while shrinking toward zero.
+///
+/// Panics if `precision` or the scale exceeds 28.
+pub fn decimals(
+    precision: u32,
+    scale: impl Into<SizeRange>,
+) -> DecimalStrategy {
+    let params = DecimalParams::new(precision, scale);
+    let max = 10i128.pow(params.precision) - 1;
+    DecimalStrategy {
+        mantissa: -max..=max,
+        scale: params.scale,
+    }
+}
+
+impl Strategy for DecimalStrategy {
+    type Tree = DecimalValueTree;
+    type Value = Decimal;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let (start, end) = self.scale.start_end_incl();
+        let scale = sample_uniform_incl(runner, start, end) as u32;
+        Ok(DecimalValueTree {
+            mantissa: self.mantissa.new_tree(runner)?,
+            scale,
+        })
+    }
+}
+
+/// `ValueTree` corresponding to `DecimalStrategy` and ranges of `Decimal`s.
+///
+/// Shrinks the mantissa towards 0 and keeps the scale.
+#[derive(Clone, Copy, Debug)]
+pub struct DecimalValueTree {
+    mantissa: num::i128::BinarySearch,
+    scale: u32,
+}
+
+impl ValueTree for DecimalValueTree {
+    type Value = Decimal;
+
+    fn current(&self) -> Decimal {
+        Decimal::from_i128_with_scale(self.mantissa.current(), self.scale)
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.mantissa.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.mantissa.complicate()
+    }
+}
+
+/// Return the mantissa of `value` at the greater `scale`, saturating at the
+/// greatest mantissa a `Decimal` can have.
+fn mantissa_at(value: Decimal, scale: u32) -> i128 {
+    10i128
+        .checked_pow(scale - value.scale())
+        .and_then(|factor| value.mantissa().checked_mul(factor))
+        .map_or(MAX_MANTISSA * value.mantissa().signum(), |mantissa| {
+            mantissa.clamp(-MAX_MANTISSA, MAX_MANTISSA)
+        })
+}
+
+/// Create a strategy for the `Decimal`s from `start` to `end`, included only
+/// if `inclusive`, with the scale of the more precise bound.
+fn range(start: Decimal, end: Decimal, inclusive: bool) -> DecimalStrategy {
+    let scale = start.scale().max(end.scale());
+    let end = mantissa_at(end, scale);
+    DecimalStrategy {
+        mantissa: mantissa_at(start, scale)..=end - !inclusive as i128,
+        scale: (scale as usize).into(),
+    }
+}
+
+impl Strategy for Range<Decimal> {
+    type Tree = DecimalValueTree;
+    type Value = Decimal;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        if self.is_empty() {
+            panic!("Invalid use of empty range {}..{}.", self.start, self.end);
+        }
+
+        range(self.start, self.end, false).new_tree(runner)
+    }
+}
+
+impl Strategy for RangeInclusive<Decimal> {
+    type Tree = DecimalValueTree;
+    type Value = Decimal;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        if self.is_empty() {
+            panic!(
+                "Invalid use of empty range {}..={}.",
+                self.start(),
+                self.end()
+            );
+        }
+
+        range(*self.start(), *self.end(), true).new_tree(runner)
+    }
+}
+
+impl Arbitrary for Decimal {
+    type Parameters = DecimalParams;
+    type Strategy = DecimalStrategy;
+
+    fn arbitrary_with(params: DecimalParams) -> DecimalStrategy {
+        decimals(params.precision, params.scale)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arbitrary::any;
+
+    fn minimal<S: Strategy>(
+        strategy: S,
+        fails: impl Fn(&S::Value) -> bool,
+    ) -> S::Value {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&strategy, |v| {
+            prop_assert!(!fails(&v));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, v)) => v,
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn precision_and_scale_are_respected() {
+        let mut runner = TestRunner::deterministic();
+        let mut scales = [false; 5];
+        let limit = Decimal::from(1000);
+        for _ in 0..1024 {
+            let d = decimals(7, 2..=6).new_tree(&mut runner).unwrap().current();
+            assert!((2..=6).contains(&d.scale()), "{}", d);
+            assert!(d.mantissa().abs() < 10_000_000, "{}", d);
+            scales[d.scale() as usize - 2] = true;
+
+            let d = decimals(5, 2).new_tree(&mut runner).unwrap().current();
+            assert_eq!(2, d.scale());
+            assert!(d.abs() < limit, "{}", d);
+        }
+        assert!(scales.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn ranges_are_respected() {
+        let mut runner = TestRunner::deterministic();
+        let start = Decimal::new(-15, 1);
+        let end = Decimal::new(2_500, 3);
+        for _ in 0..1024 {
+            let d = (start..end).new_tree(&mut runner).unwrap().current();
+            assert!(start <= d && d < end, "{}", d);
+            assert_eq!(3, d.scale());
+
+            let d = (start..=start).new_tree(&mut runner).unwrap().current();
+            assert_eq!(start, d);
+            assert_eq!(1, d.scale());
+
+            let d = (Decimal::MIN..=Decimal::MAX)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert_eq!(0, d.scale());
+        }
+    }
+
+    #[test]
+    fn extreme_bounds_saturate() {
+        assert_eq!(MAX_MANTISSA, mantissa_at(Decimal::MAX, 0));
+        assert_eq!(MAX_MANTISSA, mantissa_at(Decimal::MAX, 5));
+        assert_eq!(-MAX_MANTISSA, mantissa_at(Decimal::MIN, 28));
+        assert_eq!(-150, mantissa_at(Decimal::new(-15, 1), 2));
+    }
+
+    #[test]
+    fn shrinks_toward_zero_keeping_scale() {
+        let d = minimal(decimals(10, 2), |d| *d > Decimal::new(12_345, 1));
+        assert_eq!(Decimal::new(123_451, 2), d);
+        assert_eq!(2, d.scale());
+
+        let d = minimal(any::<Decimal>(), |_| true);
+        assert!(d.is_zero(), "{}", d);
+
+        let threshold = Decimal::new(-25, 1);
+        let d = minimal(Decimal::new(-500, 2)..Decimal::new(-1, 0), |d| {
+            *d <= threshold
+        });
+        assert_eq!(Decimal::new(-250, 2), d);
+        assert_eq!(2, d.scale());
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(any::<Decimal>(), None);
+        check_strategy_sanity(decimals(4, 2), None);
+        check_strategy_sanity(Decimal::new(5, 1)..=Decimal::new(9, 0), None);
+    }
+}
@@ -0,0 +1,354 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for non-uniformly distributed numbers.
+//!
+//! The macros here add `normal()` and `exponential()` to the float modules
+//! and `zipf()` to some of the unsigned integer modules. Values are sampled
+//! from the distribution but shrink toward its mode with the usual binary
+//! search.
+
+macro_rules! float_distributions {
+    ($typ:ident) => {
+        mod distribution {
+            use core::$typ::consts::PI;
+
+            use num_traits::float::Float as FloatMath;
+            use rand::Rng;
+
+            use super::BinarySearch;
+            use crate::strategy::*;
+            use crate::test_runner::TestRunner;
+
+            /// Sample uniformly from `(0, 1]`, which is safe to take the
+            /// logarithm of.
+            fn unit(runner: &mut TestRunner) -> $typ {
+                1.0 - runner.rng().random::<$typ>()
+            }
+
+            /// Strategy to generate normally distributed values.
+            ///
+            /// Created by the `normal()` function in the same module.
+            #[derive(Clone, Copy, Debug)]
+            #[must_use = "strategies do nothing unless used"]
+            pub struct Normal {
+                mean: $typ,
+                sd: $typ,
+            }
+
+            /// Create a strategy to generate values from the normal
+            /// distribution with the given mean and standard deviation.
+            ///
+            /// Values shrink toward `mean`.
+            ///
+            /// ## Panics
+            ///
+            /// Panics if `mean` is not finite, or if `sd` is negative or not
+            /// finite.
+            pub fn normal(mean: $typ, sd: $typ) -> Normal {
+                assert!(mean.is_finite(), "normal() mean must be finite");
+                assert!(
+                    sd.is_finite() && sd >= 0.0,
+                    "normal() standard deviation must be finite and \
+                     non-negative"
+                );
+                Normal { mean, sd }
+            }
+
+            /// `ValueTree` corresponding to `Normal`.
+            #[derive(Clone, Copy, Debug)]
+            pub struct NormalValueTree {
+                mean: $typ,
+                offset: BinarySearch,
+            }
+
+            impl Strategy for Normal {
+                type Tree = NormalValueTree;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    // Box-Muller transform.
+                    let radius =
+                        FloatMath::sqrt(-2.0 * FloatMath::ln(unit(runner)));
+                    let angle = 2.0 * PI * runner.rng().random::<$typ>();
+                    let z = radius * FloatMath::cos(angle);
+
+                    Ok(NormalValueTree {
+                        mean: self.mean,
+                        offset: BinarySearch::new(z * self.sd),
+                    })
+                }
+            }
+
+            impl ValueTree for NormalValueTree {
+                type Value = $typ;
+
+                fn current(&self) -> $typ {
+                    self.mean + self.offset.current()
+                }
+
+                fn simplify(&mut self) -> bool {
+                    self.offset.simplify()
+                }
+
+                fn complicate(&mut self) -> bool {
+                    self.offset.complicate()
+                }
+            }
+
+            /// Strategy to generate exponentially distributed values.
+            ///
+            /// Created by the `exponential()` function in the same module.
+            #[derive(Clone, Copy, Debug)]
+            #[must_use = "strategies do nothing unless used"]
+            pub struct Exponential {
+                lambda: $typ,
+            }
+
+            /// Create a strategy to generate non-negative values from the
+            /// exponential distribution with rate `lambda`, and thus mean
+            /// `1 / lambda`.
+            ///
+            /// Values shrink toward 0.
+            ///
+            /// ## Panics
+            ///
+            /// Panics if `lambda` is not positive and finite.
+            pub fn exponential(lambda: $typ) -> Exponential {
+                assert!(
+                    lambda.is_finite() && lambda > 0.0,
+                    "exponential() rate must be positive and finite"
+                );
+                Exponential { lambda }
+            }
+
+            impl Strategy for Exponential {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    Ok(BinarySearch::new(
+                        -FloatMath::ln(unit(runner)) / self.lambda,
+                    ))
+                }
+            }
+        }
+
+        pub use self::distribution::{
+            exponential, normal, Exponential, Normal, NormalValueTree,
+        };
+    };
+}
+
+macro_rules! zipf_distribution {
+    ($typ:ident) => {
+        mod distribution {
+            use num_traits::float::Float as FloatMath;
+            use rand::Rng;
+
+            use super::BinarySearch;
+            use crate::strategy::*;
+            use crate::test_runner::TestRunner;
+
+            /// Strategy to generate Zipf-distributed ranks.
+            ///
+            /// Created by the `zipf()` function in the same module.
+            #[derive(Clone, Copy, Debug)]
+            #[must_use = "strategies do nothing unless used"]
+            pub struct Zipf {
+                n: $typ,
+                s: f64,
+                t: f64,
+            }
+
+            /// Create a strategy to generate ranks between 1 and `n`
+            /// inclusive from the Zipf distribution with exponent `s`, under
+            /// which rank `k` is chosen with probability proportional to
+            /// `1 / k^s`.
+            ///
+            /// This models popularity, where a few items are much more
+            /// common than the rest. An exponent of 0 makes all ranks equally
+            /// likely, and larger exponents favour the low ranks more.
+            ///
+            /// Values shrink toward 1.
+            ///
+            /// ## Panics
+            ///
+            /// Panics if `n` is 0, or if `s` is negative or not finite.
+            pub fn zipf(n: $typ, s: f64) -> Zipf {
+                assert!(n > 0, "zipf() requires at least one rank");
+                assert!(
+                    s.is_finite() && s >= 0.0,
+                    "zipf() exponent must be finite and non-negative"
+                );
+                let nf = n as f64;
+                let t = if s != 1.0 {
+                    (FloatMath::powf(nf, 1.0 - s) - s) / (1.0 - s)
+                } else {
+                    1.0 + FloatMath::ln(nf)
+                };
+                Zipf { n, s, t }
+            }
+
+            impl Zipf {
+                /// The inverse of the integral of the hat function
+                /// `max(1, x)^-s` used by rejection-inversion sampling.
+                fn inv_cdf(&self, p: f64) -> f64 {
+                    let pt = p * self.t;
+                    if pt <= 1.0 {
+                        pt
+                    } else if self.s != 1.0 {
+                        FloatMath::powf(
+                            pt * (1.0 - self.s) + self.s,
+                            1.0 / (1.0 - self.s),
+                        )
+                    } else {
+                        FloatMath::exp(pt - 1.0)
+                    }
+                }
+            }
+
+            impl Strategy for Zipf {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    let rng = runner.rng();
+                    // Rejection-inversion sampling (Hörmann and Derflinger),
+                    // which needs neither a table nor a normalising sum.
+                    let rank = loop {
+                        let inv_b = self.inv_cdf(rng.random::<f64>());
+                        let x = FloatMath::floor(inv_b + 1.0);
+                        let mut ratio = FloatMath::powf(x, -self.s);
+                        if x > 1.0 {
+                            ratio *= FloatMath::powf(inv_b, self.s);
+                        }
+                        if rng.random::<f64>() < ratio {
+                            break x;
+                        }
+                    };
+
+                    Ok(BinarySearch::new_above(
+                        1,
+                        (rank as $typ).clamp(1, self.n),
+                    ))
+                }
+            }
+        }
+
+        pub use self::distribution::{zipf, Zipf};
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num;
+    use crate::std_facade::Vec;
+    use crate::strategy::*;
+    use crate::test_runner::*;
+
+    #[test]
+    fn normal_has_mean_and_sd() {
+        let mut runner = TestRunner::deterministic();
+        let input = num::f64::normal(10.0, 2.0);
+        let samples = (0..4096)
+            .map(|_| input.new_tree(&mut runner).unwrap().current())
+            .collect::<Vec<_>>();
+
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let var = samples
+            .iter()
+            .map(|&x| (x - mean) * (x - mean))
+            .sum::<f64>()
+            / n;
+        assert!((mean - 10.0).abs() < 0.2, "mean {}", mean);
+        assert!((var.sqrt() - 2.0).abs() < 0.2, "sd {}", var.sqrt());
+    }
+
+    #[test]
+    fn normal_shrinks_toward_mean() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&num::f32::normal(100.0, 10.0), |x| {
+            prop_assert!((..105.0).contains(&x));
+            Ok(())
+        });
+
+        match result {
+            Err(TestError::Fail(_, x)) => {
+                assert!((105.0..110.0).contains(&x), "{}", x)
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn exponential_has_mean() {
+        let mut runner = TestRunner::deterministic();
+        let input = num::f64::exponential(4.0);
+        let mut sum = 0.0;
+        for _ in 0..4096 {
+            let x = input.new_tree(&mut runner).unwrap().current();
+            assert!(x >= 0.0);
+            sum += x;
+        }
+        let mean = sum / 4096.0;
+        assert!((mean - 0.25).abs() < 0.02, "mean {}", mean);
+    }
+
+    #[test]
+    fn exponential_shrinks_toward_zero() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree =
+            num::f64::exponential(1.0).new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        assert_eq!(0.0, tree.current());
+    }
+
+    #[test]
+    fn zipf_favours_low_ranks() {
+        let mut runner = TestRunner::deterministic();
+        let mut counts = [0usize; 11];
+        for &s in &[0.0, 1.0, 1.5] {
+            counts.iter_mut().for_each(|c| *c = 0);
+            let input = num::u64::zipf(10, s);
+            for _ in 0..4096 {
+                let rank = input.new_tree(&mut runner).unwrap().current();
+                assert!((1..=10).contains(&rank));
+                counts[rank as usize] += 1;
+            }
+            if s == 0.0 {
+                // Every rank is expected around 410 times.
+                assert!(counts[1..].iter().all(|&c| c > 300), "{:?}", counts);
+            } else {
+                assert!(counts[1] > 2 * counts[2], "{:?}", counts);
+                assert!(counts[2] > counts[10], "{:?}", counts);
+            }
+        }
+    }
+
+    #[test]
+    fn zipf_huge_range() {
+        let mut runner = TestRunner::deterministic();
+        let input = num::usize::zipf(usize::MAX, 1.1);
+        for _ in 0..256 {
+            let mut tree = input.new_tree(&mut runner).unwrap();
+            assert!(tree.current() >= 1);
+            while tree.simplify() {}
+            assert_eq!(1, tree.current());
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(num::f64::normal(-3.0, 7.0), None);
+        check_strategy_sanity(num::f32::exponential(0.5), None);
+        check_strategy_sanity(num::u32::zipf(1000, 1.2), None);
+    }
+}
@@ -0,0 +1,416 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Biasing numeric strategies toward edge values.
+//!
+//! Sampling uniformly over a large range practically never produces the
+//! values at which bugs tend to lie, such as 0, `MAX` or the bounds of the
+//! range itself. The `EdgeCases` trait is implemented by the range and `ANY`
+//! strategies of the `num` module, and its `with_edge_bias()` adapter
+//! produces one of those values with a given probability.
+
+use core::fmt;
+
+use rand::Rng;
+
+use crate::std_facade::Vec;
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// Numeric strategies which know the edge values of their domain.
+pub trait EdgeCases: Strategy {
+    /// Return the edge values which this strategy can produce.
+    ///
+    /// For integers these are 0, ±1, `MIN`, `MAX`, the powers of two and
+    /// their neighbours, and the bounds of the range. For floats these
+    /// include ±0, ±1, the smallest subnormal and normal values, `MIN`,
+    /// `MAX`, the bounds of the range and, if the strategy allows them,
+    /// infinities and NaN.
+    fn edge_cases(&self) -> Vec<Self::Value>;
+
+    /// Return a value tree which starts at `value`, one of the values from
+    /// `edge_cases()`, and shrinks like the value trees of this strategy.
+    fn edge_tree(&self, value: Self::Value) -> Self::Tree;
+
+    /// Return a strategy which produces one of the values from
+    /// `edge_cases()` with the given probability, and otherwise samples from
+    /// this strategy as usual.
+    ///
+    /// ```
+    /// use proptest::num::EdgeCases;
+    /// use proptest::prelude::*;
+    ///
+    /// proptest! {
+    ///     fn addition_does_not_overflow(
+    ///         a in any::<i64>().with_edge_bias(0.2),
+    ///         b in (-100i64..100).with_edge_bias(0.2),
+    ///     ) {
+    ///         let _ = a.checked_add(b);
+    ///     }
+    /// }
+    /// # fn main() { addition_does_not_overflow(); }
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `probability` is not between 0 and 1 inclusive.
+    fn with_edge_bias(self, probability: f64) -> EdgeBias<Self>
+    where
+        Self: Sized,
+    {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "with_edge_bias() probability must be between 0 and 1"
+        );
+        EdgeBias {
+            source: self,
+            probability,
+        }
+    }
+}
+
+/// Strategy which produces edge values with a certain probability.
+///
+/// Created by `EdgeCases::with_edge_bias()`.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Copy)]
+pub struct EdgeBias<S> {
+    source: S,
+    probability: f64,
+}
+
+impl<S: fmt::Debug> fmt::Debug for EdgeBias<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EdgeBias")
+            .field("source", &self.source)
+            .field("probability", &self.probability)
+            .finish()
+    }
+}
+
+impl<S: EdgeCases> Strategy for EdgeBias<S> {
+    type Tree = S::Tree;
+    type Value = S::Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        if runner.rng().random_bool(self.probability) {
+            let mut edges = self.source.edge_cases();
+            if !edges.is_empty() {
+                let ix = runner.rng().random_range(0..edges.len());
+                return Ok(self.source.edge_tree(edges.swap_remove(ix)));
+            }
+        }
+
+        self.source.new_tree(runner)
+    }
+}
+
+macro_rules! int_edge_cases {
+    ($typ:ident) => {
+        mod edge {
+            use core::ops;
+
+            use super::{Any, BinarySearch};
+            use crate::num::EdgeCases;
+            use crate::std_facade::Vec;
+
+            /// Return the edge values within `lo..=hi`.
+            fn edges(lo: $typ, hi: $typ) -> Vec<$typ> {
+                let mut edges = vec![0, 1, $typ::MIN, $typ::MAX, lo, hi];
+                if lo < hi {
+                    edges.extend([lo + 1, hi - 1]);
+                }
+                for k in 0..$typ::BITS {
+                    let pow = (1 as $typ).wrapping_shl(k);
+                    for edge in [pow, pow.wrapping_sub(1), pow.wrapping_add(1)]
+                    {
+                        // For unsigned types the negation wraps around to
+                        // values near `MAX`, which are edge values too.
+                        edges.extend([edge, (0 as $typ).wrapping_sub(edge)]);
+                    }
+                }
+
+                edges.retain(|edge| (lo..=hi).contains(edge));
+                edges.sort_unstable();
+                edges.dedup();
+                edges
+            }
+
+            impl EdgeCases for Any {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    edges($typ::MIN, $typ::MAX)
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new(value)
+                }
+            }
+
+            impl EdgeCases for ops::Range<$typ> {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    if self.is_empty() {
+                        Vec::new()
+                    } else {
+                        edges(self.start, self.end - 1)
+                    }
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_clamped(self.start, value, self.end - 1)
+                }
+            }
+
+            impl EdgeCases for ops::RangeInclusive<$typ> {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    if self.is_empty() {
+                        Vec::new()
+                    } else {
+                        edges(*self.start(), *self.end())
+                    }
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_clamped(*self.start(), value, *self.end())
+                }
+            }
+
+            impl EdgeCases for ops::RangeFrom<$typ> {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    edges(self.start, $typ::MAX)
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_clamped(self.start, value, $typ::MAX)
+                }
+            }
+
+            impl EdgeCases for ops::RangeTo<$typ> {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    if self.end == $typ::MIN {
+                        Vec::new()
+                    } else {
+                        edges($typ::MIN, self.end - 1)
+                    }
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_clamped($typ::MIN, value, self.end)
+                }
+            }
+
+            impl EdgeCases for ops::RangeToInclusive<$typ> {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    edges($typ::MIN, self.end)
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_clamped($typ::MIN, value, self.end)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! float_edge_cases {
+    ($typ:ident) => {
+        mod edge {
+            use core::ops;
+
+            use super::{Any, BinarySearch};
+            use crate::num::EdgeCases;
+            use crate::std_facade::Vec;
+
+            /// Return the finite edge values within `lo..=hi`, or within
+            /// `lo..hi` if `exclusive`.
+            fn edges(lo: $typ, hi: $typ, exclusive: bool) -> Vec<$typ> {
+                let mut edges = vec![lo];
+                if !exclusive {
+                    edges.push(hi);
+                }
+                for edge in [
+                    0.0,
+                    1.0,
+                    $typ::EPSILON,
+                    $typ::MIN_POSITIVE,
+                    $typ::from_bits(1),
+                    $typ::MAX,
+                ] {
+                    edges.extend([edge, -edge]);
+                }
+
+                edges.retain(|edge| {
+                    lo <= *edge && (*edge < hi || !exclusive && *edge <= hi)
+                });
+                edges
+            }
+
+            impl EdgeCases for Any {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    let mut edges = edges($typ::MIN, $typ::MAX, false);
+                    edges.extend([
+                        $typ::INFINITY,
+                        $typ::NEG_INFINITY,
+                        $typ::NAN,
+                    ]);
+                    edges
+                        .retain(|&edge| self.edge_tree(edge).current_allowed());
+                    edges
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_with_types(value, self.0.normalise())
+                }
+            }
+
+            impl EdgeCases for ops::Range<$typ> {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    if self.is_empty() {
+                        Vec::new()
+                    } else {
+                        edges(self.start, self.end, true)
+                    }
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_clamped(self.start, value, self.end)
+                }
+            }
+
+            impl EdgeCases for ops::RangeInclusive<$typ> {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    if self.is_empty() {
+                        Vec::new()
+                    } else {
+                        edges(*self.start(), *self.end(), false)
+                    }
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_clamped(*self.start(), value, *self.end())
+                }
+            }
+
+            impl EdgeCases for ops::RangeFrom<$typ> {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    edges(self.start, $typ::MAX, false)
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_clamped(self.start, value, $typ::MAX)
+                }
+            }
+
+            impl EdgeCases for ops::RangeTo<$typ> {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    edges($typ::MIN, self.end, true)
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_clamped($typ::MIN, value, self.end)
+                }
+            }
+
+            impl EdgeCases for ops::RangeToInclusive<$typ> {
+                fn edge_cases(&self) -> Vec<$typ> {
+                    edges($typ::MIN, self.end, false)
+                }
+
+                fn edge_tree(&self, value: $typ) -> BinarySearch {
+                    BinarySearch::new_clamped($typ::MIN, value, self.end)
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num;
+
+    use super::*;
+
+    #[test]
+    fn int_edges_in_range() {
+        let edges = (-100i32..100).edge_cases();
+        for edge in [-100, -99, -65, -64, -63, -1, 0, 1, 2, 64, 65, 98, 99] {
+            assert!(edges.contains(&edge), "{} missing from {:?}", edge, edges);
+        }
+        assert!(edges.iter().all(|e| (-100..100).contains(e)));
+
+        let edges = num::u8::ANY.edge_cases();
+        for edge in [0, 1, 2, 127, 128, 129, 254, 255] {
+            assert!(edges.contains(&edge), "{} missing from {:?}", edge, edges);
+        }
+
+        let edges = (i64::MIN..=i64::MAX).edge_cases();
+        assert!(edges.contains(&i64::MIN) && edges.contains(&i64::MAX));
+        assert!((..i8::MIN).edge_cases().is_empty());
+    }
+
+    #[test]
+    fn float_edges_in_range() {
+        let edges = num::f64::ANY.edge_cases();
+        assert!(edges.iter().any(|e| e.is_nan()));
+        assert!(edges.contains(&f64::INFINITY));
+        assert!(edges.contains(&f64::MIN_POSITIVE));
+
+        let edges = num::f64::POSITIVE.edge_cases();
+        assert!(!edges.is_empty());
+        assert!(
+            edges.iter().all(|&e| e.is_normal() && e > 0.0),
+            "{:?}",
+            edges
+        );
+
+        let edges = (-1.0f32..1.0).edge_cases();
+        assert!(edges.contains(&-1.0) && !edges.contains(&1.0));
+        assert!(edges.contains(&0.0) && edges.contains(&f32::EPSILON));
+        assert!(edges.iter().all(|e| (-1.0..1.0).contains(e)));
+    }
+
+    #[test]
+    fn bias_produces_edges() {
+        let mut runner = TestRunner::deterministic();
+        let input = (i64::MIN..=i64::MAX).with_edge_bias(0.5);
+        let mut extremes = 0;
+        for _ in 0..1024 {
+            let x = input.new_tree(&mut runner).unwrap().current();
+            if x == i64::MIN || x == i64::MAX {
+                extremes += 1;
+            }
+        }
+        assert!(extremes > 0);
+
+        let input = (0u32..10).with_edge_bias(1.0);
+        for _ in 0..256 {
+            let x = input.new_tree(&mut runner).unwrap().current();
+            assert!([0, 1, 2, 3, 4, 5, 7, 8, 9].contains(&x), "{}", x);
+        }
+    }
+
+    #[test]
+    fn edges_shrink_within_range() {
+        let mut runner = TestRunner::deterministic();
+        let input = (10i32..1000).with_edge_bias(1.0);
+        for _ in 0..64 {
+            let mut tree = input.new_tree(&mut runner).unwrap();
+            while tree.simplify() {
+                assert!((10..1000).contains(&tree.current()));
+            }
+            assert_eq!(10, tree.current());
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(num::i16::ANY.with_edge_bias(0.5), None);
+        check_strategy_sanity((-5.0f64..5.0).with_edge_bias(0.5), None);
+        check_strategy_sanity(num::f32::ANY.with_edge_bias(0.5), None);
+    }
+}
@@ -0,0 +1,182 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for the fixed-point types of the `fixed` crate.
+//!
+//! A fixed-point number is an integer scaled by a power of two given by its
+//! number of fractional bits, so values are generated and shrunk as that
+//! integer: ranges of any of the types, such as `FixedI32<U16>` or its alias
+//! `I16F16`, are strategies sampling uniformly and shrinking toward zero, or
+//! toward the bound of the range closest to it, and each type implements
+//! `Arbitrary` covering all of its values.
+//!
+//! ```rust
+//! use fixed::types::I16F16;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn negation_is_an_involution(
+//!         x in I16F16::from_num(-100)..=I16F16::from_num(100)
+//!     ) {
+//!         prop_assert_eq!(x, -(-x));
+//!     }
+//! }
+//! # fn main() { negation_is_an_involution(); }
+//! ```
+
+use core::ops::{Range, RangeInclusive};
+
+use ::fixed::types::extra::{LeEqU128, LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+use ::fixed::{
+    FixedI128, FixedI16, FixedI32, FixedI64, FixedI8, FixedU128, FixedU16,
+    FixedU32, FixedU64, FixedU8,
+};
+
+use crate::arbitrary::Arbitrary;
+use crate::num;
+use crate::strategy::statics::{static_map, Map};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+macro_rules! fixed_strategies {
+    ($fixed:ident, $bits:ident, $frac:ident) => {
+        impl<Frac: $frac> Strategy for Range<$fixed<Frac>> {
+            type Tree =
+                Map<num::$bits::BinarySearch, fn($bits) -> $fixed<Frac>>;
+            type Value = $fixed<Frac>;
+
+            fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                if self.is_empty() {
+                    panic!(
+                        "Invalid use of empty range {}..{}.",
+                        self.start, self.end
+                    );
+                }
+
+                static_map(
+                    self.start.to_bits()..self.end.to_bits(),
+                    $fixed::from_bits as fn($bits) -> $fixed<Frac>,
+                )
+                .new_tree(runner)
+            }
+        }
+
+        impl<Frac: $frac> Strategy for RangeInclusive<$fixed<Frac>> {
+            type Tree =
+                Map<num::$bits::BinarySearch, fn($bits) -> $fixed<Frac>>;
+            type Value = $fixed<Frac>;
+
+            fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                if self.is_empty() {
+                    panic!(
+                        "Invalid use of empty range {}..={}.",
+                        self.start(),
+                        self.end()
+                    );
+                }
+
+                static_map(
+                    self.start().to_bits()..=self.end().to_bits(),
+                    $fixed::from_bits as fn($bits) -> $fixed<Frac>,
+                )
+                .new_tree(runner)
+            }
+        }
+
+        impl<Frac: $frac> Arbitrary for $fixed<Frac> {
+            type Parameters = ();
+            type Strategy = Map<num::$bits::Any, fn($bits) -> $fixed<Frac>>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                static_map(
+                    num::$bits::ANY,
+                    $fixed::from_bits as fn($bits) -> $fixed<Frac>,
+                )
+            }
+        }
+    };
+}
+
+fixed_strategies!(FixedI8, i8, LeEqU8);
+fixed_strategies!(FixedI16, i16, LeEqU16);
+fixed_strategies!(FixedI32, i32, LeEqU32);
+fixed_strategies!(FixedI64, i64, LeEqU64);
+fixed_strategies!(FixedI128, i128, LeEqU128);
+fixed_strategies!(FixedU8, u8, LeEqU8);
+fixed_strategies!(FixedU16, u16, LeEqU16);
+fixed_strategies!(FixedU32, u32, LeEqU32);
+fixed_strategies!(FixedU64, u64, LeEqU64);
+fixed_strategies!(FixedU128, u128, LeEqU128);
+
+#[cfg(test)]
+mod test {
+    use ::fixed::types::{I16F16, I4F4, U0F64, U8F8};
+
+    use super::*;
+    use crate::arbitrary::any;
+
+    fn minimal<S: Strategy>(
+        strategy: S,
+        fails: impl Fn(&S::Value) -> bool,
+    ) -> S::Value {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&strategy, |v| {
+            prop_assert!(!fails(&v));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, v)) => v,
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn ranges_are_respected() {
+        let mut runner = TestRunner::deterministic();
+        let start = I16F16::from_num(-2.5);
+        let end = I16F16::from_num(3.25);
+        let mut fractional = false;
+        for _ in 0..1024 {
+            let x = (start..end).new_tree(&mut runner).unwrap().current();
+            assert!(start <= x && x < end, "{}", x);
+            fractional |= x.frac() != 0;
+
+            let x = (U8F8::ONE..=U8F8::MAX)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!(x >= U8F8::ONE, "{}", x);
+        }
+        assert!(fractional);
+    }
+
+    #[test]
+    fn shrinks_toward_zero() {
+        let threshold = I16F16::from_num(10.5);
+        assert_eq!(
+            threshold + I16F16::DELTA,
+            minimal(any::<I16F16>(), |x| *x > threshold)
+        );
+        assert_eq!(I4F4::ZERO, minimal(any::<I4F4>(), |_| true));
+        assert_eq!(U8F8::ONE, minimal(U8F8::ONE..U8F8::MAX, |_| true));
+        assert_eq!(
+            I16F16::from_num(-2),
+            minimal(I16F16::from_num(-8)..=I16F16::from_num(-1), |x| {
+                *x <= -2
+            })
+        );
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(any::<I16F16>(), None);
+        check_strategy_sanity(any::<U0F64>(), None);
+        check_strategy_sanity(I4F4::MIN..I4F4::from_num(1), None);
+    }
+}
@@ -0,0 +1,713 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fine-grained control over generated floats.
+//!
+//! The `Any` constants of the float modules select whole classes of values,
+//! but cannot restrict those classes further. The `builder()` function added
+//! to the float modules by the macro here combines classes and signs with an
+//! exponent range, numeric bounds, a fixed NaN payload and a limit on the
+//! number of significant decimal digits.
+
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+/// Stack buffer for formatting a float in scientific notation.
+struct DecimalBuf {
+    buf: [u8; 32],
+    len: usize,
+}
+
+impl Write for DecimalBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Return the float nearest to `value` rounded to `digits` significant
+/// decimal digits.
+///
+/// Finite floats of any type round-trip through 17 digits, so more are
+/// never needed.
+pub(super) fn round_decimal<T>(value: T, digits: u32) -> T
+where
+    T: Copy + fmt::LowerExp + FromStr,
+{
+    if digits >= 17 {
+        return value;
+    }
+
+    let mut buf = DecimalBuf {
+        buf: [0; 32],
+        len: 0,
+    };
+    write!(buf, "{:.*e}", digits as usize - 1, value)
+        .ok()
+        .and_then(|_| core::str::from_utf8(&buf.buf[..buf.len]).ok())
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(value)
+}
+
+macro_rules! float_builder {
+    (f32) => {
+        float_builder!(f32, u32);
+    };
+    (f64) => {
+        float_builder!(f64, u64);
+    };
+    ($typ:ident, $bits:ident) => {
+        mod builder {
+            use core::ops::{Bound, RangeBounds, RangeInclusive};
+
+            use rand::Rng;
+
+            use super::BinarySearch;
+            use crate::num::float_builder::round_decimal;
+            use crate::num::{FloatLayout, FloatTypes};
+            use crate::std_facade::Vec;
+            use crate::strategy::*;
+            use crate::test_runner::TestRunner;
+
+            type Bits = $bits;
+
+            const MANTISSA_BITS: u32 = $typ::MANTISSA_DIGITS - 1;
+            const EXP_BIAS: i32 = $typ::MAX_EXP - 1;
+            const MIN_EXP: i32 = $typ::MIN_EXP - 1;
+            const MAX_EXP: i32 = $typ::MAX_EXP - 1;
+
+            /// Return the smallest float greater than `value`.
+            fn next_up(value: $typ) -> $typ {
+                let bits = value.to_bits();
+                if value == 0.0 {
+                    $typ::from_bits(1)
+                } else if value > 0.0 {
+                    $typ::from_bits(bits + 1)
+                } else {
+                    $typ::from_bits(bits - 1)
+                }
+            }
+
+            /// Strategy to generate floats from a precise description of the
+            /// values allowed.
+            ///
+            /// Created by the `builder()` function in the same module, which
+            /// documents the defaults. Each method restricts or extends the
+            /// values generated and returns the updated strategy.
+            #[derive(Clone, Copy, Debug)]
+            #[must_use = "strategies do nothing unless used"]
+            pub struct FloatBuilder {
+                types: FloatTypes,
+                exponents: (i32, i32),
+                lo: $typ,
+                hi: $typ,
+                nan_payload: Option<Bits>,
+                decimal_digits: Option<u32>,
+            }
+
+            /// Create a strategy to generate floats of precisely controlled
+            /// classes, signs, exponents and magnitudes.
+            ///
+            /// Classes are enabled by calling `normal()`, `subnormal()`,
+            /// `zero()`, `infinite()`, `quiet_nan()` and `signaling_nan()`.
+            /// If none are enabled, only normal values are generated. Both
+            /// signs are allowed unless restricted with `positive()` or
+            /// `negative()`.
+            ///
+            /// The classes are weighted in the same way as for `Any`, and
+            /// values are uniform over the discrete floating-point space
+            /// allowed within each class.
+            ///
+            /// For example, the following generates normal values between
+            /// -10⁶ and 10⁶, along with both positive and negative zero.
+            ///
+            /// ```
+            /// use proptest::prelude::*;
+            ///
+            /// proptest! {
+            ///     fn bounded_or_zero(
+            ///         x in proptest::num::f64::builder()
+            ///             .normal()
+            ///             .zero()
+            ///             .range(-1e6..=1e6)
+            ///     ) {
+            ///         prop_assert!(x.is_normal() || x == 0.0);
+            ///         prop_assert!(x.abs() <= 1e6);
+            ///     }
+            /// }
+            /// # fn main() { bounded_or_zero(); }
+            /// ```
+            ///
+            /// Values shrink toward the allowed value of the same sign which
+            /// is nearest to zero.
+            pub fn builder() -> FloatBuilder {
+                FloatBuilder {
+                    types: FloatTypes::empty(),
+                    exponents: (MIN_EXP, MAX_EXP),
+                    lo: $typ::MIN,
+                    hi: $typ::MAX,
+                    nan_payload: None,
+                    decimal_digits: None,
+                }
+            }
+
+            impl FloatBuilder {
+                /// Allow normal values.
+                pub fn normal(mut self) -> Self {
+                    self.types |= FloatTypes::NORMAL;
+                    self
+                }
+
+                /// Allow subnormal values.
+                pub fn subnormal(mut self) -> Self {
+                    self.types |= FloatTypes::SUBNORMAL;
+                    self
+                }
+
+                /// Allow zero, of each sign allowed.
+                pub fn zero(mut self) -> Self {
+                    self.types |= FloatTypes::ZERO;
+                    self
+                }
+
+                /// Allow infinity, of each sign allowed.
+                ///
+                /// Infinities are not affected by `range()`.
+                pub fn infinite(mut self) -> Self {
+                    self.types |= FloatTypes::INFINITE;
+                    self
+                }
+
+                /// Allow quiet NaNs.
+                ///
+                /// NaNs are not affected by `range()`.
+                pub fn quiet_nan(mut self) -> Self {
+                    self.types |= FloatTypes::QUIET_NAN;
+                    self
+                }
+
+                /// Allow signalling NaNs.
+                ///
+                /// See the caveats on `SIGNALING_NAN` about signalling NaNs
+                /// being turned into quiet NaNs on some platforms.
+                pub fn signaling_nan(mut self) -> Self {
+                    self.types |= FloatTypes::SIGNALING_NAN;
+                    self
+                }
+
+                /// Allow positive values.
+                ///
+                /// Calling this without `negative()` restricts all classes,
+                /// including zero and NaN, to the positive sign.
+                pub fn positive(mut self) -> Self {
+                    self.types |= FloatTypes::POSITIVE;
+                    self
+                }
+
+                /// Allow negative values.
+                ///
+                /// Calling this without `positive()` restricts all classes,
+                /// including zero and NaN, to the negative sign.
+                pub fn negative(mut self) -> Self {
+                    self.types |= FloatTypes::NEGATIVE;
+                    self
+                }
+
+                /// Restrict normal values to those whose unbiased binary
+                /// exponent is within `exponents`, so that their magnitude
+                /// is at least `2^start` and less than `2^(end + 1)`.
+                ///
+                /// ## Panics
+                ///
+                /// Panics if the range is empty or includes exponents which
+                /// normal values of this type cannot have.
+                pub fn exponent_range(
+                    mut self,
+                    exponents: RangeInclusive<i32>,
+                ) -> Self {
+                    let (start, end) = exponents.into_inner();
+                    assert!(
+                        MIN_EXP <= start && start <= end && end <= MAX_EXP,
+                        "exponent_range() must be a non-empty range within \
+                         {}..={}",
+                        MIN_EXP,
+                        MAX_EXP
+                    );
+                    self.exponents = (start, end);
+                    self
+                }
+
+                /// Restrict finite values to `range`.
+                ///
+                /// Infinities and NaNs are not affected by the range, and are
+                /// generated if and only if their classes are enabled.
+                ///
+                /// ## Panics
+                ///
+                /// Panics if the range contains no finite values, or if
+                /// either bound is NaN.
+                pub fn range(mut self, range: impl RangeBounds<$typ>) -> Self {
+                    let lo = match range.start_bound() {
+                        Bound::Included(&lo) => lo,
+                        Bound::Excluded(&lo) => next_up(lo),
+                        Bound::Unbounded => $typ::MIN,
+                    };
+                    let hi = match range.end_bound() {
+                        Bound::Included(&hi) => hi,
+                        Bound::Excluded(&hi) => -next_up(-hi),
+                        Bound::Unbounded => $typ::MAX,
+                    };
+                    assert!(
+                        !lo.is_nan() && !hi.is_nan(),
+                        "range() bounds must not be NaN"
+                    );
+                    let (lo, hi) = (lo.max($typ::MIN), hi.min($typ::MAX));
+                    assert!(lo <= hi, "range() must contain finite values");
+                    self.lo = lo;
+                    self.hi = hi;
+                    self
+                }
+
+                /// Only generate NaNs with the given payload, the mantissa
+                /// bits other than the quiet bit.
+                ///
+                /// A signalling NaN cannot have a zero payload, so in that
+                /// case signalling NaNs have a payload of 1.
+                pub fn nan_payload(mut self, payload: Bits) -> Self {
+                    self.nan_payload =
+                        Some(payload & ($typ::MANTISSA_MASK >> 1));
+                    self
+                }
+
+                /// Only generate finite values which are exactly the float
+                /// nearest to some decimal number with at most `digits`
+                /// significant digits, such as `0.1` or `-2.5e-3` with
+                /// `digits` of 2.
+                ///
+                /// Such values survive being written out and parsed again
+                /// with that precision, which makes them suitable for testing
+                /// text formats and other decimal representations.
+                ///
+                /// ## Panics
+                ///
+                /// Panics if `digits` is 0.
+                pub fn decimal_digits(mut self, digits: u32) -> Self {
+                    assert!(digits > 0, "decimal_digits() must be positive");
+                    self.decimal_digits = Some(digits);
+                    self
+                }
+
+                fn flags(&self) -> FloatTypes {
+                    let mut flags = self.types;
+                    if !flags.intersects(
+                        FloatTypes::POSITIVE | FloatTypes::NEGATIVE,
+                    ) {
+                        flags |= FloatTypes::POSITIVE | FloatTypes::NEGATIVE;
+                    }
+                    flags.normalise()
+                }
+
+                /// Return the range of magnitude bits allowed for finite
+                /// values of `class` and the given sign, if any.
+                fn magnitudes(
+                    &self,
+                    class: FloatTypes,
+                    negative: bool,
+                ) -> Option<(Bits, Bits)> {
+                    let (class_lo, class_hi): (Bits, Bits) = if class
+                        == FloatTypes::ZERO
+                    {
+                        (0, 0)
+                    } else if class == FloatTypes::SUBNORMAL {
+                        (1, $typ::MANTISSA_MASK)
+                    } else {
+                        let (start, end) = self.exponents;
+                        (
+                            ((start + EXP_BIAS) as Bits) << MANTISSA_BITS,
+                            (((end + EXP_BIAS + 1) as Bits) << MANTISSA_BITS)
+                                - 1,
+                        )
+                    };
+
+                    let magnitude = |x: $typ| x.to_bits() & !$typ::SIGN_MASK;
+                    let (bound_lo, bound_hi) = if negative {
+                        if self.lo > 0.0 {
+                            return None;
+                        }
+                        let lo = if self.hi < 0.0 { magnitude(self.hi) } else { 0 };
+                        (lo, magnitude(self.lo))
+                    } else {
+                        if self.hi < 0.0 {
+                            return None;
+                        }
+                        let lo = if self.lo > 0.0 { magnitude(self.lo) } else { 0 };
+                        (lo, magnitude(self.hi))
+                    };
+
+                    let lo = class_lo.max(bound_lo);
+                    let hi = class_hi.min(bound_hi);
+                    if lo <= hi {
+                        Some((lo, hi))
+                    } else {
+                        None
+                    }
+                }
+
+                /// Return the value nearest to zero of the given sign which
+                /// is allowed, if any finite values of that sign are.
+                fn shrink_target(&self, negative: bool) -> Option<$typ> {
+                    let flags = self.flags();
+                    [FloatTypes::ZERO, FloatTypes::SUBNORMAL, FloatTypes::NORMAL]
+                        .into_iter()
+                        .filter(|&class| flags.contains(class))
+                        .filter_map(|class| self.magnitudes(class, negative))
+                        .map(|(lo, _)| lo)
+                        .min()
+                        .map(|lo| {
+                            $typ::from_bits(
+                                lo | if negative { $typ::SIGN_MASK } else { 0 },
+                            )
+                        })
+                }
+
+                /// Return whether `value` is one of the values described.
+                fn allows(&self, value: $typ) -> bool {
+                    use core::num::FpCategory::*;
+
+                    let flags = self.flags();
+                    let sign_allowed = if value.is_sign_negative() {
+                        flags.contains(FloatTypes::NEGATIVE)
+                    } else {
+                        flags.contains(FloatTypes::POSITIVE)
+                    };
+
+                    let class = match value.classify() {
+                        Nan => {
+                            return sign_allowed
+                                && flags.intersects(
+                                    FloatTypes::QUIET_NAN
+                                        | FloatTypes::SIGNALING_NAN,
+                                );
+                        }
+                        Infinite => {
+                            return sign_allowed
+                                && flags.contains(FloatTypes::INFINITE);
+                        }
+                        Zero => FloatTypes::ZERO,
+                        Subnormal => FloatTypes::SUBNORMAL,
+                        Normal => FloatTypes::NORMAL,
+                    };
+
+                    let magnitude = value.to_bits() & !$typ::SIGN_MASK;
+                    sign_allowed
+                        && flags.contains(class)
+                        && self
+                            .magnitudes(class, value.is_sign_negative())
+                            .map_or(false, |(lo, hi)| {
+                                lo <= magnitude && magnitude <= hi
+                            })
+                        && self.decimal_digits.map_or(true, |digits| {
+                            round_decimal(value, digits) == value
+                        })
+                }
+
+                fn generate(&self, runner: &mut TestRunner) -> $typ {
+                    let flags = self.flags();
+
+                    // A few CPUs disagree with IEEE about the meaning of the
+                    // signalling bit, so base NaNs on the `NAN` constant as
+                    // `Any` does.
+                    let quiet_or = $typ::NAN.to_bits()
+                        & ($typ::EXP_MASK | ($typ::EXP_MASK >> 1));
+                    let signaling_or =
+                        (quiet_or ^ ($typ::EXP_MASK >> 1)) | $typ::EXP_MASK;
+
+                    // Each choice is a weight, the range of bits to sample
+                    // from and the bits to set, which distinguishes NaNs.
+                    let mut choices: Vec<(u32, Bits, Bits, Bits)> = Vec::new();
+                    for &negative in &[false, true] {
+                        let sign = if negative {
+                            FloatTypes::NEGATIVE
+                        } else {
+                            FloatTypes::POSITIVE
+                        };
+                        if !flags.contains(sign) {
+                            continue;
+                        }
+                        let sign_or = if negative { $typ::SIGN_MASK } else { 0 };
+
+                        for &(class, weight) in &[
+                            (FloatTypes::NORMAL, 20),
+                            (FloatTypes::SUBNORMAL, 3),
+                            (FloatTypes::ZERO, 4),
+                        ] {
+                            if let Some((lo, hi)) = flags
+                                .contains(class)
+                                .then(|| self.magnitudes(class, negative))
+                                .flatten()
+                            {
+                                choices.push((weight, lo, hi, sign_or));
+                            }
+                        }
+
+                        if flags.contains(FloatTypes::INFINITE) {
+                            choices.push((2, 0, 0, sign_or | $typ::EXP_MASK));
+                        }
+                        let payloads = match self.nan_payload {
+                            Some(payload) => (payload, payload),
+                            None => (0, $typ::MANTISSA_MASK >> 1),
+                        };
+                        if flags.contains(FloatTypes::QUIET_NAN) {
+                            choices.push((
+                                1,
+                                payloads.0,
+                                payloads.1,
+                                sign_or | quiet_or,
+                            ));
+                        }
+                        if flags.contains(FloatTypes::SIGNALING_NAN) {
+                            choices.push((
+                                1,
+                                payloads.0.max(1),
+                                payloads.1.max(1),
+                                sign_or | signaling_or,
+                            ));
+                        }
+                    }
+
+                    assert!(
+                        !choices.is_empty(),
+                        "float builder() allows no values: {:?}",
+                        self
+                    );
+
+                    let total = choices.iter().map(|c| c.0).sum::<u32>();
+                    let mut pick = runner.rng().random_range(0..total);
+                    let &(_, lo, hi, or) = choices
+                        .iter()
+                        .find(|c| {
+                            if pick < c.0 {
+                                true
+                            } else {
+                                pick -= c.0;
+                                false
+                            }
+                        })
+                        .expect("weights sum to total");
+
+                    let value = $typ::from_bits(
+                        runner.rng().random_range(lo..=hi) | or,
+                    );
+                    match self.decimal_digits {
+                        Some(digits) if value.is_finite() => {
+                            round_decimal(value, digits)
+                        }
+                        _ => value,
+                    }
+                }
+            }
+
+            /// `ValueTree` corresponding to `FloatBuilder`.
+            #[derive(Clone, Copy, Debug)]
+            pub struct FloatBuilderValueTree {
+                builder: FloatBuilder,
+                search: BinarySearch,
+            }
+
+            impl Strategy for FloatBuilder {
+                type Tree = FloatBuilderValueTree;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    // Rounding to decimal digits can carry a value out of its
+                    // class or range.
+                    let value = loop {
+                        let value = self.generate(runner);
+                        if self.allows(value) {
+                            break value;
+                        }
+                        runner.reject_local("float builder() decimal digits")?;
+                    };
+
+                    let target = self
+                        .shrink_target(value.is_sign_negative())
+                        .unwrap_or(value);
+                    Ok(FloatBuilderValueTree {
+                        builder: *self,
+                        search: BinarySearch {
+                            lo: target,
+                            curr: value,
+                            hi: value,
+                            allowed: FloatTypes::all(),
+                        },
+                    })
+                }
+            }
+
+            impl ValueTree for FloatBuilderValueTree {
+                type Value = $typ;
+
+                fn current(&self) -> $typ {
+                    self.search.current()
+                }
+
+                fn simplify(&mut self) -> bool {
+                    if !self.search.simplify() {
+                        return false;
+                    }
+                    if self.builder.allows(self.search.current()) {
+                        true
+                    } else {
+                        // Floats only ever shrink straight to the target, so
+                        // this returns to the value before simplifying.
+                        self.search.complicate();
+                        false
+                    }
+                }
+
+                fn complicate(&mut self) -> bool {
+                    self.search.complicate()
+                }
+            }
+        }
+
+        pub use self::builder::{builder, FloatBuilder, FloatBuilderValueTree};
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::num;
+    use crate::strategy::*;
+    use crate::test_runner::*;
+
+    #[test]
+    fn bounded_normals_and_zeros() {
+        let mut runner = TestRunner::deterministic();
+        let input = num::f64::builder().normal().zero().range(-1e6..=1e6);
+        let (mut pos_zero, mut neg_zero) = (false, false);
+        for _ in 0..4096 {
+            let x = input.new_tree(&mut runner).unwrap().current();
+            assert!(x.is_normal() || x == 0.0, "{}", x);
+            assert!((-1e6..=1e6).contains(&x), "{}", x);
+            if x == 0.0 {
+                pos_zero |= x.is_sign_positive();
+                neg_zero |= x.is_sign_negative();
+            }
+        }
+        assert!(pos_zero && neg_zero);
+    }
+
+    #[test]
+    fn exponent_range_and_sign() {
+        let mut runner = TestRunner::deterministic();
+        let input = num::f32::builder().negative().exponent_range(-2..=3);
+        for _ in 0..1024 {
+            let x = input.new_tree(&mut runner).unwrap().current();
+            assert!((-16.0..=-0.25).contains(&x), "{}", x);
+        }
+    }
+
+    #[test]
+    fn exclusive_range() {
+        let mut runner = TestRunner::deterministic();
+        let input = num::f64::builder().subnormal().zero().range(0.0..1e-310);
+        for _ in 0..1024 {
+            let x = input.new_tree(&mut runner).unwrap().current();
+            assert!((0.0..1e-310).contains(&x), "{}", x);
+        }
+    }
+
+    #[test]
+    fn nan_payload() {
+        let mut runner = TestRunner::deterministic();
+        let input = num::f64::builder()
+            .quiet_nan()
+            .signaling_nan()
+            .positive()
+            .nan_payload(42);
+        for _ in 0..256 {
+            let x = input.new_tree(&mut runner).unwrap().current();
+            assert!(x.is_nan() && x.is_sign_positive());
+            assert_eq!(42, x.to_bits() & (0x0007_FFFF_FFFF_FFFF));
+        }
+    }
+
+    #[test]
+    fn decimal_digits() {
+        let mut runner = TestRunner::deterministic();
+        let input = num::f64::builder()
+            .normal()
+            .zero()
+            .range(-1.0..=1.0)
+            .decimal_digits(3);
+        for _ in 0..1024 {
+            let mut tree = input.new_tree(&mut runner).unwrap();
+            let x = tree.current();
+            assert_eq!(x, format!("{:.2e}", x).parse::<f64>().unwrap());
+            while tree.simplify() {}
+            assert_eq!(0.0, tree.current());
+        }
+    }
+
+    #[test]
+    fn shrinks_within_constraints() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(
+            &num::f64::builder().normal().range(10.0..1e9),
+            |x| {
+                prop_assert!(x < 1e3);
+                Ok(())
+            },
+        );
+        match result {
+            Err(TestError::Fail(_, x)) => assert!(x >= 1e3, "{}", x),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        let mut tree = num::f64::builder()
+            .normal()
+            .exponent_range(4..=8)
+            .new_tree(&mut runner)
+            .unwrap();
+        while tree.simplify() {}
+        assert_eq!(16.0, tree.current().abs());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_when_nothing_allowed() {
+        let mut runner = TestRunner::deterministic();
+        let _ = num::f64::builder()
+            .subnormal()
+            .range(1.0..2.0)
+            .new_tree(&mut runner);
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(num::f64::builder(), None);
+        check_strategy_sanity(
+            num::f32::builder()
+                .normal()
+                .subnormal()
+                .zero()
+                .infinite()
+                .quiet_nan()
+                .range(-5.0..5.0),
+            None,
+        );
+        check_strategy_sanity(num::f64::builder().decimal_digits(1), None);
+    }
+}
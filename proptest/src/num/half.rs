@@ -0,0 +1,352 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for the half-precision `f16` and `bf16` types of the `half`
+//! crate.
+//!
+//! The modules here mirror `num::f32`: the `Any` constants select classes of
+//! values in the same way, and ranges of either type are strategies. Values
+//! are sampled and shrunk as `f32`s, which can represent every value of both
+//! types exactly.
+
+use super::FloatLayout;
+
+impl FloatLayout for ::half::f16 {
+    type Bits = u16;
+
+    const SIGN_MASK: u16 = 0x8000;
+    const EXP_MASK: u16 = 0x7C00;
+    const EXP_ZERO: u16 = 0x3C00;
+    const MANTISSA_MASK: u16 = 0x03FF;
+}
+
+impl FloatLayout for ::half::bf16 {
+    type Bits = u16;
+
+    const SIGN_MASK: u16 = 0x8000;
+    const EXP_MASK: u16 = 0x7F80;
+    const EXP_ZERO: u16 = 0x3F80;
+    const MANTISSA_MASK: u16 = 0x007F;
+}
+
+macro_rules! half_float {
+    ($typ:ident) => {
+        #[allow(missing_docs)]
+        pub mod $typ {
+            use core::ops;
+
+            use ::half::$typ;
+            use rand::Rng;
+
+            use crate::num::float_samplers::F32U;
+            use crate::num::{self, FloatLayout, FloatTypes};
+            use crate::strategy::*;
+            use crate::test_runner::TestRunner;
+
+            float_any!($typ);
+
+            /// Return the greatest value less than the finite `value`.
+            fn next_down(value: $typ) -> $typ {
+                let bits = value.to_bits();
+                if value == $typ::ZERO {
+                    $typ::from_bits($typ::SIGN_MASK | 1)
+                } else if value > $typ::ZERO {
+                    $typ::from_bits(bits - 1)
+                } else {
+                    $typ::from_bits(bits + 1)
+                }
+            }
+
+            /// Return the greatest value not greater than `value`.
+            fn floor(value: f32) -> $typ {
+                let nearest = $typ::from_f32(value);
+                if nearest.to_f32() > value {
+                    next_down(nearest)
+                } else {
+                    nearest
+                }
+            }
+
+            /// Shrinks a float towards 0, using binary search to find boundary
+            /// points.
+            ///
+            /// Non-finite values immediately shrink to 0.
+            #[derive(Clone, Copy, Debug)]
+            pub struct BinarySearch {
+                start: $typ,
+                search: num::f32::BinarySearch,
+            }
+
+            impl BinarySearch {
+                /// Creates a new binary searcher starting at the given value.
+                pub fn new(start: $typ) -> Self {
+                    BinarySearch {
+                        start,
+                        search: num::f32::BinarySearch::new(start.to_f32()),
+                    }
+                }
+
+                fn new_with_types(start: $typ, allowed: FloatTypes) -> Self {
+                    // The subnormals of this type may be normal `f32`s. The
+                    // search only ever moves between `start` and 0, so only
+                    // the class of 0 needs checking.
+                    let allowed =
+                        allowed | FloatTypes::NORMAL | FloatTypes::SUBNORMAL;
+                    BinarySearch {
+                        start,
+                        search: num::f32::BinarySearch::new_with_types(
+                            start.to_f32(),
+                            allowed,
+                        ),
+                    }
+                }
+
+                /// Creates a new binary searcher which will not produce values
+                /// on the other side of `lo` or `hi` from `start`. `lo` and
+                /// `hi` are both inclusive.
+                fn new_clamped(lo: $typ, start: $typ, hi: $typ) -> Self {
+                    BinarySearch {
+                        start,
+                        search: num::f32::BinarySearch::new_clamped(
+                            lo.to_f32(),
+                            start.to_f32(),
+                            hi.to_f32(),
+                        ),
+                    }
+                }
+            }
+
+            impl ValueTree for BinarySearch {
+                type Value = $typ;
+
+                fn current(&self) -> $typ {
+                    let current = self.search.current();
+                    // Converting a NaN to `f32` may change its payload or
+                    // quieten it, but the only NaN the search can be at is
+                    // the one it started at.
+                    if current.is_nan() {
+                        self.start
+                    } else {
+                        $typ::from_f32(current)
+                    }
+                }
+
+                fn simplify(&mut self) -> bool {
+                    self.search.simplify()
+                }
+
+                fn complicate(&mut self) -> bool {
+                    self.search.complicate()
+                }
+            }
+
+            impl Strategy for ops::Range<$typ> {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    if self.is_empty() {
+                        panic!(
+                            "Invalid use of empty range {}..{}.",
+                            self.start, self.end
+                        );
+                    }
+
+                    let value = floor(
+                        num::sample_uniform::<F32U>(
+                            runner,
+                            self.start.to_f32().into(),
+                            self.end.to_f32().into(),
+                        )
+                        .into(),
+                    );
+                    Ok(BinarySearch::new_clamped(
+                        self.start,
+                        value,
+                        next_down(self.end),
+                    ))
+                }
+            }
+
+            impl Strategy for ops::RangeInclusive<$typ> {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    if self.is_empty() {
+                        panic!(
+                            "Invalid use of empty range {}..={}.",
+                            self.start(),
+                            self.end()
+                        );
+                    }
+
+                    let value = $typ::from_f32(
+                        num::sample_uniform_incl::<F32U>(
+                            runner,
+                            self.start().to_f32().into(),
+                            self.end().to_f32().into(),
+                        )
+                        .into(),
+                    );
+                    Ok(BinarySearch::new_clamped(
+                        *self.start(),
+                        value,
+                        *self.end(),
+                    ))
+                }
+            }
+
+            impl Strategy for ops::RangeFrom<$typ> {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    (self.start..=$typ::MAX).new_tree(runner)
+                }
+            }
+
+            impl Strategy for ops::RangeTo<$typ> {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    ($typ::MIN..self.end).new_tree(runner)
+                }
+            }
+
+            impl Strategy for ops::RangeToInclusive<$typ> {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    ($typ::MIN..=self.end).new_tree(runner)
+                }
+            }
+        }
+    };
+}
+
+half_float!(f16);
+half_float!(bf16);
+
+#[cfg(test)]
+mod test {
+    use ::half::{bf16, f16};
+
+    use crate::num::{half, FloatTypes};
+    use crate::strategy::*;
+    use crate::test_runner::*;
+
+    #[test]
+    fn classes_are_respected() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..1024 {
+            let x = half::f16::SUBNORMAL.new_tree(&mut runner).unwrap();
+            let class = x.current().classify();
+            assert_eq!(core::num::FpCategory::Subnormal, class);
+            let x = half::bf16::NEGATIVE.new_tree(&mut runner).unwrap();
+            assert!(x.current().is_normal(), "{}", x.current());
+            assert!(x.current().is_sign_negative(), "{}", x.current());
+        }
+    }
+
+    macro_rules! any_generates_allowed_classes {
+        ($name:ident, $typ:ident) => {
+            proptest! {
+                #[test]
+                fn $name(
+                    strategy in crate::bits::u32::ANY
+                        .prop_map(half::$typ::Any::from_bits)
+                ) {
+                    use core::num::FpCategory::*;
+
+                    let bits = strategy.normal_bits();
+                    let mut runner = TestRunner::deterministic();
+                    for _ in 0..64 {
+                        let x =
+                            strategy.new_tree(&mut runner).unwrap().current();
+                        let class = match x.classify() {
+                            Nan => {
+                                FloatTypes::QUIET_NAN
+                                    | FloatTypes::SIGNALING_NAN
+                            }
+                            Infinite => FloatTypes::INFINITE,
+                            Zero => FloatTypes::ZERO,
+                            Subnormal => FloatTypes::SUBNORMAL,
+                            Normal => FloatTypes::NORMAL,
+                        };
+                        let sign = if x.is_sign_negative() {
+                            FloatTypes::NEGATIVE
+                        } else {
+                            FloatTypes::POSITIVE
+                        };
+                        prop_assert!(bits.intersects(class), "{}", x);
+                        prop_assert!(bits.contains(sign), "{}", x);
+                    }
+                }
+            }
+        };
+    }
+
+    any_generates_allowed_classes!(f16_any_generates_allowed_classes, f16);
+    any_generates_allowed_classes!(bf16_any_generates_allowed_classes, bf16);
+
+    #[test]
+    fn signaling_nan_survives() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let x = half::f16::SIGNALING_NAN
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!(x.is_nan());
+            assert_eq!(0, x.to_bits() & 0x0200, "{:04x}", x.to_bits());
+        }
+    }
+
+    #[test]
+    fn ranges_are_respected() {
+        let mut runner = TestRunner::deterministic();
+        let (lo, hi) = (f16::from_f32(-2.0), f16::from_f32(0.5));
+        for _ in 0..1024 {
+            let x = (lo..hi).new_tree(&mut runner).unwrap().current();
+            assert!(lo <= x && x < hi, "{}", x);
+            let x = (lo..=hi).new_tree(&mut runner).unwrap().current();
+            assert!(lo <= x && x <= hi, "{}", x);
+        }
+
+        let (lo, hi) = (bf16::from_f32(1.0), bf16::from_f32(1.03125));
+        let mut seen = [false; 4];
+        for _ in 0..1024 {
+            let x = (lo..hi).new_tree(&mut runner).unwrap().current();
+            seen[((x.to_f32() - 1.0) * 128.0) as usize] = true;
+        }
+        assert_eq!([true; 4], seen);
+    }
+
+    #[test]
+    fn shrinks_to_range_bound() {
+        let mut runner = TestRunner::deterministic();
+        let input = f16::from_f32(10.0)..f16::from_f32(1000.0);
+        for _ in 0..64 {
+            let mut tree = input.new_tree(&mut runner).unwrap();
+            while tree.simplify() {}
+            assert_eq!(f16::from_f32(10.0), tree.current());
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(half::f16::ANY, None);
+        check_strategy_sanity(half::bf16::ANY | half::bf16::SIGNALING_NAN, None);
+        check_strategy_sanity(f16::from_f32(-5.0)..f16::from_f32(5.0), None);
+        check_strategy_sanity(..=bf16::from_f32(3.0), None);
+    }
+}
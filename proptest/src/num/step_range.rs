@@ -0,0 +1,250 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategy for integers on a stride.
+
+use core::fmt;
+use core::ops::Range;
+
+use crate::strategy::*;
+use crate::test_runner::*;
+
+use super::{sample_u128_below, u128::BinarySearch};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Integer types which `step_range()` can generate.
+///
+/// This trait is sealed and implemented for all primitive integer types.
+pub trait StepInt: private::Sealed + Copy + fmt::Debug {
+    /// Return the number of grid points in `start..end` with stride `step`,
+    /// and the index of the grid point nearest to zero.
+    #[doc(hidden)]
+    fn grid(start: Self, end: Self, step: Self) -> (u128, u128);
+
+    /// Return the grid point `index` steps above `start`.
+    #[doc(hidden)]
+    fn nth(start: Self, step: Self, index: u128) -> Self;
+}
+
+macro_rules! step_int {
+    ($signed:expr; $($typ:ident => $utyp:ident),*) => {$(
+        impl private::Sealed for $typ {}
+
+        impl StepInt for $typ {
+            #[allow(unused_comparisons)]
+            fn grid(start: $typ, end: $typ, step: $typ) -> (u128, u128) {
+                assert!(start < end, "step_range() requires a non-empty range");
+                assert!(step > 0, "step_range() requires a positive step");
+
+                // Wrapping arithmetic in the unsigned type gives the exact
+                // distance even when it overflows the signed type.
+                let step = step as $utyp;
+                let dist = end.wrapping_sub(start) as $utyp;
+                let count = (dist - 1) / step + 1;
+
+                let origin = if $signed && start < 0 {
+                    let to_zero = (0 as $typ).wrapping_sub(start) as $utyp;
+                    let (below, rem) = (to_zero / step, to_zero % step);
+                    let nearest = if rem > step - rem { below + 1 } else { below };
+                    nearest.min(count - 1)
+                } else {
+                    0
+                };
+
+                (count as u128, origin as u128)
+            }
+
+            fn nth(start: $typ, step: $typ, index: u128) -> $typ {
+                start.wrapping_add(
+                    (index as $utyp).wrapping_mul(step as $utyp) as $typ)
+            }
+        }
+    )*};
+}
+
+step_int!(true; i8 => u8, i16 => u16, i32 => u32, i64 => u64,
+          i128 => u128, isize => usize);
+step_int!(false; u8 => u8, u16 => u16, u32 => u32, u64 => u64,
+          u128 => u128, usize => usize);
+
+/// Strategy to generate integers on a stride.
+///
+/// Created by the `step_range()` function in the same module.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Copy, Debug)]
+pub struct StepRange<T> {
+    start: T,
+    step: T,
+    count: u128,
+    origin: u128,
+}
+
+/// `ValueTree` corresponding to `StepRange`.
+#[derive(Clone, Copy, Debug)]
+pub struct StepRangeValueTree<T> {
+    start: T,
+    step: T,
+    origin: u128,
+    /// Whether the current value lies above the origin.
+    above: bool,
+    /// The number of steps from the origin to the current value.
+    offset: BinarySearch,
+}
+
+/// Create a strategy to generate the values `start + k * step` within
+/// `range`, for any `k`, such as page-aligned offsets with a `step` of 4096.
+///
+/// Values are chosen uniformly among the points on the grid, and shrink
+/// along the grid towards the point nearest to zero. Unlike mapping a
+/// smaller range with `prop_map(|x| x * step)`, the strategy is written in
+/// terms of the true range and a failing value shrinks to the smallest grid
+/// point which still fails.
+///
+/// ```
+/// use proptest::num::step_range;
+/// use proptest::prelude::*;
+///
+/// proptest! {
+///     fn offsets_are_page_aligned(
+///         offset in step_range(0u64..1 << 30, 4096)
+///     ) {
+///         prop_assert_eq!(offset % 4096, 0);
+///     }
+/// }
+/// # fn main() { offsets_are_page_aligned(); }
+/// ```
+///
+/// ## Panics
+///
+/// Panics if `range` is empty or `step` is not positive.
+pub fn step_range<T: StepInt>(range: Range<T>, step: T) -> StepRange<T> {
+    let (count, origin) = T::grid(range.start, range.end, step);
+    StepRange {
+        start: range.start,
+        step,
+        count,
+        origin,
+    }
+}
+
+impl<T: StepInt> Strategy for StepRange<T> {
+    type Tree = StepRangeValueTree<T>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let index = sample_u128_below(runner, self.count);
+        let (above, offset) = if index >= self.origin {
+            (true, index - self.origin)
+        } else {
+            (false, self.origin - index)
+        };
+
+        Ok(StepRangeValueTree {
+            start: self.start,
+            step: self.step,
+            origin: self.origin,
+            above,
+            offset: BinarySearch::new(offset),
+        })
+    }
+}
+
+impl<T: StepInt> ValueTree for StepRangeValueTree<T> {
+    type Value = T;
+
+    fn current(&self) -> T {
+        let index = if self.above {
+            self.origin + self.offset.current()
+        } else {
+            self.origin - self.offset.current()
+        };
+        T::nth(self.start, self.step, index)
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.offset.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.offset.complicate()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn values_lie_on_grid() {
+        let mut runner = TestRunner::deterministic();
+        let input = step_range(-10_000i32..10_000, 4096);
+        let mut seen = [false; 5];
+        for _ in 0..256 {
+            let mut tree = input.new_tree(&mut runner).unwrap();
+            loop {
+                let x = tree.current();
+                assert!((-10_000..10_000).contains(&x));
+                assert_eq!(0, (x + 10_000) % 4096, "{}", x);
+                if !tree.simplify() {
+                    break;
+                }
+            }
+            seen[((tree.current() + 10_000) / 4096) as usize] = true;
+        }
+        // Everything shrinks to the grid point nearest to zero.
+        assert_eq!([false, false, true, false, false], seen);
+    }
+
+    #[test]
+    fn shrinks_to_smallest_failing_point() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&step_range(0u64..1 << 40, 4096), |x| {
+            prop_assert!(x < 1_000_000);
+            Ok(())
+        });
+
+        match result {
+            Err(TestError::Fail(_, x)) => assert_eq!(245 * 4096, x),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn extreme_ranges() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let x = step_range(i8::MIN..i8::MAX, 100)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!([-128, -28, 72].contains(&x), "{}", x);
+
+            let x = step_range(i128::MIN..i128::MAX, i128::MAX)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!([i128::MIN, -1, i128::MAX - 1].contains(&x), "{}", x);
+
+            let x = step_range(u128::MAX - 10..u128::MAX, 3)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert_eq!(0, (x - (u128::MAX - 10)) % 3);
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(step_range(-1000i64..1000, 7), None);
+        check_strategy_sanity(step_range(5usize..5000, 64), None);
+    }
+}
@@ -0,0 +1,182 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategy for probability vectors.
+
+use rand::Rng;
+
+use crate::std_facade::Vec;
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// Strategy to create `Vec<f64>`s whose entries are non-negative and sum to
+/// 1.
+///
+/// Created by the `stochastic_vec()` function in the same module.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Copy, Debug)]
+pub struct StochasticVecStrategy {
+    len: usize,
+}
+
+/// `ValueTree` corresponding to `StochasticVecStrategy`.
+#[derive(Clone, Debug)]
+pub struct StochasticVecValueTree {
+    /// The `len - 1` interior cut points of the generated vector, in
+    /// ascending order within `[0, 1]`.
+    cuts: Vec<f64>,
+    /// How far the cut points are moved from the uniform partition toward
+    /// `cuts`, in units of `1 / MIX_SCALE`.
+    ///
+    /// This is an integer since the float binary search only tries 0 rather
+    /// than converging on the boundary between passing and failing values.
+    mix: super::u64::BinarySearch,
+}
+
+/// The value of `StochasticVecValueTree::mix` for the generated cut points.
+const MIX_SCALE: u64 = 1 << 32;
+
+/// Create a strategy to generate `Vec<f64>`s of exactly `len` elements which
+/// are non-negative and sum to 1, up to rounding error.
+///
+/// The vectors are distributed uniformly over the probability simplex, like
+/// a flat Dirichlet distribution, except that occasionally some entries are
+/// exactly 0.
+///
+/// Values shrink toward the uniform distribution, in which every entry is
+/// `1.0 / len`, by moving all entries toward it at once.
+///
+/// ## Panics
+///
+/// Panics if `len` is 0.
+pub fn stochastic_vec(len: usize) -> StochasticVecStrategy {
+    assert!(len > 0, "stochastic_vec() requires a non-zero length");
+    StochasticVecStrategy { len }
+}
+
+impl Strategy for StochasticVecStrategy {
+    type Tree = StochasticVecValueTree;
+    type Value = Vec<f64>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let rng = runner.rng();
+        let mut cuts = Vec::with_capacity(self.len - 1);
+        for _ in 1..self.len {
+            // Repeat the previous cut point now and then to produce entries
+            // which are exactly 0.
+            let cut = match cuts.last() {
+                Some(&prev) if rng.random_ratio(1, 8) => prev,
+                _ => rng.random::<f64>(),
+            };
+            cuts.push(cut);
+        }
+        cuts.sort_by(f64::total_cmp);
+
+        Ok(StochasticVecValueTree {
+            cuts,
+            mix: super::u64::BinarySearch::new(MIX_SCALE),
+        })
+    }
+}
+
+impl ValueTree for StochasticVecValueTree {
+    type Value = Vec<f64>;
+
+    fn current(&self) -> Vec<f64> {
+        let len = self.cuts.len() + 1;
+        let mix = self.mix.current() as f64 / MIX_SCALE as f64;
+        // Interpolating between two ascending sequences of cut points yields
+        // another ascending sequence, so no entry can become negative.
+        let cut = |ix: usize| {
+            let uniform = ix as f64 / len as f64;
+            uniform + mix * (self.cuts[ix - 1] - uniform)
+        };
+
+        let mut values = Vec::with_capacity(len);
+        let mut prev = 0.0;
+        for ix in 1..len {
+            let next = cut(ix);
+            values.push(next - prev);
+            prev = next;
+        }
+        values.push(1.0 - prev);
+        values
+    }
+
+    fn simplify(&mut self) -> bool {
+        !self.cuts.is_empty() && self.mix.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        !self.cuts.is_empty() && self.mix.complicate()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_stochastic(values: &[f64]) {
+        assert!(values.iter().all(|&v| v >= 0.0), "{:?}", values);
+        let sum = values.iter().sum::<f64>();
+        assert!((sum - 1.0).abs() < 1e-9, "{:?} sums to {}", values, sum);
+    }
+
+    #[test]
+    fn generates_probability_vectors() {
+        let mut runner = TestRunner::deterministic();
+        let mut saw_zero = false;
+        for len in 1..20 {
+            let input = stochastic_vec(len);
+            for _ in 0..32 {
+                let mut tree = input.new_tree(&mut runner).unwrap();
+                let values = tree.current();
+                assert_eq!(len, values.len());
+                assert_stochastic(&values);
+                saw_zero |= values.contains(&0.0);
+
+                while tree.simplify() {
+                    assert_stochastic(&tree.current());
+                }
+            }
+        }
+        assert!(saw_zero);
+    }
+
+    #[test]
+    fn shrinks_to_uniform() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = stochastic_vec(4).new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        assert_eq!(vec![0.25; 4], tree.current());
+    }
+
+    #[test]
+    fn shrinks_to_minimal_skew() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&stochastic_vec(5), |values| {
+            prop_assert!(values.iter().all(|&v| v < 0.5));
+            Ok(())
+        });
+
+        match result {
+            Err(TestError::Fail(_, values)) => {
+                assert_stochastic(&values);
+                let max = values.iter().cloned().fold(0.0, f64::max);
+                assert!((0.5..0.501).contains(&max), "{:?}", values);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(stochastic_vec(6), None);
+    }
+}
@@ -0,0 +1,226 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for the integer wrapper types of `core::num`.
+//!
+//! The `int_non_zero!` macro adds a `NON_ZERO` strategy to the integer
+//! modules, which samples non-zero values directly rather than rejecting
+//! zero. `wrapping()` and `saturating()` wrap the values of any strategy.
+
+use core::num::{Saturating, Wrapping};
+
+use crate::strategy::*;
+use crate::test_runner::*;
+
+macro_rules! int_non_zero {
+    ($typ:ident, $incl:ident, signed) => {
+        int_non_zero!($typ, |runner| {
+            // Skip over zero by shifting the non-negative half up by one.
+            let value = $crate::num::$incl::<$typ>(
+                runner,
+                $typ::MIN,
+                $typ::MAX - 1,
+            );
+            let value = if value >= 0 { value + 1 } else { value };
+            BinarySearch::new_clamped(1, value, 0)
+        });
+    };
+    ($typ:ident, $incl:ident, unsigned) => {
+        int_non_zero!($typ, |runner| {
+            BinarySearch::new_above(
+                1,
+                $crate::num::$incl::<$typ>(runner, 1, $typ::MAX),
+            )
+        });
+    };
+    ($typ:ident, $sample:expr) => {
+        mod non_zero {
+            use core::num::NonZero;
+
+            use super::BinarySearch;
+            use crate::strategy::*;
+            use crate::test_runner::TestRunner;
+
+            /// Type of the `NON_ZERO` constant.
+            #[derive(Clone, Copy, Debug)]
+            #[must_use = "strategies do nothing unless used"]
+            pub struct NonZeroAny(());
+            /// Generates non-zero integers, uniformly distributed over all
+            /// non-zero values.
+            ///
+            /// Values shrink toward 1, or toward -1 if negative.
+            pub const NON_ZERO: NonZeroAny = NonZeroAny(());
+
+            /// `ValueTree` corresponding to `NonZeroAny`.
+            #[derive(Clone, Copy, Debug)]
+            pub struct NonZeroBinarySearch(BinarySearch);
+
+            impl Strategy for NonZeroAny {
+                type Tree = NonZeroBinarySearch;
+                type Value = NonZero<$typ>;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    let sample: fn(&mut TestRunner) -> BinarySearch = $sample;
+                    Ok(NonZeroBinarySearch(sample(runner)))
+                }
+            }
+
+            impl ValueTree for NonZeroBinarySearch {
+                type Value = NonZero<$typ>;
+
+                fn current(&self) -> NonZero<$typ> {
+                    NonZero::new(self.0.current())
+                        .expect("non-zero search reached zero")
+                }
+
+                fn simplify(&mut self) -> bool {
+                    self.0.simplify()
+                }
+
+                fn complicate(&mut self) -> bool {
+                    self.0.complicate()
+                }
+            }
+        }
+
+        pub use self::non_zero::{NonZeroAny, NonZeroBinarySearch, NON_ZERO};
+    };
+}
+
+macro_rules! wrapper_strategy {
+    ($wrapper:ident, $fun:ident, $strategy:ident, $tree:ident, $doc:expr) => {
+        #[doc = concat!("Strategy to generate `", stringify!($wrapper), "` values.")]
+        ///
+        #[doc = concat!("Created by the `", stringify!($fun), "()` function.")]
+        #[derive(Clone, Copy, Debug)]
+        #[must_use = "strategies do nothing unless used"]
+        pub struct $strategy<S>(S);
+
+        #[doc = concat!(
+            "Create a strategy to generate `", stringify!($wrapper),
+            "` values wrapping those generated by `source`."
+        )]
+        ///
+        #[doc = $doc]
+        ///
+        /// Values shrink as the values of `source` do, usually toward zero or
+        /// the start of the range `source` samples from.
+        pub fn $fun<S: Strategy>(source: S) -> $strategy<S> {
+            $strategy(source)
+        }
+
+        #[doc = concat!("`ValueTree` corresponding to `", stringify!($strategy), "`.")]
+        #[derive(Clone, Copy, Debug)]
+        pub struct $tree<T>(T);
+
+        impl<S: Strategy> Strategy for $strategy<S> {
+            type Tree = $tree<S::Tree>;
+            type Value = $wrapper<S::Value>;
+
+            fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                self.0.new_tree(runner).map($tree)
+            }
+        }
+
+        impl<T: ValueTree> ValueTree for $tree<T> {
+            type Value = $wrapper<T::Value>;
+
+            fn current(&self) -> $wrapper<T::Value> {
+                $wrapper(self.0.current())
+            }
+
+            fn simplify(&mut self) -> bool {
+                self.0.simplify()
+            }
+
+            fn complicate(&mut self) -> bool {
+                self.0.complicate()
+            }
+        }
+    };
+}
+
+wrapper_strategy!(
+    Wrapping,
+    wrapping,
+    WrappingStrategy,
+    WrappingValueTree,
+    "Arithmetic on `Wrapping` values wraps around on overflow, so pairing \
+     this with `ANY` or a range near the bounds of the type exercises it."
+);
+wrapper_strategy!(
+    Saturating,
+    saturating,
+    SaturatingStrategy,
+    SaturatingValueTree,
+    "Arithmetic on `Saturating` values clamps at the bounds of the type, so \
+     pairing this with `ANY` or a range near the bounds exercises it."
+);
+
+#[cfg(test)]
+mod test {
+    use crate::num;
+
+    use super::*;
+
+    #[test]
+    fn non_zero_covers_both_signs() {
+        let mut runner = TestRunner::deterministic();
+        let (mut negative, mut positive) = (0, 0);
+        for _ in 0..1024 {
+            let x = num::i8::NON_ZERO.new_tree(&mut runner).unwrap().current();
+            if x.get() < 0 {
+                negative += 1;
+            } else {
+                positive += 1;
+            }
+        }
+        assert!(negative > 400 && positive > 400, "{} {}", negative, positive);
+
+        for _ in 0..1024 {
+            let x = num::u8::NON_ZERO.new_tree(&mut runner).unwrap();
+            assert!(x.current().get() >= 1);
+        }
+    }
+
+    #[test]
+    fn non_zero_shrinks_toward_one() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let mut tree = num::i64::NON_ZERO.new_tree(&mut runner).unwrap();
+            let negative = tree.current().get() < 0;
+            while tree.simplify() {}
+            assert_eq!(if negative { -1 } else { 1 }, tree.current().get());
+
+            let mut tree = num::u128::NON_ZERO.new_tree(&mut runner).unwrap();
+            while tree.simplify() {}
+            assert_eq!(1, tree.current().get());
+        }
+    }
+
+    #[test]
+    fn wrappers_shrink_like_source() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = wrapping(10u32..1000).new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        assert_eq!(Wrapping(10), tree.current());
+
+        let mut tree = saturating(num::i16::ANY).new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        assert_eq!(Saturating(0), tree.current());
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(num::i32::NON_ZERO, None);
+        check_strategy_sanity(num::usize::NON_ZERO, None);
+        check_strategy_sanity(wrapping(num::u8::ANY), None);
+        check_strategy_sanity(saturating(-5i64..5), None);
+    }
+}
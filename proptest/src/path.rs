@@ -2,8 +2,20 @@
 //!
 //! [`PathParams`] in this module is used as the argument to the
 //! [`Arbitrary`](crate::arbitrary::Arbitrary) implementation for [`PathBuf`].
+//!
+//! [`valid()`] generates paths which are also guaranteed to be usable on the
+//! current platform.
+
+use std::path::{PathBuf, MAIN_SEPARATOR};
 
-use crate::{collection::SizeRange, string::StringParam};
+use crate::{
+    arbitrary::any,
+    collection::{vec, SizeRange},
+    std_facade::{Box, String, ToOwned, Vec},
+    strategy::*,
+    string::{string_regex, StringParam},
+    test_runner::*,
+};
 
 /// Parameters for the [`Arbitrary`] implementation for [`PathBuf`].
 ///
@@ -53,3 +65,253 @@ impl Default for PathParams {
         }
     }
 }
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates paths valid on the current platform.
+    ///
+    /// Created by the `valid()` function in the same module.
+    #[derive(Debug)]
+    pub struct ValidPathStrategy[][](SBoxedStrategy<PathBuf>)
+        -> ValidPathValueTree;
+    /// `ValueTree` corresponding to `ValidPathStrategy`.
+    pub struct ValidPathValueTree[][](Box<dyn ValueTree<Value = PathBuf>>)
+        -> PathBuf;
+}
+
+/// The maximum length of a path component, in bytes on Unix and in UTF-16
+/// code units on Windows.
+const MAX_COMPONENT_LEN: usize = 255;
+
+/// The maximum length of a whole path, in the same units.
+#[cfg(target_os = "macos")]
+const MAX_PATH_LEN: usize = 1023;
+#[cfg(windows)]
+const MAX_PATH_LEN: usize = 259;
+#[cfg(not(any(target_os = "macos", windows)))]
+const MAX_PATH_LEN: usize = 4095;
+
+/// Names which Windows reserves for devices, regardless of case or
+/// extension.
+#[cfg(windows)]
+const RESERVED_NAMES: &[&str] = &[
+    "CON",
+    "PRN",
+    "AUX",
+    "NUL",
+    "CONIN$",
+    "CONOUT$",
+    "COM0",
+    "COM1",
+    "COM2",
+    "COM3",
+    "COM4",
+    "COM5",
+    "COM6",
+    "COM7",
+    "COM8",
+    "COM9",
+    "COM\u{b9}",
+    "COM\u{b2}",
+    "COM\u{b3}",
+    "LPT0",
+    "LPT1",
+    "LPT2",
+    "LPT3",
+    "LPT4",
+    "LPT5",
+    "LPT6",
+    "LPT7",
+    "LPT8",
+    "LPT9",
+    "LPT\u{b9}",
+    "LPT\u{b2}",
+    "LPT\u{b3}",
+];
+
+/// Returns the length of `s` in the units of `MAX_PATH_LEN`.
+fn platform_len(s: &str) -> usize {
+    if cfg!(windows) {
+        s.encode_utf16().count()
+    } else {
+        s.len()
+    }
+}
+
+/// Returns whether `c` may not appear in a path component.
+fn is_forbidden(c: char) -> bool {
+    if cfg!(windows) {
+        c < ' ' || "<>:\"/\\|?*".contains(c)
+    } else {
+        c == '\0' || std::path::is_separator(c)
+    }
+}
+
+/// Turns `component` into a file name which is valid on the current
+/// platform, changing it as little as possible.
+fn sanitize_component(component: &str) -> String {
+    let mut name = String::new();
+    let mut len = 0;
+    for c in component.chars().filter(|&c| !is_forbidden(c)) {
+        len += platform_len(c.encode_utf8(&mut [0; 4]));
+        if len > MAX_COMPONENT_LEN {
+            break;
+        }
+        name.push(c);
+    }
+
+    #[cfg(windows)]
+    {
+        // Windows strips trailing dots and spaces, so such names cannot be
+        // created as written.
+        name.truncate(name.trim_end_matches(['.', ' ']).len());
+        let stem = name.split('.').next().unwrap_or("").trim_end();
+        if RESERVED_NAMES
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+        {
+            name.insert(0, '_');
+            if platform_len(&name) > MAX_COMPONENT_LEN {
+                name.pop();
+            }
+        }
+    }
+
+    if name.is_empty() || name == "." || name == ".." {
+        name = "_".to_owned();
+    }
+    name
+}
+
+/// Builds a path from `components`, dropping the components which would
+/// take it over the platform's length limit.
+fn build_valid_path(is_absolute: bool, components: Vec<String>) -> PathBuf {
+    let mut path = String::new();
+    if is_absolute {
+        if cfg!(windows) {
+            path.push_str("C:");
+        }
+        path.push(MAIN_SEPARATOR);
+    }
+
+    for component in components {
+        let component = sanitize_component(&component);
+        let separator = !path.is_empty() && !path.ends_with(MAIN_SEPARATOR);
+        if platform_len(&path)
+            + usize::from(separator)
+            + platform_len(&component)
+            > MAX_PATH_LEN
+        {
+            continue;
+        }
+        if separator {
+            path.push(MAIN_SEPARATOR);
+        }
+        path.push_str(&component);
+    }
+
+    path.into()
+}
+
+/// Creates a strategy which generates relative or absolute paths according
+/// to `params`, which are also valid on the current platform.
+///
+/// Unlike with `any::<PathBuf>()`, every component is a normal file name:
+///
+/// - Separators, NULs, and on Windows the other characters Windows forbids,
+///   are removed from the components.
+/// - Empty, `.` and `..` components are replaced by `_`.
+/// - On Windows, trailing dots and spaces are removed, and names reserved
+///   for devices, such as `CON` or `lpt1.txt`, get a `_` prefix.
+/// - Components are truncated to 255 bytes (UTF-16 code units on Windows),
+///   and components which would make the path longer than the platform's
+///   maximum path length are left out. The maximum is 4095 bytes, 1023 on
+///   macOS, and 259 code units on Windows, where absolute paths start with
+///   `C:\`.
+///
+/// ## Panics
+///
+/// Panics if the component regex of `params` is invalid.
+pub fn valid(params: PathParams) -> ValidPathStrategy {
+    let component = string_regex(params.component_regex().into())
+        .expect("invalid component regex");
+    ValidPathStrategy(
+        (any::<bool>(), vec(component, params.components()))
+            .prop_map(|(is_absolute, components)| {
+                build_valid_path(is_absolute, components)
+            })
+            .sboxed(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::Component;
+
+    use super::*;
+
+    #[test]
+    fn sanitizes_components() {
+        assert_eq!("_", sanitize_component(""));
+        assert_eq!("_", sanitize_component(".."));
+        assert_eq!("ab", sanitize_component("a/b"));
+        assert_eq!("ab", sanitize_component("a\0b"));
+        let long = sanitize_component(&"\u{1f600}".repeat(200));
+        assert!(platform_len(&long) > MAX_COMPONENT_LEN - 4);
+        assert!(platform_len(&long) <= MAX_COMPONENT_LEN);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn sanitizes_windows_components() {
+        assert_eq!("ab", sanitize_component("a<b>"));
+        assert_eq!("a", sanitize_component("a. ."));
+        assert_eq!("_con", sanitize_component("con"));
+        assert_eq!("_LPT1 .txt", sanitize_component("LPT1 .txt"));
+        assert_eq!("CONSOLE", sanitize_component("CONSOLE"));
+    }
+
+    #[test]
+    fn respects_path_length_limit() {
+        let path = build_valid_path(true, vec!["a".repeat(200); 100]);
+        assert!(platform_len(path.to_str().unwrap()) <= MAX_PATH_LEN);
+        assert!(path.is_absolute());
+    }
+
+    #[test]
+    fn paths_have_only_normal_components() {
+        let mut runner = TestRunner::deterministic();
+        let input =
+            valid(PathParams::default().with_component_regex("[./\\\\a]{0,3}"));
+        for _ in 0..256 {
+            let path = input.new_tree(&mut runner).unwrap().current();
+            for component in path.components() {
+                assert!(
+                    matches!(
+                        component,
+                        Component::Normal(_)
+                            | Component::RootDir
+                            | Component::Prefix(_)
+                    ),
+                    "{:?}",
+                    path
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn paths_can_be_created() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut runner = TestRunner::deterministic();
+        let input = valid(PathParams::default().with_components(1..4));
+        for _ in 0..64 {
+            let path = input.new_tree(&mut runner).unwrap().current();
+            if path.has_root() {
+                continue;
+            }
+            fs::create_dir_all(dir.path().join(&path))
+                .unwrap_or_else(|err| panic!("{} creating {:?}", err, path));
+        }
+    }
+}
@@ -25,8 +25,8 @@ pub use crate::strategy::{BoxedStrategy, Just, SBoxedStrategy, Strategy};
 pub use crate::test_runner::Config as ProptestConfig;
 pub use crate::test_runner::{TestCaseError, ProptestResultExt};
 pub use crate::{
-    prop_assert, prop_assert_eq, prop_assert_ne, prop_assume, prop_compose,
-    prop_oneof, proptest,
+    prop_assert, prop_assert_eq, prop_assert_ne, prop_assume, prop_classify,
+    prop_collect, prop_compose, prop_oneof, proptest,
 };
 
 pub use rand::{Rng, RngCore};
@@ -41,6 +41,8 @@ pub mod prop {
     pub use crate::bool;
     pub use crate::char;
     pub use crate::collection;
+    #[cfg(feature = "std")]
+    pub use crate::func;
     pub use crate::num;
     pub use crate::option;
     pub use crate::result;
@@ -21,7 +21,9 @@ use core::u64;
 
 use rand::Rng;
 
-use crate::bits::{self, BitSetValueTree, SampledBitSetStrategy, VarBitSet};
+use crate::bits::{
+    self, BitSetLike, BitSetValueTree, SampledBitSetStrategy, VarBitSet,
+};
 use crate::num;
 use crate::strategy::*;
 use crate::test_runner::*;
@@ -115,6 +117,409 @@ impl<T: fmt::Debug + Clone + 'static> ValueTree for SubsequenceValueTree<T> {
     }
 }
 
+/// Sample contiguous subslices whose size are within `size` from the given
+/// collection `values`.
+///
+/// Unlike [`subsequence`], the elements are always adjacent in `values`,
+/// which matters when e.g. splitting input into chunks. The subslice is
+/// chosen by picking a length within `size` and then a uniformly random
+/// start where a subslice of that length fits.
+///
+/// Shrinking first makes the subslice shorter, and then moves it toward the
+/// start of `values`.
+///
+/// `values` may be a static slice or a `Vec`.
+///
+/// ## Panics
+///
+/// Panics if the maximum size implied by `size` is larger than the size of
+/// `values`.
+///
+/// Panics if `size` is a zero-length range.
+pub fn subslice<T: Clone + 'static>(
+    values: impl Into<Cow<'static, [T]>>,
+    size: impl Into<SizeRange>,
+) -> Subslice<T> {
+    let values = values.into();
+    let len = values.len();
+    let size = size.into();
+
+    size.assert_nonempty();
+    assert!(
+        size.end_incl() <= len,
+        "Maximum size of subslice {} exceeds length of input {}",
+        size.end_incl(),
+        len
+    );
+    Subslice {
+        values: Arc::new(values),
+        size,
+    }
+}
+
+/// Strategy to generate `Vec`s by sampling a contiguous subslice from another
+/// collection.
+///
+/// This is created by the `subslice` function in the same module.
+#[derive(Debug, Clone)]
+#[must_use = "strategies do nothing unless used"]
+pub struct Subslice<T: Clone + 'static> {
+    values: Arc<Cow<'static, [T]>>,
+    size: SizeRange,
+}
+
+impl<T: fmt::Debug + Clone + 'static> Strategy for Subslice<T> {
+    type Tree = SubsliceValueTree<T>;
+    type Value = Vec<T>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let (min, max) = self.size.start_end_incl();
+        let max = runner.sized(min, max);
+        let len = num::sample_uniform_incl(runner, min, max);
+        let start =
+            num::sample_uniform_incl(runner, 0, self.values.len() - len);
+        Ok(SubsliceValueTree {
+            values: Arc::clone(&self.values),
+            len: num::usize::BinarySearch::new_above(min, len),
+            start: num::usize::BinarySearch::new(start),
+            shrinker: SubsliceShrink::Len,
+            prev_shrinker: None,
+        })
+    }
+}
+
+/// The parts of a `SubsliceValueTree` in the order they are shrunk.
+#[derive(Debug, Clone, Copy)]
+enum SubsliceShrink {
+    Len,
+    Start,
+}
+
+/// `ValueTree` for `Subslice`.
+#[derive(Debug, Clone)]
+pub struct SubsliceValueTree<T: Clone + 'static> {
+    values: Arc<Cow<'static, [T]>>,
+    len: num::usize::BinarySearch,
+    start: num::usize::BinarySearch,
+    shrinker: SubsliceShrink,
+    prev_shrinker: Option<SubsliceShrink>,
+}
+
+impl<T: Clone + 'static> SubsliceValueTree<T> {
+    fn tree(&mut self, part: SubsliceShrink) -> &mut num::usize::BinarySearch {
+        match part {
+            SubsliceShrink::Len => &mut self.len,
+            SubsliceShrink::Start => &mut self.start,
+        }
+    }
+}
+
+impl<T: fmt::Debug + Clone + 'static> ValueTree for SubsliceValueTree<T> {
+    type Value = Vec<T>;
+
+    fn current(&self) -> Self::Value {
+        let start = self.start.current();
+        self.values[start..start + self.len.current()].to_vec()
+    }
+
+    // Neither making the subslice shorter nor moving it toward the start
+    // can move its end past the end of `values`.
+    fn simplify(&mut self) -> bool {
+        loop {
+            let shrinker = self.shrinker;
+            if self.tree(shrinker).simplify() {
+                self.prev_shrinker = Some(shrinker);
+                return true;
+            }
+
+            match shrinker {
+                SubsliceShrink::Len => self.shrinker = SubsliceShrink::Start,
+                SubsliceShrink::Start => return false,
+            }
+        }
+    }
+
+    fn complicate(&mut self) -> bool {
+        if let Some(shrinker) = self.prev_shrinker {
+            if self.tree(shrinker).complicate() {
+                self.shrinker = shrinker;
+                return true;
+            } else {
+                self.prev_shrinker = None;
+            }
+        }
+        false
+    }
+}
+
+/// Sample combinations of `size` distinct elements from the given collection
+/// `values`, in the order they occur in that collection.
+///
+/// This generates the same values as [`subsequence`], but shrinks toward the
+/// combinations that come first: it first removes elements, and then moves
+/// each remaining element toward the start of `values`, so that the minimal
+/// combination of `n` elements is the first `n` elements of `values`.
+///
+/// `values` may be a static slice or a `Vec`.
+///
+/// ## Panics
+///
+/// Panics if the maximum size implied by `size` is larger than the size of
+/// `values`.
+///
+/// Panics if `size` is a zero-length range.
+pub fn combinations<T: Clone + 'static>(
+    values: impl Into<Cow<'static, [T]>>,
+    size: impl Into<SizeRange>,
+) -> Combinations<T> {
+    let values = values.into();
+    let len = values.len();
+    let size = size.into();
+
+    size.assert_nonempty();
+    assert!(
+        size.end_incl() <= len,
+        "Maximum size of combination {} exceeds length of input {}",
+        size.end_incl(),
+        len
+    );
+    Combinations {
+        values: Arc::new(values),
+        size,
+    }
+}
+
+/// Strategy to generate `Vec`s by sampling a combination of elements from
+/// another collection.
+///
+/// This is created by the `combinations` function in the same module.
+#[derive(Debug, Clone)]
+#[must_use = "strategies do nothing unless used"]
+pub struct Combinations<T: Clone + 'static> {
+    values: Arc<Cow<'static, [T]>>,
+    size: SizeRange,
+}
+
+impl<T: fmt::Debug + Clone + 'static> Strategy for Combinations<T> {
+    type Tree = CombinationsValueTree<T>;
+    type Value = Vec<T>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let (min, max) = self.size.start_end_incl();
+        let max = runner.sized(min, max);
+        let size = num::sample_uniform_incl(runner, min, max);
+        let mut indices =
+            rand::seq::index::sample(runner.rng(), self.values.len(), size)
+                .into_vec();
+        indices.sort_unstable();
+        Ok(CombinationsValueTree {
+            values: Arc::clone(&self.values),
+            indices: indices
+                .into_iter()
+                .map(num::usize::BinarySearch::new)
+                .collect(),
+            included_indices: VarBitSet::saturated(size),
+            min_size: min,
+            shrink: CombinationsShrink::DeleteIndex(0),
+            prev_shrink: None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum CombinationsShrink {
+    DeleteIndex(usize),
+    ShiftIndex(usize),
+}
+
+/// `ValueTree` for `Combinations`.
+#[derive(Debug, Clone)]
+pub struct CombinationsValueTree<T: Clone + 'static> {
+    values: Arc<Cow<'static, [T]>>,
+    /// The indices of the chosen elements in ascending order.
+    indices: Vec<num::usize::BinarySearch>,
+    included_indices: VarBitSet,
+    min_size: usize,
+    shrink: CombinationsShrink,
+    prev_shrink: Option<CombinationsShrink>,
+}
+
+impl<T: Clone + 'static> CombinationsValueTree<T> {
+    /// Prepares to shift the index at `ix` toward the start, but not past
+    /// the previous included index, which has already been shifted.
+    fn start_shift(&mut self, ix: usize) {
+        self.shrink = CombinationsShrink::ShiftIndex(ix);
+        if let Some(index) = self.indices.get(ix).map(ValueTree::current) {
+            let lo = (0..ix)
+                .rev()
+                .find(|&prev| self.included_indices.test(prev))
+                .map_or(0, |prev| self.indices[prev].current() + 1);
+            self.indices[ix] = num::usize::BinarySearch::new_above(lo, index);
+        }
+    }
+}
+
+impl<T: fmt::Debug + Clone + 'static> ValueTree for CombinationsValueTree<T> {
+    type Value = Vec<T>;
+
+    fn current(&self) -> Self::Value {
+        self.indices
+            .iter()
+            .enumerate()
+            .filter(|&(ix, _)| self.included_indices.test(ix))
+            .map(|(_, index)| self.values[index.current()].clone())
+            .collect()
+    }
+
+    fn simplify(&mut self) -> bool {
+        // As for `VecValueTree`, we first delete elements until we can do so
+        // no further, and then shift each remaining index in sequence. Since
+        // the indices before the one being shifted no longer change, it can
+        // be shifted down to just after them, keeping the indices distinct
+        // and in order.
+        if let CombinationsShrink::DeleteIndex(ix) = self.shrink {
+            if ix >= self.indices.len()
+                || self.included_indices.count() == self.min_size
+            {
+                self.start_shift(0);
+            } else {
+                self.included_indices.clear(ix);
+                self.prev_shrink = Some(self.shrink);
+                self.shrink = CombinationsShrink::DeleteIndex(ix + 1);
+                return true;
+            }
+        }
+
+        while let CombinationsShrink::ShiftIndex(ix) = self.shrink {
+            if ix >= self.indices.len() {
+                // Nothing more we can do
+                return false;
+            }
+
+            if !self.included_indices.test(ix) || !self.indices[ix].simplify() {
+                // Move on to the next index
+                self.start_shift(ix + 1);
+            } else {
+                self.prev_shrink = Some(self.shrink);
+                return true;
+            }
+        }
+
+        panic!("Unexpected shrink state");
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.prev_shrink {
+            None => false,
+            Some(CombinationsShrink::DeleteIndex(ix)) => {
+                // Undo the last deletion. Can't complicate any further.
+                self.included_indices.set(ix);
+                self.prev_shrink = None;
+                true
+            }
+            Some(CombinationsShrink::ShiftIndex(ix)) => {
+                if self.indices[ix].complicate() {
+                    true
+                } else {
+                    self.prev_shrink = None;
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Sample permutations of the given collection `values`.
+///
+/// Every permutation is equally likely. Shrinking moves toward the original
+/// order of `values` one position at a time from the front, putting the
+/// earliest element of `values` it can at each position, so that the
+/// elements after the ones that matter end up in their original order.
+///
+/// This is an alternative to `Just(values).prop_shuffle()`, which shrinks
+/// only by limiting how far elements are moved.
+///
+/// `values` may be a static slice or a `Vec`.
+pub fn permutations<T: Clone + 'static>(
+    values: impl Into<Cow<'static, [T]>>,
+) -> Permutations<T> {
+    Permutations(Arc::new(values.into()))
+}
+
+/// Strategy to generate `Vec`s by permuting another collection.
+///
+/// This is created by the `permutations` function in the same module.
+#[derive(Debug, Clone)]
+#[must_use = "strategies do nothing unless used"]
+pub struct Permutations<T: Clone + 'static>(Arc<Cow<'static, [T]>>);
+
+impl<T: fmt::Debug + Clone + 'static> Strategy for Permutations<T> {
+    type Tree = PermutationsValueTree<T>;
+    type Value = Vec<T>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        // The permutation is represented by its Lehmer code, where digit `ix`
+        // picks which of the elements not picked before goes at `ix`. The
+        // code of all zeros is the identity, and drawing each digit uniformly
+        // makes every permutation equally likely.
+        let len = self.0.len();
+        let code = (0..len)
+            .map(|ix| runner.rng().random_range(0..len - ix))
+            .map(num::usize::BinarySearch::new)
+            .collect();
+        Ok(PermutationsValueTree {
+            values: Arc::clone(&self.0),
+            code,
+            shrinker: 0,
+            prev_shrinker: None,
+        })
+    }
+}
+
+/// `ValueTree` for `Permutations`.
+#[derive(Debug, Clone)]
+pub struct PermutationsValueTree<T: Clone + 'static> {
+    values: Arc<Cow<'static, [T]>>,
+    code: Vec<num::usize::BinarySearch>,
+    shrinker: usize,
+    prev_shrinker: Option<usize>,
+}
+
+impl<T: fmt::Debug + Clone + 'static> ValueTree for PermutationsValueTree<T> {
+    type Value = Vec<T>;
+
+    fn current(&self) -> Self::Value {
+        let mut remaining = (0..self.values.len()).collect::<Vec<_>>();
+        self.code
+            .iter()
+            .map(|digit| self.values[remaining.remove(digit.current())].clone())
+            .collect()
+    }
+
+    fn simplify(&mut self) -> bool {
+        while let Some(digit) = self.code.get_mut(self.shrinker) {
+            if digit.simplify() {
+                self.prev_shrinker = Some(self.shrinker);
+                return true;
+            }
+            self.shrinker += 1;
+        }
+        false
+    }
+
+    fn complicate(&mut self) -> bool {
+        if let Some(shrinker) = self.prev_shrinker {
+            if self.code[shrinker].complicate() {
+                self.shrinker = shrinker;
+                return true;
+            } else {
+                self.prev_shrinker = None;
+            }
+        }
+        false
+    }
+}
+
 #[derive(Debug, Clone)]
 struct SelectMapFn<T: Clone + 'static>(Arc<Cow<'static, [T]>>);
 
@@ -163,6 +568,113 @@ pub fn select<T: Clone + fmt::Debug + 'static>(
     Select(statics::Map::new(0..cow.len(), SelectMapFn(Arc::new(cow))))
 }
 
+/// Create a strategy which selects one value from `values`, where each value
+/// is paired with its relative weight.
+///
+/// `values` should be a `&'static [(u32, T)]` or a `Vec<(u32, T)>`, or
+/// potentially another type that can be coerced to
+/// `Cow<'static, [(u32, T)]>`.
+///
+/// A value with weight `w` is picked with probability `w / total`, where
+/// `total` is the sum of all weights, as with weighted `prop_oneof!`. Values
+/// with weight 0 are never picked.
+///
+/// Shrinking binary searches toward the value with the highest weight, with
+/// ties broken in favour of the value that occurs first in `values`.
+///
+/// ## Panics
+///
+/// Panics if `values` is empty or if all weights are 0.
+pub fn select_weighted<T: Clone + fmt::Debug + 'static>(
+    values: impl Into<Cow<'static, [(u32, T)]>>,
+) -> SelectWeighted<T> {
+    select_weighted_iter(values.into().iter().cloned())
+}
+
+/// Same as [`select_weighted`], but taking the weighted values from any
+/// iterator instead.
+///
+/// ## Panics
+///
+/// Panics if `values` is empty or if all weights are 0.
+pub fn select_weighted_iter<T: Clone + fmt::Debug + 'static>(
+    values: impl IntoIterator<Item = (u32, T)>,
+) -> SelectWeighted<T> {
+    let mut values = values.into_iter().collect::<Vec<_>>();
+    assert!(!values.is_empty(), "Cannot select from empty collection");
+
+    // Order the values the way they shrink. The sort is stable, so values
+    // of equal weight keep their order. Values of weight 0 come last and
+    // thus never lie between a picked value and the start.
+    values.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    let mut total = 0u64;
+    let (cumulative, values) = values
+        .into_iter()
+        .map(|(weight, value)| {
+            total += u64::from(weight);
+            (total, value)
+        })
+        .unzip();
+    assert!(total > 0, "Cannot select when all weights are 0");
+
+    SelectWeighted {
+        values: Arc::new(values),
+        cumulative: Arc::new(cumulative),
+    }
+}
+
+/// Strategy to produce one value from a fixed collection of weighted options.
+///
+/// Created by `select_weighted()` or `select_weighted_iter()` in the same
+/// module.
+#[derive(Clone, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct SelectWeighted<T: Clone + 'static> {
+    /// The values ordered by descending weight.
+    values: Arc<Vec<T>>,
+    /// The cumulative weights of `values`.
+    cumulative: Arc<Vec<u64>>,
+}
+
+impl<T: Clone + fmt::Debug + 'static> Strategy for SelectWeighted<T> {
+    type Tree = SelectWeightedValueTree<T>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let total = *self.cumulative.last().unwrap();
+        let pick = runner.rng().random_range(0..total);
+        let ix = self.cumulative.partition_point(|&c| c <= pick);
+        Ok(SelectWeightedValueTree {
+            values: Arc::clone(&self.values),
+            inner: num::usize::BinarySearch::new(ix),
+        })
+    }
+}
+
+/// `ValueTree` corresponding to `SelectWeighted`.
+#[derive(Clone, Debug)]
+pub struct SelectWeightedValueTree<T: Clone + 'static> {
+    values: Arc<Vec<T>>,
+    inner: num::usize::BinarySearch,
+}
+
+impl<T: Clone + fmt::Debug + 'static> ValueTree for SelectWeightedValueTree<T> {
+    type Value = T;
+
+    fn current(&self) -> T {
+        self.values[self.inner.current()].clone()
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.inner.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.inner.complicate()
+    }
+}
+
 /// A stand-in for an index into a slice or similar collection or conceptually
 /// similar things.
 ///
@@ -420,7 +932,7 @@ impl Selector {
 
 #[cfg(test)]
 mod test {
-    use crate::std_facade::BTreeSet;
+    use crate::std_facade::{BTreeMap, BTreeSet};
 
     use super::*;
     use crate::arbitrary::any;
@@ -507,6 +1019,253 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_subslice() {
+        static VALUES: &[usize] = &[0, 1, 2, 3, 4, 5, 6, 7];
+        let mut size_counts = [0; 8];
+        let mut start_counts = [0; 8];
+
+        let mut runner = TestRunner::deterministic();
+        let input = subslice(VALUES, 2..5);
+
+        for _ in 0..2048 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            // Generated the correct number of items
+            assert!(value.len() >= 2 && value.len() < 5);
+            // Chose adjacent items
+            assert!(value.windows(2).all(|w| w[0] + 1 == w[1]));
+
+            size_counts[value.len()] += 1;
+            start_counts[value[0]] += 1;
+        }
+
+        for (size, &count) in size_counts.iter().enumerate().take(5).skip(2) {
+            assert!(
+                (512..896).contains(&count),
+                "size {} was chosen {} times",
+                size,
+                count
+            );
+        }
+
+        for (ix, &count) in start_counts.iter().enumerate() {
+            assert!(
+                ix <= 6 && count > 0 || ix > 6 && count == 0,
+                "Start {} was chosen {} times",
+                ix,
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn subslice_shrinks_to_shortest_at_front() {
+        let mut runner = TestRunner::deterministic();
+        let input = subslice((0..20).collect::<Vec<u32>>(), 3..10);
+
+        for _ in 0..16 {
+            let mut tree = input.new_tree(&mut runner).unwrap();
+            while tree.simplify() {}
+
+            assert_eq!(vec![0, 1, 2], tree.current());
+        }
+    }
+
+    #[test]
+    fn subslice_full_vec_works() {
+        let v = vec![1u32, 2u32, 3u32];
+        let mut runner = TestRunner::deterministic();
+        let input = subslice(v.clone(), 3);
+        assert_eq!(v, input.new_tree(&mut runner).unwrap().current());
+    }
+
+    #[test]
+    fn test_subslice_sanity() {
+        check_strategy_sanity(subslice(vec![0, 1, 2, 3, 4], 1..3), None);
+    }
+
+    #[test]
+    fn test_combinations() {
+        static VALUES: &[usize] = &[0, 1, 2, 3, 4, 5, 6, 7];
+        let mut value_counts = [0; 8];
+
+        let mut runner = TestRunner::deterministic();
+        let input = combinations(VALUES, 3);
+
+        for _ in 0..2048 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            // Chose distinct items in order
+            assert_eq!(3, value.len());
+            assert!(value.windows(2).all(|w| w[0] < w[1]));
+
+            for value in value {
+                value_counts[value] += 1;
+            }
+        }
+
+        for (ix, &v) in value_counts.iter().enumerate() {
+            assert!(
+                (640..896).contains(&v),
+                "Value {} was chosen {} times",
+                ix,
+                v
+            );
+        }
+    }
+
+    #[test]
+    fn combinations_shrink_to_first_elements() {
+        let mut runner = TestRunner::deterministic();
+        let input = combinations((0..20).collect::<Vec<u32>>(), 2..6);
+
+        for _ in 0..16 {
+            let mut tree = input.new_tree(&mut runner).unwrap();
+            while tree.simplify() {}
+
+            assert_eq!(vec![0, 1], tree.current());
+        }
+    }
+
+    #[test]
+    fn combinations_shrink_to_minimal_failure() {
+        let mut runner = TestRunner::deterministic();
+        let input = combinations((0..20).collect::<Vec<u32>>(), 0..10);
+
+        for _ in 0..64 {
+            let case = input.new_tree(&mut runner).unwrap();
+            let result = runner.run_one(case, |v| {
+                prop_assert!(!v.iter().any(|&x| x >= 10));
+                Ok(())
+            });
+
+            match result {
+                Ok(true) => {}
+                Err(TestError::Fail(_, value)) => assert_eq!(vec![10], value),
+                result => panic!("Unexpected result: {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_combinations_sanity() {
+        check_strategy_sanity(combinations(vec![0, 1, 2, 3, 4], 1..4), None);
+    }
+
+    #[test]
+    fn test_permutations() {
+        let values = vec![0, 1, 2];
+        let mut counts = BTreeMap::new();
+
+        let mut runner = TestRunner::deterministic();
+        let input = permutations(values.clone());
+
+        for _ in 0..1200 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            *counts.entry(value).or_insert(0) += 1;
+        }
+
+        assert_eq!(6, counts.len());
+        for (value, &count) in &counts {
+            let mut sorted = value.clone();
+            sorted.sort();
+            assert_eq!(values, sorted);
+            assert!(
+                (150..250).contains(&count),
+                "Generated {:?} {} times",
+                value,
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn permutations_shrink_to_original_order() {
+        let mut runner = TestRunner::deterministic();
+        let input = permutations((0..10).collect::<Vec<u32>>());
+
+        for _ in 0..64 {
+            let case = input.new_tree(&mut runner).unwrap();
+            let result = runner.run_one(case, |v| {
+                prop_assert!(v.iter().position(|&x| x == 7) > Some(2));
+                Ok(())
+            });
+
+            match result {
+                Ok(true) => {}
+                Err(TestError::Fail(_, mut value)) => {
+                    // Only 7 is out of place.
+                    assert!(value.iter().position(|&x| x == 7) <= Some(2));
+                    value.retain(|&x| x != 7);
+                    assert_eq!(vec![0, 1, 2, 3, 4, 5, 6, 8, 9], value);
+                }
+                result => panic!("Unexpected result: {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_permutations_sanity() {
+        check_strategy_sanity(permutations(vec![0, 1, 2, 3, 4]), None);
+    }
+
+    #[test]
+    fn test_select_weighted() {
+        let values = vec![(1, 'a'), (0, 'b'), (3, 'c'), (4, 'd')];
+        let mut counts = [0; 4];
+
+        let mut runner = TestRunner::deterministic();
+        let input = select_weighted(values);
+
+        for _ in 0..2048 {
+            let value = input.new_tree(&mut runner).unwrap().current();
+            counts[(value as u8 - b'a') as usize] += 1;
+        }
+
+        assert_eq!(0, counts[1], "Generated value of weight 0");
+        for (ix, weight) in [(0, 1), (2, 3), (3, 4)] {
+            let expected = 2048 / 8 * weight;
+            assert!(
+                counts[ix] >= expected * 3 / 4 && counts[ix] < expected * 5 / 4,
+                "Generated value {} {} times",
+                ix,
+                counts[ix]
+            );
+        }
+    }
+
+    #[test]
+    fn select_weighted_shrinks_to_highest_weight() {
+        let mut runner = TestRunner::deterministic();
+        let input =
+            select_weighted_iter(vec![(1, "foo"), (2, "bar"), (2, "baz")]);
+        let mut seen = BTreeSet::new();
+
+        for _ in 0..64 {
+            let mut tree = input.new_tree(&mut runner).unwrap();
+            seen.insert(tree.current());
+
+            while tree.simplify() {}
+
+            assert_eq!("bar", tree.current());
+        }
+
+        assert_eq!(3, seen.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "all weights are 0")]
+    fn select_weighted_rejects_zero_total() {
+        let _ = select_weighted(vec![(0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn test_select_weighted_sanity() {
+        check_strategy_sanity(
+            select_weighted(vec![(1, 0), (2, 1), (0, 2), (5, 3)]),
+            None,
+        );
+    }
+
     #[test]
     fn test_sample_sanity() {
         check_strategy_sanity(subsequence(vec![0, 1, 2, 3, 4], 1..3), None);
@@ -0,0 +1,1098 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for SQL identifiers and simple queries.
+//!
+//! `identifier()` generates [`Identifier`]s, which may be quoted and have
+//! names made of quotes, comment markers and other fragments of SQL syntax,
+//! or colliding with reserved words. `query()` generates small [`Query`]
+//! trees using such identifiers, and string literals made of the same kind
+//! of fragments. Both render themselves as SQL with `Display`, escaping
+//! quotes by doubling them, so the text is always syntactically valid
+//! standard SQL. This is aimed at testing query builders and escaping
+//! functions against inputs shaped like injection attacks.
+
+use core::fmt;
+
+use crate::arbitrary::any;
+use crate::collection::{vec, SizeRange};
+use crate::sample::select;
+use crate::std_facade::{Box, String, ToOwned, Vec};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// The reserved words of SQL:2016, and a few reserved by common dialects.
+const RESERVED_WORDS: &[&str] = &[
+    "ABS",
+    "ACOS",
+    "ALL",
+    "ALLOCATE",
+    "ALTER",
+    "AND",
+    "ANY",
+    "ARE",
+    "ARRAY",
+    "ARRAY_AGG",
+    "ARRAY_MAX_CARDINALITY",
+    "AS",
+    "ASC",
+    "ASENSITIVE",
+    "ASIN",
+    "ASYMMETRIC",
+    "AT",
+    "ATAN",
+    "ATOMIC",
+    "AUTHORIZATION",
+    "AVG",
+    "BEGIN",
+    "BEGIN_FRAME",
+    "BEGIN_PARTITION",
+    "BETWEEN",
+    "BIGINT",
+    "BINARY",
+    "BLOB",
+    "BOOLEAN",
+    "BOTH",
+    "BY",
+    "CALL",
+    "CALLED",
+    "CARDINALITY",
+    "CASCADED",
+    "CASE",
+    "CAST",
+    "CEIL",
+    "CEILING",
+    "CHAR",
+    "CHARACTER",
+    "CHARACTER_LENGTH",
+    "CHAR_LENGTH",
+    "CHECK",
+    "CLASSIFIER",
+    "CLOB",
+    "CLOSE",
+    "COALESCE",
+    "COLLATE",
+    "COLLECT",
+    "COLUMN",
+    "COMMIT",
+    "CONDITION",
+    "CONNECT",
+    "CONSTRAINT",
+    "CONTAINS",
+    "CONVERT",
+    "COPY",
+    "CORR",
+    "CORRESPONDING",
+    "COS",
+    "COSH",
+    "COUNT",
+    "COVAR_POP",
+    "COVAR_SAMP",
+    "CREATE",
+    "CROSS",
+    "CUBE",
+    "CUME_DIST",
+    "CURRENT",
+    "CURRENT_CATALOG",
+    "CURRENT_DATE",
+    "CURRENT_DEFAULT_TRANSFORM_GROUP",
+    "CURRENT_PATH",
+    "CURRENT_ROLE",
+    "CURRENT_ROW",
+    "CURRENT_SCHEMA",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "CURRENT_TRANSFORM_GROUP_FOR_TYPE",
+    "CURRENT_USER",
+    "CURSOR",
+    "CYCLE",
+    "DATE",
+    "DAY",
+    "DEALLOCATE",
+    "DEC",
+    "DECFLOAT",
+    "DECIMAL",
+    "DECLARE",
+    "DEFAULT",
+    "DEFINE",
+    "DELETE",
+    "DENSE_RANK",
+    "DEREF",
+    "DESC",
+    "DESCRIBE",
+    "DETERMINISTIC",
+    "DISCONNECT",
+    "DISTINCT",
+    "DOUBLE",
+    "DROP",
+    "DYNAMIC",
+    "EACH",
+    "ELEMENT",
+    "ELSE",
+    "EMPTY",
+    "END",
+    "END_FRAME",
+    "END_PARTITION",
+    "EQUALS",
+    "ESCAPE",
+    "EVERY",
+    "EXCEPT",
+    "EXEC",
+    "EXECUTE",
+    "EXISTS",
+    "EXP",
+    "EXTERNAL",
+    "EXTRACT",
+    "FALSE",
+    "FETCH",
+    "FILTER",
+    "FIRST_VALUE",
+    "FLOAT",
+    "FLOOR",
+    "FOR",
+    "FOREIGN",
+    "FRAME_ROW",
+    "FREE",
+    "FROM",
+    "FULL",
+    "FUNCTION",
+    "FUSION",
+    "GET",
+    "GLOBAL",
+    "GRANT",
+    "GROUP",
+    "GROUPING",
+    "GROUPS",
+    "HAVING",
+    "HOLD",
+    "HOUR",
+    "IDENTITY",
+    "IN",
+    "INDICATOR",
+    "INITIAL",
+    "INNER",
+    "INOUT",
+    "INSENSITIVE",
+    "INSERT",
+    "INT",
+    "INTEGER",
+    "INTERSECT",
+    "INTERSECTION",
+    "INTERVAL",
+    "INTO",
+    "IS",
+    "JOIN",
+    "JSON_ARRAY",
+    "JSON_ARRAYAGG",
+    "JSON_EXISTS",
+    "JSON_OBJECT",
+    "JSON_OBJECTAGG",
+    "JSON_QUERY",
+    "JSON_TABLE",
+    "JSON_TABLE_PRIMITIVE",
+    "JSON_VALUE",
+    "LAG",
+    "LANGUAGE",
+    "LARGE",
+    "LAST_VALUE",
+    "LATERAL",
+    "LEAD",
+    "LEADING",
+    "LEFT",
+    "LIKE",
+    "LIKE_REGEX",
+    "LIMIT",
+    "LISTAGG",
+    "LN",
+    "LOCAL",
+    "LOCALTIME",
+    "LOCALTIMESTAMP",
+    "LOG",
+    "LOG10",
+    "LOWER",
+    "MATCH",
+    "MATCHES",
+    "MATCH_NUMBER",
+    "MATCH_RECOGNIZE",
+    "MAX",
+    "MEASURES",
+    "MEMBER",
+    "MERGE",
+    "METHOD",
+    "MIN",
+    "MINUTE",
+    "MOD",
+    "MODIFIES",
+    "MODULE",
+    "MONTH",
+    "MULTISET",
+    "NATIONAL",
+    "NATURAL",
+    "NCHAR",
+    "NCLOB",
+    "NEW",
+    "NO",
+    "NONE",
+    "NORMALIZE",
+    "NOT",
+    "NTH_VALUE",
+    "NTILE",
+    "NULL",
+    "NULLIF",
+    "NUMERIC",
+    "OCCURRENCES_REGEX",
+    "OCTET_LENGTH",
+    "OF",
+    "OFFSET",
+    "OLD",
+    "OMIT",
+    "ON",
+    "ONE",
+    "ONLY",
+    "OPEN",
+    "OR",
+    "ORDER",
+    "OUT",
+    "OUTER",
+    "OVER",
+    "OVERLAPS",
+    "OVERLAY",
+    "PARAMETER",
+    "PARTITION",
+    "PATTERN",
+    "PER",
+    "PERCENT",
+    "PERCENTILE_CONT",
+    "PERCENTILE_DISC",
+    "PERCENT_RANK",
+    "PERIOD",
+    "PORTION",
+    "POSITION",
+    "POSITION_REGEX",
+    "POWER",
+    "PRECEDES",
+    "PRECISION",
+    "PREPARE",
+    "PRIMARY",
+    "PROCEDURE",
+    "PTF",
+    "RANGE",
+    "RANK",
+    "READS",
+    "REAL",
+    "RECURSIVE",
+    "REF",
+    "REFERENCES",
+    "REFERENCING",
+    "REGR_AVGX",
+    "REGR_AVGY",
+    "REGR_COUNT",
+    "REGR_INTERCEPT",
+    "REGR_R2",
+    "REGR_SLOPE",
+    "REGR_SXX",
+    "REGR_SXY",
+    "REGR_SYY",
+    "RELEASE",
+    "RESULT",
+    "RETURN",
+    "RETURNS",
+    "REVOKE",
+    "RIGHT",
+    "ROLLBACK",
+    "ROLLUP",
+    "ROW",
+    "ROWS",
+    "ROW_NUMBER",
+    "RUNNING",
+    "SAVEPOINT",
+    "SCOPE",
+    "SCROLL",
+    "SEARCH",
+    "SECOND",
+    "SEEK",
+    "SELECT",
+    "SENSITIVE",
+    "SESSION_USER",
+    "SET",
+    "SHOW",
+    "SIMILAR",
+    "SIN",
+    "SINH",
+    "SKIP",
+    "SMALLINT",
+    "SOME",
+    "SPECIFIC",
+    "SPECIFICTYPE",
+    "SQL",
+    "SQLEXCEPTION",
+    "SQLSTATE",
+    "SQLWARNING",
+    "SQRT",
+    "START",
+    "STATIC",
+    "STDDEV_POP",
+    "STDDEV_SAMP",
+    "SUBMULTISET",
+    "SUBSET",
+    "SUBSTRING",
+    "SUBSTRING_REGEX",
+    "SUCCEEDS",
+    "SUM",
+    "SYMMETRIC",
+    "SYSTEM",
+    "SYSTEM_TIME",
+    "SYSTEM_USER",
+    "TABLE",
+    "TABLESAMPLE",
+    "TAN",
+    "TANH",
+    "THEN",
+    "TIME",
+    "TIMESTAMP",
+    "TIMEZONE_HOUR",
+    "TIMEZONE_MINUTE",
+    "TO",
+    "TRAILING",
+    "TRANSLATE",
+    "TRANSLATE_REGEX",
+    "TRANSLATION",
+    "TREAT",
+    "TRIGGER",
+    "TRIM",
+    "TRIM_ARRAY",
+    "TRUE",
+    "TRUNCATE",
+    "UESCAPE",
+    "UNION",
+    "UNIQUE",
+    "UNKNOWN",
+    "UNNEST",
+    "UPDATE",
+    "UPPER",
+    "USER",
+    "USING",
+    "VALUE",
+    "VALUES",
+    "VALUE_OF",
+    "VARBINARY",
+    "VARCHAR",
+    "VARYING",
+    "VAR_POP",
+    "VAR_SAMP",
+    "VERSIONING",
+    "WHEN",
+    "WHENEVER",
+    "WHERE",
+    "WIDTH_BUCKET",
+    "WINDOW",
+    "WITH",
+    "WITHIN",
+    "WITHOUT",
+    "YEAR",
+];
+
+/// Fragments of SQL syntax used to build quoted names and string literals.
+const INJECTION_FRAGMENTS: &[&str] = &[
+    "\"",
+    "'",
+    "''",
+    "`",
+    ";",
+    "--",
+    "/*",
+    "*/",
+    "\\",
+    "[",
+    "]",
+    " ",
+    "\t",
+    "\n",
+    "%",
+    "_",
+    "?",
+    "$1",
+    ":name",
+    " OR 1=1",
+    "'; DROP TABLE t; --",
+];
+
+/// Returns whether `name` is a reserved word, ignoring case.
+fn is_reserved(name: &str) -> bool {
+    RESERVED_WORDS
+        .iter()
+        .any(|word| word.eq_ignore_ascii_case(name))
+}
+
+/// An SQL identifier, such as the name of a table or column.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Identifier {
+    /// The name, unescaped.
+    pub name: String,
+    /// Whether the identifier is a delimited identifier, in double quotes.
+    pub quoted: bool,
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.quoted {
+            write!(f, "\"{}\"", self.name.replace('"', "\"\""))
+        } else {
+            f.write_str(&self.name)
+        }
+    }
+}
+
+/// A literal value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Value {
+    /// `NULL`.
+    Null,
+    /// `TRUE` or `FALSE`.
+    Bool(bool),
+    /// An integer.
+    Integer(i64),
+    /// A string, unescaped.
+    Text(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Null => f.write_str("NULL"),
+            Value::Bool(true) => f.write_str("TRUE"),
+            Value::Bool(false) => f.write_str("FALSE"),
+            Value::Integer(i) => write!(f, "{}", i),
+            Value::Text(ref text) => {
+                write!(f, "'{}'", text.replace('\'', "''"))
+            }
+        }
+    }
+}
+
+/// A binary operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    /// `=`
+    Eq,
+    /// `<>`
+    NotEq,
+    /// `<`
+    Lt,
+    /// `<=`
+    LtEq,
+    /// `>`
+    Gt,
+    /// `>=`
+    GtEq,
+    /// `AND`
+    And,
+    /// `OR`
+    Or,
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+    /// `*`
+    Multiply,
+}
+
+const BINARY_OPS: &[BinaryOp] = &[
+    BinaryOp::Eq,
+    BinaryOp::NotEq,
+    BinaryOp::Lt,
+    BinaryOp::LtEq,
+    BinaryOp::Gt,
+    BinaryOp::GtEq,
+    BinaryOp::And,
+    BinaryOp::Or,
+    BinaryOp::Plus,
+    BinaryOp::Minus,
+    BinaryOp::Multiply,
+];
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            BinaryOp::Eq => "=",
+            BinaryOp::NotEq => "<>",
+            BinaryOp::Lt => "<",
+            BinaryOp::LtEq => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::GtEq => ">=",
+            BinaryOp::And => "AND",
+            BinaryOp::Or => "OR",
+            BinaryOp::Plus => "+",
+            BinaryOp::Minus => "-",
+            BinaryOp::Multiply => "*",
+        })
+    }
+}
+
+/// An expression. Expressions are not typed, so they may compare strings
+/// to numbers or add booleans.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Expr {
+    /// A reference to a column.
+    Column(Identifier),
+    /// A literal value.
+    Literal(Value),
+    /// A binary operation, rendered in parentheses.
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+    /// `NOT`, rendered in parentheses.
+    Not(Box<Expr>),
+    /// `IS NULL`, or `IS NOT NULL` if the flag is true, rendered in
+    /// parentheses.
+    IsNull(Box<Expr>, bool),
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Expr::Column(ref column) => write!(f, "{}", column),
+            Expr::Literal(ref value) => write!(f, "{}", value),
+            Expr::Binary(ref left, op, ref right) => {
+                write!(f, "({} {} {})", left, op, right)
+            }
+            Expr::Not(ref expr) => write!(f, "(NOT {})", expr),
+            Expr::IsNull(ref expr, false) => write!(f, "({} IS NULL)", expr),
+            Expr::IsNull(ref expr, true) => {
+                write!(f, "({} IS NOT NULL)", expr)
+            }
+        }
+    }
+}
+
+/// A `SELECT` query.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Select {
+    /// The selected columns, or `*` if empty.
+    pub columns: Vec<Identifier>,
+    /// The table to select from.
+    pub table: Identifier,
+    /// The `WHERE` clause.
+    pub filter: Option<Expr>,
+    /// The columns of the `ORDER BY` clause, if any.
+    pub order_by: Vec<Identifier>,
+    /// The `LIMIT` clause.
+    pub limit: Option<u32>,
+}
+
+/// An `INSERT` statement.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Insert {
+    /// The table to insert into.
+    pub table: Identifier,
+    /// The columns to set.
+    pub columns: Vec<Identifier>,
+    /// The inserted rows, each with a value for each column.
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// An `UPDATE` statement.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Update {
+    /// The table to update.
+    pub table: Identifier,
+    /// The columns to set, and the expressions to set them to.
+    pub assignments: Vec<(Identifier, Expr)>,
+    /// The `WHERE` clause.
+    pub filter: Option<Expr>,
+}
+
+/// A `DELETE` statement.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Delete {
+    /// The table to delete from.
+    pub table: Identifier,
+    /// The `WHERE` clause.
+    pub filter: Option<Expr>,
+}
+
+/// A query or statement.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Query {
+    /// A `SELECT` query.
+    Select(Select),
+    /// An `INSERT` statement.
+    Insert(Insert),
+    /// An `UPDATE` statement.
+    Update(Update),
+    /// A `DELETE` statement.
+    Delete(Delete),
+}
+
+/// Write `items` separated by commas.
+fn comma_separated<T: fmt::Display>(
+    f: &mut fmt::Formatter,
+    items: &[T],
+) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+/// Write the `WHERE` clause `filter`, if any.
+fn where_clause(f: &mut fmt::Formatter, filter: &Option<Expr>) -> fmt::Result {
+    match *filter {
+        Some(ref filter) => write!(f, " WHERE {}", filter),
+        None => Ok(()),
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Query::Select(ref select) => {
+                f.write_str("SELECT ")?;
+                if select.columns.is_empty() {
+                    f.write_str("*")?;
+                } else {
+                    comma_separated(f, &select.columns)?;
+                }
+                write!(f, " FROM {}", select.table)?;
+                where_clause(f, &select.filter)?;
+                if !select.order_by.is_empty() {
+                    f.write_str(" ORDER BY ")?;
+                    comma_separated(f, &select.order_by)?;
+                }
+                if let Some(limit) = select.limit {
+                    write!(f, " LIMIT {}", limit)?;
+                }
+                Ok(())
+            }
+            Query::Insert(ref insert) => {
+                write!(f, "INSERT INTO {} (", insert.table)?;
+                comma_separated(f, &insert.columns)?;
+                f.write_str(") VALUES ")?;
+                for (i, row) in insert.rows.iter().enumerate() {
+                    f.write_str(if i > 0 { ", (" } else { "(" })?;
+                    comma_separated(f, row)?;
+                    f.write_str(")")?;
+                }
+                Ok(())
+            }
+            Query::Update(ref update) => {
+                write!(f, "UPDATE {} SET ", update.table)?;
+                for (i, (column, value)) in
+                    update.assignments.iter().enumerate()
+                {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{} = {}", column, value)?;
+                }
+                where_clause(f, &update.filter)
+            }
+            Query::Delete(ref delete) => {
+                write!(f, "DELETE FROM {}", delete.table)?;
+                where_clause(f, &delete.filter)
+            }
+        }
+    }
+}
+
+/// Parameters for generating identifiers.
+///
+/// By default, identifiers are unquoted lower case ASCII names such as
+/// `user_id`, avoiding reserved words.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IdentifierParams {
+    /// Whether identifiers may be quoted, with names made of quotes,
+    /// whitespace, comment markers and other fragments of SQL syntax.
+    pub quoted: bool,
+    /// Whether names may be reserved words, such as `select` or `ORDER`.
+    /// Such identifiers are quoted.
+    pub reserved_words: bool,
+    /// Whether names may contain letters outside ASCII.
+    pub unicode: bool,
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates SQL identifiers.
+    ///
+    /// Created by the `identifier()` function in the same module.
+    #[derive(Clone, Debug)]
+    pub struct IdentifierStrategy[][](SBoxedStrategy<Identifier>)
+        -> IdentifierValueTree;
+    /// `ValueTree` corresponding to `IdentifierStrategy`.
+    pub struct IdentifierValueTree[][](Box<dyn ValueTree<Value = Identifier>>)
+        -> Identifier;
+}
+
+/// Generates text made of ordinary words and fragments of SQL syntax, for
+/// quoted names and string literals.
+fn injection_text(unicode: bool) -> SBoxedStrategy<String> {
+    let word = if unicode {
+        "[A-Za-z0-9_\\p{L}]{1,6}"
+    } else {
+        "[A-Za-z0-9_]{1,6}"
+    };
+    vec(
+        prop_oneof![
+            2 => word,
+            1 => select(INJECTION_FRAGMENTS).prop_map(ToOwned::to_owned),
+        ],
+        1..6,
+    )
+    .prop_map(|fragments| fragments.concat())
+    .sboxed()
+}
+
+/// Creates a strategy which generates SQL identifiers, as configured by
+/// `params`.
+///
+/// Unquoted identifiers are never reserved words, and are valid regular
+/// identifiers in standard SQL and the common dialects. Identifiers shrink
+/// toward `a`.
+pub fn identifier(params: IdentifierParams) -> IdentifierStrategy {
+    let unquoted = if params.unicode {
+        "[a-z\\p{Ll}][a-z0-9_\\p{Ll}]{0,11}"
+    } else {
+        "[a-z][a-z0-9_]{0,11}"
+    }
+    .prop_filter("Reserved words must be quoted", |name| !is_reserved(name))
+    .prop_map(|name| Identifier {
+        name,
+        quoted: false,
+    });
+
+    let mut identifiers = vec![(3, unquoted.sboxed())];
+    if params.quoted {
+        identifiers.push((
+            2,
+            injection_text(params.unicode)
+                .prop_map(|name| Identifier { name, quoted: true })
+                .sboxed(),
+        ));
+    }
+    if params.reserved_words {
+        identifiers.push((
+            1,
+            (select(RESERVED_WORDS), any::<bool>())
+                .prop_map(|(word, upper)| Identifier {
+                    name: if upper {
+                        word.to_owned()
+                    } else {
+                        word.to_ascii_lowercase()
+                    },
+                    quoted: true,
+                })
+                .sboxed(),
+        ));
+    }
+    IdentifierStrategy(Union::new_weighted(identifiers).sboxed())
+}
+
+/// The kinds of queries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QueryKind {
+    /// `SELECT` queries.
+    Select,
+    /// `INSERT` statements.
+    Insert,
+    /// `UPDATE` statements.
+    Update,
+    /// `DELETE` statements.
+    Delete,
+}
+
+impl QueryKind {
+    /// All the kinds of queries.
+    pub const ALL: &'static [QueryKind] = &[
+        QueryKind::Select,
+        QueryKind::Insert,
+        QueryKind::Update,
+        QueryKind::Delete,
+    ];
+}
+
+/// Parameters for generating queries with `query()`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct QueryParams {
+    /// The parameters for the identifiers of tables and columns, also
+    /// controlling whether string literals contain fragments of SQL syntax
+    /// (with `quoted`) and non-ASCII letters (with `unicode`).
+    ///
+    /// The default is `IdentifierParams::default()`.
+    pub identifiers: IdentifierParams,
+    /// The kinds of queries to generate. Must not be empty.
+    ///
+    /// The default is `QueryKind::ALL`.
+    pub kinds: &'static [QueryKind],
+    /// The range of the number of columns selected, inserted or updated.
+    ///
+    /// The default is `1..4`.
+    pub columns: SizeRange,
+    /// The maximum depth of expressions.
+    ///
+    /// The default is 3.
+    pub max_depth: u32,
+}
+
+impl Default for QueryParams {
+    fn default() -> Self {
+        QueryParams {
+            identifiers: IdentifierParams::default(),
+            kinds: QueryKind::ALL,
+            columns: (1..4).into(),
+            max_depth: 3,
+        }
+    }
+}
+
+/// Generates literal values.
+fn value(params: IdentifierParams) -> SBoxedStrategy<Value> {
+    let text = if params.quoted {
+        injection_text(params.unicode)
+    } else if params.unicode {
+        "[a-z0-9 \\p{L}]{0,12}".sboxed()
+    } else {
+        "[a-z0-9 ]{0,12}".sboxed()
+    };
+    prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<i64>().prop_map(Value::Integer),
+        text.prop_map(Value::Text),
+    ]
+    .sboxed()
+}
+
+/// Generates expressions up to `max_depth` deep.
+fn expr(params: IdentifierParams, max_depth: u32) -> BoxedStrategy<Expr> {
+    let leaf = prop_oneof![
+        identifier(params).prop_map(Expr::Column),
+        value(params).prop_map(Expr::Literal),
+    ];
+    leaf.prop_recursive(max_depth, 16, 2, |inner| {
+        prop_oneof![
+            (inner.clone(), select(BINARY_OPS), inner.clone()).prop_map(
+                |(left, op, right)| {
+                    Expr::Binary(Box::new(left), op, Box::new(right))
+                }
+            ),
+            inner.clone().prop_map(|expr| Expr::Not(Box::new(expr))),
+            (inner, any::<bool>())
+                .prop_map(|(expr, not)| Expr::IsNull(Box::new(expr), not)),
+        ]
+    })
+    .boxed()
+}
+
+/// Creates a strategy which generates small SQL queries of the kinds in
+/// `params`.
+///
+/// The rendered queries are syntactically valid, but not necessarily
+/// meaningful: expressions are untyped, and columns may be repeated.
+/// Queries shrink toward `SELECT * FROM a`.
+///
+/// ## Panics
+///
+/// Panics if `params.kinds` is empty.
+pub fn query(params: QueryParams) -> BoxedStrategy<Query> {
+    assert!(!params.kinds.is_empty(), "No kinds of queries to generate");
+    let ids = params.identifiers;
+    let columns = || vec(identifier(ids), params.columns.clone());
+    let filter = || crate::option::of(expr(ids, params.max_depth));
+
+    let queries = params.kinds.iter().map(|kind| match *kind {
+        QueryKind::Select => (
+            prop_oneof![Just(Vec::new()), columns()],
+            identifier(ids),
+            filter(),
+            vec(identifier(ids), 0..3),
+            crate::option::of(any::<u32>()),
+        )
+            .prop_map(|(columns, table, filter, order_by, limit)| {
+                Query::Select(Select {
+                    columns,
+                    table,
+                    filter,
+                    order_by,
+                    limit,
+                })
+            })
+            .boxed(),
+        QueryKind::Insert => (
+            identifier(ids),
+            columns().prop_flat_map(move |columns| {
+                let len = columns.len();
+                (Just(columns), vec(vec(value(ids), len), 1..4))
+            }),
+        )
+            .prop_map(|(table, (columns, rows))| {
+                Query::Insert(Insert {
+                    table,
+                    columns,
+                    rows,
+                })
+            })
+            .boxed(),
+        QueryKind::Update => (
+            identifier(ids),
+            vec(
+                (identifier(ids), expr(ids, params.max_depth)),
+                params.columns.clone(),
+            ),
+            filter(),
+        )
+            .prop_map(|(table, assignments, filter)| {
+                Query::Update(Update {
+                    table,
+                    assignments,
+                    filter,
+                })
+            })
+            .boxed(),
+        QueryKind::Delete => (identifier(ids), filter())
+            .prop_map(|(table, filter)| Query::Delete(Delete { table, filter }))
+            .boxed(),
+    });
+    Union::new(queries).boxed()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::std_facade::string::ToString;
+    use sqlparser::ast::{self, Statement};
+    use sqlparser::dialect::GenericDialect;
+    use sqlparser::parser::Parser;
+
+    use super::*;
+
+    fn parse(sql: &str) -> Statement {
+        match Parser::parse_sql(&GenericDialect {}, sql) {
+            Ok(mut statements) if statements.len() == 1 => {
+                statements.pop().unwrap()
+            }
+            result => panic!("Failed to parse {:?}: {:?}", sql, result),
+        }
+    }
+
+    fn sample<S: Strategy>(input: S) -> Vec<S::Value> {
+        let mut runner = TestRunner::deterministic();
+        (0..256)
+            .map(|_| input.new_tree(&mut runner).unwrap().current())
+            .collect()
+    }
+
+    /// Check that `id` parses back to the same identifier, and return the
+    /// number of quoted and reserved identifiers.
+    fn check_identifier(id: &Identifier) -> (usize, usize) {
+        let sql = format!("SELECT {} FROM t", id);
+        let projection = match parse(&sql) {
+            Statement::Query(query) => match *query.body {
+                ast::SetExpr::Select(select) => select.projection,
+                body => panic!("Unexpected query: {:?}", body),
+            },
+            statement => panic!("Unexpected statement: {:?}", statement),
+        };
+        match projection[..] {
+            [ast::SelectItem::UnnamedExpr(ast::Expr::Identifier(ref ident))] => {
+                assert_eq!(id.name, ident.value, "{}", sql);
+                assert_eq!(id.quoted, ident.quote_style == Some('"'));
+            }
+            ref projection => panic!("Unexpected {:?} in {}", projection, sql),
+        }
+        (id.quoted as usize, is_reserved(&id.name) as usize)
+    }
+
+    #[test]
+    fn generates_plain_identifiers() {
+        for id in sample(identifier(IdentifierParams::default())) {
+            assert_eq!((0, 0), check_identifier(&id));
+            assert!(id.name.chars().all(|c| c.is_ascii_lowercase()
+                || c.is_ascii_digit()
+                || c == '_'));
+        }
+    }
+
+    #[test]
+    fn generates_quoted_and_reserved_identifiers() {
+        let params = IdentifierParams {
+            quoted: true,
+            reserved_words: true,
+            unicode: true,
+        };
+        let ids = sample(identifier(params));
+        let (quoted, reserved) = ids
+            .iter()
+            .map(check_identifier)
+            .fold((0, 0), |(q, r), (dq, dr)| (q + dq, r + dr));
+        assert!(quoted > 0 && reserved > 0);
+        assert!(ids.iter().any(|id| id.name.contains('"')));
+        assert!(ids.iter().any(|id| !id.name.is_ascii()));
+    }
+
+    #[test]
+    fn generates_valid_queries() {
+        for params in [
+            QueryParams::default(),
+            QueryParams {
+                identifiers: IdentifierParams {
+                    quoted: true,
+                    reserved_words: true,
+                    unicode: true,
+                },
+                ..QueryParams::default()
+            },
+        ] {
+            let queries = sample(query(params));
+            for query in &queries {
+                parse(&query.to_string());
+            }
+            for kind in QueryKind::ALL {
+                assert!(queries.iter().any(|query| matches!(
+                    (kind, query),
+                    (QueryKind::Select, Query::Select(_))
+                        | (QueryKind::Insert, Query::Insert(_))
+                        | (QueryKind::Update, Query::Update(_))
+                        | (QueryKind::Delete, Query::Delete(_))
+                )));
+            }
+        }
+    }
+
+    #[test]
+    fn generates_only_requested_kinds() {
+        let params = QueryParams {
+            kinds: &[QueryKind::Insert],
+            ..QueryParams::default()
+        };
+        for query in sample(query(params)) {
+            match query {
+                Query::Insert(insert) => {
+                    for row in &insert.rows {
+                        assert_eq!(insert.columns.len(), row.len());
+                    }
+                }
+                query => panic!("Unexpected query {}", query),
+            }
+        }
+    }
+
+    #[test]
+    fn shrinks_to_simple_query() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&query(QueryParams::default()), |_| {
+            Err(TestCaseError::fail("minimize"))
+        });
+        match result {
+            Err(TestError::Fail(_, query)) => {
+                assert_eq!("SELECT * FROM a", query.to_string())
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
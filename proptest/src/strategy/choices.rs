@@ -0,0 +1,331 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::std_facade::{fmt, Arc, Vec};
+
+use rand::Rng;
+
+use crate::strategy::traits::*;
+use crate::strategy::Description;
+use crate::test_runner::*;
+
+/// The sizes in bytes of the blocks of choices deleted, zeroed and lowered
+/// while shrinking, largest first.
+const BLOCKS: [usize; 4] = [8, 4, 2, 1];
+
+/// `Strategy` shrinking the sequence of random choices made to generate the
+/// values of the source strategy.
+///
+/// See `Strategy::prop_shrink_choices()`.
+#[must_use = "strategies do nothing unless used"]
+pub struct ShrinkChoices<S> {
+    pub(super) source: Arc<S>,
+}
+
+impl<S: fmt::Debug> fmt::Debug for ShrinkChoices<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShrinkChoices")
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl<S> Clone for ShrinkChoices<S> {
+    fn clone(&self) -> Self {
+        ShrinkChoices {
+            source: Arc::clone(&self.source),
+        }
+    }
+}
+
+impl<S: Strategy> Strategy for ShrinkChoices<S> {
+    type Tree = ShrinkChoicesValueTree<S>;
+    type Value = S::Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let fresh = runner.rng().random();
+        let mut tree = ShrinkChoicesValueTree {
+            source: Arc::clone(&self.source),
+            runner: runner.partial_clone(),
+            fresh,
+            accepted: (Vec::new(), None),
+            trying: None,
+            cursor: Cursor::default(),
+            retry: Cursor::default(),
+            progress: false,
+        };
+        let (choices, current) = tree.generate(Vec::new())?;
+        tree.accepted = (choices, Some(current));
+        Ok(tree)
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_shrink_choices").child(self.source.describe())
+    }
+}
+
+/// The position of the next edit to the accepted choices.
+#[derive(Clone, Copy, Debug, Default)]
+struct Cursor {
+    /// Deleting (0), zeroing (1) or lowering (2) blocks.
+    pass: usize,
+    /// The index of the block size in `BLOCKS`.
+    block: usize,
+    /// The offset of the block in the choices.
+    pos: usize,
+    /// When lowering, how far the block was shifted right to compute the
+    /// amount it was lowered by last.
+    shift: u32,
+}
+
+/// `ValueTree` corresponding to `ShrinkChoices`.
+pub struct ShrinkChoicesValueTree<S: Strategy> {
+    source: Arc<S>,
+    runner: TestRunner,
+    // The seed of the choices made past the end of the replayed ones.
+    fresh: [u8; 32],
+    // The shortest and then lowest choices known to fail, and the value tree
+    // generated from them. The tree is only `None` during construction.
+    accepted: (Vec<u8>, Option<S::Tree>),
+    // The edited choices being tried, and the value tree generated from them.
+    trying: Option<(Vec<u8>, S::Tree)>,
+    cursor: Cursor,
+    // Where to resume if the choices being tried are accepted, so that an
+    // edit which succeeded is tried again.
+    retry: Cursor,
+    // Whether any choices were accepted since `cursor` was reset.
+    progress: bool,
+}
+
+impl<S: Strategy> fmt::Debug for ShrinkChoicesValueTree<S>
+where
+    S::Tree: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShrinkChoicesValueTree")
+            .field("accepted", &self.accepted)
+            .field("trying", &self.trying)
+            .field("cursor", &self.cursor)
+            .field("progress", &self.progress)
+            .finish()
+    }
+}
+
+impl<S: Strategy> Clone for ShrinkChoicesValueTree<S>
+where
+    S::Tree: Clone,
+{
+    fn clone(&self) -> Self {
+        ShrinkChoicesValueTree {
+            source: Arc::clone(&self.source),
+            runner: self.runner.clone(),
+            fresh: self.fresh,
+            accepted: self.accepted.clone(),
+            trying: self.trying.clone(),
+            cursor: self.cursor,
+            retry: self.retry,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<S: Strategy> ShrinkChoicesValueTree<S> {
+    /// Generate a value tree from `source` replaying `choices`, returning it
+    /// with the choices actually made.
+    fn generate(&self, choices: Vec<u8>) -> Result<(Vec<u8>, S::Tree), Reason> {
+        let mut runner = self.runner.clone();
+        *runner.rng() = TestRng::from_choices(choices, self.fresh);
+        let tree = self.source.new_tree(&mut runner)?;
+        let choices = runner
+            .rng()
+            .choices_made()
+            .expect("generation replaced the runner's RNG")
+            .to_vec();
+        Ok((choices, tree))
+    }
+
+    /// Return the next edit to the accepted choices, or `None` once every
+    /// edit has been tried.
+    fn next_edit(&mut self) -> Option<Vec<u8>> {
+        let choices = &self.accepted.0;
+        while self.cursor.pass < 3 {
+            let size = BLOCKS[self.cursor.block];
+            let pos = self.cursor.pos;
+            if pos + size > choices.len() {
+                self.cursor.pos = 0;
+                self.cursor.block += 1;
+                if self.cursor.block == BLOCKS.len() {
+                    self.cursor.block = 0;
+                    self.cursor.pass += 1;
+                }
+                continue;
+            }
+
+            self.retry = self.cursor;
+            let mut edit = choices.clone();
+            let block = &mut edit[pos..pos + size];
+            match self.cursor.pass {
+                0 => {
+                    self.cursor.pos += 1;
+                    edit.drain(pos..pos + size);
+                }
+                1 => {
+                    self.cursor.pos += 1;
+                    if block.iter().all(|&b| b == 0) {
+                        continue;
+                    }
+                    block.fill(0);
+                }
+                _ => {
+                    // Lower the block, read as a big-endian integer as the
+                    // RNG does, by half of its value, then by a quarter, and
+                    // so on.
+                    let value = block
+                        .iter()
+                        .fold(0u64, |value, &b| value << 8 | u64::from(b));
+                    self.cursor.shift += 1;
+                    let delta = value.checked_shr(self.cursor.shift);
+                    if delta.is_none_or(|delta| delta == 0) {
+                        self.cursor.shift = 0;
+                        self.cursor.pos += 1;
+                        continue;
+                    }
+                    self.retry.shift = 0;
+                    let lowered = value - delta.unwrap();
+                    block.copy_from_slice(&lowered.to_be_bytes()[8 - size..]);
+                }
+            }
+            return Some(edit);
+        }
+        None
+    }
+
+    /// Find the next edit to the accepted choices which still yields a value
+    /// and is simpler once replayed, and start trying it.
+    fn next_candidate(&mut self) -> bool {
+        self.trying = None;
+        loop {
+            let edit = match self.next_edit() {
+                Some(edit) => edit,
+                // Edits may have become possible after the last ones
+                // accepted, so start over until nothing is accepted.
+                None if self.progress => {
+                    self.cursor = Cursor::default();
+                    self.progress = false;
+                    continue;
+                }
+                None => return false,
+            };
+
+            if let Ok((choices, tree)) = self.generate(edit) {
+                let accepted = &self.accepted.0;
+                if (choices.len(), &choices) < (accepted.len(), accepted) {
+                    self.trying = Some((choices, tree));
+                    return true;
+                }
+            }
+        }
+    }
+}
+
+impl<S: Strategy> ValueTree for ShrinkChoicesValueTree<S> {
+    type Value = S::Value;
+
+    fn current(&self) -> S::Value {
+        match self.trying {
+            Some((_, ref tree)) => tree.current(),
+            None => self.accepted.1.as_ref().unwrap().current(),
+        }
+    }
+
+    fn simplify(&mut self) -> bool {
+        // `simplify()` is only called when the current value fails, so the
+        // choices being tried become the ones to edit.
+        if let Some((choices, tree)) = self.trying.take() {
+            self.accepted = (choices, Some(tree));
+            self.cursor = self.retry;
+            self.progress = true;
+        }
+        self.next_candidate()
+    }
+
+    fn complicate(&mut self) -> bool {
+        if self.trying.is_none() {
+            return false;
+        }
+        // The edit passed, so try the next one, if any, or return to the
+        // accepted choices.
+        self.next_candidate();
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::collection::vec;
+
+    fn minimize<S: Strategy>(
+        input: S,
+        test: impl Fn(S::Value) -> TestCaseResult,
+    ) -> S::Value {
+        let mut runner = TestRunner::new_with_rng(
+            Config {
+                max_shrink_iters: u32::MAX - 1,
+                ..Config::default()
+            },
+            TestRng::deterministic_rng(RngAlgorithm::default()),
+        );
+        match runner.run(&input, test) {
+            Err(TestError::Fail(_, value)) => value,
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn shrinks_through_flat_map() {
+        let input = (0..100usize)
+            .prop_flat_map(|n| vec(0..10_000u32, n))
+            .prop_shrink_choices();
+
+        let v = minimize(input, |v| {
+            prop_assert!(v.iter().all(|&x| x < 9_900));
+            Ok(())
+        });
+        assert_eq!(vec![9_900], v);
+    }
+
+    #[test]
+    fn shrinks_through_filter() {
+        let input = (0..1_000u32, 0..1_000u32)
+            .prop_filter("a < b", |&(a, b)| a < b)
+            .prop_shrink_choices();
+
+        let v = minimize(input, |(_, b)| {
+            prop_assert!(b < 500);
+            Ok(())
+        });
+        assert_eq!((0, 500), v);
+    }
+
+    #[test]
+    fn replays_choices_made() {
+        let input = vec(0..1_000u32, 0..10).prop_shrink_choices();
+        let mut runner = TestRunner::deterministic();
+        let case = input.new_tree(&mut runner).unwrap();
+        let (choices, _) = case.generate(case.accepted.0.clone()).unwrap();
+        assert_eq!(case.accepted.0, choices);
+        assert_eq!(case.current(), case.generate(choices).unwrap().1.current());
+    }
+
+    #[test]
+    fn test_sanity() {
+        check_strategy_sanity((0..100u8).prop_shrink_choices(), None);
+    }
+}
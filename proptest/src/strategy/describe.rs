@@ -0,0 +1,168 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::std_facade::{fmt, String, Vec};
+use core::fmt::Write;
+
+/// A structured description of a `Strategy`, as returned by
+/// `Strategy::describe()`.
+///
+/// Each node names a combinator, such as `prop_map` or `prop_filter`, along
+/// with its parameters, and has one child per strategy it draws values
+/// from, in the order they contribute to the generated value. Strategies
+/// which do not describe themselves are described by their `Debug`
+/// representation, without children.
+///
+/// The `Display` implementation renders the tree with one node per line,
+/// children indented under their parent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Description {
+    name: String,
+    params: Vec<(&'static str, String)>,
+    children: Vec<Description>,
+}
+
+impl Description {
+    /// Create a description named `name`, without parameters or children.
+    pub fn new(name: impl Into<String>) -> Self {
+        Description {
+            name: name.into(),
+            params: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Create a description named after the `Debug` representation of
+    /// `value`.
+    pub fn from_debug<T: fmt::Debug + ?Sized>(value: &T) -> Self {
+        Self::new(debug(value))
+    }
+
+    /// Add a parameter called `name`, recorded as the `Debug`
+    /// representation of `value`.
+    pub fn param(mut self, name: &'static str, value: impl fmt::Debug) -> Self {
+        self.params.push((name, debug(&value)));
+        self
+    }
+
+    /// Add `child` as the description of the next strategy this one draws
+    /// values from.
+    pub fn child(mut self, child: Description) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Return the name of the combinator described.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the parameters of the combinator, as pairs of names and
+    /// `Debug` representations.
+    pub fn params(&self) -> &[(&'static str, String)] {
+        &self.params
+    }
+
+    /// Return the descriptions of the strategies the combinator draws
+    /// values from.
+    pub fn children(&self) -> &[Description] {
+        &self.children
+    }
+
+    fn fmt_indented(
+        &self,
+        f: &mut fmt::Formatter,
+        depth: usize,
+    ) -> fmt::Result {
+        write!(f, "{:width$}{}", "", self.name, width = 2 * depth)?;
+        for (i, (name, value)) in self.params.iter().enumerate() {
+            let sep = if 0 == i { "(" } else { ", " };
+            write!(f, "{}{} = {}", sep, name, value)?;
+        }
+        if !self.params.is_empty() {
+            f.write_str(")")?;
+        }
+        for child in &self.children {
+            f.write_str("\n")?;
+            child.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Description {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+fn debug<T: fmt::Debug + ?Sized>(value: &T) -> String {
+    let mut s = String::new();
+    let _ = write!(s, "{:?}", value);
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::collection::vec;
+    use crate::std_facade::string::ToString;
+    use crate::strategy::{Just, Strategy};
+
+    #[test]
+    fn describes_leaves_by_debug() {
+        assert_eq!(Description::new("0..10"), (0..10u32).describe());
+        assert_eq!(Description::new("Just(5)"), Just(5).describe());
+    }
+
+    #[test]
+    fn describes_combinators() {
+        let input = vec((0..10u32, Just(1u8)), 1..4)
+            .prop_filter("non-empty", |v| !v.is_empty())
+            .prop_map(|v| v.len())
+            .boxed();
+
+        let expected = Description::new("prop_map").child(
+            Description::new("prop_filter")
+                .param("whence", "non-empty")
+                .child(
+                    Description::new("vec").param("size", 1..=3usize).child(
+                        Description::new("tuple")
+                            .child(Description::new("0..10"))
+                            .child(Description::new("Just(1)")),
+                    ),
+                ),
+        );
+        assert_eq!(expected, input.describe());
+    }
+
+    #[test]
+    fn describes_unions() {
+        let input = prop_oneof![3 => Just(0u8), 1 => 1..10u8];
+        let description = input.describe();
+        assert_eq!("prop_oneof", description.name());
+        assert_eq!(&[("weights", "[3, 1]".into())], description.params());
+        assert_eq!(2, description.children().len());
+    }
+
+    #[test]
+    fn displays_tree() {
+        let input = (0..10u32, Just(1u8))
+            .prop_filter("a > b", |&(a, b)| a > u32::from(b))
+            .prop_map(|(a, _)| a);
+        assert_eq!(
+            "prop_map\n  \
+             prop_filter(whence = \"a > b\")\n    \
+             tuple\n      \
+             0..10\n      \
+             Just(1)",
+            input.describe().to_string()
+        );
+    }
+}
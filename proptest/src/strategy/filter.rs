@@ -10,6 +10,7 @@
 use crate::std_facade::{fmt, Arc};
 
 use crate::strategy::traits::*;
+use crate::strategy::Description;
 use crate::test_runner::*;
 
 /// `Strategy` and `ValueTree` filter adaptor.
@@ -70,6 +71,12 @@ impl<S: Strategy, F: Fn(&S::Value) -> bool> Strategy for Filter<S, F> {
             }
         }
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_filter")
+            .param("whence", self.whence.message())
+            .child(self.source.describe())
+    }
 }
 
 impl<S: ValueTree, F: Fn(&S::Value) -> bool> Filter<S, F> {
@@ -10,6 +10,7 @@
 use crate::std_facade::{fmt, Arc, Cell};
 
 use crate::strategy::traits::*;
+use crate::strategy::Description;
 use crate::test_runner::*;
 
 /// `Strategy` and `ValueTree` filter_map adaptor.
@@ -68,6 +69,12 @@ impl<S: Strategy, F: Fn(S::Value) -> Option<O>, O: fmt::Debug> Strategy
             }
         }
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_filter_map")
+            .param("whence", self.whence.message())
+            .child(self.source.describe())
+    }
 }
 
 /// `ValueTree` corresponding to `FilterMap`.
@@ -188,6 +195,18 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_filter_map_rejects_locally() {
+        let mut runner = TestRunner::new(Config {
+            max_local_rejects: 10,
+            ..Config::default()
+        });
+        let input = (0..256).prop_filter_map("never", |_| None::<i32>);
+
+        assert!(input.new_tree(&mut runner).is_err());
+        assert!(format!("{}", runner).contains("10 times at never"));
+    }
+
     #[test]
     fn test_filter_map_sanity() {
         check_strategy_sanity(
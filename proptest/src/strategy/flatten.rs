@@ -11,7 +11,9 @@ use crate::std_facade::{fmt, Arc};
 use core::mem;
 
 use crate::strategy::fuse::Fuse;
+use crate::strategy::just::Just;
 use crate::strategy::traits::*;
+use crate::strategy::Description;
 use crate::test_runner::*;
 
 /// Adaptor that flattens a `Strategy` which produces other `Strategy`s into a
@@ -41,6 +43,10 @@ where
         let meta = self.source.new_tree(runner)?;
         FlattenValueTree::new(runner, meta)
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_flatten").child(self.source.describe())
+    }
 }
 
 /// The `ValueTree` produced by `Flatten`.
@@ -50,6 +56,13 @@ where
 {
     meta: Fuse<S>,
     current: Fuse<<S::Value as Strategy>::Tree>,
+    // The state of the RNG from which `current` was generated.
+    current_rng: TestRng,
+    // The state of the RNG from which the last failing value was generated.
+    // When the meta value changes, the new strategy first generates from this
+    // same state, so that the new value resembles the failing one as closely
+    // as the strategies allow, rather than being a fresh random value.
+    failing_rng: TestRng,
     // The final value to produce after successive calls to complicate() on the
     // underlying objects return false.
     final_complication: Option<Fuse<<S::Value as Strategy>::Tree>>,
@@ -77,6 +90,8 @@ where
         FlattenValueTree {
             meta: self.meta.clone(),
             current: self.current.clone(),
+            current_rng: self.current_rng.clone(),
+            failing_rng: self.failing_rng.clone(),
             final_complication: self.final_complication.clone(),
             runner: self.runner.clone(),
             complicate_regen_remaining: self.complicate_regen_remaining,
@@ -94,6 +109,8 @@ where
         f.debug_struct("FlattenValueTree")
             .field("meta", &self.meta)
             .field("current", &self.current)
+            .field("current_rng", &self.current_rng)
+            .field("failing_rng", &self.failing_rng)
             .field("final_complication", &self.final_complication)
             .field(
                 "complicate_regen_remaining",
@@ -108,15 +125,38 @@ where
     S::Value: Strategy,
 {
     fn new(runner: &mut TestRunner, meta: S) -> Result<Self, Reason> {
+        let current_rng = runner.rng().clone();
         let current = meta.current().new_tree(runner)?;
         Ok(FlattenValueTree {
             meta: Fuse::new(meta),
             current: Fuse::new(current),
+            failing_rng: current_rng.clone(),
+            current_rng,
             final_complication: None,
             runner: runner.partial_clone(),
             complicate_regen_remaining: 0,
         })
     }
+
+    /// Generates a new value from the current meta value, replaying the
+    /// randomness of the last failing value if `replay` is true, or from
+    /// fresh randomness otherwise.
+    fn regenerate(
+        &mut self,
+        replay: bool,
+    ) -> Result<(Fuse<<S::Value as Strategy>::Tree>, TestRng), Reason> {
+        let saved = if replay {
+            Some(mem::replace(self.runner.rng(), self.failing_rng.clone()))
+        } else {
+            None
+        };
+        let rng = self.runner.rng().clone();
+        let tree = self.meta.current().new_tree(&mut self.runner);
+        if let Some(saved) = saved {
+            *self.runner.rng() = saved;
+        }
+        Ok((Fuse::new(tree?), rng))
+    }
 }
 
 impl<S: ValueTree> ValueTree for FlattenValueTree<S>
@@ -131,6 +171,7 @@ where
 
     fn simplify(&mut self) -> bool {
         self.complicate_regen_remaining = 0;
+        self.failing_rng = self.current_rng.clone();
 
         if self.current.simplify() {
             // Now that we've simplified the derivative value, we can't
@@ -143,19 +184,17 @@ where
             true
         } else if !self.meta.simplify() {
             false
-        } else if let Ok(v) = self.meta.current().new_tree(&mut self.runner) {
+        } else if let Ok((v, rng)) = self.regenerate(true) {
             // Shift current into final_complication and `v` into
             // `current`. We also need to prevent that value from
             // complicating beyond the current point in the future
             // since we're going to return `true` from `simplify()`
             // ourselves.
             self.current.disallow_complicate();
-            self.final_complication = Some(Fuse::new(v));
-            mem::swap(
-                self.final_complication.as_mut().unwrap(),
-                &mut self.current,
-            );
-            // Initially complicate by regenerating the chosen value.
+            self.final_complication = Some(mem::replace(&mut self.current, v));
+            self.current_rng = rng;
+            // If the replayed value passes, complicate by regenerating the
+            // chosen value from fresh randomness.
             self.complicate_regen_remaining = self.runner.config().cases;
             true
         } else {
@@ -168,8 +207,9 @@ where
             if self.runner.flat_map_regen() {
                 self.complicate_regen_remaining -= 1;
 
-                if let Ok(v) = self.meta.current().new_tree(&mut self.runner) {
-                    self.current = Fuse::new(v);
+                if let Ok((v, rng)) = self.regenerate(false) {
+                    self.current = v;
+                    self.current_rng = rng;
                     return true;
                 }
             } else {
@@ -180,15 +220,18 @@ where
         if self.current.complicate() {
             return true;
         } else if self.meta.complicate() {
-            if let Ok(v) = self.meta.current().new_tree(&mut self.runner) {
+            if let Ok((v, rng)) = self.regenerate(true) {
                 self.complicate_regen_remaining = self.runner.config().cases;
-                self.current = Fuse::new(v);
+                self.current = v;
+                self.current_rng = rng;
                 return true;
             }
         }
 
         if let Some(v) = self.final_complication.take() {
+            // This is the last failing value.
             self.current = v;
+            self.current_rng = self.failing_rng.clone();
             true
         } else {
             false
@@ -213,6 +256,10 @@ where
         let inner = self.0.new_tree(runner)?;
         inner.current().new_tree(runner)
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_ind_flatten").child(self.0.describe())
+    }
 }
 
 /// Similar to `Map` plus `Flatten`, but does not shrink the input strategy and
@@ -255,6 +302,105 @@ impl<S: Strategy, R: Strategy, F: Fn(S::Value) -> R> Strategy
 
         Ok(crate::tuple::TupleValueTree::new((left, right)))
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_ind_flat_map2").child(self.source.describe())
+    }
+}
+
+/// Similar to `Map` plus `Flatten`, but passes the original input through
+/// and shrinks it together with the derived value.
+///
+/// See `Strategy::prop_dependent()` for more details.
+#[must_use = "strategies do nothing unless used"]
+pub struct Dependent<S, F> {
+    pub(super) source: S,
+    pub(super) fun: Arc<F>,
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for Dependent<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Dependent")
+            .field("source", &self.source)
+            .field("fun", &"<function>")
+            .finish()
+    }
+}
+
+impl<S: Clone, F> Clone for Dependent<S, F> {
+    fn clone(&self) -> Self {
+        Dependent {
+            source: self.source.clone(),
+            fun: Arc::clone(&self.fun),
+        }
+    }
+}
+
+impl<S: Strategy, R: Strategy, F: Fn(S::Value) -> R> Strategy
+    for Dependent<S, F>
+where
+    S::Value: Clone,
+{
+    type Tree = FlattenValueTree<DependentValueTree<S::Tree, F>>;
+    type Value = (S::Value, R::Value);
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let meta = DependentValueTree {
+            source: self.source.new_tree(runner)?,
+            fun: Arc::clone(&self.fun),
+        };
+        FlattenValueTree::new(runner, meta)
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_dependent").child(self.source.describe())
+    }
+}
+
+/// `ValueTree` producing the strategy of the pairs generated by `Dependent`,
+/// made of the input and the strategy derived from it.
+pub struct DependentValueTree<S, F> {
+    source: S,
+    fun: Arc<F>,
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for DependentValueTree<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DependentValueTree")
+            .field("source", &self.source)
+            .field("fun", &"<function>")
+            .finish()
+    }
+}
+
+impl<S: Clone, F> Clone for DependentValueTree<S, F> {
+    fn clone(&self) -> Self {
+        DependentValueTree {
+            source: self.source.clone(),
+            fun: Arc::clone(&self.fun),
+        }
+    }
+}
+
+impl<S: ValueTree, R: Strategy, F: Fn(S::Value) -> R> ValueTree
+    for DependentValueTree<S, F>
+where
+    S::Value: Clone,
+{
+    type Value = (Just<S::Value>, R);
+
+    fn current(&self) -> Self::Value {
+        let input = self.source.current();
+        (Just(input.clone()), (self.fun)(input))
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.source.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.source.complicate()
+    }
 }
 
 #[cfg(test)]
@@ -263,7 +409,6 @@ mod test {
 
     use std::u32;
 
-    use crate::strategy::just::Just;
     use crate::test_runner::Config;
 
     #[test]
@@ -304,6 +449,41 @@ mod test {
         assert!(failures > 250);
     }
 
+    #[test]
+    fn flat_map_replays_inner_randomness() {
+        // The length is chosen first and only rarely produces a failing
+        // vector. Simplifying the length must replay the randomness which
+        // produced the failing element, rather than generate a fresh vector
+        // which most likely passes, so that the vector shrinks to just long
+        // enough to contain that element, even without regenerating values
+        // at random.
+        let input = (0..100usize)
+            .prop_flat_map(|n| crate::collection::vec(0..10_000u32, n));
+
+        let mut runner = TestRunner::new_with_rng(
+            Config {
+                max_shrink_iters: u32::MAX - 1,
+                max_flat_map_regens: 0,
+                ..Config::default()
+            },
+            TestRng::deterministic_rng(RngAlgorithm::default()),
+        );
+        for _ in 0..16 {
+            let result = runner.run(&input, |v| {
+                prop_assert!(v.iter().all(|&x| x < 9_900));
+                Ok(())
+            });
+            match result {
+                Err(TestError::Fail(_, v)) => {
+                    let (last, rest) = v.split_last().unwrap();
+                    assert_eq!(9_900, *last, "{:?}", v);
+                    assert!(rest.iter().all(|&x| 0 == x), "{:?}", v);
+                }
+                result => panic!("Unexpected result: {:?}", result),
+            }
+        }
+    }
+
     #[test]
     fn test_flat_map_sanity() {
         check_strategy_sanity(
@@ -341,6 +521,44 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_dependent_sanity() {
+        check_strategy_sanity(
+            (0..65536).prop_dependent(|a| a - 5..a + 5),
+            None,
+        );
+    }
+
+    #[test]
+    fn dependent_shrinks_index_with_vec() {
+        // Pick a vector, then an index into it, and fail if the element at
+        // that index is large. Both shrink together, down to a vector with
+        // just the failing element.
+        let input = crate::collection::vec(0..100u32, 1..50)
+            .prop_dependent(|v| 0..v.len());
+
+        let mut runner = TestRunner::new_with_rng(
+            Config {
+                max_shrink_iters: u32::MAX - 1,
+                ..Config::default()
+            },
+            TestRng::deterministic_rng(RngAlgorithm::default()),
+        );
+        for _ in 0..16 {
+            let result = runner.run(&input, |(v, i)| {
+                prop_assert!(i < v.len());
+                prop_assert!(v[i] < 50);
+                Ok(())
+            });
+            match result {
+                Err(TestError::Fail(_, (v, i))) => {
+                    assert_eq!((vec![50], 0), (v, i));
+                }
+                result => panic!("Unexpected result: {:?}", result),
+            }
+        }
+    }
+
     #[test]
     fn test_ind_flat_map_sanity() {
         check_strategy_sanity(
@@ -63,6 +63,10 @@ impl<T: Strategy> Strategy for Fuse<T> {
     fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
         self.inner.new_tree(runner).map(Fuse::new)
     }
+
+    fn describe(&self) -> Description {
+        self.inner.describe()
+    }
 }
 
 impl<T: ValueTree> Fuse<T> {
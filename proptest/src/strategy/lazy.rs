@@ -9,8 +9,11 @@
 
 use crate::std_facade::{fmt, Arc};
 use core::mem;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
 
 use crate::strategy::traits::*;
+use crate::strategy::Description;
 use crate::test_runner::*;
 
 /// Represents a value tree that is initialized on the first call to any
@@ -48,6 +51,14 @@ impl<S: Strategy> LazyValueTree<S> {
         }
     }
 
+    /// Create a value tree that is never initialized, for options which must
+    /// not be generated.
+    pub(crate) fn new_disabled() -> Self {
+        Self {
+            state: LazyValueTreeState::Failed,
+        }
+    }
+
     /// Returns a reference to the inner value tree if initialized.
     pub(crate) fn as_inner(&self) -> Option<&S::Tree> {
         match &self.state {
@@ -173,3 +184,154 @@ where
         }
     }
 }
+
+/// A `Strategy` which builds its inner strategy on first use and shares it
+/// between all its clones.
+///
+/// This is useful for strategies which are expensive to construct, such as
+/// those compiling a regular expression or loading data from a file: the
+/// inner strategy is built once when the first value is generated, rather
+/// than each time the enclosing strategy is built, and never if no value is
+/// generated. Clones only copy an `Arc`.
+///
+/// **It is important that the function used be pure.**
+///
+/// ```
+/// use proptest::prelude::*;
+/// use proptest::strategy::LazyStrategy;
+/// use proptest::string::string_regex;
+///
+/// fn arb_ident() -> impl Strategy<Value = String> + Clone {
+///     LazyStrategy::new(|| string_regex("[a-z][a-z0-9_]{0,15}").unwrap())
+/// }
+///
+/// proptest! {
+///     fn idents_are_short(ident in arb_ident()) {
+///         prop_assert!(ident.len() <= 16);
+///     }
+/// }
+/// # fn main() { idents_are_short(); }
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[must_use = "strategies do nothing unless used"]
+pub struct LazyStrategy<S, F: Fn() -> S> {
+    inner: Arc<LazyStrategyInner<S, F>>,
+}
+
+/// Shorthand for `LazyStrategy<S, fn () -> S>`.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub type LazyStrategyFn<S> = LazyStrategy<S, fn() -> S>;
+
+#[cfg(feature = "std")]
+struct LazyStrategyInner<S, F> {
+    strategy: OnceLock<S>,
+    // `None` if the strategy was built up front.
+    function: Option<F>,
+}
+
+#[cfg(feature = "std")]
+impl<S, F: Fn() -> S> LazyStrategy<S, F> {
+    /// Constructs a `LazyStrategy` given the function/closure building the
+    /// inner strategy, which is called at most once.
+    pub fn new(function: F) -> Self {
+        LazyStrategy {
+            inner: Arc::new(LazyStrategyInner {
+                strategy: OnceLock::new(),
+                function: Some(function),
+            }),
+        }
+    }
+
+    /// Returns the inner strategy, building it if this is its first use.
+    pub fn get(&self) -> &S {
+        self.inner.strategy.get_or_init(|| {
+            let function = self.inner.function.as_ref();
+            function.expect("strategy is built unless it has a function")()
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S> LazyStrategyFn<S> {
+    /// Constructs a `LazyStrategy` sharing the already built `strategy`.
+    pub(crate) fn from_strategy(strategy: S) -> Self {
+        LazyStrategy {
+            inner: Arc::new(LazyStrategyInner {
+                strategy: OnceLock::from(strategy),
+                function: None,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Strategy, F: Fn() -> S> Strategy for LazyStrategy<S, F> {
+    type Tree = S::Tree;
+    type Value = S::Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.get().new_tree(runner)
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_cached").child(self.get().describe())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F: Fn() -> S> Clone for LazyStrategy<S, F> {
+    fn clone(&self) -> Self {
+        LazyStrategy {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: fmt::Debug, F: Fn() -> S> fmt::Debug for LazyStrategy<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner.strategy.get() {
+            Some(strategy) => {
+                f.debug_tuple("LazyStrategy").field(strategy).finish()
+            }
+            None => f
+                .debug_struct("LazyStrategy")
+                .field("function", &"<function>")
+                .finish(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn lazy_strategy_builds_once_on_first_use() {
+        static BUILDS: AtomicUsize = AtomicUsize::new(0);
+        let input = LazyStrategy::new(|| {
+            BUILDS.fetch_add(1, Ordering::SeqCst);
+            0..10u32
+        });
+        let clone = input.clone();
+        assert_eq!(0, BUILDS.load(Ordering::SeqCst));
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..16 {
+            assert!(input.new_tree(&mut runner).unwrap().current() < 10);
+            assert!(clone.new_tree(&mut runner).unwrap().current() < 10);
+        }
+        assert_eq!(1, BUILDS.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn cached_strategy_is_shared_between_clones() {
+        let input = (0..10u32).prop_cached();
+        let clone = input.clone();
+        assert!(core::ptr::eq(input.get(), clone.get()));
+    }
+}
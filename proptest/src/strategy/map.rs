@@ -12,6 +12,7 @@ use core::fmt;
 use core::marker::PhantomData;
 
 use crate::strategy::traits::*;
+use crate::strategy::Description;
 use crate::test_runner::*;
 
 //==============================================================================
@@ -55,6 +56,10 @@ impl<S: Strategy, O: fmt::Debug, F: Fn(S::Value) -> O> Strategy for Map<S, F> {
             fun: Arc::clone(&self.fun),
         })
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_map").child(self.source.describe())
+    }
 }
 
 impl<S: ValueTree, O: fmt::Debug, F: Fn(S::Value) -> O> ValueTree
@@ -126,6 +131,10 @@ where
     fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
         self.source.new_tree(runner).map(MapInto::new)
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_map_into").child(self.source.describe())
+    }
 }
 
 impl<S: ValueTree, O: fmt::Debug> ValueTree for MapInto<S, O>
@@ -193,6 +202,10 @@ impl<S: Strategy, O: fmt::Debug, F: Fn(S::Value, TestRng) -> O> Strategy
             fun: Arc::clone(&self.fun),
         })
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_perturb").child(self.source.describe())
+    }
 }
 
 /// `ValueTree` perturbation adaptor.
@@ -242,6 +255,90 @@ impl<S: ValueTree, O: fmt::Debug, F: Fn(S::Value, TestRng) -> O> ValueTree
     }
 }
 
+//==============================================================================
+// Normalize
+//==============================================================================
+
+/// `Strategy` and `ValueTree` normalization adaptor.
+///
+/// See `Strategy::prop_normalize()`.
+#[must_use = "strategies do nothing unless used"]
+pub struct Normalize<S, F> {
+    pub(super) source: S,
+    pub(super) fun: Arc<F>,
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for Normalize<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Normalize")
+            .field("source", &self.source)
+            .field("fun", &"<function>")
+            .finish()
+    }
+}
+
+impl<S: Clone, F> Clone for Normalize<S, F> {
+    fn clone(&self) -> Self {
+        Normalize {
+            source: self.source.clone(),
+            fun: Arc::clone(&self.fun),
+        }
+    }
+}
+
+impl<S: Strategy, F: Fn(S::Value) -> S::Value> Strategy for Normalize<S, F>
+where
+    S::Value: PartialEq,
+{
+    type Tree = Normalize<S::Tree, F>;
+    type Value = S::Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.source.new_tree(runner).map(|v| Normalize {
+            source: v,
+            fun: Arc::clone(&self.fun),
+        })
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_normalize").child(self.source.describe())
+    }
+}
+
+impl<S: ValueTree, F: Fn(S::Value) -> S::Value> ValueTree for Normalize<S, F>
+where
+    S::Value: PartialEq,
+{
+    type Value = S::Value;
+
+    fn current(&self) -> S::Value {
+        (self.fun)(self.source.current())
+    }
+
+    fn simplify(&mut self) -> bool {
+        // A source value which normalizes to the current value would fail
+        // the test again, so keep simplifying until the value changes.
+        let prev = self.current();
+        while self.source.simplify() {
+            if self.current() != prev {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn complicate(&mut self) -> bool {
+        // Likewise, such a value would pass the test again.
+        let prev = self.current();
+        while self.source.complicate() {
+            if self.current() != prev {
+                return true;
+            }
+        }
+        false
+    }
+}
+
 //==============================================================================
 // Tests
 //==============================================================================
@@ -298,4 +395,42 @@ mod test {
 
         assert_eq!(64, seen.len());
     }
+
+    #[test]
+    fn normalize_holds_while_shrinking() {
+        let input =
+            crate::collection::vec(0..100i32, 1..10).prop_normalize(|mut v| {
+                v.sort();
+                v
+            });
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let mut value = input.new_tree(&mut runner).unwrap();
+            let mut prev = value.current();
+            while value.simplify() {
+                let current = value.current();
+                assert!(current.windows(2).all(|w| w[0] <= w[1]));
+                assert_ne!(prev, current);
+                prev = current;
+            }
+        }
+    }
+
+    #[test]
+    fn normalize_shrinks_to_minimal_value() {
+        let input = (0..100i32, 0..100i32)
+            .prop_normalize(|(a, b)| (a.min(b), a.max(b)));
+
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&input, |(a, b)| {
+            prop_assert!(a <= b);
+            prop_assert!(b < 50);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, v)) => assert_eq!((0, 50), v),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
 }
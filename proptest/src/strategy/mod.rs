@@ -9,6 +9,8 @@
 
 //! Defines the core traits used by Proptest.
 
+mod choices;
+mod describe;
 mod filter;
 mod filter_map;
 mod flatten;
@@ -17,10 +19,14 @@ mod just;
 mod lazy;
 mod map;
 mod recursive;
+mod shrink_cost;
+mod shrink_with;
 mod shuffle;
 mod traits;
 mod unions;
 
+pub use self::choices::*;
+pub use self::describe::*;
 pub use self::filter::*;
 pub use self::filter_map::*;
 pub use self::flatten::*;
@@ -29,6 +35,8 @@ pub use self::just::*;
 pub use self::lazy::*;
 pub use self::map::*;
 pub use self::recursive::*;
+pub use self::shrink_cost::*;
+pub use self::shrink_with::*;
 pub use self::shuffle::*;
 pub use self::traits::*;
 pub use self::unions::*;
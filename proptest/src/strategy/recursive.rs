@@ -11,6 +11,7 @@ use crate::std_facade::{fmt, Arc, Box, Vec};
 
 use crate::strategy::traits::*;
 use crate::strategy::unions::float_to_weight;
+use crate::strategy::Description;
 use crate::test_runner::*;
 
 /// Return type from `Strategy::prop_recursive()`.
@@ -108,11 +109,17 @@ impl<
         // also clamp all probabilities to 0.9 to ensure that we can't end up
         // with levels which are always pure branches, which further
         // underestimates size.
+        //
+        // Both the depth and the desired size are scaled down according to the
+        // runner's current size.
+
+        let depth = runner.sized(0, self.depth as usize);
+        let desired_size = runner.sized(0, self.desired_size as usize);
 
         let mut branch_probabilities = Vec::new();
         let mut k2 = u64::from(self.expected_branch_size) * 2;
-        for _ in 0..self.depth {
-            branch_probabilities.push(f64::from(self.desired_size) / k2 as f64);
+        for _ in 0..depth {
+            branch_probabilities.push(desired_size as f64 / k2 as f64);
             k2 = k2.saturating_mul(u64::from(self.expected_branch_size) * 2);
         }
 
@@ -135,6 +142,14 @@ impl<
 
         strat.new_tree(runner)
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_recursive")
+            .param("depth", self.depth)
+            .param("desired_size", self.desired_size)
+            .param("expected_branch_size", self.expected_branch_size)
+            .child(self.base.describe())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,187 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::std_facade::{fmt, Arc};
+
+use crate::strategy::traits::*;
+use crate::strategy::Description;
+use crate::test_runner::*;
+
+/// `Strategy` and `ValueTree` adaptor which only shrinks towards values of no
+/// greater cost.
+///
+/// See `Strategy::prop_shrink_cost()`.
+#[must_use = "strategies do nothing unless used"]
+pub struct ShrinkCost<S, F> {
+    pub(super) source: S,
+    pub(super) fun: Arc<F>,
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for ShrinkCost<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShrinkCost")
+            .field("source", &self.source)
+            .field("fun", &"<function>")
+            .finish()
+    }
+}
+
+impl<S: Clone, F> Clone for ShrinkCost<S, F> {
+    fn clone(&self) -> Self {
+        ShrinkCost {
+            source: self.source.clone(),
+            fun: Arc::clone(&self.fun),
+        }
+    }
+}
+
+impl<S: Strategy, F: Fn(&S::Value) -> u64> Strategy for ShrinkCost<S, F> {
+    type Tree = ShrinkCostValueTree<S::Tree, F>;
+    type Value = S::Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.source
+            .new_tree(runner)
+            .map(|source| ShrinkCostValueTree {
+                cost: (self.fun)(&source.current()),
+                source,
+                fun: Arc::clone(&self.fun),
+            })
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_shrink_cost").child(self.source.describe())
+    }
+}
+
+/// `ValueTree` corresponding to `ShrinkCost`.
+pub struct ShrinkCostValueTree<V, F> {
+    source: V,
+    fun: Arc<F>,
+    // The cost of the last failing value.
+    cost: u64,
+}
+
+impl<V: fmt::Debug, F> fmt::Debug for ShrinkCostValueTree<V, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShrinkCostValueTree")
+            .field("source", &self.source)
+            .field("fun", &"<function>")
+            .field("cost", &self.cost)
+            .finish()
+    }
+}
+
+impl<V: Clone, F> Clone for ShrinkCostValueTree<V, F> {
+    fn clone(&self) -> Self {
+        ShrinkCostValueTree {
+            source: self.source.clone(),
+            fun: Arc::clone(&self.fun),
+            cost: self.cost,
+        }
+    }
+}
+
+impl<V: ValueTree, F: Fn(&V::Value) -> u64> ShrinkCostValueTree<V, F> {
+    fn affordable(&self) -> bool {
+        (self.fun)(&self.source.current()) <= self.cost
+    }
+}
+
+impl<V: ValueTree, F: Fn(&V::Value) -> u64> ValueTree
+    for ShrinkCostValueTree<V, F>
+{
+    type Value = V::Value;
+
+    fn current(&self) -> V::Value {
+        self.source.current()
+    }
+
+    fn simplify(&mut self) -> bool {
+        // `simplify()` is only called when the current value fails, so its
+        // cost bounds the cost of the values to try next.
+        self.cost = (self.fun)(&self.source.current());
+        while self.source.simplify() {
+            if self.affordable() {
+                return true;
+            }
+            // Costlier values are treated as passing, without running the
+            // test, until the source returns to the failing value and can
+            // be simplified in another way.
+            while self.source.complicate() {
+                if self.affordable() {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn complicate(&mut self) -> bool {
+        if !self.source.complicate() {
+            return false;
+        }
+        while !self.affordable() && self.source.complicate() {}
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::collection::vec;
+
+    fn minimize<S: Strategy>(
+        input: S,
+        test: impl Fn(S::Value) -> TestCaseResult,
+    ) -> S::Value {
+        let mut runner = TestRunner::deterministic();
+        match runner.run(&input, test) {
+            Err(TestError::Fail(_, value)) => value,
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn never_shrinks_to_costlier_values() {
+        // Without a cost, this would shrink to 100.
+        let input = (0..1000u32).prop_shrink_cost(|&x| x.abs_diff(500).into());
+
+        let x = minimize(input, |x| {
+            prop_assert!(x < 100);
+            Ok(())
+        });
+        assert!(x >= 100);
+        assert!(x.abs_diff(500) < 400, "{}", x);
+    }
+
+    #[test]
+    fn shrinks_values_of_equal_cost() {
+        let input = vec(0..1000u32, 1..10).prop_shrink_cost(|v| v.len() as u64);
+
+        let v = minimize(input, |v| {
+            prop_assert!(v.iter().all(|&x| x < 500));
+            Ok(())
+        });
+        assert_eq!(vec![500], v);
+    }
+
+    #[test]
+    fn test_sanity() {
+        check_strategy_sanity(
+            (0..1000u32).prop_shrink_cost(|&x| x.abs_diff(500).into()),
+            Some(CheckStrategySanityOptions {
+                // Skipping costlier values may use up the ways the source
+                // can be complicated.
+                strict_complicate_after_simplify: false,
+                ..CheckStrategySanityOptions::default()
+            }),
+        );
+    }
+}
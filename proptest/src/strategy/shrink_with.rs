@@ -0,0 +1,284 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::std_facade::{fmt, vec, Arc, Vec};
+
+use crate::strategy::traits::*;
+use crate::strategy::Description;
+use crate::test_runner::*;
+
+/// `Strategy` and `ValueTree` adaptor shrinking with user-supplied
+/// candidates.
+///
+/// See `Strategy::prop_shrink_with()`.
+#[must_use = "strategies do nothing unless used"]
+pub struct ShrinkWith<S, F> {
+    pub(super) source: S,
+    pub(super) fun: Arc<F>,
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for ShrinkWith<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShrinkWith")
+            .field("source", &self.source)
+            .field("fun", &"<function>")
+            .finish()
+    }
+}
+
+impl<S: Clone, F> Clone for ShrinkWith<S, F> {
+    fn clone(&self) -> Self {
+        ShrinkWith {
+            source: self.source.clone(),
+            fun: Arc::clone(&self.fun),
+        }
+    }
+}
+
+impl<S: Strategy, I, F> Strategy for ShrinkWith<S, F>
+where
+    S::Value: Clone,
+    I: IntoIterator<Item = S::Value>,
+    F: Fn(&S::Value) -> I,
+{
+    type Tree = ShrinkWithValueTree<S::Tree, F>;
+    type Value = S::Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.source
+            .new_tree(runner)
+            .map(|source| ShrinkWithValueTree {
+                source,
+                fun: Arc::clone(&self.fun),
+                accepted: None,
+                candidates: Vec::new().into_iter(),
+                trying: None,
+            })
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_shrink_with").child(self.source.describe())
+    }
+}
+
+/// `ValueTree` corresponding to `ShrinkWith`.
+pub struct ShrinkWithValueTree<V: ValueTree, F> {
+    source: V,
+    fun: Arc<F>,
+    // Once `source` can no longer be simplified, the last failing value,
+    // from which the user-supplied candidates are derived.
+    accepted: Option<V::Value>,
+    // The candidates derived from `accepted` not tried yet.
+    candidates: vec::IntoIter<V::Value>,
+    // The candidate being tried.
+    trying: Option<V::Value>,
+}
+
+impl<V: ValueTree, F> fmt::Debug for ShrinkWithValueTree<V, F>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShrinkWithValueTree")
+            .field("source", &self.source)
+            .field("fun", &"<function>")
+            .field("accepted", &self.accepted)
+            .field("candidates", &self.candidates)
+            .field("trying", &self.trying)
+            .finish()
+    }
+}
+
+impl<V: ValueTree + Clone, F> Clone for ShrinkWithValueTree<V, F>
+where
+    V::Value: Clone,
+{
+    fn clone(&self) -> Self {
+        ShrinkWithValueTree {
+            source: self.source.clone(),
+            fun: Arc::clone(&self.fun),
+            accepted: self.accepted.clone(),
+            candidates: self.candidates.clone(),
+            trying: self.trying.clone(),
+        }
+    }
+}
+
+impl<V: ValueTree, F> ShrinkWithValueTree<V, F> {
+    fn next_candidate(&mut self) -> bool {
+        self.trying = self.candidates.next();
+        self.trying.is_some()
+    }
+}
+
+impl<V: ValueTree, I, F> ValueTree for ShrinkWithValueTree<V, F>
+where
+    V::Value: Clone,
+    I: IntoIterator<Item = V::Value>,
+    F: Fn(&V::Value) -> I,
+{
+    type Value = V::Value;
+
+    fn current(&self) -> V::Value {
+        match (&self.trying, &self.accepted) {
+            (Some(value), _) | (None, Some(value)) => value.clone(),
+            (None, None) => self.source.current(),
+        }
+    }
+
+    fn simplify(&mut self) -> bool {
+        // `simplify()` is only called when the current value fails, so that
+        // value is where the user-supplied candidates continue from.
+        let accepted = match (self.trying.take(), &self.accepted) {
+            (Some(value), _) => value,
+            // Every candidate of the accepted value passed.
+            (None, Some(_)) => return false,
+            (None, None) => {
+                if self.source.simplify() {
+                    return true;
+                }
+                self.source.current()
+            }
+        };
+        self.candidates = (self.fun)(&accepted)
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into_iter();
+        self.accepted = Some(accepted);
+        self.next_candidate()
+    }
+
+    fn complicate(&mut self) -> bool {
+        if self.accepted.is_none() {
+            self.source.complicate()
+        } else if self.trying.is_some() {
+            // The candidate passed, so try the next one, if any, or return
+            // to the last failing value.
+            self.next_candidate();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arbitrary::any;
+    use crate::collection;
+    use crate::std_facade::Box;
+    use crate::strategy::Just;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Tree {
+        Leaf(u8),
+        Node(Box<Tree>, Box<Tree>),
+    }
+
+    fn children(tree: &Tree) -> Vec<Tree> {
+        match tree {
+            Tree::Leaf(_) => vec![],
+            Tree::Node(left, right) => {
+                vec![(**left).clone(), (**right).clone()]
+            }
+        }
+    }
+
+    fn depth(tree: &Tree) -> usize {
+        match tree {
+            Tree::Leaf(_) => 0,
+            Tree::Node(left, right) => 1 + depth(left).max(depth(right)),
+        }
+    }
+
+    fn trees() -> impl Strategy<Value = Tree> {
+        any::<u8>()
+            .prop_map(Tree::Leaf)
+            .prop_recursive(4, 16, 2, |inner| {
+                (inner.clone(), inner)
+                    .prop_map(|(l, r)| Tree::Node(Box::new(l), Box::new(r)))
+            })
+    }
+
+    #[test]
+    fn shrinks_with_candidates_after_source() {
+        // Once the built-in shrinking is done, replacing nodes with their
+        // children keeps shrinking the tree.
+        let input = trees().prop_shrink_with(children);
+
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&input, |tree| {
+            prop_assert!(depth(&tree) < 2);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, tree)) => assert_eq!(2, depth(&tree)),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn replaces_builtin_shrinking() {
+        // Halving the length is the only shrinking step, so a vector of 7
+        // elements can only shrink to 3 elements, not 1.
+        let input = Just(vec![0u8; 7])
+            .no_shrink()
+            .prop_shrink_with(|v| vec![v[..v.len() / 2].to_vec()]);
+
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&input, |v| {
+            prop_assert!(v.len() < 2);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, v)) => assert_eq!(3, v.len()),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn returns_to_failing_value_when_candidates_pass() {
+        let input =
+            collection::vec(0..10u32, 4)
+                .no_shrink()
+                .prop_shrink_with(|v| {
+                    (0..v.len())
+                        .map(|i| {
+                            let mut v = v.clone();
+                            v.remove(i);
+                            v
+                        })
+                        .collect::<Vec<_>>()
+                });
+
+        let mut runner = TestRunner::deterministic();
+        let mut case = input.new_tree(&mut runner).unwrap();
+        let orig = case.current();
+        assert!(case.simplify());
+        for _ in 0..4 {
+            assert_eq!(3, case.current().len());
+            assert!(case.complicate());
+        }
+        assert_eq!(orig, case.current());
+        assert!(!case.complicate());
+        assert!(!case.simplify());
+        assert_eq!(orig, case.current());
+    }
+
+    #[test]
+    fn test_sanity() {
+        check_strategy_sanity(
+            (0..1000u32).no_shrink().prop_shrink_with(|&v| {
+                [v / 2, v / 3].into_iter().filter(move |&c| c < v)
+            }),
+            None,
+        );
+    }
+}
@@ -13,6 +13,7 @@ use rand::Rng;
 
 use crate::num;
 use crate::strategy::traits::*;
+use crate::strategy::Description;
 use crate::test_runner::*;
 
 /// `Strategy` shuffle adaptor.
@@ -104,6 +105,10 @@ where
             simplifying_inner: false,
         })
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_shuffle").child(self.0.describe())
+    }
 }
 
 /// `ValueTree` shuffling adaptor.
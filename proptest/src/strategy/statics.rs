@@ -27,6 +27,7 @@
 use crate::std_facade::fmt;
 
 use crate::strategy::traits::*;
+use crate::strategy::Description;
 use crate::test_runner::*;
 
 //==============================================================================
@@ -90,6 +91,12 @@ impl<S: Strategy, F: FilterFn<S::Value> + Clone> Strategy for Filter<S, F> {
             }
         }
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_filter")
+            .param("whence", self.whence.message())
+            .child(self.source.describe())
+    }
 }
 
 impl<S: ValueTree, F: FilterFn<S::Value>> Filter<S, F> {
@@ -178,6 +185,10 @@ impl<S: Strategy, F: Clone + MapFn<S::Value>> Strategy for Map<S, F> {
             fun: self.fun.clone(),
         })
     }
+
+    fn describe(&self) -> Description {
+        Description::new("prop_map").child(self.source.describe())
+    }
 }
 
 impl<S: ValueTree, F: MapFn<S::Value>> ValueTree for Map<S, F> {
@@ -147,6 +147,46 @@ pub trait Strategy: fmt::Debug {
         }
     }
 
+    /// Returns a strategy which produces values canonicalized by the function
+    /// `fun`, such as by sorting, clamping or fixing checksums.
+    ///
+    /// Like `prop_map()`, `fun` is applied to every value, including every
+    /// candidate tried while shrinking, so values never leave the canonical
+    /// domain the way they could when a `prop_filter()` rejects them.
+    /// Additionally, shrinking skips steps of the source whose normalized
+    /// value is the same as the current one, since the test would have the
+    /// same outcome for them.
+    ///
+    /// `fun` should be deterministic, and is best idempotent.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use proptest::prelude::*;
+    ///
+    /// proptest! {
+    ///   fn test_something(v in prop::collection::vec(0..100, 0..10)
+    ///       .prop_normalize(|mut v| { v.sort(); v }))
+    ///   {
+    ///       prop_assert!(v.windows(2).all(|w| w[0] <= w[1]));
+    ///   }
+    /// }
+    /// # fn main() { test_something(); }
+    /// ```
+    fn prop_normalize<F: Fn(Self::Value) -> Self::Value>(
+        self,
+        fun: F,
+    ) -> Normalize<Self, F>
+    where
+        Self: Sized,
+        Self::Value: PartialEq,
+    {
+        Normalize {
+            source: self,
+            fun: Arc::new(fun),
+        }
+    }
+
     /// Maps values produced by this strategy into new strategies and picks
     /// values from those strategies.
     ///
@@ -160,8 +200,11 @@ pub trait Strategy: fmt::Debug {
     /// In the case of test failure, shrinking will not only shrink the output
     /// from the combinator itself, but also the input, i.e., the strategy used
     /// to generate the output itself. Doing this requires searching the new
-    /// derived strategy for a new failing input. The combinator will generate
-    /// up to `Config::cases` values for this search.
+    /// derived strategy for a new failing input. The combinator first
+    /// generates a value from the same random state as the last failing
+    /// value, so that, for example, a shorter vector keeps the elements of
+    /// the longer one which failed. If that value passes, the combinator will
+    /// generate up to `Config::cases` fresh values for this search.
     ///
     /// As a result, nested `prop_flat_map`/`Flatten` combinators risk
     /// exponential run time on this search for new failing values. To ensure
@@ -292,6 +335,48 @@ pub trait Strategy: fmt::Debug {
         }
     }
 
+    /// Returns a strategy which produces 2-tuples of the values generated by
+    /// `self` and values from the strategy derived from them by `fun`.
+    ///
+    /// This is a shorthand for
+    /// `prop_flat_map(|a| (Just(a.clone()), fun(a)))`, covering the common
+    /// case of a value which depends on a previously generated one, such as
+    /// an index into a vector. Unlike with `prop_ind_flat_map2()`, both values
+    /// shrink together: when the input is simplified, the derived value is
+    /// regenerated from the same random state, so the invariant between the
+    /// two is maintained.
+    ///
+    /// See `prop_flat_map()` for a more detailed explanation on how the
+    /// flat-map combinators differ.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use proptest::prelude::*;
+    ///
+    /// proptest! {
+    ///   fn test_index((v, i) in prop::collection::vec(0..10, 1..10)
+    ///       .prop_dependent(|v| 0..v.len()))
+    ///   {
+    ///       prop_assert!(v[i] < 10);
+    ///   }
+    /// }
+    /// # fn main() { test_index(); }
+    /// ```
+    fn prop_dependent<S: Strategy, F: Fn(Self::Value) -> S>(
+        self,
+        fun: F,
+    ) -> Dependent<Self, F>
+    where
+        Self: Sized,
+        Self::Value: Clone,
+    {
+        Dependent {
+            source: self,
+            fun: Arc::new(fun),
+        }
+    }
+
     /// Returns a strategy which only produces values accepted by `fun`.
     ///
     /// This results in a very naïve form of rejection sampling and should only
@@ -346,6 +431,21 @@ pub trait Strategy: fmt::Debug {
     /// whole-input rejections.
     ///
     /// `whence` is used to record where and why the rejection occurred.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use proptest::prelude::*;
+    ///
+    /// proptest! {
+    ///   fn test_something(n in "[0-9]{1,3}".prop_filter_map(
+    ///       "not a byte", |s| s.parse::<u8>().ok()))
+    ///   {
+    ///       prop_assert!(n.to_string().len() <= 3);
+    ///   }
+    /// }
+    /// # fn main() { test_something(); }
+    /// ```
     fn prop_filter_map<F: Fn(Self::Value) -> Option<O>, O: fmt::Debug>(
         self,
         whence: impl Into<Reason>,
@@ -505,6 +605,132 @@ pub trait Strategy: fmt::Debug {
         Shuffle(self)
     }
 
+    /// Returns a strategy which shrinks values with the candidates returned
+    /// by `fun`, in addition to the built-in shrinking of `self`.
+    ///
+    /// Once the value tree of `self` cannot be simplified any further, each
+    /// candidate `fun` returns for the current value is tried in order. The
+    /// first one with which the test still fails becomes the current value,
+    /// and shrinking continues with its own candidates. This allows
+    /// domain-specific shrinking steps, such as replacing a subtree with one
+    /// of its children, without writing a whole `ValueTree`. To replace the
+    /// built-in shrinking entirely, call `no_shrink()` first.
+    ///
+    /// Candidates should be simpler than the value they are derived from;
+    /// otherwise shrinking may go on until it is stopped by
+    /// `Config::max_shrink_iters`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use proptest::prelude::*;
+    ///
+    /// proptest! {
+    ///   fn test_something(s in "[a-z]{0,20}".prop_shrink_with(|s| {
+    ///       // Try dropping either half of the string.
+    ///       let mid = s.len() / 2;
+    ///       vec![s[..mid].to_owned(), s[mid..].to_owned()]
+    ///   })) {
+    ///       prop_assert!(s.len() <= 20);
+    ///   }
+    /// }
+    /// # fn main() { test_something(); }
+    /// ```
+    fn prop_shrink_with<I, F>(self, fun: F) -> ShrinkWith<Self, F>
+    where
+        Self: Sized,
+        Self::Value: Clone,
+        I: IntoIterator<Item = Self::Value>,
+        F: Fn(&Self::Value) -> I,
+    {
+        ShrinkWith {
+            source: self,
+            fun: Arc::new(fun),
+        }
+    }
+
+    /// Returns a strategy which shrinks values like `self`, but never to
+    /// values whose cost, as computed by `fun`, is greater than that of the
+    /// last failing value.
+    ///
+    /// The built-in shrinking makes values "smaller" in ways which do not
+    /// always match what makes a counterexample easier to understand. With a
+    /// cost such as the number of nodes of a syntax tree, shrinking steps
+    /// which would make the value costlier are skipped as though the test
+    /// passed with them, without running it, while steps of equal or lower
+    /// cost are taken as usual. The minimal value is thus never costlier
+    /// than the value originally found to fail.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use proptest::collection::vec;
+    /// use proptest::prelude::*;
+    ///
+    /// proptest! {
+    ///   fn test_something(v in vec(vec(0..10u32, 0..5), 0..5)
+    ///       // Prefer fewer elements overall to fewer inner vectors.
+    ///       .prop_shrink_cost(|v| v.iter().map(|v| v.len() as u64).sum())
+    ///   ) {
+    ///       prop_assert!(v.iter().flatten().all(|&x| x < 10));
+    ///   }
+    /// }
+    /// # fn main() { test_something(); }
+    /// ```
+    fn prop_shrink_cost<F>(self, fun: F) -> ShrinkCost<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&Self::Value) -> u64,
+    {
+        ShrinkCost {
+            source: self,
+            fun: Arc::new(fun),
+        }
+    }
+
+    /// Returns a strategy which shrinks values by shrinking the sequence of
+    /// random choices made to generate them, instead of with the value trees
+    /// of `self`.
+    ///
+    /// Every byte `self` draws from the RNG while generating a value is
+    /// recorded. Shrinking deletes, zeroes and lowers blocks of these bytes
+    /// and generates a new value replaying them, keeping the edits with
+    /// which the test still fails. Since this only relies on `new_tree()`,
+    /// shrinking works through `prop_flat_map()`, filters and custom
+    /// strategies which cannot shrink on their own, as long as smaller
+    /// choices generate simpler values, as they do for the strategies
+    /// provided by proptest. Randomness handed over to other RNGs, such as
+    /// with `prop_perturb()`, is only replayed, not shrunk.
+    ///
+    /// Each shrinking step generates a new value, so this is slower than
+    /// shrinking value trees where those work well.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use proptest::collection::vec;
+    /// use proptest::prelude::*;
+    ///
+    /// proptest! {
+    ///   fn test_something(v in (1..10usize)
+    ///       .prop_flat_map(|len| vec(0..100u32, len))
+    ///       .prop_shrink_choices()
+    ///   ) {
+    ///       // If this failed, `v` would shrink to a single element.
+    ///       prop_assert!(v.iter().all(|&x| x < 100));
+    ///   }
+    /// }
+    /// # fn main() { test_something(); }
+    /// ```
+    fn prop_shrink_choices(self) -> ShrinkChoices<Self>
+    where
+        Self: Sized,
+    {
+        ShrinkChoices {
+            source: Arc::new(self),
+        }
+    }
+
     /// Erases the type of this `Strategy` so it can be passed around as a
     /// simple trait object.
     ///
@@ -535,6 +761,34 @@ pub trait Strategy: fmt::Debug {
         SBoxedStrategy(Arc::new(BoxedStrategyWrapper(self)))
     }
 
+    /// Erases the type of this `Strategy` and shares it behind an `Arc`, so
+    /// that it can be stored in a static or a registry and cloned cheaply
+    /// across tests and threads.
+    ///
+    /// Like `sboxed()`, this retains the `Send` and `Sync` traits, and the
+    /// inner strategy is never copied or boxed again by clones or by calling
+    /// `arced()`, `sboxed()` or `boxed()` on the result.
+    fn arced(self) -> ArcStrategy<Self::Value>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        ArcStrategy(Arc::new(BoxedStrategyWrapper(self)))
+    }
+
+    /// Wraps this strategy in a `LazyStrategy`, so that it is shared rather
+    /// than copied between clones while keeping its type.
+    ///
+    /// To also defer building an expensive strategy until it is first used,
+    /// construct it with `LazyStrategy::new()` instead.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    fn prop_cached(self) -> LazyStrategyFn<Self>
+    where
+        Self: Sized,
+    {
+        LazyStrategy::from_strategy(self)
+    }
+
     /// Wraps this strategy to prevent values from being subject to shrinking.
     ///
     /// Suppressing shrinking is useful when testing things like linear
@@ -550,6 +804,33 @@ pub trait Strategy: fmt::Debug {
     {
         NoShrink(self)
     }
+
+    /// Returns a structured description of this strategy, naming each
+    /// combinator it is made of along with its parameters.
+    ///
+    /// Strategies which do not override this are described by their `Debug`
+    /// representation. Unlike `Debug`, the combinators provided by proptest
+    /// describe themselves by the method or macro that built them and omit
+    /// the internal state, so a long pipeline reads as a short tree:
+    ///
+    /// ```
+    /// use proptest::prelude::*;
+    ///
+    /// let strategy = (0..10u32, 0..10u32)
+    ///     .prop_filter("a < b", |&(a, b)| a < b)
+    ///     .prop_map(|(a, b)| b - a);
+    /// assert_eq!(
+    ///     "prop_map\n  \
+    ///      prop_filter(whence = \"a < b\")\n    \
+    ///      tuple\n      \
+    ///      0..10\n      \
+    ///      0..10",
+    ///     strategy.describe().to_string()
+    /// );
+    /// ```
+    fn describe(&self) -> Description {
+        Description::from_debug(self)
+    }
 }
 
 /// A generated value and its associated shrinker.
@@ -639,6 +920,10 @@ impl<T: Strategy> Strategy for NoShrink<T> {
     fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
         self.0.new_tree(runner).map(NoShrink)
     }
+
+    fn describe(&self) -> Description {
+        Description::new("no_shrink").child(self.0.describe())
+    }
 }
 
 impl<T: ValueTree> ValueTree for NoShrink<T> {
@@ -669,6 +954,10 @@ macro_rules! proxy_strategy {
             fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
                 (**self).new_tree(runner)
             }
+
+            fn describe(&self) -> Description {
+                (**self).describe()
+            }
         }
     };
 }
@@ -713,6 +1002,36 @@ pub struct SBoxedStrategy<T>(
     Arc<dyn Strategy<Value = T, Tree = BoxedVT<T>> + Sync + Send>,
 );
 
+/// A shared `Strategy` trait object which is `Sync` and `Send`, as produced
+/// by `Strategy::arced()`.
+///
+/// Cloning only increments the count of the `Arc` holding the strategy,
+/// which is never copied or boxed again, so a large composed strategy can be
+/// built once, stored in a static and shared across tests and threads:
+///
+/// ```
+/// use std::sync::LazyLock;
+///
+/// use proptest::collection::vec;
+/// use proptest::prelude::*;
+/// use proptest::strategy::ArcStrategy;
+///
+/// static MATRICES: LazyLock<ArcStrategy<Vec<Vec<u8>>>> =
+///     LazyLock::new(|| vec(vec(any::<u8>(), 4), 4).arced());
+///
+/// proptest! {
+///     fn matrices_are_square(m in MATRICES.clone()) {
+///         prop_assert!(m.iter().all(|row| row.len() == m.len()));
+///     }
+/// }
+/// # fn main() { matrices_are_square(); }
+/// ```
+#[derive(Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct ArcStrategy<T>(
+    Arc<dyn Strategy<Value = T, Tree = BoxedVT<T>> + Sync + Send>,
+);
+
 impl<T> Clone for BoxedStrategy<T> {
     fn clone(&self) -> Self {
         BoxedStrategy(Arc::clone(&self.0))
@@ -725,6 +1044,12 @@ impl<T> Clone for SBoxedStrategy<T> {
     }
 }
 
+impl<T> Clone for ArcStrategy<T> {
+    fn clone(&self) -> Self {
+        ArcStrategy(Arc::clone(&self.0))
+    }
+}
+
 impl<T: fmt::Debug> Strategy for BoxedStrategy<T> {
     type Tree = BoxedVT<T>;
     type Value = T;
@@ -733,6 +1058,10 @@ impl<T: fmt::Debug> Strategy for BoxedStrategy<T> {
         self.0.new_tree(runner)
     }
 
+    fn describe(&self) -> Description {
+        self.0.describe()
+    }
+
     // Optimization: Don't rebox the strategy.
 
     fn boxed(self) -> BoxedStrategy<Self::Value>
@@ -751,6 +1080,10 @@ impl<T: fmt::Debug> Strategy for SBoxedStrategy<T> {
         self.0.new_tree(runner)
     }
 
+    fn describe(&self) -> Description {
+        self.0.describe()
+    }
+
     // Optimization: Don't rebox the strategy.
 
     fn sboxed(self) -> SBoxedStrategy<Self::Value>
@@ -766,6 +1099,49 @@ impl<T: fmt::Debug> Strategy for SBoxedStrategy<T> {
     {
         BoxedStrategy(self.0)
     }
+
+    fn arced(self) -> ArcStrategy<Self::Value>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        ArcStrategy(self.0)
+    }
+}
+
+impl<T: fmt::Debug> Strategy for ArcStrategy<T> {
+    type Tree = BoxedVT<T>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.0.new_tree(runner)
+    }
+
+    fn describe(&self) -> Description {
+        self.0.describe()
+    }
+
+    // Optimization: Don't rebox the strategy.
+
+    fn arced(self) -> ArcStrategy<Self::Value>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        self
+    }
+
+    fn sboxed(self) -> SBoxedStrategy<Self::Value>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        SBoxedStrategy(self.0)
+    }
+
+    fn boxed(self) -> BoxedStrategy<Self::Value>
+    where
+        Self: Sized + 'static,
+    {
+        BoxedStrategy(self.0)
+    }
 }
 
 #[derive(Debug)]
@@ -780,6 +1156,10 @@ where
     fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
         Ok(Box::new(self.0.new_tree(runner)?))
     }
+
+    fn describe(&self) -> Description {
+        self.0.describe()
+    }
 }
 
 //==============================================================================
@@ -1052,3 +1432,43 @@ pub fn check_strategy_sanity<S: Strategy>(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::*;
+    use crate::collection::vec;
+    use crate::std_facade::Vec;
+
+    #[test]
+    fn arced_strategies_are_shared_across_threads() {
+        let strategy = vec(0..100u32, 1..10).arced();
+        let handles = (0..4)
+            .map(|_| {
+                let strategy = strategy.clone();
+                thread::spawn(move || {
+                    let mut runner = TestRunner::deterministic();
+                    strategy.new_tree(&mut runner).unwrap().current()
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut runner = TestRunner::deterministic();
+        let expected = strategy.new_tree(&mut runner).unwrap().current();
+        for handle in handles {
+            assert_eq!(expected, handle.join().unwrap());
+        }
+        // The strategy itself is shared, not copied.
+        assert_eq!(1, Arc::strong_count(&strategy.0));
+    }
+
+    #[test]
+    fn arcing_again_reuses_the_arc() {
+        let strategy = (0..10u8).arced();
+        let inner = Arc::as_ptr(&strategy.0) as *const ();
+        let sboxed = strategy.clone().sboxed();
+        assert_eq!(inner, Arc::as_ptr(&sboxed.0) as *const ());
+        assert_eq!(inner, Arc::as_ptr(&sboxed.arced().0) as *const ());
+        assert_eq!(inner, Arc::as_ptr(&strategy.arced().0) as *const ());
+    }
+}
@@ -15,7 +15,7 @@ use core::u32;
 use num_traits::float::FloatCore;
 
 use crate::num::sample_uniform;
-use crate::strategy::{lazy::LazyValueTree, traits::*};
+use crate::strategy::{lazy::LazyValueTree, traits::*, Description};
 use crate::test_runner::*;
 
 /// A **relative** `weight` of a particular `Strategy` corresponding to `T`
@@ -35,6 +35,7 @@ pub struct Union<T: Strategy> {
     // In principle T could be any `Strategy + Clone`, but that isn't possible
     // for BC reasons with the 0.9 series.
     options: Vec<WA<T>>,
+    simplest_alternatives: bool,
 }
 
 impl<T: Strategy> Union<T> {
@@ -52,7 +53,10 @@ impl<T: Strategy> Union<T> {
         let options: Vec<WA<T>> =
             options.into_iter().map(|v| (1, Arc::new(v))).collect();
         assert!(!options.is_empty());
-        Self { options }
+        Self {
+            options,
+            simplest_alternatives: false,
+        }
     }
 
     pub(crate) fn try_new<E>(
@@ -63,7 +67,10 @@ impl<T: Strategy> Union<T> {
             .collect::<Result<_, _>>()?;
 
         assert!(!options.is_empty());
-        Ok(Self { options })
+        Ok(Self {
+            options,
+            simplest_alternatives: false,
+        })
     }
 
     /// Create a strategy which selects from the given delegate strategies.
@@ -91,7 +98,63 @@ impl<T: Strategy> Union<T> {
         );
         let options =
             options.into_iter().map(|(w, v)| (w, Arc::new(v))).collect();
-        Self { options }
+        Self {
+            options,
+            simplest_alternatives: false,
+        }
+    }
+
+    /// Create a strategy which selects from the given delegate strategies with
+    /// weights computed at run time, such as from configuration or a schema.
+    ///
+    /// This is like `new_weighted()`, except that options with a weight of 0
+    /// are allowed and never generated, not even while shrinking, and that
+    /// invalid weights are reported as errors rather than panics.
+    ///
+    /// ```
+    /// use proptest::prelude::*;
+    /// use proptest::strategy::Union;
+    ///
+    /// let weights = [("small", 3), ("large", 1), ("huge", 0)];
+    /// let sizes = Union::try_new_weighted(weights.iter().map(|&(name, w)| {
+    ///     let range = match name {
+    ///         "small" => 0..10,
+    ///         "large" => 10..1000,
+    ///         _ => 1000..u32::MAX,
+    ///     };
+    ///     (w, range)
+    /// }))
+    /// .unwrap();
+    ///
+    /// proptest!(|(size in sizes)| {
+    ///     prop_assert!(size < 1000);
+    /// });
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no option has a non-zero weight or if the sum of
+    /// the weights overflows a `u32`.
+    pub fn try_new_weighted(
+        options: impl IntoIterator<Item = W<T>>,
+    ) -> Result<Self, Reason> {
+        let options: Vec<WA<T>> = options
+            .into_iter()
+            .filter(|&(w, _)| 0 != w)
+            .map(|(w, v)| (w, Arc::new(v)))
+            .collect();
+        if options.is_empty() {
+            return Err("Union has no option with a non-zero weight".into());
+        }
+        if options.iter().map(|&(w, _)| u64::from(w)).sum::<u64>()
+            > u64::from(u32::MAX)
+        {
+            return Err("Union weights overflow u32".into());
+        }
+        Ok(Self {
+            options,
+            simplest_alternatives: false,
+        })
     }
 
     /// Add `other` as an additional alternate strategy with weight 1.
@@ -99,6 +162,19 @@ impl<T: Strategy> Union<T> {
         self.options.push((1, Arc::new(other)));
         self
     }
+
+    /// Make shrinking try the simplest value of each earlier option.
+    ///
+    /// By default, when shrinking moves to an earlier option, it tries a
+    /// value generated at random from that option, which often does not
+    /// reproduce the failure. With this, it instead tries the value that
+    /// option simplifies to without bound, such as the lower bound of a
+    /// range or an empty collection. This lets, for example, a failure
+    /// found in a complex tree shrink to a leaf when a leaf still fails.
+    pub fn simplest_alternatives(mut self) -> Self {
+        self.simplest_alternatives = true;
+        self
+    }
 }
 
 fn pick_weighted<I: Iterator<Item = u32>>(
@@ -151,8 +227,18 @@ impl<T: Strategy> Strategy for Union<T> {
             pick,
             min_pick: 0,
             prev_pick: None,
+            untried_end: pick,
+            simplest_alternatives: self.simplest_alternatives,
         })
     }
+
+    fn describe(&self) -> Description {
+        let weights: Vec<u32> = self.options.iter().map(|&(w, _)| w).collect();
+        self.options.iter().fold(
+            Description::new("prop_oneof").param("weights", weights),
+            |description, (_, option)| description.child(option.describe()),
+        )
+    }
 }
 
 macro_rules! access_vec {
@@ -171,6 +257,9 @@ pub struct UnionValueTree<T: Strategy> {
     pick: usize,
     min_pick: usize,
     prev_pick: Option<usize>,
+    // Only options before this index remain to be tried by `simplify()`.
+    untried_end: usize,
+    simplest_alternatives: bool,
 }
 
 macro_rules! lazy_union_value_tree_body {
@@ -208,7 +297,8 @@ macro_rules! lazy_union_value_tree_body {
                 self.pick,
                 self.min_pick,
             );
-            if self.pick == self.min_pick {
+            let mut next_pick = min(self.pick, self.untried_end);
+            if next_pick <= self.min_pick {
                 // No more simplification to be done.
                 return false;
             }
@@ -216,11 +306,16 @@ macro_rules! lazy_union_value_tree_body {
             // self.prev_pick is always a valid pick.
             self.prev_pick = Some(self.pick);
 
-            let mut next_pick = self.pick;
+            let simplest = self.simplest_alternatives;
             while next_pick > self.min_pick {
                 next_pick -= 1;
                 let initialized = $access!([mut] opt = self, next_pick, {
                     opt.maybe_init();
+                    if simplest {
+                        if let Some(inner) = opt.as_inner_mut() {
+                            while inner.simplify() {}
+                        }
+                    }
                     opt.is_initialized()
                 });
                 if initialized {
@@ -236,8 +331,14 @@ macro_rules! lazy_union_value_tree_body {
         fn complicate(&mut self) -> bool {
             if let Some(pick) = self.prev_pick {
                 // simplify() ensures that the previous pick was initialized.
+                if self.simplest_alternatives {
+                    // The options before the one which did not reproduce the
+                    // failure may still do so.
+                    self.untried_end = self.pick;
+                } else {
+                    self.min_pick = pick;
+                }
                 self.pick = pick;
-                self.min_pick = pick;
                 self.prev_pick = None;
                 true
             } else {
@@ -269,6 +370,8 @@ where
             pick: self.pick,
             min_pick: self.min_pick,
             prev_pick: self.prev_pick,
+            untried_end: self.untried_end,
+            simplest_alternatives: self.simplest_alternatives,
         }
     }
 }
@@ -283,6 +386,8 @@ where
             .field("pick", &self.pick)
             .field("min_pick", &self.min_pick)
             .field("prev_pick", &self.prev_pick)
+            .field("untried_end", &self.untried_end)
+            .field("simplest_alternatives", &self.simplest_alternatives)
             .finish()
     }
 }
@@ -337,7 +442,7 @@ def_access_tuple!($ access_tupleA, 1 2 3 4 5 6 7 8 9);
 /// for variants that aren't picked at first are generated lazily.
 #[must_use = "strategies do nothing unless used"]
 #[derive(Clone, Copy, Debug)]
-pub struct TupleUnion<T>(T);
+pub struct TupleUnion<T>(T, bool);
 
 impl<T> TupleUnion<T> {
     /// Wrap `tuple` in a `TupleUnion`.
@@ -348,10 +453,20 @@ impl<T> TupleUnion<T> {
     /// relative weight of its corresponding strategy.
     /// You may use `WA<S>` as an alias for `(u32, Arc<S>)`.
     ///
+    /// Options with a weight of 0 are never generated, not even while
+    /// shrinking.
+    ///
     /// Using this constructor directly is discouraged; prefer to use
     /// `prop_oneof!` since it is generally clearer.
     pub fn new(tuple: T) -> Self {
-        TupleUnion(tuple)
+        TupleUnion(tuple, false)
+    }
+
+    /// Make shrinking try the simplest value of each earlier option.
+    ///
+    /// See `Union::simplest_alternatives()`.
+    pub fn simplest_alternatives(self) -> Self {
+        TupleUnion(self.0, true)
     }
 }
 
@@ -365,6 +480,10 @@ macro_rules! tuple_union {
 
             fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
                 let weights = [((self.0).0).0, $(((self.0).$ix).0),*];
+                assert!(
+                    weights.iter().any(|&w| 0 != w),
+                    "TupleUnion has no option with a non-zero weight"
+                );
                 let pick = pick_weighted(runner, weights.iter().cloned(),
                                          weights.iter().cloned());
 
@@ -373,6 +492,8 @@ macro_rules! tuple_union {
                         if 0 == pick {
                             LazyValueTree::new_initialized(
                                 ((self.0).0).1.new_tree(runner)?)
+                        } else if 0 == ((self.0).0).0 {
+                            LazyValueTree::new_disabled()
                         } else {
                             LazyValueTree::new(
                                 Arc::clone(&((self.0).0).1), runner)
@@ -381,6 +502,8 @@ macro_rules! tuple_union {
                         if $ix == pick {
                             Some(LazyValueTree::new_initialized(
                                  ((self.0).$ix).1.new_tree(runner)?))
+                        } else if $ix < pick && 0 == ((self.0).$ix).0 {
+                            Some(LazyValueTree::new_disabled())
                         } else if $ix < pick {
                             Some(LazyValueTree::new(
                                     Arc::clone(&((self.0).$ix).1), runner))
@@ -390,8 +513,17 @@ macro_rules! tuple_union {
                     pick: pick,
                     min_pick: 0,
                     prev_pick: None,
+                    untried_end: pick,
+                    simplest_alternatives: self.1,
                 })
             }
+
+            fn describe(&self) -> Description {
+                Description::new("prop_oneof")
+                    .param("weights", [((self.0).0).0, $(((self.0).$ix).0),*])
+                    .child(((self.0).0).1.describe())
+                    $(.child(((self.0).$ix).1.describe()))*
+            }
         }
     }
 }
@@ -413,6 +545,9 @@ pub struct TupleUnionValueTree<T> {
     pick: usize,
     min_pick: usize,
     prev_pick: Option<usize>,
+    // Only options before this index remain to be tried by `simplify()`.
+    untried_end: usize,
+    simplest_alternatives: bool,
 }
 
 macro_rules! value_tree_tuple {
@@ -514,6 +649,36 @@ mod test {
         );
     }
 
+    #[test]
+    fn union_shrinks_to_simplest_alternative() {
+        // Only the simplest value of the first option and the second option
+        // fail, so shrinking can only reach the former by trying it directly.
+        let test = |v: u32| {
+            prop_assert!(v != 100 && v != 5000);
+            Ok(())
+        };
+        let minimize = |input: &Union<BoxedStrategy<u32>>| {
+            let mut runner = TestRunner::deterministic();
+            loop {
+                let case = input.new_tree(&mut runner).unwrap();
+                if 5000 == case.current() {
+                    return runner.run_one(case, test);
+                }
+            }
+        };
+
+        let input =
+            Union::new(vec![(100u32..1000).boxed(), Just(5000u32).boxed()]);
+        match minimize(&input) {
+            Err(TestError::Fail(_, v)) => assert_eq!(5000, v),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        match minimize(&input.simplest_alternatives()) {
+            Err(TestError::Fail(_, v)) => assert_eq!(100, v),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
     #[test]
     fn test_union_weighted() {
         let input = Union::new_weighted(vec![
@@ -535,6 +700,38 @@ mod test {
         assert!(counts[1] > counts[2] * 3 / 2);
     }
 
+    #[test]
+    fn try_new_weighted_skips_zero_weights() {
+        let input = Union::try_new_weighted(vec![
+            (0, Just(0usize)),
+            (1, Just(1usize)),
+            (0, Just(2usize)),
+            (1, Just(3usize)),
+        ])
+        .unwrap();
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            assert!([1, 3].contains(&case.current()));
+            while case.simplify() {
+                assert!([1, 3].contains(&case.current()));
+            }
+            assert_eq!(1, case.current());
+        }
+    }
+
+    #[test]
+    fn try_new_weighted_rejects_invalid_weights() {
+        assert!(Union::try_new_weighted(vec![(0, Just(0))]).is_err());
+        assert!(Union::<Just<i32>>::try_new_weighted(vec![]).is_err());
+        assert!(Union::try_new_weighted(vec![
+            (u32::MAX, Just(0)),
+            (1, Just(1)),
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_union_sanity() {
         check_strategy_sanity(
@@ -647,6 +844,63 @@ mod test {
         test!(r, r, r, r, r, r, r, r, r); // 10
     }
 
+    #[test]
+    fn tuple_union_shrinks_to_simplest_alternative() {
+        let input = TupleUnion::new((
+            (1, Arc::new(100u32..1000)),
+            (1, Arc::new(1000u32..2000)),
+            (1, Arc::new(Just(5000u32))),
+        ))
+        .simplest_alternatives();
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let case = input.new_tree(&mut runner).unwrap();
+            let result = runner.run_one(case, |v| {
+                prop_assert!(v != 100 && v != 5000);
+                Ok(())
+            });
+            match result {
+                Ok(_) | Err(TestError::Fail(_, 100)) => (),
+                result => panic!("Unexpected result: {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_union_simplest_alternatives_sanity() {
+        check_strategy_sanity(
+            Union::new_weighted(vec![
+                (1, 0i32..100),
+                (2, 200i32..300),
+                (1, 400i32..500),
+            ])
+            .simplest_alternatives(),
+            None,
+        );
+    }
+
+    #[test]
+    fn tuple_union_skips_zero_weights() {
+        let weights = [0, 1, 0, 1];
+        let input = TupleUnion::new((
+            (weights[0], Arc::new(Just(0usize))),
+            (weights[1], Arc::new(Just(1usize))),
+            (weights[2], Arc::new(Just(2usize))),
+            (weights[3], Arc::new(Just(3usize))),
+        ));
+
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            assert!([1, 3].contains(&case.current()));
+            while case.simplify() {
+                assert!([1, 3].contains(&case.current()));
+            }
+            assert_eq!(1, case.current());
+        }
+    }
+
     #[test]
     fn test_tuple_union_sanity() {
         check_strategy_sanity(
@@ -9,6 +9,16 @@
 
 //! Strategies for generating strings and byte strings from regular
 //! expressions.
+//!
+//! `chars()` and `graphemes()` instead generate strings whose length is
+//! bounded in characters or in grapheme clusters respectively, and
+//! `hostile()` generates strings full of content known to cause trouble.
+//! The `normalization` module, behind the `unicode-normalization` feature,
+//! generates strings in or out of the Unicode normalization forms.
+//!
+//! `template()` fills the holes of a fixed template with generated values.
+//!
+//! `ident()` generates identifiers following UAX #31, for testing lexers.
 
 use crate::std_facade::{Box, Cow, String, ToOwned, Vec};
 use core::fmt;
@@ -21,10 +31,19 @@ use regex_syntax::{Error as ParseError, ParserBuilder};
 
 use crate::bool;
 use crate::char;
-use crate::collection::{size_range, vec, SizeRange};
+use crate::collection::{size_range, vec, SizeRange, VecStrategy, VecValueTree};
 use crate::strategy::*;
 use crate::test_runner::*;
 
+#[cfg(feature = "unicode-normalization")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unicode-normalization")))]
+pub mod normalization;
+mod ident;
+mod template;
+
+pub use self::ident::*;
+pub use self::template::*;
+
 /// Wraps the regex that forms the `Strategy` for `String` so that a sensible
 /// `Default` can be given. The default is a string of non-control characters.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -170,6 +189,26 @@ pub fn string_regex_parsed(expr: &Hir) -> ParseResult<String> {
 /// will generate newline characters (byte value `0x0A`).  See the
 /// [`regex` crate's documentation](https://docs.rs/regex/*/regex/#opt-out-of-unicode-support)
 /// for more information.
+///
+/// With Unicode disabled, escapes such as `\xFF` and classes such as
+/// `[\x80-\xFF]` match single arbitrary bytes, which makes this suitable for
+/// describing binary formats:
+///
+/// ```
+/// use proptest::prelude::*;
+/// use proptest::string::bytes_regex;
+///
+/// proptest! {
+///     // A magic number, a version byte, then up to 16 bytes of payload.
+///     fn header_is_recognised(
+///         frame in bytes_regex(r"(?s-u)\x89PNG[\x01-\x03].{0,16}").unwrap()
+///     ) {
+///         prop_assert_eq!(b"\x89PNG", &frame[..4]);
+///         prop_assert!((1..=3).contains(&frame[4]));
+///     }
+/// }
+/// # fn main() { header_is_recognised(); }
+/// ```
 pub fn bytes_regex(regex: &str) -> ParseResult<Vec<u8>> {
     let hir = ParserBuilder::new()
         .utf8(false)
@@ -236,6 +275,361 @@ pub fn bytes_regex_parsed(expr: &Hir) -> ParseResult<Vec<u8>> {
     .map(RegexGeneratorStrategy)
 }
 
+/// Strategy which generates strings with a bounded number of characters.
+///
+/// Created by the `chars()` function in the same module.
+#[derive(Clone, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct CharsStrategy<S: Strategy<Value = char>>(VecStrategy<S>);
+
+/// Creates a strategy which generates strings of characters drawn from
+/// `element`, whose length _in characters_ is within `size`.
+///
+/// Unlike the repetitions of a regular expression, which bound the length in
+/// whatever units the pattern repeats, `size` counts `char`s regardless of
+/// how many bytes each one takes. Strings shrink by removing characters, then
+/// by shrinking the characters which remain.
+///
+/// ```
+/// use proptest::prelude::*;
+/// use proptest::string;
+///
+/// proptest! {
+///     fn char_count_is_bounded(s in string::chars(any::<char>(), 1..8)) {
+///         let n = s.chars().count();
+///         prop_assert!(1 <= n && n < 8);
+///     }
+/// }
+/// # fn main() { char_count_is_bounded(); }
+/// ```
+pub fn chars<S: Strategy<Value = char>>(
+    element: S,
+    size: impl Into<SizeRange>,
+) -> CharsStrategy<S> {
+    CharsStrategy(vec(element, size))
+}
+
+/// `ValueTree` corresponding to `CharsStrategy`.
+#[derive(Clone, Debug)]
+pub struct CharsValueTree<T: ValueTree<Value = char>>(VecValueTree<T>);
+
+impl<S: Strategy<Value = char>> Strategy for CharsStrategy<S> {
+    type Tree = CharsValueTree<S::Tree>;
+    type Value = String;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.0.new_tree(runner).map(CharsValueTree)
+    }
+}
+
+impl<T: ValueTree<Value = char>> ValueTree for CharsValueTree<T> {
+    type Value = String;
+
+    fn current(&self) -> String {
+        self.0.current().into_iter().collect()
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.0.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.0.complicate()
+    }
+}
+
+/// Strategy which generates strings with a bounded number of grapheme
+/// clusters.
+///
+/// Created by the `graphemes()` function in the same module.
+#[derive(Clone, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct GraphemesStrategy(VecStrategy<SBoxedStrategy<String>>);
+
+/// Creates a strategy which generates strings made of a number of extended
+/// grapheme clusters, i.e. user-perceived characters, within `size`.
+///
+/// Each cluster is one of a fixed set of shapes: a printable ASCII character,
+/// a letter followed by up to four combining marks, a CJK ideograph, a Hangul
+/// syllable either precomposed or spelt with conjoining jamo, an emoji with
+/// an optional skin tone or joined to another emoji with a zero-width joiner,
+/// a flag made of two regional indicators, or `"\r\n"`. The shapes are chosen
+/// so that adjacent clusters never merge, so segmenting the string according
+/// to [UAX #29](https://www.unicode.org/reports/tr29/) yields exactly the
+/// clusters generated.
+///
+/// Strings shrink by removing whole clusters, then by shrinking the clusters
+/// which remain toward ASCII.
+pub fn graphemes(size: impl Into<SizeRange>) -> GraphemesStrategy {
+    GraphemesStrategy(vec(grapheme_cluster(), size))
+}
+
+/// `ValueTree` corresponding to `GraphemesStrategy`.
+pub struct GraphemesValueTree(
+    VecValueTree<Box<dyn ValueTree<Value = String>>>,
+);
+
+impl Strategy for GraphemesStrategy {
+    type Tree = GraphemesValueTree;
+    type Value = String;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.0.new_tree(runner).map(GraphemesValueTree)
+    }
+}
+
+impl ValueTree for GraphemesValueTree {
+    type Value = String;
+
+    fn current(&self) -> String {
+        self.0.current().concat()
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.0.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.0.complicate()
+    }
+}
+
+fn grapheme_cluster() -> SBoxedStrategy<String> {
+    // No cluster starts with a mark, a joiner, a modifier or a lone vowel or
+    // trailing jamo, and regional indicators only come in pairs, so no
+    // cluster extends the one before it.
+    let letter = char::ranges(Cow::Owned(vec![
+        'a'..='z',
+        'A'..='Z',
+        '\u{00C0}'..='\u{00FF}',
+        '\u{0391}'..='\u{03A1}',
+        '\u{0410}'..='\u{044F}',
+    ]));
+    let emoji = || {
+        (
+            char::range('\u{1F600}', '\u{1F64F}'),
+            crate::option::of(char::range('\u{1F3FB}', '\u{1F3FF}')),
+        )
+            .prop_map(|(emoji, tone)| {
+                let mut cluster = String::from(emoji);
+                cluster.extend(tone);
+                cluster
+            })
+    };
+
+    prop_oneof![
+        4 => char::range(' ', '~').prop_map(String::from),
+        2 => (letter, vec(char::range('\u{0300}', '\u{036F}'), 1..=4)).prop_map(
+            |(letter, marks)| {
+                let mut cluster = String::from(letter);
+                cluster.extend(marks);
+                cluster
+            }
+        ),
+        1 => char::range('\u{4E00}', '\u{9FFF}').prop_map(String::from),
+        1 => char::range('\u{AC00}', '\u{D7A3}').prop_map(String::from),
+        1 => (
+            char::range('\u{1100}', '\u{1112}'),
+            char::range('\u{1161}', '\u{1175}'),
+            crate::option::of(char::range('\u{11A8}', '\u{11C2}')),
+        )
+            .prop_map(|(lead, vowel, tail)| {
+                let mut cluster: String = [lead, vowel].iter().collect();
+                cluster.extend(tail);
+                cluster
+            }),
+        1 => emoji(),
+        1 => (emoji(), emoji()).prop_map(|(a, b)| a + "\u{200D}" + &b),
+        1 => (
+            char::range('\u{1F1E6}', '\u{1F1FF}'),
+            char::range('\u{1F1E6}', '\u{1F1FF}'),
+        )
+            .prop_map(|(a, b)| [a, b].iter().collect()),
+        1 => Just("\r\n".to_owned()),
+    ]
+    .sboxed()
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates strings or byte strings biased toward content
+    /// known to cause trouble.
+    ///
+    /// Created by the `hostile()` and `hostile_bytes()` functions in the same
+    /// module.
+    #[derive(Debug)]
+    pub struct HostileStrategy[<T>][where T : fmt::Debug]
+        (SBoxedStrategy<T>) -> HostileValueTree<T>;
+    /// `ValueTree` corresponding to `HostileStrategy`.
+    pub struct HostileValueTree[<T>][where T : fmt::Debug]
+        (Box<dyn ValueTree<Value = T>>) -> T;
+}
+
+/// Fragments of text which commonly break code handling strings.
+const HOSTILE_FRAGMENTS: &[&str] = &[
+    // NULs and other control characters
+    "\0",
+    "\0\0\0\0",
+    "\r",
+    "\n",
+    "\r\n",
+    "\t",
+    "\x08",
+    "\x7F",
+    "\u{85}",
+    "\u{2028}",
+    "\u{2029}",
+    // Bidirectional overrides, embeddings, isolates and marks
+    "\u{202E}",
+    "\u{202D}",
+    "\u{202A}",
+    "\u{202B}",
+    "\u{202C}",
+    "\u{2066}",
+    "\u{2067}",
+    "\u{2068}",
+    "\u{2069}",
+    "\u{200E}",
+    "\u{200F}",
+    "\u{202E}fdp.exe",
+    // Zero-width and invisible characters
+    "\u{200B}",
+    "\u{200C}",
+    "\u{200D}",
+    "\u{2060}",
+    "\u{FEFF}",
+    "\u{00AD}",
+    "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}",
+    // ANSI escape sequences
+    "\x1B[31m",
+    "\x1B[0m",
+    "\x1B[2J",
+    "\x1B[1A\x1B[2K",
+    "\x1B]0;title\x07",
+    "\x1B]8;;http://example.com\x1B\\",
+    // Text which looks like overlong or otherwise invalid encodings
+    "%C0%AF",
+    "%c0%ae%c0%ae/",
+    "%E0%80%AF",
+    "%00",
+    "\\xC0\\x80",
+    "\\uD800",
+    "&#0;",
+    // Specials and noncharacters
+    "\u{FFFD}",
+    "\u{FFFE}",
+    "\u{FFFF}",
+    "\u{1FFFE}",
+    "\u{10FFFF}",
+    // Characters whose case mapping or normalisation changes length
+    "\u{0130}",
+    "\u{00DF}",
+    "\u{FB03}",
+    "\u{0390}",
+    "\u{FF21}\u{FF22}\u{FF23}",
+    // Injection and format string fodder
+    "%s%n%x%d",
+    "{0}{}",
+    "${x}",
+    "'\"`",
+    "'; --",
+    "<script>",
+    "../../",
+    "\\",
+];
+
+/// Byte sequences which are not valid UTF-8.
+const HOSTILE_BYTE_FRAGMENTS: &[&[u8]] = &[
+    // Overlong encodings of '/' and NUL
+    b"\xC0\xAF",
+    b"\xE0\x80\xAF",
+    b"\xF0\x80\x80\xAF",
+    b"\xC0\x80",
+    // Lone surrogates, as WTF-8 or CESU-8 would encode them
+    b"\xED\xA0\x80",
+    b"\xED\xBF\xBF",
+    b"\xED\xA0\xBD\xED\xB8\x80",
+    // Truncated sequences and stray continuation bytes
+    b"\xE2\x82",
+    b"\xF0\x9F\x98",
+    b"\x80",
+    b"\xBF\xBF",
+    // Bytes which never occur in UTF-8, and code points past U+10FFFF
+    b"\xFE",
+    b"\xFF",
+    b"\xF4\x90\x80\x80",
+    b"\xF8\x88\x80\x80\x80",
+];
+
+/// Characters repeated to make very long runs.
+const HOSTILE_RUNS: &[char] = &['a', '\0', ' ', '\u{FFFF}', '\u{1F600}'];
+
+/// Creates a strategy which generates strings biased toward content known to
+/// break code handling text.
+///
+/// Each string is a sequence of fragments, interleaving short runs of
+/// printable ASCII with:
+///
+/// - NULs and other control characters, including line and paragraph
+///   separators;
+/// - bidirectional override, embedding and isolate characters;
+/// - zero-width and otherwise invisible characters, and emoji joined with
+///   zero-width joiners;
+/// - ANSI terminal escape sequences;
+/// - text which looks like overlong or invalid encodings, such as `%C0%AF`;
+/// - noncharacters and characters whose case mapping changes length;
+/// - format string and injection fodder;
+/// - long sequences of combining marks on a single base character;
+/// - very long runs of a single character.
+///
+/// A `String` cannot contain lone surrogates or invalid UTF-8; use
+/// `hostile_bytes()` to also generate those.
+///
+/// Strings shrink by removing fragments, then by shrinking the fragments
+/// which remain toward plain ASCII.
+pub fn hostile() -> HostileStrategy<String> {
+    HostileStrategy(
+        vec(hostile_fragment(), 0..8)
+            .prop_map(|fragments| fragments.concat())
+            .sboxed(),
+    )
+}
+
+/// Like `hostile()`, but generates byte strings which may also contain
+/// invalid UTF-8: overlong encodings, lone surrogates encoded as WTF-8 would
+/// encode them, truncated sequences, stray continuation bytes and bytes which
+/// never occur in UTF-8.
+pub fn hostile_bytes() -> HostileStrategy<Vec<u8>> {
+    let fragment = prop_oneof![
+        4 => hostile_fragment().prop_map(String::into_bytes),
+        1 => crate::sample::select(HOSTILE_BYTE_FRAGMENTS)
+            .prop_map(<[u8]>::to_vec),
+    ];
+    HostileStrategy(
+        vec(fragment, 0..8)
+            .prop_map(|fragments| fragments.concat())
+            .sboxed(),
+    )
+}
+
+fn hostile_fragment() -> SBoxedStrategy<String> {
+    prop_oneof![
+        3 => chars(char::range(' ', '~'), 1..8),
+        4 => crate::sample::select(HOSTILE_FRAGMENTS).prop_map(str::to_owned),
+        1 => (
+            char::range('a', 'z'),
+            vec(char::range('\u{0300}', '\u{036F}'), 16..256),
+        )
+            .prop_map(|(base, marks)| {
+                let mut fragment = String::from(base);
+                fragment.extend(marks);
+                fragment
+            }),
+        1 => (crate::sample::select(HOSTILE_RUNS), 256..4096usize)
+            .prop_map(|(c, n)| core::iter::repeat_n(c, n).collect()),
+    ]
+    .sboxed()
+}
+
 fn unicode_class_strategy(
     class: &hir::ClassUnicode,
 ) -> char::CharStrategy<'static> {
@@ -641,5 +1035,101 @@ mod test {
         }
     }
 
+    #[test]
+    fn chars_bounds_char_count() {
+        let mut runner = TestRunner::deterministic();
+        let input = chars(char::range('a', 'z'), 3..6);
+        for _ in 0..256 {
+            let mut tree = input.new_tree(&mut runner).unwrap();
+            let n = tree.current().chars().count();
+            assert!((3..6).contains(&n), "{:?}", tree.current());
+            let s = chars(char::any(), 3..6).new_tree(&mut runner).unwrap();
+            let n = s.current().chars().count();
+            assert!((3..6).contains(&n), "{:?}", s.current());
+            while tree.simplify() {}
+            assert_eq!("aaa", tree.current());
+        }
+    }
+
+    #[test]
+    fn graphemes_bounds_cluster_count() {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let mut runner = TestRunner::deterministic();
+        let input = graphemes(1..10);
+        let mut multi_char = 0;
+        for _ in 0..1024 {
+            let s = input.new_tree(&mut runner).unwrap().current();
+            let clusters = s.graphemes(true).collect::<Vec<_>>();
+            assert!((1..10).contains(&clusters.len()), "{:?}", clusters);
+            if s.chars().count() > clusters.len() {
+                multi_char += 1;
+            }
+        }
+        assert!(multi_char > 512, "{}", multi_char);
+    }
+
+    #[test]
+    fn graphemes_shrink_by_cluster() {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&graphemes(0..20), |s| {
+            prop_assert!(s.graphemes(true).count() < 5);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, s)) => {
+                assert!(s.is_ascii(), "{:?}", s);
+                assert_eq!(5, s.graphemes(true).count(), "{:?}", s);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn chars_is_sane() {
+        check_strategy_sanity(chars(char::range('a', 'z'), 0..10), None);
+    }
+
+    #[test]
+    fn hostile_generates_hostile_content() {
+        let mut runner = TestRunner::deterministic();
+        let mut seen = [false; 5];
+        for _ in 0..1024 {
+            let s = hostile().new_tree(&mut runner).unwrap().current();
+            seen[0] |= s.contains('\0');
+            seen[1] |= s.contains('\u{202E}');
+            seen[2] |= s.contains('\x1B');
+            let mut marks =
+                s.chars().filter(|c| ('\u{0300}'..='\u{036F}').contains(c));
+            seen[3] |= marks.nth(15).is_some();
+            seen[4] |= s.chars().count() >= 256;
+        }
+        assert_eq!([true; 5], seen);
+
+        let mut invalid = 0;
+        for _ in 0..1024 {
+            let s = hostile_bytes().new_tree(&mut runner).unwrap().current();
+            if String::from_utf8(s).is_err() {
+                invalid += 1;
+            }
+        }
+        assert!(invalid > 100, "{}", invalid);
+    }
+
+    #[test]
+    fn hostile_shrinks_by_fragment() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&hostile(), |s| {
+            prop_assert!(!s.contains('\0'));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, s)) => assert_eq!("\0", s),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
     include!("regex-contrib/crates_regex.rs");
 }
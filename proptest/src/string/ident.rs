@@ -0,0 +1,327 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for identifiers following Unicode Standard Annex #31.
+
+#[cfg(feature = "unicode-normalization")]
+use ::unicode_normalization::UnicodeNormalization;
+
+use super::string_regex;
+use crate::collection::SizeRange;
+use crate::std_facade::{Box, String, ToOwned, Vec};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// The scripts from which `IdentScripts::Single` draws letters.
+const SCRIPTS: &[&str] = &[
+    "Latin",
+    "Greek",
+    "Cyrillic",
+    "Armenian",
+    "Hebrew",
+    "Arabic",
+    "Devanagari",
+    "Bengali",
+    "Thai",
+    "Georgian",
+    "Hangul",
+    "Hiragana",
+    "Katakana",
+    "Han",
+    "Ethiopic",
+    "Cherokee",
+];
+
+/// How the characters of identifiers generated by `ident()` are chosen
+/// among scripts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IdentScripts {
+    /// Only ASCII letters, digits and underscores.
+    Ascii,
+    /// The letters of each identifier come from a single script, such as
+    /// Latin, Greek or Han, possibly with characters common to all scripts,
+    /// such as digits and underscores, and combining marks. Identifiers are
+    /// thus "single script" in the sense of [UTS #39][uts39].
+    ///
+    /// [uts39]: https://www.unicode.org/reports/tr39/#Mixed_Script_Detection
+    Single,
+    /// Every character is chosen independently from all scripts, so
+    /// identifiers usually mix scripts, as confusable or spoofed identifiers
+    /// do.
+    Mixed,
+}
+
+/// Parameters for generating identifiers with `ident()`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IdentParams {
+    /// The range of the length of identifiers in characters, before any
+    /// normalization. Identifiers always have at least one character.
+    ///
+    /// The default is `1..16`.
+    pub len: SizeRange,
+    /// Whether identifiers may start with `_`, as in most programming
+    /// languages, in addition to the `XID_Start` characters.
+    ///
+    /// The default is `false`.
+    pub leading_underscore: bool,
+    /// Words which are never generated, such as the keywords of a language.
+    /// They are compared to identifiers exactly, after any normalization.
+    ///
+    /// The default is empty.
+    pub keywords: &'static [&'static str],
+    /// How characters are chosen among scripts.
+    ///
+    /// The default is `IdentScripts::Single`.
+    pub scripts: IdentScripts,
+    /// Whether identifiers are converted to Normalization Form C, as most
+    /// languages require before comparing identifiers.
+    ///
+    /// The default is `true`.
+    #[cfg(feature = "unicode-normalization")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-normalization")))]
+    pub nfc: bool,
+}
+
+impl Default for IdentParams {
+    fn default() -> Self {
+        IdentParams {
+            len: (1..16).into(),
+            leading_underscore: false,
+            keywords: &[],
+            scripts: IdentScripts::Single,
+            #[cfg(feature = "unicode-normalization")]
+            nfc: true,
+        }
+    }
+}
+
+opaque_strategy_wrapper! {
+    /// Strategy which generates identifiers.
+    ///
+    /// Created by the `ident()` function in the same module.
+    #[derive(Debug)]
+    pub struct IdentStrategy[][](SBoxedStrategy<String>)
+        -> IdentValueTree;
+    /// `ValueTree` corresponding to `IdentStrategy`.
+    pub struct IdentValueTree[][](Box<dyn ValueTree<Value = String>>)
+        -> String;
+}
+
+/// Creates a strategy which generates identifiers as defined by the default
+/// identifier syntax of [UAX #31][uax31]: an `XID_Start` character followed
+/// by `XID_Continue` characters.
+///
+/// `params` selects the length, the scripts the characters come from,
+/// whether identifiers may start with an underscore, keywords to avoid and,
+/// with the `unicode-normalization` feature, whether identifiers are in NFC.
+/// Identifiers shrink toward `a`.
+///
+/// ```
+/// use proptest::prelude::*;
+/// use proptest::string::{ident, IdentParams};
+///
+/// const KEYWORDS: &[&str] = &["fn", "let", "if", "else"];
+///
+/// proptest! {
+///     fn idents_are_not_keywords(s in ident(IdentParams {
+///         keywords: KEYWORDS,
+///         ..IdentParams::default()
+///     })) {
+///         prop_assert!(!KEYWORDS.contains(&s.as_str()));
+///     }
+/// }
+/// # fn main() { idents_are_not_keywords(); }
+/// ```
+///
+/// ## Panics
+///
+/// Panics if `params.len` only allows empty identifiers.
+///
+/// [uax31]: https://www.unicode.org/reports/tr31/#Default_Identifier_Syntax
+pub fn ident(params: IdentParams) -> IdentStrategy {
+    let (min, max) = params.len.start_end_incl();
+    assert!(max >= 1, "Identifiers have at least one character");
+    // The first character is outside the repetition
+    let repeat = format!("{{{},{}}}", min.max(1) - 1, max - 1);
+    let underscore = if params.leading_underscore { "|_" } else { "" };
+    let regex = |pattern: String| {
+        string_regex(&pattern).expect("identifier patterns are valid")
+    };
+    // Each identifier is an alternative first character followed by the
+    // rest. ASCII letters come first, so that identifiers shrink toward
+    // them.
+    let ident = |first: Vec<String>, rest: String| {
+        (Union::new(first.into_iter().map(regex)), regex(rest))
+            .prop_map(|(first, rest)| first + &rest)
+    };
+    let lower = || "[a-z]".to_owned();
+    let idents = Union::new(match params.scripts {
+        IdentScripts::Ascii => vec![ident(
+            vec![lower(), format!("[A-Z]{}", underscore)],
+            format!("[a-zA-Z0-9_]{}", repeat),
+        )],
+        IdentScripts::Single => SCRIPTS
+            .iter()
+            .map(|script| {
+                let mut first = Vec::new();
+                if *script == "Latin" {
+                    first.push(lower());
+                }
+                first.push(format!(
+                    "[\\p{{XID_Start}}&&\\p{{sc={}}}]{}",
+                    script, underscore
+                ));
+                ident(
+                    first,
+                    format!(
+                        "[\\p{{XID_Continue}}&&[\\p{{sc={}}}\\p{{sc=Common}}\
+                         \\p{{sc=Inherited}}]]{}",
+                        script, repeat
+                    ),
+                )
+            })
+            .collect(),
+        IdentScripts::Mixed => vec![ident(
+            vec![lower(), format!("\\p{{XID_Start}}{}", underscore)],
+            format!("\\p{{XID_Continue}}{}", repeat),
+        )],
+    });
+
+    #[cfg(feature = "unicode-normalization")]
+    let nfc = params.nfc;
+    let keywords = params.keywords;
+    IdentStrategy(
+        idents
+            .prop_map(move |ident| {
+                #[cfg(feature = "unicode-normalization")]
+                if nfc {
+                    return ident.nfc().collect();
+                }
+                ident
+            })
+            .prop_filter("Keywords are not identifiers", move |ident| {
+                !keywords.contains(&ident.as_str())
+            })
+            .sboxed(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use regex::Regex;
+    use unicode_ident::{is_xid_continue, is_xid_start};
+
+    use super::*;
+
+    fn sample(params: IdentParams) -> Vec<String> {
+        let mut runner = TestRunner::deterministic();
+        let input = ident(params);
+        (0..256)
+            .map(|_| input.new_tree(&mut runner).unwrap().current())
+            .collect()
+    }
+
+    fn is_ident(s: &str, leading_underscore: bool) -> bool {
+        let mut chars = s.chars();
+        chars.next().is_some_and(|c| {
+            is_xid_start(c) || (leading_underscore && c == '_')
+        }) && chars.all(is_xid_continue)
+    }
+
+    fn single_script(s: &str) -> bool {
+        SCRIPTS.iter().any(|script| {
+            Regex::new(&format!(
+                "^[\\p{{sc={}}}\\p{{sc=Common}}\\p{{sc=Inherited}}]*$",
+                script
+            ))
+            .unwrap()
+            .is_match(s)
+        })
+    }
+
+    #[test]
+    fn generates_ascii_identifiers() {
+        let params = IdentParams {
+            scripts: IdentScripts::Ascii,
+            len: (3..6).into(),
+            ..IdentParams::default()
+        };
+        for ident in sample(params) {
+            assert!(is_ident(&ident, false), "{:?}", ident);
+            assert!(ident.is_ascii(), "{:?}", ident);
+            assert!((3..6).contains(&ident.len()), "{:?}", ident);
+        }
+    }
+
+    #[test]
+    fn generates_single_script_identifiers() {
+        let idents = sample(IdentParams::default());
+        for ident in &idents {
+            assert!(is_ident(ident, false), "{:?}", ident);
+            assert!(single_script(ident), "{:?}", ident);
+        }
+        assert!(idents.iter().any(|ident| !ident.is_ascii()));
+    }
+
+    #[test]
+    fn generates_mixed_script_identifiers() {
+        let params = IdentParams {
+            scripts: IdentScripts::Mixed,
+            leading_underscore: true,
+            ..IdentParams::default()
+        };
+        let idents = sample(params);
+        for ident in &idents {
+            assert!(is_ident(ident, true), "{:?}", ident);
+        }
+        assert!(idents.iter().any(|ident| !single_script(ident)));
+        assert!(idents.iter().any(|ident| ident.starts_with('_')));
+    }
+
+    #[test]
+    fn avoids_keywords() {
+        const KEYWORDS: &[&str] = &["a", "b", "c", "d", "e"];
+        let params = IdentParams {
+            scripts: IdentScripts::Ascii,
+            len: (1..2).into(),
+            keywords: KEYWORDS,
+            ..IdentParams::default()
+        };
+        for ident in sample(params) {
+            assert!(!KEYWORDS.contains(&ident.as_str()));
+        }
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn generates_nfc_identifiers() {
+        use ::unicode_normalization::is_nfc;
+
+        let params = IdentParams {
+            scripts: IdentScripts::Mixed,
+            ..IdentParams::default()
+        };
+        for ident in sample(params) {
+            assert!(is_nfc(&ident), "{:?}", ident);
+            assert!(is_ident(&ident, false), "{:?}", ident);
+        }
+    }
+
+    #[test]
+    fn shrinks_to_a() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&ident(IdentParams::default()), |_| {
+            Err(TestCaseError::fail("minimize"))
+        });
+        match result {
+            Err(TestError::Fail(_, ident)) => assert_eq!("a", ident),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
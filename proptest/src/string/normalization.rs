@@ -0,0 +1,308 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for strings in, or deliberately not in, a Unicode
+//! normalization form.
+//!
+//! Each strategy here transforms the strings of another strategy, and shrinks
+//! as that strategy does. Sources which generate combining marks,
+//! precomposed letters or Hangul, such as `string::graphemes()`, give the
+//! normalization forms the most to do.
+
+use ::unicode_normalization::{
+    is_nfc, is_nfd, is_nfkc, is_nfkd, UnicodeNormalization,
+};
+
+use crate::std_facade::String;
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// A Unicode normalization form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Form {
+    /// Canonical decomposition followed by canonical composition.
+    Nfc,
+    /// Canonical decomposition.
+    Nfd,
+    /// Compatibility decomposition followed by canonical composition.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl Form {
+    fn normalize(self, s: &str) -> String {
+        match self {
+            Form::Nfc => s.nfc().collect(),
+            Form::Nfd => s.nfd().collect(),
+            Form::Nfkc => s.nfkc().collect(),
+            Form::Nfkd => s.nfkd().collect(),
+        }
+    }
+
+    fn is_normalized(self, s: &str) -> bool {
+        match self {
+            Form::Nfc => is_nfc(s),
+            Form::Nfd => is_nfd(s),
+            Form::Nfkc => is_nfkc(s),
+            Form::Nfkd => is_nfkd(s),
+        }
+    }
+
+    /// Return `s` changed so that it is not in this form.
+    fn denormalize(self, s: &str) -> String {
+        // Composed forms are broken by decomposing, and decomposed forms by
+        // composing. If that changes nothing, the text has nothing to
+        // (de)compose, so add something which does.
+        let (mut s, extra): (String, _) = match self {
+            Form::Nfc | Form::Nfkc => (s.nfd().collect(), "e\u{0301}"),
+            Form::Nfd | Form::Nfkd => (s.nfc().collect(), "\u{00E9}"),
+        };
+        if self.is_normalized(&s) {
+            s.push_str(extra);
+        }
+        s
+    }
+}
+
+/// Strategy which generates strings in a normalization form.
+///
+/// Created by the `normalized()` function in the same module.
+#[derive(Clone, Copy, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct Normalized<S> {
+    source: S,
+    form: Form,
+}
+
+/// Create a strategy which generates the strings of `source` normalized to
+/// `form`.
+///
+/// ```
+/// use proptest::prelude::*;
+/// use proptest::string::{graphemes, normalization::*};
+///
+/// proptest! {
+///     fn nfc_is_idempotent(s in normalized(graphemes(0..10), Form::Nfc)) {
+///         use unicode_normalization::UnicodeNormalization;
+///
+///         prop_assert_eq!(&s, &s.nfc().collect::<String>());
+///     }
+/// }
+/// # fn main() { nfc_is_idempotent(); }
+/// ```
+pub fn normalized<S: Strategy<Value = String>>(
+    source: S,
+    form: Form,
+) -> Normalized<S> {
+    Normalized { source, form }
+}
+
+/// `ValueTree` corresponding to `Normalized`.
+#[derive(Clone, Copy, Debug)]
+pub struct NormalizedValueTree<T> {
+    source: T,
+    form: Form,
+}
+
+impl<S: Strategy<Value = String>> Strategy for Normalized<S> {
+    type Tree = NormalizedValueTree<S::Tree>;
+    type Value = String;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(NormalizedValueTree {
+            source: self.source.new_tree(runner)?,
+            form: self.form,
+        })
+    }
+}
+
+impl<T: ValueTree<Value = String>> ValueTree for NormalizedValueTree<T> {
+    type Value = String;
+
+    fn current(&self) -> String {
+        self.form.normalize(&self.source.current())
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.source.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.source.complicate()
+    }
+}
+
+/// Strategy which generates strings not in a normalization form.
+///
+/// Created by the `not_normalized()` function in the same module.
+#[derive(Clone, Copy, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct NotNormalized<S> {
+    source: S,
+    form: Form,
+}
+
+/// Create a strategy which generates strings which are _not_ in `form`,
+/// derived from the strings of `source`.
+///
+/// Strings are decomposed if `form` is composed, or composed if `form` is
+/// decomposed. Strings which that leaves in `form` have a sequence which
+/// `form` would change, either `"e\u{0301}"` or `"\u{00E9}"`, appended.
+pub fn not_normalized<S: Strategy<Value = String>>(
+    source: S,
+    form: Form,
+) -> NotNormalized<S> {
+    NotNormalized { source, form }
+}
+
+/// `ValueTree` corresponding to `NotNormalized`.
+#[derive(Clone, Copy, Debug)]
+pub struct NotNormalizedValueTree<T> {
+    source: T,
+    form: Form,
+}
+
+impl<S: Strategy<Value = String>> Strategy for NotNormalized<S> {
+    type Tree = NotNormalizedValueTree<S::Tree>;
+    type Value = String;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(NotNormalizedValueTree {
+            source: self.source.new_tree(runner)?,
+            form: self.form,
+        })
+    }
+}
+
+impl<T: ValueTree<Value = String>> ValueTree for NotNormalizedValueTree<T> {
+    type Value = String;
+
+    fn current(&self) -> String {
+        self.form.denormalize(&self.source.current())
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.source.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.source.complicate()
+    }
+}
+
+/// Strategy which generates the NFC and NFD forms of the same text.
+///
+/// Created by the `nfc_nfd()` function in the same module.
+#[derive(Clone, Copy, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct NfcNfd<S>(S);
+
+/// Create a strategy which generates pairs `(nfc, nfd)` of the NFC and NFD
+/// forms of the strings of `source`.
+///
+/// The two strings are canonically equivalent, but differ whenever the text
+/// contains anything which composes.
+pub fn nfc_nfd<S: Strategy<Value = String>>(source: S) -> NfcNfd<S> {
+    NfcNfd(source)
+}
+
+/// `ValueTree` corresponding to `NfcNfd`.
+#[derive(Clone, Copy, Debug)]
+pub struct NfcNfdValueTree<T>(T);
+
+impl<S: Strategy<Value = String>> Strategy for NfcNfd<S> {
+    type Tree = NfcNfdValueTree<S::Tree>;
+    type Value = (String, String);
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.0.new_tree(runner).map(NfcNfdValueTree)
+    }
+}
+
+impl<T: ValueTree<Value = String>> ValueTree for NfcNfdValueTree<T> {
+    type Value = (String, String);
+
+    fn current(&self) -> (String, String) {
+        let s = self.0.current();
+        (Form::Nfc.normalize(&s), Form::Nfd.normalize(&s))
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.0.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.0.complicate()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::string::{chars, graphemes};
+
+    const FORMS: [Form; 4] = [Form::Nfc, Form::Nfd, Form::Nfkc, Form::Nfkd];
+
+    #[test]
+    fn normalized_strings_are_in_form() {
+        let mut runner = TestRunner::deterministic();
+        for form in FORMS {
+            let input = normalized(graphemes(0..10), form);
+            for _ in 0..256 {
+                let s = input.new_tree(&mut runner).unwrap().current();
+                assert!(form.is_normalized(&s), "{:?} {:?}", form, s);
+            }
+        }
+    }
+
+    #[test]
+    fn not_normalized_strings_are_not_in_form() {
+        let mut runner = TestRunner::deterministic();
+        for form in FORMS {
+            let sources = [
+                graphemes(0..10).boxed(),
+                chars(crate::char::range('a', 'z'), 0..10).boxed(),
+            ];
+            for source in sources {
+                let input = not_normalized(source, form);
+                for _ in 0..256 {
+                    let s = input.new_tree(&mut runner).unwrap().current();
+                    assert!(!form.is_normalized(&s), "{:?} {:?}", form, s);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pairs_are_equivalent() {
+        let mut runner = TestRunner::deterministic();
+        let mut differ = 0;
+        for _ in 0..256 {
+            let (nfc, nfd) = nfc_nfd(graphemes(1..10))
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!(is_nfc(&nfc) && is_nfd(&nfd), "{:?} {:?}", nfc, nfd);
+            assert_eq!(nfc, Form::Nfc.normalize(&nfd));
+            if nfc != nfd {
+                differ += 1;
+            }
+        }
+        assert!(differ > 64, "{}", differ);
+    }
+
+    #[test]
+    fn sanity() {
+        let source = || chars(crate::char::any(), 0..10);
+        check_strategy_sanity(normalized(source(), Form::Nfkc), None);
+        check_strategy_sanity(not_normalized(source(), Form::Nfd), None);
+        check_strategy_sanity(nfc_nfd(source()), None);
+    }
+}
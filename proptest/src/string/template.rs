@@ -0,0 +1,260 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies splicing generated values into the holes of a fixed template.
+
+use core::fmt::{self, Write};
+
+use crate::std_facade::{Arc, String, Vec};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// Values which can fill the holes of a `template()`.
+///
+/// This is implemented for tuples of up to 10 values implementing
+/// `Display`, each filling one hole.
+pub trait TemplateHoles: fmt::Debug {
+    /// The number of holes filled.
+    const COUNT: usize;
+
+    /// Write the value filling hole `index` to `out`.
+    fn write_hole(&self, index: usize, out: &mut String);
+}
+
+macro_rules! template_holes {
+    ($count:expr; $($ix:tt $typ:ident),*) => {
+        impl<$($typ: fmt::Display + fmt::Debug),*> TemplateHoles
+        for ($($typ,)*) {
+            const COUNT: usize = $count;
+
+            fn write_hole(&self, index: usize, out: &mut String) {
+                match index {
+                    $($ix => write!(out, "{}", self.$ix),)*
+                    _ => panic!("No hole {} in template", index),
+                }
+                .expect("writing to a String failed");
+            }
+        }
+    };
+}
+
+template_holes!(1; 0 A);
+template_holes!(2; 0 A, 1 B);
+template_holes!(3; 0 A, 1 B, 2 C);
+template_holes!(4; 0 A, 1 B, 2 C, 3 D);
+template_holes!(5; 0 A, 1 B, 2 C, 3 D, 4 E);
+template_holes!(6; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+template_holes!(7; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+template_holes!(8; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
+template_holes!(9; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I);
+template_holes!(10; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J);
+
+/// Strategy which generates strings by filling the holes of a template.
+///
+/// Created by the `template()` function in the same module.
+#[derive(Clone, Debug)]
+#[must_use = "strategies do nothing unless used"]
+pub struct Template<S> {
+    literals: Arc<[String]>,
+    holes: S,
+}
+
+/// Creates a strategy which generates strings from `template`, with each
+/// `{}` in it replaced by the corresponding value generated by `holes`, a
+/// tuple of strategies.
+///
+/// As with `format!`, `{{` and `}}` stand for literal braces. Unlike
+/// `format!`, holes may not have names, positions or format specifications.
+///
+/// The text of the template never changes; strings shrink by shrinking the
+/// value in each hole in turn.
+///
+/// ```
+/// use proptest::prelude::*;
+/// use proptest::string::template;
+///
+/// proptest! {
+///     fn request_line_has_three_parts(
+///         line in template(
+///             "GET /{}?id={} HTTP/1.1",
+///             ("[a-z]{1,8}(/[a-z]{1,8}){0,3}", 0..10_000u32),
+///         )
+///     ) {
+///         prop_assert_eq!(3, line.split(' ').count());
+///     }
+/// }
+/// # fn main() { request_line_has_three_parts(); }
+/// ```
+///
+/// ## Panics
+///
+/// Panics if `template` has a different number of holes than `holes` has
+/// elements, or contains an unmatched brace or a hole with anything between
+/// its braces.
+pub fn template<S>(template: &str, holes: S) -> Template<S>
+where
+    S: Strategy,
+    S::Value: TemplateHoles,
+{
+    let literals = parse_template(template);
+    assert_eq!(
+        S::Value::COUNT + 1,
+        literals.len(),
+        "Template {:?} has {} holes, but {} strategies were given",
+        template,
+        literals.len() - 1,
+        S::Value::COUNT
+    );
+    Template {
+        literals: literals.into(),
+        holes,
+    }
+}
+
+/// Split `template` into the literal text around each hole.
+fn parse_template(template: &str) -> Vec<String> {
+    let mut literals = vec![String::new()];
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        let literal = literals.last_mut().unwrap();
+        match (c, chars.clone().next()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                literal.push(c);
+                chars.next();
+            }
+            ('{', Some('}')) => {
+                literals.push(String::new());
+                chars.next();
+            }
+            ('{', _) => {
+                panic!("Template {:?} has a hole which is not `{{}}`", template)
+            }
+            ('}', _) => {
+                panic!("Template {:?} has an unmatched `}}`", template)
+            }
+            _ => literal.push(c),
+        }
+    }
+    literals
+}
+
+/// `ValueTree` corresponding to `Template`.
+#[derive(Clone, Debug)]
+pub struct TemplateValueTree<T> {
+    literals: Arc<[String]>,
+    holes: T,
+}
+
+impl<S> Strategy for Template<S>
+where
+    S: Strategy,
+    S::Value: TemplateHoles,
+{
+    type Tree = TemplateValueTree<S::Tree>;
+    type Value = String;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(TemplateValueTree {
+            literals: Arc::clone(&self.literals),
+            holes: self.holes.new_tree(runner)?,
+        })
+    }
+}
+
+impl<T> ValueTree for TemplateValueTree<T>
+where
+    T: ValueTree,
+    T::Value: TemplateHoles,
+{
+    type Value = String;
+
+    fn current(&self) -> String {
+        let holes = self.holes.current();
+        let mut out = self.literals[0].clone();
+        for (index, literal) in self.literals[1..].iter().enumerate() {
+            holes.write_hole(index, &mut out);
+            out.push_str(literal);
+        }
+        out
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.holes.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.holes.complicate()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_templates() {
+        assert_eq!(vec!["a", "b{c}", ""], parse_template("a{}b{{c}}{}"));
+        assert_eq!(vec!["", ""], parse_template("{}"));
+        assert_eq!(vec!["no holes"], parse_template("no holes"));
+    }
+
+    #[test]
+    #[should_panic(expected = "has 2 holes, but 1 strategies")]
+    fn rejects_wrong_hole_count() {
+        let _ = template("{}{}", (0..1u8,));
+    }
+
+    #[test]
+    #[should_panic(expected = "hole which is not")]
+    fn rejects_positional_holes() {
+        let _ = template("{0}", (0..1u8,));
+    }
+
+    #[test]
+    #[should_panic(expected = "unmatched")]
+    fn rejects_unmatched_braces() {
+        let _ = template("}", (0..1u8,));
+    }
+
+    #[test]
+    fn fills_holes_in_order() {
+        let mut runner = TestRunner::deterministic();
+        let input = template("x={}, y={{{}}}", (0..10i32, "[a-c]"));
+        for _ in 0..64 {
+            let s = input.new_tree(&mut runner).unwrap().current();
+            let (x, y) = s
+                .strip_prefix("x=")
+                .and_then(|s| s.strip_suffix('}'))
+                .and_then(|s| s.split_once(", y={"))
+                .unwrap();
+            assert!(x.parse::<i32>().is_ok_and(|x| (0..10).contains(&x)));
+            assert!(["a", "b", "c"].contains(&y), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn shrinks_each_hole() {
+        let mut runner = TestRunner::deterministic();
+        let input = template("/{}/{}", (10..1000u32, 5..50u8));
+        let result = runner.run(&input, |s| {
+            let (a, b) = s[1..].split_once('/').unwrap();
+            prop_assert!(a.len() < 3 || b.len() < 2);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, s)) => assert_eq!("/100/10", s),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(template("{}-{}", (0..10u8, 0..10u8)), None);
+    }
+}
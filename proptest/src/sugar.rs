@@ -7,7 +7,11 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use crate::std_facade::fmt;
+use crate::std_facade::{fmt, String};
+use crate::test_runner::{TestCaseError, TestCaseResult};
+use core::any::Any;
+use core::fmt::Write;
+use core::future::Future;
 
 /// Easily define `proptest` tests.
 ///
@@ -96,6 +100,65 @@ use crate::std_facade::fmt;
 /// # fn main() { test_addition(); }
 /// ```
 ///
+/// ## Fallible Tests
+///
+/// Like `#[test]` functions, functions may be declared to return
+/// `Result<(), E>`, so that `?` can be used on errors of type `E` within the
+/// body. A test case returning `Err` fails with the `Debug` representation of
+/// the error, and is shrunk as usual. `E` may be any `Debug + 'static` type;
+/// declaring the return type as `TestCaseResult` instead also allows
+/// `prop_assert!` and `prop_assume!` in the body.
+///
+/// ```
+/// use proptest::prelude::*;
+/// use std::num::ParseIntError;
+///
+/// proptest! {
+///   # /*
+///   #[test]
+///   # */
+///   fn parses_printed_numbers(n: u32) -> Result<(), ParseIntError> {
+///     let parsed: u32 = n.to_string().parse()?;
+///     assert_eq!(n, parsed);
+///     Ok(())
+///   }
+/// }
+/// #
+/// # fn main() { parses_printed_numbers(); }
+/// ```
+///
+/// ## Async Tests
+///
+/// Functions may also be declared `async fn`, in which case the body may use
+/// `.await`. Each generated case, including those run while shrinking, is
+/// driven to completion by the runtime created by the `async_runtime` field
+/// of the configuration, so the body should not call `block_on` itself. This
+/// keeps forking, timeouts, and shrinking working as with non-`async` tests.
+///
+/// The default runtime is a minimal executor; see
+/// `proptest::test_runner::AsyncRuntime` for how to use tokio, async-std,
+/// smol, etc, instead.
+///
+/// Due to limitations in `macro_rules!`, all functions within a single
+/// `proptest!` block must either all be `async` or all be non-`async`.
+///
+/// ```
+/// use proptest::prelude::*;
+///
+/// async fn double(x: u32) -> u32 { x * 2 }
+///
+/// proptest! {
+///   # /*
+///   #[test]
+///   # */
+///   async fn test_double(a in 0u32..1000) {
+///     prop_assert_eq!(double(a).await, a + a);
+///   }
+/// }
+/// #
+/// # fn main() { test_double(); }
+/// ```
+///
 /// ## Closure-Style Invocation
 ///
 /// As of proptest 0.8.1, an alternative, "closure-style" invocation is
@@ -152,7 +215,7 @@ macro_rules! proptest {
     (#![proptest_config($config:expr)]
      $(
         $(#[$meta:meta])*
-       fn $test_name:ident($($parm:pat in $strategy:expr),+ $(,)?) $body:block
+       fn $test_name:ident($($parm:pat in $strategy:expr),+ $(,)?) $(-> $ret:ty)? $body:block
     )*) => {
         $(
             $(#[$meta])*
@@ -160,14 +223,16 @@ macro_rules! proptest {
                 let mut config = $crate::test_runner::contextualize_config($config.clone());
                 config.test_name = ::core::option::Option::Some(
                     ::core::concat!(::core::module_path!(), "::", ::core::stringify!($test_name)));
-                $crate::proptest_helper!(@_BODY config ($($parm in $strategy),+) [] $body);
+                $crate::proptest_helper!(@_BODY config ($($parm in $strategy),+) []
+                    ($crate::proptest_helper!(@_TEMPLATE $test_name [$($ret)?] $body))
+                    $crate::proptest_helper!(@_RETURN [$($ret)?] $body));
             }
         )*
     };
     (#![proptest_config($config:expr)]
      $(
         $(#[$meta:meta])*
-        fn $test_name:ident($($arg:tt)+) $body:block
+        fn $test_name:ident($($arg:tt)+) $(-> $ret:ty)? $body:block
     )*) => {
         $(
             $(#[$meta])*
@@ -175,27 +240,80 @@ macro_rules! proptest {
                 let mut config = $crate::test_runner::contextualize_config($config.clone());
                 config.test_name = ::core::option::Option::Some(
                     ::core::concat!(::core::module_path!(), "::", ::core::stringify!($test_name)));
-                $crate::proptest_helper!(@_BODY2 config ($($arg)+) [] $body);
+                $crate::proptest_helper!(@_BODY2 config ($($arg)+) []
+                    ($crate::proptest_helper!(@_TEMPLATE $test_name [$($ret)?] $body))
+                    $crate::proptest_helper!(@_RETURN [$($ret)?] $body));
+            }
+        )*
+    };
+
+    (#![proptest_config($config:expr)]
+     $(
+        $(#[$meta:meta])*
+        async fn $test_name:ident($($parm:pat in $strategy:expr),+ $(,)?) $(-> $ret:ty)? $body:block
+    )*) => {
+        $(
+            $(#[$meta])*
+            fn $test_name() {
+                let mut config = $crate::test_runner::contextualize_config($config.clone());
+                config.test_name = ::core::option::Option::Some(
+                    ::core::concat!(::core::module_path!(), "::", ::core::stringify!($test_name)));
+                $crate::proptest_helper!(@_BODY_ASYNC config ($($parm in $strategy),+)
+                    $crate::proptest_helper!(@_RETURN_ASYNC [$($ret)?] $body));
+            }
+        )*
+    };
+    (#![proptest_config($config:expr)]
+     $(
+        $(#[$meta:meta])*
+        async fn $test_name:ident($($arg:tt)+) $(-> $ret:ty)? $body:block
+    )*) => {
+        $(
+            $(#[$meta])*
+            fn $test_name() {
+                let mut config = $crate::test_runner::contextualize_config($config.clone());
+                config.test_name = ::core::option::Option::Some(
+                    ::core::concat!(::core::module_path!(), "::", ::core::stringify!($test_name)));
+                $crate::proptest_helper!(@_BODY2_ASYNC config ($($arg)+)
+                    $crate::proptest_helper!(@_RETURN_ASYNC [$($ret)?] $body));
             }
         )*
     };
 
     ($(
         $(#[$meta:meta])*
-        fn $test_name:ident($($parm:pat in $strategy:expr),+ $(,)?) $body:block
+        fn $test_name:ident($($parm:pat in $strategy:expr),+ $(,)?) $(-> $ret:ty)? $body:block
     )*) => { $crate::proptest! {
         #![proptest_config($crate::test_runner::Config::default())]
         $($(#[$meta])*
-          fn $test_name($($parm in $strategy),+) $body)*
+          fn $test_name($($parm in $strategy),+) $(-> $ret)? $body)*
     } };
 
     ($(
         $(#[$meta:meta])*
-        fn $test_name:ident($($arg:tt)+) $body:block
+        fn $test_name:ident($($arg:tt)+) $(-> $ret:ty)? $body:block
     )*) => { $crate::proptest! {
         #![proptest_config($crate::test_runner::Config::default())]
         $($(#[$meta])*
-          fn $test_name($($arg)+) $body)*
+          fn $test_name($($arg)+) $(-> $ret)? $body)*
+    } };
+
+    ($(
+        $(#[$meta:meta])*
+        async fn $test_name:ident($($parm:pat in $strategy:expr),+ $(,)?) $(-> $ret:ty)? $body:block
+    )*) => { $crate::proptest! {
+        #![proptest_config($crate::test_runner::Config::default())]
+        $($(#[$meta])*
+          async fn $test_name($($parm in $strategy),+) $(-> $ret)? $body)*
+    } };
+
+    ($(
+        $(#[$meta:meta])*
+        async fn $test_name:ident($($arg:tt)+) $(-> $ret:ty)? $body:block
+    )*) => { $crate::proptest! {
+        #![proptest_config($crate::test_runner::Config::default())]
+        $($(#[$meta])*
+          async fn $test_name($($arg)+) $(-> $ret)? $body)*
     } };
 
     (|($($parm:pat in $strategy:expr),+ $(,)?)| $body:expr) => {
@@ -225,25 +343,25 @@ macro_rules! proptest {
     ($config:expr, |($($parm:pat in $strategy:expr),+ $(,)?)| $body:expr) => { {
         let mut config = $crate::test_runner::contextualize_config($config.__sugar_to_owned());
         $crate::sugar::force_no_fork(&mut config);
-        $crate::proptest_helper!(@_BODY config ($($parm in $strategy),+) [] $body)
+        $crate::proptest_helper!(@_BODY config ($($parm in $strategy),+) [] (::core::option::Option::None) $body)
     } };
 
     ($config:expr, move |($($parm:pat in $strategy:expr),+ $(,)?)| $body:expr) => { {
         let mut config = $crate::test_runner::contextualize_config($config.__sugar_to_owned());
         $crate::sugar::force_no_fork(&mut config);
-        $crate::proptest_helper!(@_BODY config ($($parm in $strategy),+) [move] $body)
+        $crate::proptest_helper!(@_BODY config ($($parm in $strategy),+) [move] (::core::option::Option::None) $body)
     } };
 
     ($config:expr, |($($arg:tt)+)| $body:expr) => { {
         let mut config = $crate::test_runner::contextualize_config($config.__sugar_to_owned());
         $crate::sugar::force_no_fork(&mut config);
-        $crate::proptest_helper!(@_BODY2 config ($($arg)+) [] $body);
+        $crate::proptest_helper!(@_BODY2 config ($($arg)+) [] (::core::option::Option::None) $body);
     } };
 
     ($config:expr, move |($($arg:tt)+)| $body:expr) => { {
         let mut config = $crate::test_runner::contextualize_config($config.__sugar_to_owned());
         $crate::sugar::force_no_fork(&mut config);
-        $crate::proptest_helper!(@_BODY2 config ($($arg)+) [move] $body);
+        $crate::proptest_helper!(@_BODY2 config ($($arg)+) [move] (::core::option::Option::None) $body);
     } };
 }
 
@@ -275,6 +393,76 @@ macro_rules! prop_assume {
     };
 }
 
+/// Records a label against the current test case if a condition holds.
+///
+/// This is invoked as `prop_classify!(condition, label)`, where `label` is
+/// anything convertible into a `String`. The distribution of labels is
+/// available from `TestRunner::label_statistics()`, and if `Config::verbose`
+/// is at least 1 and no `Config::report_format` is selected, the percentage
+/// of cases carrying each label is printed to stderr at the end of a passing
+/// test. This makes it easy to notice when filters or strategies skew
+/// generation toward trivial inputs.
+///
+/// Labels are only counted for cases which pass, and at most once per case.
+/// Without the `std` feature, this does nothing. Labels are not collected
+/// when `Config::fork` is enabled, since the cases run in a child process.
+///
+/// ```
+/// use proptest::prelude::*;
+///
+/// proptest! {
+///   # /*
+///   #[test]
+///   # */
+///   fn reverse_is_involutive(v in prop::collection::vec(any::<u8>(), 0..10)) {
+///     prop_classify!(v.is_empty(), "empty");
+///     prop_classify!(v.len() == 1, "singleton");
+///     let mut rev = v.clone();
+///     rev.reverse();
+///     rev.reverse();
+///     prop_assert_eq!(v, rev);
+///   }
+/// }
+/// # fn main() { reverse_is_involutive(); }
+/// ```
+#[macro_export]
+macro_rules! prop_classify {
+    ($cond:expr, $label:expr $(,)?) => {
+        if $cond {
+            $crate::test_runner::record_label(
+                ::core::convert::Into::into($label));
+        }
+    };
+}
+
+/// Records the `Debug` representation of a value as a label against the
+/// current test case.
+///
+/// This is QuickCheck's `collect`; it behaves like `prop_classify!` with a
+/// condition which is always true and the value itself as the label.
+///
+/// ```
+/// use proptest::prelude::*;
+///
+/// proptest! {
+///   # /*
+///   #[test]
+///   # */
+///   fn len_is_bounded(v in prop::collection::vec(any::<u8>(), 0..4)) {
+///     prop_collect!(v.len());
+///     prop_assert!(v.len() < 4);
+///   }
+/// }
+/// # fn main() { len_is_bounded(); }
+/// ```
+#[macro_export]
+macro_rules! prop_collect {
+    ($value:expr $(,)?) => {{
+        extern crate alloc;
+        $crate::test_runner::record_label(alloc::format!("{:?}", $value));
+    }};
+}
+
 /// Produce a strategy which picks one of the listed choices.
 ///
 /// This is conceptually equivalent to calling `prop_union` on the first two
@@ -287,7 +475,9 @@ macro_rules! prop_assume {
 /// strategies separated by commas; this will cause value generation to pick
 /// from the strategies uniformly. The other form is to provide a weight in the
 /// form of a `u32` before each strategy, separated from the strategy with
-/// `=>`.
+/// `=>`. Weights may be any expressions, computed at run time, and strategies
+/// with a weight of 0 are never generated, not even while shrinking. To build
+/// the set of strategies itself at run time, use `Union::try_new_weighted()`.
 ///
 /// Note that the exact type returned by the macro varies depending on how many
 /// inputs there are. In particular, if given exactly one option, it will
@@ -608,6 +798,31 @@ macro_rules! prop_oneof {
 /// # fn main() { }
 /// ```
 ///
+/// The function may also be generic, with any lifetime, type and const
+/// parameters after its name and a `where` clause after the return type. The
+/// strategy returned may borrow from arguments with a named lifetime.
+///
+/// ```rust,no_run
+/// # #![allow(dead_code)]
+/// use proptest::prelude::*;
+///
+/// prop_compose! {
+///   fn arb_pair<T: Arbitrary>()(x in any::<T>(), y in any::<T>()) -> (T, T) {
+///     (x, y)
+///   }
+/// }
+///
+/// prop_compose! {
+///   fn arb_element<'a, T>(items: &'a [T])(index in 0..items.len()) -> &'a T
+///   where
+///     T: std::fmt::Debug,
+///   {
+///     &items[index]
+///   }
+/// }
+/// # fn main() { }
+/// ```
+///
 /// ## Comparison with Hypothesis' `@composite`
 ///
 /// `prop_compose!` makes it easy to do a lot of things you can do with
@@ -622,6 +837,14 @@ macro_rules! prop_oneof {
 /// `prop_flat_map()` by hand.
 #[macro_export]
 macro_rules! prop_compose {
+    ($(#[$meta:meta])*
+     $vis:vis
+     $([$($modi:tt)*])? fn $name:ident < $($rest:tt)+) =>
+    {
+        $crate::proptest_helper!(@_COMPOSE_GEN
+            [$(#[$meta])* $vis $($($modi)*)? fn $name] [] [] [] ^ $($rest)+);
+    };
+
     ($(#[$meta:meta])*
      $vis:vis
      $([$($modi:tt)*])? fn $name:ident $params:tt
@@ -766,6 +989,10 @@ macro_rules! prop_assert {
 ///
 /// See `prop_assert!` for a more in-depth discussion.
 ///
+/// Values whose `Debug` representations are long are not shown in full, but
+/// as the differences between their pretty-printed representations, as
+/// described by `Config::diff_formatter`.
+///
 /// ## Example
 ///
 /// ```
@@ -795,9 +1022,8 @@ macro_rules! prop_assert_eq {
         let right = $right;
         $crate::prop_assert!(
             left == right,
-            "assertion failed: `(left == right)` \
-             \n  left: `{:?}`,\n right: `{:?}`",
-            left, right);
+            "assertion failed: `(left == right)` {}",
+            $crate::test_runner::describe_unequal(&left, &right));
     }};
 
     ($left:expr, $right:expr, $fmt:tt $($args:tt)*) => {{
@@ -805,10 +1031,8 @@ macro_rules! prop_assert_eq {
         let right = $right;
         $crate::prop_assert!(
             left == right,
-            concat!(
-                "assertion failed: `(left == right)` \
-                 \n  left: `{:?}`, \n right: `{:?}`: ", $fmt),
-            left, right $($args)*);
+            concat!("assertion failed: `(left == right)` {}: ", $fmt),
+            $crate::test_runner::describe_unequal(&left, &right) $($args)*);
     }};
 }
 
@@ -962,7 +1186,7 @@ macro_rules! proptest_helper {
         (::core::stringify!($a), $crate::proptest_helper!(@_WRAPSTR ($($rest),*)))
     };
     // build a property testing block that when executed, executes the full property test.
-    (@_BODY $config:ident ($($parm:pat in $strategy:expr),+) [$($mod:tt)*] $body:expr) => {{
+    (@_BODY $config:ident ($($parm:pat in $strategy:expr),+) [$($mod:tt)*] ($template:expr) $body:expr) => {{
         $config.source_file = Some(file!());
         let mut runner = $crate::test_runner::TestRunner::new($config);
         let names = $crate::proptest_helper!(@_WRAPSTR ($($parm),*));
@@ -978,11 +1202,14 @@ macro_rules! proptest_helper {
             })
         {
             ::core::result::Result::Ok(()) => (),
-            ::core::result::Result::Err(e) => ::core::panic!("{}\n{}", e, runner),
+            ::core::result::Result::Err(e) => {
+                $crate::sugar::write_regression_test(runner.config(), $template, &e);
+                ::core::panic!("{}\n{}", e, runner)
+            }
         }
     }};
     // build a property testing block that when executed, executes the full property test.
-    (@_BODY2 $config:ident ($($arg:tt)+) [$($mod:tt)*] $body:expr) => {{
+    (@_BODY2 $config:ident ($($arg:tt)+) [$($mod:tt)*] ($template:expr) $body:expr) => {{
         $config.source_file = Some(::core::file!());
         let mut runner = $crate::test_runner::TestRunner::new($config);
         let names = $crate::proptest_helper!(@_EXT _STR ($($arg)*));
@@ -998,10 +1225,61 @@ macro_rules! proptest_helper {
             })
         {
             ::core::result::Result::Ok(()) => (),
-            ::core::result::Result::Err(e) => ::core::panic!("{}\n{}", e, runner),
+            ::core::result::Result::Err(e) => {
+                $crate::sugar::write_regression_test(runner.config(), $template, &e);
+                ::core::panic!("{}\n{}", e, runner)
+            }
         }
     }};
 
+    // build a property testing block whose body is an `async` block driven
+    // to completion by the configured `AsyncRuntime` for each case.
+    (@_BODY_ASYNC $config:ident ($($parm:pat in $strategy:expr),+) $body:expr) => {{
+        let async_runtime = ($config.async_runtime)();
+        $crate::proptest_helper!(@_BODY $config ($($parm in $strategy),+) [] (::core::option::Option::None) {
+            async_runtime.block_on($crate::std_facade::Box::pin(async move {
+                let (): () = $body;
+                ::core::result::Result::Ok(())
+            }))?
+        })
+    }};
+    (@_BODY2_ASYNC $config:ident ($($arg:tt)+) $body:expr) => {{
+        let async_runtime = ($config.async_runtime)();
+        $crate::proptest_helper!(@_BODY2 $config ($($arg)+) [] (::core::option::Option::None) {
+            async_runtime.block_on($crate::std_facade::Box::pin(async move {
+                let (): () = $body;
+                ::core::result::Result::Ok(())
+            }))?
+        })
+    }};
+
+    // describe a test function for writing a regression test for it.
+    (@_TEMPLATE $test_name:ident [$($ret:ty)?] $body:block) => {
+        ::core::option::Option::Some($crate::sugar::RegressionTemplate {
+            name: ::core::stringify!($test_name),
+            ret: $crate::proptest_helper!(@_RET_STR [$($ret)?]),
+            body: ::core::stringify!($body),
+        })
+    };
+    (@_RET_STR []) => { ::core::option::Option::None };
+    (@_RET_STR [$ret:ty]) => {
+        ::core::option::Option::Some(::core::stringify!($ret))
+    };
+
+    // turn the body of a test function declared with a return type into a
+    // unit expression which fails the test case on an `Err` result.
+    (@_RETURN [] $body:block) => { $body };
+    (@_RETURN [$ret:ty] $body:block) => {{
+        #[allow(clippy::redundant_closure_call)]
+        let result = (|| -> $ret { $body })();
+        $crate::sugar::TestCaseOutput::into_test_case_result(result)?
+    }};
+    (@_RETURN_ASYNC [] $body:block) => { $body };
+    (@_RETURN_ASYNC [$ret:ty] $body:block) => {
+        $crate::sugar::TestCaseOutput::into_test_case_result(
+            $crate::sugar::async_output::<$ret, _>(async move { $body }).await)?
+    };
+
     // The logic below helps support `pat: type` in the proptest! macro.
 
     // These matchers define the actual logic:
@@ -1012,6 +1290,127 @@ macro_rules! proptest_helper {
     (@_PAT in [$s:expr] [$p:pat]) => { $p };
     (@_STR in [$s:expr] [$p:pat]) => { ::core::stringify!($p) };
 
+    // Split off the generic parameters of a `prop_compose!` function up to
+    // their closing `>`, which may be glued to a nested one as `>>`, and
+    // collect the names they declare. `^` marks the start of a parameter, and
+    // each `x` in the third list is a `<` not yet closed.
+    (@_COMPOSE_GEN $head:tt $gen:tt $names:tt [] ^ > $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_BODY $head $gen $names $($rest)*);
+    };
+    (@_COMPOSE_GEN $head:tt [$($gen:tt)*] [$($names:tt)*] []
+     ^ $lt:lifetime $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_GEN $head
+            [$($gen)* $lt] [$($names)* $lt,] [] - $($rest)*);
+    };
+    (@_COMPOSE_GEN $head:tt [$($gen:tt)*] [$($names:tt)*] []
+     ^ const $n:ident $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_GEN $head
+            [$($gen)* const $n] [$($names)* $n,] [] - $($rest)*);
+    };
+    (@_COMPOSE_GEN $head:tt [$($gen:tt)*] [$($names:tt)*] []
+     ^ $n:ident $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_GEN $head
+            [$($gen)* $n] [$($names)* $n,] [] - $($rest)*);
+    };
+    (@_COMPOSE_GEN $head:tt [$($gen:tt)*] $names:tt [] - , $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_GEN $head
+            [$($gen)* ,] $names [] ^ $($rest)*);
+    };
+    (@_COMPOSE_GEN $head:tt $gen:tt $names:tt [] - > $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_BODY $head $gen $names $($rest)*);
+    };
+    (@_COMPOSE_GEN $head:tt [$($gen:tt)*] $names:tt [x] - >> $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_BODY $head
+            [$($gen)* >] $names $($rest)*);
+    };
+    (@_COMPOSE_GEN $head:tt [$($gen:tt)*] $names:tt [x x $($d:tt)*]
+     - >> $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_GEN $head
+            [$($gen)* >>] $names [$($d)*] - $($rest)*);
+    };
+    (@_COMPOSE_GEN $head:tt [$($gen:tt)*] $names:tt [$($d:tt)*]
+     - < $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_GEN $head
+            [$($gen)* <] $names [x $($d)*] - $($rest)*);
+    };
+    (@_COMPOSE_GEN $head:tt [$($gen:tt)*] $names:tt [x $($d:tt)*]
+     - > $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_GEN $head
+            [$($gen)* >] $names [$($d)*] - $($rest)*);
+    };
+    (@_COMPOSE_GEN $head:tt [$($gen:tt)*] $names:tt $d:tt
+     - $t:tt $($rest:tt)*) => {
+        $crate::proptest_helper!(@_COMPOSE_GEN $head
+            [$($gen)* $t] $names $d - $($rest)*);
+    };
+
+    // Split the rest of a generic `prop_compose!` function into its argument
+    // lists, return type and `where` clause.
+    (@_COMPOSE_BODY $head:tt $gen:tt $names:tt $params:tt
+     $args:tt -> $return_type:ty $body:block) => {
+        $crate::proptest_helper!(@_COMPOSE_FN $head $gen $names $params
+            [$args] [$return_type] [] $body);
+    };
+    (@_COMPOSE_BODY $head:tt $gen:tt $names:tt $params:tt
+     $args:tt $args2:tt -> $return_type:ty $body:block) => {
+        $crate::proptest_helper!(@_COMPOSE_FN $head $gen $names $params
+            [$args $args2] [$return_type] [] $body);
+    };
+    (@_COMPOSE_BODY $head:tt $gen:tt $names:tt $params:tt
+     $args:tt -> $return_type:ty where $($rest:tt)+) => {
+        $crate::proptest_helper!(@_COMPOSE_WHERE $head $gen $names $params
+            [$args] [$return_type] [] $($rest)+);
+    };
+    (@_COMPOSE_BODY $head:tt $gen:tt $names:tt $params:tt
+     $args:tt $args2:tt -> $return_type:ty where $($rest:tt)+) => {
+        $crate::proptest_helper!(@_COMPOSE_WHERE $head $gen $names $params
+            [$args $args2] [$return_type] [] $($rest)+);
+    };
+    (@_COMPOSE_WHERE $head:tt $gen:tt $names:tt $params:tt $args:tt
+     $return_type:tt $where:tt { $($body:tt)* }) => {
+        $crate::proptest_helper!(@_COMPOSE_FN $head $gen $names $params
+            $args $return_type $where { $($body)* });
+    };
+    (@_COMPOSE_WHERE $head:tt $gen:tt $names:tt $params:tt $args:tt
+     $return_type:tt [$($where:tt)*] $t:tt $($rest:tt)+) => {
+        $crate::proptest_helper!(@_COMPOSE_WHERE $head $gen $names $params
+            $args $return_type [$($where)* $t] $($rest)+);
+    };
+
+    // The generated function captures all its generic parameters, so that
+    // the strategy may borrow from arguments with a named lifetime.
+    (@_COMPOSE_FN [$($head:tt)*] [$($gen:tt)*] [$($names:tt)*] $params:tt
+     [($($arg:tt)+)] [$return_type:ty] [$($where:tt)*] $body:block) => {
+        #[must_use = "strategies do nothing unless used"]
+        $($head)* <$($gen)*> $params
+            -> impl $crate::strategy::Strategy<Value = $return_type>
+                + use<$($names)*>
+        where $($where)*
+        {
+            let strat = $crate::proptest_helper!(@_EXT _STRAT ($($arg)+));
+            $crate::strategy::Strategy::prop_map(strat,
+                move |$crate::proptest_helper!(@_EXT _PAT ($($arg)+))| $body)
+        }
+    };
+    (@_COMPOSE_FN [$($head:tt)*] [$($gen:tt)*] [$($names:tt)*] $params:tt
+     [($($arg:tt)+) ($($arg2:tt)+)] [$return_type:ty] [$($where:tt)*]
+     $body:block) => {
+        #[must_use = "strategies do nothing unless used"]
+        $($head)* <$($gen)*> $params
+            -> impl $crate::strategy::Strategy<Value = $return_type>
+                + use<$($names)*>
+        where $($where)*
+        {
+            let strat = $crate::proptest_helper!(@_EXT _STRAT ($($arg)+));
+            let strat = $crate::strategy::Strategy::prop_flat_map(
+                strat,
+                move |$crate::proptest_helper!(@_EXT _PAT ($($arg)+))|
+                $crate::proptest_helper!(@_EXT _STRAT ($($arg2)+)));
+            $crate::strategy::Strategy::prop_map(strat,
+                move |$crate::proptest_helper!(@_EXT _PAT ($($arg2)+))| $body)
+        }
+    };
+
     // These matchers rewrite into the above extractors.
     // We have to do this because `:` can't FOLLOW(pat).
     // Note that this is not the full `pat` grammar...
@@ -1080,6 +1479,81 @@ impl<V: fmt::Debug> fmt::Debug for NamedArguments<&'static str, V> {
     }
 }
 
+/// Writes the arguments of a `proptest!` test as `let` statements binding
+/// their patterns to their values, for a regression test.
+#[doc(hidden)]
+pub trait RegressionBindings {
+    fn write_bindings(&self, out: &mut String);
+}
+
+impl<V: fmt::Debug> RegressionBindings for NamedArguments<&'static str, V> {
+    fn write_bindings(&self, out: &mut String) {
+        let _ = writeln!(out, "    let {} = {:?};", self.0, self.1);
+    }
+}
+
+/// What `proptest!` knows of a test function, from which to write a
+/// regression test for its minimal failing case.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug)]
+pub struct RegressionTemplate {
+    pub name: &'static str,
+    pub ret: Option<&'static str>,
+    pub body: &'static str,
+}
+
+impl RegressionTemplate {
+    /// Render a `#[test]` function running the body with its arguments
+    /// bound by `bindings`.
+    fn render(&self, bindings: &dyn RegressionBindings) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "#[test]\nfn {}_regression() -> {} {{",
+            self.name,
+            self.ret.unwrap_or(
+                "::core::result::Result<(), \
+                 ::proptest::test_runner::TestCaseError>"
+            )
+        );
+        bindings.write_bindings(&mut out);
+        let _ = writeln!(out, "    {}", self.body);
+        if self.ret.is_none() {
+            out.push_str("    ::core::result::Result::Ok(())\n");
+        }
+        out.push('}');
+        out
+    }
+}
+
+/// If `Config::regression_test` is set, write a regression test for the
+/// minimal failing case of a failed `proptest!` test.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn write_regression_test<T: RegressionBindings>(
+    config: &crate::test_runner::Config,
+    template: Option<RegressionTemplate>,
+    error: &crate::test_runner::TestError<T>,
+) {
+    use crate::test_runner::{RegressionTestOutput, TestError};
+
+    if let (Some(template), TestError::Fail(_, ref value)) = (template, error)
+    {
+        if RegressionTestOutput::Off != config.regression_test {
+            config.regression_test.write(&template.render(value));
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub fn write_regression_test<T: RegressionBindings>(
+    _: &crate::test_runner::Config,
+    _: Option<RegressionTemplate>,
+    _: &crate::test_runner::TestError<T>,
+) {
+}
+
 macro_rules! named_arguments_tuple {
     ($($ix:tt $argn:ident $argv:ident)*) => {
         impl<'a, $($argn : Copy),*, $($argv),*> fmt::Debug
@@ -1120,6 +1594,31 @@ macro_rules! named_arguments_tuple {
                 Ok(())
             }
         }
+
+        impl<'a, $($argn : Copy),*, $($argv),*> RegressionBindings
+        for NamedArguments<($($argn,)*),&'a ($($argv,)*)>
+        where $(NamedArguments<$argn, &'a $argv> : RegressionBindings),*,
+              $($argv : 'a),*
+        {
+            fn write_bindings(&self, out: &mut String) {
+                $(
+                    NamedArguments((self.0).$ix, &(self.1).$ix)
+                        .write_bindings(out);
+                )*
+            }
+        }
+
+        impl<$($argn : Copy),*, $($argv),*> RegressionBindings
+        for NamedArguments<($($argn,)*), ($($argv,)*)>
+        where $(for<'a> NamedArguments<$argn, &'a $argv> : RegressionBindings),*
+        {
+            fn write_bindings(&self, out: &mut String) {
+                $(
+                    NamedArguments((self.0).$ix, &(self.1).$ix)
+                        .write_bindings(out);
+                )*
+            }
+        }
     }
 }
 
@@ -1139,6 +1638,48 @@ named_arguments_tuple!(0 AN AV 1 BN BV 2 CN CV 3 DN DV 4 EN EV
 named_arguments_tuple!(0 AN AV 1 BN BV 2 CN CV 3 DN DV 4 EN EV
                        5 FN FV 6 GN GV 7 HN HV 8 IN IV 9 JN JV);
 
+/// The value returned by the body of a test function in `proptest!`.
+///
+/// A body with no declared return type produces `()`. A body declared to
+/// return `Result<(), E>` fails the test case when it returns `Err`: a
+/// `TestCaseError` is passed through as is, so that `prop_assume!` still
+/// rejects the case, while any other error fails it with its `Debug`
+/// representation, as `#[test]` functions do.
+#[doc(hidden)]
+pub trait TestCaseOutput {
+    fn into_test_case_result(self) -> TestCaseResult;
+}
+
+impl TestCaseOutput for () {
+    fn into_test_case_result(self) -> TestCaseResult {
+        Ok(())
+    }
+}
+
+impl<E: fmt::Debug + 'static> TestCaseOutput for Result<(), E> {
+    fn into_test_case_result(self) -> TestCaseResult {
+        self.map_err(|error| {
+            let mut error = Some(error);
+            if let Some(error) = (&mut error as &mut dyn Any)
+                .downcast_mut::<Option<TestCaseError>>()
+            {
+                return error.take().expect("error taken twice");
+            }
+
+            let mut message = String::new();
+            let _ = write!(message, "{:?}", error.expect("error taken twice"));
+            TestCaseError::fail(message)
+        })
+    }
+}
+
+/// Fix the output type of the `async` body of a test function declared
+/// with a return type, so that `?` within it converts to that type.
+#[doc(hidden)]
+pub fn async_output<T, F: Future<Output = T>>(body: F) -> F {
+    body
+}
+
 #[cfg(feature = "std")]
 #[doc(hidden)]
 pub fn force_no_fork(config: &mut crate::test_runner::Config) {
@@ -1165,6 +1706,7 @@ pub fn force_no_fork(_: &mut crate::test_runner::Config) {}
 
 #[cfg(test)]
 mod test {
+    use crate::std_facade::Vec;
     use crate::strategy::Just;
 
     prop_compose! {
@@ -1231,6 +1773,52 @@ mod test {
         }
     }
 
+    prop_compose! {
+        fn generic_pair<T: crate::arbitrary::Arbitrary + Clone>()
+                       (x in crate::arbitrary::any::<T>())
+                       -> (T, T) {
+            (x.clone(), x)
+        }
+    }
+
+    prop_compose! {
+        fn generic_element<'a, T: core::fmt::Debug + 'a>
+            (items: &'a [T])
+            (index in 0..items.len())
+            -> &'a T
+        {
+            &items[index]
+        }
+    }
+
+    prop_compose! {
+        /// Nested generics end with `>>`.
+        pub(crate) fn generic_where<
+            T, const N: usize, I: IntoIterator<Item = Option<T>>>
+            (items: I)
+            (index in 0..N,
+             items in Just(items.into_iter().collect::<Vec<_>>()))
+            -> Option<T>
+        where
+            T: core::fmt::Debug + Clone,
+        {
+            items.get(index).cloned().flatten()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn generic_prop_compose(
+            (a, b) in generic_pair::<u8>(),
+            x in generic_element(&[1, 2, 3]),
+            y in generic_where::<_, 4, _>(vec![Some(1), None]),
+        ) {
+            prop_assert_eq!(a, b);
+            prop_assert!([1, 2, 3].contains(x));
+            prop_assert!(y.is_none() || y == Some(1));
+        }
+    }
+
     prop_compose! {
         #[allow(dead_code)]
         fn double_closure_is_move
@@ -1653,3 +2241,230 @@ mod any_tests {
         let proptest_helper!(@_EXT _PAT( _x in 1..2 )) = 1;
     }
 }
+
+#[cfg(test)]
+mod result_tests {
+    use crate::test_runner::{Config, TestCaseError, TestCaseResult};
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct OddError(u32);
+
+    fn even(x: u32) -> Result<u32, OddError> {
+        if 0 == x % 2 {
+            Ok(x / 2)
+        } else {
+            Err(OddError(x))
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn result_with_user_error(x in 0u32..100) -> Result<(), OddError> {
+            let half = even(x * 2)?;
+            assert_eq!(x, half);
+            Ok(())
+        }
+
+        #[test]
+        fn result_with_test_case_error(x: u8) -> TestCaseResult {
+            prop_assume!(x > 0);
+            prop_assert!(u32::from(x) < 256);
+            Ok(())
+        }
+
+        #[test]
+        fn result_with_early_return(x in 0u32..100) -> Result<(), OddError> {
+            if x > 50 {
+                return Ok(());
+            }
+            even(x * 2).map(|_| ())
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "OddError(1)")]
+    fn user_errors_fail_and_shrink() {
+        proptest! {
+            #![proptest_config(Config {
+                failure_persistence: None,
+                .. Config::default()
+            })]
+            fn inner(x in 1u32..1000) -> Result<(), OddError> {
+                even(x * 2 - 1)?;
+                Ok(())
+            }
+        }
+
+        inner();
+    }
+
+    #[test]
+    #[should_panic(expected = "Too many global rejects")]
+    fn test_case_errors_keep_rejections() {
+        proptest! {
+            #![proptest_config(Config {
+                failure_persistence: None,
+                .. Config::default()
+            })]
+            fn inner(_x: u8) -> Result<(), TestCaseError> {
+                Err(TestCaseError::reject("always"))
+            }
+        }
+
+        inner();
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod regression_test_tests {
+    use super::{NamedArguments, RegressionTemplate};
+    use crate::test_runner::{Config, RegressionTestOutput};
+    use std::path::PathBuf;
+
+    fn regression_file() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "proptest-regression-test-{}.rs",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn renders_bindings_and_body() {
+        let template = RegressionTemplate {
+            name: "adds",
+            ret: None,
+            body: "{ prop_assert!(a + b < c.1); }",
+        };
+        let value = NamedArguments(("a", ("b", "c")), (1u8, (2u8, ('x', 3u8))));
+        assert_eq!(
+            "#[test]\n\
+             fn adds_regression() -> ::core::result::Result<(), \
+             ::proptest::test_runner::TestCaseError> {\n    \
+             let a = 1;\n    \
+             let b = 2;\n    \
+             let c = ('x', 3);\n    \
+             { prop_assert!(a + b < c.1); }\n    \
+             ::core::result::Result::Ok(())\n\
+             }",
+            template.render(&value)
+        );
+
+        let template = RegressionTemplate {
+            ret: Some("Result<(), MyError>"),
+            ..template
+        };
+        assert!(template
+            .render(&NamedArguments("a", 1))
+            .starts_with("#[test]\nfn adds_regression() -> Result<(), MyError> {"));
+    }
+
+    #[test]
+    fn writes_regression_test_of_failing_test() {
+        proptest! {
+            #![proptest_config(Config {
+                failure_persistence: None,
+                regression_test: RegressionTestOutput::File(regression_file()),
+                .. Config::default()
+            })]
+            fn inner(a in 0u32..1000, ref s in "[a-z]") {
+                prop_assert!(a < 5, "{}", s);
+            }
+        }
+
+        let _ = std::fs::remove_file(regression_file());
+        assert!(std::panic::catch_unwind(inner).is_err());
+        let code = std::fs::read_to_string(regression_file()).unwrap();
+        let _ = std::fs::remove_file(regression_file());
+
+        assert!(code.contains("fn inner_regression() -> "), "{}", code);
+        assert!(code.contains("    let a = 5;\n    let ref s = \"a\";\n"), "{}", code);
+        assert!(code.contains("prop_assert!(a < 5, \"{}\", s);"), "{}", code);
+    }
+}
+
+#[cfg(test)]
+mod async_tests {
+    use crate::std_facade::Box;
+    use crate::test_runner::{
+        AsyncRuntime, BasicAsyncRuntime, Config, TestCaseFuture,
+        TestCaseResult,
+    };
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static CASES_DRIVEN: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountingRuntime;
+    impl AsyncRuntime for CountingRuntime {
+        fn block_on(&self, future: TestCaseFuture<'_>) -> TestCaseResult {
+            CASES_DRIVEN.fetch_add(1, Ordering::SeqCst);
+            BasicAsyncRuntime.block_on(future)
+        }
+    }
+
+    fn counting_runtime() -> Box<dyn AsyncRuntime> {
+        Box::new(CountingRuntime)
+    }
+
+    async fn double(x: u32) -> u64 {
+        u64::from(x) * 2
+    }
+
+    proptest! {
+        #[test]
+        async fn async_in_args(a in 0u32..100, b in 0u32..100) {
+            prop_assert_eq!(double(a).await + double(b).await, double(a + b).await);
+        }
+
+        #[test]
+        async fn async_typed_args(a: u16, ref b in "[a-z]") {
+            prop_assume!(a > 0);
+            prop_assert!(double(u32::from(a)).await > 0);
+            prop_assert_eq!(1, b.len());
+        }
+
+        #[test]
+        async fn async_result(a in 0u32..100) -> Result<(), u64> {
+            let doubled = double(a).await;
+            if 1 == doubled % 2 {
+                return Err(doubled);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn async_body_is_driven_by_configured_runtime() {
+        proptest! {
+            #![proptest_config(Config {
+                cases: 10,
+                failure_persistence: None,
+                async_runtime: counting_runtime,
+                .. Config::default()
+            })]
+            async fn inner(_a in 0u32..10) {
+                double(1).await;
+            }
+        }
+
+        CASES_DRIVEN.store(0, Ordering::SeqCst);
+        inner();
+        assert_eq!(10, CASES_DRIVEN.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(expected = "a = 5")]
+    fn async_failures_shrink() {
+        proptest! {
+            #![proptest_config(Config {
+                failure_persistence: None,
+                .. Config::default()
+            })]
+            async fn inner(a in 0u32..1000) {
+                prop_assert!(double(a).await < 10);
+            }
+        }
+
+        inner();
+    }
+}
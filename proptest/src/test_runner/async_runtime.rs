@@ -0,0 +1,146 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::std_facade::Box;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::test_runner::errors::TestCaseResult;
+
+/// The future produced by the body of an `async fn` test case.
+pub type TestCaseFuture<'a> = Pin<Box<dyn Future<Output = TestCaseResult> + 'a>>;
+
+/// An executor which can drive the body of an `async fn` declared within
+/// `proptest!` to completion.
+///
+/// A fresh runtime is created (via `Config::async_runtime`) each time a test
+/// function starts, and each generated case, including every case run while
+/// shrinking, is driven to completion independently by a call to
+/// `block_on`. Since the runtime lives inside the test function, it is also
+/// created anew in the child process when forking is enabled.
+///
+/// Adapting an existing executor is usually a one-liner. For example, with
+/// tokio:
+///
+/// ```ignore
+/// struct Tokio(tokio::runtime::Runtime);
+///
+/// impl AsyncRuntime for Tokio {
+///     fn block_on(&self, future: TestCaseFuture<'_>) -> TestCaseResult {
+///         self.0.block_on(future)
+///     }
+/// }
+///
+/// fn tokio_runtime() -> Box<dyn AsyncRuntime> {
+///     Box::new(Tokio(
+///         tokio::runtime::Builder::new_current_thread()
+///             .enable_all()
+///             .build()
+///             .unwrap(),
+///     ))
+/// }
+/// ```
+///
+/// `async_std::task::block_on` and `smol::block_on` can be wrapped in the
+/// same way.
+pub trait AsyncRuntime {
+    /// Run `future` to completion on the current thread and return its
+    /// output.
+    fn block_on(&self, future: TestCaseFuture<'_>) -> TestCaseResult;
+}
+
+/// A minimal single-threaded executor.
+///
+/// With the `std` feature, the current thread is parked while the future is
+/// pending. Without it, the future is simply polled in a loop.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BasicAsyncRuntime;
+
+impl AsyncRuntime for BasicAsyncRuntime {
+    #[cfg(feature = "std")]
+    fn block_on(&self, mut future: TestCaseFuture<'_>) -> TestCaseResult {
+        use std::sync::Arc;
+        use std::task::Wake;
+        use std::thread::{self, Thread};
+
+        struct ThreadWaker(Thread);
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+
+            fn wake_by_ref(self: &Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = Arc::new(ThreadWaker(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(result) => return result,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn block_on(&self, mut future: TestCaseFuture<'_>) -> TestCaseResult {
+        let mut cx = Context::from_waker(core::task::Waker::noop());
+        loop {
+            if let Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+                return result;
+            }
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Creates a `BasicAsyncRuntime`.
+///
+/// This is the default value of `ProptestConfig.async_runtime`.
+pub fn basic_async_runtime() -> Box<dyn AsyncRuntime> {
+    Box::new(BasicAsyncRuntime)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_runner::TestCaseError;
+
+    struct YieldOnce(bool);
+    impl Future for YieldOnce {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn basic_runtime_drives_pending_futures() {
+        let runtime = basic_async_runtime();
+        let result = runtime.block_on(Box::pin(async {
+            YieldOnce(false).await;
+            Err(TestCaseError::fail("done"))
+        }));
+        match result {
+            Err(TestCaseError::Fail(reason)) => {
+                assert_eq!("done", reason.message())
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -9,7 +9,17 @@
 
 use crate::std_facade::Box;
 use core::{fmt, str, u32};
+#[cfg(feature = "std")]
+use core::time::Duration;
 
+use crate::test_runner::async_runtime::{basic_async_runtime, AsyncRuntime};
+use crate::test_runner::coverage::CoverageSource;
+use crate::test_runner::diff::{line_diff, DiffFormatter};
+use crate::test_runner::observer::{noop_observer, TestObserver};
+#[cfg(feature = "std")]
+use crate::test_runner::report::ReportFormat;
+#[cfg(feature = "std")]
+use crate::test_runner::regression_test::RegressionTestOutput;
 use crate::test_runner::result_cache::{noop_result_cache, ResultCache};
 use crate::test_runner::rng::RngAlgorithm;
 use crate::test_runner::FailurePersistence;
@@ -22,24 +32,33 @@ pub fn contextualize_config(mut result: Config) -> Config {
     use std::ffi::OsString;
     use std::fmt;
     use std::str::FromStr;
+    #[cfg(feature = "serde")]
+    use crate::test_runner::PersistenceFormat;
 
     const CASES: &str = "PROPTEST_CASES";
+    const MAX_DURATION: &str = "PROPTEST_MAX_DURATION";
 
     const MAX_LOCAL_REJECTS: &str = "PROPTEST_MAX_LOCAL_REJECTS";
     const MAX_GLOBAL_REJECTS: &str = "PROPTEST_MAX_GLOBAL_REJECTS";
     const MAX_FLAT_MAP_REGENS: &str = "PROPTEST_MAX_FLAT_MAP_REGENS";
     const MAX_SHRINK_TIME: &str = "PROPTEST_MAX_SHRINK_TIME";
     const MAX_SHRINK_ITERS: &str = "PROPTEST_MAX_SHRINK_ITERS";
+    const SHRINK_THREADS: &str = "PROPTEST_SHRINK_THREADS";
     const MAX_DEFAULT_SIZE_RANGE: &str = "PROPTEST_MAX_DEFAULT_SIZE_RANGE";
+    const SIZE_RAMP: &str = "PROPTEST_SIZE_RAMP";
     #[cfg(feature = "fork")]
     const FORK: &str = "PROPTEST_FORK";
-    #[cfg(feature = "timeout")]
     const TIMEOUT: &str = "PROPTEST_TIMEOUT";
     const VERBOSE: &str = "PROPTEST_VERBOSE";
+    const REPORT_FORMAT: &str = "PROPTEST_REPORT_FORMAT";
+    const REGRESSION_TEST: &str = "PROPTEST_REGRESSION_TEST";
     const RNG_ALGORITHM: &str = "PROPTEST_RNG_ALGORITHM";
     const RNG_SEED: &str = "PROPTEST_RNG_SEED";
     const DISABLE_FAILURE_PERSISTENCE: &str =
         "PROPTEST_DISABLE_FAILURE_PERSISTENCE";
+    const REGRESSIONS_ONLY: &str = "PROPTEST_REGRESSIONS_ONLY";
+    #[cfg(feature = "serde")]
+    const PERSISTENCE_FORMAT: &str = "PROPTEST_PERSISTENCE_FORMAT";
 
     fn parse_or_warn<T: FromStr + fmt::Display>(
         src: &OsString,
@@ -66,6 +85,9 @@ pub fn contextualize_config(mut result: Config) -> Config {
         }
     }
 
+    #[cfg(feature = "serde")]
+    let mut persistence_format = None;
+
     for (var, value) in
         env::vars_os().filter_map(|(k, v)| k.into_string().ok().map(|k| (k, v)))
     {
@@ -77,14 +99,35 @@ pub fn contextualize_config(mut result: Config) -> Config {
             continue;
         }
 
-        #[cfg(feature = "timeout")]
-        if var == TIMEOUT {
-            parse_or_warn(&value, &mut result.timeout, "timeout", TIMEOUT);
+        #[cfg(feature = "serde")]
+        if var == PERSISTENCE_FORMAT {
+            let mut format = PersistenceFormat::default();
+            parse_or_warn(
+                &value,
+                &mut format,
+                "PersistenceFormat",
+                PERSISTENCE_FORMAT,
+            );
+            persistence_format = Some(format);
             continue;
         }
 
-        if var == CASES {
+        if var == TIMEOUT {
+            parse_or_warn(&value, &mut result.timeout, "timeout", TIMEOUT);
+        } else if var == CASES {
             parse_or_warn(&value, &mut result.cases, "u32", CASES);
+        } else if var == MAX_DURATION {
+            match value.to_str().and_then(|v| v.parse::<u64>().ok()) {
+                Some(0) => result.max_duration = None,
+                Some(ms) => {
+                    result.max_duration = Some(Duration::from_millis(ms))
+                }
+                None => eprintln!(
+                    "proptest: The env-var {} must be a number of \
+                     milliseconds, using default of {:?}.",
+                    MAX_DURATION, result.max_duration
+                ),
+            }
         } else if var == MAX_LOCAL_REJECTS {
             parse_or_warn(
                 &value,
@@ -120,6 +163,13 @@ pub fn contextualize_config(mut result: Config) -> Config {
                 "u32",
                 MAX_SHRINK_ITERS,
             );
+        } else if var == SHRINK_THREADS {
+            parse_or_warn(
+                &value,
+                &mut result.shrink_threads,
+                "u32",
+                SHRINK_THREADS,
+            );
         } else if var == MAX_DEFAULT_SIZE_RANGE {
             parse_or_warn(
                 &value,
@@ -127,8 +177,24 @@ pub fn contextualize_config(mut result: Config) -> Config {
                 "usize",
                 MAX_DEFAULT_SIZE_RANGE,
             );
+        } else if var == SIZE_RAMP {
+            parse_or_warn(&value, &mut result.size_ramp, "bool", SIZE_RAMP);
         } else if var == VERBOSE {
             parse_or_warn(&value, &mut result.verbose, "u32", VERBOSE);
+        } else if var == REPORT_FORMAT {
+            parse_or_warn(
+                &value,
+                &mut result.report_format,
+                "ReportFormat",
+                REPORT_FORMAT,
+            );
+        } else if var == REGRESSION_TEST {
+            parse_or_warn(
+                &value,
+                &mut result.regression_test,
+                "RegressionTestOutput",
+                REGRESSION_TEST,
+            );
         } else if var == RNG_ALGORITHM {
             parse_or_warn(
                 &value,
@@ -145,11 +211,27 @@ pub fn contextualize_config(mut result: Config) -> Config {
             );
         } else if var == DISABLE_FAILURE_PERSISTENCE {
             result.failure_persistence = None;
+        } else if var == REGRESSIONS_ONLY {
+            match value.to_str() {
+                Some("1") | Some("true") => result.regressions_only = true,
+                Some("0") | Some("false") => result.regressions_only = false,
+                _ => eprintln!(
+                    "proptest: The env-var {} must be 0, 1, true or false, \
+                     using default of {}.",
+                    REGRESSIONS_ONLY, result.regressions_only
+                ),
+            }
         } else if var.starts_with("PROPTEST_") {
             eprintln!("proptest: Ignoring unknown env-var {}.", var);
         }
     }
 
+    #[cfg(feature = "serde")]
+    if let Some(format) = persistence_format {
+        result.failure_persistence =
+            result.failure_persistence.map(|p| format.apply(p));
+    }
+
     result
 }
 
@@ -162,6 +244,8 @@ pub fn contextualize_config(result: Config) -> Config {
 fn default_default_config() -> Config {
     Config {
         cases: 256,
+        #[cfg(feature = "std")]
+        max_duration: None,
         max_local_rejects: 65_536,
         max_global_rejects: 1024,
         max_flat_map_regens: 1_000_000,
@@ -170,15 +254,29 @@ fn default_default_config() -> Config {
         test_name: None,
         #[cfg(feature = "fork")]
         fork: false,
-        #[cfg(feature = "timeout")]
+        #[cfg(feature = "std")]
         timeout: 0,
         #[cfg(feature = "std")]
         max_shrink_time: 0,
         max_shrink_iters: u32::MAX,
+        #[cfg(feature = "std")]
+        shrink_threads: 0,
         max_default_size_range: 100,
+        size_ramp: false,
         result_cache: noop_result_cache,
+        async_runtime: basic_async_runtime,
+        observer: noop_observer,
+        diff_formatter: line_diff,
+        coverage_source: None,
+        targeted: false,
+        regressions_only: false,
+        expect_failure: false,
         #[cfg(feature = "std")]
         verbose: 0,
+        #[cfg(feature = "std")]
+        report_format: ReportFormat::Off,
+        #[cfg(feature = "std")]
+        regression_test: RegressionTestOutput::Off,
         rng_algorithm: RngAlgorithm::default(),
         rng_seed: RngSeed::Random,
         _non_exhaustive: (),
@@ -220,7 +318,7 @@ impl fmt::Display for RngSeed {
 }
 
 /// Configuration for how a proptest test should be run.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Config {
     /// The number of successful test cases that must execute for the test as a
     /// whole to pass.
@@ -232,6 +330,26 @@ pub struct Config {
     /// when the `std` feature is enabled, which it is by default.)
     pub cases: u32,
 
+    /// If set, run as many test cases as fit into this wall-clock budget
+    /// instead of a fixed number, so that the thoroughness of a test adapts
+    /// to the speed of the machine running it.
+    ///
+    /// `cases` is ignored while this is set. New cases are generated until
+    /// the budget is spent, though never before at least one case has
+    /// passed. Replaying persisted failures counts towards the budget;
+    /// shrinking a failure does not stop when it runs out (see
+    /// `max_shrink_time` for that).
+    ///
+    /// This configuration is only available when the `std` feature is enabled
+    /// (which it is by default), and has no effect on `wasm32`.
+    ///
+    /// The default is `None`, which can be overridden by setting the
+    /// `PROPTEST_MAX_DURATION` environment variable to a number of
+    /// milliseconds (`0` meaning `None`).
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub max_duration: Option<Duration>,
+
     /// The maximum number of individual inputs that may be rejected before the
     /// test as a whole aborts.
     ///
@@ -274,6 +392,12 @@ pub struct Config {
     /// with an environment variable. (The variable is
     /// only considered when the `std` feature is enabled, which it is by
     /// default.)
+    ///
+    /// With the `serde` feature, setting the `PROPTEST_PERSISTENCE_FORMAT`
+    /// environment variable to `json` (or `toml`, with the `toml` feature)
+    /// stores regressions in the same location through
+    /// [`StructuredFailurePersistence`](struct.StructuredFailurePersistence.html)
+    /// instead, and `text` selects the default format.
     pub failure_persistence: Option<Box<dyn FailurePersistence>>,
 
     /// File location of the current test, relevant for persistence
@@ -315,26 +439,35 @@ pub struct Config {
     #[cfg_attr(docsrs, doc(cfg(feature = "fork")))]
     pub fork: bool,
 
-    /// If non-zero, tests are run in a subprocess and each generated case
-    /// fails if it takes longer than this number of milliseconds.
+    /// If non-zero, each generated case fails if it takes longer than this
+    /// number of milliseconds.
+    ///
+    /// With the "timeout" feature (enabled by default), this implicitly
+    /// enables forking, even if the `fork` field is `false`, so that a case
+    /// which hangs can be killed and shrunk like any other failure. Setting a
+    /// timeout to less than the time it takes the process to start up and
+    /// initialise the first test case will cause the whole test to be
+    /// aborted.
     ///
-    /// This implicitly enables forking, even if the `fork` field is `false`.
+    /// Without that feature (for example where forking is unavailable), cases
+    /// are instead run in-process under a watchdog thread. A case which
+    /// overruns the timeout fails once it returns, and is shrunk as usual
+    /// (with each shrinking candidate subject to the same timeout). A case
+    /// which has still not returned after twice the timeout cannot be
+    /// interrupted, so the watchdog prints its input, and the test fails if
+    /// the case ever returns; other tests are unaffected. The watchdog is not
+    /// available on `wasm32`.
     ///
     /// The type here is plain `u32` (rather than
     /// `Option<std::time::Duration>`) for the sake of ergonomics.
     ///
-    /// This requires the "timeout" feature, enabled by default.
-    ///
-    /// Setting a timeout to less than the time it takes the process to start
-    /// up and initialise the first test case will cause the whole test to be
-    /// aborted.
+    /// This configuration is only available when the `std` feature is enabled
+    /// (which it is by default).
     ///
     /// The default is `0` (i.e., no timeout), which can be overridden by
-    /// setting the `PROPTEST_TIMEOUT` environment variable. (The variable is
-    /// only considered when the `std` feature is enabled, which it is by
-    /// default.)
-    #[cfg(feature = "timeout")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "timeout")))]
+    /// setting the `PROPTEST_TIMEOUT` environment variable.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     pub timeout: u32,
 
     /// If non-zero, give up the shrinking process after this many milliseconds
@@ -369,6 +502,20 @@ pub struct Config {
     /// considered when the `std` feature is enabled, which it is by default.)
     pub max_shrink_iters: u32,
 
+    /// The number of threads `TestRunner::run_with_parallel_shrinking` uses
+    /// to evaluate shrinking candidates. `0` means to use one thread per
+    /// available CPU, and `1` shrinks sequentially as `TestRunner::run`
+    /// does.
+    ///
+    /// This configuration is only available when the `std` feature is enabled
+    /// (which it is by default).
+    ///
+    /// The default is `0`, which can be overridden by setting the
+    /// `PROPTEST_SHRINK_THREADS` environment variable.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub shrink_threads: u32,
+
     /// The default maximum size to `proptest::collection::SizeRange`. The default
     /// strategy for collections (like `Vec`) use collections in the range of
     /// `0..max_default_size_range`.
@@ -379,6 +526,26 @@ pub struct Config {
     /// default.)
     pub max_default_size_range: usize,
 
+    /// If true, the size of generated inputs (see `TestRunner::size`) starts
+    /// at 0 and grows with each passing case, reaching the full size halfway
+    /// through `cases`.
+    ///
+    /// Collections (including those generated by regular expression
+    /// repetitions in string strategies) and recursive strategies scale their
+    /// maximum length or depth by the size, so the first cases are the
+    /// simplest allowed by each strategy. Early failures thus tend to have
+    /// small, readable inputs even before shrinking, while later cases still
+    /// exercise large ones.
+    ///
+    /// The size of a failing case is persisted along with its seed, so that
+    /// it is reproduced exactly.
+    ///
+    /// The default is `false`, which can be overridden by setting the
+    /// `PROPTEST_SIZE_RAMP` environment variable to `true`. (The variable is
+    /// only considered when the `std` feature is enabled, which it is by
+    /// default.)
+    pub size_ramp: bool,
+
     /// A function to create new result caches.
     ///
     /// The default is to do no caching. The easiest way to enable caching is
@@ -397,6 +564,94 @@ pub struct Config {
     /// more slowly.
     pub result_cache: fn() -> Box<dyn ResultCache>,
 
+    /// A function to create the runtime which drives `async fn` tests
+    /// declared within `proptest!`.
+    ///
+    /// A new runtime is created each time such a test function starts, and is
+    /// used to run every generated case (including those run while shrinking)
+    /// to completion. It is unused for non-`async` tests.
+    ///
+    /// The default is `basic_async_runtime`, a minimal executor which is
+    /// sufficient for futures that do not depend on a particular reactor. To
+    /// use tokio, async-std, smol, etc, implement `AsyncRuntime` for a
+    /// wrapper around that executor and set this field to a function
+    /// constructing it.
+    pub async_runtime: fn() -> Box<dyn AsyncRuntime>,
+
+    /// A function to create an observer which is notified of each case
+    /// started, passed, rejected or failed, of each shrinking step, and of
+    /// the minimal failing input once shrinking is done.
+    ///
+    /// A new observer is created each time a test run starts. See
+    /// `TestObserver` for details.
+    ///
+    /// The default is `noop_observer`, which ignores all events.
+    pub observer: fn() -> Box<dyn TestObserver>,
+
+    /// A function to create the formatter describing how the values compared
+    /// by a failing `prop_assert_eq!` differ, when they are too long to be
+    /// compared at a glance.
+    ///
+    /// See `DiffFormatter` for details. The formatter only applies to
+    /// assertions made on the thread running the test case.
+    ///
+    /// The default is `line_diff`, which lists the lines of the
+    /// pretty-printed values which differ. Use `no_diff` to always show both
+    /// values in full.
+    pub diff_formatter: fn() -> Box<dyn DiffFormatter>,
+
+    /// If set, a function to create the source of edge coverage counters used
+    /// to guide input generation.
+    ///
+    /// The counters are reset before and read after each test case. Inputs
+    /// reaching new coverage are kept in a corpus, and subsequent cases are
+    /// usually generated by mutating the random bytes a corpus entry was
+    /// generated from rather than from scratch. See `CoverageSource` for
+    /// details.
+    ///
+    /// Coverage guidance is not applied when forking, since the parent
+    /// process cannot observe the counters of the child.
+    ///
+    /// The default is `None`, i.e., blind random generation.
+    pub coverage_source: Option<fn() -> Box<dyn CoverageSource>>,
+
+    /// If true, search for inputs which maximise the utility the test reports
+    /// through `report_utility`, rather than generating every input
+    /// independently.
+    ///
+    /// Each input is generated from a buffer of random bytes, and once a
+    /// case reports a utility, later inputs are derived by mutating the bytes
+    /// of the current best input, accepting worse ones by simulated annealing
+    /// with a temperature that falls over the course of `cases`.
+    ///
+    /// Like coverage guidance, this is not applied when forking, and requires
+    /// the `std` feature for `report_utility` to be available.
+    ///
+    /// The default is `false`.
+    pub targeted: bool,
+
+    /// If true, only the failures loaded from `failure_persistence` are
+    /// replayed, and no new cases are generated. This makes for a fast check
+    /// that previously found bugs are still fixed.
+    ///
+    /// The default is `false`, which can be overridden by setting the
+    /// `PROPTEST_REGRESSIONS_ONLY` environment variable to `1` or `true`.
+    /// (The variable is only considered when the `std` feature is enabled,
+    /// which it is by default.)
+    pub regressions_only: bool,
+
+    /// If true, the test is expected to find a failing case: the run passes
+    /// once a case fails, and fails if all `cases` pass.
+    ///
+    /// This is useful for checking that a detector, validator, etc, actually
+    /// rejects some bad inputs. The failing case is still shrunk as usual,
+    /// and the minimal failing input is printed to stderr (when the `std`
+    /// feature is enabled). Failures are not persisted, since they are the
+    /// expected outcome.
+    ///
+    /// The default is `false`.
+    pub expect_failure: bool,
+
     /// Set to non-zero values to cause proptest to emit human-targeted
     /// messages to stderr as it runs.
     ///
@@ -404,7 +659,8 @@ pub struct Config {
     /// meaning of certain levels other than 0 is subject to change.
     ///
     /// - 0: No extra output.
-    /// - 1: Log test failure messages. In state machine tests, this level is
+    /// - 1: Log test failure messages, and the share of local rejects by
+    ///   reason once the test passes. In state machine tests, this level is
     ///   used to print transitions.
     /// - 2: Trace low-level details.
     ///
@@ -418,6 +674,42 @@ pub struct Config {
     #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     pub verbose: u32,
 
+    /// Selects a machine-readable report which the runner writes to stderr
+    /// once each test finishes, giving the outcome, the number of cases run
+    /// and rejected, the number of shrink iterations, and, on failure, the
+    /// minimal failing input and the seed which produced it. See
+    /// `ReportFormat` for the available formats.
+    ///
+    /// This is only available with the `std` feature (enabled by default).
+    ///
+    /// The default is `ReportFormat::Off`, which can be overridden by
+    /// setting the `PROPTEST_REPORT_FORMAT` environment variable to `off`,
+    /// `json` or `junit`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub report_format: ReportFormat,
+
+    /// Selects where, once a test defined with `proptest!` fails, to write a
+    /// `#[test]` function which binds the arguments to the minimal failing
+    /// input and runs the body of the test. This allows keeping the
+    /// regression as a plain unit test, which unlike a persisted seed still
+    /// reproduces the failure after the strategies change.
+    ///
+    /// Values are written as their `Debug` representations, which is valid
+    /// Rust for numbers, `bool`s, `char`s and tuples and structs of those, but
+    /// may need editing for other types (e.g. `String`s become string
+    /// literals). `async` tests and closure-style invocations are not
+    /// supported. See `RegressionTestOutput` for the available outputs.
+    ///
+    /// This is only available with the `std` feature (enabled by default).
+    ///
+    /// The default is `RegressionTestOutput::Off`, which can be overridden by
+    /// setting the `PROPTEST_REGRESSION_TEST` environment variable to `off`,
+    /// `stderr`, or the path of a file to append to.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub regression_test: RegressionTestOutput,
+
     /// The RNG algorithm to use when not using a user-provided RNG.
     ///
     /// The default is `RngAlgorithm::default()`, which can be overridden by
@@ -439,6 +731,49 @@ pub struct Config {
     pub _non_exhaustive: (),
 }
 
+/// Compares every field except `result_cache`, `async_runtime`, `observer`,
+/// `diff_formatter` and `coverage_source`.
+///
+/// Function pointers have no meaningful equality, since one function may
+/// have several addresses and distinct functions may share one, so those
+/// fields are not considered.
+impl PartialEq for Config {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(feature = "std")]
+        let std_eq = self.max_duration == other.max_duration
+            && self.timeout == other.timeout
+            && self.max_shrink_time == other.max_shrink_time
+            && self.shrink_threads == other.shrink_threads
+            && self.verbose == other.verbose
+            && self.report_format == other.report_format
+            && self.regression_test == other.regression_test;
+        #[cfg(not(feature = "std"))]
+        let std_eq = true;
+        #[cfg(feature = "fork")]
+        let fork_eq = self.fork == other.fork;
+        #[cfg(not(feature = "fork"))]
+        let fork_eq = true;
+
+        std_eq
+            && fork_eq
+            && self.cases == other.cases
+            && self.max_local_rejects == other.max_local_rejects
+            && self.max_global_rejects == other.max_global_rejects
+            && self.max_flat_map_regens == other.max_flat_map_regens
+            && self.failure_persistence == other.failure_persistence
+            && self.source_file == other.source_file
+            && self.test_name == other.test_name
+            && self.max_shrink_iters == other.max_shrink_iters
+            && self.max_default_size_range == other.max_default_size_range
+            && self.size_ramp == other.size_ramp
+            && self.targeted == other.targeted
+            && self.regressions_only == other.regressions_only
+            && self.expect_failure == other.expect_failure
+            && self.rng_algorithm == other.rng_algorithm
+            && self.rng_seed == other.rng_seed
+    }
+}
+
 impl Config {
     /// Constructs a `Config` only differing from the `default()` in the
     /// number of test cases required to pass the test successfully.
@@ -536,7 +871,7 @@ impl Config {
     /// This method exists even if the "fork" feature is disabled, in which
     /// case it simply returns false.
     pub fn fork(&self) -> bool {
-        self._fork() || self.timeout() > 0
+        self._fork() || (cfg!(feature = "timeout") && self.timeout() > 0)
     }
 
     #[cfg(feature = "fork")]
@@ -551,18 +886,18 @@ impl Config {
 
     /// Returns the configured timeout.
     ///
-    /// This method exists even if the "timeout" feature is disabled, in which
+    /// This method exists even if the "std" feature is disabled, in which
     /// case it simply returns 0.
-    #[cfg(feature = "timeout")]
+    #[cfg(feature = "std")]
     pub fn timeout(&self) -> u32 {
         self.timeout
     }
 
     /// Returns the configured timeout.
     ///
-    /// This method exists even if the "timeout" feature is disabled, in which
+    /// This method exists even if the "std" feature is disabled, in which
     /// case it simply returns 0.
-    #[cfg(not(feature = "timeout"))]
+    #[cfg(not(feature = "std"))]
     pub fn timeout(&self) -> u32 {
         0
     }
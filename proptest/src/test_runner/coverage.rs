@@ -0,0 +1,341 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support for coverage-guided generation.
+//!
+//! When `Config::coverage_source` is set, the runner reads a set of edge
+//! counters after each test case. Inputs which exercise an edge not seen
+//! before, or which exercise a known edge a number of times falling into a
+//! new bucket (1, 2, 3, 4-7, 8-15, 16-31, 32-127, 128+, as in libFuzzer),
+//! are added to a corpus.
+//!
+//! As in targeted mode, each input is generated from an explicit buffer of
+//! random bytes via the `PassThrough` RNG. Subsequent cases are generated
+//! either from fresh bytes or by mutating the bytes of a corpus entry:
+//! changing, inserting and erasing bytes, or splicing in bytes from another
+//! entry. Since strategies consume the buffer sequentially, this explores
+//! inputs "near" interesting ones in every direction, including ones more
+//! complex than any seen so far, and a failure found this way is reproduced
+//! from its bytes like any other persisted seed.
+
+use crate::std_facade::{Arc, BTreeMap, Box, Vec};
+use core::fmt;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use rand::Rng;
+
+use crate::test_runner::rng::{Seed, TestRng};
+
+/// The number of random bytes each coverage-guided input is generated from.
+const INPUT_LEN: usize = 4096;
+
+/// Byte values which often sit on the boundary of a branch.
+const INTERESTING_BYTES: [u8; 6] = [0, 1, 0x7f, 0x80, 0xfe, 0xff];
+
+/// A source of edge coverage counters, such as those maintained by
+/// `-C instrument-coverage`, `-C passes=sancov-module` with
+/// `-C llvm-args=-sanitizer-coverage-inline-8bit-counters`, or manual
+/// instrumentation.
+pub trait CoverageSource {
+    /// Zero all counters. Called immediately before each test case runs.
+    fn reset(&mut self);
+
+    /// Invoke `f` with the index and counter value of every edge hit since
+    /// the last call to `reset`. Edges with a zero counter may be skipped.
+    fn for_each_hit(&self, f: &mut dyn FnMut(usize, u8));
+}
+
+/// A fixed-size table of shared 8-bit edge counters.
+///
+/// Clones share the same counters, so one clone can be handed to the code
+/// under test (or to sanitizer coverage callbacks) while another is returned
+/// from the function in `Config::coverage_source`.
+///
+/// ```
+/// use std::sync::LazyLock;
+/// use proptest::test_runner::{CoverageCounters, CoverageSource};
+///
+/// static EDGES: LazyLock<CoverageCounters> =
+///     LazyLock::new(|| CoverageCounters::new(64));
+///
+/// fn coverage() -> Box<dyn CoverageSource> {
+///     Box::new(EDGES.clone())
+/// }
+///
+/// fn code_under_test(x: u32) {
+///     if x > 1000 {
+///         EDGES.hit(0);
+///     }
+/// }
+/// # let _ = (coverage, code_under_test);
+/// ```
+#[derive(Clone)]
+pub struct CoverageCounters {
+    counters: Arc<[AtomicU8]>,
+}
+
+impl CoverageCounters {
+    /// Create a table of `len` counters, all initially zero.
+    pub fn new(len: usize) -> Self {
+        Self {
+            counters: (0..len).map(|_| AtomicU8::new(0)).collect(),
+        }
+    }
+
+    /// Record a hit on edge `index`, saturating at 255.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn hit(&self, index: usize) {
+        let _ = self.counters[index].fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |n| n.checked_add(1),
+        );
+    }
+
+    /// Return the number of counters in this table.
+    pub fn len(&self) -> usize {
+        self.counters.len()
+    }
+
+    /// Return whether this table has no counters.
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty()
+    }
+}
+
+impl fmt::Debug for CoverageCounters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CoverageCounters")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl CoverageSource for CoverageCounters {
+    fn reset(&mut self) {
+        for counter in self.counters.iter() {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    fn for_each_hit(&self, f: &mut dyn FnMut(usize, u8)) {
+        for (index, counter) in self.counters.iter().enumerate() {
+            let count = counter.load(Ordering::Relaxed);
+            if count > 0 {
+                f(index, count);
+            }
+        }
+    }
+}
+
+/// Map a hit count onto a bit identifying its libFuzzer-style bucket.
+fn bucket(count: u8) -> u8 {
+    match count {
+        0 => 0,
+        1 => 1 << 0,
+        2 => 1 << 1,
+        3 => 1 << 2,
+        4..=7 => 1 << 3,
+        8..=15 => 1 << 4,
+        16..=31 => 1 << 5,
+        32..=127 => 1 << 6,
+        128..=255 => 1 << 7,
+    }
+}
+
+/// An input which reached new coverage.
+struct CorpusEntry {
+    data: Arc<[u8]>,
+    /// How many bytes from the front of `data` were drawn directly by the
+    /// strategy.
+    consumed: usize,
+}
+
+/// Coverage feedback accumulated over a single test run.
+pub(crate) struct CoverageState {
+    source: Box<dyn CoverageSource>,
+    /// For each edge seen so far, the set of buckets seen.
+    seen: BTreeMap<usize, u8>,
+    /// Inputs which reached new coverage.
+    corpus: Vec<CorpusEntry>,
+}
+
+impl CoverageState {
+    pub(crate) fn new(source: Box<dyn CoverageSource>) -> Self {
+        Self {
+            source,
+            seen: BTreeMap::new(),
+            corpus: Vec::new(),
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.source.reset();
+    }
+
+    /// Fold the counters from the last case into the accumulated coverage,
+    /// returning whether anything new was reached.
+    pub(crate) fn observe(&mut self) -> bool {
+        let seen = &mut self.seen;
+        let mut new_coverage = false;
+        self.source.for_each_hit(&mut |index, count| {
+            let buckets = seen.entry(index).or_insert(0);
+            let bit = bucket(count);
+            if *buckets & bit == 0 {
+                *buckets |= bit;
+                new_coverage = true;
+            }
+        });
+        new_coverage
+    }
+
+    /// Return the number of inputs in the corpus.
+    pub(crate) fn corpus_len(&self) -> usize {
+        self.corpus.len()
+    }
+
+    /// Add the input generated from `seed`, which consumed `consumed` bytes,
+    /// to the corpus. Seeds not produced by `next_input` are ignored.
+    pub(crate) fn add(&mut self, seed: &Seed, consumed: Option<usize>) {
+        match *seed {
+            Seed::PassThrough(None, ref data) if data.len() == INPUT_LEN => {
+                self.corpus.push(CorpusEntry {
+                    data: Arc::clone(data),
+                    consumed: consumed.unwrap_or(0).min(INPUT_LEN),
+                })
+            }
+            _ => (),
+        }
+    }
+
+    /// Choose the seed for the next input: usually a mutation of a random
+    /// corpus entry, but sometimes fresh random bytes.
+    pub(crate) fn next_input(&self, rng: &mut TestRng) -> Seed {
+        let mut data = vec![0u8; INPUT_LEN];
+        if self.corpus.is_empty() || rng.random_bool(0.25) {
+            rng.fill(&mut data[..]);
+        } else {
+            let entry = &self.corpus[rng.random_range(0..self.corpus.len())];
+            data.copy_from_slice(&entry.data);
+            for _ in 0..rng.random_range(1..=4) {
+                self.mutate(&mut data, entry.consumed, rng);
+            }
+        }
+        Seed::PassThrough(None, data.into())
+    }
+
+    /// Apply one random mutation to `data`, of which the strategy drew the
+    /// first `consumed` bytes.
+    fn mutate(&self, data: &mut [u8], consumed: usize, rng: &mut TestRng) {
+        // Mostly mutate the bytes the strategy is known to have drawn, but
+        // sometimes anywhere, since sub-RNGs draw from the back.
+        let end = if consumed > 0 && rng.random_bool(0.9) {
+            consumed
+        } else {
+            INPUT_LEN
+        };
+        let ix = rng.random_range(0..end);
+        match rng.random_range(0..8) {
+            0 => data[ix] = data[ix].wrapping_add(rng.random_range(1..=8)),
+            1 => data[ix] = data[ix].wrapping_sub(rng.random_range(1..=8)),
+            2 => data[ix] ^= 1 << rng.random_range(0..8),
+            3 => {
+                data[ix] = INTERESTING_BYTES
+                    [rng.random_range(0..INTERESTING_BYTES.len())]
+            }
+            // Shifting the following bytes lets, for example, a collection
+            // gain or lose an element without disturbing the others.
+            4 => {
+                data[ix..end].rotate_right(1);
+                data[ix] = rng.random();
+            }
+            5 => data[ix..end].rotate_left(1),
+            6 => {
+                let other =
+                    &self.corpus[rng.random_range(0..self.corpus.len())];
+                let len = rng.random_range(1..=(end - ix).min(64));
+                data[ix..ix + len].copy_from_slice(&other.data[ix..ix + len]);
+            }
+            _ => data[ix] = rng.random(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new_buckets_count_as_new_coverage() {
+        let counters = CoverageCounters::new(4);
+        let mut state = CoverageState::new(Box::new(counters.clone()));
+
+        state.reset();
+        assert!(!state.observe());
+
+        state.reset();
+        counters.hit(2);
+        assert!(state.observe());
+
+        state.reset();
+        counters.hit(2);
+        assert!(!state.observe());
+
+        state.reset();
+        counters.hit(2);
+        counters.hit(2);
+        assert!(state.observe());
+
+        state.reset();
+        for _ in 0..300 {
+            counters.hit(3);
+        }
+        assert!(state.observe());
+    }
+
+    #[test]
+    fn inputs_are_mutated_from_the_corpus() {
+        let mut rng = TestRng::deterministic_rng(Default::default());
+        let mut state = CoverageState::new(Box::new(CoverageCounters::new(1)));
+
+        let first = state.next_input(&mut rng);
+        state.add(&first, Some(16));
+        assert_eq!(1, state.corpus_len());
+
+        let base = match first {
+            Seed::PassThrough(None, ref data) => Arc::clone(data),
+            _ => panic!("coverage inputs should be pass-through seeds"),
+        };
+        let mut mutated = 0;
+        for _ in 0..100 {
+            match state.next_input(&mut rng) {
+                Seed::PassThrough(None, ref data) => {
+                    assert_eq!(INPUT_LEN, data.len());
+                    // A mutation leaves most of the base input intact.
+                    let same = data
+                        .iter()
+                        .zip(base.iter())
+                        .filter(|(a, b)| a == b)
+                        .count();
+                    if same > INPUT_LEN / 2 && **data != *base {
+                        mutated += 1;
+                    }
+                }
+                _ => panic!("coverage inputs should be pass-through seeds"),
+            }
+        }
+        assert!(mutated > 50, "only {} mutated inputs", mutated);
+
+        state.add(&Seed::ChaCha([0; 32]), None);
+        assert_eq!(1, state.corpus_len());
+    }
+}
@@ -0,0 +1,333 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support for showing how the values compared by a failing
+//! `prop_assert_eq!` differ.
+//!
+//! The runner installs the formatter selected by `Config::diff_formatter`
+//! for the current thread while a test case runs, where `prop_assert_eq!`
+//! picks it up to describe values too long to compare at a glance.
+
+use crate::std_facade::{fmt, Box, String, Vec};
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use std::cell::Cell;
+
+/// Values whose `Debug` representations are both at most this long are
+/// shown in full rather than diffed.
+const INLINE_LEN: usize = 80;
+
+/// The number of unchanged lines shown around each change by `LineDiff`.
+const CONTEXT: usize = 2;
+
+/// Above this many pairs of lines compared, `LineDiff` shows the lines
+/// between the common prefix and suffix as entirely replaced instead.
+const MAX_COMPARISONS: usize = 1 << 22;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static FORMATTER: Cell<Option<fn() -> Box<dyn DiffFormatter>>> =
+        const { Cell::new(None) };
+}
+
+/// Describes how the two values compared by a failing `prop_assert_eq!`
+/// differ.
+///
+/// A formatter is created (via `Config::diff_formatter`) each time an
+/// assertion on values too long to show inline fails.
+///
+/// ```
+/// use proptest::test_runner::DiffFormatter;
+///
+/// /// Shows only the first line which differs.
+/// struct FirstDifference;
+///
+/// impl DiffFormatter for FirstDifference {
+///     fn diff(&self, left: &str, right: &str) -> Option<String> {
+///         left.lines()
+///             .zip(right.lines())
+///             .find(|(l, r)| l != r)
+///             .map(|(l, r)| format!("- {}\n+ {}", l, r))
+///     }
+/// }
+///
+/// fn first_difference() -> Box<dyn DiffFormatter> {
+///     Box::new(FirstDifference)
+/// }
+/// # let _ = first_difference;
+/// ```
+pub trait DiffFormatter {
+    /// Describe how `left` and `right`, the pretty-printed (`{:#?}`) `Debug`
+    /// representations of two unequal values, differ, or return `None` to
+    /// show both values in full instead.
+    fn diff(&self, left: &str, right: &str) -> Option<String>;
+}
+
+/// A `DiffFormatter` listing the lines which differ between the two values,
+/// prefixed by `-` for the left value and `+` for the right one, with a few
+/// unchanged lines around each change.
+///
+/// Values which are each printed on a single line are shown in full.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LineDiff;
+
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+impl DiffFormatter for LineDiff {
+    fn diff(&self, left: &str, right: &str) -> Option<String> {
+        let left: Vec<&str> = left.lines().collect();
+        let right: Vec<&str> = right.lines().collect();
+        if left.len() < 2 && right.len() < 2 {
+            return None;
+        }
+
+        let lines = diff_lines(&left, &right);
+        let changed = |i: usize| {
+            matches!(lines.get(i), Some(Line::Removed(_) | Line::Added(_)))
+        };
+        let near_change =
+            |i: usize| (i.saturating_sub(CONTEXT)..=i + CONTEXT).any(&changed);
+
+        let mut out = String::new();
+        let mut elided = false;
+        for (i, line) in lines.iter().enumerate() {
+            let (prefix, text) = match *line {
+                Line::Same(text) => ("  ", text),
+                Line::Removed(text) => ("- ", text),
+                Line::Added(text) => ("+ ", text),
+            };
+            if near_change(i) {
+                let _ = writeln!(out, "{}{}", prefix, text);
+                elided = false;
+            } else if !elided {
+                out.push_str("  ...\n");
+                elided = true;
+            }
+        }
+        out.pop();
+        Some(out)
+    }
+}
+
+/// Compute a shortest edit from `left` to `right`, as each line of either
+/// marked as unchanged, removed or added.
+fn diff_lines<'a>(left: &[&'a str], right: &[&'a str]) -> Vec<Line<'a>> {
+    let prefix = left.iter().zip(right).take_while(|(l, r)| l == r).count();
+    let suffix = left[prefix..]
+        .iter()
+        .rev()
+        .zip(right[prefix..].iter().rev())
+        .take_while(|(l, r)| l == r)
+        .count();
+    let a = &left[prefix..left.len() - suffix];
+    let b = &right[prefix..right.len() - suffix];
+
+    let mut lines: Vec<Line<'a>> =
+        left[..prefix].iter().map(|&l| Line::Same(l)).collect();
+    if a.len().saturating_mul(b.len()) > MAX_COMPARISONS {
+        lines.extend(a.iter().map(|&l| Line::Removed(l)));
+        lines.extend(b.iter().map(|&r| Line::Added(r)));
+    } else {
+        // `lcs[i * width + j]` is the length of the longest common
+        // subsequence of `a[i..]` and `b[j..]`.
+        let width = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * width + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                lines.push(Line::Same(a[i]));
+                i += 1;
+                j += 1;
+            } else if j == b.len()
+                || (i < a.len()
+                    && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+            {
+                lines.push(Line::Removed(a[i]));
+                i += 1;
+            } else {
+                lines.push(Line::Added(b[j]));
+                j += 1;
+            }
+        }
+    }
+    lines.extend(left[left.len() - suffix..].iter().map(|&l| Line::Same(l)));
+    lines
+}
+
+/// A `DiffFormatter` which always shows both values in full.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoDiff;
+
+impl DiffFormatter for NoDiff {
+    fn diff(&self, _: &str, _: &str) -> Option<String> {
+        None
+    }
+}
+
+/// A formatter listing the lines which differ.
+///
+/// This is the default value of `ProptestConfig.diff_formatter`.
+pub fn line_diff() -> Box<dyn DiffFormatter> {
+    Box::new(LineDiff)
+}
+
+/// A formatter which always shows both values in full.
+pub fn no_diff() -> Box<dyn DiffFormatter> {
+    Box::new(NoDiff)
+}
+
+/// Run `f` with `formatter` describing the values of failing
+/// `prop_assert_eq!`s on this thread.
+#[cfg(feature = "std")]
+pub(crate) fn with_diff_formatter<R>(
+    formatter: fn() -> Box<dyn DiffFormatter>,
+    f: impl FnOnce() -> R,
+) -> R {
+    struct Restore(Option<fn() -> Box<dyn DiffFormatter>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            FORMATTER.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore =
+        Restore(FORMATTER.with(|current| current.replace(Some(formatter))));
+    f()
+}
+
+fn current_formatter() -> Box<dyn DiffFormatter> {
+    #[cfg(feature = "std")]
+    {
+        FORMATTER.with(Cell::get).unwrap_or(line_diff)()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        line_diff()
+    }
+}
+
+/// Describe the unequal values compared by `prop_assert_eq!`, either in full
+/// or as the diff from the current formatter if they are long.
+#[doc(hidden)]
+pub fn describe_unequal(
+    left: &dyn fmt::Debug,
+    right: &dyn fmt::Debug,
+) -> String {
+    let mut out = String::new();
+    let (compact_left, compact_right) =
+        (debug(left, false), debug(right, false));
+    if compact_left.len() > INLINE_LEN || compact_right.len() > INLINE_LEN {
+        let diff =
+            current_formatter().diff(&debug(left, true), &debug(right, true));
+        if let Some(diff) = diff {
+            let _ = write!(out, "\n diff (- left, + right):\n{}", diff);
+            return out;
+        }
+    }
+    let _ = write!(
+        out,
+        "\n  left: `{}`,\n right: `{}`",
+        compact_left, compact_right
+    );
+    out
+}
+
+fn debug(value: &dyn fmt::Debug, pretty: bool) -> String {
+    let mut s = String::new();
+    let _ = if pretty {
+        write!(s, "{:#?}", value)
+    } else {
+        write!(s, "{:?}", value)
+    };
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Model {
+        name: &'static str,
+        items: Vec<u32>,
+    }
+
+    fn model(changed: u32) -> Model {
+        Model {
+            name: "a model with a rather long name to exceed the limit",
+            items: (0..20).map(|i| if 10 == i { changed } else { i }).collect(),
+        }
+    }
+
+    #[test]
+    fn shows_short_values_in_full() {
+        assert_eq!("\n  left: `1`,\n right: `2`", describe_unequal(&1, &2));
+    }
+
+    #[test]
+    fn diffs_long_values() {
+        let expected = [
+            "",
+            " diff (- left, + right):",
+            "  ...",
+            "          8,",
+            "          9,",
+            "-         10,",
+            "+         99,",
+            "          11,",
+            "          12,",
+            "  ...",
+        ];
+        assert_eq!(
+            expected.join("\n"),
+            describe_unequal(&model(10), &model(99))
+        );
+    }
+
+    #[test]
+    fn diffs_inserted_and_removed_lines() {
+        let diff = LineDiff.diff("a\nb\nc\nd", "a\nc\nd\ne").unwrap();
+        assert_eq!("  a\n- b\n  c\n  d\n+ e", diff);
+    }
+
+    #[test]
+    fn uses_installed_formatter() {
+        let description = with_diff_formatter(no_diff, || {
+            describe_unequal(&model(10), &model(99))
+        });
+        assert!(
+            description.starts_with("\n  left: `Model {"),
+            "{}",
+            description
+        );
+
+        let description = describe_unequal(&model(10), &model(99));
+        assert!(description.starts_with("\n diff"), "{}", description);
+    }
+
+    #[test]
+    fn diffs_lines_of_single_line_values_in_full() {
+        assert_eq!(None, LineDiff.diff(&"x".repeat(100), "y"));
+    }
+}
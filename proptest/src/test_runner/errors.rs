@@ -133,6 +133,32 @@ impl<T: fmt::Debug> ::std::error::Error for TestError<T> {
     }
 }
 
+/// Statistics on a `TestRunner` at the point a run finished.
+///
+/// The counts are cumulative over every run made with the same runner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RunStats {
+    /// The number of cases which passed.
+    pub successes: u32,
+    /// The number of cases rejected with `TestCaseError::Reject`, for
+    /// example through `prop_assume!`.
+    pub global_rejects: u32,
+    /// The number of values rejected by filters while being generated.
+    pub local_rejects: u32,
+    /// The number of candidates tested while shrinking failing cases.
+    pub shrink_iterations: u32,
+}
+
+/// The minimal failing input found by `TestRunner::run_with_counterexample`,
+/// together with statistics on the run which found and shrank it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterexample<T> {
+    /// The minimal input found to reproduce the failure.
+    pub value: T,
+    /// Statistics on the run as of the failure being minimized.
+    pub stats: RunStats,
+}
+
 mod private {
     pub trait Sealed {}
 
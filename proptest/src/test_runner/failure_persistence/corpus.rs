@@ -0,0 +1,224 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::any::Any;
+use core::fmt::Debug;
+use std::boxed::Box;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::Hasher;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use crate::test_runner::failure_persistence::file::PERSISTENCE_LOCK;
+use crate::test_runner::failure_persistence::{
+    FailurePersistence, PersistedSeed,
+};
+use crate::test_runner::rng::Seed;
+
+/// The extension of corpus files holding a seed in the textual persistence
+/// format, rather than raw input bytes.
+const SEED_EXTENSION: &str = "seed";
+
+/// Failure persistence which stores each failing case in its own file under
+/// a corpus directory, in the manner of `cargo fuzz`.
+///
+/// Failures found with the `PassThrough` RNG (including those from targeted
+/// search) are written as the raw bytes the input was generated from, which
+/// is exactly the form a fuzzer's corpus takes. Other seeds are written in the
+/// usual textual form to a file with a `.seed` extension.
+///
+/// When loading, every `.seed` file is parsed as a seed, and every other
+/// regular file is treated as raw input bytes and replayed through the
+/// `PassThrough` RNG. A corpus directory built by a fuzz target whose input
+/// is fed through the same strategy can thus be shared with a property test
+/// directly. Files are loaded in order of name.
+///
+/// The directory is used as given, regardless of the source file of the
+/// test, so tests which should not share a corpus need separate
+/// directories. It is created if it does not exist.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CorpusFailurePersistence {
+    /// The corpus directory.
+    pub directory: &'static str,
+}
+
+impl CorpusFailurePersistence {
+    /// Store failing cases under `directory`.
+    pub fn new(directory: &'static str) -> Self {
+        CorpusFailurePersistence { directory }
+    }
+
+    fn entries(&self) -> io::Result<Vec<PathBuf>> {
+        let mut entries = fs::read_dir(self.directory)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        entries.retain(|path| path.is_file());
+        entries.sort();
+        Ok(entries)
+    }
+
+    fn load_entry(path: &Path) -> io::Result<Option<PersistedSeed>> {
+        let data = fs::read(path)?;
+        if path.extension().map_or(false, |ext| ext == SEED_EXTENSION) {
+            let seed = std::str::from_utf8(&data)
+                .ok()
+                .and_then(|text| text.trim().parse::<PersistedSeed>().ok());
+            if seed.is_none() {
+                eprintln!(
+                    "proptest: {}: unparsable seed, ignoring",
+                    path.display()
+                );
+            }
+            Ok(seed)
+        } else {
+            Ok(Some(PersistedSeed(Seed::PassThrough(None, data.into()), None)))
+        }
+    }
+}
+
+/// Return the contents and file name for `seed` within a corpus.
+pub(crate) fn corpus_file(seed: &PersistedSeed) -> (Vec<u8>, String) {
+    let (data, extension) = match *seed {
+        // Raw inputs are always replayed at the maximum size.
+        PersistedSeed(Seed::PassThrough(bounds, ref data), None) => {
+            let (start, end) = bounds.unwrap_or((0, data.len()));
+            (data[start..end].to_vec(), None)
+        }
+        ref seed => {
+            let mut text = seed.to_string();
+            text.push('\n');
+            (text.into_bytes(), Some(SEED_EXTENSION))
+        }
+    };
+
+    let mut hasher = DefaultHasher::new();
+    hasher.write(&data);
+    let mut name = format!("{:016x}", hasher.finish());
+    if let Some(extension) = extension {
+        name.push('.');
+        name.push_str(extension);
+    }
+    (data, name)
+}
+
+impl FailurePersistence for CorpusFailurePersistence {
+    fn load_persisted_failures2(
+        &self,
+        _source_file: Option<&'static str>,
+    ) -> Vec<PersistedSeed> {
+        // .ok() instead of .unwrap() so we don't propagate panics here
+        let _lock = PERSISTENCE_LOCK.read().ok();
+        let result = self.entries().and_then(|entries| {
+            entries
+                .iter()
+                .filter_map(|path| Self::load_entry(path).transpose())
+                .collect::<io::Result<Vec<_>>>()
+        });
+
+        unwrap_or!(result, err => {
+            if io::ErrorKind::NotFound != err.kind() {
+                eprintln!(
+                    "proptest: failed to read corpus {}: {}",
+                    self.directory,
+                    err
+                );
+            }
+            vec![]
+        })
+    }
+
+    fn save_persisted_failure2(
+        &mut self,
+        _source_file: Option<&'static str>,
+        seed: PersistedSeed,
+        _shrunken_value: &dyn Debug,
+    ) {
+        let (data, name) = corpus_file(&seed);
+        let path = Path::new(self.directory).join(name);
+
+        // .ok() instead of .unwrap() so we don't propagate panics here
+        let _lock = PERSISTENCE_LOCK.write().ok();
+        if path.is_file() {
+            return;
+        }
+        let result = fs::create_dir_all(self.directory)
+            .and_then(|()| fs::write(&path, &data));
+        match result {
+            Ok(()) => eprintln!(
+                "proptest: Saving this failure as {}",
+                path.display()
+            ),
+            Err(e) => eprintln!(
+                "proptest: failed to write {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn FailurePersistence> {
+        Box::new(*self)
+    }
+
+    fn eq(&self, other: &dyn FailurePersistence) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<Self>()
+            .map_or(false, |x| x == self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_runner::failure_persistence::tests::INC_SEED;
+    use std::borrow::ToOwned;
+    use std::env;
+
+    #[test]
+    fn seeds_and_raw_inputs_round_trip() {
+        let dir =
+            env::temp_dir().join(format!("proptest-corpus-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut persistence = CorpusFailurePersistence::new(Box::leak(
+            dir.to_str().unwrap().to_owned().into_boxed_str(),
+        ));
+
+        assert!(persistence.load_persisted_failures2(None).is_empty());
+
+        let raw = PersistedSeed(
+            Seed::PassThrough(Some((1, 3)), vec![9u8, 1, 2, 9].into()),
+            None,
+        );
+        persistence.save_persisted_failure2(None, INC_SEED, &"");
+        persistence.save_persisted_failure2(None, INC_SEED, &"");
+        persistence.save_persisted_failure2(None, raw, &"");
+        // A file dropped in by a fuzzer.
+        fs::write(dir.join("crash-0001"), [7u8, 7]).unwrap();
+
+        let mut loaded = persistence.load_persisted_failures2(None);
+        loaded.sort();
+        let mut expected = vec![
+            INC_SEED,
+            PersistedSeed(Seed::PassThrough(None, vec![1u8, 2].into()), None),
+            PersistedSeed(Seed::PassThrough(None, vec![7u8, 7].into()), None),
+        ];
+        expected.sort();
+        assert_eq!(expected, loaded);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
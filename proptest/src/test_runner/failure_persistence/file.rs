@@ -163,6 +163,68 @@ impl FailurePersistence for FileFailurePersistence {
         }
     }
 
+    fn load_persisted_values(
+        &self,
+        source_file: Option<&'static str>,
+    ) -> Vec<String> {
+        let path = self.resolve(source_file.map(Path::new));
+        let path = match path {
+            Some(path) => path,
+            None => return vec![],
+        };
+
+        // .ok() instead of .unwrap() so we don't propagate panics here
+        let _lock = PERSISTENCE_LOCK.read().ok();
+        let result: io::Result<Vec<String>> = fs::File::open(&path)
+            .and_then(|file| {
+                io::BufReader::new(file)
+                    .lines()
+                    .filter_map(|line| match line {
+                        Err(err) => Some(Err(err)),
+                        Ok(line) => parse_value_line(&line).map(Ok),
+                    })
+                    .collect()
+            });
+
+        unwrap_or!(result, err => {
+            if io::ErrorKind::NotFound != err.kind() {
+                eprintln!(
+                    "proptest: failed to open {}: {}",
+                    path.display(),
+                    err
+                );
+            }
+            vec![]
+        })
+    }
+
+    fn save_persisted_value(
+        &mut self,
+        source_file: Option<&'static str>,
+        serialized: &str,
+    ) {
+        let path = self.resolve(source_file.map(Path::new));
+        if let Some(path) = path {
+            // .ok() instead of .unwrap() so we don't propagate panics here
+            let _lock = PERSISTENCE_LOCK.write().ok();
+
+            let mut to_write = Vec::<u8>::new();
+            if !path.is_file() {
+                write_header(&mut to_write)
+                    .expect("proptest: couldn't write header.");
+            }
+            write_value_line(&mut to_write, serialized);
+
+            if let Err(e) = write_seed_data_to_file(&path, &to_write) {
+                eprintln!(
+                    "proptest: failed to append to {}: {}",
+                    path.display(),
+                    e
+                );
+            }
+        }
+    }
+
     fn box_clone(&self) -> Box<dyn FailurePersistence> {
         Box::new(*self)
     }
@@ -254,6 +316,10 @@ fn parse_seed_line(
     path: &Path,
     lineno: usize,
 ) -> Option<PersistedSeed> {
+    if parse_value_line(&line).is_some() {
+        return None;
+    }
+
     // Remove anything after and including '#':
     if let Some(comment_start) = line.find('#') {
         line.truncate(comment_start);
@@ -298,6 +364,28 @@ fn write_seed_line(
     Ok(())
 }
 
+/// Prefix of lines holding a serialized failing value rather than a seed.
+///
+/// The value runs to the end of the line; since it may itself contain `#`,
+/// no comment is written after it.
+const VALUE_PREFIX: &str = "sv ";
+
+fn parse_value_line(line: &str) -> Option<String> {
+    line.strip_prefix(VALUE_PREFIX)
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+fn write_value_line(buf: &mut Vec<u8>, serialized: &str) {
+    buf.extend_from_slice(VALUE_PREFIX.as_bytes());
+    // Serialized values are normally single-line already, but make sure.
+    buf.extend(serialized.bytes().map(|byte| match byte {
+        b'\n' | b'\r' => b' ',
+        byte => byte,
+    }));
+    buf.push(b'\n');
+}
+
 fn write_header(buf: &mut Vec<u8>) -> io::Result<()> {
     writeln!(
         buf,
@@ -408,7 +496,7 @@ impl FileFailurePersistence {
 /// We don't have much protecting us should two separate process try to
 /// write to the same file at once (depending on how atomic append mode is
 /// on the OS), but this should be extremely rare.
-static PERSISTENCE_LOCK: RwLock<()> = RwLock::new(());
+pub(super) static PERSISTENCE_LOCK: RwLock<()> = RwLock::new(());
 
 #[cfg(test)]
 mod tests {
@@ -495,6 +583,30 @@ mod tests {
         assert_eq!(None, SourceParallel("ext").resolve(None));
     }
 
+    #[test]
+    fn values_persisted_alongside_seeds() {
+        use crate::test_runner::failure_persistence::tests::INC_SEED;
+
+        let path = env::temp_dir().join(format!(
+            "proptest-values-persisted-{}.txt",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let mut persistence = Direct(Box::leak(
+            path.to_str().unwrap().to_owned().into_boxed_str(),
+        ));
+
+        persistence.save_persisted_failure2(None, INC_SEED, &"a # b");
+        persistence.save_persisted_value(None, "{\"s\":\"a # b\"}");
+
+        assert_eq!(vec![INC_SEED], persistence.load_persisted_failures2(None));
+        assert_eq!(
+            vec!["{\"s\":\"a # b\"}".to_owned()],
+            persistence.load_persisted_values(None)
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn relative_source_files_absolutified() {
         const TEST_RUNNER_PATH: &[&str] = &["src", "test_runner", "mod.rs"];
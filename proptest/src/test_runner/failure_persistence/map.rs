@@ -7,7 +7,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use crate::std_facade::{fmt, BTreeMap, BTreeSet, Box, Vec};
+use crate::std_facade::{fmt, BTreeMap, BTreeSet, Box, String, ToOwned, Vec};
 use core::any::Any;
 
 use crate::test_runner::failure_persistence::FailurePersistence;
@@ -21,6 +21,8 @@ use crate::test_runner::failure_persistence::PersistedSeed;
 pub struct MapFailurePersistence {
     /// Backing map, keyed by source_file.
     pub map: BTreeMap<&'static str, BTreeSet<PersistedSeed>>,
+    /// Serialized failing values, keyed by source_file.
+    pub values: BTreeMap<&'static str, BTreeSet<String>>,
 }
 
 impl FailurePersistence for MapFailurePersistence {
@@ -48,6 +50,29 @@ impl FailurePersistence for MapFailurePersistence {
         set.insert(seed);
     }
 
+    fn load_persisted_values(
+        &self,
+        source_file: Option<&'static str>,
+    ) -> Vec<String> {
+        source_file
+            .and_then(|source| self.values.get(source))
+            .map(|values| values.iter().cloned().collect::<Vec<_>>())
+            .unwrap_or_default()
+    }
+
+    fn save_persisted_value(
+        &mut self,
+        source_file: Option<&'static str>,
+        serialized: &str,
+    ) {
+        let s = match source_file {
+            Some(sf) => sf,
+            None => return,
+        };
+        let set = self.values.entry(s).or_insert_with(BTreeSet::new);
+        set.insert(serialized.to_owned());
+    }
+
     fn box_clone(&self) -> Box<dyn FailurePersistence> {
         Box::new(self.clone())
     }
@@ -88,6 +113,16 @@ mod tests {
         assert!(p.load_persisted_failures2(UNREL_PATH).is_empty());
     }
 
+    #[test]
+    fn values_recoverable() {
+        let mut p = MapFailurePersistence::default();
+        p.save_persisted_value(HI_PATH, "[1,2]");
+        p.save_persisted_value(HI_PATH, "[1,2]");
+        assert_eq!(vec!["[1,2]".to_owned()], p.load_persisted_values(HI_PATH));
+        assert!(p.load_persisted_values(UNREL_PATH).is_empty());
+        assert!(p.load_persisted_failures2(HI_PATH).is_empty());
+    }
+
     #[test]
     fn seeds_deduplicated() {
         let mut p = MapFailurePersistence::default();
@@ -7,21 +7,31 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use crate::std_facade::{fmt, Box, Vec};
+use crate::std_facade::{fmt, Box, String, Vec};
 use core::any::Any;
 use core::fmt::Display;
 use core::result::Result;
 use core::str::FromStr;
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod corpus;
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 mod file;
 mod map;
 mod noop;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+mod structured;
 
+#[cfg(feature = "std")]
+pub use self::corpus::*;
 #[cfg(feature = "std")]
 pub use self::file::*;
 pub use self::map::*;
+#[cfg(feature = "serde")]
+pub use self::structured::*;
 
 use crate::test_runner::Seed;
 
@@ -29,12 +39,22 @@ use crate::test_runner::Seed;
 ///
 /// The `Display` and `FromStr` implementations go to and from the format
 /// Proptest uses for its persistence file.
+///
+/// Besides the seed itself, this records the size (see `TestRunner::size`)
+/// the failing case was generated at, if that was less than the maximum.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct PersistedSeed(pub(crate) Seed);
+pub struct PersistedSeed(pub(crate) Seed, pub(crate) Option<u32>);
+
+/// The prefix of the token recording the size of a `PersistedSeed`.
+const SIZE_PREFIX: &str = "size=";
 
 impl Display for PersistedSeed {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0.to_persistence())
+        write!(f, "{}", self.0.to_persistence())?;
+        if let Some(size) = self.1 {
+            write!(f, " {}{}", SIZE_PREFIX, size)?;
+        }
+        Ok(())
     }
 }
 
@@ -42,7 +62,17 @@ impl FromStr for PersistedSeed {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, ()> {
-        Seed::from_persistence(s).map(PersistedSeed).ok_or(())
+        let s = s.trim();
+        let (seed, size) = match s.rsplit_once(' ') {
+            Some((seed, size)) if size.starts_with(SIZE_PREFIX) => (
+                seed,
+                Some(size[SIZE_PREFIX.len()..].parse().map_err(|_| ())?),
+            ),
+            _ => (s, None),
+        };
+        Seed::from_persistence(seed)
+            .map(|seed| PersistedSeed(seed, size))
+            .ok_or(())
     }
 }
 
@@ -67,7 +97,7 @@ pub trait FailurePersistence: Send + Sync + fmt::Debug {
     ) -> Vec<PersistedSeed> {
         self.load_persisted_failures(source_file)
             .into_iter()
-            .map(|seed| PersistedSeed(Seed::XorShift(seed)))
+            .map(|seed| PersistedSeed(Seed::XorShift(seed), None))
             .collect()
     }
 
@@ -118,6 +148,35 @@ pub trait FailurePersistence: Send + Sync + fmt::Debug {
         panic!("save_persisted_failure2 not implemented");
     }
 
+    /// Supply the serialized failing values associated with the given
+    /// `source_file`, as previously stored by `save_persisted_value`.
+    ///
+    /// Unlike seeds, these remain valid when the implementation of a strategy
+    /// changes. They are only used by `TestRunner::run_serialized`, which
+    /// skips any that cannot be deserialized as the type under test.
+    ///
+    /// The default implementation returns nothing.
+    #[allow(unused_variables)]
+    fn load_persisted_values(
+        &self,
+        source_file: Option<&'static str>,
+    ) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Store the serialized form of a minimal failing value associated with
+    /// the given `source_file`. The seed which produced the value, if any, is
+    /// stored separately through `save_persisted_failure2`.
+    ///
+    /// The default implementation discards the value.
+    #[allow(unused_variables)]
+    fn save_persisted_value(
+        &mut self,
+        source_file: Option<&'static str>,
+        serialized: &str,
+    ) {
+    }
+
     /// Delegate method for producing a trait object usable with `Clone`
     fn box_clone(&self) -> Box<dyn FailurePersistence>;
 
@@ -147,10 +206,25 @@ mod tests {
     use super::PersistedSeed;
     use crate::test_runner::rng::Seed;
 
-    pub const INC_SEED: PersistedSeed = PersistedSeed(Seed::XorShift([
-        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
-    ]));
+    pub const INC_SEED: PersistedSeed = PersistedSeed(
+        Seed::XorShift([
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+        ]),
+        None,
+    );
 
     pub const HI_PATH: Option<&str> = Some("hi");
     pub const UNREL_PATH: Option<&str> = Some("unrelated");
+
+    #[test]
+    fn size_round_trips() {
+        use std::string::ToString;
+
+        let seed = PersistedSeed(INC_SEED.0, Some(37));
+        let text = seed.to_string();
+        assert_eq!(INC_SEED.to_string() + " size=37", text);
+        assert_eq!(Ok(seed), text.parse());
+        assert_eq!(Ok(INC_SEED), INC_SEED.to_string().parse());
+        assert!("xs 1 2 3 4 size=".parse::<PersistedSeed>().is_err());
+    }
 }
@@ -0,0 +1,515 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::any::Any;
+use core::fmt::{self, Debug};
+use core::str::FromStr;
+use std::boxed::Box;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::test_runner::failure_persistence::file::PERSISTENCE_LOCK;
+use crate::test_runner::failure_persistence::{
+    FailurePersistence, FileFailurePersistence, PersistedSeed,
+};
+
+/// The format in which failing test cases are persisted.
+///
+/// `Text` is the line-based format of `FileFailurePersistence`; the others
+/// are used by `StructuredFailurePersistence`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PersistenceFormat {
+    /// The line-based `proptest-regressions` format.
+    #[default]
+    Text,
+    /// A JSON document.
+    Json,
+    /// A TOML document.
+    ///
+    /// This requires the `toml` feature. Without it, failures can't be
+    /// loaded from or saved to a TOML file, and an error saying so is printed
+    /// instead.
+    Toml,
+}
+
+impl PersistenceFormat {
+    /// Make `persistence` store its failures in this format, if it is one of
+    /// the file-based backends. Other backends are returned unchanged.
+    pub fn apply(
+        self,
+        persistence: Box<dyn FailurePersistence>,
+    ) -> Box<dyn FailurePersistence> {
+        let any = persistence.as_any();
+        let location = if let Some(file) =
+            any.downcast_ref::<FileFailurePersistence>()
+        {
+            *file
+        } else if let Some(structured) =
+            any.downcast_ref::<StructuredFailurePersistence>()
+        {
+            structured.location
+        } else {
+            return persistence;
+        };
+
+        match self {
+            PersistenceFormat::Text => Box::new(location),
+            format => {
+                Box::new(StructuredFailurePersistence::new(location, format))
+            }
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            PersistenceFormat::Text => "txt",
+            PersistenceFormat::Json => "json",
+            PersistenceFormat::Toml => "toml",
+        }
+    }
+}
+
+impl FromStr for PersistenceFormat {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "text" => Ok(PersistenceFormat::Text),
+            "json" => Ok(PersistenceFormat::Json),
+            "toml" => Ok(PersistenceFormat::Toml),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for PersistenceFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            PersistenceFormat::Text => "text",
+            PersistenceFormat::Json => "json",
+            PersistenceFormat::Toml => "toml",
+        })
+    }
+}
+
+/// Failure persistence which stores regressions in a JSON or TOML document,
+/// with metadata about each, so that tooling can diff and dedupe them.
+///
+/// The file is located as for `location`, except that with
+/// `FileFailurePersistence::SourceParallel` the extension is `json` or
+/// `toml` rather than `txt`. A JSON file looks like this:
+///
+/// ```json
+/// {
+///   "regressions": [
+///     {
+///       "seed": "cc 4d6b...",
+///       "rng_algorithm": "cc",
+///       "shrinks_to": "x = 5",
+///       "timestamp": 1760572800,
+///       "proptest_version": "1.11.0"
+///     }
+///   ],
+///   "values": []
+/// }
+/// ```
+///
+/// `values` holds the serialized failing values saved by
+/// `TestRunner::run_serialized`. Entries are deduplicated by seed (or
+/// value), and `timestamp` is in seconds since the Unix epoch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StructuredFailurePersistence {
+    /// Where the file is stored.
+    pub location: FileFailurePersistence,
+    /// The format of the file. `PersistenceFormat::Text` is treated as
+    /// `PersistenceFormat::Json`.
+    pub format: PersistenceFormat,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct RegressionFile {
+    #[serde(default)]
+    regressions: Vec<Regression>,
+    #[serde(default)]
+    values: Vec<PersistedValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Regression {
+    seed: String,
+    rng_algorithm: String,
+    shrinks_to: String,
+    timestamp: u64,
+    proptest_version: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct PersistedValue {
+    value: String,
+    timestamp: u64,
+    proptest_version: String,
+}
+
+impl StructuredFailurePersistence {
+    /// Store failures in `format` at the path described by `location`.
+    pub fn new(
+        location: FileFailurePersistence,
+        format: PersistenceFormat,
+    ) -> Self {
+        StructuredFailurePersistence { location, format }
+    }
+
+    /// Store failures as JSON in the default location.
+    pub fn json() -> Self {
+        Self::new(FileFailurePersistence::default(), PersistenceFormat::Json)
+    }
+
+    /// Store failures as TOML in the default location.
+    #[cfg(feature = "toml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+    pub fn toml() -> Self {
+        Self::new(FileFailurePersistence::default(), PersistenceFormat::Toml)
+    }
+
+    fn format(&self) -> PersistenceFormat {
+        match self.format {
+            PersistenceFormat::Text => PersistenceFormat::Json,
+            format => format,
+        }
+    }
+
+    fn resolve(&self, source_file: Option<&'static str>) -> Option<PathBuf> {
+        let mut path = self.location.resolve(source_file.map(Path::new))?;
+        if let FileFailurePersistence::SourceParallel(_) = self.location {
+            path.set_extension(self.format().extension());
+        }
+        Some(path)
+    }
+
+    fn load(&self, path: &Path) -> RegressionFile {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => {
+                if io::ErrorKind::NotFound != err.kind() {
+                    eprintln!(
+                        "proptest: failed to open {}: {}",
+                        path.display(),
+                        err
+                    );
+                }
+                return RegressionFile::default();
+            }
+        };
+
+        let parsed = match self.format() {
+            PersistenceFormat::Toml => from_toml(&text),
+            _ => serde_json::from_str(&text).map_err(|e| e.to_string()),
+        };
+        parsed.unwrap_or_else(|err| {
+            eprintln!(
+                "proptest: {}: unparsable regression file, ignoring: {}",
+                path.display(),
+                err
+            );
+            RegressionFile::default()
+        })
+    }
+
+    fn store(&self, path: &Path, file: &RegressionFile) {
+        let text = match self.format() {
+            PersistenceFormat::Toml => to_toml(file),
+            _ => serde_json::to_string_pretty(file)
+                .map(|mut text| {
+                    text.push('\n');
+                    text
+                })
+                .map_err(|e| e.to_string()),
+        };
+
+        let result = text
+            .map_err(io::Error::other)
+            .and_then(|text| {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, text)
+            });
+        if let Err(e) = result {
+            eprintln!("proptest: failed to write {}: {}", path.display(), e);
+        }
+    }
+
+    /// Apply `update` to the contents of the file for `source_file`, writing
+    /// the result back if `update` returns `true`.
+    fn update(
+        &self,
+        source_file: Option<&'static str>,
+        update: impl FnOnce(&mut RegressionFile) -> bool,
+    ) -> Option<PathBuf> {
+        let path = self.resolve(source_file)?;
+        // .ok() instead of .unwrap() so we don't propagate panics here
+        let _lock = PERSISTENCE_LOCK.write().ok();
+        let mut file = self.load(&path);
+        if update(&mut file) {
+            self.store(&path, &file);
+            Some(path)
+        } else {
+            None
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+impl FailurePersistence for StructuredFailurePersistence {
+    fn load_persisted_failures2(
+        &self,
+        source_file: Option<&'static str>,
+    ) -> Vec<PersistedSeed> {
+        let path = match self.resolve(source_file) {
+            Some(path) => path,
+            None => return vec![],
+        };
+        let _lock = PERSISTENCE_LOCK.read().ok();
+        self.load(&path)
+            .regressions
+            .into_iter()
+            .filter_map(|regression| {
+                let seed = regression.seed.parse().ok();
+                if seed.is_none() {
+                    eprintln!(
+                        "proptest: {}: unparsable seed {:?}, ignoring",
+                        path.display(),
+                        regression.seed
+                    );
+                }
+                seed
+            })
+            .collect()
+    }
+
+    fn save_persisted_failure2(
+        &mut self,
+        source_file: Option<&'static str>,
+        seed: PersistedSeed,
+        shrunken_value: &dyn Debug,
+    ) {
+        let seed = seed.to_string();
+        let saved = self.update(source_file, |file| {
+            if file.regressions.iter().any(|r| r.seed == seed) {
+                return false;
+            }
+            file.regressions.push(Regression {
+                rng_algorithm: seed
+                    .split(' ')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                seed: seed.clone(),
+                shrinks_to: format!("{:?}", shrunken_value),
+                timestamp: now(),
+                proptest_version: env!("CARGO_PKG_VERSION").to_string(),
+            });
+            true
+        });
+
+        if let Some(path) = saved {
+            eprintln!(
+                "proptest: Saving this and future failures in {}",
+                path.display()
+            );
+        }
+    }
+
+    fn load_persisted_values(
+        &self,
+        source_file: Option<&'static str>,
+    ) -> Vec<String> {
+        let path = match self.resolve(source_file) {
+            Some(path) => path,
+            None => return vec![],
+        };
+        let _lock = PERSISTENCE_LOCK.read().ok();
+        self.load(&path)
+            .values
+            .into_iter()
+            .map(|value| value.value)
+            .collect()
+    }
+
+    fn save_persisted_value(
+        &mut self,
+        source_file: Option<&'static str>,
+        serialized: &str,
+    ) {
+        self.update(source_file, |file| {
+            if file.values.iter().any(|v| v.value == serialized) {
+                return false;
+            }
+            file.values.push(PersistedValue {
+                value: serialized.to_string(),
+                timestamp: now(),
+                proptest_version: env!("CARGO_PKG_VERSION").to_string(),
+            });
+            true
+        });
+    }
+
+    fn box_clone(&self) -> Box<dyn FailurePersistence> {
+        Box::new(*self)
+    }
+
+    fn eq(&self, other: &dyn FailurePersistence) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<Self>()
+            .is_some_and(|x| x == self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(feature = "toml")]
+fn from_toml(text: &str) -> Result<RegressionFile, String> {
+    toml::from_str(text).map_err(|e| e.to_string())
+}
+
+#[cfg(feature = "toml")]
+fn to_toml(file: &RegressionFile) -> Result<String, String> {
+    toml::to_string_pretty(file).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "toml"))]
+const TOML_DISABLED: &str =
+    "TOML persistence requires the `toml` feature of proptest";
+
+#[cfg(not(feature = "toml"))]
+fn from_toml(_: &str) -> Result<RegressionFile, String> {
+    Err(TOML_DISABLED.to_string())
+}
+
+#[cfg(not(feature = "toml"))]
+fn to_toml(_: &RegressionFile) -> Result<String, String> {
+    Err(TOML_DISABLED.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_runner::failure_persistence::tests::INC_SEED;
+    use std::borrow::ToOwned;
+    use std::env;
+
+    fn round_trip(format: PersistenceFormat, name: &str) {
+        let path = env::temp_dir()
+            .join(format!("proptest-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut persistence = StructuredFailurePersistence::new(
+            FileFailurePersistence::Direct(Box::leak(
+                path.to_str().unwrap().to_owned().into_boxed_str(),
+            )),
+            format,
+        );
+
+        persistence.save_persisted_failure2(None, INC_SEED, &"x = 5");
+        persistence.save_persisted_failure2(None, INC_SEED, &"x = 5");
+        persistence.save_persisted_value(None, "[1, \"#\"]");
+
+        assert_eq!(vec![INC_SEED], persistence.load_persisted_failures2(None));
+        assert_eq!(
+            vec!["[1, \"#\"]".to_owned()],
+            persistence.load_persisted_values(None)
+        );
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains(&INC_SEED.to_string()));
+        assert!(text.contains("x = 5"));
+        assert!(text.contains(env!("CARGO_PKG_VERSION")));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn json_round_trip() {
+        round_trip(PersistenceFormat::Json, "json-round-trip");
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_round_trip() {
+        round_trip(PersistenceFormat::Toml, "toml-round-trip");
+    }
+
+    #[cfg(not(feature = "toml"))]
+    #[test]
+    fn toml_without_feature_saves_nothing() {
+        let path = env::temp_dir()
+            .join(format!("proptest-toml-disabled-{}", std::process::id()));
+        let mut persistence = StructuredFailurePersistence::new(
+            FileFailurePersistence::Direct(Box::leak(
+                path.to_str().unwrap().to_owned().into_boxed_str(),
+            )),
+            PersistenceFormat::Toml,
+        );
+
+        persistence.save_persisted_failure2(None, INC_SEED, &"x = 5");
+        assert!(persistence.load_persisted_failures2(None).is_empty());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn formats_parse_regardless_of_features() {
+        for format in [
+            PersistenceFormat::Text,
+            PersistenceFormat::Json,
+            PersistenceFormat::Toml,
+        ] {
+            assert_eq!(Ok(format), format.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn format_applies_to_file_backends_only() {
+        use crate::test_runner::MapFailurePersistence;
+
+        let file: Box<dyn FailurePersistence> =
+            Box::new(FileFailurePersistence::Direct("x"));
+        let json = PersistenceFormat::Json.apply(file);
+        assert_eq!(
+            Some(&StructuredFailurePersistence::new(
+                FileFailurePersistence::Direct("x"),
+                PersistenceFormat::Json
+            )),
+            json.as_any().downcast_ref::<StructuredFailurePersistence>()
+        );
+        let text = PersistenceFormat::Text.apply(json);
+        assert_eq!(
+            Some(&FileFailurePersistence::Direct("x")),
+            text.as_any().downcast_ref::<FileFailurePersistence>()
+        );
+
+        let map: Box<dyn FailurePersistence> =
+            Box::new(MapFailurePersistence::default());
+        assert!(PersistenceFormat::Json
+            .apply(map)
+            .as_any()
+            .is::<MapFailurePersistence>());
+    }
+}
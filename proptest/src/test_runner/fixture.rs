@@ -0,0 +1,115 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::ops::Deref;
+
+/// A resource, such as a temporary directory, a database handle or an async
+/// runtime, which is set up once per test and shared by all of its cases.
+///
+/// Parameters of a `#[property_test]` function marked `#[fixture]` must be
+/// shared references to a type implementing this trait. The fixture is set
+/// up before the first case and torn down after the last one, whether the
+/// test passes or fails.
+///
+/// ```
+/// use std::cell::Cell;
+/// use proptest::test_runner::{Fixture, FixtureGuard};
+///
+/// struct Counter(Cell<u32>);
+///
+/// impl Fixture for Counter {
+///     fn setup() -> Self {
+///         Counter(Cell::new(0))
+///     }
+///
+///     fn teardown(self) {
+///         assert!(self.0.get() > 0);
+///     }
+/// }
+///
+/// let counter = FixtureGuard::<Counter>::setup();
+/// counter.0.set(counter.0.get() + 1);
+/// ```
+pub trait Fixture: Sized {
+    /// Set up the fixture before the first case of a test.
+    fn setup() -> Self;
+
+    /// Tear the fixture down after the last case of a test.
+    ///
+    /// The default simply drops the fixture.
+    fn teardown(self) {}
+}
+
+/// Holds a `Fixture` for the duration of a test, tearing it down when
+/// dropped, including while unwinding from a failed test.
+#[derive(Debug)]
+pub struct FixtureGuard<F: Fixture> {
+    fixture: Option<F>,
+}
+
+impl<F: Fixture> FixtureGuard<F> {
+    /// Set up a new fixture.
+    pub fn setup() -> Self {
+        Self::new(F::setup())
+    }
+
+    /// Hold `fixture`, which is already set up.
+    pub fn new(fixture: F) -> Self {
+        FixtureGuard {
+            fixture: Some(fixture),
+        }
+    }
+}
+
+impl<F: Fixture> Deref for FixtureGuard<F> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        self.fixture.as_ref().expect("fixture used after teardown")
+    }
+}
+
+impl<F: Fixture> Drop for FixtureGuard<F> {
+    fn drop(&mut self) {
+        if let Some(fixture) = self.fixture.take() {
+            fixture.teardown();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static TORN_DOWN: AtomicUsize = AtomicUsize::new(0);
+
+    struct Resource(u32);
+
+    impl Fixture for Resource {
+        fn setup() -> Self {
+            Resource(42)
+        }
+
+        fn teardown(self) {
+            TORN_DOWN.fetch_add(self.0 as usize, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn guard_sets_up_and_tears_down() {
+        TORN_DOWN.store(0, Ordering::SeqCst);
+        {
+            let resource = FixtureGuard::<Resource>::setup();
+            assert_eq!(42, resource.0);
+            assert_eq!(0, TORN_DOWN.load(Ordering::SeqCst));
+        }
+        assert_eq!(42, TORN_DOWN.load(Ordering::SeqCst));
+    }
+}
@@ -12,21 +12,46 @@
 //! You do not normally need to access things in this module directly except
 //! when implementing new low-level strategies.
 
+mod async_runtime;
 mod config;
+mod coverage;
+mod diff;
 mod errors;
 mod failure_persistence;
+mod fixture;
+mod observer;
 mod reason;
+#[cfg(feature = "std")]
+mod regression_test;
 #[cfg(feature = "fork")]
 mod replay;
+#[cfg(feature = "std")]
+mod report;
 mod result_cache;
 mod rng;
 mod runner;
 mod scoped_panic_hook;
+mod statistics;
+mod targeted;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+mod watchdog;
 
+pub use self::async_runtime::*;
 pub use self::config::*;
+pub use self::coverage::{CoverageCounters, CoverageSource};
+pub use self::diff::*;
 pub use self::errors::*;
 pub use self::failure_persistence::*;
+pub use self::fixture::{Fixture, FixtureGuard};
+pub use self::observer::*;
 pub use self::reason::*;
+#[cfg(feature = "std")]
+pub use self::regression_test::RegressionTestOutput;
+#[cfg(feature = "std")]
+pub use self::report::ReportFormat;
 pub use self::result_cache::*;
 pub use self::rng::*;
 pub use self::runner::*;
+pub use self::statistics::{record_label, LabelStatistics};
+#[cfg(feature = "std")]
+pub use self::targeted::report_utility;
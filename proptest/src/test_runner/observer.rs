@@ -0,0 +1,113 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::std_facade::fmt;
+use crate::std_facade::Box;
+
+use crate::test_runner::reason::Reason;
+
+/// An object which is notified as the runner executes a test.
+///
+/// A fresh observer is created (via `Config::observer`) each time a test run
+/// starts. All methods do nothing by default, so implementations need only
+/// override the events they care about.
+///
+/// Case events are only reported for test cases which actually execute, so
+/// inputs answered from the result cache are not reported. When forking is
+/// enabled, case events are delivered in the child process, since that is
+/// where the test body runs, while the parent also reports `shrink_step` and
+/// `failure_minimized` as it replays the results of its children.
+///
+/// ```
+/// use std::time::Instant;
+/// use proptest::test_runner::{Reason, TestObserver};
+///
+/// #[derive(Default)]
+/// struct CaseTimer {
+///     started: Option<Instant>,
+/// }
+///
+/// impl CaseTimer {
+///     fn finish(&mut self) {
+///         if let Some(started) = self.started.take() {
+///             eprintln!("case took {:?}", started.elapsed());
+///         }
+///     }
+/// }
+///
+/// impl TestObserver for CaseTimer {
+///     fn case_started(&mut self, _: &dyn std::fmt::Debug) {
+///         self.started = Some(Instant::now());
+///     }
+///
+///     fn case_passed(&mut self) {
+///         self.finish();
+///     }
+///
+///     fn case_rejected(&mut self, _: &Reason) {
+///         self.finish();
+///     }
+///
+///     fn case_failed(&mut self, _: &Reason) {
+///         self.finish();
+///     }
+/// }
+///
+/// fn case_timer() -> Box<dyn TestObserver> {
+///     Box::new(CaseTimer::default())
+/// }
+/// # let _ = case_timer;
+/// ```
+pub trait TestObserver {
+    /// Called immediately before the test body runs with `input`.
+    ///
+    /// This includes the inputs tried while shrinking.
+    fn case_started(&mut self, input: &dyn fmt::Debug) {
+        let _ = input;
+    }
+
+    /// Called when the test body passes on the input last passed to
+    /// `case_started`.
+    fn case_passed(&mut self) {}
+
+    /// Called when the test body rejects the input last passed to
+    /// `case_started`.
+    fn case_rejected(&mut self, reason: &Reason) {
+        let _ = reason;
+    }
+
+    /// Called when the test body fails on the input last passed to
+    /// `case_started`.
+    fn case_failed(&mut self, reason: &Reason) {
+        let _ = reason;
+    }
+
+    /// Called after each step of shrinking a failing input. `iteration`
+    /// counts from 1, and `still_fails` is whether the input tried at this
+    /// step also failed.
+    fn shrink_step(&mut self, iteration: u32, still_fails: bool) {
+        let _ = (iteration, still_fails);
+    }
+
+    /// Called once shrinking has finished with the minimal failing input
+    /// found and the reason it failed.
+    fn failure_minimized(&mut self, input: &dyn fmt::Debug, reason: &Reason) {
+        let _ = (input, reason);
+    }
+}
+
+pub(crate) struct NoOpObserver;
+impl TestObserver for NoOpObserver {}
+
+/// An observer that does nothing.
+///
+/// This is the default value of `ProptestConfig.observer`.
+pub fn noop_observer() -> Box<dyn TestObserver> {
+    Box::new(NoOpObserver)
+}
@@ -0,0 +1,109 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::{fmt, str};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Selects where, if anywhere, a `#[test]` function reproducing the minimal
+/// failing case of a `proptest!` test is written once shrinking finishes.
+///
+/// See `Config::regression_test`.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum RegressionTestOutput {
+    /// No test is written.
+    #[default]
+    Off,
+    /// The test is printed to stderr.
+    Stderr,
+    /// The test is appended to the file at this path, which is created if
+    /// it does not exist.
+    File(PathBuf),
+}
+
+impl RegressionTestOutput {
+    /// Write `code` to the selected output.
+    pub(crate) fn write(&self, code: &str) {
+        match *self {
+            RegressionTestOutput::Off => (),
+            RegressionTestOutput::Stderr => eprintln!(
+                "proptest: Regression test for the minimal failing case:\n\n{}",
+                code
+            ),
+            RegressionTestOutput::File(ref path) => {
+                let written = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut file| writeln!(file, "\n{}", code));
+                if let Err(e) = written {
+                    eprintln!(
+                        "proptest: Failed to write regression test to {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl str::FromStr for RegressionTestOutput {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "" => Err(()),
+            "off" => Ok(RegressionTestOutput::Off),
+            "stderr" => Ok(RegressionTestOutput::Stderr),
+            path => Ok(RegressionTestOutput::File(path.into())),
+        }
+    }
+}
+
+impl fmt::Display for RegressionTestOutput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RegressionTestOutput::Off => f.write_str("off"),
+            RegressionTestOutput::Stderr => f.write_str("stderr"),
+            RegressionTestOutput::File(ref path) => {
+                fmt::Display::fmt(&path.display(), f)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_outputs() {
+        assert_eq!(Ok(RegressionTestOutput::Off), "off".parse());
+        assert_eq!(Ok(RegressionTestOutput::Stderr), "stderr".parse());
+        assert_eq!(
+            Ok(RegressionTestOutput::File("tests/regressions.rs".into())),
+            "tests/regressions.rs".parse()
+        );
+        assert_eq!(Err(()), "".parse::<RegressionTestOutput>());
+    }
+
+    #[test]
+    fn appends_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("regressions.rs");
+        let output = RegressionTestOutput::File(path.clone());
+        output.write("fn a() {}");
+        output.write("fn b() {}");
+        assert_eq!(
+            "\nfn a() {}\n\nfn b() {}\n",
+            std::fs::read_to_string(path).unwrap()
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::std_facade::{fmt, String};
+use core::str;
+
+/// Selects the machine-readable report, if any, which the runner writes to
+/// stderr when a test finishes.
+///
+/// The report is in addition to the usual human-readable panic message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// No report is written.
+    #[default]
+    Off,
+    /// A single line holding a JSON object with the fields `test`,
+    /// `source_file`, `result` (`"pass"`, `"fail"` or `"abort"`), `cases`,
+    /// `local_rejects`, `global_rejects`, `shrink_iterations`, `message`,
+    /// `minimal_input` and `seed`. Fields which do not apply are `null`.
+    Json,
+    /// A JUnit XML `<testsuite>` element holding a single `<testcase>`, with
+    /// the statistics given as `<properties>`.
+    JUnit,
+}
+
+impl str::FromStr for ReportFormat {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "off" => Ok(ReportFormat::Off),
+            "json" => Ok(ReportFormat::Json),
+            "junit" => Ok(ReportFormat::JUnit),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ReportFormat::Off => "off",
+            ReportFormat::Json => "json",
+            ReportFormat::JUnit => "junit",
+        })
+    }
+}
+
+/// The outcome of a test, as described by a report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Pass,
+    Fail,
+    Abort,
+}
+
+/// Everything which goes into a report on a single test.
+#[derive(Debug)]
+pub(crate) struct RunReport<'a> {
+    pub(crate) test_name: Option<&'a str>,
+    pub(crate) source_file: Option<&'a str>,
+    pub(crate) outcome: Outcome,
+    pub(crate) cases: u32,
+    pub(crate) local_rejects: u32,
+    pub(crate) global_rejects: u32,
+    pub(crate) shrink_iterations: u32,
+    pub(crate) message: Option<&'a str>,
+    pub(crate) minimal_input: Option<String>,
+    pub(crate) seed: Option<String>,
+}
+
+impl RunReport<'_> {
+    /// Write the report in `format` to `out`. Writes nothing for
+    /// `ReportFormat::Off`.
+    pub(crate) fn write(
+        &self,
+        format: ReportFormat,
+        out: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        match format {
+            ReportFormat::Off => Ok(()),
+            ReportFormat::Json => self.write_json(out),
+            ReportFormat::JUnit => self.write_junit(out),
+        }
+    }
+
+    fn outcome_str(&self) -> &'static str {
+        match self.outcome {
+            Outcome::Pass => "pass",
+            Outcome::Fail => "fail",
+            Outcome::Abort => "abort",
+        }
+    }
+
+    fn write_json(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        fn string(out: &mut dyn fmt::Write, s: Option<&str>) -> fmt::Result {
+            let s = match s {
+                Some(s) => s,
+                None => return out.write_str("null"),
+            };
+            out.write_char('"')?;
+            for c in s.chars() {
+                match c {
+                    '"' => out.write_str("\\\"")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\t' => out.write_str("\\t")?,
+                    c if (c as u32) < 0x20 => {
+                        write!(out, "\\u{:04x}", c as u32)?
+                    }
+                    c => out.write_char(c)?,
+                }
+            }
+            out.write_char('"')
+        }
+
+        out.write_str("{\"test\":")?;
+        string(out, self.test_name)?;
+        out.write_str(",\"source_file\":")?;
+        string(out, self.source_file)?;
+        write!(
+            out,
+            ",\"result\":\"{}\",\"cases\":{},\"local_rejects\":{},\
+             \"global_rejects\":{},\"shrink_iterations\":{}",
+            self.outcome_str(),
+            self.cases,
+            self.local_rejects,
+            self.global_rejects,
+            self.shrink_iterations
+        )?;
+        out.write_str(",\"message\":")?;
+        string(out, self.message)?;
+        out.write_str(",\"minimal_input\":")?;
+        string(out, self.minimal_input.as_deref())?;
+        out.write_str(",\"seed\":")?;
+        string(out, self.seed.as_deref())?;
+        out.write_str("}\n")
+    }
+
+    fn write_junit(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        fn escaped(out: &mut dyn fmt::Write, s: &str) -> fmt::Result {
+            for c in s.chars() {
+                match c {
+                    '&' => out.write_str("&amp;")?,
+                    '<' => out.write_str("&lt;")?,
+                    '>' => out.write_str("&gt;")?,
+                    '"' => out.write_str("&quot;")?,
+                    '\'' => out.write_str("&apos;")?,
+                    c => out.write_char(c)?,
+                }
+            }
+            Ok(())
+        }
+
+        let name = self.test_name.unwrap_or("proptest");
+        let (classname, short_name) = match name.rfind("::") {
+            Some(ix) => (&name[..ix], &name[ix + 2..]),
+            None => ("", name),
+        };
+
+        writeln!(
+            out,
+            "<testsuite name=\"proptest\" tests=\"1\" failures=\"{}\" \
+             errors=\"{}\">",
+            (self.outcome == Outcome::Fail) as u32,
+            (self.outcome == Outcome::Abort) as u32
+        )?;
+        out.write_str("  <testcase name=\"")?;
+        escaped(out, short_name)?;
+        out.write_str("\" classname=\"")?;
+        escaped(out, classname)?;
+        if let Some(source_file) = self.source_file {
+            out.write_str("\" file=\"")?;
+            escaped(out, source_file)?;
+        }
+        out.write_str("\">\n")?;
+
+        write!(
+            out,
+            "    <properties>\n      \
+             <property name=\"cases\" value=\"{}\"/>\n      \
+             <property name=\"local_rejects\" value=\"{}\"/>\n      \
+             <property name=\"global_rejects\" value=\"{}\"/>\n      \
+             <property name=\"shrink_iterations\" value=\"{}\"/>\n",
+            self.cases,
+            self.local_rejects,
+            self.global_rejects,
+            self.shrink_iterations
+        )?;
+        if let Some(ref seed) = self.seed {
+            out.write_str("      <property name=\"seed\" value=\"")?;
+            escaped(out, seed)?;
+            out.write_str("\"/>\n")?;
+        }
+        out.write_str("    </properties>\n")?;
+
+        let element = match self.outcome {
+            Outcome::Pass => None,
+            Outcome::Fail => Some("failure"),
+            Outcome::Abort => Some("error"),
+        };
+        if let Some(element) = element {
+            write!(out, "    <{} message=\"", element)?;
+            escaped(out, self.message.unwrap_or(""))?;
+            out.write_str("\">")?;
+            if let Some(ref input) = self.minimal_input {
+                escaped(out, input)?;
+            }
+            writeln!(out, "</{}>", element)?;
+        }
+
+        out.write_str("  </testcase>\n</testsuite>\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::string::ToString;
+
+    fn failing_report() -> RunReport<'static> {
+        RunReport {
+            test_name: Some("my_crate::tests::it_works"),
+            source_file: Some("src/lib.rs"),
+            outcome: Outcome::Fail,
+            cases: 12,
+            local_rejects: 1,
+            global_rejects: 2,
+            shrink_iterations: 30,
+            message: Some("assertion failed: \"x\" < 5"),
+            minimal_input: Some("x = 5".to_string()),
+            seed: Some("cc 0123".to_string()),
+        }
+    }
+
+    #[test]
+    fn json_report() {
+        let mut out = String::new();
+        failing_report().write(ReportFormat::Json, &mut out).unwrap();
+        assert_eq!(
+            "{\"test\":\"my_crate::tests::it_works\",\
+             \"source_file\":\"src/lib.rs\",\"result\":\"fail\",\
+             \"cases\":12,\"local_rejects\":1,\"global_rejects\":2,\
+             \"shrink_iterations\":30,\
+             \"message\":\"assertion failed: \\\"x\\\" < 5\",\
+             \"minimal_input\":\"x = 5\",\"seed\":\"cc 0123\"}\n",
+            out
+        );
+    }
+
+    #[test]
+    fn junit_report() {
+        let mut out = String::new();
+        failing_report().write(ReportFormat::JUnit, &mut out).unwrap();
+        assert!(out.starts_with(
+            "<testsuite name=\"proptest\" tests=\"1\" failures=\"1\" \
+             errors=\"0\">\n  <testcase name=\"it_works\" \
+             classname=\"my_crate::tests\" file=\"src/lib.rs\">\n"
+        ));
+        assert!(out.contains(
+            "<failure message=\"assertion failed: &quot;x&quot; &lt; 5\">\
+             x = 5</failure>"
+        ));
+        assert!(out.contains("<property name=\"seed\" value=\"cc 0123\"/>"));
+    }
+
+    #[test]
+    fn off_writes_nothing() {
+        let mut out = String::new();
+        failing_report().write(ReportFormat::Off, &mut out).unwrap();
+        assert!(out.is_empty());
+    }
+}
@@ -105,6 +105,10 @@ impl fmt::Display for RngAlgorithm {
     }
 }
 
+/// The seed of the sequence an RNG created with `from_fuzz_input()`
+/// continues with once its data is depleted.
+const FUZZ_INPUT_DEPLETED_SEED: [u8; 16] = *b"pass-through-rng";
+
 /// Proptest's random number generator.
 #[derive(Clone, Debug)]
 pub struct TestRng {
@@ -119,11 +123,22 @@ enum TestRngImpl {
         off: usize,
         end: usize,
         data: Arc<[u8]>,
+        /// The source of the bytes past `end`, which are 0s if `None`.
+        depleted: Option<XorShiftRng>,
     },
     Recorder {
         rng: ChaChaRng,
         record: Vec<u8>,
     },
+    Choices {
+        /// The bytes produced so far, followed by the bytes still to replay.
+        choices: Vec<u8>,
+        /// The number of bytes produced so far.
+        off: usize,
+        /// The source of the bytes past the end of `choices`, which are
+        /// appended to it.
+        fresh: ChaChaRng,
+    },
 }
 
 impl RngCore for TestRng {
@@ -136,6 +151,12 @@ impl RngCore for TestRng {
                 self.fill_bytes(&mut buf[..]);
                 u32::from_le_bytes(buf)
             }
+            // Big-endian, so that lower choices compare lower as bytes.
+            TestRngImpl::Choices { .. } => {
+                let mut buf = [0; 4];
+                self.fill_bytes(&mut buf[..]);
+                u32::from_be_bytes(buf)
+            }
             TestRngImpl::Recorder { rng, record } => {
                 let read = rng.next_u32();
                 record.extend_from_slice(&read.to_le_bytes());
@@ -153,6 +174,12 @@ impl RngCore for TestRng {
                 self.fill_bytes(&mut buf[..]);
                 u64::from_le_bytes(buf)
             }
+            // Big-endian, so that lower choices compare lower as bytes.
+            TestRngImpl::Choices { .. } => {
+                let mut buf = [0; 8];
+                self.fill_bytes(&mut buf[..]);
+                u64::from_be_bytes(buf)
+            }
             TestRngImpl::Recorder { rng, record } => {
                 let read = rng.next_u64();
                 record.extend_from_slice(&read.to_le_bytes());
@@ -165,18 +192,30 @@ impl RngCore for TestRng {
         match &mut self.rng {
             TestRngImpl::XorShift(rng) => rng.fill_bytes(dest),
             TestRngImpl::ChaCha(rng) => rng.fill_bytes(dest),
-            TestRngImpl::PassThrough { off, end, data } => {
+            TestRngImpl::PassThrough { off, end, data, depleted } => {
                 let bytes_to_copy = dest.len().min(*end - *off);
                 dest[.. bytes_to_copy].copy_from_slice(&data[*off .. *off + bytes_to_copy]);
                 *off += bytes_to_copy;
-                for i in bytes_to_copy .. dest.len() {
-                    dest[i] = 0;
+                match depleted {
+                    Some(rng) => rng.fill_bytes(&mut dest[bytes_to_copy ..]),
+                    None => {
+                        for i in bytes_to_copy .. dest.len() {
+                            dest[i] = 0;
+                        }
+                    }
                 }
             }
             TestRngImpl::Recorder { rng, record } => {
                 rng.fill_bytes(dest);
                 record.extend_from_slice(dest);
             }
+            TestRngImpl::Choices { choices, off, fresh } => {
+                let bytes_to_copy = dest.len().min(choices.len() - *off);
+                dest[.. bytes_to_copy].copy_from_slice(&choices[*off .. *off + bytes_to_copy]);
+                fresh.fill_bytes(&mut dest[bytes_to_copy ..]);
+                choices.extend_from_slice(&dest[bytes_to_copy ..]);
+                *off += dest.len();
+            }
         }
     }
 }
@@ -515,10 +554,77 @@ impl TestRng {
         })
     }
 
+    /// Return how many bytes have been consumed from the front of the data
+    /// of a `PassThrough` RNG, or `None` for other algorithms.
+    pub(crate) fn pass_through_consumed(&self) -> Option<usize> {
+        match self.rng {
+            TestRngImpl::PassThrough { off, .. } => Some(off),
+            _ => None,
+        }
+    }
+
+    /// Construct a `PassThrough` TestRng from `data` which, unlike one
+    /// created with `from_seed()`, continues with a fixed pseudo-random
+    /// sequence instead of 0s once `data` is depleted, as do the RNGs derived
+    /// from it. (A constant sequence would make rejection sampling, as used
+    /// to pick numbers from ranges, loop forever.)
+    pub(crate) fn from_fuzz_input(data: &[u8]) -> Self {
+        let mut rng =
+            Self::from_seed_internal(Seed::PassThrough(None, data.into()));
+        rng.continue_when_depleted();
+        rng
+    }
+
+    /// Make a `PassThrough` RNG continue with a fixed pseudo-random sequence
+    /// once its data is depleted.
+    fn continue_when_depleted(&mut self) {
+        if let TestRngImpl::PassThrough { ref mut depleted, .. } = self.rng {
+            *depleted = Some(XorShiftRng::from_seed(FUZZ_INPUT_DEPLETED_SEED));
+        }
+    }
+
+    /// Construct a TestRng which replays `choices` as the bytes it produces,
+    /// continuing with bytes from a ChaCha RNG seeded with `fresh` once they
+    /// are depleted. All bytes produced are recorded.
+    ///
+    /// RNGs derived from it are seeded with bytes it produces, so that the
+    /// bytes it records are enough to replay a whole generation.
+    pub(crate) fn from_choices(choices: Vec<u8>, fresh: [u8; 32]) -> Self {
+        Self {
+            rng: TestRngImpl::Choices {
+                choices,
+                off: 0,
+                fresh: ChaChaRng::from_seed(fresh),
+            },
+        }
+    }
+
+    /// Return the bytes produced so far by an RNG created with
+    /// `from_choices()`, or `None` for other RNGs.
+    pub(crate) fn choices_made(&self) -> Option<&[u8]> {
+        match self.rng {
+            TestRngImpl::Choices { ref choices, off, .. } => {
+                Some(&choices[..off])
+            }
+            _ => None,
+        }
+    }
+
     /// Construct a TestRng by the perturbed randomized seed
     /// from an existing TestRng.
     pub(crate) fn gen_rng(&mut self) -> Self {
-        Self::from_seed_internal(self.new_rng_seed())
+        let continues = matches!(
+            self.rng,
+            TestRngImpl::PassThrough {
+                depleted: Some(_),
+                ..
+            }
+        );
+        let mut rng = Self::from_seed_internal(self.new_rng_seed());
+        if continues {
+            rng.continue_when_depleted();
+        }
+        rng
     }
 
     /// Overwrite the given TestRng with the provided seed.
@@ -559,6 +665,7 @@ impl TestRng {
                 ref mut off,
                 ref mut end,
                 ref data,
+                ..
             } => {
                 let len = *end - *off;
                 let child_start = *off + len / 2;
@@ -573,6 +680,12 @@ impl TestRng {
             TestRngImpl::Recorder { ref mut rng, .. } => {
                 Seed::Recorder(rng.random())
             }
+
+            TestRngImpl::Choices { .. } => {
+                let mut seed = [0; 32];
+                self.fill_bytes(&mut seed);
+                Seed::ChaCha(seed)
+            }
         }
     }
 
@@ -594,6 +707,7 @@ impl TestRng {
                         off: start,
                         end,
                         data,
+                        depleted: None,
                     }
                 }
 
@@ -27,13 +27,23 @@ use tempfile;
 
 use crate::strategy::*;
 use crate::test_runner::config::*;
+use crate::test_runner::coverage::CoverageState;
+#[cfg(feature = "std")]
+use crate::test_runner::diff::{with_diff_formatter, DiffFormatter};
 use crate::test_runner::errors::*;
 use crate::test_runner::failure_persistence::PersistedSeed;
+use crate::test_runner::observer::TestObserver;
 use crate::test_runner::reason::*;
 #[cfg(feature = "fork")]
 use crate::test_runner::replay;
+#[cfg(feature = "std")]
+use crate::test_runner::report::{Outcome, ReportFormat, RunReport};
 use crate::test_runner::result_cache::*;
-use crate::test_runner::rng::TestRng;
+use crate::test_runner::rng::{Seed, TestRng};
+use crate::test_runner::statistics::{take_labels, LabelStatistics};
+use crate::test_runner::targeted::{take_reported_utility, TargetState};
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+use crate::test_runner::watchdog::Watchdog;
 
 #[cfg(feature = "fork")]
 const ENV_FORK_FILE: &'static str = "_PROPTEST_FORKFILE";
@@ -66,6 +76,42 @@ macro_rules! verbose_message {
 
 type RejectionDetail = BTreeMap<Reason, u32>;
 
+/// Write a line for each reason in `detail`, most frequent first, with its
+/// share of the `total` rejections.
+fn write_rejection_table(
+    out: &mut impl fmt::Write,
+    detail: &RejectionDetail,
+    total: u32,
+) -> fmt::Result {
+    let mut rows: Vec<_> = detail.iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    for (whence, &count) in rows {
+        let percent = f64::from(count) * 100.0 / f64::from(total.max(1));
+        writeln!(out, "\t{:6.2}% {}", percent, whence)?;
+    }
+    Ok(())
+}
+
+/// The size at which strategies generate their full range of inputs.
+const MAX_SIZE: u32 = 100;
+
+/// The size for a case after `successes` passing cases when ramping the size
+/// up over the first half of `cases`.
+fn ramp_size(successes: u32, cases: u32) -> u32 {
+    let ramp_cases = (cases / 2).max(1);
+    (u64::from(successes) * u64::from(MAX_SIZE) / u64::from(ramp_cases))
+        .min(u64::from(MAX_SIZE)) as u32
+}
+
+/// Shrinks the failing case generated from a seed in place of the usual
+/// shrinking, returning the reason for the last failure seen, if any, and the
+/// minimal value.
+type ShrinkOverride<'a, T> = &'a mut dyn FnMut(
+    &mut TestRunner,
+    &Seed,
+    &mut dyn TestObserver,
+) -> (Option<Reason>, T);
+
 /// State used when running a proptest test.
 #[derive(Clone)]
 pub struct TestRunner {
@@ -78,6 +124,15 @@ pub struct TestRunner {
 
     local_reject_detail: RejectionDetail,
     global_reject_detail: RejectionDetail,
+
+    label_statistics: LabelStatistics,
+    shrink_iterations: u32,
+    /// Set while failing cases are shrunk by a `ShrinkOverride` instead.
+    defer_shrinking: bool,
+    size: u32,
+    /// Enforces `Config::timeout` while running in-process without forking.
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    watchdog: Option<Arc<Watchdog>>,
 }
 
 impl fmt::Debug for TestRunner {
@@ -91,6 +146,10 @@ impl fmt::Debug for TestRunner {
             .field("flat_map_regens", &self.flat_map_regens)
             .field("local_reject_detail", &self.local_reject_detail)
             .field("global_reject_detail", &self.global_reject_detail)
+            .field("label_statistics", &self.label_statistics)
+            .field("shrink_iterations", &self.shrink_iterations)
+            .field("defer_shrinking", &self.defer_shrinking)
+            .field("size", &self.size)
             .finish()
     }
 }
@@ -175,14 +234,30 @@ impl ForkOutput {
     }
 }
 
+/// The state shared by the test cases of one run: the cache of their
+/// results, the observer of their progress, and the output to the parent
+/// process when running in a fork.
+struct RunContext {
+    result_cache: Box<dyn ResultCache>,
+    observer: Box<dyn TestObserver>,
+    fork_output: ForkOutput,
+}
+
+fn notify_result(observer: &mut dyn TestObserver, result: &TestCaseResult) {
+    match *result {
+        Ok(()) => observer.case_passed(),
+        Err(TestCaseError::Reject(ref reason)) => observer.case_rejected(reason),
+        Err(TestCaseError::Fail(ref reason)) => observer.case_failed(reason),
+    }
+}
+
 #[cfg(not(feature = "std"))]
 fn call_test<V, F, R>(
     _runner: &mut TestRunner,
     case: V,
     test: &F,
     replay_from_fork: &mut R,
-    result_cache: &mut dyn ResultCache,
-    _: &mut ForkOutput,
+    ctx: &mut RunContext,
     is_from_persisted_seed: bool,
 ) -> TestCaseResultV2
 where
@@ -194,13 +269,15 @@ where
         return result.map(|_| TestCaseOk::ReplayFromForkSuccess);
     }
 
-    let cache_key = result_cache.key(&ResultCacheKey::new(&case));
-    if let Some(result) = result_cache.get(cache_key) {
+    let cache_key = ctx.result_cache.key(&ResultCacheKey::new(&case));
+    if let Some(result) = ctx.result_cache.get(cache_key) {
         return result.clone().map(|_| TestCaseOk::CacheHitSuccess);
     }
 
+    ctx.observer.case_started(&case);
     let result = test(case);
-    result_cache.put(cache_key, &result);
+    ctx.result_cache.put(cache_key, &result);
+    notify_result(&mut *ctx.observer, &result);
     result.map(|_| {
         if is_from_persisted_seed {
             TestCaseOk::PersistedCaseSuccess
@@ -210,14 +287,34 @@ where
     })
 }
 
+/// Call `test` with `diff_formatter` describing failed equality assertions,
+/// turning a panic into a `TestCaseError::Fail`.
+#[cfg(feature = "std")]
+fn call_catching_panics(
+    diff_formatter: fn() -> Box<dyn DiffFormatter>,
+    test: impl FnOnce() -> TestCaseResult,
+) -> TestCaseResult {
+    unwrap_or!(
+        super::scoped_panic_hook::with_hook(
+            |_| { /* Silence out panic backtrace */ },
+            || with_diff_formatter(diff_formatter, ||
+                panic::catch_unwind(AssertUnwindSafe(test)))
+        ),
+        what => Err(TestCaseError::Fail(
+            what.downcast::<&'static str>().map(|s| (*s).into())
+                .or_else(|what| what.downcast::<String>().map(|b| (*b).into()))
+                .or_else(|what| what.downcast::<Box<str>>().map(|b| (*b).into()))
+                .unwrap_or_else(|_| "<unknown panic value>".into())))
+    )
+}
+
 #[cfg(feature = "std")]
 fn call_test<V, F, R>(
     runner: &mut TestRunner,
     case: V,
     test: &F,
     replay_from_fork: &mut R,
-    result_cache: &mut dyn ResultCache,
-    fork_output: &mut ForkOutput,
+    ctx: &mut RunContext,
     is_from_persisted_seed: bool,
 ) -> TestCaseResultV2
 where
@@ -225,7 +322,7 @@ where
     F: Fn(V) -> TestCaseResult,
     R: Iterator<Item = TestCaseResult>,
 {
-    #[cfg(feature = "timeout")]
+    #[cfg(not(target_arch = "wasm32"))]
     let timeout = runner.config.timeout();
 
     if let Some(result) = replay_from_fork.next() {
@@ -235,12 +332,12 @@ where
     // Now that we're about to start a new test (as far as the replay system is
     // concerned), ping the replay file so the parent process can determine
     // that we made it this far.
-    fork_output.ping();
+    ctx.fork_output.ping();
 
     verbose_message!(runner, TRACE, "Next test input: {:?}", case);
 
-    let cache_key = result_cache.key(&ResultCacheKey::new(&case));
-    if let Some(result) = result_cache.get(cache_key) {
+    let cache_key = ctx.result_cache.key(&ResultCacheKey::new(&case));
+    if let Some(result) = ctx.result_cache.get(cache_key) {
         verbose_message!(
             runner,
             TRACE,
@@ -249,24 +346,27 @@ where
         return result.clone().map(|_| TestCaseOk::CacheHitSuccess);
     }
 
-    #[cfg(feature = "timeout")]
+    ctx.observer.case_started(&case);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(ref watchdog) = runner.watchdog {
+        watchdog.arm(format!("{:?}", case));
+    }
+    #[cfg(not(target_arch = "wasm32"))]
     let time_start = std::time::Instant::now();
 
-    let mut result = unwrap_or!(
-        super::scoped_panic_hook::with_hook(
-            |_| { /* Silence out panic backtrace */ },
-            || panic::catch_unwind(AssertUnwindSafe(|| test(case)))
-        ),
-        what => Err(TestCaseError::Fail(
-            what.downcast::<&'static str>().map(|s| (*s).into())
-                .or_else(|what| what.downcast::<String>().map(|b| (*b).into()))
-                .or_else(|what| what.downcast::<Box<str>>().map(|b| (*b).into()))
-                .unwrap_or_else(|_| "<unknown panic value>".into()))));
+    let mut result =
+        call_catching_panics(runner.config.diff_formatter, || test(case));
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(ref watchdog) = runner.watchdog {
+        watchdog.disarm();
+    }
 
     // If there is a timeout and we exceeded it, fail the test here so we get
     // consistent behaviour. (The parent process cannot precisely time the test
-    // cases itself.)
-    #[cfg(feature = "timeout")]
+    // cases itself, and the watchdog cannot interrupt them.)
+    #[cfg(not(target_arch = "wasm32"))]
     if timeout > 0 && result.is_ok() {
         let elapsed = time_start.elapsed();
         let elapsed_millis = elapsed.as_secs() as u32 * 1000
@@ -280,8 +380,9 @@ where
         }
     }
 
-    result_cache.put(cache_key, &result);
-    fork_output.append(&result);
+    ctx.result_cache.put(cache_key, &result);
+    ctx.fork_output.append(&result);
+    notify_result(&mut *ctx.observer, &result);
 
     match result {
         Ok(()) => verbose_message!(runner, TRACE, "Test case passed"),
@@ -351,6 +452,12 @@ impl TestRunner {
             flat_map_regens: Arc::new(AtomicUsize::new(0)),
             local_reject_detail: BTreeMap::new(),
             global_reject_detail: BTreeMap::new(),
+            label_statistics: LabelStatistics::default(),
+            shrink_iterations: 0,
+            defer_shrinking: false,
+            size: MAX_SIZE,
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            watchdog: None,
         }
     }
 
@@ -367,6 +474,12 @@ impl TestRunner {
             flat_map_regens: Arc::clone(&self.flat_map_regens),
             local_reject_detail: BTreeMap::new(),
             global_reject_detail: BTreeMap::new(),
+            label_statistics: LabelStatistics::default(),
+            shrink_iterations: 0,
+            defer_shrinking: false,
+            size: self.size,
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            watchdog: None,
         }
     }
 
@@ -386,6 +499,35 @@ impl TestRunner {
         &self.config
     }
 
+    /// Returns the size of the inputs currently being generated, from 0 for
+    /// the simplest up to 100 for the full range allowed by each strategy.
+    ///
+    /// This is always 100 unless `Config::size_ramp` is set.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the upper bound to use in place of `high` for a parameter in
+    /// the range `low..=high` (such as the length of a collection or the
+    /// depth of a recursive value), scaled according to the current `size`.
+    ///
+    /// At the maximum size this is simply `high`, and at size 0 it is `low`.
+    pub fn sized(&self, low: usize, high: usize) -> usize {
+        if self.size >= MAX_SIZE || high <= low {
+            return high;
+        }
+
+        let span = (high - low) as u128 * u128::from(self.size)
+            / u128::from(MAX_SIZE);
+        low + span as usize
+    }
+
+    /// Returns the distribution of labels recorded with `prop_classify!` and
+    /// `prop_collect!` over the passing cases run so far.
+    pub fn label_statistics(&self) -> &LabelStatistics {
+        &self.label_statistics
+    }
+
     /// Dumps the bytes obtained from the RNG so far (only works if the RNG is
     /// set to `Recorder`).
     ///
@@ -419,6 +561,173 @@ impl TestRunner {
         }
     }
 
+    /// Like `run`, but a failure carries a `Counterexample` holding the
+    /// minimal failing value together with the statistics of the run, for
+    /// harnesses which act on the value rather than just reporting it.
+    ///
+    /// ```
+    /// use proptest::prelude::*;
+    /// use proptest::test_runner::{Counterexample, TestError, TestRunner};
+    ///
+    /// let mut runner = TestRunner::default();
+    /// let result = runner.run_with_counterexample(&(0u32..1000), |v| {
+    ///     prop_assert!(v < 500);
+    ///     Ok(())
+    /// });
+    /// match result {
+    ///     Err(TestError::Fail(_, Counterexample { value, stats })) => {
+    ///         assert_eq!(500, value);
+    ///         assert!(stats.shrink_iterations > 0);
+    ///     }
+    ///     other => panic!("unexpected result: {:?}", other),
+    /// }
+    /// ```
+    pub fn run_with_counterexample<S: Strategy>(
+        &mut self,
+        strategy: &S,
+        test: impl Fn(S::Value) -> TestCaseResult,
+    ) -> Result<(), TestError<Counterexample<S::Value>>> {
+        match self.run(strategy, test) {
+            Ok(()) => Ok(()),
+            Err(TestError::Abort(why)) => Err(TestError::Abort(why)),
+            Err(TestError::Fail(why, value)) => Err(TestError::Fail(
+                why,
+                Counterexample {
+                    value,
+                    stats: self.stats(),
+                },
+            )),
+        }
+    }
+
+    /// Return the statistics accumulated by this runner so far.
+    pub fn stats(&self) -> RunStats {
+        RunStats {
+            successes: self.successes,
+            global_rejects: self.global_rejects,
+            local_rejects: self.local_rejects,
+            shrink_iterations: self.shrink_iterations,
+        }
+    }
+
+    /// Like `run`, but also persists the minimal failing value itself, not
+    /// just the seed which produced it.
+    ///
+    /// Values are stored as JSON through
+    /// `FailurePersistence::save_persisted_value`. Before any seeds are
+    /// replayed, every persisted value which deserializes as `S::Value` is
+    /// passed to `test` directly, so regressions keep being checked even
+    /// after a change to a strategy's implementation invalidates the old
+    /// seeds. Persisted values are always run in-process, even when forking
+    /// is enabled, and are not shrunk further.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn run_serialized<S: Strategy>(
+        &mut self,
+        strategy: &S,
+        test: impl Fn(S::Value) -> TestCaseResult,
+    ) -> TestRunResult<S>
+    where
+        S::Value: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let persisted_values: Vec<String> = self
+            .config
+            .failure_persistence
+            .as_ref()
+            .map(|f| f.load_persisted_values(self.config.source_file))
+            .unwrap_or_default();
+
+        for serialized in persisted_values.iter().rev() {
+            if serde_json::from_str::<S::Value>(serialized).is_err() {
+                continue;
+            }
+
+            self.run_one(
+                SerializedValueTree::<S::Value>::new(serialized.clone()),
+                &test,
+            )?;
+        }
+
+        let result = self.run(strategy, &test);
+        if let Err(TestError::Fail(_, ref value)) = result {
+            let source_file = self.config.source_file;
+            match (
+                serde_json::to_string(value),
+                self.config.failure_persistence.as_mut(),
+            ) {
+                (Ok(serialized), Some(failure_persistence))
+                    if !persisted_values.contains(&serialized) =>
+                {
+                    failure_persistence
+                        .save_persisted_value(source_file, &serialized);
+                }
+                (Err(e), _) => verbose_message!(
+                    self,
+                    ALWAYS,
+                    "Failed to serialize the minimal failing value: {}",
+                    e
+                ),
+                _ => (),
+            }
+        }
+        result
+    }
+
+    /// Like `run`, but shrinks failing cases by evaluating several candidates
+    /// at once on `Config::shrink_threads` threads.
+    ///
+    /// Shrinking is inherently sequential, since which candidate comes next
+    /// depends on whether the last one failed. This speculatively evaluates
+    /// the candidates along every possible outcome for the next few steps in
+    /// parallel, and then follows the path the actual outcomes select. The
+    /// minimal failing case is therefore exactly the one sequential shrinking
+    /// would find, but for slow tests it is found in a fraction of the wall
+    /// clock time.
+    ///
+    /// Each speculative candidate is obtained by regenerating the value tree
+    /// from its seed and replaying the shrinking steps taken so far, so this
+    /// is only worthwhile when the test itself dominates the cost of
+    /// generation. While shrinking in parallel, `Config::timeout` is not
+    /// enforced, the result cache is not consulted, and the observer receives
+    /// only `shrink_step` and `failure_minimized` events.
+    ///
+    /// If `shrink_threads` is 1, or when forking, this is the same as `run`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn run_with_parallel_shrinking<S: Strategy>(
+        &mut self,
+        strategy: &S,
+        test: impl Fn(S::Value) -> TestCaseResult + Sync,
+    ) -> TestRunResult<S>
+    where
+        S::Value: Send,
+    {
+        let threads = match self.config.shrink_threads {
+            0 => std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            n => n as usize,
+        };
+        if threads <= 1 || self.config.fork() {
+            return self.run(strategy, test);
+        }
+
+        let (replay_steps, fork_output) = init_replay(&mut self.rng);
+        let result = self.run_in_process_with_replay(
+            strategy,
+            &test,
+            replay_steps.into_iter(),
+            fork_output,
+            Some(&mut |runner: &mut TestRunner, seed, observer| {
+                runner.shrink_in_parallel(
+                    strategy, &test, seed, observer, threads,
+                )
+            }),
+        );
+        self.defer_shrinking = false;
+        result
+    }
+
     #[cfg(not(feature = "fork"))]
     fn run_in_fork<S: Strategy>(
         &mut self,
@@ -563,6 +872,7 @@ impl TestRunner {
             |_| panic!("Ran past the end of the replay"),
             replay.steps.into_iter(),
             ForkOutput::empty(),
+            None,
         )
     }
 
@@ -577,6 +887,7 @@ impl TestRunner {
             test,
             replay_steps.into_iter(),
             fork_output,
+            None,
         )
     }
 
@@ -584,10 +895,136 @@ impl TestRunner {
         &mut self,
         strategy: &S,
         test: impl Fn(S::Value) -> TestCaseResult,
-        mut replay_from_fork: impl Iterator<Item = TestCaseResult>,
-        mut fork_output: ForkOutput,
+        replay_from_fork: impl Iterator<Item = TestCaseResult>,
+        fork_output: ForkOutput,
+        shrink_override: Option<ShrinkOverride<'_, S::Value>>,
+    ) -> TestRunResult<S> {
+        // A forked child only runs on behalf of its parent, which reports
+        // the result of the whole run itself.
+        let in_fork = fork_output.is_in_fork();
+        #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+        {
+            let timeout = self.config.timeout();
+            self.watchdog = if timeout > 0 && !self.config.fork() {
+                Some(Arc::new(Watchdog::new(timeout)))
+            } else {
+                None
+            };
+        }
+        let mut failing_seed = None;
+        let result = self.run_cases(
+            strategy,
+            test,
+            replay_from_fork,
+            fork_output,
+            &mut failing_seed,
+            shrink_override,
+        );
+        #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+        {
+            self.watchdog = None;
+        }
+        let result = if self.config.expect_failure && !in_fork {
+            self.expected_failure_result(result)
+        } else {
+            result
+        };
+
+        #[cfg(feature = "std")]
+        if !in_fork && self.config.report_format != ReportFormat::Off {
+            self.write_report(&result, failing_seed);
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = failing_seed;
+
+        result
+    }
+
+    /// Turn the result of a run with `Config::expect_failure` set into a
+    /// success if a case failed, and into a failure if none did.
+    fn expected_failure_result<T: fmt::Debug>(
+        &self,
+        result: Result<(), TestError<T>>,
+    ) -> Result<(), TestError<T>> {
+        match result {
+            Ok(()) => {
+                use core::fmt::Write;
+
+                let mut message = String::new();
+                let _ = write!(
+                    message,
+                    "Expected a failing case, but all {} cases passed",
+                    self.successes
+                );
+                Err(TestError::Abort(message.into()))
+            }
+            Err(TestError::Fail(why, value)) => {
+                #[cfg(feature = "std")]
+                eprintln!(
+                    "proptest: Found expected failure: {}; \
+                     minimal failing input: {:?}",
+                    why, value
+                );
+                #[cfg(not(feature = "std"))]
+                let _ = (why, value);
+                Ok(())
+            }
+            Err(abort) => Err(abort),
+        }
+    }
+
+    /// Write the report selected by `Config::report_format` on the outcome
+    /// of a run to stderr.
+    #[cfg(feature = "std")]
+    fn write_report<T: fmt::Debug>(
+        &self,
+        result: &Result<(), TestError<T>>,
+        failing_seed: Option<Seed>,
+    ) {
+        let (outcome, message, minimal_input) = match *result {
+            Ok(()) => (Outcome::Pass, None, None),
+            Err(TestError::Fail(ref why, ref value)) => (
+                Outcome::Fail,
+                Some(why.message()),
+                Some(format!("{:?}", value)),
+            ),
+            Err(TestError::Abort(ref why)) => {
+                (Outcome::Abort, Some(why.message()), None)
+            }
+        };
+        let report = RunReport {
+            test_name: self.config.test_name,
+            source_file: self.config.source_file,
+            outcome,
+            cases: self.successes,
+            local_rejects: self.local_rejects,
+            global_rejects: self.global_rejects,
+            shrink_iterations: self.shrink_iterations,
+            message,
+            minimal_input,
+            seed: failing_seed.map(|seed| seed.to_persistence()),
+        };
+
+        let mut out = String::new();
+        if report.write(self.config.report_format, &mut out).is_ok() {
+            eprint!("{}", out);
+        }
+    }
+
+    fn run_cases<S: Strategy>(
+        &mut self,
+        strategy: &S,
+        test: impl Fn(S::Value) -> TestCaseResult,
+        replay_from_fork: impl Iterator<Item = TestCaseResult>,
+        fork_output: ForkOutput,
+        failing_seed: &mut Option<Seed>,
+        mut shrink_override: Option<ShrinkOverride<'_, S::Value>>,
     ) -> TestRunResult<S> {
+        #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+        let start_time = std::time::Instant::now();
+        let mut replay_from_fork = replay_from_fork.peekable();
         let old_rng = self.rng.clone();
+        self.defer_shrinking = shrink_override.is_some();
 
         let persisted_failure_seeds: Vec<PersistedSeed> = self
             .config
@@ -596,35 +1033,167 @@ impl TestRunner {
             .map(|f| f.load_persisted_failures2(self.config.source_file))
             .unwrap_or_default();
 
-        let mut result_cache = self.new_cache();
+        let mut ctx = self.new_context(fork_output);
 
-        for PersistedSeed(persisted_seed) in
+        #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+        let persisted_count = persisted_failure_seeds.len();
+        for PersistedSeed(persisted_seed, size) in
             persisted_failure_seeds.into_iter().rev()
         {
-            self.rng.set_seed(persisted_seed);
-            self.gen_and_run_case(
+            self.rng.set_seed(persisted_seed.clone());
+            self.size = size.unwrap_or(MAX_SIZE);
+            let result = self.gen_and_run_case(
                 strategy,
                 &test,
                 &mut replay_from_fork,
-                &mut *result_cache,
-                &mut fork_output,
+                &mut ctx,
                 true,
-            )?;
+            );
+            let result = self.apply_shrink_override(
+                result,
+                &mut shrink_override,
+                &persisted_seed,
+                &mut *ctx.observer,
+            );
+            if result.is_err() {
+                *failing_seed = Some(persisted_seed);
+                return result;
+            }
         }
         self.rng = old_rng;
+        self.size = MAX_SIZE;
+
+        if self.config.regressions_only {
+            verbose_message!(
+                self,
+                INFO_LOG,
+                "Replayed {} persisted failures; not generating new cases",
+                persisted_count
+            );
+            ctx.fork_output.terminate();
+            return Ok(());
+        }
+
+        // The parent of a forked run only replays results and never observes
+        // coverage, so it could not reproduce coverage-guided choices.
+        let mut coverage = match self.config.coverage_source {
+            Some(source) if !self.config.fork() => {
+                Some(CoverageState::new(source()))
+            }
+            _ => None,
+        };
+
+        let mut target = if self.config.targeted && !self.config.fork() {
+            Some(TargetState::new(self.config.cases))
+        } else {
+            None
+        };
+
+        loop {
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            let more_cases = match self.config.max_duration {
+                // The parent of a forked run can't know how long its children
+                // took, so it stops where they did.
+                Some(_)
+                    if self.config.fork() && !ctx.fork_output.is_in_fork() =>
+                {
+                    replay_from_fork.peek().is_some()
+                }
+                Some(budget) => {
+                    self.successes == 0 || start_time.elapsed() < budget
+                }
+                None => self.successes < self.config.cases,
+            };
+            #[cfg(not(all(feature = "std", not(target_arch = "wasm32"))))]
+            let more_cases = self.successes < self.config.cases;
+            if !more_cases {
+                break;
+            }
+
+            let derived = match (&target, &coverage) {
+                (Some(target), _) => Some(target.next_input(&mut self.rng)),
+                (None, Some(coverage)) => {
+                    Some(coverage.next_input(&mut self.rng))
+                }
+                (None, None) => None,
+            };
+            if let Some(ref mut coverage) = coverage {
+                coverage.reset();
+            }
+            let _ = take_reported_utility();
+            let _ = take_labels();
+            let successes_before = self.successes;
+            if self.config.size_ramp {
+                self.size = ramp_size(self.successes, self.config.cases);
+            }
 
-        while self.successes < self.config.cases {
             // Generate a new seed and make an RNG from that so that we know
             // what seed to persist if this case fails.
             let seed = self.rng.gen_get_seed();
-            let result = self.gen_and_run_case(
-                strategy,
-                &test,
-                &mut replay_from_fork,
-                &mut *result_cache,
-                &mut fork_output,
-                false,
+            let mut consumed = None;
+            let (result, seed) = match derived {
+                None => (
+                    self.gen_and_run_case(
+                        strategy,
+                        &test,
+                        &mut replay_from_fork,
+                        &mut ctx,
+                        false,
+                    ),
+                    seed,
+                ),
+                Some(seed) => (
+                    self.gen_from_seed(strategy, &seed).and_then(
+                        |(case, used)| {
+                            consumed = used;
+                            self.run_generated_case(
+                                case,
+                                &test,
+                                &mut replay_from_fork,
+                                &mut ctx,
+                                false,
+                            )
+                        },
+                    ),
+                    seed,
+                ),
+            };
+            let result = self.apply_shrink_override(
+                result,
+                &mut shrink_override,
+                &seed,
+                &mut *ctx.observer,
             );
+
+            if let (Some(ref mut target), Ok(()), Some(utility)) =
+                (&mut target, &result, take_reported_utility())
+            {
+                target.observe(&seed, consumed, utility, &mut self.rng);
+                verbose_message!(
+                    self,
+                    TRACE,
+                    "Test case reported utility {}; current utility is {:?}",
+                    utility,
+                    target.current_utility()
+                );
+            }
+
+            if self.successes > successes_before {
+                self.label_statistics.add_case(take_labels());
+            }
+
+            if let Some(ref mut coverage) = coverage {
+                if result.is_ok() && coverage.observe() {
+                    coverage.add(&seed, consumed);
+                    verbose_message!(
+                        self,
+                        TRACE,
+                        "Test input reached new coverage; corpus size is now {}",
+                        coverage.corpus_len()
+                    );
+                }
+            }
+
             if let Err(TestError::Fail(_, ref value)) = result {
                 if let Some(ref mut failure_persistence) =
                     self.config.failure_persistence
@@ -633,11 +1202,17 @@ impl TestRunner {
 
                     // Don't update the persistence file if we're a child
                     // process. The parent relies on it remaining consistent
-                    // and will take care of updating it itself.
-                    if !fork_output.is_in_fork() {
+                    // and will take care of updating it itself. Nor if the
+                    // failure is the expected outcome.
+                    if !ctx.fork_output.is_in_fork()
+                        && !self.config.expect_failure
+                    {
                         failure_persistence.save_persisted_failure2(
                             *source_file,
-                            PersistedSeed(seed),
+                            PersistedSeed(
+                                seed.clone(),
+                                Some(self.size).filter(|&s| s < MAX_SIZE),
+                            ),
                             value,
                         );
                     }
@@ -645,35 +1220,106 @@ impl TestRunner {
             }
 
             if let Err(e) = result {
-                fork_output.terminate();
+                *failing_seed = Some(seed);
+                ctx.fork_output.terminate();
                 return Err(e.into());
             }
         }
 
-        fork_output.terminate();
+        #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+        if let Some(budget) = self.config.max_duration {
+            verbose_message!(
+                self,
+                INFO_LOG,
+                "Ran {} cases within the time budget of {:?}",
+                self.successes,
+                budget
+            );
+        }
+
+        // A forked child only tallies the labels of the cases it runs, so
+        // the table would be partial; and a structured report on stderr must
+        // not be interleaved with it.
+        #[cfg(feature = "std")]
+        if self.config.verbose >= INFO_LOG
+            && self.config.report_format == ReportFormat::Off
+            && !ctx.fork_output.is_in_fork()
+            && !self.label_statistics.is_empty()
+        {
+            eprint!("proptest: {}", self.label_statistics);
+        }
+        ctx.fork_output.terminate();
+        #[cfg(feature = "std")]
+        if self.config.verbose >= INFO_LOG && 0 != self.local_rejects {
+            let mut table = format!(
+                "local rejects by reason over {} rejects:\n",
+                self.local_rejects
+            );
+            let _ = write_rejection_table(
+                &mut table,
+                &self.local_reject_detail,
+                self.local_rejects,
+            );
+            eprint!("proptest: {}", table);
+        }
         Ok(())
     }
 
+    /// If `result` is a failure and a `ShrinkOverride` is in use, shrink the
+    /// case generated from `seed` with it.
+    fn apply_shrink_override<T: fmt::Debug>(
+        &mut self,
+        result: Result<(), TestError<T>>,
+        shrink_override: &mut Option<ShrinkOverride<'_, T>>,
+        seed: &Seed,
+        observer: &mut dyn TestObserver,
+    ) -> Result<(), TestError<T>> {
+        match (result, shrink_override) {
+            (Err(TestError::Fail(why, _)), Some(shrinker)) => {
+                let (last_failure, minimal) = shrinker(self, seed, observer);
+                let why = last_failure.unwrap_or(why);
+                observer.failure_minimized(&minimal, &why);
+                Err(TestError::Fail(why, minimal))
+            }
+            (result, _) => result,
+        }
+    }
+
     fn gen_and_run_case<S: Strategy>(
         &mut self,
         strategy: &S,
         f: &impl Fn(S::Value) -> TestCaseResult,
         replay_from_fork: &mut impl Iterator<Item = TestCaseResult>,
-        result_cache: &mut dyn ResultCache,
-        fork_output: &mut ForkOutput,
+        ctx: &mut RunContext,
         is_from_persisted_seed: bool,
     ) -> TestRunResult<S> {
         let case = unwrap_or!(strategy.new_tree(self), msg =>
                 return Err(TestError::Abort(msg)));
 
+        self.run_generated_case(
+            case,
+            f,
+            replay_from_fork,
+            ctx,
+            is_from_persisted_seed,
+        )
+    }
+
+    fn run_generated_case<V: ValueTree>(
+        &mut self,
+        case: V,
+        f: &impl Fn(V::Value) -> TestCaseResult,
+        replay_from_fork: &mut impl Iterator<Item = TestCaseResult>,
+        ctx: &mut RunContext,
+        is_from_persisted_seed: bool,
+    ) -> Result<(), TestError<V::Value>> {
         // We only count new cases to our set of successful runs against
         // `PROPTEST_CASES` config.
         let ok_type = self.run_one_with_replay(
             case,
             f,
             replay_from_fork,
-            result_cache,
-            fork_output,
+            ctx,
             is_from_persisted_seed,
         )?;
         match ok_type {
@@ -688,6 +1334,24 @@ impl TestRunner {
         Ok(())
     }
 
+    /// Regenerate the value tree from `seed`, along with the number of bytes
+    /// it consumed if `seed` is a `PassThrough` one.
+    ///
+    /// The runner's own RNG is left as it was, so that revisiting a seed does
+    /// not cause the sequence of fresh seeds to repeat.
+    fn gen_from_seed<S: Strategy>(
+        &mut self,
+        strategy: &S,
+        seed: &Seed,
+    ) -> Result<(S::Tree, Option<usize>), TestError<S::Value>> {
+        let saved_rng = self.rng.clone();
+        self.rng.set_seed(seed.clone());
+        let case = strategy.new_tree(self);
+        let consumed = self.rng.pass_through_consumed();
+        self.rng = saved_rng;
+        Ok((case.map_err(TestError::Abort)?, consumed))
+    }
+
     /// Run one specific test case against this runner.
     ///
     /// If the test fails, finds the minimal failing test case. If the test
@@ -702,13 +1366,12 @@ impl TestRunner {
         case: V,
         test: impl Fn(V::Value) -> TestCaseResult,
     ) -> Result<bool, TestError<V::Value>> {
-        let mut result_cache = self.new_cache();
+        let mut ctx = self.new_context(ForkOutput::empty());
         self.run_one_with_replay(
             case,
             test,
             &mut iter::empty::<TestCaseResult>().fuse(),
-            &mut *result_cache,
-            &mut ForkOutput::empty(),
+            &mut ctx,
             false,
         )
         .map(|ok_type| match ok_type {
@@ -722,8 +1385,7 @@ impl TestRunner {
         mut case: V,
         test: impl Fn(V::Value) -> TestCaseResult,
         replay_from_fork: &mut impl Iterator<Item = TestCaseResult>,
-        result_cache: &mut dyn ResultCache,
-        fork_output: &mut ForkOutput,
+        ctx: &mut RunContext,
         is_from_persisted_seed: bool,
     ) -> Result<TestCaseOk, TestError<V::Value>> {
         let result = call_test(
@@ -731,25 +1393,28 @@ impl TestRunner {
             case.current(),
             &test,
             replay_from_fork,
-            result_cache,
-            fork_output,
+            ctx,
             is_from_persisted_seed,
         );
 
         match result {
             Ok(success_type) => Ok(success_type),
+            Err(TestCaseError::Fail(why)) if self.defer_shrinking => {
+                Err(TestError::Fail(why, case.current()))
+            }
             Err(TestCaseError::Fail(why)) => {
                 let why = self
                     .shrink(
                         &mut case,
                         test,
                         replay_from_fork,
-                        result_cache,
-                        fork_output,
+                        ctx,
                         is_from_persisted_seed,
                     )
                     .unwrap_or(why);
-                Err(TestError::Fail(why, case.current()))
+                let minimal = case.current();
+                ctx.observer.failure_minimized(&minimal, &why);
+                Err(TestError::Fail(why, minimal))
             }
             Err(TestCaseError::Reject(whence)) => {
                 self.reject_global(whence)?;
@@ -758,26 +1423,197 @@ impl TestRunner {
         }
     }
 
-    fn shrink<V: ValueTree>(
+    /// Regenerate the value tree from `seed` and apply the shrinking `steps`
+    /// (`true` for `simplify()`, `false` for `complicate()`) to it, returning
+    /// the tree and whether every step succeeded.
+    #[cfg(feature = "std")]
+    fn replay_shrink_steps<S: Strategy>(
         &mut self,
-        case: &mut V,
-        test: impl Fn(V::Value) -> TestCaseResult,
-        replay_from_fork: &mut impl Iterator<Item = TestCaseResult>,
-        result_cache: &mut dyn ResultCache,
-        fork_output: &mut ForkOutput,
-        is_from_persisted_seed: bool,
-    ) -> Option<Reason> {
-        // exit early if shrink disabled
-        if self.config.max_shrink_iters == 0 {
-            verbose_message!(
-                self,
-                INFO_LOG,
-                "Shrinking disabled by configuration"
-            );
-            return None
+        strategy: &S,
+        seed: &Seed,
+        steps: &[bool],
+    ) -> (S::Tree, bool) {
+        let (mut case, _) = self
+            .gen_from_seed(strategy, seed)
+            .ok()
+            .expect("failing case could not be regenerated");
+        let mut all_ok = true;
+        for &simplify in steps {
+            all_ok &= if simplify {
+                case.simplify()
+            } else {
+                case.complicate()
+            };
         }
+        (case, all_ok)
+    }
 
-        #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    /// Shrink the failing case generated from `seed` as `shrink` would, but
+    /// evaluating up to `threads` speculative candidates at a time.
+    #[cfg(feature = "std")]
+    fn shrink_in_parallel<S: Strategy>(
+        &mut self,
+        strategy: &S,
+        test: &(impl Fn(S::Value) -> TestCaseResult + Sync),
+        seed: &Seed,
+        observer: &mut dyn TestObserver,
+        threads: usize,
+    ) -> (Option<Reason>, S::Value)
+    where
+        S::Value: Send,
+    {
+        // The candidates evaluated in each round, identified by the outcomes
+        // leading to them from the first (`true` for failure, which leads to
+        // `simplify()`, `false` for `complicate()`), in breadth-first order.
+        let mut speculation: Vec<Vec<bool>> = vec![vec![]];
+        let mut next = 0;
+        while speculation.len() < threads {
+            for outcome in [true, false] {
+                let mut outcomes = speculation[next].clone();
+                outcomes.push(outcome);
+                speculation.push(outcomes);
+            }
+            next += 1;
+        }
+        speculation.truncate(threads);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let start_time = std::time::Instant::now();
+        let mut last_failure = None;
+        let mut iterations = 0;
+        let mut steps = vec![true];
+
+        verbose_message!(
+            self,
+            TRACE,
+            "Starting shrinking with {} threads",
+            threads
+        );
+
+        if self.config.max_shrink_iters == 0 {
+            let (case, _) =
+                self.replay_shrink_steps(strategy, seed, &[]);
+            return (None, case.current());
+        }
+
+        loop {
+            #[cfg(not(target_arch = "wasm32"))]
+            let timed_out = self.config.max_shrink_time > 0
+                && start_time.elapsed().as_millis()
+                    > u128::from(self.config.max_shrink_time);
+            #[cfg(target_arch = "wasm32")]
+            let timed_out = false;
+
+            if iterations >= self.config.max_shrink_iters() || timed_out {
+                verbose_message!(
+                    self,
+                    ALWAYS,
+                    "Aborting shrinking after {} iterations",
+                    iterations
+                );
+                // Move back to the most recent failing case
+                let (mut case, _) =
+                    self.replay_shrink_steps(strategy, seed, &steps);
+                while case.complicate() {}
+                return (last_failure, case.current());
+            }
+
+            // Generate every candidate, noting where shrinking would stop
+            // because a step could not be taken.
+            let mut candidates = Vec::with_capacity(speculation.len());
+            for outcomes in &speculation {
+                let mut candidate_steps = steps.clone();
+                candidate_steps.extend_from_slice(outcomes);
+                let (case, all_ok) =
+                    self.replay_shrink_steps(strategy, seed, &candidate_steps);
+                candidates.push(if all_ok { Some(case.current()) } else { None });
+            }
+
+            let diff_formatter = self.config.diff_formatter;
+            let results: Vec<Option<TestCaseResult>> =
+                std::thread::scope(|scope| {
+                    let handles: Vec<_> = candidates
+                        .into_iter()
+                        .map(|candidate| {
+                            candidate.map(|value| {
+                                scope.spawn(move || {
+                                    call_catching_panics(
+                                        diff_formatter,
+                                        || test(value),
+                                    )
+                                })
+                            })
+                        })
+                        .collect();
+                    handles
+                        .into_iter()
+                        .map(|handle| {
+                            handle.map(|handle| {
+                                handle.join().unwrap_or_else(|_| {
+                                    Err(TestCaseError::fail(
+                                        "shrinking thread panicked",
+                                    ))
+                                })
+                            })
+                        })
+                        .collect()
+                });
+
+            // Follow the path selected by the actual outcomes.
+            let mut outcomes = Vec::new();
+            loop {
+                let ix = match speculation.iter().position(|o| *o == outcomes)
+                {
+                    Some(ix) => ix,
+                    None => break,
+                };
+                let result = match results[ix] {
+                    Some(ref result) => result,
+                    None => {
+                        // The step to this candidate could not be taken.
+                        steps.extend(outcomes);
+                        let (case, _) =
+                            self.replay_shrink_steps(strategy, seed, &steps);
+                        return (last_failure, case.current());
+                    }
+                };
+
+                iterations += 1;
+                self.shrink_iterations = self.shrink_iterations.saturating_add(1);
+                let fails = matches!(result, Err(TestCaseError::Fail(_)));
+                observer.shrink_step(iterations, fails);
+                if let Err(TestCaseError::Fail(ref why)) = *result {
+                    last_failure = Some(why.clone());
+                }
+                outcomes.push(fails);
+
+                if iterations >= self.config.max_shrink_iters() {
+                    break;
+                }
+            }
+            steps.extend(outcomes);
+        }
+    }
+
+    fn shrink<V: ValueTree>(
+        &mut self,
+        case: &mut V,
+        test: impl Fn(V::Value) -> TestCaseResult,
+        replay_from_fork: &mut impl Iterator<Item = TestCaseResult>,
+        ctx: &mut RunContext,
+        is_from_persisted_seed: bool,
+    ) -> Option<Reason> {
+        // exit early if shrink disabled
+        if self.config.max_shrink_iters == 0 {
+            verbose_message!(
+                self,
+                INFO_LOG,
+                "Shrinking disabled by configuration"
+            );
+            return None
+        }
+
+        #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
         let start_time = std::time::Instant::now();
         let mut last_failure = None;
         let mut iterations = 0;
@@ -846,23 +1682,28 @@ impl TestRunner {
                 if bail {
                     // Move back to the most recent failing case
                     while case.complicate() {
-                        fork_output.append(&Ok(()));
+                        ctx.fork_output.append(&Ok(()));
                     }
                     break;
                 }
 
                 iterations += 1;
+                self.shrink_iterations = self.shrink_iterations.saturating_add(1);
 
                 let result = call_test(
                     self,
                     case.current(),
                     &test,
                     replay_from_fork,
-                    result_cache,
-                    fork_output,
+                    ctx,
                     is_from_persisted_seed,
                 );
 
+                ctx.observer.shrink_step(
+                    iterations,
+                    matches!(result, Err(TestCaseError::Fail(_))),
+                );
+
                 match result {
                     // Rejections are effectively a pass here,
                     // since they indicate that any behaviour of
@@ -904,7 +1745,16 @@ impl TestRunner {
         whence: impl Into<Reason>,
     ) -> Result<(), Reason> {
         if self.local_rejects >= self.config.max_local_rejects {
-            Err("Too many local rejects".into())
+            // Name the filters responsible, since a strategy may have many.
+            let mut message =
+                String::from("Too many local rejects, by reason:\n");
+            let _ = write_rejection_table(
+                &mut message,
+                &self.local_reject_detail,
+                self.local_rejects,
+            );
+            message.truncate(message.trim_end().len());
+            Err(message.into())
         } else {
             self.local_rejects += 1;
             Self::insert_or_increment(
@@ -944,6 +1794,56 @@ impl TestRunner {
     fn new_cache(&self) -> Box<dyn ResultCache> {
         (self.config.result_cache)()
     }
+
+    fn new_observer(&self) -> Box<dyn TestObserver> {
+        (self.config.observer)()
+    }
+
+    fn new_context(&self, fork_output: ForkOutput) -> RunContext {
+        RunContext {
+            result_cache: self.new_cache(),
+            observer: self.new_observer(),
+            fork_output,
+        }
+    }
+}
+
+/// A value tree over a single persisted value, which is deserialized anew
+/// each time it is requested.
+#[cfg(feature = "serde")]
+struct SerializedValueTree<T> {
+    serialized: String,
+    _marker: core::marker::PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> SerializedValueTree<T> {
+    fn new(serialized: String) -> Self {
+        SerializedValueTree {
+            serialized,
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: fmt::Debug + serde::de::DeserializeOwned> ValueTree
+    for SerializedValueTree<T>
+{
+    type Value = T;
+
+    fn current(&self) -> T {
+        serde_json::from_str(&self.serialized)
+            .expect("persisted value no longer deserializes")
+    }
+
+    fn simplify(&mut self) -> bool {
+        false
+    }
+
+    fn complicate(&mut self) -> bool {
+        false
+    }
 }
 
 #[cfg(feature = "fork")]
@@ -1068,8 +1968,9 @@ fn await_child(
 
 #[cfg(test)]
 mod test {
-    use std::cell::Cell;
+    use std::cell::{Cell, RefCell};
     use std::fs;
+    use std::time::{Duration, Instant};
 
     use super::*;
     use crate::strategy::Strategy;
@@ -1091,6 +1992,54 @@ mod test {
         assert_eq!(config.max_global_rejects + 1, runs.get());
     }
 
+    #[test]
+    fn describes_unequal_values_with_configured_formatter() {
+        let run = |diff_formatter| {
+            let mut runner = TestRunner::new(Config {
+                failure_persistence: None,
+                diff_formatter,
+                ..Config::default()
+            });
+            let result = runner.run(&Just(vec![0u32; 40]), |v| {
+                let mut expected = v.clone();
+                expected[20] = 1;
+                prop_assert_eq!(v, expected);
+                Ok(())
+            });
+            match result {
+                Err(TestError::Fail(why, _)) => String::from(why.message()),
+                e => panic!("Unexpected result: {:?}", e),
+            }
+        };
+
+        let message = run(crate::test_runner::line_diff);
+        assert!(message.contains("\n-     0,\n+     1,\n"), "{}", message);
+        let message = run(crate::test_runner::no_diff);
+        assert!(message.contains("\n  left: `[0, 0,"), "{}", message);
+    }
+
+    #[test]
+    fn names_filters_after_too_many_local_rejects() {
+        let mut runner = TestRunner::default();
+        let input = (0u32..100)
+            .prop_filter("rarely false", |&v| v != 0)
+            .prop_filter("always false", |_| false);
+        let result = runner.run(&input, |_| Ok(()));
+        let why = match result {
+            Err(TestError::Abort(why)) => why,
+            e => panic!("Unexpected result: {:?}", e),
+        };
+        let message = why.message();
+        assert!(
+            message.starts_with("Too many local rejects, by reason:\n"),
+            "{}",
+            message
+        );
+        let always = message.find("always false").unwrap();
+        let rarely = message.find("rarely false").unwrap_or(usize::MAX);
+        assert!(always < rarely, "{}", message);
+    }
+
     #[test]
     fn test_pass() {
         let mut runner = TestRunner::default();
@@ -1118,6 +2067,291 @@ mod test {
         assert_eq!(Err(TestError::Fail("not less than 5".into(), 5)), result);
     }
 
+    #[test]
+    fn expected_failure_passes_and_is_shrunk() {
+        use crate::test_runner::MapFailurePersistence;
+
+        let mut runner = TestRunner::new(Config {
+            failure_persistence: Some(Box::new(
+                MapFailurePersistence::default(),
+            )),
+            source_file: Some("expect_failure"),
+            expect_failure: true,
+            ..Config::default()
+        });
+        let result = runner.run(&(0u32..1000), |v| {
+            prop_assert!(v < 500);
+            Ok(())
+        });
+        assert_eq!(Ok(()), result);
+        assert!(runner.shrink_iterations > 0);
+
+        let persistence = runner.config().failure_persistence.as_ref();
+        let seeds = persistence
+            .unwrap()
+            .load_persisted_failures2(Some("expect_failure"));
+        assert!(seeds.is_empty());
+    }
+
+    #[test]
+    fn expected_failure_not_found_fails() {
+        let mut runner = TestRunner::new(Config {
+            cases: 10,
+            failure_persistence: None,
+            expect_failure: true,
+            ..Config::default()
+        });
+        match runner.run(&(0u32..10), |_| Ok(())) {
+            Err(TestError::Abort(why)) => assert_eq!(
+                "Expected a failing case, but all 10 cases passed",
+                why.message()
+            ),
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn counterexample_carries_value_and_stats() {
+        let mut runner = TestRunner::new(Config {
+            failure_persistence: None,
+            ..Config::default()
+        });
+        let result = runner.run_with_counterexample(&(0u32..1000), |v| {
+            prop_assert!(v < 500);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(why, Counterexample { value, stats })) => {
+                assert!(why.message().contains("v < 500"));
+                assert_eq!(500, value);
+                assert_eq!(runner.stats(), stats);
+                assert_eq!(runner.shrink_iterations, stats.shrink_iterations);
+                assert!(stats.shrink_iterations > 0);
+            }
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn counterexample_passes_through_success_and_abort() {
+        let mut runner = TestRunner::new(Config {
+            cases: 10,
+            failure_persistence: None,
+            ..Config::default()
+        });
+        assert_eq!(
+            Ok(()),
+            runner.run_with_counterexample(&(0u32..10), |_| Ok(()))
+        );
+        assert_eq!(10, runner.stats().successes);
+
+        let mut runner = TestRunner::new(Config {
+            max_global_rejects: 5,
+            failure_persistence: None,
+            ..Config::default()
+        });
+        let result = runner.run_with_counterexample(&(0u32..10), |_| {
+            Err(TestCaseError::reject("never"))
+        });
+        assert!(matches!(result, Err(TestError::Abort(..))));
+        assert_eq!(5, runner.stats().global_rejects);
+    }
+
+    #[test]
+    fn regressions_only_replays_persisted_failures() {
+        use crate::test_runner::{FailurePersistence, MapFailurePersistence};
+
+        let mut persistence = MapFailurePersistence::default();
+        persistence.save_persisted_failure2(
+            Some("regressions_only"),
+            PersistedSeed(TestRunner::deterministic().rng.gen_get_seed(), None),
+            &"",
+        );
+        let config = Config {
+            failure_persistence: Some(Box::new(persistence)),
+            source_file: Some("regressions_only"),
+            regressions_only: true,
+            ..Config::default()
+        };
+
+        let runs = Cell::new(0);
+        let result = TestRunner::new(config.clone()).run(&(0u32..10), |_| {
+            runs.set(runs.get() + 1);
+            Ok(())
+        });
+        assert_eq!(Ok(()), result);
+        assert_eq!(1, runs.get());
+
+        let result = TestRunner::new(config).run(&(0u32..10), |_| {
+            Err(TestCaseError::fail("still broken"))
+        });
+        assert!(matches!(result, Err(TestError::Fail(..))));
+    }
+
+    #[test]
+    fn parallel_shrinking_matches_sequential_shrinking() {
+        use crate::collection;
+
+        let strategy = collection::vec(0u32..1000, 0..50);
+        let test = |v: Vec<u32>| {
+            prop_assert!(v.iter().filter(|&&x| x > 300).count() < 3);
+            Ok(())
+        };
+
+        for threads in [1, 2, 3, 8] {
+            let config = Config {
+                failure_persistence: None,
+                shrink_threads: threads,
+                ..Config::default()
+            };
+            let sequential = TestRunner::new_with_rng(
+                config.clone(),
+                TestRng::deterministic_rng(RngAlgorithm::ChaCha),
+            )
+            .run(&strategy, test);
+            let mut runner = TestRunner::new_with_rng(
+                config,
+                TestRng::deterministic_rng(RngAlgorithm::ChaCha),
+            );
+            let parallel = runner.run_with_parallel_shrinking(&strategy, test);
+
+            assert!(matches!(sequential, Err(TestError::Fail(..))));
+            assert_eq!(sequential, parallel);
+        }
+    }
+
+    #[test]
+    fn max_duration_replaces_case_count() {
+        let config = Config {
+            failure_persistence: None,
+            cases: 1,
+            max_duration: Some(Duration::from_millis(50)),
+            ..Config::default()
+        };
+        let runs = Cell::new(0);
+        let start = Instant::now();
+        let result = TestRunner::new(config).run(&(0u32..10), |_| {
+            runs.set(runs.get() + 1);
+            std::thread::sleep(Duration::from_millis(1));
+            Ok(())
+        });
+        assert_eq!(Ok(()), result);
+        assert!(runs.get() > 1);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        let config = Config {
+            failure_persistence: None,
+            max_duration: Some(Duration::ZERO),
+            ..Config::default()
+        };
+        let runs = Cell::new(0);
+        let result = TestRunner::new(config).run(&(0u32..10), |_| {
+            runs.set(runs.get() + 1);
+            Ok(())
+        });
+        assert_eq!(Ok(()), result);
+        assert_eq!(1, runs.get());
+    }
+
+    #[cfg(all(not(feature = "timeout"), not(target_arch = "wasm32")))]
+    #[test]
+    fn watchdog_fails_and_shrinks_slow_cases() {
+        let mut runner = TestRunner::new(Config {
+            failure_persistence: None,
+            timeout: 50,
+            ..Config::default()
+        });
+        let result = runner.run(&(0u32..100), |v| {
+            if v >= 50 {
+                std::thread::sleep(Duration::from_millis(70));
+            }
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(why, 50)) => {
+                assert!(why.message().starts_with("Timeout of 50 ms exceeded"))
+            }
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
+    #[cfg(all(not(feature = "timeout"), not(target_arch = "wasm32")))]
+    #[test]
+    fn watchdog_fails_only_the_test_with_hanging_cases() {
+        let mut runner = TestRunner::new(Config {
+            failure_persistence: None,
+            timeout: 20,
+            ..Config::default()
+        });
+        // Cases from 5 take long enough to be reported as hanging, which
+        // used to abort the whole test process.
+        let result = runner.run(&(0u32..10), |v| {
+            if v >= 5 {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(why, 5)) => {
+                assert!(why.message().starts_with("Timeout of 20 ms exceeded"))
+            }
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn size_ramp_grows_inputs_and_persists_size() {
+        use crate::collection;
+        use crate::test_runner::MapFailurePersistence;
+
+        let strategy = collection::vec(0u32..10, 0..100);
+        let config = Config {
+            failure_persistence: Some(Box::new(
+                MapFailurePersistence::default(),
+            )),
+            source_file: Some("size_ramp"),
+            size_ramp: true,
+            ..Config::default()
+        };
+
+        let lengths = RefCell::new(Vec::new());
+        TestRunner::new(config.clone())
+            .run(&strategy, |v| {
+                lengths.borrow_mut().push(v.len());
+                Ok(())
+            })
+            .unwrap();
+        let lengths = lengths.into_inner();
+        assert_eq!(0, lengths[0]);
+        assert!(lengths[..10].iter().all(|&len| len < 10));
+        assert!(lengths.iter().any(|&len| len > 50));
+
+        let test = |v: Vec<u32>| {
+            prop_assert!(v.len() < 20);
+            Ok(())
+        };
+        let mut runner = TestRunner::new(config);
+        let minimal = match runner.run(&strategy, test) {
+            Err(TestError::Fail(_, minimal)) => minimal,
+            e => panic!("Unexpected result: {:?}", e),
+        };
+        let persistence = runner.config().failure_persistence.clone().unwrap();
+        let seeds = persistence.load_persisted_failures2(Some("size_ramp"));
+        assert_eq!(1, seeds.len());
+        assert!(seeds[0].1.map_or(false, |size| size < MAX_SIZE));
+
+        let mut replay = TestRunner::new(Config {
+            failure_persistence: Some(persistence),
+            source_file: Some("size_ramp"),
+            regressions_only: true,
+            ..Config::default()
+        });
+        match replay.run(&strategy, test) {
+            Err(TestError::Fail(_, value)) => assert_eq!(minimal, value),
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
     #[test]
     fn test_fail_via_panic() {
         let mut runner = TestRunner::new(Config {
@@ -1477,6 +2711,309 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn coverage_source_observes_every_case() {
+        use crate::test_runner::{CoverageCounters, CoverageSource};
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::LazyLock;
+
+        static EDGES: LazyLock<CoverageCounters> =
+            LazyLock::new(|| CoverageCounters::new(10));
+        static RESETS: AtomicUsize = AtomicUsize::new(0);
+
+        struct CountingSource(CoverageCounters);
+        impl CoverageSource for CountingSource {
+            fn reset(&mut self) {
+                RESETS.fetch_add(1, SeqCst);
+                self.0.reset();
+            }
+
+            fn for_each_hit(&self, f: &mut dyn FnMut(usize, u8)) {
+                self.0.for_each_hit(f)
+            }
+        }
+
+        fn source() -> Box<dyn CoverageSource> {
+            Box::new(CountingSource(EDGES.clone()))
+        }
+
+        let runs = Cell::new(0);
+        let mut runner = TestRunner::new(Config {
+            failure_persistence: None,
+            coverage_source: Some(source),
+            ..Config::default()
+        });
+        let result = runner.run(&(0u32..1_000_000), |v| {
+            runs.set(runs.get() + 1);
+            EDGES.hit(v as usize / 100_000);
+            Ok(())
+        });
+
+        assert_eq!(Ok(()), result);
+        assert_eq!(runs.get(), RESETS.load(SeqCst));
+    }
+
+    #[test]
+    fn targeted_search_climbs_toward_greater_utility() {
+        use crate::test_runner::report_utility;
+
+        const TARGET: i64 = 12_345_678;
+        let closest = Cell::new(i64::MAX);
+        let mut runner = TestRunner::new_with_rng(
+            Config {
+                cases: 1024,
+                failure_persistence: None,
+                targeted: true,
+                ..Config::default()
+            },
+            TestRng::deterministic_rng(RngAlgorithm::ChaCha),
+        );
+        let result = runner.run(&(0u32..100_000_000), |v| {
+            let distance = (i64::from(v) - TARGET).abs();
+            closest.set(closest.get().min(distance));
+            report_utility(-distance as f64);
+            Ok(())
+        });
+
+        assert_eq!(Ok(()), result);
+        // Blind generation would typically get no closer than ~50_000.
+        assert!(closest.get() < 2_000, "only got within {}", closest.get());
+    }
+
+    #[test]
+    fn labels_tallied_over_passing_cases() {
+        let mut runner = TestRunner::new(Config {
+            cases: 100,
+            failure_persistence: None,
+            ..Config::default()
+        });
+        let result = runner.run(&(0u32..10), |v| {
+            prop_assume!(v != 9);
+            prop_classify!(v < 5, "small");
+            prop_classify!(v < 5, "small");
+            prop_collect!(v % 2 == 0);
+            Ok(())
+        });
+
+        assert_eq!(Ok(()), result);
+        let stats = runner.label_statistics();
+        assert_eq!(100, stats.cases());
+        assert_eq!(
+            100,
+            stats.count("true") + stats.count("false"),
+        );
+        assert!(stats.count("small") > 0 && stats.count("small") < 100);
+    }
+
+    #[test]
+    fn observer_sees_cases_and_shrinking() {
+        use std::cell::RefCell;
+        use std::string::ToString;
+
+        std::thread_local! {
+            static EVENTS: RefCell<Vec<String>> = const {
+                RefCell::new(Vec::new())
+            };
+        }
+
+        struct Recorder;
+        impl TestObserver for Recorder {
+            fn case_started(&mut self, input: &dyn fmt::Debug) {
+                EVENTS.with(|e| e.borrow_mut().push(format!("{:?}", input)));
+            }
+            fn case_passed(&mut self) {
+                EVENTS.with(|e| e.borrow_mut().push("pass".to_string()));
+            }
+            fn case_rejected(&mut self, _: &Reason) {
+                EVENTS.with(|e| e.borrow_mut().push("reject".to_string()));
+            }
+            fn case_failed(&mut self, _: &Reason) {
+                EVENTS.with(|e| e.borrow_mut().push("fail".to_string()));
+            }
+            fn shrink_step(&mut self, iteration: u32, still_fails: bool) {
+                EVENTS.with(|e| {
+                    e.borrow_mut()
+                        .push(format!("shrink {} {}", iteration, still_fails))
+                });
+            }
+            fn failure_minimized(
+                &mut self,
+                input: &dyn fmt::Debug,
+                _: &Reason,
+            ) {
+                EVENTS.with(|e| {
+                    e.borrow_mut().push(format!("minimal {:?}", input))
+                });
+            }
+        }
+
+        let mut runner = TestRunner::new(Config {
+            failure_persistence: None,
+            observer: || Box::new(Recorder),
+            ..Config::default()
+        });
+        let result = runner.run(&(0u32..1000), |v| {
+            prop_assume!(v % 7 != 0);
+            prop_assert!(v < 500);
+            Ok(())
+        });
+        let minimal = match result {
+            Err(TestError::Fail(_, v)) => v,
+            other => panic!("unexpected result: {:?}", other),
+        };
+
+        let events = EVENTS.with(|e| e.take());
+        let outcomes = ["pass", "reject", "fail"];
+        // Every started case reports exactly one outcome.
+        let mut pending = false;
+        for event in &events {
+            if outcomes.contains(&event.as_str()) {
+                assert!(pending);
+                pending = false;
+            } else if !event.starts_with("shrink")
+                && !event.starts_with("minimal")
+            {
+                assert!(!pending);
+                pending = true;
+            }
+        }
+        assert!(events.iter().any(|e| e.starts_with("shrink 1 ")));
+        assert_eq!(&format!("minimal {}", minimal), events.last().unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_values_outlive_strategy_changes() {
+        use crate::strategy::Just;
+        use crate::std_facade::ToOwned;
+        use crate::test_runner::MapFailurePersistence;
+
+        let test = |v: Vec<u32>| {
+            prop_assert!(v.iter().all(|&x| x < 50));
+            Ok(())
+        };
+
+        let mut runner = TestRunner::new(Config {
+            failure_persistence: Some(Box::new(
+                MapFailurePersistence::default(),
+            )),
+            source_file: Some("serialized"),
+            ..Config::default()
+        });
+        let result = runner.run_serialized(
+            &crate::collection::vec(0u32..100, 1..10),
+            test,
+        );
+        assert!(matches!(result, Err(TestError::Fail(_, ref v)) if v == &[50]));
+
+        let persistence = runner.config.failure_persistence.clone();
+        assert_eq!(
+            vec!["[50]".to_owned()],
+            persistence
+                .as_ref()
+                .unwrap()
+                .load_persisted_values(Some("serialized"))
+        );
+
+        // A strategy which can no longer produce the failure still reports
+        // it, since the value itself was persisted.
+        let mut runner = TestRunner::new(Config {
+            failure_persistence: persistence,
+            source_file: Some("serialized"),
+            ..Config::default()
+        });
+        let result = runner.run_serialized(&Just(vec![0u32]), test);
+        assert!(matches!(result, Err(TestError::Fail(_, ref v)) if v == &[50]));
+    }
+
+    #[test]
+    fn seeds_regenerate_their_case() {
+        let mut runner = TestRunner::deterministic();
+        let seed = runner.rng.new_rng_seed();
+
+        let expected = {
+            let mut runner = TestRunner::deterministic();
+            runner.rng.set_seed(seed.clone());
+            (0u32..1_000_000).new_tree(&mut runner).unwrap()
+        };
+
+        let (actual, consumed) =
+            runner.gen_from_seed(&(0u32..1_000_000), &seed).unwrap();
+        assert_eq!(expected.current(), actual.current());
+        assert_eq!(None, consumed);
+    }
+
+    #[test]
+    fn coverage_guidance_reaches_deep_branches() {
+        use crate::arbitrary::any;
+        use crate::test_runner::{
+            CoverageCounters, CoverageSource, MapFailurePersistence,
+        };
+        use std::sync::LazyLock;
+
+        static EDGES: LazyLock<CoverageCounters> =
+            LazyLock::new(|| CoverageCounters::new(8));
+
+        fn source() -> Box<dyn CoverageSource> {
+            Box::new(EDGES.clone())
+        }
+
+        // Failing needs five bytes to each fall into one of 16 classes,
+        // which blind generation has a one in a million chance of per case.
+        // Each byte matched in turn hits a new edge.
+        const MAGIC: [u8; 5] = [3, 1, 4, 1, 5];
+        let test = |bytes: [u8; 5]| {
+            for (i, (&byte, &magic)) in bytes.iter().zip(&MAGIC).enumerate() {
+                if byte % 16 != magic {
+                    return Ok(());
+                }
+                EDGES.hit(i);
+            }
+            Err(TestCaseError::fail("reached the deepest branch"))
+        };
+        let config = Config {
+            cases: 20_000,
+            failure_persistence: Some(Box::new(
+                MapFailurePersistence::default(),
+            )),
+            source_file: Some("coverage"),
+            ..Config::default()
+        };
+
+        let mut blind = TestRunner::new_with_rng(
+            config.clone(),
+            TestRng::deterministic_rng(RngAlgorithm::ChaCha),
+        );
+        assert_eq!(Ok(()), blind.run(&any::<[u8; 5]>(), &test));
+
+        let mut guided = TestRunner::new_with_rng(
+            Config {
+                coverage_source: Some(source),
+                ..config.clone()
+            },
+            TestRng::deterministic_rng(RngAlgorithm::ChaCha),
+        );
+        match guided.run(&any::<[u8; 5]>(), &test) {
+            Err(TestError::Fail(_, bytes)) => {
+                for (&byte, &magic) in bytes.iter().zip(&MAGIC) {
+                    assert_eq!(magic, byte % 16);
+                }
+            }
+            e => panic!("Unexpected result: {:?}", e),
+        }
+
+        // The failure is persisted, and reproduced without guidance.
+        let mut replay = TestRunner::new(Config {
+            failure_persistence: guided.config().failure_persistence.clone(),
+            cases: 1,
+            ..config
+        });
+        assert!(matches!(
+            replay.run(&any::<[u8; 5]>(), &test),
+            Err(TestError::Fail(..))
+        ));
+    }
 }
 
 #[cfg(all(feature = "fork", feature = "timeout", test))]
@@ -0,0 +1,134 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support for `prop_classify!` and `prop_collect!`.
+//!
+//! Labels recorded while a test case runs are buffered per thread; the
+//! runner takes them after each passing case and tallies them, so that the
+//! distribution of labels can be reported once the test finishes.
+
+use crate::std_facade::{BTreeMap, String, Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static LABELS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Record `label` against the current test case.
+///
+/// This is normally invoked through `prop_classify!` or `prop_collect!`. A
+/// label recorded several times within one case is only counted once.
+///
+/// Without the `std` feature, this does nothing.
+pub fn record_label(label: String) {
+    #[cfg(feature = "std")]
+    LABELS.with(|labels| labels.borrow_mut().push(label));
+    #[cfg(not(feature = "std"))]
+    let _ = label;
+}
+
+/// Take the labels recorded since the last call.
+pub(crate) fn take_labels() -> Vec<String> {
+    #[cfg(feature = "std")]
+    {
+        LABELS.with(|labels| core::mem::take(&mut *labels.borrow_mut()))
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        Vec::new()
+    }
+}
+
+/// The distribution of labels over the passing cases of a test.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LabelStatistics {
+    cases: u32,
+    counts: BTreeMap<String, u32>,
+}
+
+impl LabelStatistics {
+    /// Tally the labels recorded by one passing case.
+    pub(crate) fn add_case(&mut self, mut labels: Vec<String>) {
+        labels.sort();
+        labels.dedup();
+        self.cases += 1;
+        for label in labels {
+            *self.counts.entry(label).or_insert(0) += 1;
+        }
+    }
+
+    /// Return the number of passing cases tallied.
+    pub fn cases(&self) -> u32 {
+        self.cases
+    }
+
+    /// Return the number of passing cases which recorded `label`.
+    pub fn count(&self, label: &str) -> u32 {
+        self.counts.get(label).copied().unwrap_or(0)
+    }
+
+    /// Iterate over each label recorded and the number of passing cases which
+    /// recorded it, in order of label.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> + '_ {
+        self.counts.iter().map(|(label, &count)| (label.as_str(), count))
+    }
+
+    /// Return whether no labels have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+/// Formats the distribution as a table, most frequent label first.
+impl fmt::Display for LabelStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "label distribution over {} cases:", self.cases)?;
+        let mut rows: Vec<_> = self.iter().collect();
+        rows.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        for (label, count) in rows {
+            let percent = if self.cases == 0 {
+                0.0
+            } else {
+                f64::from(count) * 100.0 / f64::from(self.cases)
+            };
+            writeln!(f, "\t{:6.2}% {}", percent, label)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::std_facade::ToOwned;
+    use std::string::ToString;
+
+    #[test]
+    fn labels_counted_once_per_case() {
+        let mut stats = LabelStatistics::default();
+        stats.add_case(vec!["a".to_owned(), "b".to_owned(), "a".to_owned()]);
+        stats.add_case(vec!["a".to_owned()]);
+        stats.add_case(vec![]);
+        stats.add_case(vec!["a".to_owned()]);
+
+        assert_eq!(4, stats.cases());
+        assert_eq!(3, stats.count("a"));
+        assert_eq!(1, stats.count("b"));
+        assert_eq!(0, stats.count("c"));
+        assert_eq!(
+            "label distribution over 4 cases:\n\
+             \t 75.00% a\n\
+             \t 25.00% b\n",
+            stats.to_string()
+        );
+    }
+}
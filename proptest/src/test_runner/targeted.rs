@@ -0,0 +1,248 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support for targeted property testing.
+//!
+//! When `Config::targeted` is set, each input is generated from an explicit
+//! buffer of random bytes (via the `PassThrough` RNG) rather than from a
+//! seed. After a case which reported a utility with `report_utility`, the
+//! runner decides by simulated annealing whether to move to that input, and
+//! subsequent inputs are generated by mutating a few bytes of the current
+//! one. Since strategies consume the buffer sequentially, a small change to
+//! the bytes usually means a small change to the generated value, which
+//! lets the search climb toward inputs with greater utility.
+
+use crate::std_facade::{Arc, Vec};
+#[cfg(feature = "std")]
+use std::cell::Cell;
+
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
+use rand::Rng;
+
+use crate::test_runner::rng::{Seed, TestRng};
+
+/// The number of random bytes each targeted input is generated from.
+const INPUT_LEN: usize = 4096;
+
+/// The temperature at the start of the search, relative to the typical
+/// recent improvement in utility.
+const INITIAL_TEMPERATURE: f64 = 1.0;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static REPORTED_UTILITY: Cell<Option<f64>> = const { Cell::new(None) };
+}
+
+/// Report the utility of the current test case for targeted property
+/// testing.
+///
+/// When `Config::targeted` is enabled, the runner searches for inputs which
+/// maximise the utility, so a property like "the queue never holds more than
+/// `N` items" can report the maximum queue depth it observed to steer
+/// generation toward inputs which stress the bound.
+///
+/// If called several times within one case, the last value wins. Calls have
+/// no effect when targeted search is disabled.
+///
+/// ```
+/// use proptest::prelude::*;
+/// use proptest::test_runner::report_utility;
+///
+/// proptest! {
+///   #![proptest_config(ProptestConfig {
+///     targeted: true, .. ProptestConfig::default()
+///   })]
+///   # /*
+///   #[test]
+///   # */
+///   fn queue_depth_is_bounded(ops in prop::collection::vec(any::<bool>(), 0..64)) {
+///     let mut depth = 0i32;
+///     let mut max_depth = 0;
+///     for push in ops {
+///       depth = if push { depth + 1 } else { (depth - 1).max(0) };
+///       max_depth = max_depth.max(depth);
+///     }
+///     report_utility(max_depth as f64);
+///     prop_assert!(max_depth <= 64);
+///   }
+/// }
+/// # fn main() { queue_depth_is_bounded(); }
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn report_utility(utility: f64) {
+    REPORTED_UTILITY.with(|cell| cell.set(Some(utility)));
+}
+
+/// Take the utility reported by the last case, if any.
+#[cfg(feature = "std")]
+pub(crate) fn take_reported_utility() -> Option<f64> {
+    REPORTED_UTILITY.with(Cell::take)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn take_reported_utility() -> Option<f64> {
+    None
+}
+
+/// The input the search is currently at.
+struct Current {
+    data: Arc<[u8]>,
+    /// How many bytes from the front of `data` were drawn directly by the
+    /// strategy, if known.
+    consumed: usize,
+    utility: f64,
+}
+
+/// Simulated annealing state for a single test run.
+pub(crate) struct TargetState {
+    current: Option<Current>,
+    /// A running average of the size of recent improvements in utility, used
+    /// as the scale of the temperature.
+    mean_delta: f64,
+    steps: u32,
+    max_steps: u32,
+}
+
+impl TargetState {
+    pub(crate) fn new(max_steps: u32) -> Self {
+        Self {
+            current: None,
+            mean_delta: 1.0,
+            steps: 0,
+            max_steps,
+        }
+    }
+
+    /// Choose the seed for the next input: fresh random bytes until some
+    /// case has reported a utility, then a mutation of the current input.
+    pub(crate) fn next_input(&self, rng: &mut TestRng) -> Seed {
+        let data: Arc<[u8]> = match self.current {
+            None => {
+                let mut data = vec![0u8; INPUT_LEN];
+                rng.fill(&mut data[..]);
+                data.into()
+            }
+            Some(ref current) => {
+                let mut data: Vec<u8> = current.data.to_vec();
+                for _ in 0..rng.random_range(1..=2) {
+                    // Mostly mutate the bytes the strategy is known to have
+                    // drawn, but sometimes pick anywhere (log-uniformly, to
+                    // favour the front) since sub-RNGs draw from the back.
+                    let ix = if current.consumed > 0 && rng.random_bool(0.75) {
+                        rng.random_range(0..current.consumed)
+                    } else {
+                        let bits =
+                            rng.random_range(0..=INPUT_LEN.trailing_zeros());
+                        rng.random_range(0..(1usize << bits).min(INPUT_LEN))
+                    };
+                    data[ix] = match rng.random_range(0..4) {
+                        0 => data[ix].wrapping_add(1),
+                        1 => data[ix].wrapping_sub(1),
+                        2 => data[ix] ^ (1 << rng.random_range(0..8)),
+                        _ => rng.random(),
+                    };
+                }
+                data.into()
+            }
+        };
+        Seed::PassThrough(None, data)
+    }
+
+    /// Consider moving to the input generated from `seed`, which consumed
+    /// `consumed` bytes and had the given utility.
+    ///
+    /// Better inputs are always accepted; worse ones with a probability which
+    /// decreases with how much worse they are and as the run progresses.
+    pub(crate) fn observe(
+        &mut self,
+        seed: &Seed,
+        consumed: Option<usize>,
+        utility: f64,
+        rng: &mut TestRng,
+    ) {
+        let data = match *seed {
+            Seed::PassThrough(None, ref data) => Arc::clone(data),
+            _ => return,
+        };
+        if utility.is_nan() {
+            return;
+        }
+
+        self.steps = self.steps.saturating_add(1);
+        let accept = match self.current {
+            None => true,
+            Some(ref current) => {
+                let delta = utility - current.utility;
+                if delta > 0.0 && delta.is_finite() {
+                    self.mean_delta = 0.9 * self.mean_delta + 0.1 * delta;
+                }
+
+                let temperature = INITIAL_TEMPERATURE
+                    * (1.0
+                        - f64::from(self.steps)
+                            / f64::from(self.max_steps.max(1)));
+                delta >= 0.0
+                    || (temperature > 0.0
+                        && rng.random_bool(
+                            (delta / (temperature * self.mean_delta))
+                                .exp()
+                                .clamp(0.0, 1.0),
+                        ))
+            }
+        };
+
+        if accept {
+            self.current = Some(Current {
+                data,
+                consumed: consumed.unwrap_or(0).min(INPUT_LEN),
+                utility,
+            });
+        }
+    }
+
+    /// Return the utility of the current input, if any.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn current_utility(&self) -> Option<f64> {
+        self.current.as_ref().map(|current| current.utility)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_runner::RngAlgorithm;
+
+    #[test]
+    fn better_inputs_are_always_accepted() {
+        let mut rng = TestRng::deterministic_rng(RngAlgorithm::ChaCha);
+        let mut state = TargetState::new(10);
+
+        let first = state.next_input(&mut rng);
+        state.observe(&first, Some(8), 1.0, &mut rng);
+        assert_eq!(Some(1.0), state.current_utility());
+
+        let second = state.next_input(&mut rng);
+        state.observe(&second, Some(8), 5.0, &mut rng);
+        assert_eq!(Some(5.0), state.current_utility());
+    }
+
+    #[test]
+    fn much_worse_inputs_are_rejected_when_cold() {
+        let mut rng = TestRng::deterministic_rng(RngAlgorithm::ChaCha);
+        let mut state = TargetState::new(1);
+
+        let first = state.next_input(&mut rng);
+        state.observe(&first, Some(8), 100.0, &mut rng);
+        let second = state.next_input(&mut rng);
+        state.observe(&second, Some(8), -100.0, &mut rng);
+        assert_eq!(Some(100.0), state.current_utility());
+    }
+}
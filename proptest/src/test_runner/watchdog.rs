@@ -0,0 +1,143 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A thread which enforces `Config::timeout` for test cases run in-process.
+
+use std::string::String;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+struct State {
+    /// The case currently running, if any, with the time after which it is
+    /// reported as hanging, or `None` once it has been.
+    armed: Option<(Option<Instant>, String)>,
+    shutdown: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+/// Watches over test cases run in the current process.
+///
+/// A thread cannot be interrupted, so a case which overruns the timeout is
+/// only failed once it returns. But if it has not returned after twice the
+/// timeout, it is assumed to hang, and the watchdog reports its input so that
+/// it can be diagnosed even if it never returns. Other tests in the process
+/// are unaffected.
+pub(crate) struct Watchdog {
+    timeout: u32,
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    /// Start a watchdog enforcing a timeout of `timeout` milliseconds.
+    pub(crate) fn new(timeout: u32) -> Self {
+        let shared = Arc::new(Shared::default());
+        let thread = {
+            let shared = Arc::clone(&shared);
+            thread::Builder::new()
+                .name("proptest-watchdog".into())
+                .spawn(move || watch(&shared, timeout))
+                .expect("Failed to spawn watchdog thread")
+        };
+        Watchdog {
+            timeout,
+            shared,
+            thread: Some(thread),
+        }
+    }
+
+    /// Note that a case with the given `input` is starting.
+    pub(crate) fn arm(&self, input: String) {
+        let deadline =
+            Instant::now() + Duration::from_millis(u64::from(self.timeout) * 2);
+        self.shared.state.lock().unwrap().armed = Some((Some(deadline), input));
+        self.shared.changed.notify_one();
+    }
+
+    /// Note that the case last passed to `arm` has returned.
+    pub(crate) fn disarm(&self) {
+        self.shared.state.lock().unwrap().armed = None;
+        self.shared.changed.notify_one();
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.shutdown = true;
+        }
+        self.shared.changed.notify_one();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn watch(shared: &Shared, timeout: u32) {
+    let mut state = shared.state.lock().unwrap();
+    loop {
+        if state.shutdown {
+            return;
+        }
+
+        let now = Instant::now();
+        let wait = match state.armed {
+            Some((ref mut deadline, ref input)) => match *deadline {
+                Some(at) if now >= at => {
+                    eprintln!(
+                        "proptest: Test case has not returned after {} ms \
+                         (timeout is {} ms); it will fail if it returns.\n\
+                         proptest: The hanging input is: {}",
+                        u64::from(timeout) * 2,
+                        timeout,
+                        input
+                    );
+                    *deadline = None;
+                    None
+                }
+                Some(at) => Some(at - now),
+                None => None,
+            },
+            None => None,
+        };
+
+        state = match wait {
+            None => shared.changed.wait(state).unwrap(),
+            Some(wait) => shared.changed.wait_timeout(state, wait).unwrap().0,
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hanging_case_is_reported_without_aborting() {
+        let watchdog = Watchdog::new(10);
+        watchdog.arm("hangs".into());
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(
+            Some((None, "hangs".into())),
+            watchdog.shared.state.lock().unwrap().armed
+        );
+        watchdog.disarm();
+
+        watchdog.arm("returns".into());
+        watchdog.disarm();
+        assert_eq!(None, watchdog.shared.state.lock().unwrap().armed);
+    }
+}
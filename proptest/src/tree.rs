@@ -0,0 +1,650 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating trees with control over their size and shape.
+//!
+//! `Strategy::prop_recursive` can build any recursive type, but only loosely
+//! bounds the number of nodes and shrinks one level of recursion at a time.
+//! The strategies here generate `RoseTree`s, where a node may have any number
+//! of children, and `BinaryTree`s. The number of nodes is drawn from a size
+//! range, the depth is bounded, and rose trees can bound the number of
+//! children of each node.
+//!
+//! The trees shrink by pruning whole subtrees, those nearest the root first,
+//! as long as the tree keeps its minimum number of nodes, and then by
+//! shrinking the payloads of the nodes which remain.
+//!
+//! ```rust
+//! use proptest::prelude::*;
+//! use proptest::tree::{self, RoseTree};
+//!
+//! fn sum(tree: &RoseTree<u32>) -> u32 {
+//!     tree.value + tree.children.iter().map(sum).sum::<u32>()
+//! }
+//!
+//! proptest! {
+//!     #[test]
+//!     fn sum_is_at_least_root(t in tree::rose_tree(0u32..100).max_depth(4)) {
+//!         prop_assert!(sum(&t) >= t.value);
+//!     }
+//! }
+//! # fn main() { }
+//! ```
+
+use crate::std_facade::{Box, Vec};
+use core::mem;
+
+use rand::Rng;
+
+use crate::bits::{BitSetLike, VarBitSet};
+use crate::collection::{size_range, SizeRange};
+use crate::num::sample_uniform_incl;
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// A tree in which each node holds a value and any number of children.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RoseTree<T> {
+    /// The payload of this node.
+    pub value: T,
+    /// The subtrees below this node, in order.
+    pub children: Vec<RoseTree<T>>,
+}
+
+impl<T> RoseTree<T> {
+    /// Return the number of nodes in this tree, including the root.
+    pub fn node_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(RoseTree::node_count)
+            .sum::<usize>()
+    }
+
+    /// Return the number of levels in this tree. A tree with only a root has
+    /// a depth of 1.
+    pub fn depth(&self) -> usize {
+        1 + self.children.iter().map(RoseTree::depth).max().unwrap_or(0)
+    }
+}
+
+/// A tree in which each node holds a value and up to two children.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BinaryTree<T> {
+    /// The payload of this node.
+    pub value: T,
+    /// The left subtree, if any.
+    pub left: Option<Box<BinaryTree<T>>>,
+    /// The right subtree, if any.
+    pub right: Option<Box<BinaryTree<T>>>,
+}
+
+impl<T> BinaryTree<T> {
+    /// Return the number of nodes in this tree, including the root.
+    pub fn node_count(&self) -> usize {
+        1 + self.subtrees().map(BinaryTree::node_count).sum::<usize>()
+    }
+
+    /// Return the number of levels in this tree. A tree with only a root has
+    /// a depth of 1.
+    pub fn depth(&self) -> usize {
+        1 + self.subtrees().map(BinaryTree::depth).max().unwrap_or(0)
+    }
+
+    fn subtrees(&self) -> impl Iterator<Item = &BinaryTree<T>> {
+        self.left.iter().chain(self.right.iter()).map(|t| &**t)
+    }
+}
+
+/// Return the largest number of nodes a tree with at most `max_depth` levels
+/// and `max_children` children per node can have.
+fn max_nodes(max_depth: usize, max_children: usize) -> usize {
+    let mut total = 0usize;
+    let mut level = 1usize;
+    for _ in 0..max_depth {
+        total = total.saturating_add(level);
+        level = level.saturating_mul(max_children);
+    }
+    total
+}
+
+/// Generate the shape of a tree and the payload of each of its nodes.
+///
+/// When `binary` is set, each child is placed at random in the left (slot 0)
+/// or right (slot 1) position of its parent; otherwise children occupy
+/// consecutive slots in the order they are created.
+fn new_nodes<T: Strategy>(
+    runner: &mut TestRunner,
+    payload: &T,
+    size: &SizeRange,
+    max_depth: usize,
+    max_children: usize,
+    binary: bool,
+) -> Result<Nodes<T::Tree>, Reason> {
+    let limit = max_nodes(max_depth, max_children);
+    let (start, end) = size.start_end_incl();
+    assert!(
+        start <= limit,
+        "Trees with at most {} levels and {} children per node have at \
+         most {} nodes, but at least {} were requested",
+        max_depth,
+        max_children,
+        limit,
+        start
+    );
+    let end = runner.sized(start, end.min(limit));
+    let len = sample_uniform_incl(runner, start, end);
+
+    let mut parents = Vec::with_capacity(len);
+    let mut slots = Vec::with_capacity(len);
+    let mut depths = Vec::with_capacity(len);
+    let mut child_counts = Vec::with_capacity(len);
+    // The slot of the first child of each node, if it has one.
+    let mut first_slots = Vec::with_capacity(len);
+    // Nodes which can still take another child.
+    let mut open = Vec::new();
+
+    parents.push(0);
+    slots.push(0);
+    depths.push(1);
+    child_counts.push(0);
+    first_slots.push(0);
+    if max_depth > 1 && max_children > 0 {
+        open.push(0);
+    }
+
+    while parents.len() < len {
+        let pick = runner.rng().random_range(0..open.len());
+        let parent = open[pick];
+
+        let slot = if !binary {
+            child_counts[parent]
+        } else if child_counts[parent] == 0 {
+            runner.rng().random_range(0..2)
+        } else {
+            // The other side from the existing child.
+            1 - first_slots[parent]
+        };
+
+        let ix = parents.len();
+        parents.push(parent);
+        slots.push(slot);
+        depths.push(depths[parent] + 1);
+        child_counts.push(0);
+        first_slots.push(0);
+
+        if child_counts[parent] == 0 {
+            first_slots[parent] = slot;
+        }
+        child_counts[parent] += 1;
+        if child_counts[parent] == max_children {
+            open.swap_remove(pick);
+        }
+        if depths[ix] < max_depth {
+            open.push(ix);
+        }
+    }
+
+    let mut payloads = Vec::with_capacity(len);
+    while payloads.len() < len {
+        payloads.push(payload.new_tree(runner)?);
+    }
+
+    Ok(Nodes {
+        payloads,
+        parents,
+        slots,
+        pruned: VarBitSet::new_bitset(len),
+        min_size: start,
+        shrink: Shrink::Prune(1),
+        prev_shrink: None,
+    })
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Shrink {
+    Prune(usize),
+    ShrinkPayload(usize),
+}
+
+/// The nodes of a generated tree, in the order they were created, so every
+/// node comes after its parent.
+#[derive(Clone, Debug)]
+struct Nodes<T: ValueTree> {
+    payloads: Vec<T>,
+    // The parent of each node; meaningless for the root at index 0.
+    parents: Vec<usize>,
+    // The position of each node among the children of its parent.
+    slots: Vec<usize>,
+    // The roots of the subtrees which have been pruned.
+    pruned: VarBitSet,
+    min_size: usize,
+    shrink: Shrink,
+    prev_shrink: Option<Shrink>,
+}
+
+impl<T: ValueTree> Nodes<T> {
+    /// Return whether each node is still in the tree, i.e., neither it nor
+    /// any of its ancestors has been pruned.
+    fn live(&self) -> Vec<bool> {
+        let mut live = Vec::with_capacity(self.parents.len());
+        live.push(true);
+        for ix in 1..self.parents.len() {
+            live.push(!self.pruned.test(ix) && live[self.parents[ix]]);
+        }
+        live
+    }
+
+    /// Assemble the live nodes into a tree. `make_node` is given the payload
+    /// of a node and its children along with their slots, ordered by slot.
+    fn build<U>(
+        &self,
+        mut make_node: impl FnMut(T::Value, Vec<(usize, U)>) -> U,
+    ) -> U {
+        let live = self.live();
+        let mut children = (0..self.parents.len())
+            .map(|_| Vec::new())
+            .collect::<Vec<Vec<(usize, U)>>>();
+
+        for ix in (1..self.parents.len()).rev() {
+            if live[ix] {
+                let mut kids = mem::take(&mut children[ix]);
+                kids.sort_by_key(|&(slot, _)| slot);
+                let node = make_node(self.payloads[ix].current(), kids);
+                children[self.parents[ix]].push((self.slots[ix], node));
+            }
+        }
+
+        let mut kids = mem::take(&mut children[0]);
+        kids.sort_by_key(|&(slot, _)| slot);
+        make_node(self.payloads[0].current(), kids)
+    }
+
+    fn simplify(&mut self) -> bool {
+        if let Shrink::Prune(start) = self.shrink {
+            let live = self.live();
+            // The number of live nodes in the subtree rooted at each node.
+            let mut sizes =
+                live.iter().map(|&l| l as usize).collect::<Vec<_>>();
+            for ix in (1..self.parents.len()).rev() {
+                sizes[self.parents[ix]] += sizes[ix];
+            }
+
+            for ix in start..self.parents.len() {
+                if live[ix] && sizes[0] - sizes[ix] >= self.min_size {
+                    self.pruned.set(ix);
+                    self.prev_shrink = Some(Shrink::Prune(ix));
+                    self.shrink = Shrink::Prune(ix + 1);
+                    return true;
+                }
+            }
+
+            self.shrink = Shrink::ShrinkPayload(0);
+        }
+
+        let live = self.live();
+        while let Shrink::ShrinkPayload(ix) = self.shrink {
+            if ix >= self.payloads.len() {
+                // Nothing more we can do
+                return false;
+            }
+
+            if !live[ix] {
+                // No use shrinking something we're not including.
+                self.shrink = Shrink::ShrinkPayload(ix + 1);
+                continue;
+            }
+
+            if !self.payloads[ix].simplify() {
+                // Move on to the next node
+                self.shrink = Shrink::ShrinkPayload(ix + 1);
+            } else {
+                self.prev_shrink = Some(self.shrink);
+                return true;
+            }
+        }
+
+        panic!("Unexpected shrink state");
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.prev_shrink {
+            None => false,
+            Some(Shrink::Prune(ix)) => {
+                // Undo the last prune. Can't complicate any further, so unset
+                // prev_shrink.
+                self.pruned.clear(ix);
+                self.prev_shrink = None;
+                true
+            }
+            Some(Shrink::ShrinkPayload(ix)) => {
+                if self.payloads[ix].complicate() {
+                    // Don't unset prev_shrink; we may be able to complicate
+                    // again.
+                    true
+                } else {
+                    // Can't complicate the last payload any further.
+                    self.prev_shrink = None;
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Strategy to create `RoseTree`s.
+///
+/// Created by the `rose_tree()` function in the same module.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct RoseTreeStrategy<T: Strategy> {
+    payload: T,
+    size: SizeRange,
+    max_depth: usize,
+    max_children: usize,
+}
+
+/// Create a strategy to generate `RoseTree`s whose nodes hold values drawn
+/// from `payload`.
+///
+/// By default the trees have between 1 and 31 nodes, at most 8 levels and at
+/// most 4 children per node; use `size`, `max_depth` and `max_children` to
+/// change this.
+pub fn rose_tree<T: Strategy>(payload: T) -> RoseTreeStrategy<T> {
+    RoseTreeStrategy {
+        payload,
+        size: size_range(1..32),
+        max_depth: 8,
+        max_children: 4,
+    }
+}
+
+impl<T: Strategy> RoseTreeStrategy<T> {
+    /// Generate trees whose number of nodes is within `size`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` is empty or allows trees with no nodes.
+    pub fn size(mut self, size: impl Into<SizeRange>) -> Self {
+        self.size = size.into();
+        self.size.assert_nonempty();
+        assert!(self.size.start() >= 1, "Trees have at least one node");
+        self
+    }
+
+    /// Generate trees with at most `max_depth` levels. A depth of 1 only
+    /// allows the root.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `max_depth` is 0.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        assert!(max_depth >= 1, "Trees have at least one level");
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Generate trees in which each node has at most `max_children`
+    /// children.
+    pub fn max_children(mut self, max_children: usize) -> Self {
+        self.max_children = max_children;
+        self
+    }
+}
+
+impl<T: Strategy> Strategy for RoseTreeStrategy<T> {
+    type Tree = RoseTreeValueTree<T::Tree>;
+    type Value = RoseTree<T::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        new_nodes(
+            runner,
+            &self.payload,
+            &self.size,
+            self.max_depth,
+            self.max_children,
+            false,
+        )
+        .map(RoseTreeValueTree)
+    }
+}
+
+/// `ValueTree` corresponding to `RoseTreeStrategy`.
+#[derive(Clone, Debug)]
+pub struct RoseTreeValueTree<T: ValueTree>(Nodes<T>);
+
+impl<T: ValueTree> ValueTree for RoseTreeValueTree<T> {
+    type Value = RoseTree<T::Value>;
+
+    fn current(&self) -> RoseTree<T::Value> {
+        self.0.build(|value, children| RoseTree {
+            value,
+            children: children.into_iter().map(|(_, child)| child).collect(),
+        })
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.0.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.0.complicate()
+    }
+}
+
+/// Strategy to create `BinaryTree`s.
+///
+/// Created by the `binary_tree()` function in the same module.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct BinaryTreeStrategy<T: Strategy> {
+    payload: T,
+    size: SizeRange,
+    max_depth: usize,
+}
+
+/// Create a strategy to generate `BinaryTree`s whose nodes hold values drawn
+/// from `payload`.
+///
+/// By default the trees have between 1 and 31 nodes and at most 8 levels; use
+/// `size` and `max_depth` to change this.
+pub fn binary_tree<T: Strategy>(payload: T) -> BinaryTreeStrategy<T> {
+    BinaryTreeStrategy {
+        payload,
+        size: size_range(1..32),
+        max_depth: 8,
+    }
+}
+
+impl<T: Strategy> BinaryTreeStrategy<T> {
+    /// Generate trees whose number of nodes is within `size`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` is empty or allows trees with no nodes.
+    pub fn size(mut self, size: impl Into<SizeRange>) -> Self {
+        self.size = size.into();
+        self.size.assert_nonempty();
+        assert!(self.size.start() >= 1, "Trees have at least one node");
+        self
+    }
+
+    /// Generate trees with at most `max_depth` levels. A depth of 1 only
+    /// allows the root.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `max_depth` is 0.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        assert!(max_depth >= 1, "Trees have at least one level");
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+impl<T: Strategy> Strategy for BinaryTreeStrategy<T> {
+    type Tree = BinaryTreeValueTree<T::Tree>;
+    type Value = BinaryTree<T::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        new_nodes(runner, &self.payload, &self.size, self.max_depth, 2, true)
+            .map(BinaryTreeValueTree)
+    }
+}
+
+/// `ValueTree` corresponding to `BinaryTreeStrategy`.
+#[derive(Clone, Debug)]
+pub struct BinaryTreeValueTree<T: ValueTree>(Nodes<T>);
+
+impl<T: ValueTree> ValueTree for BinaryTreeValueTree<T> {
+    type Value = BinaryTree<T::Value>;
+
+    fn current(&self) -> BinaryTree<T::Value> {
+        self.0.build(|value, children| {
+            let mut tree = BinaryTree {
+                value,
+                left: None,
+                right: None,
+            };
+            for (slot, child) in children {
+                if slot == 0 {
+                    tree.left = Some(Box::new(child));
+                } else {
+                    tree.right = Some(Box::new(child));
+                }
+            }
+            tree
+        })
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.0.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.0.complicate()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rose_tree_respects_bounds() {
+        let input = rose_tree(0u32..100)
+            .size(3..20)
+            .max_depth(3)
+            .max_children(2);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            loop {
+                let tree = case.current();
+                assert!((3..20).contains(&tree.node_count()));
+                assert!(tree.depth() <= 3);
+                fn check_width(tree: &RoseTree<u32>) {
+                    assert!(tree.children.len() <= 2);
+                    tree.children.iter().for_each(check_width);
+                }
+                check_width(&tree);
+                if !case.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn binary_tree_respects_bounds() {
+        let input = binary_tree(0u32..100).size(5..16).max_depth(4);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..64 {
+            let mut case = input.new_tree(&mut runner).unwrap();
+            loop {
+                let tree = case.current();
+                assert!((5..16).contains(&tree.node_count()));
+                assert!(tree.depth() <= 4);
+                if !case.simplify() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn full_binary_tree() {
+        let input = binary_tree(0u32..100).size(7..=7).max_depth(3);
+        let mut runner = TestRunner::deterministic();
+
+        for _ in 0..16 {
+            let tree = input.new_tree(&mut runner).unwrap().current();
+            assert_eq!(7, tree.node_count());
+            assert_eq!(3, tree.depth());
+        }
+    }
+
+    #[test]
+    fn shrinks_by_pruning_subtrees() {
+        fn contains(tree: &RoseTree<u32>, v: u32) -> bool {
+            tree.value == v || tree.children.iter().any(|c| contains(c, v))
+        }
+
+        let input = rose_tree(0u32..10).size(1..64);
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&input, |tree| {
+            prop_assert!(!contains(&tree, 7));
+            Ok(())
+        });
+
+        match result {
+            Err(TestError::Fail(_, value)) => {
+                // Either the root is 7, or it has been shrunk to 0 and has a
+                // single child which is 7.
+                assert!(
+                    value
+                        == RoseTree {
+                            value: 7,
+                            children: vec![],
+                        }
+                        || value
+                            == RoseTree {
+                                value: 0,
+                                children: vec![RoseTree {
+                                    value: 7,
+                                    children: vec![],
+                                }],
+                            },
+                    "Unexpected minimal value: {:?}",
+                    value
+                );
+            }
+            e => panic!("Unexpected result: {:?}", e),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 3 nodes")]
+    fn unreachable_size_panics() {
+        let input = binary_tree(0u32..100).size(4..8).max_depth(2);
+        let _ = input.new_tree(&mut TestRunner::deterministic());
+    }
+
+    #[test]
+    fn rose_tree_sanity() {
+        check_strategy_sanity(rose_tree(0i32..1000).size(2..16), None);
+    }
+
+    #[test]
+    fn binary_tree_sanity() {
+        check_strategy_sanity(binary_tree(0i32..1000).size(2..16), None);
+    }
+}
@@ -47,6 +47,10 @@ macro_rules! tuple {
                 let values = ($(self.$fld.new_tree(runner)?,)*);
                 Ok(TupleValueTree::new(values))
             }
+
+            fn describe(&self) -> Description {
+                Description::new("tuple")$(.child(self.$fld.describe()))*
+            }
         }
 
         impl<$($typ : ValueTree),*> ValueTree
@@ -0,0 +1,220 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for `Uuid`s from the `uuid` crate.
+//!
+//! The [`UuidVersion`] selects which UUIDs are generated: random (version 4)
+//! UUIDs, time-ordered (version 7) UUIDs, only the nil UUID, or any 128 bits
+//! at all. Every kind shrinks toward the nil UUID, as far as its version and
+//! variant bits allow.
+//!
+//! `Uuid` also implements `Arbitrary`, taking a `UuidVersion` as parameters
+//! and generating version 4 UUIDs by default.
+//!
+//! ```rust
+//! use proptest::prelude::*;
+//! use proptest::uuid::{uuids, UuidVersion};
+//!
+//! proptest! {
+//!     fn parses_own_output(id in uuids(UuidVersion::SortRand)) {
+//!         prop_assert_eq!(Ok(id), uuid::Uuid::parse_str(&id.to_string()));
+//!     }
+//! }
+//! # fn main() { parses_own_output(); }
+//! ```
+
+use ::uuid::{Builder, Uuid};
+
+use crate::arbitrary::Arbitrary;
+use crate::num;
+use crate::strategy::*;
+use crate::test_runner::*;
+use crate::tuple::TupleValueTree;
+
+/// The number of distinct Unix timestamps, in milliseconds, of a version 7
+/// UUID.
+const TIMESTAMPS: u64 = 1 << 48;
+
+/// The kind of `Uuid`s to generate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UuidVersion {
+    /// Only the nil UUID.
+    Nil,
+    /// Random UUIDs, of version 4.
+    #[default]
+    Random,
+    /// Time-ordered UUIDs, of version 7, whose Unix timestamp shrinks toward
+    /// the epoch.
+    SortRand,
+    /// Any 128 bits, regardless of version and variant.
+    Any,
+}
+
+/// Generates `Uuid`s of the given kind.
+///
+/// Shrinks toward the nil UUID, or for versions 4 and 7 toward the least
+/// UUID with the same version.
+pub fn uuids(version: UuidVersion) -> UuidStrategy {
+    UuidStrategy { version }
+}
+
+/// Strategy returned by [`uuids`].
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Copy, Debug)]
+pub struct UuidStrategy {
+    version: UuidVersion,
+}
+
+impl Strategy for UuidStrategy {
+    type Tree = UuidValueTree;
+    type Value = Uuid;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let timestamp = match self.version {
+            UuidVersion::SortRand => (0..TIMESTAMPS).new_tree(runner)?,
+            _ => num::u64::BinarySearch::new(0),
+        };
+        let bits = match self.version {
+            UuidVersion::Nil => num::u128::BinarySearch::new(0),
+            _ => num::u128::ANY.new_tree(runner)?,
+        };
+        Ok(UuidValueTree {
+            version: self.version,
+            parts: TupleValueTree::new((timestamp, bits)),
+        })
+    }
+}
+
+/// `ValueTree` corresponding to `UuidStrategy`.
+#[derive(Clone, Copy, Debug)]
+pub struct UuidValueTree {
+    version: UuidVersion,
+    parts: TupleValueTree<(num::u64::BinarySearch, num::u128::BinarySearch)>,
+}
+
+impl ValueTree for UuidValueTree {
+    type Value = Uuid;
+
+    fn current(&self) -> Uuid {
+        let (timestamp, bits) = self.parts.current();
+        let bytes = bits.to_be_bytes();
+        match self.version {
+            UuidVersion::Nil => Uuid::nil(),
+            UuidVersion::Random => {
+                Builder::from_random_bytes(bytes).into_uuid()
+            }
+            UuidVersion::SortRand => {
+                let mut random = [0; 10];
+                random.copy_from_slice(&bytes[6..]);
+                Builder::from_unix_timestamp_millis(timestamp, &random)
+                    .into_uuid()
+            }
+            UuidVersion::Any => Uuid::from_u128(bits),
+        }
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.parts.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.parts.complicate()
+    }
+}
+
+impl Arbitrary for Uuid {
+    type Parameters = UuidVersion;
+    type Strategy = UuidStrategy;
+
+    fn arbitrary_with(version: UuidVersion) -> UuidStrategy {
+        uuids(version)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::string::ToString;
+
+    use super::*;
+    use crate::arbitrary::any;
+
+    fn minimal(version: UuidVersion) -> Uuid {
+        let mut runner = TestRunner::deterministic();
+        match runner.run(&uuids(version), |_| Err(TestCaseError::fail("no"))) {
+            Err(TestError::Fail(_, id)) => id,
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn generates_requested_versions() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let id = uuids(UuidVersion::Random)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert_eq!(4, id.get_version_num(), "{}", id);
+            assert_eq!(::uuid::Variant::RFC4122, id.get_variant(), "{}", id);
+
+            let id = uuids(UuidVersion::SortRand)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert_eq!(7, id.get_version_num(), "{}", id);
+            assert_eq!(::uuid::Variant::RFC4122, id.get_variant(), "{}", id);
+
+            let id = uuids(UuidVersion::Nil)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!(id.is_nil());
+        }
+    }
+
+    #[test]
+    fn time_ordered_uuids_sort_by_timestamp() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..256 {
+            let x = uuids(UuidVersion::SortRand)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            let y = uuids(UuidVersion::SortRand)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            let (x_ts, y_ts) = (x.as_u128() >> 80, y.as_u128() >> 80);
+            if x_ts != y_ts {
+                assert_eq!(x_ts < y_ts, x < y, "{} {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn shrinks_toward_nil() {
+        assert!(minimal(UuidVersion::Any).is_nil());
+        assert!(minimal(UuidVersion::Nil).is_nil());
+        assert_eq!(
+            "00000000-0000-4000-8000-000000000000",
+            minimal(UuidVersion::Random).to_string()
+        );
+        assert_eq!(
+            "00000000-0000-7000-8000-000000000000",
+            minimal(UuidVersion::SortRand).to_string()
+        );
+    }
+
+    #[test]
+    fn sanity() {
+        check_strategy_sanity(any::<Uuid>(), None);
+        check_strategy_sanity(uuids(UuidVersion::SortRand), None);
+        check_strategy_sanity(uuids(UuidVersion::Any), None);
+    }
+}
@@ -0,0 +1,456 @@
+//-
+// Copyright 2026 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating well-formed XML documents, and slightly
+//! malformed ones.
+//!
+//! Documents are trees of [`Element`]s, whose names, attributes and text are
+//! drawn from the strategies in [`XmlParams`], nested within its depth and
+//! fanout limits. Elements render themselves as XML with `Display`, escaping
+//! attribute values and text as needed, so every end tag matches its start
+//! tag. Shrinking first removes children and attributes, then simplifies the
+//! remaining ones, and eventually replaces elements by text.
+//!
+//! `malformed_documents()` renders the same trees with a single defect, for
+//! testing that parsers and sanitizers reject broken input gracefully.
+
+use core::fmt;
+
+use crate::arbitrary::any;
+use crate::collection::{btree_map, vec, SizeRange};
+use crate::sample::{select, Index};
+use crate::std_facade::{String, ToOwned, Vec};
+use crate::strategy::*;
+
+/// An XML element.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Element {
+    /// The name of the element.
+    pub name: String,
+    /// The names and values of the attributes of the element, in order.
+    pub attributes: Vec<(String, String)>,
+    /// The content of the element.
+    pub children: Vec<Node>,
+}
+
+/// An item of the content of an XML element.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Node {
+    /// A child element.
+    Element(Element),
+    /// Character data, unescaped.
+    Text(String),
+}
+
+/// A defect introduced into a document by `malformed_documents()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Defect {
+    /// An element has no end tag.
+    Unclosed,
+    /// The name in an end tag does not match the start tag.
+    MismatchedEndTag,
+    /// An attribute appears twice in a start tag.
+    DuplicateAttribute,
+    /// An attribute value has no quotes.
+    UnquotedAttribute,
+    /// An element contains a bare `&`.
+    BareAmpersand,
+}
+
+const DEFECTS: &[Option<Defect>] = &[
+    Some(Defect::Unclosed),
+    Some(Defect::MismatchedEndTag),
+    Some(Defect::DuplicateAttribute),
+    Some(Defect::UnquotedAttribute),
+    Some(Defect::BareAmpersand),
+    // The document is truncated instead.
+    None,
+];
+
+impl Element {
+    /// Return the number of elements in the tree rooted at this one.
+    fn count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(|child| match child {
+                Node::Element(element) => element.count(),
+                Node::Text(_) => 0,
+            })
+            .sum::<usize>()
+    }
+
+    /// Write this element to `out`, with `defect` in the element numbered
+    /// `target` in document order. `next` is the number of this element.
+    fn render(
+        &self,
+        out: &mut String,
+        defect: Option<Defect>,
+        target: usize,
+        next: &mut usize,
+    ) {
+        let here = defect.filter(|_| *next == target);
+        *next += 1;
+
+        out.push('<');
+        out.push_str(&self.name);
+        let mut attributes = self
+            .attributes
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        match here {
+            Some(Defect::DuplicateAttribute) => {
+                let first = attributes.first().copied().unwrap_or(("a", ""));
+                attributes.push(first);
+                if attributes.len() == 1 {
+                    attributes.push(first);
+                }
+            }
+            Some(Defect::UnquotedAttribute) if attributes.is_empty() => {
+                attributes.push(("a", "1"));
+            }
+            _ => (),
+        }
+        for (i, (name, value)) in attributes.into_iter().enumerate() {
+            out.push(' ');
+            out.push_str(name);
+            if i == 0 && here == Some(Defect::UnquotedAttribute) {
+                out.push('=');
+                escape(value, out);
+            } else {
+                out.push_str("=\"");
+                escape(value, out);
+                out.push('"');
+            }
+        }
+
+        let needs_content = matches!(
+            here,
+            Some(Defect::MismatchedEndTag | Defect::BareAmpersand)
+        );
+        if self.children.is_empty() && !needs_content {
+            out.push_str(if here == Some(Defect::Unclosed) {
+                ">"
+            } else {
+                "/>"
+            });
+            return;
+        }
+
+        out.push('>');
+        if here == Some(Defect::BareAmpersand) {
+            out.push('&');
+        }
+        for child in &self.children {
+            match child {
+                Node::Element(element) => {
+                    element.render(out, defect, target, next)
+                }
+                Node::Text(text) => escape(text, out),
+            }
+        }
+        if here == Some(Defect::Unclosed) {
+            return;
+        }
+        out.push_str("</");
+        out.push_str(&self.name);
+        if here == Some(Defect::MismatchedEndTag) {
+            out.push('x');
+        }
+        out.push('>');
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        self.render(&mut out, None, 0, &mut 0);
+        f.write_str(&out)
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Element(element) => element.fmt(f),
+            Node::Text(text) => {
+                let mut out = String::new();
+                escape(text, &mut out);
+                f.write_str(&out)
+            }
+        }
+    }
+}
+
+/// Write `text` to `out`, escaping the characters which may not appear
+/// literally in text or in attribute values.
+fn escape(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// The XML declaration which starts every document.
+const PROLOG: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+
+/// Parameters for generating XML documents.
+#[derive(Clone, Debug)]
+pub struct XmlParams {
+    /// The maximum number of levels of elements nested inside the root
+    /// element. With a depth of 0, the root element only contains text.
+    ///
+    /// The default is 4.
+    pub max_depth: u32,
+    /// The range of the number of children of each element.
+    ///
+    /// The default is `0..6`.
+    pub children: SizeRange,
+    /// The range of the number of attributes of each element. Fewer may be
+    /// generated if `names` generates few distinct names.
+    ///
+    /// The default is `0..4`.
+    pub attributes: SizeRange,
+    /// Generates the names of elements and attributes. The names must be
+    /// valid XML names.
+    ///
+    /// The default generates short lowercase ASCII names.
+    pub names: BoxedStrategy<String>,
+    /// Generates attribute values. The values may contain any characters
+    /// allowed in XML, and are escaped when rendered.
+    ///
+    /// The default generates short strings of printable characters.
+    pub attribute_values: BoxedStrategy<String>,
+    /// Generates the text between elements. The text may contain any
+    /// characters allowed in XML, and is escaped when rendered.
+    ///
+    /// The default generates short non-empty strings of printable
+    /// characters.
+    pub text: BoxedStrategy<String>,
+}
+
+impl Default for XmlParams {
+    fn default() -> Self {
+        XmlParams {
+            max_depth: 4,
+            children: (0..6).into(),
+            attributes: (0..4).into(),
+            names: "[a-z_][a-z0-9_.-]{0,7}".boxed(),
+            attribute_values: "\\PC{0,8}".boxed(),
+            text: "\\PC{1,16}".boxed(),
+        }
+    }
+}
+
+/// Generates elements with the given children.
+fn element(
+    params: &XmlParams,
+    children: impl Strategy<Value = Vec<Node>> + 'static,
+) -> BoxedStrategy<Element> {
+    (
+        params.names.clone(),
+        btree_map(
+            params.names.clone(),
+            params.attribute_values.clone(),
+            params.attributes.clone(),
+        ),
+        children,
+    )
+        .prop_map(|(name, attributes, children)| Element {
+            name,
+            attributes: attributes.into_iter().collect(),
+            children,
+        })
+        .boxed()
+}
+
+/// Generates element trees within the limits of `params`.
+pub fn elements(params: XmlParams) -> BoxedStrategy<Element> {
+    let text = params.text.clone().prop_map(Node::Text);
+    if params.max_depth == 0 {
+        return element(&params, vec(text, params.children.clone()));
+    }
+
+    let (start, end) = params.children.start_end_incl();
+    let leaves = prop_oneof![
+        text,
+        element(&params, Just(Vec::new())).prop_map(Node::Element),
+    ];
+    let recursive_params = params.clone();
+    let nodes = leaves.prop_recursive(
+        // Childless elements are already one level deep
+        params.max_depth - 1,
+        // Target around 64 nodes in total
+        64,
+        ((start + end) / 2).max(1) as u32,
+        move |inner| {
+            let children = vec(inner, recursive_params.children.clone());
+            element(&recursive_params, children).prop_map(Node::Element)
+        },
+    );
+    element(&params, vec(nodes, params.children.clone()))
+}
+
+/// Generates well-formed XML documents, made of an XML declaration followed
+/// by an element tree generated within the limits of `params`.
+pub fn documents(params: XmlParams) -> BoxedStrategy<String> {
+    elements(params)
+        .prop_map(|root| format!("{}{}", PROLOG, root))
+        .boxed()
+}
+
+/// Generates XML documents like `documents()`, but each with a single
+/// defect which makes it not well-formed.
+///
+/// The defect is one of an element without an end tag, an end tag whose
+/// name does not match, a duplicated attribute, an unquoted attribute value,
+/// a bare `&` in the content of an element, or the document being cut off
+/// before its end.
+pub fn malformed_documents(params: XmlParams) -> BoxedStrategy<String> {
+    (elements(params), select(DEFECTS), any::<Index>())
+        .prop_map(|(root, defect, index)| {
+            let mut out = PROLOG.to_owned();
+            match defect {
+                Some(defect) => {
+                    let target = index.index(root.count());
+                    root.render(&mut out, Some(defect), target, &mut 0);
+                }
+                None => {
+                    root.render(&mut out, None, 0, &mut 0);
+                    let mut end = index.index(out.len());
+                    while !out.is_char_boundary(end) {
+                        end -= 1;
+                    }
+                    out.truncate(end);
+                }
+            }
+            out
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_runner::{TestError, TestRunner};
+
+    /// Returns the number of levels of elements nested inside `element`.
+    fn depth(element: &Element) -> u32 {
+        element
+            .children
+            .iter()
+            .map(|child| match child {
+                Node::Element(element) => 1 + depth(element),
+                Node::Text(_) => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn documents_are_well_formed() {
+        let mut runner = TestRunner::deterministic();
+        let input = documents(XmlParams::default());
+        for _ in 0..256 {
+            let doc = input.new_tree(&mut runner).unwrap().current();
+            if let Err(err) = roxmltree::Document::parse(&doc) {
+                panic!("{} in {:?}", err, doc);
+            }
+        }
+    }
+
+    #[test]
+    fn text_and_attributes_round_trip() {
+        let mut runner = TestRunner::deterministic();
+        let params = XmlParams {
+            attribute_values: "[<>&\"' a]{0,8}".boxed(),
+            text: "[<>&\"' a]{1,8}".boxed(),
+            ..XmlParams::default()
+        };
+        let input = elements(params);
+        for _ in 0..256 {
+            let root = input.new_tree(&mut runner).unwrap().current();
+            let xml = format!("{}", root);
+            let doc = roxmltree::Document::parse(&xml).unwrap();
+            let parsed = doc.root_element();
+            assert_eq!(root.name, parsed.tag_name().name());
+            for (name, value) in &root.attributes {
+                assert_eq!(
+                    Some(value.as_str()),
+                    parsed.attribute(name.as_str())
+                );
+            }
+            let text = root
+                .children
+                .iter()
+                .filter_map(|child| match child {
+                    Node::Text(text) => Some(text.as_str()),
+                    Node::Element(_) => None,
+                })
+                .collect::<String>();
+            let parsed_text = parsed
+                .children()
+                .filter(|child| child.is_text())
+                .filter_map(|child| child.text())
+                .collect::<String>();
+            assert_eq!(text, parsed_text);
+        }
+    }
+
+    #[test]
+    fn depth_and_fanout_are_bounded() {
+        let mut runner = TestRunner::deterministic();
+        let params = XmlParams {
+            max_depth: 2,
+            children: (1..=3).into(),
+            ..XmlParams::default()
+        };
+        let input = elements(params);
+        let mut deepest = 0;
+        for _ in 0..256 {
+            let root = input.new_tree(&mut runner).unwrap().current();
+            deepest = deepest.max(depth(&root));
+            assert!((1..=3).contains(&root.children.len()));
+        }
+        assert_eq!(2, deepest);
+    }
+
+    #[test]
+    fn malformed_documents_are_not_well_formed() {
+        let mut runner = TestRunner::deterministic();
+        let input = malformed_documents(XmlParams::default());
+        for _ in 0..256 {
+            let doc = input.new_tree(&mut runner).unwrap().current();
+            assert!(roxmltree::Document::parse(&doc).is_err(), "{:?}", doc);
+        }
+    }
+
+    #[test]
+    fn shrinks_to_minimal_document() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&elements(XmlParams::default()), |root| {
+            prop_assert!(root.children.len() < 2);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, root)) => {
+                assert_eq!(2, root.children.len());
+                assert!(root.attributes.is_empty());
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
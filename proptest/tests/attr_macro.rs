@@ -9,3 +9,67 @@ fn attr_macro_does_not_clobber_mutability(mut x: i32, (mut y, _z): (i32, i32)) {
     assert_eq!(x, y);
 }
 
+
+#[cfg(feature = "attr-macro")]
+#[proptest::property_test(examples = [(0, 1), (i32::MAX, i32::MIN)])]
+fn attr_macro_runs_examples(x: i32, y: i32) {
+    assert_eq!(x.wrapping_add(y), y.wrapping_add(x));
+}
+
+#[cfg(feature = "attr-macro")]
+#[proptest::property_test(examples = [10u8, 200])]
+#[should_panic(expected = "Example 1 failed")]
+fn attr_macro_fails_on_failing_example(x: u8) {
+    proptest::prop_assert!(x < 100);
+}
+
+#[cfg(feature = "attr-macro")]
+mod fixtures {
+    use proptest::test_runner::Fixture;
+    use std::cell::Cell;
+
+    struct Counter {
+        cases: Cell<u32>,
+    }
+
+    impl Fixture for Counter {
+        fn setup() -> Self {
+            Counter {
+                cases: Cell::new(0),
+            }
+        }
+
+        fn teardown(self) {
+            assert_eq!(10, self.cases.get());
+        }
+    }
+
+    #[proptest::property_test(
+        config = proptest::test_runner::Config {
+            cases: 10,
+            failure_persistence: None,
+            ..Default::default()
+        }
+    )]
+    fn fixture_is_shared_by_all_cases(
+        #[fixture] counter: &Counter,
+        x: u8,
+        #[fixture = vec![1, 2, 3]] items: &Vec<u8>,
+    ) {
+        counter.cases.set(counter.cases.get() + 1);
+        assert_eq!(3, items.len());
+        let _ = x;
+    }
+
+    #[proptest::property_test(
+        config = proptest::test_runner::Config {
+            cases: 10,
+            failure_persistence: None,
+            ..Default::default()
+        }
+    )]
+    #[should_panic(expected = "left: 10")]
+    fn fixture_is_torn_down_after_last_case(#[fixture] counter: &Counter) {
+        counter.cases.set(counter.cases.get() + 2);
+    }
+}